@@ -1,9 +1,16 @@
 use scrypto::prelude::*;
 
+/// The most engine calls a deposit hook may make for each deposit.
+pub const DEPOSIT_HOOK_MAX_ENGINE_CALLS: u32 = 1000;
+
 blueprint! {
     struct Account {
         public_key: EcdsaPublicKey,
         vaults: LazyMap<Address, Vault>,
+        frozen: bool,
+        guardian: Option<Address>,
+        deposit_hook: Option<Address>,
+        owner: NonFungibleAddress,
     }
 
     impl Account {
@@ -11,6 +18,10 @@ blueprint! {
             Account {
                 public_key,
                 vaults: LazyMap::new(),
+                frozen: false,
+                guardian: None,
+                deposit_hook: None,
+                owner: NonFungibleAddress::from_public_key(&public_key),
             }
             .instantiate()
         }
@@ -19,7 +30,29 @@ blueprint! {
             let vaults = LazyMap::new();
             vaults.insert(bucket.resource_address(), Vault::with_bucket(bucket));
 
-            Account { public_key, vaults }.instantiate()
+            Account {
+                public_key,
+                vaults,
+                frozen: false,
+                guardian: None,
+                deposit_hook: None,
+                owner: NonFungibleAddress::from_public_key(&public_key),
+            }
+            .instantiate()
+        }
+
+        /// Creates an account holding the given buckets.
+        pub fn with_buckets(public_key: EcdsaPublicKey, buckets: Vec<Bucket>) -> Component {
+            let mut account = Account {
+                public_key,
+                vaults: LazyMap::new(),
+                frozen: false,
+                guardian: None,
+                deposit_hook: None,
+                owner: NonFungibleAddress::from_public_key(&public_key),
+            };
+            account.deposit_batch(buckets);
+            account.instantiate()
         }
 
         /// Deposit a batch of buckets into this account
@@ -31,7 +64,10 @@ blueprint! {
 
         /// Deposits resource into this account.
         pub fn deposit(&mut self, bucket: Bucket) {
+            self.assert_not_frozen();
+
             let address = bucket.resource_address();
+            let amount = bucket.amount();
             match self.vaults.get(&address) {
                 Some(mut v) => {
                     v.put(bucket);
@@ -41,20 +77,103 @@ blueprint! {
                     self.vaults.insert(address, v);
                 }
             }
+
+            if let Some(hook) = self.deposit_hook {
+                self.notify_deposit(hook, address, amount);
+            }
+        }
+
+        /// Calls `on_deposit(account, resource_address, amount)` on the deposit hook.
+        ///
+        /// The hook is called on a best-effort basis with bounded cost: if it fails, or makes
+        /// more than `DEPOSIT_HOOK_MAX_ENGINE_CALLS` engine calls, its changes are reverted
+        /// and the deposit goes ahead. Calling back into this account fails the hook.
+        fn notify_deposit(&self, hook: Address, resource_address: Address, amount: Decimal) {
+            let account = match Context::actor() {
+                Actor::Component(address) => address,
+                Actor::Blueprint(..) => return,
+            };
+            let notified = Component::from(hook).try_call::<()>(
+                "on_deposit",
+                args![account, resource_address, amount],
+                DEPOSIT_HOOK_MAX_ENGINE_CALLS,
+            );
+            if notified.is_none() {
+                warn!("Deposit hook {} failed", hook);
+            }
         }
 
-        fn non_fungible_key(&self) -> NonFungibleKey {
-            NonFungibleKey::new(self.public_key.to_vec())
+        /// Sets the component notified of every deposit into this account, or removes it if
+        /// `None`.
+        ///
+        /// The hook must have a method `on_deposit(account: Address, resource_address: Address,
+        /// amount: Decimal)`, and its engine calls count towards the limits of whichever
+        /// transaction deposits into this account.
+        #[auth(owner)]
+        pub fn set_deposit_hook(&mut self, hook: Option<Address>) {
+            if let Some(hook) = hook {
+                assert!(hook.is_component(), "Deposit hook must be a component");
+            }
+
+            self.deposit_hook = hook;
+        }
+
+        /// Returns the component notified of deposits, if any.
+        pub fn deposit_hook(&self) -> Option<Address> {
+            self.deposit_hook
+        }
+
+        fn assert_not_frozen(&self) {
+            assert!(!self.frozen, "Account is frozen");
+        }
+
+        fn check_owner(&self, account_auth: BucketRef) {
+            account_auth.check_non_fungible(&self.owner);
+        }
+
+        /// Sets the guardian badge, which is then required to unfreeze this account.
+        #[auth(owner)]
+        pub fn set_guardian(&mut self, guardian: Option<Address>) {
+            self.assert_not_frozen();
+
+            self.guardian = guardian;
+        }
+
+        /// Freezes this account, rejecting all deposits, withdrawals and presentations until
+        /// it's unfrozen.
+        ///
+        /// Both the owner and the guardian may freeze the account.
+        pub fn freeze(&mut self, auth: BucketRef) {
+            match self.guardian {
+                Some(guardian) if auth.resource_address() == guardian => auth.drop(),
+                _ => self.check_owner(auth),
+            }
+
+            self.frozen = true;
+        }
+
+        /// Unfreezes this account.
+        ///
+        /// When a guardian is set, only the guardian badge can unfreeze the account, so that
+        /// a compromised key can't undo the freeze.
+        pub fn unfreeze(&mut self, auth: BucketRef) {
+            match self.guardian {
+                Some(guardian) => auth.check(guardian),
+                None => self.check_owner(auth),
+            }
+
+            self.frozen = false;
+        }
+
+        /// Returns whether this account is frozen.
+        pub fn is_frozen(&self) -> bool {
+            self.frozen
         }
 
         /// Withdraws resource from this account.
-        pub fn withdraw(
-            &mut self,
-            amount: Decimal,
-            resource_address: Address,
-            account_auth: BucketRef,
-        ) -> Bucket {
-            account_auth.check_non_fungible_key(ECDSA_TOKEN, |key| key == &self.non_fungible_key());
+        #[auth(owner)]
+        pub fn withdraw(&mut self, amount: Decimal, resource_address: Address) -> Bucket {
+            self.assert_not_frozen();
 
             let vault = self.vaults.get(&resource_address);
             match vault {
@@ -66,18 +185,18 @@ blueprint! {
         }
 
         /// Withdraws resource from this account.
+        #[auth(owner)]
         pub fn withdraw_with_auth(
             &mut self,
             amount: Decimal,
             resource_address: Address,
-            auth: BucketRef,
-            account_auth: BucketRef,
+            vault_auth: BucketRef,
         ) -> Bucket {
-            account_auth.check_non_fungible_key(ECDSA_TOKEN, |key| key == &self.non_fungible_key());
+            self.assert_not_frozen();
 
             let vault = self.vaults.get(&resource_address);
             match vault {
-                Some(mut vault) => vault.take_with_auth(amount, auth),
+                Some(mut vault) => vault.take_with_auth(amount, vault_auth),
                 None => {
                     panic!("Insufficient balance");
                 }
@@ -85,13 +204,13 @@ blueprint! {
         }
 
         /// Withdraws non-fungibles from this account.
+        #[auth(owner)]
         pub fn withdraw_non_fungibles(
             &mut self,
             keys: BTreeSet<NonFungibleKey>,
             resource_address: Address,
-            account_auth: BucketRef,
         ) -> Bucket {
-            account_auth.check_non_fungible_key(ECDSA_TOKEN, |key| key == &self.non_fungible_key());
+            self.assert_not_frozen();
 
             let vault = self.vaults.get(&resource_address);
             match vault {
@@ -109,21 +228,21 @@ blueprint! {
         }
 
         /// Withdraws non-fungibles from this account.
+        #[auth(owner)]
         pub fn withdraw_non_fungibles_with_auth(
             &mut self,
             keys: BTreeSet<NonFungibleKey>,
             resource_address: Address,
-            auth: BucketRef,
-            account_auth: BucketRef,
+            vault_auth: BucketRef,
         ) -> Bucket {
-            account_auth.check_non_fungible_key(ECDSA_TOKEN, |key| key == &self.non_fungible_key());
+            self.assert_not_frozen();
 
             let vault = self.vaults.get(&resource_address);
             match vault {
                 Some(vault) => {
                     let mut bucket = Bucket::new(resource_address);
                     for key in keys {
-                        bucket.put(vault.take_non_fungible_with_auth(&key, auth.clone()));
+                        bucket.put(vault.take_non_fungible_with_auth(&key, vault_auth.clone()));
                     }
                     bucket
                 }
@@ -132,5 +251,133 @@ blueprint! {
                 }
             }
         }
+
+        /// Presents resource held by this account to a component method, which takes the
+        /// bucket ref as its only argument.
+        ///
+        /// The bucket ref is created straight from the vault, so the callee can check that
+        /// this account owns the resource with `BucketRef::is_owned_by`.
+        #[auth(owner)]
+        pub fn present(&self, resource_address: Address, component_address: Address, method: String) {
+            self.assert_not_frozen();
+
+            let vault = self.vaults.get(&resource_address);
+            match vault {
+                Some(vault) => {
+                    Component::from(component_address)
+                        .call::<()>(&method, vec![scrypto_encode(&vault.present())]);
+                }
+                None => {
+                    panic!("Insufficient balance")
+                }
+            }
+        }
+
+        /// Offers the given bucket in exchange for `want_amount` of `want_resource`, which is
+        /// deposited into this account once someone accepts the offer.
+        ///
+        /// Returns the escrow component, which only the owner of this account can cancel.
+        pub fn create_escrow(
+            &self,
+            offer: Bucket,
+            want_resource: Address,
+            want_amount: Decimal,
+        ) -> Address {
+            self.assert_not_frozen();
+
+            let account = match Context::actor() {
+                Actor::Component(address) => address,
+                Actor::Blueprint(..) => panic!("Not called on an account"),
+            };
+            Escrow::new(
+                offer,
+                want_resource,
+                want_amount,
+                account,
+                self.owner.clone(),
+            )
+            .into()
+        }
+    }
+}
+
+blueprint! {
+    struct Escrow {
+        offer: Vault,
+        want_resource: Address,
+        want_amount: Decimal,
+        account: Address,
+        owner: NonFungibleAddress,
+    }
+
+    impl Escrow {
+        pub fn new(
+            offer: Bucket,
+            want_resource: Address,
+            want_amount: Decimal,
+            account: Address,
+            owner: NonFungibleAddress,
+        ) -> Component {
+            assert!(!offer.is_empty(), "Nothing offered");
+            assert!(want_amount > Decimal::zero(), "Invalid amount wanted");
+
+            Escrow {
+                offer: Vault::with_bucket(offer),
+                want_resource,
+                want_amount,
+                account,
+                owner,
+            }
+            .instantiate()
+        }
+
+        /// Pays the wanted amount, which is deposited into the offering account, and returns
+        /// the offer along with any change.
+        pub fn accept(&mut self, mut payment: Bucket) -> Vec<Bucket> {
+            self.assert_open();
+            assert!(
+                payment.resource_address() == self.want_resource,
+                "Unexpected payment resource"
+            );
+
+            let price = payment.take(self.want_amount);
+            Component::from(self.account).call::<()>("deposit", vec![scrypto_encode(&price)]);
+
+            let mut buckets = vec![self.offer.take_all()];
+            if payment.is_empty() {
+                payment.drop_empty();
+            } else {
+                buckets.push(payment);
+            }
+            buckets
+        }
+
+        /// Withdraws the offer, returning it to the offering account.
+        #[auth(owner)]
+        pub fn cancel(&mut self) {
+            self.assert_open();
+
+            let offer = self.offer.take_all();
+            Component::from(self.account).call::<()>("deposit", vec![scrypto_encode(&offer)]);
+        }
+
+        /// Returns the offered resource and amount, and the wanted resource and amount.
+        pub fn terms(&self) -> (Address, Decimal, Address, Decimal) {
+            (
+                self.offer.resource_address(),
+                self.offer.amount(),
+                self.want_resource,
+                self.want_amount,
+            )
+        }
+
+        /// Returns whether the offer is still available.
+        pub fn is_open(&self) -> bool {
+            !self.offer.is_empty()
+        }
+
+        fn assert_open(&self) {
+            assert!(self.is_open(), "Escrow is closed");
+        }
     }
 }
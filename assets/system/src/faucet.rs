@@ -0,0 +1,43 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Faucet {
+        xrd: Vault,
+        amount: Decimal,
+        cooldown: u64,
+        last_claims: LazyMap<Address, u64>,
+    }
+
+    impl Faucet {
+        /// Creates a faucet which dispenses `amount` XRD per claim, and lets each account
+        /// claim again `cooldown` epochs after its last claim.
+        pub fn instantiate_faucet(xrd: Bucket, amount: Decimal, cooldown: u64) -> Component {
+            assert!(xrd.resource_address() == RADIX_TOKEN, "Only XRD can be dispensed");
+
+            Self {
+                xrd: Vault::with_bucket(xrd),
+                amount,
+                cooldown,
+                last_claims: LazyMap::new(),
+            }
+            .instantiate()
+        }
+
+        /// Deposits XRD into an account.
+        pub fn claim(&mut self, account: Address) {
+            let epoch = Context::current_epoch();
+            if let Some(last_claim) = self.last_claims.get(&account) {
+                let next_claim = last_claim + self.cooldown;
+                assert!(
+                    epoch >= next_claim,
+                    "Account can't claim again until epoch {}",
+                    next_claim
+                );
+            }
+            self.last_claims.insert(account, epoch);
+
+            let bucket = self.xrd.take(self.amount);
+            Component::from(account).call::<()>("deposit", vec![scrypto_encode(&bucket)]);
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use scrypto::prelude::*;
 
+pub mod faucet;
+pub mod staking;
+
 blueprint! {
     // nobody can instantiate a system component except the bootstrap process
     struct System {
@@ -13,6 +16,17 @@ blueprint! {
             package.into()
         }
 
+        /// Publishes a package, which can be upgraded by the holder of the owner badge.
+        pub fn publish_package_with_owner(code: Vec<u8>, owner_badge: Address) -> Address {
+            let package = Package::with_owner(&code, owner_badge);
+            package.into()
+        }
+
+        /// Publishes a new version of a package, and returns its number.
+        pub fn upgrade_package(package_address: Address, code: Vec<u8>, auth: BucketRef) -> u32 {
+            Package::from(package_address).upgrade(&code, auth)
+        }
+
         /// Creates a resource.
         pub fn new_resource(
             resource_type: ResourceType,
@@ -37,6 +51,43 @@ blueprint! {
             ResourceDef::from(resource_address).mint(amount, auth)
         }
 
+        /// Recalls resource from a vault.
+        ///
+        /// The vault is identified by the string form of its ID, as vaults can't be passed
+        /// to calls.
+        pub fn recall(
+            resource_address: Address,
+            component_address: Address,
+            vault_id: String,
+            amount: Decimal,
+            auth: BucketRef,
+        ) -> Bucket {
+            let vid: VaultId = vault_id.parse().unwrap();
+            ResourceDef::from(resource_address).recall(component_address, vid, amount, auth)
+        }
+
+        /// Freezes a vault, identified like in `recall`.
+        pub fn freeze_vault(
+            resource_address: Address,
+            component_address: Address,
+            vault_id: String,
+            auth: BucketRef,
+        ) {
+            let vid: VaultId = vault_id.parse().unwrap();
+            ResourceDef::from(resource_address).freeze_vault(component_address, vid, auth)
+        }
+
+        /// Unfreezes a vault, identified like in `recall`.
+        pub fn unfreeze_vault(
+            resource_address: Address,
+            component_address: Address,
+            vault_id: String,
+            auth: BucketRef,
+        ) {
+            let vid: VaultId = vault_id.parse().unwrap();
+            ResourceDef::from(resource_address).unfreeze_vault(component_address, vid, auth)
+        }
+
         /// Gives away XRD tokens for testing.
         pub fn free_xrd(&mut self, amount: Decimal) -> Bucket {
             self.xrd.take(amount)
@@ -0,0 +1,146 @@
+use scrypto::prelude::*;
+
+/// The maximum number of validators in the validator set.
+const MAX_VALIDATORS: usize = 100;
+
+blueprint! {
+    struct Staking {
+        /// The XRD staked, or unstaked but not yet claimed.
+        xrd: Vault,
+        /// The number of epochs after which unstaked XRD can be claimed.
+        unstake_delay: u64,
+        /// The total stake of each validator.
+        validators: Vec<(Address, Decimal)>,
+        /// The validator set as of the start of `validator_set_epoch`.
+        validator_set: Vec<(Address, Decimal)>,
+        validator_set_epoch: u64,
+        /// The stake of each owner, by validator and owner badge.
+        stakes: LazyMap<(Address, Address), Decimal>,
+        /// The unstaked XRD of each owner badge, with the epoch it can be claimed from.
+        unstakes: LazyMap<Address, Vec<(u64, Decimal)>>,
+    }
+
+    impl Staking {
+        /// Creates an emulation of staking, where stakes are owned by badges, e.g. held by
+        /// accounts or by liquid staking components, and unstaked XRD can be claimed
+        /// `unstake_delay` epochs after unstaking.
+        pub fn instantiate_staking(unstake_delay: u64) -> Component {
+            Self {
+                xrd: Vault::new(RADIX_TOKEN),
+                unstake_delay,
+                validators: Vec::new(),
+                validator_set: Vec::new(),
+                validator_set_epoch: Context::current_epoch(),
+                stakes: LazyMap::new(),
+                unstakes: LazyMap::new(),
+            }
+            .instantiate()
+        }
+
+        /// Stakes XRD to a validator, identified by any address, e.g. of its account, on behalf
+        /// of the owner of a badge.
+        ///
+        /// The stake counts towards the validator set from the next epoch.
+        pub fn stake(&mut self, validator: Address, xrd: Bucket, owner: BucketRef) {
+            assert!(xrd.resource_address() == RADIX_TOKEN, "Only XRD can be staked");
+            assert!(!xrd.is_empty(), "Nothing to stake");
+            let owner = Self::owner_of(owner);
+            self.update_validator_set();
+
+            let key = (validator, owner);
+            let stake = self.stakes.get(&key).unwrap_or_else(Decimal::zero);
+            self.stakes.insert(key, stake + xrd.amount());
+            match self.validators.iter_mut().find(|(v, _)| *v == validator) {
+                Some((_, total)) => *total += xrd.amount(),
+                None => self.validators.push((validator, xrd.amount())),
+            }
+            self.xrd.put(xrd);
+        }
+
+        /// Unstakes XRD from a validator, which can be claimed once the unstake delay has
+        /// passed.
+        pub fn unstake(&mut self, validator: Address, amount: Decimal, owner: BucketRef) {
+            assert!(amount > Decimal::zero(), "Nothing to unstake");
+            let owner = Self::owner_of(owner);
+            self.update_validator_set();
+
+            let key = (validator, owner);
+            let stake = self.stakes.get(&key).unwrap_or_else(Decimal::zero);
+            assert!(stake >= amount, "Can't unstake more than the {} XRD staked", stake);
+            self.stakes.insert(key, stake - amount);
+            if let Some((_, total)) = self.validators.iter_mut().find(|(v, _)| *v == validator) {
+                *total -= amount;
+            }
+            self.validators.retain(|(_, total)| *total > Decimal::zero());
+
+            let mut unstakes = self.unstakes.get(&owner).unwrap_or_default();
+            unstakes.push((Context::current_epoch() + self.unstake_delay, amount));
+            self.unstakes.insert(owner, unstakes);
+        }
+
+        /// Claims the XRD unstaked by the owner of a badge whose unstake delay has passed.
+        pub fn claim_unstaked(&mut self, owner: BucketRef) -> Bucket {
+            let owner = Self::owner_of(owner);
+            let epoch = Context::current_epoch();
+            let (claimable, pending): (Vec<_>, Vec<_>) = self
+                .unstakes
+                .get(&owner)
+                .unwrap_or_default()
+                .into_iter()
+                .partition(|(claimable_from, _)| *claimable_from <= epoch);
+            self.unstakes.insert(owner, pending);
+
+            let amount = claimable
+                .into_iter()
+                .fold(Decimal::zero(), |sum, (_, amount)| sum + amount);
+            self.xrd.take(amount)
+        }
+
+        /// Returns the XRD staked to a validator by the owner of a badge.
+        pub fn get_stake(&self, validator: Address, owner: Address) -> Decimal {
+            self.stakes.get(&(validator, owner)).unwrap_or_else(Decimal::zero)
+        }
+
+        /// Returns the XRD unstaked by the owner of a badge and not yet claimed, with the epoch
+        /// it can be claimed from.
+        pub fn get_unstakes(&self, owner: Address) -> Vec<(u64, Decimal)> {
+            self.unstakes.get(&owner).unwrap_or_default()
+        }
+
+        /// Returns the validators of the current epoch and their stake, by decreasing stake.
+        pub fn get_validator_set(&self) -> Vec<(Address, Decimal)> {
+            if Context::current_epoch() > self.validator_set_epoch {
+                Self::select_validators(&self.validators)
+            } else {
+                self.validator_set.clone()
+            }
+        }
+
+        /// Records the validator set of the current epoch before its first stake change.
+        fn update_validator_set(&mut self) {
+            let epoch = Context::current_epoch();
+            if epoch > self.validator_set_epoch {
+                self.validator_set = Self::select_validators(&self.validators);
+                self.validator_set_epoch = epoch;
+            }
+        }
+
+        fn select_validators(
+            validators: &[(Address, Decimal)],
+        ) -> Vec<(Address, Decimal)> {
+            let mut set = validators.to_vec();
+            set.sort_by(|(a, a_stake), (b, b_stake)| {
+                b_stake.cmp(a_stake).then(a.to_vec().cmp(&b.to_vec()))
+            });
+            set.truncate(MAX_VALIDATORS);
+            set
+        }
+
+        fn owner_of(owner: BucketRef) -> Address {
+            assert!(!owner.is_empty(), "Owner badge required");
+            let address = owner.resource_address();
+            owner.drop();
+            address
+        }
+    }
+}
@@ -26,7 +26,7 @@ fn test_proxy_1() {
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
     println!("{:?}\n", receipt1);
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
 
     // Test the `get_gumball` method.
     let component = receipt1.component(0).unwrap();
@@ -37,7 +37,7 @@ fn test_proxy_1() {
         .unwrap();
     let receipt2 = executor.run(transaction2).unwrap();
     println!("{:?}\n", receipt2);
-    assert!(receipt2.result.is_ok());
+    assert!(receipt2.status.is_committed());
 }
 
 #[test]
@@ -64,7 +64,7 @@ fn test_proxy_2() {
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
     println!("{:?}\n", receipt1);
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
 
     // Test the `get_gumball` method.
     let component = receipt1.component(0).unwrap();
@@ -75,5 +75,5 @@ fn test_proxy_2() {
         .unwrap();
     let receipt2 = executor.run(transaction2).unwrap();
     println!("{:?}\n", receipt2);
-    assert!(receipt2.result.is_ok());
+    assert!(receipt2.status.is_committed());
 }
@@ -159,6 +159,7 @@ blueprint! {
 
             // Update user state
             let to_return_amount = user.on_liquidate(repaid.amount(), self.max_liquidation_percent);
+            self.liquidity_pool.put(repaid);
             let to_return = self.liquidity_pool.take(to_return_amount);
 
             // Commit state changes
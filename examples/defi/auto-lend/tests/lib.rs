@@ -66,7 +66,7 @@ fn create_user<'a, L: SubstateStore>(env: &mut TestEnv<'a, L>) -> Address {
                 .unwrap()
         )
         .unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
     receipt.resource_def(0).unwrap()
 }
 
@@ -86,7 +86,7 @@ fn get_user_state<'a, L: SubstateStore>(env: &mut TestEnv<'a, L>, user_id: Addre
                 .unwrap()
         )
         .unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
     let encoded = receipt.outputs.swap_remove(0).raw;
     scrypto_decode(&encoded).unwrap()
 }
@@ -147,7 +147,7 @@ pub struct Position {
     pub position_in_base: Decimal,
 }
 
-#[derive(TypeId, Encode, Decode)]
+#[derive(TypeId, Encode, Decode, Describe)]
 struct AMM {
     /// Supply of base asset
     base_supply: Decimal,
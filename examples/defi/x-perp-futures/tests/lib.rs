@@ -67,7 +67,7 @@ fn create_user<'a, L: SubstateStore>(env: &mut TestEnv<'a, L>) -> Address {
                 .unwrap()
         )
         .unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
     receipt.resource_def(0).unwrap()
 }
 
@@ -87,7 +87,7 @@ fn get_position<'a, L: SubstateStore>(env: &mut TestEnv<'a, L>, user_id: Address
                 .unwrap()
         )
         .unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
     let encoded = receipt.outputs.swap_remove(0).raw;
     scrypto_decode(&encoded).unwrap()
 }
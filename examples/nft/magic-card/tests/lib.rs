@@ -18,7 +18,7 @@ fn test_magic_card() {
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
     println!("{:?}\n", receipt1);
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
 
     // Test the `buy_special_card` method.
     let component = receipt1.component(0).unwrap();
@@ -34,7 +34,7 @@ fn test_magic_card() {
         .unwrap();
     let receipt2 = executor.run(transaction2).unwrap();
     println!("{:?}\n", receipt2);
-    assert!(receipt2.result.is_ok());
+    assert!(receipt2.status.is_committed());
 
     // Test the `buy_special_card` method.
     let component = receipt1.component(0).unwrap();
@@ -50,5 +50,5 @@ fn test_magic_card() {
         .unwrap();
     let receipt3 = executor.run(transaction3).unwrap();
     println!("{:?}\n", receipt3);
-    assert!(receipt3.result.is_ok());
+    assert!(receipt3.status.is_committed());
 }
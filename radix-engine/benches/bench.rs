@@ -1,6 +1,8 @@
 #[macro_use]
 extern crate bencher;
 use bencher::Bencher;
+use std::fs;
+use std::process::Command;
 
 use radix_engine::ledger::*;
 use radix_engine::transaction::*;
@@ -27,9 +29,45 @@ fn bench_transfer(b: &mut Bencher) {
 
     b.iter(|| {
         let receipt = executor.run(transaction.clone()).unwrap();
-        assert!(receipt.result.is_ok());
+        assert!(receipt.status.is_committed());
     });
 }
 
-benchmark_group!(radix_engine, bench_transfer);
+fn compile(name: &str) -> Vec<u8> {
+    Command::new("cargo")
+        .current_dir(format!("./tests/{}", name))
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    fs::read(format!(
+        "./tests/{}/target/wasm32-unknown-unknown/release/{}.wasm",
+        name,
+        name.replace("-", "_")
+    ))
+    .unwrap()
+}
+
+fn bench_large_return(b: &mut Bencher) {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("non_fungible")).unwrap();
+    // About 2 MB of return data
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "enumerate_non_fungibles",
+            vec!["50000".to_owned()],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+
+    b.iter(|| {
+        let receipt = executor.run(transaction.clone()).unwrap();
+        assert!(receipt.status.is_committed());
+    });
+}
+
+benchmark_group!(radix_engine, bench_transfer, bench_large_return);
 benchmark_main!(radix_engine);
@@ -0,0 +1,46 @@
+use scrypto::types::*;
+
+/// The addresses that well-known names resolve to within a transaction.
+///
+/// The defaults match the entities created by ledger bootstrap. A different book only changes
+/// what blueprints see through `Context::well_known_address`; the ledger is expected to hold
+/// the referenced entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressBook {
+    pub system_package: Address,
+    pub system_component: Address,
+    pub account_package: Address,
+    pub radix_token: Address,
+    pub ecdsa_token: Address,
+    pub faucet_component: Address,
+    pub staking_component: Address,
+}
+
+impl AddressBook {
+    /// Returns the address the given name resolves to.
+    pub fn resolve(&self, name: WellKnownAddress) -> Address {
+        match name {
+            WellKnownAddress::SystemPackage => self.system_package,
+            WellKnownAddress::SystemComponent => self.system_component,
+            WellKnownAddress::AccountPackage => self.account_package,
+            WellKnownAddress::RadixToken => self.radix_token,
+            WellKnownAddress::EcdsaToken => self.ecdsa_token,
+            WellKnownAddress::FaucetComponent => self.faucet_component,
+            WellKnownAddress::StakingComponent => self.staking_component,
+        }
+    }
+}
+
+impl Default for AddressBook {
+    fn default() -> Self {
+        Self {
+            system_package: WellKnownAddress::SystemPackage.default_address(),
+            system_component: WellKnownAddress::SystemComponent.default_address(),
+            account_package: WellKnownAddress::AccountPackage.default_address(),
+            radix_token: WellKnownAddress::RadixToken.default_address(),
+            ecdsa_token: WellKnownAddress::EcdsaToken.default_address(),
+            faucet_component: WellKnownAddress::FaucetComponent.default_address(),
+            staking_component: WellKnownAddress::StakingComponent.default_address(),
+        }
+    }
+}
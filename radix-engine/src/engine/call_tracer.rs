@@ -0,0 +1,50 @@
+use scrypto::rust::vec::Vec;
+
+use crate::model::*;
+
+/// Builds the call tree of a transaction from nested begin/end pairs.
+#[derive(Default)]
+pub struct CallTracer {
+    stack: Vec<CallFrame>,
+    #[cfg(not(feature = "alloc"))]
+    started_at: Vec<std::time::Instant>,
+    frames: Vec<CallFrame>,
+}
+
+impl CallTracer {
+    /// Opens a frame as a child of the innermost open frame.
+    pub fn begin(&mut self, kind: CallKind, input_size: usize) {
+        self.stack.push(CallFrame {
+            kind,
+            input_size,
+            output_size: 0,
+            time: None,
+            cost: 0,
+            children: Vec::new(),
+        });
+        #[cfg(not(feature = "alloc"))]
+        self.started_at.push(std::time::Instant::now());
+    }
+
+    /// Closes the innermost open frame.
+    pub fn end(&mut self, output_size: usize) {
+        let mut frame = self.stack.pop().expect("No call frame to end");
+        frame.output_size = output_size;
+        #[cfg(not(feature = "alloc"))]
+        {
+            frame.time = self.started_at.pop().map(|t| t.elapsed().as_micros());
+        }
+        frame.cost = frame.children.iter().map(|c| c.cost).sum::<u32>()
+            + matches!(frame.kind, CallKind::EngineCall { .. }) as u32;
+
+        match self.stack.last_mut() {
+            Some(parent) => parent.children.push(frame),
+            None => self.frames.push(frame),
+        }
+    }
+
+    /// Returns the completed top-level frames.
+    pub fn frames(&self) -> &[CallFrame] {
+        &self.frames
+    }
+}
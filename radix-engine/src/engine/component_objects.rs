@@ -8,11 +8,11 @@ pub struct UnclaimedLazyMap {
     pub lazy_map: LazyMap,
     /// All descendents (not just direct children) of the unclaimed lazy map
     pub descendent_lazy_maps: HashMap<Mid, LazyMap>,
-    pub descendent_vaults: HashMap<Vid, Vault>,
+    pub descendent_vaults: HashMap<VaultId, Vault>,
 }
 
 impl UnclaimedLazyMap {
-    fn insert_vault(&mut self, vid: Vid, vault: Vault) {
+    fn insert_vault(&mut self, vid: VaultId, vault: Vault) {
         if self.descendent_vaults.contains_key(&vid) {
             panic!("duplicate vault insertion: {}", vid);
         }
@@ -52,7 +52,7 @@ impl UnclaimedLazyMap {
 
 pub struct ComponentObjectRefs {
     pub mids: HashSet<Mid>,
-    pub vids: HashSet<Vid>,
+    pub vids: HashSet<VaultId>,
 }
 
 impl ComponentObjectRefs {
@@ -92,7 +92,7 @@ pub struct ComponentObjects {
     /// Keeps track of vault and lazy map descendents.
     pub lazy_maps: HashMap<Mid, UnclaimedLazyMap>,
     /// Vaults which haven't been assigned to a component or lazy map yet.
-    pub vaults: HashMap<Vid, Vault>,
+    pub vaults: HashMap<VaultId, Vault>,
 }
 
 impl ComponentObjects {
@@ -147,7 +147,7 @@ impl ComponentObjects {
         None
     }
 
-    pub fn get_vault_mut(&mut self, vid: &Vid) -> Option<&mut Vault> {
+    pub fn get_vault_mut(&mut self, vid: &VaultId) -> Option<&mut Vault> {
         let vault = self.vaults.get_mut(vid);
         if vault.is_some() {
             return Some(vault.unwrap());
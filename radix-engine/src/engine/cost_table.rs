@@ -0,0 +1,154 @@
+use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::*;
+use scrypto::rust::fmt;
+use scrypto::rust::str::FromStr;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+
+/// The prices, in cost units, of what a transaction does while running.
+///
+/// Costs are charged against `ExecutionLimits::max_engine_calls`, and against the bound of a
+/// method called with bounded cost. The default table charges one unit per engine call and
+/// nothing else, so that the cost of a transaction is the number of engine calls it makes.
+///
+/// A table can be written to and read from a text file, one `key = price` entry per line:
+///
+/// ```text
+/// engine_call = 1
+/// engine_call.CallMethod = 10
+/// state_read_byte = 0
+/// state_write_byte = 0
+/// wasm_instruction = 0
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostTable {
+    /// The price of an engine call which has no price of its own.
+    pub engine_call: u32,
+    /// The prices of specific engine calls, by name, e.g. `CallMethod`.
+    pub engine_calls: BTreeMap<String, u32>,
+    /// The price of each byte of state loaded from the ledger.
+    pub state_read_byte: u32,
+    /// The price of each byte of state updated, charged once the transaction has run.
+    pub state_write_byte: u32,
+    /// The price of each WASM instruction run. Code is only metered if this is not zero.
+    pub wasm_instruction: u32,
+}
+
+impl Default for CostTable {
+    fn default() -> Self {
+        Self {
+            engine_call: 1,
+            engine_calls: BTreeMap::new(),
+            state_read_byte: 0,
+            state_write_byte: 0,
+            wasm_instruction: 0,
+        }
+    }
+}
+
+impl CostTable {
+    /// Returns a table in which everything is free, as used for system transactions.
+    pub fn free() -> Self {
+        Self {
+            engine_call: 0,
+            engine_calls: BTreeMap::new(),
+            state_read_byte: 0,
+            state_write_byte: 0,
+            wasm_instruction: 0,
+        }
+    }
+
+    /// Returns the price of the engine call with the given name.
+    pub fn engine_call_price(&self, name: &str) -> u32 {
+        self.engine_calls
+            .get(name)
+            .copied()
+            .unwrap_or(self.engine_call)
+    }
+
+    /// Returns the price of loading the given number of bytes of state.
+    pub fn state_read_cost(&self, bytes: u64) -> u32 {
+        Self::cost_of_bytes(bytes, self.state_read_byte)
+    }
+
+    /// Returns the price of updating the given number of bytes of state.
+    pub fn state_write_cost(&self, bytes: u64) -> u32 {
+        Self::cost_of_bytes(bytes, self.state_write_byte)
+    }
+
+    fn cost_of_bytes(bytes: u64, price: u32) -> u32 {
+        bytes.saturating_mul(price as u64).min(u32::MAX as u64) as u32
+    }
+}
+
+/// Represents an error when parsing `CostTable` from string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCostTableError {
+    /// The line, counting from one, is not a `key = price` entry.
+    InvalidLine(usize),
+    /// The key is not one of the table.
+    UnknownKey(String),
+    /// The price of the key is not a valid number.
+    InvalidPrice(String),
+}
+
+impl fmt::Display for ParseCostTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseCostTableError {}
+
+impl FromStr for CostTable {
+    type Err = ParseCostTableError;
+
+    /// Parses a table, where entries not listed keep their default price.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = Self::default();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.splitn(2, '=').map(str::trim).collect();
+            if tokens.len() != 2 || tokens[0].is_empty() {
+                return Err(ParseCostTableError::InvalidLine(i + 1));
+            }
+            let (key, price) = (tokens[0], tokens[1]);
+            let price: u32 = price
+                .parse()
+                .map_err(|_| ParseCostTableError::InvalidPrice(key.to_owned()))?;
+
+            match key {
+                "engine_call" => table.engine_call = price,
+                "state_read_byte" => table.state_read_byte = price,
+                "state_write_byte" => table.state_write_byte = price,
+                "wasm_instruction" => table.wasm_instruction = price,
+                _ => match key.strip_prefix("engine_call.") {
+                    Some(name) if !name.is_empty() => {
+                        table.engine_calls.insert(name.to_owned(), price);
+                    }
+                    _ => return Err(ParseCostTableError::UnknownKey(key.to_owned())),
+                },
+            }
+        }
+        Ok(table)
+    }
+}
+
+impl fmt::Display for CostTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "engine_call = {}", self.engine_call)?;
+        for (name, price) in &self.engine_calls {
+            writeln!(f, "engine_call.{} = {}", name, price)?;
+        }
+        writeln!(f, "state_read_byte = {}", self.state_read_byte)?;
+        writeln!(f, "state_write_byte = {}", self.state_write_byte)?;
+        writeln!(f, "wasm_instruction = {}", self.wasm_instruction)
+    }
+}
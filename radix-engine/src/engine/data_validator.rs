@@ -5,6 +5,70 @@ use scrypto::types::*;
 
 use crate::model::*;
 
+/// Where a value is passed, which determines the resources and objects it may contain.
+///
+/// | Context           | Bucket | BucketRef | Vault | LazyMap |
+/// |-------------------|--------|-----------|-------|---------|
+/// | Argument          | yes    | yes       | no    | no      |
+/// | Return            | yes    | no        | no    | no      |
+/// | ComponentState    | no     | no        | yes   | yes     |
+/// | LazyMapKey        | no     | no        | no    | no      |
+/// | LazyMapValue      | no     | no        | yes   | yes     |
+/// | NonFungibleData   | no     | no        | no    | no      |
+/// | Event             | no     | no        | no    | no      |
+/// | ScheduledArgument | no     | no        | no    | no      |
+///
+/// All other custom types, e.g. `Decimal`, `Address` and `NonFungibleKey`, are plain values
+/// and allowed everywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataContext {
+    Argument,
+    Return,
+    ComponentState,
+    LazyMapKey,
+    LazyMapValue,
+    NonFungibleData,
+    Event,
+    ScheduledArgument,
+}
+
+impl DataContext {
+    /// Returns whether buckets, which are moved along with the value, are allowed.
+    pub fn allows_buckets(&self) -> bool {
+        matches!(self, Self::Argument | Self::Return)
+    }
+
+    /// Returns whether bucket refs, which are lent for the duration of a call, are allowed.
+    pub fn allows_bucket_refs(&self) -> bool {
+        matches!(self, Self::Argument)
+    }
+
+    /// Returns whether vaults and lazy maps, which are owned by a component, are allowed.
+    pub fn allows_owned_objects(&self) -> bool {
+        matches!(self, Self::ComponentState | Self::LazyMapValue)
+    }
+}
+
+/// Checks that a value only contains the resources and objects allowed in the given context.
+pub fn check_data_context(
+    validated: &ValidatedData,
+    context: DataContext,
+) -> Result<(), RuntimeError> {
+    if !validated.buckets.is_empty() && !context.allows_buckets() {
+        return Err(RuntimeError::BucketNotAllowed(context));
+    }
+    if !validated.bucket_refs.is_empty() && !context.allows_bucket_refs() {
+        return Err(RuntimeError::BucketRefNotAllowed(context));
+    }
+    if !validated.vaults.is_empty() && !context.allows_owned_objects() {
+        return Err(RuntimeError::VaultNotAllowed(context));
+    }
+    if !validated.lazy_maps.is_empty() && !context.allows_owned_objects() {
+        return Err(RuntimeError::LazyMapNotAllowed(context));
+    }
+    Ok(())
+}
+
 pub fn validate_data(slice: &[u8]) -> Result<ValidatedData, DataValidationError> {
     let value = decode_any(slice).map_err(DataValidationError::DecodeError)?;
 
@@ -25,9 +89,9 @@ pub fn validate_data(slice: &[u8]) -> Result<ValidatedData, DataValidationError>
 }
 
 pub struct CustomValueValidator {
-    pub buckets: Vec<Bid>,
+    pub buckets: Vec<BucketId>,
     pub bucket_refs: Vec<Rid>,
-    pub vaults: Vec<Vid>,
+    pub vaults: Vec<VaultId>,
     pub lazy_maps: Vec<Mid>,
 }
 
@@ -61,7 +125,7 @@ impl CustomValueVisitor for CustomValueValidator {
             }
             SCRYPTO_TYPE_BID => {
                 self.buckets
-                    .push(Bid::try_from(data).map_err(DataValidationError::InvalidBid)?);
+                    .push(BucketId::try_from(data).map_err(DataValidationError::InvalidBid)?);
             }
             SCRYPTO_TYPE_RID => {
                 self.bucket_refs
@@ -73,12 +137,19 @@ impl CustomValueVisitor for CustomValueValidator {
             }
             SCRYPTO_TYPE_VID => {
                 self.vaults
-                    .push(Vid::try_from(data).map_err(DataValidationError::InvalidVid)?);
+                    .push(VaultId::try_from(data).map_err(DataValidationError::InvalidVid)?);
             }
             SCRYPTO_TYPE_NON_FUNGIBLE_KEY => {
                 NonFungibleKey::try_from(data)
                     .map_err(DataValidationError::InvalidNonFungibleKey)?;
             }
+            SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS => {
+                NonFungibleAddress::try_from(data)
+                    .map_err(DataValidationError::InvalidNonFungibleAddress)?;
+            }
+            SCRYPTO_TYPE_URL => {
+                Url::try_from(data).map_err(DataValidationError::InvalidUrl)?;
+            }
             _ => {
                 return Err(DataValidationError::InvalidTypeId(kind));
             }
@@ -0,0 +1,20 @@
+use k256::ecdsa::signature::Verifier;
+use k256::ecdsa::{Signature, VerifyingKey};
+use scrypto::types::*;
+
+/// Verifies an ECDSA (secp256k1) signature of the SHA-256 hash of a message.
+///
+/// The signature can be either in the 64-byte compact form or DER encoded. Malformed keys
+/// or signatures are treated as invalid.
+pub fn verify_ecdsa(message: &[u8], signature: &[u8], public_key: &EcdsaPublicKey) -> bool {
+    let key = match VerifyingKey::from_sec1_bytes(&public_key.0) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::try_from(signature).or_else(|_| Signature::from_der(signature))
+    {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    key.verify(message, &signature).is_ok()
+}
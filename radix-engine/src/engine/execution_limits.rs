@@ -0,0 +1,22 @@
+/// Limits on what a transaction may use while running, which are checked at the same points
+/// on every run, so that a transaction exceeding them always fails alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    /// The maximum depth of nested blueprint calls, where the transaction is at depth 0.
+    pub max_call_depth: usize,
+    /// The maximum cost units charged within a transaction, which is the number of engine
+    /// calls made by all blueprints with the default `CostTable`.
+    pub max_engine_calls: u32,
+    /// The maximum size of the memory of a WASM instance, in 64 KiB pages.
+    pub max_memory_pages: u32,
+}
+
+impl Default for ExecutionLimits {
+    fn default() -> Self {
+        Self {
+            max_call_depth: 16,
+            max_engine_calls: 1_000_000,
+            max_memory_pages: 512,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use scrypto::rust::ops::Range;
 use scrypto::types::*;
 use scrypto::utils::*;
 
-pub const ECDSA_TOKEN_BID: Bid = Bid(0);
+pub const ECDSA_TOKEN_BID: BucketId = BucketId::new(0);
 pub const ECDSA_TOKEN_RID: Rid = Rid(1);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,11 +12,51 @@ pub enum IdSpace {
     Application,
 }
 
+impl IdSpace {
+    /// Returns the range of IDs within this space.
+    pub fn range(&self) -> Range<u32> {
+        match self {
+            IdSpace::System => 0..512,
+            IdSpace::Transaction => 512..1024,
+            IdSpace::Application => 1024..u32::MAX,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdAllocatorError {
     OutOfID,
 }
 
+fn derive_hash(transaction_hash: H256, id: u32) -> H256 {
+    let mut data = transaction_hash.as_ref().to_vec();
+    data.extend(id.to_le_bytes());
+    sha256_twice(data)
+}
+
+/// Derives the address of the package allocated with the given ID in a transaction.
+///
+/// Entities created by a transaction draw their IDs from `IdSpace::Application`, in
+/// allocation order, which makes it possible to compute addresses before submission.
+pub fn derive_package_address(transaction_hash: H256, id: u32) -> Address {
+    Address::Package(derive_hash(transaction_hash, id).lower_26_bytes())
+}
+
+/// Derives the address of the component allocated with the given ID in a transaction.
+pub fn derive_component_address(transaction_hash: H256, id: u32) -> Address {
+    Address::Component(derive_hash(transaction_hash, id).lower_26_bytes())
+}
+
+/// Derives the address of the resource definition allocated with the given ID in a transaction.
+pub fn derive_resource_address(transaction_hash: H256, id: u32) -> Address {
+    Address::ResourceDef(derive_hash(transaction_hash, id).lower_26_bytes())
+}
+
+/// Derives the ID of the vault allocated with the given ID in a transaction.
+pub fn derive_vid(transaction_hash: H256, id: u32) -> VaultId {
+    VaultId::new(transaction_hash, id)
+}
+
 /// An ID allocator defines how identities are generated.
 pub struct IdAllocator {
     available: Range<u32>,
@@ -26,11 +66,7 @@ impl IdAllocator {
     /// Creates an ID allocator.
     pub fn new(kind: IdSpace) -> Self {
         Self {
-            available: match kind {
-                IdSpace::System => (0..512),
-                IdSpace::Transaction => (512..1024),
-                IdSpace::Application => (1024..u32::MAX),
-            },
+            available: kind.range(),
         }
     }
 
@@ -49,9 +85,7 @@ impl IdAllocator {
         &mut self,
         transaction_hash: H256,
     ) -> Result<Address, IdAllocatorError> {
-        let mut data = transaction_hash.as_ref().to_vec();
-        data.extend(self.next()?.to_le_bytes());
-        Ok(Address::Package(sha256_twice(data).lower_26_bytes()))
+        Ok(derive_package_address(transaction_hash, self.next()?))
     }
 
     /// Creates a new component address.
@@ -59,9 +93,7 @@ impl IdAllocator {
         &mut self,
         transaction_hash: H256,
     ) -> Result<Address, IdAllocatorError> {
-        let mut data = transaction_hash.as_ref().to_vec();
-        data.extend(self.next()?.to_le_bytes());
-        Ok(Address::Component(sha256_twice(data).lower_26_bytes()))
+        Ok(derive_component_address(transaction_hash, self.next()?))
     }
 
     /// Creates a new resource def address.
@@ -69,21 +101,19 @@ impl IdAllocator {
         &mut self,
         transaction_hash: H256,
     ) -> Result<Address, IdAllocatorError> {
-        let mut data = transaction_hash.as_ref().to_vec();
-        data.extend(self.next()?.to_le_bytes());
-        Ok(Address::ResourceDef(sha256_twice(data).lower_26_bytes()))
+        Ok(derive_resource_address(transaction_hash, self.next()?))
     }
 
     /// Creates a new UUID.
     pub fn new_uuid(&mut self, transaction_hash: H256) -> Result<u128, IdAllocatorError> {
-        let mut data = transaction_hash.as_ref().to_vec();
-        data.extend(self.next()?.to_le_bytes());
-        Ok(u128::from_le_bytes(sha256_twice(data).lower_16_bytes()))
+        Ok(u128::from_le_bytes(
+            derive_hash(transaction_hash, self.next()?).lower_16_bytes(),
+        ))
     }
 
     /// Creates a new bucket ID.
-    pub fn new_bid(&mut self) -> Result<Bid, IdAllocatorError> {
-        Ok(Bid(self.next()?))
+    pub fn new_bid(&mut self) -> Result<BucketId, IdAllocatorError> {
+        Ok(BucketId::new(self.next()?))
     }
 
     /// Creates a new bucket ref ID.
@@ -92,8 +122,8 @@ impl IdAllocator {
     }
 
     /// Creates a new vault ID.
-    pub fn new_vid(&mut self, transaction_hash: H256) -> Result<Vid, IdAllocatorError> {
-        Ok(Vid(transaction_hash, self.next()?))
+    pub fn new_vid(&mut self, transaction_hash: H256) -> Result<VaultId, IdAllocatorError> {
+        Ok(derive_vid(transaction_hash, self.next()?))
     }
 
     /// Creates a new lazy map ID.
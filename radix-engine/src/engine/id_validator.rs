@@ -1,4 +1,5 @@
 use scrypto::rust::collections::*;
+use scrypto::rust::vec::Vec;
 use scrypto::types::*;
 
 use crate::engine::*;
@@ -7,15 +8,15 @@ use crate::model::*;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IdValidatorError {
     IdAllocatorError(IdAllocatorError),
-    BucketNotFound(Bid),
+    BucketNotFound(BucketId),
     BucketRefNotFound(Rid),
-    BucketLocked(Bid),
+    BucketLocked(BucketId),
 }
 
 pub struct IdValidator {
     id_allocator: IdAllocator,
-    buckets: HashMap<Bid, usize>,
-    bucket_refs: HashMap<Rid, Bid>,
+    buckets: HashMap<BucketId, usize>,
+    bucket_refs: HashMap<Rid, BucketId>,
 }
 
 impl IdValidator {
@@ -29,7 +30,7 @@ impl IdValidator {
         }
     }
 
-    pub fn new_bucket(&mut self) -> Result<Bid, IdValidatorError> {
+    pub fn new_bucket(&mut self) -> Result<BucketId, IdValidatorError> {
         let bid = self
             .id_allocator
             .new_bid()
@@ -38,7 +39,14 @@ impl IdValidator {
         Ok(bid)
     }
 
-    pub fn drop_bucket(&mut self, bid: Bid) -> Result<(), IdValidatorError> {
+    /// Returns the buckets which haven't been moved or dropped, in order of creation.
+    pub fn buckets(&self) -> Vec<BucketId> {
+        let mut buckets: Vec<BucketId> = self.buckets.keys().cloned().collect();
+        buckets.sort();
+        buckets
+    }
+
+    pub fn drop_bucket(&mut self, bid: BucketId) -> Result<(), IdValidatorError> {
         if let Some(cnt) = self.buckets.get(&bid) {
             if *cnt == 0 {
                 self.buckets.remove(&bid);
@@ -51,7 +59,7 @@ impl IdValidator {
         }
     }
 
-    pub fn new_bucket_ref(&mut self, bid: Bid) -> Result<Rid, IdValidatorError> {
+    pub fn new_bucket_ref(&mut self, bid: BucketId) -> Result<Rid, IdValidatorError> {
         if let Some(cnt) = self.buckets.get_mut(&bid) {
             *cnt += 1;
             let rid = self
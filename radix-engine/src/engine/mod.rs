@@ -1,19 +1,46 @@
+mod address_book;
+mod call_tracer;
 mod component_objects;
+mod cost_table;
 mod data_validator;
+mod ecdsa;
+mod execution_limits;
 mod id_allocator;
 mod id_validator;
+#[cfg(feature = "native")]
+mod native;
 mod process;
+#[cfg(feature = "native")]
+mod system_packages;
 mod track;
+mod wasm_engine;
 mod wasm_env;
 mod wasm_loader;
+mod wasm_metering;
 mod wasm_validator;
 
+pub use address_book::AddressBook;
 pub use component_objects::*;
-pub use data_validator::validate_data;
+pub use cost_table::{CostTable, ParseCostTableError};
+pub use data_validator::{check_data_context, validate_data, DataContext};
+pub use ecdsa::verify_ecdsa;
+pub use execution_limits::ExecutionLimits;
 pub use id_allocator::*;
 pub use id_validator::*;
+#[cfg(feature = "native")]
+pub use native::{run_native_export, NativeExport, NativePackage, NativeSystem};
 pub use process::{Invocation, Process};
-pub use track::Track;
-pub use wasm_env::{EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME};
+#[cfg(feature = "native")]
+pub use system_packages::{account_package, system_package, system_packages};
+pub use track::{Checkpoint, Track};
+pub use wasm_engine::{default_wasm_engine, WasmEngine, WasmiEngine};
+pub use wasm_env::{
+    EnvModuleResolver, ENGINE_FUNCTION_INDEX, ENGINE_FUNCTION_NAME, METERING_FUNCTION_INDEX,
+    METERING_FUNCTION_NAME,
+};
 pub use wasm_loader::instantiate_module;
-pub use wasm_validator::{parse_module, validate_module};
+pub use wasm_metering::inject_metering;
+pub use wasm_validator::{
+    blueprint_names, dependency_names, parse_module, validate_module, validate_module_with_limits,
+    PackageLimits,
+};
@@ -0,0 +1,167 @@
+use scrypto::abi;
+use scrypto::buffer::*;
+use scrypto::engine::*;
+use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::cell::RefCell;
+use scrypto::rust::collections::*;
+use scrypto::rust::format;
+use scrypto::rust::mem;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+use wasmi::Error;
+
+use crate::model::*;
+use crate::transaction::decode_abi;
+
+/// A blueprint export compiled natively, e.g. the `Hello_main` or `Hello_abi` function
+/// generated by `blueprint!` for blueprint `Hello`.
+pub type NativeExport = extern "C" fn() -> *mut u8;
+
+/// A package whose blueprints are compiled into the running binary instead of WASM, which
+/// makes testing blueprints much faster.
+#[derive(Debug, Clone, Default)]
+pub struct NativePackage {
+    exports: HashMap<String, NativeExport>,
+}
+
+impl NativePackage {
+    pub fn new() -> Self {
+        Self {
+            exports: HashMap::new(),
+        }
+    }
+
+    /// Adds a blueprint, given the dispatcher and ABI functions generated for it.
+    pub fn with_blueprint(mut self, name: &str, main: NativeExport, abi: NativeExport) -> Self {
+        self.exports.insert(format!("{}_main", name), main);
+        self.exports.insert(format!("{}_abi", name), abi);
+        self
+    }
+
+    /// Adds a blueprint of another package this package depends on, given the function
+    /// generated for it by `import!`.
+    pub fn with_dependency(mut self, name: &str, dependency: NativeExport) -> Self {
+        self.exports
+            .insert(format!("{}_dependency", name), dependency);
+        self
+    }
+
+    /// Returns the names of the blueprints in this package.
+    pub fn blueprint_names(&self) -> Vec<String> {
+        self.exports
+            .keys()
+            .filter_map(|name| name.strip_suffix("_abi"))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Returns the names of the blueprints this package depends on.
+    pub fn dependency_names(&self) -> Vec<String> {
+        self.exports
+            .keys()
+            .filter_map(|name| name.strip_suffix("_dependency"))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Returns the export of the given name.
+    pub fn export(&self, name: &str) -> Result<NativeExport, RuntimeError> {
+        self.exports.get(name).cloned().ok_or_else(|| {
+            // Same as a WASM module without the export
+            RuntimeError::InvokeError(Error::Function(format!(
+                "Module doesn't have export {}",
+                name
+            )))
+        })
+    }
+
+    /// Exports the ABI of a blueprint.
+    pub fn export_abi(
+        &self,
+        package_address: Address,
+        blueprint_name: &str,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        let export = self.export(&format!("{}_abi", blueprint_name))?;
+        let output = unsafe {
+            scrypto_consume(export(), |slice| {
+                decode_abi(slice).map_err(RuntimeError::AbiValidationError)
+            })
+        }?;
+
+        Ok(abi::Blueprint {
+            package: package_address.to_string(),
+            name: blueprint_name.to_owned(),
+            functions: output.0,
+            methods: output.1,
+            state: output.2,
+            metadata: output.3,
+            non_fungible_data: output.4,
+        })
+    }
+
+    /// Exports the ABI a blueprint of another package is expected to have.
+    pub fn export_dependency(&self, blueprint_name: &str) -> Result<abi::Blueprint, RuntimeError> {
+        let export = self.export(&format!("{}_dependency", blueprint_name))?;
+        unsafe {
+            scrypto_consume(export(), |slice| {
+                scrypto_decode(slice).map_err(RuntimeError::AbiValidationError)
+            })
+        }
+    }
+}
+
+/// Handles the radix engine calls made by native blueprint code.
+pub trait NativeSystem {
+    fn handle_native_call(&mut self, op: u32, input: &[u8]) -> Result<Vec<u8>, RuntimeError>;
+}
+
+std::thread_local! {
+    /// The systems running native blueprint code on this thread, innermost last.
+    static SYSTEMS: RefCell<Vec<*mut (dyn NativeSystem + 'static)>> = const { RefCell::new(Vec::new()) };
+
+    /// The error which aborted the innermost native blueprint code, if any.
+    static ABORT_ERROR: RefCell<Option<RuntimeError>> = const { RefCell::new(None) };
+}
+
+/// Runs a native export on behalf of the given system, and returns its output.
+///
+/// If the export panics, the error of the radix engine call which made it panic is returned,
+/// or none if the panic comes from the blueprint itself.
+pub fn run_native_export<'a>(
+    system: &mut (dyn NativeSystem + 'a),
+    export: NativeExport,
+) -> Result<*mut u8, Option<RuntimeError>> {
+    // The system is only called back while the export runs, during which it stays borrowed
+    let system: *mut (dyn NativeSystem + 'a) = system;
+    let system: *mut (dyn NativeSystem + 'static) = unsafe { mem::transmute(system) };
+
+    set_native_engine(Some(native_engine));
+    SYSTEMS.with(|s| s.borrow_mut().push(system));
+    let output = export();
+    SYSTEMS.with(|s| s.borrow_mut().pop());
+
+    if output.is_null() {
+        Err(ABORT_ERROR.with(|e| e.borrow_mut().take()))
+    } else {
+        Ok(output)
+    }
+}
+
+fn native_engine(op: u32, input: Vec<u8>) -> Vec<u8> {
+    let system = SYSTEMS.with(|s| {
+        *s.borrow()
+            .last()
+            .expect("Radix engine called outside of a native export")
+    });
+
+    match unsafe { (*system).handle_native_call(op, &input) } {
+        Ok(output) => output,
+        Err(e) => {
+            ABORT_ERROR.with(|a| *a.borrow_mut() = Some(e));
+            // Unlike `panic!`, this doesn't run the panic hook, which would log the panic
+            std::panic::resume_unwind(scrypto::rust::boxed::Box::new("Radix engine call failed"))
+        }
+    }
+}
@@ -1,13 +1,18 @@
 use colored::*;
 use sbor::*;
+use scrypto::abi;
+use scrypto::abi::Access;
 use scrypto::buffer::*;
 use scrypto::engine::*;
+use scrypto::rust::any;
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
 use scrypto::rust::fmt;
 use scrypto::rust::format;
 use scrypto::rust::rc::Rc;
+use scrypto::rust::slice;
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
@@ -54,8 +59,27 @@ macro_rules! re_warn {
 /// Represents an interpreter instance.
 pub struct Interpreter {
     invocation: Invocation,
-    module: ModuleRef,
-    memory: MemoryRef,
+    code: Code,
+}
+
+/// The code run by an interpreter.
+enum Code {
+    Wasm {
+        module: ModuleRef,
+        memory: MemoryRef,
+    },
+    #[cfg(feature = "native")]
+    Native(NativeExport),
+}
+
+impl Interpreter {
+    fn wasm(&self) -> (&ModuleRef, &MemoryRef) {
+        match &self.code {
+            Code::Wasm { module, memory } => (module, memory),
+            #[cfg(feature = "native")]
+            Code::Native(_) => panic!("Not a WASM interpreter"),
+        }
+    }
 }
 
 /// Keeps invocation information.
@@ -63,9 +87,12 @@ pub struct Interpreter {
 pub struct Invocation {
     actor: Actor,
     package_address: Address,
+    package_version: Option<u32>,
     export_name: String,
     function: String,
     args: Vec<ValidatedData>,
+    /// A module instance to run the WASM code in, instead of instantiating the package.
+    instance: Option<(ModuleRef, MemoryRef)>,
 }
 
 /// Qualitative states for a WASM process
@@ -94,19 +121,19 @@ struct WasmProcess {
 }
 
 impl WasmProcess {
-    fn check_resource(&self) -> bool {
-        let mut success = true;
+    fn check_resource(&self) -> Result<(), RuntimeError> {
+        let mut result = Ok(());
 
         for (vid, vault) in &self.process_owned_objects.vaults {
             re_warn!(self, "Dangling vault: {:?}, {:?}", vid, vault);
-            success = false;
+            result = result.and(Err(RuntimeError::DanglingVault(*vid)));
         }
         for (mid, lazy_map) in &self.process_owned_objects.lazy_maps {
             re_warn!(self, "Dangling lazy map: {:?}, {:?}", mid, lazy_map);
-            success = false;
+            result = result.and(Err(RuntimeError::ResourceCheckFailure));
         }
 
-        return success;
+        result
     }
 
     /// Logs a message to the console.
@@ -125,6 +152,14 @@ impl WasmProcess {
     }
 }
 
+/// Returns the name of an engine call from the type of its input, e.g. `CallMethod` for
+/// `CallMethodInput`.
+fn call_name<I>() -> &'static str {
+    let name = any::type_name::<I>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+    name.strip_suffix("Input").unwrap_or(name)
+}
+
 ///TODO: Remove
 enum LazyMapState {
     Uncommitted { root: Mid },
@@ -136,7 +171,18 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         &mut self,
         new_objects: ComponentObjects,
         component_address: Address,
-    ) {
+    ) -> Result<(), RuntimeError> {
+        // Check that the component may hold the resources of all incoming vaults
+        let vaults = new_objects.vaults.values().chain(
+            new_objects
+                .lazy_maps
+                .values()
+                .flat_map(|unclaimed| unclaimed.descendent_vaults.values()),
+        );
+        for vault in vaults {
+            self.check_deposit(vault.resource_address(), component_address)?;
+        }
+
         for (vid, vault) in new_objects.vaults {
             self.put_vault(component_address, vid, vault);
         }
@@ -149,6 +195,18 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                 self.put_vault(component_address, vid, vault);
             }
         }
+        Ok(())
+    }
+
+    fn check_deposit(
+        &mut self,
+        resource_address: Address,
+        component_address: Address,
+    ) -> Result<(), RuntimeError> {
+        self.get_resource_def(resource_address)
+            .ok_or(RuntimeError::ResourceDefNotFound(resource_address))?
+            .check_deposit(component_address)
+            .map_err(RuntimeError::ResourceDefError)
     }
 }
 
@@ -161,15 +219,22 @@ pub struct Process<'r, 'l, L: SubstateStore> {
     /// Transactional state updates
     track: &'r mut Track<'l, L>,
     /// Buckets owned by this process
-    buckets: HashMap<Bid, Bucket>,
+    buckets: HashMap<BucketId, Bucket>,
     /// Buckets owned by this process (but LOCKED because there is a reference to it)
-    buckets_locked: HashMap<Bid, BucketRef>,
+    buckets_locked: HashMap<BucketId, BucketRef>,
     /// Bucket references
     bucket_refs: HashMap<Rid, BucketRef>,
+    /// Bucket references made of several others, which they keep alive
+    composite_bucket_refs: HashMap<Rid, CompositeBucketRef>,
     /// The buckets that will be moved to another process SHORTLY.
-    moving_buckets: HashMap<Bid, Bucket>,
+    moving_buckets: HashMap<BucketId, Bucket>,
     /// The bucket refs that will be moved to another process SHORTLY.
     moving_bucket_refs: HashMap<Rid, BucketRef>,
+    /// The composite bucket refs that will be moved to another process SHORTLY.
+    moving_composite_bucket_refs: HashMap<Rid, CompositeBucketRef>,
+    /// The vaults which locked buckets were taken from to create bucket refs, and which
+    /// the buckets go back to once unlocked.
+    vault_bucket_refs: HashMap<BucketId, VaultId>,
 
     /// State for the given wasm process, empty only on the root process
     /// (root process cannot create components nor is a component itself)
@@ -197,8 +262,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             buckets: HashMap::new(),
             buckets_locked: HashMap::new(),
             bucket_refs: HashMap::new(),
+            composite_bucket_refs: HashMap::new(),
             moving_buckets: HashMap::new(),
             moving_bucket_refs: HashMap::new(),
+            moving_composite_bucket_refs: HashMap::new(),
+            vault_bucket_refs: HashMap::new(),
             wasm_process_state: None,
             id_allocator: IdAllocator::new(IdSpace::Transaction),
             worktop: HashMap::new(),
@@ -239,7 +307,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     }
 
     // (Transaction ONLY) Returns resource back to worktop.
-    pub fn return_to_worktop(&mut self, bid: Bid) -> Result<ValidatedData, RuntimeError> {
+    pub fn return_to_worktop(&mut self, bid: BucketId) -> Result<ValidatedData, RuntimeError> {
         re_debug!(self, "(Transaction) Returning to worktop: bid = {:?}", bid);
 
         let bucket = self
@@ -291,8 +359,119 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }
     }
 
+    // (Transaction ONLY) Assert worktop contains the given non-fungibles.
+    pub fn assert_worktop_contains_non_fungibles(
+        &mut self,
+        keys: BTreeSet<NonFungibleKey>,
+        resource_address: Address,
+    ) -> Result<ValidatedData, RuntimeError> {
+        re_debug!(
+            self,
+            "(Transaction) Asserting worktop contains non-fungibles: keys = {:?}, resource_address = {:?}",
+            keys,
+            resource_address
+        );
+
+        let actual: BTreeSet<NonFungibleKey> = match self.worktop.get(&resource_address) {
+            Some(bucket) => bucket
+                .get_non_fungible_keys()
+                .map_err(RuntimeError::BucketError)?
+                .into_iter()
+                .collect(),
+            None => BTreeSet::new(),
+        };
+
+        if !keys.is_subset(&actual) {
+            re_warn!(
+                self,
+                "(Transaction) Assertion failed: required = {:?}, actual = {:?}, resource_address = {}",
+                keys,
+                actual,
+                resource_address
+            );
+            Err(RuntimeError::AssertionFailed)
+        } else {
+            Ok(validate_data(&scrypto_encode(&())).unwrap())
+        }
+    }
+
+    // (Transaction ONLY) Burns all of a given resource on worktop.
+    pub fn burn_all_from_worktop(
+        &mut self,
+        resource_address: Address,
+    ) -> Result<ValidatedData, RuntimeError> {
+        re_debug!(
+            self,
+            "(Transaction) Burning all from worktop: resource_address = {:?}",
+            resource_address
+        );
+
+        // Transactions have no badge to present, so only freely burnable resources can be
+        // burnt, which validation checks for resources that exist before the transaction
+        if let Some(bucket) = self.worktop.remove(&resource_address) {
+            self.track
+                .get_resource_def_mut(resource_address)
+                .ok_or(RuntimeError::ResourceDefNotFound(resource_address))?
+                .burn(bucket.supply(), None)
+                .map_err(RuntimeError::ResourceDefError)?;
+        }
+        Ok(validate_data(&scrypto_encode(&())).unwrap())
+    }
+
+    // (Transaction ONLY) Takes at most this amount of resource from worktop and returns a bucket,
+    // which is empty if the worktop has none of the resource.
+    pub fn take_up_to_from_worktop(
+        &mut self,
+        amount: Decimal,
+        resource_address: Address,
+    ) -> Result<ValidatedData, RuntimeError> {
+        re_debug!(
+            self,
+            "(Transaction) Taking up to from worktop: amount = {:?}, resource_address = {:?}",
+            amount,
+            resource_address
+        );
+
+        let new_bid = self
+            .id_allocator
+            .new_bid()
+            .map_err(RuntimeError::IdAllocatorError)?;
+        let bucket = match self.worktop.remove(&resource_address) {
+            Some(mut bucket) => {
+                let to_return = bucket
+                    .take(amount.min(bucket.amount()))
+                    .map_err(RuntimeError::BucketError)?;
+
+                if !bucket.amount().is_zero() {
+                    self.worktop.insert(resource_address, bucket);
+                }
+                to_return
+            }
+            None => {
+                let definition = self
+                    .track
+                    .get_resource_def(resource_address)
+                    .ok_or(RuntimeError::ResourceDefNotFound(resource_address))?;
+                Bucket::new(
+                    resource_address,
+                    definition.resource_type(),
+                    match definition.resource_type() {
+                        ResourceType::Fungible { .. } => Supply::Fungible {
+                            amount: Decimal::zero(),
+                        },
+                        ResourceType::NonFungible => Supply::NonFungible {
+                            keys: BTreeSet::new(),
+                        },
+                    },
+                )
+            }
+        };
+        self.buckets.insert(new_bid, bucket);
+        Ok(validate_data(&scrypto_encode(&new_bid)).unwrap())
+    }
+
     // (Transaction ONLY) Creates a bucket ref.
-    pub fn create_bucket_ref(&mut self, bid: Bid) -> Result<ValidatedData, RuntimeError> {
+    pub fn create_bucket_ref(&mut self, bid: BucketId) -> Result<ValidatedData, RuntimeError> {
         re_debug!(self, "(Transaction) Creating bucket ref: bid = {:?}", bid);
 
         let new_rid = self
@@ -357,6 +536,14 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             self,
             "(Transaction) Calling method with all resources started"
         );
+        // 0. Pay royalties before the worktop is emptied
+        let component = self
+            .track
+            .get_component(component_address)
+            .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+        let package_address = component.package_address();
+        self.charge_royalties(package_address, Some(component_address))?;
+
         // 1. Move collected resource to temp buckets
         for (_, bucket) in self.worktop.clone() {
             let bid = self.track.new_bid(); // this is unbounded
@@ -368,23 +555,24 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         self.drop_all_bucket_refs()?;
 
         // 3. Call the method with all buckets
-        let to_deposit: Vec<Bid> = self.buckets.keys().cloned().collect();
+        let to_deposit: Vec<BucketId> = self.buckets.keys().cloned().collect();
         let invocation = self.prepare_call_method(
             component_address,
             method,
             vec![validate_data(&scrypto_encode(&to_deposit)).unwrap()],
         )?;
-        let result = self.call(invocation);
+        let result = self.call(invocation)?;
+        self.pay_royalties_due()?;
 
         re_debug!(
             self,
             "(Transaction) Calling method with all resources ended"
         );
-        result
+        Ok(result)
     }
 
     /// (SYSTEM ONLY)  Creates a bucket ref which references a virtual bucket
-    pub fn create_virtual_bucket_ref(&mut self, bid: Bid, rid: Rid, bucket: Bucket) {
+    pub fn create_virtual_bucket_ref(&mut self, bid: BucketId, rid: Rid, bucket: Bucket) {
         let locked_bucket = LockedBucket::new(bid, bucket);
         let bucket_ref = BucketRef::new(locked_bucket);
         self.bucket_refs.insert(rid, bucket_ref);
@@ -393,11 +581,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     /// Moves buckets and bucket refs into this process.
     pub fn move_in_resources(
         &mut self,
-        buckets: HashMap<Bid, Bucket>,
+        buckets: HashMap<BucketId, Bucket>,
         bucket_refs: HashMap<Rid, BucketRef>,
+        composite_bucket_refs: HashMap<Rid, CompositeBucketRef>,
     ) -> Result<(), RuntimeError> {
         if self.depth == 0 {
             assert!(bucket_refs.is_empty());
+            assert!(composite_bucket_refs.is_empty());
 
             for (_, bucket) in buckets {
                 if !bucket.amount().is_zero() {
@@ -411,6 +601,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             }
         } else {
             self.bucket_refs.extend(bucket_refs);
+            self.composite_bucket_refs.extend(composite_bucket_refs);
             self.buckets.extend(buckets);
         }
 
@@ -418,10 +609,17 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     }
 
     /// Moves all marked buckets and bucket refs from this process.
-    pub fn move_out_resources(&mut self) -> (HashMap<Bid, Bucket>, HashMap<Rid, BucketRef>) {
+    pub fn move_out_resources(
+        &mut self,
+    ) -> (
+        HashMap<BucketId, Bucket>,
+        HashMap<Rid, BucketRef>,
+        HashMap<Rid, CompositeBucketRef>,
+    ) {
         let buckets = self.moving_buckets.drain().collect();
         let bucket_refs = self.moving_bucket_refs.drain().collect();
-        (buckets, bucket_refs)
+        let composite_bucket_refs = self.moving_composite_bucket_refs.drain().collect();
+        (buckets, bucket_refs, composite_bucket_refs)
     }
 
     /// Runs the given export within this process.
@@ -436,14 +634,30 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         );
 
         // Load the code
-        let (module, memory) = self
+        #[cfg(feature = "native")]
+        let native_package = self
             .track
-            .load_module(invocation.package_address)
-            .ok_or(RuntimeError::PackageNotFound(invocation.package_address))?;
+            .native_package(invocation.package_address)
+            .cloned();
+        #[cfg(not(feature = "native"))]
+        let native_package: Option<()> = None;
+        let code = match native_package {
+            #[cfg(feature = "native")]
+            Some(package) => Code::Native(package.export(&invocation.export_name)?),
+            _ => {
+                let (module, memory) = match invocation.instance.clone() {
+                    Some(instance) => instance,
+                    None => self
+                        .track
+                        .load_module(invocation.package_address, invocation.package_version)?,
+                };
+                self.check_memory(&memory)?;
+                Code::Wasm { module, memory }
+            }
+        };
         let vm = Interpreter {
             invocation: invocation.clone(),
-            module: module.clone(),
-            memory,
+            code,
         };
         self.wasm_process_state = Some(WasmProcess {
             depth: self.depth,
@@ -459,25 +673,32 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         });
 
         // run the main function
-        let result = module.invoke_export(invocation.export_name.as_str(), &[], self);
-        re_debug!(self, "Invoke result: {:?}", result);
-        let rtn = result
-            .map_err(RuntimeError::InvokeError)?
-            .ok_or(RuntimeError::NoReturnData)?;
-
-        // move resource based on return data
-        let output = match rtn {
-            RuntimeValue::I32(ptr) => {
-                let data = validate_data(&self.read_bytes(ptr)?)
-                    .map_err(RuntimeError::DataValidationError)?;
-                self.process_call_data(&data, false)?;
-                data
+        let output = match &self.wasm_process_state.as_ref().unwrap().vm.code {
+            Code::Wasm { module, .. } => {
+                let module = module.clone();
+                let result = module.invoke_export(invocation.export_name.as_str(), &[], self);
+                re_debug!(self, "Invoke result: {:?}", result);
+                let rtn = result
+                    .map_err(RuntimeError::InvokeError)?
+                    .ok_or(RuntimeError::NoReturnData)?;
+                self.check_memory(self.wasm_process_state.as_ref().unwrap().vm.wasm().1)?;
+                match rtn {
+                    RuntimeValue::I32(ptr) => self.read_return_data(ptr)?,
+                    _ => {
+                        return Err(RuntimeError::InvalidReturnType);
+                    }
+                }
             }
-            _ => {
-                return Err(RuntimeError::InvalidReturnType);
+            #[cfg(feature = "native")]
+            Code::Native(export) => {
+                let export = *export;
+                self.run_native(export)?
             }
         };
 
+        // move resource based on return data
+        self.process_call_data(&output, false)?;
+
         #[cfg(not(feature = "alloc"))]
         re_info!(
             self,
@@ -501,9 +722,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(Invocation {
             actor: Actor::Blueprint(package_address, blueprint_name.to_owned()),
             package_address,
+            package_version: None,
             export_name: format!("{}_main", blueprint_name),
             function: function.to_owned(),
             args,
+            instance: None,
         })
     }
 
@@ -525,9 +748,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(Invocation {
             actor: Actor::Component(component_address),
             package_address: component.package_address(),
+            package_version: component.package_version(),
             export_name: format!("{}_main", component.blueprint_name()),
             function: method.to_owned(),
             args: args_with_self,
+            instance: None,
         })
     }
 
@@ -540,33 +765,141 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(Invocation {
             actor: Actor::Blueprint(package_address, blueprint_name.to_owned()),
             package_address: package_address,
+            package_version: None,
             export_name: format!("{}_abi", blueprint_name),
             function: String::new(),
             args: Vec::new(),
+            instance: None,
         })
     }
 
+    /// Returns whether the bucket refs among the given arguments reference any of the badges.
+    fn presents_any(
+        &self,
+        args: &[ValidatedData],
+        badges: &[(Address, Option<NonFungibleKey>)],
+    ) -> bool {
+        args.iter()
+            .flat_map(|arg| arg.bucket_refs.iter())
+            .filter_map(|rid| self.get_bucket_ref_members(*rid).ok())
+            .flatten()
+            .any(|bucket_ref| {
+                let bucket = bucket_ref.bucket();
+                !bucket.amount().is_zero()
+                    && badges.iter().any(|(resource_address, key)| {
+                        *resource_address == bucket.resource_address()
+                            && key.as_ref().map_or(true, |key| {
+                                bucket
+                                    .get_non_fungible_keys()
+                                    .map_or(false, |keys| keys.contains(key))
+                            })
+                    })
+            })
+    }
+
     /// Calls a function/method.
     pub fn call(&mut self, invocation: Invocation) -> Result<ValidatedData, RuntimeError> {
+        // reject calls nested too deep, before they use up the host stack
+        let max_call_depth = self.track.execution_limits().max_call_depth;
+        if self.depth >= max_call_depth {
+            return Err(RuntimeError::CallDepthExceeded(max_call_depth));
+        }
+
+        // reject calls back into a running component, unless it's reentrant
+        let component_address = match invocation.actor {
+            Actor::Component(component_address) => Some(component_address),
+            Actor::Blueprint(..) => None,
+        };
+        if let Some(component_address) = component_address {
+            let component = self
+                .track
+                .get_component(component_address)
+                .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+            let package_address = component.package_address();
+            let access = component.method_access(&invocation.function);
+            let auth = component.method_auth(&invocation.function).map_err(|e| {
+                RuntimeError::DataValidationError(DataValidationError::DecodeError(e))
+            })?;
+            if !component.is_reentrant() && self.track.is_component_active(component_address) {
+                return Err(RuntimeError::ReentrantCall(component_address));
+            }
+
+            // reject calls to restricted methods from outside the component or its package
+            let caller = self.wasm_process_state.as_ref().map(|w| &w.vm.invocation);
+            let allowed = match access {
+                Access::Public => true,
+                Access::Package => caller.map(|c| c.package_address) == Some(package_address),
+                Access::Component => matches!(
+                    caller.map(|c| &c.actor),
+                    Some(Actor::Component(address)) if *address == component_address
+                ),
+            };
+            if !allowed {
+                return Err(RuntimeError::MethodNotAccessible(
+                    component_address,
+                    invocation.function,
+                ));
+            }
+
+            // reject calls to methods requiring a badge, unless one is presented
+            if !auth.is_empty() && !self.presents_any(&invocation.args, &auth) {
+                return Err(RuntimeError::MethodNotAuthorized(
+                    component_address,
+                    invocation.function,
+                ));
+            }
+        }
+
+        // calls made by blueprints owe royalties too, which the transaction pays once the
+        // instruction being run returns
+        if self.wasm_process_state.is_some() {
+            for (address, amount) in
+                self.royalties_owed(invocation.package_address, component_address)
+            {
+                self.track.add_royalty_due(address, amount);
+            }
+        }
+
         // move resource
         for arg in &invocation.args {
             self.process_call_data(arg, true)?;
         }
-        let (buckets_out, bucket_refs_out) = self.move_out_resources();
+        let (buckets_out, bucket_refs_out, composite_bucket_refs_out) = self.move_out_resources();
         let mut process = Process::new(self.depth + 1, self.trace, self.track);
-        process.move_in_resources(buckets_out, bucket_refs_out)?;
+        process.move_in_resources(buckets_out, bucket_refs_out, composite_bucket_refs_out)?;
 
         // run the function
-        let result = process.run(invocation)?;
+        if let Some(component_address) = component_address {
+            process.track.enter_component(component_address);
+        }
+        if self.trace {
+            process.track.begin_call_frame(
+                CallKind::Invocation {
+                    actor: invocation.actor.clone(),
+                    function: invocation.function.clone(),
+                },
+                invocation.args.iter().map(|arg| arg.raw.len()).sum(),
+            );
+        }
+        let result = process.run(invocation);
+        if self.trace {
+            process
+                .track
+                .end_call_frame(result.as_ref().map(|o| o.raw.len()).unwrap_or(0));
+        }
+        if component_address.is_some() {
+            process.track.exit_component();
+        }
+        let result = result?;
         process.drop_all_bucket_refs()?;
         process.check_resource()?;
 
         // move resource
-        let (buckets_in, bucket_refs_in) = process.move_out_resources();
-        self.move_in_resources(buckets_in, bucket_refs_in)?;
+        let (buckets_in, bucket_refs_in, composite_bucket_refs_in) = process.move_out_resources();
+        self.move_in_resources(buckets_in, bucket_refs_in, composite_bucket_refs_in)?;
 
         // scan locked buckets for some might have been unlocked by child processes
-        let bids: Vec<Bid> = self
+        let bids: Vec<BucketId> = self
             .buckets_locked
             .values()
             .filter(|v| Rc::strong_count(v) == 1)
@@ -576,7 +909,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             re_debug!(self, "Changing bucket {:?} to unlocked state", bid);
             let bucket_rc = self.buckets_locked.remove(&bid).unwrap();
             let bucket = Rc::try_unwrap(bucket_rc).unwrap();
-            self.buckets.insert(bid, bucket.into());
+            self.unlock_bucket(bid, bucket.into())?;
         }
 
         Ok(result)
@@ -593,9 +926,11 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         re_debug!(self, "Call function started");
         let invocation =
             self.prepare_call_function(package_address, blueprint_name, function, args)?;
-        let result = self.call(invocation);
+        self.charge_royalties(package_address, None)?;
+        let result = self.call(invocation)?;
+        self.pay_royalties_due()?;
         re_debug!(self, "Call function ended");
-        result
+        Ok(result)
     }
 
     /// Calls a method.
@@ -607,9 +942,88 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     ) -> Result<ValidatedData, RuntimeError> {
         re_debug!(self, "Call method started");
         let invocation = self.prepare_call_method(component_address, method, args)?;
-        let result = self.call(invocation);
+        self.charge_royalties(invocation.package_address, Some(component_address))?;
+        let result = self.call(invocation)?;
+        self.pay_royalties_due()?;
         re_debug!(self, "Call method ended");
-        result
+        Ok(result)
+    }
+
+    /// Returns the royalties owed for invoking the package and, for method calls, the
+    /// component.
+    fn royalties_owed(
+        &mut self,
+        package_address: Address,
+        component_address: Option<Address>,
+    ) -> Vec<(Address, Decimal)> {
+        let mut payees = vec![package_address];
+        payees.extend(component_address);
+
+        let mut royalties = Vec::new();
+        for address in payees {
+            let amount = match address {
+                Address::Package(_) => self
+                    .track
+                    .get_package(address)
+                    .and_then(|p| p.royalty())
+                    .map(|r| r.amount()),
+                _ => self
+                    .track
+                    .get_component(address)
+                    .and_then(|c| c.royalty())
+                    .map(|r| r.amount()),
+            };
+            if let Some(amount) = amount.filter(Decimal::is_positive) {
+                royalties.push((address, amount));
+            }
+        }
+        royalties
+    }
+
+    /// (Transaction ONLY) Pays the royalties of the package and, for method calls, the
+    /// component being invoked, with XRD from the worktop.
+    fn charge_royalties(
+        &mut self,
+        package_address: Address,
+        component_address: Option<Address>,
+    ) -> Result<(), RuntimeError> {
+        for (address, amount) in self.royalties_owed(package_address, component_address) {
+            self.pay_royalty(address, amount)?;
+        }
+        Ok(())
+    }
+
+    /// (Transaction ONLY) Pays the royalties owed for the calls made by blueprints while
+    /// running the last instruction, with XRD from the worktop.
+    fn pay_royalties_due(&mut self) -> Result<(), RuntimeError> {
+        for (address, amount) in self.track.take_royalties_due() {
+            self.pay_royalty(address, amount)?;
+        }
+        Ok(())
+    }
+
+    /// (Transaction ONLY) Pays a royalty with XRD from the worktop.
+    fn pay_royalty(&mut self, address: Address, amount: Decimal) -> Result<(), RuntimeError> {
+        re_debug!(
+            self,
+            "(Transaction) Paying royalty: amount = {}, address = {}",
+            amount,
+            address
+        );
+
+        let payment = match self.worktop.remove(&RADIX_TOKEN) {
+            Some(mut bucket) => {
+                let payment = bucket.take(amount);
+                if !bucket.amount().is_zero() {
+                    self.worktop.insert(RADIX_TOKEN, bucket);
+                }
+                payment.map_err(|_| RuntimeError::InsufficientRoyalty(address))
+            }
+            None => Err(RuntimeError::InsufficientRoyalty(address)),
+        }?;
+        self.get_royalty_mut(address)?
+            .collect(payment)
+            .map_err(RuntimeError::RoyaltyError)
     }
 
     /// Calls the ABI generator of a blueprint.
@@ -627,7 +1041,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
     /// Drops all bucket refs owned by this process.
     pub fn drop_all_bucket_refs(&mut self) -> Result<(), RuntimeError> {
-        let rids: Vec<Rid> = self.bucket_refs.keys().cloned().collect();
+        let rids: Vec<Rid> = self
+            .bucket_refs
+            .keys()
+            .chain(self.composite_bucket_refs.keys())
+            .cloned()
+            .collect();
         for rid in rids {
             self.handle_drop_bucket_ref(DropBucketRefInput { rid })?;
         }
@@ -651,13 +1070,14 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             re_warn!(self, "Dangling resource: {:?}", bucket);
             success = false;
         }
-        if let Some(wasm_process) = &self.wasm_process_state {
-            if !wasm_process.check_resource() {
-                success = false;
-            }
-        }
+        // Dangling vaults are reported with their own error, ahead of any other leak
+        let wasm_process_result = match &self.wasm_process_state {
+            Some(wasm_process) => wasm_process.check_resource(),
+            None => Ok(()),
+        };
 
         re_debug!(self, "Resource check ended");
+        wasm_process_result?;
         if success {
             Ok(())
         } else {
@@ -685,32 +1105,24 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         validated: &ValidatedData,
         is_argument: bool,
     ) -> Result<(), RuntimeError> {
-        self.move_buckets(&validated.buckets)?;
-        if is_argument {
-            self.move_bucket_refs(&validated.bucket_refs)?;
+        let context = if is_argument {
+            DataContext::Argument
         } else {
-            if !validated.bucket_refs.is_empty() {
-                return Err(RuntimeError::BucketRefNotAllowed);
-            }
-        }
-        if !validated.lazy_maps.is_empty() {
-            return Err(RuntimeError::LazyMapNotAllowed);
-        }
-        if !validated.vaults.is_empty() {
-            return Err(RuntimeError::VaultNotAllowed);
-        }
+            DataContext::Return
+        };
+        check_data_context(validated, context)?;
+        self.move_buckets(&validated.buckets)?;
+        self.move_bucket_refs(&validated.bucket_refs)?;
         Ok(())
     }
 
     /// Process and parse entry data from any component object (components and maps)
-    fn process_entry_data(data: &[u8]) -> Result<ComponentObjectRefs, RuntimeError> {
+    fn process_entry_data(
+        data: &[u8],
+        context: DataContext,
+    ) -> Result<ComponentObjectRefs, RuntimeError> {
         let validated = validate_data(data).map_err(RuntimeError::DataValidationError)?;
-        if !validated.buckets.is_empty() {
-            return Err(RuntimeError::BucketNotAllowed);
-        }
-        if !validated.bucket_refs.is_empty() {
-            return Err(RuntimeError::BucketRefNotAllowed);
-        }
+        check_data_context(&validated, context)?;
 
         let mut mids = HashSet::new();
         for mid in validated.lazy_maps {
@@ -728,30 +1140,17 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             vids.insert(vid);
         }
 
-        // lazy map allowed
-        // vaults allowed
         Ok(ComponentObjectRefs { mids, vids })
     }
 
     fn process_non_fungible_data(&mut self, data: &[u8]) -> Result<ValidatedData, RuntimeError> {
         let validated = validate_data(data).map_err(RuntimeError::DataValidationError)?;
-        if !validated.buckets.is_empty() {
-            return Err(RuntimeError::BucketNotAllowed);
-        }
-        if !validated.bucket_refs.is_empty() {
-            return Err(RuntimeError::BucketRefNotAllowed);
-        }
-        if !validated.lazy_maps.is_empty() {
-            return Err(RuntimeError::LazyMapNotAllowed);
-        }
-        if !validated.vaults.is_empty() {
-            return Err(RuntimeError::VaultNotAllowed);
-        }
+        check_data_context(&validated, DataContext::NonFungibleData)?;
         Ok(validated)
     }
 
     /// Remove transient buckets from this process
-    fn move_buckets(&mut self, buckets: &[Bid]) -> Result<(), RuntimeError> {
+    fn move_buckets(&mut self, buckets: &[BucketId]) -> Result<(), RuntimeError> {
         for bid in buckets {
             let bucket = self
                 .buckets
@@ -766,6 +1165,16 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     /// Remove transient buckets from this process
     fn move_bucket_refs(&mut self, bucket_refs: &[Rid]) -> Result<(), RuntimeError> {
         for rid in bucket_refs {
+            if let Some(composite) = self.composite_bucket_refs.remove(rid) {
+                re_debug!(
+                    self,
+                    "Moving composite bucket ref: {:?}, {:?}",
+                    rid,
+                    composite
+                );
+                self.moving_composite_bucket_refs.insert(*rid, composite);
+                continue;
+            }
             let bucket_ref = self
                 .bucket_refs
                 .remove(rid)
@@ -776,82 +1185,150 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(())
     }
 
+    /// Takes back a bucket which is no longer referenced, returning it to its vault if it
+    /// was locked by `CREATE_VAULT_BUCKET_REF`.
+    fn unlock_bucket(&mut self, bid: BucketId, bucket: Bucket) -> Result<(), RuntimeError> {
+        match self.vault_bucket_refs.remove(&bid) {
+            Some(vid) => self
+                .get_local_vault(vid)?
+                .put(bucket)
+                .map_err(RuntimeError::VaultError),
+            None => {
+                self.buckets.insert(bid, bucket);
+                Ok(())
+            }
+        }
+    }
+
     /// Send a byte array to wasm instance.
     fn send_bytes(&mut self, bytes: &[u8]) -> Result<i32, RuntimeError> {
-        let wasm_process = self.wasm_process_state.as_ref().unwrap();
-        let result = wasm_process.vm.module.invoke_export(
+        let (module, memory) = self.wasm_process_state.as_ref().unwrap().vm.wasm();
+        // Metered code reports the instructions of the allocator too
+        let (module, memory) = (module.clone(), memory.clone());
+        let result = module.invoke_export(
             "scrypto_alloc",
             &[RuntimeValue::I32((bytes.len()) as i32)],
-            &mut NopExternals,
+            self,
         );
 
-        if let Ok(Some(RuntimeValue::I32(ptr))) = result {
-            if wasm_process.vm.memory.set((ptr + 4) as u32, bytes).is_ok() {
+        match result {
+            Ok(Some(RuntimeValue::I32(ptr))) if memory.set((ptr + 4) as u32, bytes).is_ok() => {
                 return Ok(ptr);
             }
+            Err(e @ Error::Trap(_)) => return Err(RuntimeError::InvokeError(e)),
+            _ => {}
         }
 
         Err(RuntimeError::MemoryAllocError)
     }
 
-    /// Read a byte array from wasm instance.
-    fn read_bytes(&mut self, ptr: i32) -> Result<Vec<u8>, RuntimeError> {
-        let wasm_process = self.wasm_process_state.as_ref().unwrap();
+    /// Validates the data returned by wasm instance and frees the buffer.
+    ///
+    /// The data is validated in place, so that large return values are copied out of
+    /// the wasm memory only once.
+    fn read_return_data(&mut self, ptr: i32) -> Result<ValidatedData, RuntimeError> {
+        let (module, memory) = self.wasm_process_state.as_ref().unwrap().vm.wasm();
+        let (module, memory) = (module.clone(), memory.clone());
         // read length
-        let a = wasm_process
-            .vm
-            .memory
+        let a = memory
             .get(ptr as u32, 4)
             .map_err(RuntimeError::MemoryAccessError)?;
-        let len = u32::from_le_bytes([a[0], a[1], a[2], a[3]]);
-
-        // read data
-        let data = wasm_process
-            .vm
-            .memory
-            .get((ptr + 4) as u32, len as usize)
-            .map_err(RuntimeError::MemoryAccessError)?;
+        let len = u32::from_le_bytes([a[0], a[1], a[2], a[3]]) as usize;
+
+        // validate data
+        let start = (ptr as u32 as usize).checked_add(4).ok_or_else(|| {
+            RuntimeError::MemoryAccessError(Error::Memory(format!(
+                "trying to read {} bytes at {}",
+                len, ptr as u32
+            )))
+        })?;
+        let data = memory.with_direct_access(|buf| {
+            match start.checked_add(len).and_then(|end| buf.get(start..end)) {
+                Some(slice) => validate_data(slice).map_err(RuntimeError::DataValidationError),
+                None => Err(RuntimeError::MemoryAccessError(Error::Memory(format!(
+                    "trying to read {} bytes at {}",
+                    len, start
+                )))),
+            }
+        })?;
 
         // free the buffer
-        wasm_process
-            .vm
-            .module
-            .invoke_export(
-                "scrypto_free",
-                &[RuntimeValue::I32(ptr as i32)],
-                &mut NopExternals,
-            )
+        module
+            .invoke_export("scrypto_free", &[RuntimeValue::I32(ptr)], self)
             .map_err(RuntimeError::MemoryAccessError)?;
 
         Ok(data)
     }
 
+    /// Runs a native export, and validates the data it returns.
+    #[cfg(feature = "native")]
+    fn run_native(&mut self, export: NativeExport) -> Result<ValidatedData, RuntimeError> {
+        match run_native_export(self, export) {
+            Ok(ptr) => unsafe { scrypto_consume(ptr, validate_data) }
+                .map_err(RuntimeError::DataValidationError),
+            // Same as a WASM module which traps, either on a failed system call or a panic
+            Err(Some(e)) => Err(RuntimeError::InvokeError(Error::Trap(Trap::from(e)))),
+            Err(None) => Err(RuntimeError::InvokeError(Error::Trap(Trap::new(
+                TrapKind::Unreachable,
+            )))),
+        }
+    }
+
+    /// Checks the size of the memory of a WASM instance against the limit.
+    ///
+    /// Memory can only grow, so checking at every engine call and on return catches any
+    /// instance going past the limit, at the same point on every run.
+    fn check_memory(&self, memory: &MemoryRef) -> Result<(), RuntimeError> {
+        let max_memory_pages = self.track.execution_limits().max_memory_pages;
+        if memory.current_size().0 > max_memory_pages as usize {
+            return Err(RuntimeError::MemoryLimitExceeded(max_memory_pages));
+        }
+        Ok(())
+    }
+
     /// Handles a system call.
     fn handle<I: Decode + fmt::Debug, O: Encode + fmt::Debug>(
         &mut self,
-        args: RuntimeArgs,
+        op: u32,
+        input_bytes: &[u8],
         handler: fn(&mut Self, input: I) -> Result<O, RuntimeError>,
-    ) -> Result<Option<RuntimeValue>, Trap> {
-        let wasm_process = self.wasm_process_state.as_mut().unwrap();
-        let op: u32 = args.nth_checked(0)?;
-        let input_ptr: u32 = args.nth_checked(1)?;
-        let input_len: u32 = args.nth_checked(2)?;
-        let input_bytes = wasm_process
-            .vm
-            .memory
-            .get(input_ptr, input_len as usize)
-            .map_err(|e| Trap::from(RuntimeError::MemoryAccessError(e)))?;
-        let input: I = scrypto_decode(&input_bytes)
-            .map_err(|e| Trap::from(RuntimeError::InvalidRequestData(e)))?;
-        if input_len <= 1024 {
+    ) -> Result<Vec<u8>, RuntimeError> {
+        self.track.count_engine_call(call_name::<I>())?;
+        if let Some(Code::Wasm { memory, .. }) =
+            self.wasm_process_state.as_ref().map(|w| &w.vm.code)
+        {
+            self.check_memory(memory)?;
+        }
+
+        let input: I = scrypto_decode(input_bytes).map_err(RuntimeError::InvalidRequestData)?;
+        if input_bytes.len() <= 1024 {
             re_trace!(self, "{:?}", input);
         } else {
-            re_trace!(self, "Large request: op = {:02x}, len = {}", op, input_len);
+            re_trace!(
+                self,
+                "Large request: op = {:02x}, len = {}",
+                op,
+                input_bytes.len()
+            );
         }
 
-        let output: O = handler(self, input).map_err(Trap::from)?;
-        let output_bytes = scrypto_encode(&output);
-        let output_ptr = self.send_bytes(&output_bytes).map_err(Trap::from)?;
+        if self.trace {
+            self.track.begin_call_frame(
+                CallKind::EngineCall {
+                    op,
+                    name: call_name::<I>(),
+                },
+                input_bytes.len(),
+            );
+        }
+        let result = handler(self, input).map(|output| (scrypto_encode(&output), output));
+        if self.trace {
+            self.track
+                .end_call_frame(result.as_ref().map(|(b, _)| b.len()).unwrap_or(0));
+        }
+        let (output_bytes, output) = result?;
+        // Charges the state loaded by the call
+        self.track.charge(0)?;
         if output_bytes.len() <= 1024 {
             re_trace!(self, "{:?}", output);
         } else {
@@ -863,7 +1340,152 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             );
         }
 
-        Ok(Some(RuntimeValue::I32(output_ptr)))
+        Ok(output_bytes)
+    }
+
+    /// Dispatches a system call to its handler.
+    fn dispatch(&mut self, op: u32, input: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+        match op {
+            PUBLISH_PACKAGE => self.handle(op, input, Self::handle_publish),
+            PUBLISH_PACKAGE_WITH_OWNER => self.handle(op, input, Self::handle_publish_with_owner),
+            PUBLISH_PACKAGE_UPGRADE => self.handle(op, input, Self::handle_publish_upgrade),
+            CALL_FUNCTION => self.handle(op, input, Self::handle_call_function),
+            CALL_METHOD => self.handle(op, input, Self::handle_call_method),
+            CALL_METHODS => self.handle(op, input, Self::handle_call_methods),
+            TRY_CALL_METHOD => self.handle(op, input, Self::handle_try_call_method),
+            SET_ROYALTY => self.handle(op, input, Self::handle_set_royalty),
+            CLAIM_ROYALTY => self.handle(op, input, Self::handle_claim_royalty),
+
+            CREATE_COMPONENT => self.handle(op, input, Self::handle_create_component),
+            GET_COMPONENT_INFO => self.handle(op, input, Self::handle_get_component_info),
+            GET_COMPONENT_STATE => self.handle(op, input, Self::handle_get_component_state),
+            PUT_COMPONENT_STATE => self.handle(op, input, Self::handle_put_component_state),
+            READ_COMPONENT_STATE => self.handle(op, input, Self::handle_read_component_state),
+            SET_COMPONENT_REENTRANT => self.handle(op, input, Self::handle_set_component_reentrant),
+            SET_COMPONENT_METHOD_ACCESS => {
+                self.handle(op, input, Self::handle_set_component_method_access)
+            }
+            SET_COMPONENT_PACKAGE_VERSION => {
+                self.handle(op, input, Self::handle_set_component_package_version)
+            }
+
+            CREATE_LAZY_MAP => self.handle(op, input, Self::handle_create_lazy_map),
+            GET_LAZY_MAP_ENTRY => self.handle(op, input, Self::handle_get_lazy_map_entry),
+            PUT_LAZY_MAP_ENTRY => self.handle(op, input, Self::handle_put_lazy_map_entry),
+            SCAN_LAZY_MAP => self.handle(op, input, Self::handle_scan_lazy_map),
+
+            CREATE_RESOURCE => self.handle(op, input, Self::handle_create_resource),
+            GET_RESOURCE_TYPE => self.handle(op, input, Self::handle_get_resource_type),
+            GET_RESOURCE_METADATA => self.handle(op, input, Self::handle_get_resource_metadata),
+            GET_RESOURCE_TOTAL_SUPPLY => {
+                self.handle(op, input, Self::handle_get_resource_total_supply)
+            }
+            GET_RESOURCE_FLAGS => self.handle(op, input, Self::handle_get_resource_flags),
+            UPDATE_RESOURCE_FLAGS => self.handle(op, input, Self::handle_update_resource_flags),
+            GET_RESOURCE_MUTABLE_FLAGS => {
+                self.handle(op, input, Self::handle_get_resource_mutable_flags)
+            }
+            UPDATE_RESOURCE_MUTABLE_FLAGS => {
+                self.handle(op, input, Self::handle_update_resource_mutable_flags)
+            }
+            MINT_RESOURCE => self.handle(op, input, Self::handle_mint_resource),
+            BURN_RESOURCE => self.handle(op, input, Self::handle_burn_resource),
+            UPDATE_NON_FUNGIBLE_MUTABLE_DATA => {
+                self.handle(op, input, Self::handle_update_non_fungible_mutable_data)
+            }
+            GET_NON_FUNGIBLE_DATA => self.handle(op, input, Self::handle_get_non_fungible_data),
+            UPDATE_RESOURCE_METADATA => {
+                self.handle(op, input, Self::handle_update_resource_metadata)
+            }
+            RECALL_RESOURCE => self.handle(op, input, Self::handle_recall_resource),
+            UPDATE_ALLOWED_DEPOSITOR => {
+                self.handle(op, input, Self::handle_update_allowed_depositor)
+            }
+
+            CREATE_EMPTY_VAULT => self.handle(op, input, Self::handle_create_vault),
+            PUT_INTO_VAULT => self.handle(op, input, Self::handle_put_into_vault),
+            TAKE_FROM_VAULT => self.handle(op, input, Self::handle_take_from_vault),
+            GET_VAULT_AMOUNT => self.handle(op, input, Self::handle_get_vault_amount),
+            GET_VAULT_RESOURCE_ADDRESS => {
+                self.handle(op, input, Self::handle_get_vault_resource_address)
+            }
+            TAKE_NON_FUNGIBLE_FROM_VAULT => {
+                self.handle(op, input, Self::handle_take_non_fungible_from_vault)
+            }
+            GET_NON_FUNGIBLE_KEYS_IN_VAULT => {
+                self.handle(op, input, Self::handle_get_non_fungible_keys_in_vault)
+            }
+            TAKE_ALL_FROM_VAULT => self.handle(op, input, Self::handle_take_all_from_vault),
+            TAKE_UP_TO_FROM_VAULT => self.handle(op, input, Self::handle_take_up_to_from_vault),
+            TAKE_NON_FUNGIBLES_FROM_VAULT => {
+                self.handle(op, input, Self::handle_take_non_fungibles_from_vault)
+            }
+            GET_NON_FUNGIBLES_IN_VAULT => {
+                self.handle(op, input, Self::handle_get_non_fungibles_in_vault)
+            }
+            FREEZE_VAULT => self.handle(op, input, Self::handle_freeze_vault),
+            UNFREEZE_VAULT => self.handle(op, input, Self::handle_unfreeze_vault),
+
+            CREATE_EMPTY_BUCKET => self.handle(op, input, Self::handle_create_bucket),
+            PUT_INTO_BUCKET => self.handle(op, input, Self::handle_put_into_bucket),
+            TAKE_FROM_BUCKET => self.handle(op, input, Self::handle_take_from_bucket),
+            GET_BUCKET_AMOUNT => self.handle(op, input, Self::handle_get_bucket_amount),
+            GET_BUCKET_RESOURCE_ADDRESS => {
+                self.handle(op, input, Self::handle_get_bucket_resource_address)
+            }
+            TAKE_NON_FUNGIBLE_FROM_BUCKET => {
+                self.handle(op, input, Self::handle_take_non_fungible_from_bucket)
+            }
+            GET_NON_FUNGIBLE_KEYS_IN_BUCKET => {
+                self.handle(op, input, Self::handle_get_non_fungible_keys_in_bucket)
+            }
+            GET_NON_FUNGIBLES_IN_BUCKET => {
+                self.handle(op, input, Self::handle_get_non_fungibles_in_bucket)
+            }
+            DROP_EMPTY_BUCKET => self.handle(op, input, Self::handle_drop_empty_bucket),
+
+            CREATE_BUCKET_REF => self.handle(op, input, Self::handle_create_bucket_ref),
+            DROP_BUCKET_REF => self.handle(op, input, Self::handle_drop_bucket_ref),
+            GET_BUCKET_REF_AMOUNT => self.handle(op, input, Self::handle_get_bucket_ref_amount),
+            GET_BUCKET_REF_RESOURCE_DEF => {
+                self.handle(op, input, Self::handle_get_bucket_ref_resource_def)
+            }
+            GET_NON_FUNGIBLE_KEYS_IN_BUCKET_REF => {
+                self.handle(op, input, Self::handle_get_non_fungible_keys_in_bucket_ref)
+            }
+            CLONE_BUCKET_REF => self.handle(op, input, Self::handle_clone_bucket_ref),
+            CHECK_BUCKET_REF_METADATA => {
+                self.handle(op, input, Self::handle_check_bucket_ref_metadata)
+            }
+            GET_BUCKET_REF_ORIGIN => self.handle(op, input, Self::handle_get_bucket_ref_origin),
+            CREATE_VAULT_BUCKET_REF => self.handle(op, input, Self::handle_create_vault_bucket_ref),
+            CREATE_BUCKET_REF_FOR_AMOUNT => {
+                self.handle(op, input, Self::handle_create_bucket_ref_for_amount)
+            }
+            CREATE_BUCKET_REF_FOR_NON_FUNGIBLES => {
+                self.handle(op, input, Self::handle_create_bucket_ref_for_non_fungibles)
+            }
+            COMPOSE_BUCKET_REFS => self.handle(op, input, Self::handle_compose_bucket_refs),
+            CHECK_BUCKET_REF_CONTAINS => {
+                self.handle(op, input, Self::handle_check_bucket_ref_contains)
+            }
+
+            EMIT_LOG => self.handle(op, input, Self::handle_emit_log),
+            EMIT_EVENT => self.handle(op, input, Self::handle_emit_event),
+            GET_PACKAGE_ADDRESS => self.handle(op, input, Self::handle_get_package_address),
+            GET_CALL_DATA => self.handle(op, input, Self::handle_get_call_data),
+            GET_TRANSACTION_HASH => self.handle(op, input, Self::handle_get_transaction_hash),
+            GET_CURRENT_EPOCH => self.handle(op, input, Self::handle_get_current_epoch),
+            GENERATE_UUID => self.handle(op, input, Self::handle_generate_uuid),
+            GENERATE_RANDOM => self.handle(op, input, Self::handle_generate_random),
+            VERIFY_ECDSA => self.handle(op, input, Self::handle_verify_ecdsa),
+            GET_WELL_KNOWN_ADDRESS => self.handle(op, input, Self::handle_get_well_known_address),
+            GET_ACTOR => self.handle(op, input, Self::handle_get_actor),
+            BATCH_ENGINE_CALLS => self.handle(op, input, Self::handle_batch_engine_calls),
+            SCHEDULE_CALL => self.handle(op, input, Self::handle_schedule_call),
+
+            _ => Err(RuntimeError::InvalidRequestCode(op)),
+        }
     }
 
     fn expect_package_address(address: Address) -> Result<(), RuntimeError> {
@@ -893,10 +1515,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     fn check_badge(&mut self, optional_rid: Option<Rid>) -> Result<Option<Address>, RuntimeError> {
         if let Some(rid) = optional_rid {
             // retrieve bucket reference
-            let bucket_ref = self
-                .bucket_refs
-                .get(&rid)
-                .ok_or(RuntimeError::BucketRefNotFound(rid))?;
+            let bucket_ref = self.get_bucket_ref(rid)?;
 
             // read amount & address
             if bucket_ref.bucket().amount().is_zero() {
@@ -913,6 +1532,23 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }
     }
 
+    fn get_royalty_mut(&mut self, address: Address) -> Result<&mut Royalty, RuntimeError> {
+        let royalty = match address {
+            Address::Package(_) => self
+                .track
+                .get_package_mut(address)
+                .ok_or(RuntimeError::PackageNotFound(address))?
+                .royalty_mut(),
+            Address::Component(_) => self
+                .track
+                .get_component_mut(address)
+                .ok_or(RuntimeError::ComponentNotFound(address))?
+                .royalty_mut(),
+            _ => return Err(RuntimeError::InvalidComponentAddress(address)),
+        };
+        royalty.ok_or(RuntimeError::RoyaltyNotConfigured(address))
+    }
+
     //============================
     // SYSTEM CALL HANDLERS START
     //============================
@@ -921,18 +1557,138 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: PublishPackageInput,
     ) -> Result<PublishPackageOutput, RuntimeError> {
+        let package_address = self.publish_package(Package::new(input.code))?;
+
+        Ok(PublishPackageOutput { package_address })
+    }
+
+    fn handle_publish_with_owner(
+        &mut self,
+        input: PublishPackageWithOwnerInput,
+    ) -> Result<PublishPackageWithOwnerOutput, RuntimeError> {
+        let package_address =
+            self.publish_package(Package::with_owner_badge(input.code, input.owner_badge))?;
+
+        Ok(PublishPackageWithOwnerOutput { package_address })
+    }
+
+    fn publish_package(&mut self, package: Package) -> Result<Address, RuntimeError> {
         let package_address = self.track.new_package_address();
 
         if self.track.get_package(package_address).is_some() {
             return Err(RuntimeError::PackageAlreadyExists(package_address));
         }
-        validate_module(&input.code).map_err(RuntimeError::WasmValidationError)?;
+        validate_module_with_limits(package.code(), self.track.package_limits())
+            .map_err(RuntimeError::WasmValidationError)?;
 
+        let blueprint_names = blueprint_names(package.code());
+        let dependency_names = dependency_names(package.code());
         re_debug!(self, "New package: {:?}", package_address);
+        self.track.put_package(package_address, package);
+        self.load_metadata(package_address, blueprint_names)?;
+        self.check_dependencies(package_address, dependency_names)?;
+
+        Ok(package_address)
+    }
+
+    /// Stores the metadata declared by the blueprints of the latest version of a package.
+    fn load_metadata(
+        &mut self,
+        package_address: Address,
+        blueprint_names: Vec<String>,
+    ) -> Result<(), RuntimeError> {
+        let mut metadata = BTreeMap::new();
+        for name in blueprint_names {
+            let output = self.call_abi(package_address, &name)?;
+            let (_, _, _, pairs, _) =
+                decode_abi(&output.raw).map_err(RuntimeError::AbiValidationError)?;
+            if !pairs.is_empty() {
+                metadata.insert(name, pairs);
+            }
+        }
         self.track
-            .put_package(package_address, Package::new(input.code));
+            .get_package_mut(package_address)
+            .unwrap()
+            .set_metadata(metadata);
+        Ok(())
+    }
 
-        Ok(PublishPackageOutput { package_address })
+    /// Checks the blueprints the latest version of a package depends on against the ABIs it
+    /// was compiled with.
+    fn check_dependencies(
+        &mut self,
+        package_address: Address,
+        dependency_names: Vec<String>,
+    ) -> Result<(), RuntimeError> {
+        for name in dependency_names {
+            let invocation = Invocation {
+                actor: Actor::Blueprint(package_address, name.clone()),
+                package_address,
+                package_version: None,
+                export_name: format!("{}_dependency", name),
+                function: String::new(),
+                args: Vec::new(),
+                instance: None,
+            };
+            let output = self.call(invocation)?;
+            let expected: abi::Blueprint =
+                scrypto_decode(&output.raw).map_err(RuntimeError::AbiValidationError)?;
+            check_dependency(&expected, |address, blueprint_name| {
+                let output = self.call_abi(address, blueprint_name)?;
+                let (functions, methods, state, metadata, non_fungible_data) =
+                    decode_abi(&output.raw).map_err(RuntimeError::AbiValidationError)?;
+                Ok(abi::Blueprint {
+                    package: address.to_string(),
+                    name: blueprint_name.to_owned(),
+                    functions,
+                    methods,
+                    state,
+                    metadata,
+                    non_fungible_data,
+                })
+            })?;
+        }
+        Ok(())
+    }
+
+    fn handle_publish_upgrade(
+        &mut self,
+        input: PublishPackageUpgradeInput,
+    ) -> Result<PublishPackageUpgradeOutput, RuntimeError> {
+        Self::expect_package_address(input.package_address)?;
+        let badge = self.check_badge(Some(input.auth))?;
+
+        let owner_badge = self
+            .track
+            .get_package(input.package_address)
+            .ok_or(RuntimeError::PackageNotFound(input.package_address))?
+            .owner_badge()
+            .ok_or(RuntimeError::PackageNotUpgradeable(input.package_address))?;
+        if badge != Some(owner_badge) {
+            return Err(RuntimeError::PackageUpgradeNotAllowed(
+                input.package_address,
+            ));
+        }
+        validate_module_with_limits(&input.code, self.track.package_limits())
+            .map_err(RuntimeError::WasmValidationError)?;
+
+        let blueprint_names = blueprint_names(&input.code);
+        let dependency_names = dependency_names(&input.code);
+        let version = self
+            .track
+            .get_package_mut(input.package_address)
+            .unwrap()
+            .upgrade(input.code);
+        self.load_metadata(input.package_address, blueprint_names)?;
+        self.check_dependencies(input.package_address, dependency_names)?;
+        re_debug!(
+            self,
+            "Package upgraded: address = {:?}, version = {}",
+            input.package_address,
+            version
+        );
+
+        Ok(PublishPackageUpgradeOutput { version })
     }
 
     fn handle_call_function(
@@ -971,6 +1727,64 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: CallMethodInput,
     ) -> Result<CallMethodOutput, RuntimeError> {
+        let invocation = self.prepare_call_method_input(input)?;
+        let result = self.call(invocation);
+
+        re_debug!(self, "CALL finished");
+        Ok(CallMethodOutput { rtn: result?.raw })
+    }
+
+    fn handle_call_methods(
+        &mut self,
+        input: CallMethodsInput,
+    ) -> Result<CallMethodsOutput, RuntimeError> {
+        re_debug!(self, "CALL_METHODS started: calls = {}", input.calls.len());
+
+        // Calls into the same component share a module instance rather than each instantiating
+        // the code, so the memory of one call carries over to the next call into the same
+        // component, but never to another component of the package.
+        let mut instances: HashMap<(Address, Option<u32>), (ModuleRef, MemoryRef)> = HashMap::new();
+        let mut rtns = Vec::new();
+        for (component_address, method, args) in input.calls {
+            let mut invocation = self.prepare_call_method_input(CallMethodInput {
+                component_address,
+                method,
+                args,
+            })?;
+            #[cfg(feature = "native")]
+            let is_native = self
+                .track
+                .native_package(invocation.package_address)
+                .is_some();
+            #[cfg(not(feature = "native"))]
+            let is_native = false;
+            if !is_native {
+                let key = (component_address, invocation.package_version);
+                let instance = match instances.get(&key) {
+                    Some(instance) => instance.clone(),
+                    None => {
+                        let instance = self.track.load_module(invocation.package_address, key.1)?;
+                        instances.insert(key, instance.clone());
+                        instance
+                    }
+                };
+                invocation.instance = Some(instance);
+            }
+            let result = self.call(invocation);
+
+            re_debug!(self, "CALL finished");
+            rtns.push(result?.raw);
+        }
+
+        re_debug!(self, "CALL_METHODS finished");
+        Ok(CallMethodsOutput { rtns })
+    }
+
+    /// Validates the arguments of a method call from a blueprint, and prepares the call.
+    fn prepare_call_method_input(
+        &mut self,
+        input: CallMethodInput,
+    ) -> Result<Invocation, RuntimeError> {
         Self::expect_component_address(input.component_address)?;
 
         let mut validated_args = Vec::new();
@@ -986,15 +1800,131 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             validated_args
         );
 
-        let invocation = self.prepare_call_method(
+        self.prepare_call_method(
             input.component_address,
             input.method.as_str(),
             validated_args,
-        )?;
-        let result = self.call(invocation);
+        )
+    }
 
-        re_debug!(self, "CALL finished");
-        Ok(CallMethodOutput { rtn: result?.raw })
+    fn handle_try_call_method(
+        &mut self,
+        input: TryCallMethodInput,
+    ) -> Result<TryCallMethodOutput, RuntimeError> {
+        Self::expect_component_address(input.component_address)?;
+
+        let mut validated_args = Vec::new();
+        for arg in input.args {
+            validated_args.push(validate_data(&arg).map_err(RuntimeError::DataValidationError)?);
+        }
+
+        // resources moved into a failed call can't be given back, so none may be passed
+        if validated_args
+            .iter()
+            .any(|arg| !arg.buckets.is_empty() || !arg.bucket_refs.is_empty())
+        {
+            return Err(RuntimeError::ResourcesInTryCall(
+                input.component_address,
+                input.method,
+            ));
+        }
+
+        re_debug!(
+            self,
+            "TRY_CALL started: component = {:?}, method = {:?}, args = {:?}, max_engine_calls = {}",
+            input.component_address,
+            input.method,
+            validated_args,
+            input.max_engine_calls
+        );
+
+        let checkpoint = self.track.checkpoint();
+        let previous_limit = self.track.limit_engine_calls(input.max_engine_calls);
+        let result = self
+            .prepare_call_method(
+                input.component_address,
+                input.method.as_str(),
+                validated_args,
+            )
+            .and_then(|invocation| self.call(invocation));
+        self.track.restore_engine_call_limit(previous_limit);
+
+        let rtn = match result {
+            Ok(output) => Some(output.raw),
+            Err(e) => {
+                re_warn!(self, "TRY_CALL failed and reverted: {:?}", e);
+                self.track.revert(checkpoint);
+                None
+            }
+        };
+
+        re_debug!(self, "TRY_CALL finished");
+        Ok(TryCallMethodOutput { rtn })
+    }
+
+    fn handle_set_royalty(
+        &mut self,
+        input: SetRoyaltyInput,
+    ) -> Result<SetRoyaltyOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let package_address = wasm_process.vm.invocation.package_address;
+
+        // only the blueprints of a package may set the royalties of it and its components
+        let owner = match input.address {
+            Address::Package(_) => input.address,
+            Address::Component(_) => self
+                .track
+                .get_component(input.address)
+                .ok_or(RuntimeError::ComponentNotFound(input.address))?
+                .package_address(),
+            _ => return Err(RuntimeError::InvalidComponentAddress(input.address)),
+        };
+        if owner != package_address {
+            return Err(RuntimeError::RoyaltyUpdateNotAllowed(input.address));
+        }
+
+        re_debug!(
+            self,
+            "Setting royalty: address = {:?}, amount = {:?}, owner_badge = {:?}",
+            input.address,
+            input.amount,
+            input.owner_badge
+        );
+        match input.address {
+            Address::Package(_) => self
+                .track
+                .get_package_mut(input.address)
+                .ok_or(RuntimeError::PackageNotFound(input.address))?
+                .set_royalty(input.amount, input.owner_badge),
+            _ => self
+                .track
+                .get_component_mut(input.address)
+                .ok_or(RuntimeError::ComponentNotFound(input.address))?
+                .set_royalty(input.amount, input.owner_badge),
+        }
+        .map_err(RuntimeError::RoyaltyError)?;
+
+        Ok(SetRoyaltyOutput {})
+    }
+
+    fn handle_claim_royalty(
+        &mut self,
+        input: ClaimRoyaltyInput,
+    ) -> Result<ClaimRoyaltyOutput, RuntimeError> {
+        let badge = self.check_badge(Some(input.auth))?;
+
+        let bucket = self
+            .get_royalty_mut(input.address)?
+            .claim(badge)
+            .map_err(RuntimeError::RoyaltyError)?;
+
+        let bid = self.track.new_bid();
+        self.buckets.insert(bid, bucket);
+
+        Ok(ClaimRoyaltyOutput { bid })
     }
 
     fn handle_create_component(
@@ -1011,17 +1941,20 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             return Err(RuntimeError::ComponentAlreadyExists(component_address));
         }
 
-        let data = Self::process_entry_data(&input.state)?;
+        let data = Self::process_entry_data(&input.state, DataContext::ComponentState)?;
         let new_objects = wasm_process.process_owned_objects.take(data)?;
 
         self.track
-            .insert_objects_into_component(new_objects, component_address);
+            .insert_objects_into_component(new_objects, component_address)?;
 
-        let component = Component::new(
+        let mut component = Component::new(
             wasm_process.vm.invocation.package_address,
             input.blueprint_name,
             input.state,
         );
+        for (method, indices) in input.method_auth {
+            component.set_method_auth(method, indices);
+        }
         self.track.put_component(component_address, component);
 
         Ok(CreateComponentOutput { component_address })
@@ -1057,7 +1990,8 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             InterpreterState::ComponentEmpty { component_address } => {
                 let component = self.track.get_component(*component_address).unwrap();
                 let state = component.state();
-                let initial_loaded_object_refs = Self::process_entry_data(state).unwrap();
+                let initial_loaded_object_refs =
+                    Self::process_entry_data(state, DataContext::ComponentState).unwrap();
                 Ok((
                     state,
                     InterpreterState::ComponentLoaded {
@@ -1090,11 +2024,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                 initial_loaded_object_refs,
                 ..
             } => {
-                let mut new_set = Self::process_entry_data(&input.state)?;
+                let mut new_set =
+                    Self::process_entry_data(&input.state, DataContext::ComponentState)?;
                 new_set.remove(&initial_loaded_object_refs)?;
                 let new_objects = wasm_process.process_owned_objects.take(new_set)?;
                 self.track
-                    .insert_objects_into_component(new_objects, *component_address);
+                    .insert_objects_into_component(new_objects, *component_address)?;
 
                 // TODO: Verify that process_owned_objects is empty
 
@@ -1110,6 +2045,131 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(PutComponentStateOutput {})
     }
 
+    fn handle_read_component_state(
+        &mut self,
+        input: ReadComponentStateInput,
+    ) -> Result<ReadComponentStateOutput, RuntimeError> {
+        Self::expect_component_address(input.component_address)?;
+
+        let component = self
+            .track
+            .get_component(input.component_address)
+            .ok_or(RuntimeError::ComponentNotFound(input.component_address))?;
+        if component.package_address() != input.package_address {
+            return Err(RuntimeError::PackageNotMatching(
+                input.component_address,
+                input.package_address,
+            ));
+        }
+        if component.blueprint_name() != input.blueprint_name {
+            return Err(RuntimeError::BlueprintNotMatching(
+                input.component_address,
+                input.blueprint_name,
+            ));
+        }
+
+        Ok(ReadComponentStateOutput {
+            state: component.state().to_vec(),
+        })
+    }
+
+    fn handle_set_component_reentrant(
+        &mut self,
+        input: SetComponentReentrantInput,
+    ) -> Result<SetComponentReentrantOutput, RuntimeError> {
+        Self::expect_component_address(input.component_address)?;
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let package_address = wasm_process.vm.invocation.package_address;
+
+        // Only code of the component's own package may change its reentrancy
+        let component = self
+            .track
+            .get_component_mut(input.component_address)
+            .ok_or(RuntimeError::ComponentNotFound(input.component_address))?;
+        if component.package_address() != package_address {
+            return Err(RuntimeError::IllegalSystemCall());
+        }
+        component.set_reentrant(input.reentrant);
+
+        Ok(SetComponentReentrantOutput {})
+    }
+
+    fn handle_set_component_method_access(
+        &mut self,
+        input: SetComponentMethodAccessInput,
+    ) -> Result<SetComponentMethodAccessOutput, RuntimeError> {
+        Self::expect_component_address(input.component_address)?;
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let package_address = wasm_process.vm.invocation.package_address;
+
+        // Only code of the component's own package may restrict its methods
+        let component = self
+            .track
+            .get_component_mut(input.component_address)
+            .ok_or(RuntimeError::ComponentNotFound(input.component_address))?;
+        if component.package_address() != package_address {
+            return Err(RuntimeError::IllegalSystemCall());
+        }
+        for (method, access) in input.methods {
+            component.set_method_access(method, access);
+        }
+
+        Ok(SetComponentMethodAccessOutput {})
+    }
+
+    fn handle_set_component_package_version(
+        &mut self,
+        input: SetComponentPackageVersionInput,
+    ) -> Result<SetComponentPackageVersionOutput, RuntimeError> {
+        Self::expect_component_address(input.component_address)?;
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let package_address = wasm_process.vm.invocation.package_address;
+        let is_self = matches!(
+            wasm_process.vm.invocation.actor,
+            Actor::Component(address) if address == input.component_address
+        );
+
+        // Only code of the component's own package may pin it, and only the component itself
+        // may change the version it's pinned to
+        let component = self
+            .track
+            .get_component(input.component_address)
+            .ok_or(RuntimeError::ComponentNotFound(input.component_address))?;
+        if component.package_address() != package_address {
+            return Err(RuntimeError::IllegalSystemCall());
+        }
+        if component.package_version().is_some() && !is_self {
+            return Err(RuntimeError::PackageVersionUpdateNotAllowed(
+                input.component_address,
+            ));
+        }
+        if let Some(version) = input.version {
+            self.track
+                .get_package(package_address)
+                .and_then(|p| p.code_at(version))
+                .ok_or(RuntimeError::PackageVersionNotFound(
+                    package_address,
+                    version,
+                ))?;
+        }
+
+        self.track
+            .get_component_mut(input.component_address)
+            .unwrap()
+            .set_package_version(input.version);
+
+        Ok(SetComponentPackageVersionOutput {})
+    }
+
     fn handle_create_lazy_map(
         &mut self,
         _input: CreateLazyMapInput,
@@ -1158,8 +2218,9 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         .get_lazy_map_mut(&component_address, &input.mid)
                         .unwrap();
                     let value = lazy_map.get_entry(&input.key);
-                    if value.is_some() {
-                        let map_entry_objects = Self::process_entry_data(value.unwrap()).unwrap();
+                    if let Some(value) = &value {
+                        let map_entry_objects =
+                            Self::process_entry_data(value, DataContext::LazyMapValue).unwrap();
                         additional_object_refs.extend(map_entry_objects);
                     }
 
@@ -1213,10 +2274,15 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             },
             Some((root, lazy_map)) => Ok((lazy_map, Uncommitted { root })),
         }?;
-        let mut new_entry_object_refs = Self::process_entry_data(&input.value)?;
+        // Keys of an ordered store are not SBOR-encoded, and so can't reference any objects
+        if let Ok(key) = validate_data(&input.key) {
+            check_data_context(&key, DataContext::LazyMapKey)?;
+        }
+        let mut new_entry_object_refs =
+            Self::process_entry_data(&input.value, DataContext::LazyMapValue)?;
         let old_entry_object_refs = match lazy_map.get_entry(&input.key) {
             None => ComponentObjectRefs::new(),
-            Some(e) => Self::process_entry_data(e).unwrap(),
+            Some(e) => Self::process_entry_data(e, DataContext::LazyMapValue).unwrap(),
         };
         lazy_map.set_entry(input.key, input.value);
 
@@ -1241,13 +2307,69 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             }
             Committed { component_address } => {
                 self.track
-                    .insert_objects_into_component(new_objects, component_address);
+                    .insert_objects_into_component(new_objects, component_address)?;
             }
         }
 
         Ok(PutLazyMapEntryOutput {})
     }
 
+    fn handle_scan_lazy_map(
+        &mut self,
+        input: ScanLazyMapInput,
+    ) -> Result<ScanLazyMapOutput, RuntimeError> {
+        if input.limit > MAX_SCAN_LIMIT {
+            return Err(RuntimeError::ScanLimitExceeded(input.limit));
+        }
+
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let from = input.from.as_deref();
+        let to = input.to.as_deref();
+        let limit = input.limit as usize;
+        let entries: Vec<(Vec<u8>, Vec<u8>)> = match wasm_process
+            .process_owned_objects
+            .get_lazy_map_mut(&input.mid)
+        {
+            None => match &mut wasm_process.interpreter_state {
+                InterpreterState::ComponentLoaded {
+                    initial_loaded_object_refs,
+                    additional_object_refs,
+                    component_address,
+                } => {
+                    if !initial_loaded_object_refs.mids.contains(&input.mid)
+                        && !additional_object_refs.mids.contains(&input.mid)
+                    {
+                        return Err(RuntimeError::LazyMapNotFound(input.mid));
+                    }
+                    let lazy_map = self
+                        .track
+                        .get_lazy_map_mut(component_address, &input.mid)
+                        .unwrap();
+                    let mut entries = Vec::new();
+                    for (key, value) in lazy_map.range(from, to, limit) {
+                        let map_entry_objects =
+                            Self::process_entry_data(value, DataContext::LazyMapValue).unwrap();
+                        additional_object_refs.extend(map_entry_objects);
+                        entries.push((key.to_vec(), value.to_vec()));
+                    }
+
+                    Ok(entries)
+                }
+                _ => Err(RuntimeError::LazyMapNotFound(input.mid)),
+            },
+            Some((_, lazy_map)) => Ok(lazy_map
+                .range(from, to, limit)
+                .into_iter()
+                .map(|(k, v)| (k.to_vec(), v.to_vec()))
+                .collect()),
+        }?;
+
+        Ok(ScanLazyMapOutput { entries })
+    }
+
     fn allocate_resource(
         &mut self,
         resource_address: Address,
@@ -1556,44 +2678,163 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(UpdateResourceMetadataOutput {})
     }
 
-    fn handle_create_vault(
+    fn handle_recall_resource(
         &mut self,
-        input: CreateEmptyVaultInput,
-    ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
-        let wasm_process = self
-            .wasm_process_state
-            .as_mut()
-            .ok_or(RuntimeError::IllegalSystemCall())?;
-        let definition = self
+        input: RecallResourceInput,
+    ) -> Result<RecallResourceOutput, RuntimeError> {
+        Self::expect_resource_address(input.resource_address)?;
+        Self::expect_component_address(input.component_address)?;
+        let badge = self.check_badge(Some(input.auth))?;
+
+        let resource_def = self
             .track
             .get_resource_def(input.resource_address)
             .ok_or(RuntimeError::ResourceDefNotFound(input.resource_address))?;
+        resource_def
+            .check_recall_auth(badge)
+            .map_err(RuntimeError::ResourceDefError)?;
 
-        let new_vault = Vault::new(Bucket::new(
-            input.resource_address,
-            definition.resource_type(),
-            match definition.resource_type() {
-                ResourceType::Fungible { .. } => Supply::Fungible {
-                    amount: Decimal::zero(),
-                },
-                ResourceType::NonFungible { .. } => Supply::NonFungible {
-                    keys: BTreeSet::new(),
-                },
-            },
-        ));
-        let vid = self.track.new_vid();
-        wasm_process
-            .process_owned_objects
-            .vaults
-            .insert(vid, new_vault);
+        // only committed vaults can be recalled from
+        let vault = self
+            .track
+            .get_vault_mut(&input.component_address, &input.vid)
+            .ok_or(RuntimeError::VaultNotFound(input.vid))?;
+        if vault.resource_address() != input.resource_address {
+            return Err(RuntimeError::VaultError(VaultError::AccountingError(
+                BucketError::ResourceNotMatching,
+            )));
+        }
+        // recall is how frozen resources are seized, so it ignores freezing
+        let bucket = vault
+            .recall(input.amount)
+            .map_err(RuntimeError::VaultError)?;
 
-        Ok(CreateEmptyVaultOutput { vid })
+        let bid = self.track.new_bid();
+        self.buckets.insert(bid, bucket);
+
+        Ok(RecallResourceOutput { bid })
     }
 
-    fn get_local_vault(&mut self, vid: Vid) -> Result<&mut Vault, RuntimeError> {
-        let wasm_process = self
-            .wasm_process_state
-            .as_mut()
+    fn handle_freeze_vault(
+        &mut self,
+        input: FreezeVaultInput,
+    ) -> Result<FreezeVaultOutput, RuntimeError> {
+        self.set_vault_frozen(
+            input.resource_address,
+            input.component_address,
+            input.vid,
+            input.auth,
+            true,
+        )?;
+
+        Ok(FreezeVaultOutput {})
+    }
+
+    fn handle_unfreeze_vault(
+        &mut self,
+        input: UnfreezeVaultInput,
+    ) -> Result<UnfreezeVaultOutput, RuntimeError> {
+        self.set_vault_frozen(
+            input.resource_address,
+            input.component_address,
+            input.vid,
+            input.auth,
+            false,
+        )?;
+
+        Ok(UnfreezeVaultOutput {})
+    }
+
+    fn set_vault_frozen(
+        &mut self,
+        resource_address: Address,
+        component_address: Address,
+        vid: VaultId,
+        auth: Rid,
+        frozen: bool,
+    ) -> Result<(), RuntimeError> {
+        Self::expect_resource_address(resource_address)?;
+        Self::expect_component_address(component_address)?;
+        let badge = self.check_badge(Some(auth))?;
+
+        let resource_def = self
+            .track
+            .get_resource_def(resource_address)
+            .ok_or(RuntimeError::ResourceDefNotFound(resource_address))?;
+        resource_def
+            .check_freeze_auth(badge)
+            .map_err(RuntimeError::ResourceDefError)?;
+
+        // only committed vaults can be frozen
+        let vault = self
+            .track
+            .get_vault_mut(&component_address, &vid)
+            .ok_or(RuntimeError::VaultNotFound(vid))?;
+        if vault.resource_address() != resource_address {
+            return Err(RuntimeError::VaultError(VaultError::AccountingError(
+                BucketError::ResourceNotMatching,
+            )));
+        }
+        vault.set_frozen(frozen);
+
+        Ok(())
+    }
+
+    fn handle_update_allowed_depositor(
+        &mut self,
+        input: UpdateAllowedDepositorInput,
+    ) -> Result<UpdateAllowedDepositorOutput, RuntimeError> {
+        Self::expect_resource_address(input.resource_address)?;
+        Self::expect_component_address(input.component_address)?;
+        let badge = self.check_badge(Some(input.auth))?;
+
+        self.track
+            .get_resource_def_mut(input.resource_address)
+            .ok_or(RuntimeError::ResourceDefNotFound(input.resource_address))?
+            .update_allowed_depositor(input.component_address, input.allowed, badge)
+            .map_err(RuntimeError::ResourceDefError)?;
+
+        Ok(UpdateAllowedDepositorOutput {})
+    }
+
+    fn handle_create_vault(
+        &mut self,
+        input: CreateEmptyVaultInput,
+    ) -> Result<CreateEmptyVaultOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let definition = self
+            .track
+            .get_resource_def(input.resource_address)
+            .ok_or(RuntimeError::ResourceDefNotFound(input.resource_address))?;
+
+        let new_vault = Vault::new(Bucket::new(
+            input.resource_address,
+            definition.resource_type(),
+            match definition.resource_type() {
+                ResourceType::Fungible { .. } => Supply::Fungible {
+                    amount: Decimal::zero(),
+                },
+                ResourceType::NonFungible { .. } => Supply::NonFungible {
+                    keys: BTreeSet::new(),
+                },
+            },
+        ));
+        let vid = self.track.new_vid();
+        wasm_process
+            .process_owned_objects
+            .vaults
+            .insert(vid, new_vault);
+
+        Ok(CreateEmptyVaultOutput { vid })
+    }
+
+    fn get_local_vault(&mut self, vid: VaultId) -> Result<&mut Vault, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_mut()
             .ok_or(RuntimeError::IllegalSystemCall())?;
         match wasm_process.process_owned_objects.get_vault_mut(&vid) {
             Some(vault) => Ok(vault),
@@ -1617,6 +2858,24 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         }
     }
 
+    // Returns the component owning a committed vault, or `None` for vaults owned by this process.
+    fn vault_owner(&mut self, vid: VaultId) -> Option<Address> {
+        let wasm_process = self.wasm_process_state.as_mut()?;
+        if wasm_process
+            .process_owned_objects
+            .get_vault_mut(&vid)
+            .is_some()
+        {
+            return None;
+        }
+        match &wasm_process.interpreter_state {
+            InterpreterState::ComponentLoaded {
+                component_address, ..
+            } => Some(*component_address),
+            _ => None,
+        }
+    }
+
     fn handle_put_into_vault(
         &mut self,
         input: PutIntoVaultInput,
@@ -1628,6 +2887,12 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
             .remove(&input.bid)
             .ok_or(RuntimeError::BucketNotFound(input.bid))?;
 
+        // Vaults owned by this process are checked once they're claimed by a component
+        if let Some(component_address) = self.vault_owner(input.vid) {
+            self.track
+                .check_deposit(bucket.resource_address(), component_address)?;
+        }
+
         self.get_local_vault(input.vid)?
             .put(bucket)
             .map_err(RuntimeError::VaultError)?;
@@ -1637,7 +2902,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
 
     fn check_take_from_vault_auth(
         &mut self,
-        vid: Vid,
+        vid: VaultId,
         badge: Option<Address>,
     ) -> Result<(), RuntimeError> {
         let resource_address = self.get_local_vault(vid)?.resource_address();
@@ -1691,6 +2956,61 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(TakeNonFungibleFromVaultOutput { bid })
     }
 
+    fn handle_take_all_from_vault(
+        &mut self,
+        input: TakeAllFromVaultInput,
+    ) -> Result<TakeAllFromVaultOutput, RuntimeError> {
+        let badge = self.check_badge(input.auth)?;
+        self.check_take_from_vault_auth(input.vid, badge)?;
+
+        let new_bucket = self
+            .get_local_vault(input.vid)?
+            .take_all()
+            .map_err(RuntimeError::VaultError)?;
+
+        let bid = self.track.new_bid();
+        self.buckets.insert(bid, new_bucket);
+
+        Ok(TakeAllFromVaultOutput { bid })
+    }
+
+    fn handle_take_up_to_from_vault(
+        &mut self,
+        input: TakeUpToFromVaultInput,
+    ) -> Result<TakeUpToFromVaultOutput, RuntimeError> {
+        let badge = self.check_badge(input.auth)?;
+        self.check_take_from_vault_auth(input.vid, badge)?;
+
+        let new_bucket = self
+            .get_local_vault(input.vid)?
+            .take_up_to(input.amount)
+            .map_err(RuntimeError::VaultError)?;
+        let amount = new_bucket.amount();
+
+        let bid = self.track.new_bid();
+        self.buckets.insert(bid, new_bucket);
+
+        Ok(TakeUpToFromVaultOutput { bid, amount })
+    }
+
+    fn handle_take_non_fungibles_from_vault(
+        &mut self,
+        input: TakeNonFungiblesFromVaultInput,
+    ) -> Result<TakeNonFungiblesFromVaultOutput, RuntimeError> {
+        let badge = self.check_badge(input.auth)?;
+        self.check_take_from_vault_auth(input.vid, badge)?;
+
+        let new_bucket = self
+            .get_local_vault(input.vid)?
+            .take_non_fungibles(&input.keys)
+            .map_err(RuntimeError::VaultError)?;
+
+        let bid = self.track.new_bid();
+        self.buckets.insert(bid, new_bucket);
+
+        Ok(TakeNonFungiblesFromVaultOutput { bid })
+    }
+
     fn handle_get_non_fungible_keys_in_vault(
         &mut self,
         input: GetNonFungibleKeysInVaultInput,
@@ -1703,6 +3023,24 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(GetNonFungibleKeysInVaultOutput { keys })
     }
 
+    fn handle_get_non_fungibles_in_vault(
+        &mut self,
+        input: GetNonFungiblesInVaultInput,
+    ) -> Result<GetNonFungiblesInVaultOutput, RuntimeError> {
+        let vault = self.get_local_vault(input.vid)?;
+        let resource_address = vault.resource_address();
+        let keys = vault
+            .get_non_fungible_ids()
+            .map_err(RuntimeError::VaultError)?;
+
+        Ok(GetNonFungiblesInVaultOutput {
+            non_fungibles: keys
+                .into_iter()
+                .map(|key| self.get_non_fungible_entry(resource_address, key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
     fn handle_get_vault_amount(
         &mut self,
         input: GetVaultDecimalInput,
@@ -1854,6 +3192,61 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         })
     }
 
+    fn handle_get_non_fungibles_in_bucket(
+        &mut self,
+        input: GetNonFungiblesInBucketInput,
+    ) -> Result<GetNonFungiblesInBucketOutput, RuntimeError> {
+        let bucket = self
+            .buckets
+            .get(&input.bid)
+            .ok_or(RuntimeError::BucketNotFound(input.bid))?;
+        let resource_address = bucket.resource_address();
+        let keys = bucket
+            .get_non_fungible_keys()
+            .map_err(RuntimeError::BucketError)?;
+
+        Ok(GetNonFungiblesInBucketOutput {
+            non_fungibles: keys
+                .into_iter()
+                .map(|key| self.get_non_fungible_entry(resource_address, key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    fn handle_drop_empty_bucket(
+        &mut self,
+        input: DropEmptyBucketInput,
+    ) -> Result<DropEmptyBucketOutput, RuntimeError> {
+        let bucket = self
+            .buckets
+            .get(&input.bid)
+            .ok_or(RuntimeError::BucketNotFound(input.bid))?;
+        if !bucket.amount().is_zero() {
+            return Err(RuntimeError::BucketNotEmpty(input.bid));
+        }
+        self.buckets.remove(&input.bid);
+
+        Ok(DropEmptyBucketOutput {})
+    }
+
+    /// Loads a non-fungible as (key, immutable data, mutable data).
+    fn get_non_fungible_entry(
+        &mut self,
+        resource_address: Address,
+        key: NonFungibleKey,
+    ) -> Result<(NonFungibleKey, Vec<u8>, Vec<u8>), RuntimeError> {
+        let non_fungible = self
+            .track
+            .get_non_fungible(resource_address, &key)
+            .ok_or_else(|| RuntimeError::NonFungibleNotFound(resource_address, key.clone()))?;
+
+        Ok((
+            key,
+            non_fungible.immutable_data(),
+            non_fungible.mutable_data(),
+        ))
+    }
+
     fn handle_create_bucket_ref(
         &mut self,
         input: CreateBucketRefInput,
@@ -1862,13 +3255,38 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         let rid = self.track.new_rid();
         re_debug!(self, "Borrowing: bid = {:?}, rid = {:?}", bid, rid);
 
-        match self.buckets_locked.get_mut(&bid) {
-            Some(bucket_rc) => {
-                // re-borrow
-                self.bucket_refs.insert(rid, bucket_rc.clone());
-            }
+        let bucket_ref = self.lock_bucket(bid)?;
+        self.bucket_refs.insert(rid, bucket_ref);
+
+        Ok(CreateBucketRefOutput { rid })
+    }
+
+    fn handle_create_bucket_ref_for_amount(
+        &mut self,
+        input: CreateBucketRefForAmountInput,
+    ) -> Result<CreateBucketRefForAmountOutput, RuntimeError> {
+        let rid = self.create_scoped_bucket_ref(input.bid, |b| b.scoped_to_amount(input.amount))?;
+
+        Ok(CreateBucketRefForAmountOutput { rid })
+    }
+
+    fn handle_create_bucket_ref_for_non_fungibles(
+        &mut self,
+        input: CreateBucketRefForNonFungiblesInput,
+    ) -> Result<CreateBucketRefForNonFungiblesOutput, RuntimeError> {
+        let rid =
+            self.create_scoped_bucket_ref(input.bid, |b| b.scoped_to_non_fungibles(&input.keys))?;
+
+        Ok(CreateBucketRefForNonFungiblesOutput { rid })
+    }
+
+    /// Locks a bucket, unless already locked, and returns the reference that keeps it locked.
+    fn lock_bucket(&mut self, bid: BucketId) -> Result<BucketRef, RuntimeError> {
+        match self.buckets_locked.get(&bid) {
+            // re-borrow
+            Some(bucket_rc) => Ok(bucket_rc.clone()),
+            // first time borrow
             None => {
-                // first time borrow
                 let bucket = BucketRef::new(LockedBucket::new(
                     bid,
                     self.buckets
@@ -1876,11 +3294,70 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
                         .ok_or(RuntimeError::BucketNotFound(bid))?,
                 ));
                 self.buckets_locked.insert(bid, bucket.clone());
-                self.bucket_refs.insert(rid, bucket);
+                Ok(bucket)
             }
         }
+    }
 
-        Ok(CreateBucketRefOutput { rid })
+    /// Creates a reference to part of a bucket, which the scope function picks out of it.
+    ///
+    /// The scope is checked against the bucket, so a scoped reference never proves more than
+    /// the bucket holds.
+    fn create_scoped_bucket_ref<F: FnOnce(&Bucket) -> Result<Bucket, BucketError>>(
+        &mut self,
+        bid: BucketId,
+        scope: F,
+    ) -> Result<Rid, RuntimeError> {
+        let bucket = self
+            .buckets
+            .get(&bid)
+            .or_else(|| self.buckets_locked.get(&bid).map(|b| b.bucket()))
+            .ok_or(RuntimeError::BucketNotFound(bid))?;
+        let scoped = scope(bucket).map_err(RuntimeError::BucketError)?;
+
+        let rid = self.track.new_rid();
+        re_debug!(
+            self,
+            "Borrowing part: bid = {:?}, rid = {:?}, amount = {}",
+            bid,
+            rid,
+            scoped.amount()
+        );
+        let parent = self.lock_bucket(bid)?;
+        self.bucket_refs
+            .insert(rid, BucketRef::new(LockedBucket::scoped(&parent, scoped)));
+
+        Ok(rid)
+    }
+
+    fn handle_create_vault_bucket_ref(
+        &mut self,
+        input: CreateVaultBucketRefInput,
+    ) -> Result<CreateVaultBucketRefOutput, RuntimeError> {
+        // No transfer auth is needed, as the locked bucket can't leave this process and goes
+        // back to the vault once all references are dropped
+        let bucket = self
+            .get_local_vault(input.vid)?
+            .take_all()
+            .map_err(RuntimeError::VaultError)?;
+        let origin = self.vault_owner(input.vid);
+
+        let bid = self.track.new_bid();
+        let rid = self.track.new_rid();
+        re_debug!(
+            self,
+            "Borrowing from vault: vid = {:?}, bid = {:?}, rid = {:?}",
+            input.vid,
+            bid,
+            rid
+        );
+
+        let bucket_ref = BucketRef::new(LockedBucket::new(bid, bucket).with_origin(origin));
+        self.buckets_locked.insert(bid, bucket_ref.clone());
+        self.bucket_refs.insert(rid, bucket_ref);
+        self.vault_bucket_refs.insert(bid, input.vid);
+
+        Ok(CreateVaultBucketRefOutput { rid })
     }
 
     fn handle_drop_bucket_ref(
@@ -1889,37 +3366,134 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     ) -> Result<DropBucketRefOutput, RuntimeError> {
         let rid = input.rid;
 
-        let (count, bid) = {
-            let bucket_ref = self
-                .bucket_refs
-                .remove(&rid)
-                .ok_or(RuntimeError::BucketRefNotFound(rid))?;
+        if let Some(composite) = self.composite_bucket_refs.remove(&rid) {
             re_debug!(
                 self,
-                "Dropping bucket ref: rid = {:?}, bucket = {:?}",
+                "Dropping composite bucket ref: rid = {:?}, bucket refs = {:?}",
                 rid,
-                bucket_ref
+                composite
             );
-            (Rc::strong_count(&bucket_ref) - 1, bucket_ref.bucket_id())
-        };
-
-        if count == 1 {
-            if let Some(b) = self.buckets_locked.remove(&bid) {
-                self.buckets.insert(bid, Rc::try_unwrap(b).unwrap().into());
+            for bucket_ref in composite {
+                self.release_bucket_ref(bucket_ref)?;
             }
+            return Ok(DropBucketRefOutput {});
         }
 
+        let bucket_ref = self
+            .bucket_refs
+            .remove(&rid)
+            .ok_or(RuntimeError::BucketRefNotFound(rid))?;
+        re_debug!(
+            self,
+            "Dropping bucket ref: rid = {:?}, bucket = {:?}",
+            rid,
+            bucket_ref
+        );
+        self.release_bucket_ref(bucket_ref)?;
+
         Ok(DropBucketRefOutput {})
     }
 
+    /// Releases a reference, unlocking its bucket once no reference, whether to all or part
+    /// of it, is left.
+    fn release_bucket_ref(&mut self, bucket_ref: BucketRef) -> Result<(), RuntimeError> {
+        let bid = bucket_ref.bucket_id();
+        drop(bucket_ref);
+
+        let unreferenced = matches!(
+            self.buckets_locked.get(&bid),
+            Some(b) if Rc::strong_count(b) == 1
+        );
+        if unreferenced {
+            let b = self.buckets_locked.remove(&bid).unwrap();
+            self.unlock_bucket(bid, Rc::try_unwrap(b).unwrap().into())?;
+        }
+        Ok(())
+    }
+
+    fn handle_compose_bucket_refs(
+        &mut self,
+        input: ComposeBucketRefsInput,
+    ) -> Result<ComposeBucketRefsOutput, RuntimeError> {
+        if input.rids.is_empty() {
+            return Err(RuntimeError::EmptyCompositeBucketRef);
+        }
+        // check all bucket refs before consuming any
+        for rid in &input.rids {
+            for bucket_ref in self.get_bucket_ref_members(*rid)? {
+                if bucket_ref.bucket().amount().is_zero() {
+                    return Err(RuntimeError::EmptyBucketRef);
+                }
+            }
+        }
+
+        // composite bucket refs are flattened into the new one
+        let mut composite = Vec::new();
+        for rid in &input.rids {
+            match self.composite_bucket_refs.remove(rid) {
+                Some(bucket_refs) => composite.extend(bucket_refs),
+                None => composite.push(
+                    self.bucket_refs
+                        .remove(rid)
+                        .ok_or(RuntimeError::BucketRefNotFound(*rid))?,
+                ),
+            }
+        }
+
+        let rid = self.track.new_rid();
+        re_debug!(
+            self,
+            "Composing: rids = {:?}, new rid = {:?}",
+            input.rids,
+            rid
+        );
+        self.composite_bucket_refs.insert(rid, composite);
+
+        Ok(ComposeBucketRefsOutput { rid })
+    }
+
+    fn handle_check_bucket_ref_contains(
+        &mut self,
+        input: CheckBucketRefContainsInput,
+    ) -> Result<CheckBucketRefContainsOutput, RuntimeError> {
+        let contains = self
+            .get_bucket_ref_members(input.rid)?
+            .iter()
+            .map(|bucket_ref| bucket_ref.bucket())
+            .any(|bucket| {
+                !bucket.amount().is_zero() && bucket.resource_address() == input.resource_address
+            });
+
+        Ok(CheckBucketRefContainsOutput { contains })
+    }
+
+    /// Returns a bucket ref, which must reference a single bucket.
+    fn get_bucket_ref(&self, rid: Rid) -> Result<&BucketRef, RuntimeError> {
+        if self.composite_bucket_refs.contains_key(&rid) {
+            return Err(RuntimeError::CompositeBucketRefNotAllowed(rid));
+        }
+        self.bucket_refs
+            .get(&rid)
+            .ok_or(RuntimeError::BucketRefNotFound(rid))
+    }
+
+    /// Returns the bucket refs making up a bucket ref, which is only itself unless composite.
+    fn get_bucket_ref_members(&self, rid: Rid) -> Result<&[BucketRef], RuntimeError> {
+        match self.composite_bucket_refs.get(&rid) {
+            Some(composite) => Ok(composite),
+            None => self
+                .bucket_refs
+                .get(&rid)
+                .map(slice::from_ref)
+                .ok_or(RuntimeError::BucketRefNotFound(rid)),
+        }
+    }
+
     fn handle_get_bucket_ref_amount(
         &mut self,
         input: GetBucketRefDecimalInput,
     ) -> Result<GetBucketRefDecimalOutput, RuntimeError> {
-        let bucket_ref = self
-            .bucket_refs
-            .get(&input.rid)
-            .ok_or(RuntimeError::BucketRefNotFound(input.rid))?;
+        let bucket_ref = self.get_bucket_ref(input.rid)?;
 
         Ok(GetBucketRefDecimalOutput {
             amount: bucket_ref.bucket().amount(),
@@ -1930,10 +3504,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: GetBucketRefResourceAddressInput,
     ) -> Result<GetBucketRefResourceAddressOutput, RuntimeError> {
-        let bucket_ref = self
-            .bucket_refs
-            .get(&input.rid)
-            .ok_or(RuntimeError::BucketRefNotFound(input.rid))?;
+        let bucket_ref = self.get_bucket_ref(input.rid)?;
 
         Ok(GetBucketRefResourceAddressOutput {
             resource_address: bucket_ref.bucket().resource_address(),
@@ -1944,10 +3515,7 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: GetNonFungibleKeysInBucketRefInput,
     ) -> Result<GetNonFungibleKeysInBucketRefOutput, RuntimeError> {
-        let bucket_ref = self
-            .bucket_refs
-            .get(&input.rid)
-            .ok_or(RuntimeError::BucketRefNotFound(input.rid))?;
+        let bucket_ref = self.get_bucket_ref(input.rid)?;
 
         Ok(GetNonFungibleKeysInBucketRefOutput {
             keys: bucket_ref
@@ -1961,6 +3529,18 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         &mut self,
         input: CloneBucketRefInput,
     ) -> Result<CloneBucketRefOutput, RuntimeError> {
+        if let Some(composite) = self.composite_bucket_refs.get(&input.rid).cloned() {
+            let new_rid = self.track.new_rid();
+            re_debug!(
+                self,
+                "Cloning composite: rid = {:?}, new rid = {:?}",
+                input.rid,
+                new_rid
+            );
+            self.composite_bucket_refs.insert(new_rid, composite);
+            return Ok(CloneBucketRefOutput { rid: new_rid });
+        }
+
         let bucket_ref = self
             .bucket_refs
             .get(&input.rid)
@@ -1979,12 +3559,79 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         Ok(CloneBucketRefOutput { rid: new_rid })
     }
 
+    fn handle_check_bucket_ref_metadata(
+        &mut self,
+        input: CheckBucketRefMetadataInput,
+    ) -> Result<CheckBucketRefMetadataOutput, RuntimeError> {
+        let bucket_ref = self.get_bucket_ref(input.rid)?;
+        if bucket_ref.bucket().amount().is_zero() {
+            return Ok(CheckBucketRefMetadataOutput { matches: false });
+        }
+        let resource_address = bucket_ref.bucket().resource_address();
+
+        // resource definitions are cached by the track, so repeated checks within
+        // a transaction don't hit the ledger
+        let resource_def = self
+            .track
+            .get_resource_def(resource_address)
+            .ok_or(RuntimeError::ResourceDefNotFound(resource_address))?;
+        let matches = resource_def.metadata().get(&input.key) == Some(&input.value);
+        re_debug!(
+            self,
+            "Checking metadata: rid = {:?}, {} = {}, matches = {}",
+            input.rid,
+            input.key,
+            input.value,
+            matches
+        );
+
+        Ok(CheckBucketRefMetadataOutput { matches })
+    }
+
+    fn handle_get_bucket_ref_origin(
+        &mut self,
+        input: GetBucketRefOriginInput,
+    ) -> Result<GetBucketRefOriginOutput, RuntimeError> {
+        let bucket_ref = self.get_bucket_ref(input.rid)?;
+
+        Ok(GetBucketRefOriginOutput {
+            origin: bucket_ref.origin(),
+        })
+    }
+
     fn handle_emit_log(&mut self, input: EmitLogInput) -> Result<EmitLogOutput, RuntimeError> {
         self.track.add_log(input.level, input.message);
 
         Ok(EmitLogOutput {})
     }
 
+    fn handle_emit_event(
+        &mut self,
+        input: EmitEventInput,
+    ) -> Result<EmitEventOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        let actor = wasm_process.vm.invocation.actor.clone();
+        let data = validate_data(&input.data).map_err(RuntimeError::DataValidationError)?;
+        check_data_context(&data, DataContext::Event)?;
+
+        re_debug!(
+            self,
+            "Emitting event: name = {}, data = {}",
+            input.name,
+            data
+        );
+        self.track.add_event(Event {
+            actor,
+            name: input.name,
+            data,
+        });
+
+        Ok(EmitEventOutput {})
+    }
+
     fn handle_get_package_address(
         &mut self,
         _input: GetPackageAddressInput,
@@ -2046,6 +3693,91 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
         })
     }
 
+    fn handle_generate_random(
+        &mut self,
+        input: GenerateRandomInput,
+    ) -> Result<GenerateRandomOutput, RuntimeError> {
+        if input.len > MAX_RANDOM_LEN {
+            return Err(RuntimeError::RandomLengthExceeded(input.len));
+        }
+
+        Ok(GenerateRandomOutput {
+            bytes: self.track.random_bytes(input.len as usize),
+        })
+    }
+
+    fn handle_verify_ecdsa(
+        &mut self,
+        input: VerifyEcdsaInput,
+    ) -> Result<VerifyEcdsaOutput, RuntimeError> {
+        Ok(VerifyEcdsaOutput {
+            is_valid: verify_ecdsa(&input.message, &input.signature, &input.public_key),
+        })
+    }
+
+    fn handle_get_well_known_address(
+        &mut self,
+        input: GetWellKnownAddressInput,
+    ) -> Result<GetWellKnownAddressOutput, RuntimeError> {
+        Ok(GetWellKnownAddressOutput {
+            address: self.track.address_book().resolve(input.name),
+        })
+    }
+
+    fn handle_schedule_call(
+        &mut self,
+        input: ScheduleCallInput,
+    ) -> Result<ScheduleCallOutput, RuntimeError> {
+        let wasm_process = self
+            .wasm_process_state
+            .as_ref()
+            .ok_or(RuntimeError::IllegalSystemCall())?;
+        // Only a component can schedule calls, on itself
+        let component_address = match wasm_process.vm.invocation.actor {
+            Actor::Component(address) => address,
+            Actor::Blueprint(..) => return Err(RuntimeError::IllegalSystemCall()),
+        };
+        if input.epoch <= self.track.current_epoch() {
+            return Err(RuntimeError::ScheduledEpochNotInFuture(input.epoch));
+        }
+        for arg in &input.args {
+            let data = validate_data(arg).map_err(RuntimeError::DataValidationError)?;
+            check_data_context(&data, DataContext::ScheduledArgument)?;
+        }
+
+        re_debug!(
+            self,
+            "Scheduling call: component = {}, method = {}, epoch = {}",
+            component_address,
+            input.method,
+            input.epoch
+        );
+        self.track.schedule_call(ScheduledCall {
+            epoch: input.epoch,
+            component_address,
+            method: input.method,
+            args: input.args,
+        });
+
+        Ok(ScheduleCallOutput {})
+    }
+
+    fn handle_batch_engine_calls(
+        &mut self,
+        input: BatchEngineCallsInput,
+    ) -> Result<BatchEngineCallsOutput, RuntimeError> {
+        // Each call is handled and charged as if it had been made on its own
+        let mut outputs = Vec::new();
+        for (op, call_input) in input.calls {
+            if op == BATCH_ENGINE_CALLS {
+                return Err(RuntimeError::NestedBatchEngineCalls);
+            }
+            outputs.push(self.dispatch(op, &call_input)?);
+        }
+
+        Ok(BatchEngineCallsOutput { outputs })
+    }
+
     fn handle_get_actor(&mut self, _input: GetActorInput) -> Result<GetActorOutput, RuntimeError> {
         let wasm_process = self
             .wasm_process_state
@@ -2061,6 +3793,13 @@ impl<'r, 'l, L: SubstateStore> Process<'r, 'l, L> {
     //============================
 }
 
+#[cfg(feature = "native")]
+impl<'r, 'l, L: SubstateStore> NativeSystem for Process<'r, 'l, L> {
+    fn handle_native_call(&mut self, op: u32, input: &[u8]) -> Result<Vec<u8>, RuntimeError> {
+        self.dispatch(op, input)
+    }
+}
+
 impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
     fn invoke_index(
         &mut self,
@@ -2069,94 +3808,24 @@ impl<'r, 'l, L: SubstateStore> Externals for Process<'r, 'l, L> {
     ) -> Result<Option<RuntimeValue>, Trap> {
         match index {
             ENGINE_FUNCTION_INDEX => {
-                let operation: u32 = args.nth_checked(0)?;
-                match operation {
-                    PUBLISH_PACKAGE => self.handle(args, Self::handle_publish),
-                    CALL_FUNCTION => self.handle(args, Self::handle_call_function),
-                    CALL_METHOD => self.handle(args, Self::handle_call_method),
-
-                    CREATE_COMPONENT => self.handle(args, Self::handle_create_component),
-                    GET_COMPONENT_INFO => self.handle(args, Self::handle_get_component_info),
-                    GET_COMPONENT_STATE => self.handle(args, Self::handle_get_component_state),
-                    PUT_COMPONENT_STATE => self.handle(args, Self::handle_put_component_state),
-
-                    CREATE_LAZY_MAP => self.handle(args, Self::handle_create_lazy_map),
-                    GET_LAZY_MAP_ENTRY => self.handle(args, Self::handle_get_lazy_map_entry),
-                    PUT_LAZY_MAP_ENTRY => self.handle(args, Self::handle_put_lazy_map_entry),
-
-                    CREATE_RESOURCE => self.handle(args, Self::handle_create_resource),
-                    GET_RESOURCE_TYPE => self.handle(args, Self::handle_get_resource_type),
-                    GET_RESOURCE_METADATA => self.handle(args, Self::handle_get_resource_metadata),
-                    GET_RESOURCE_TOTAL_SUPPLY => {
-                        self.handle(args, Self::handle_get_resource_total_supply)
-                    }
-                    GET_RESOURCE_FLAGS => self.handle(args, Self::handle_get_resource_flags),
-                    UPDATE_RESOURCE_FLAGS => self.handle(args, Self::handle_update_resource_flags),
-                    GET_RESOURCE_MUTABLE_FLAGS => {
-                        self.handle(args, Self::handle_get_resource_mutable_flags)
-                    }
-                    UPDATE_RESOURCE_MUTABLE_FLAGS => {
-                        self.handle(args, Self::handle_update_resource_mutable_flags)
-                    }
-                    MINT_RESOURCE => self.handle(args, Self::handle_mint_resource),
-                    BURN_RESOURCE => self.handle(args, Self::handle_burn_resource),
-                    UPDATE_NON_FUNGIBLE_MUTABLE_DATA => {
-                        self.handle(args, Self::handle_update_non_fungible_mutable_data)
-                    }
-                    GET_NON_FUNGIBLE_DATA => self.handle(args, Self::handle_get_non_fungible_data),
-                    UPDATE_RESOURCE_METADATA => {
-                        self.handle(args, Self::handle_update_resource_metadata)
-                    }
-
-                    CREATE_EMPTY_VAULT => self.handle(args, Self::handle_create_vault),
-                    PUT_INTO_VAULT => self.handle(args, Self::handle_put_into_vault),
-                    TAKE_FROM_VAULT => self.handle(args, Self::handle_take_from_vault),
-                    GET_VAULT_AMOUNT => self.handle(args, Self::handle_get_vault_amount),
-                    GET_VAULT_RESOURCE_ADDRESS => {
-                        self.handle(args, Self::handle_get_vault_resource_address)
-                    }
-                    TAKE_NON_FUNGIBLE_FROM_VAULT => {
-                        self.handle(args, Self::handle_take_non_fungible_from_vault)
-                    }
-                    GET_NON_FUNGIBLE_KEYS_IN_VAULT => {
-                        self.handle(args, Self::handle_get_non_fungible_keys_in_vault)
-                    }
-
-                    CREATE_EMPTY_BUCKET => self.handle(args, Self::handle_create_bucket),
-                    PUT_INTO_BUCKET => self.handle(args, Self::handle_put_into_bucket),
-                    TAKE_FROM_BUCKET => self.handle(args, Self::handle_take_from_bucket),
-                    GET_BUCKET_AMOUNT => self.handle(args, Self::handle_get_bucket_amount),
-                    GET_BUCKET_RESOURCE_ADDRESS => {
-                        self.handle(args, Self::handle_get_bucket_resource_address)
-                    }
-                    TAKE_NON_FUNGIBLE_FROM_BUCKET => {
-                        self.handle(args, Self::handle_take_non_fungible_from_bucket)
-                    }
-                    GET_NON_FUNGIBLE_KEYS_IN_BUCKET => {
-                        self.handle(args, Self::handle_get_non_fungible_keys_in_bucket)
-                    }
-
-                    CREATE_BUCKET_REF => self.handle(args, Self::handle_create_bucket_ref),
-                    DROP_BUCKET_REF => self.handle(args, Self::handle_drop_bucket_ref),
-                    GET_BUCKET_REF_AMOUNT => self.handle(args, Self::handle_get_bucket_ref_amount),
-                    GET_BUCKET_REF_RESOURCE_DEF => {
-                        self.handle(args, Self::handle_get_bucket_ref_resource_def)
-                    }
-                    GET_NON_FUNGIBLE_KEYS_IN_BUCKET_REF => {
-                        self.handle(args, Self::handle_get_non_fungible_keys_in_bucket_ref)
-                    }
-                    CLONE_BUCKET_REF => self.handle(args, Self::handle_clone_bucket_ref),
-
-                    EMIT_LOG => self.handle(args, Self::handle_emit_log),
-                    GET_PACKAGE_ADDRESS => self.handle(args, Self::handle_get_package_address),
-                    GET_CALL_DATA => self.handle(args, Self::handle_get_call_data),
-                    GET_TRANSACTION_HASH => self.handle(args, Self::handle_get_transaction_hash),
-                    GET_CURRENT_EPOCH => self.handle(args, Self::handle_get_current_epoch),
-                    GENERATE_UUID => self.handle(args, Self::handle_generate_uuid),
-                    GET_ACTOR => self.handle(args, Self::handle_get_actor),
-
-                    _ => Err(RuntimeError::InvalidRequestCode(operation).into()),
-                }
+                let op: u32 = args.nth_checked(0)?;
+                let input_ptr: u32 = args.nth_checked(1)?;
+                let input_len: u32 = args.nth_checked(2)?;
+                let (_, memory) = self.wasm_process_state.as_ref().unwrap().vm.wasm();
+                let input = memory
+                    .get(input_ptr, input_len as usize)
+                    .map_err(|e| Trap::from(RuntimeError::MemoryAccessError(e)))?;
+
+                let output = self.dispatch(op, &input).map_err(Trap::from)?;
+                let output_ptr = self.send_bytes(&output).map_err(Trap::from)?;
+                Ok(Some(RuntimeValue::I32(output_ptr)))
+            }
+            METERING_FUNCTION_INDEX => {
+                let instructions: u32 = args.nth_checked(0)?;
+                self.track
+                    .count_wasm_instructions(instructions)
+                    .map_err(Trap::from)?;
+                Ok(None)
             }
             _ => Err(RuntimeError::HostFunctionNotFound(index).into()),
         }
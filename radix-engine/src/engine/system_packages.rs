@@ -0,0 +1,44 @@
+use scrypto::types::*;
+
+use crate::engine::*;
+
+#[path = "../../../assets/system/src/lib.rs"]
+mod system;
+
+// `Account::new()` and `Escrow::new()` return components
+#[allow(clippy::new_ret_no_self)]
+#[path = "../../../assets/account/src/lib.rs"]
+mod account;
+
+/// Returns the system package, with the `System`, `Faucet` and `Staking` blueprints compiled
+/// natively from the same source as `assets/system.wasm`.
+pub fn system_package() -> NativePackage {
+    NativePackage::new()
+        .with_blueprint("System", system::System_main, system::System_abi)
+        .with_blueprint(
+            "Faucet",
+            system::faucet::Faucet_main,
+            system::faucet::Faucet_abi,
+        )
+        .with_blueprint(
+            "Staking",
+            system::staking::Staking_main,
+            system::staking::Staking_abi,
+        )
+}
+
+/// Returns the account package, with the `Account` and `Escrow` blueprints compiled natively
+/// from the same source as `assets/account.wasm`.
+pub fn account_package() -> NativePackage {
+    NativePackage::new()
+        .with_blueprint("Account", account::Account_main, account::Account_abi)
+        .with_blueprint("Escrow", account::Escrow_main, account::Escrow_abi)
+}
+
+/// Returns the natively compiled packages which the ledger is bootstrapped with, by address.
+pub fn system_packages() -> Vec<(Address, NativePackage)> {
+    vec![
+        (SYSTEM_PACKAGE, system_package()),
+        (ACCOUNT_PACKAGE, account_package()),
+    ]
+}
@@ -1,11 +1,15 @@
-use lru::LruCache;
+use sbor::Encode;
 use scrypto::engine::*;
 use scrypto::rust::collections::*;
+use scrypto::rust::mem;
 use scrypto::rust::string::String;
+use scrypto::rust::sync::Arc;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
+use scrypto::utils::*;
 use wasmi::*;
 
+use crate::engine::call_tracer::CallTracer;
 use crate::engine::*;
 use crate::ledger::*;
 use crate::model::*;
@@ -22,21 +26,58 @@ pub struct Track<'s, S: SubstateStore> {
     transaction_hash: H256,
     transaction_signers: Vec<EcdsaPublicKey>,
     id_allocator: IdAllocator,
+    random_counter: u32,
+    active_components: Vec<Address>,
+    package_limits: PackageLimits,
+    execution_limits: ExecutionLimits,
+    cost_table: CostTable,
+    cost_units: u32,
+    pending_cost: u32,
+    engine_call_limit: Option<u32>,
+    address_book: AddressBook,
+    wasm_engine: Arc<dyn WasmEngine>,
+    #[cfg(feature = "native")]
+    native_packages: HashMap<Address, NativePackage>,
     logs: Vec<(LogLevel, String)>,
+    events: Vec<Event>,
+    scheduled_calls: Vec<ScheduledCall>,
+    royalties_due: Vec<(Address, Decimal)>,
+    call_tracer: CallTracer,
     packages: HashMap<Address, Package>,
     components: HashMap<Address, Component>,
     resource_defs: HashMap<Address, ResourceDef>,
     lazy_maps: HashMap<(Address, Mid), LazyMap>,
-    vaults: HashMap<(Address, Vid), Vault>,
+    vaults: HashMap<(Address, VaultId), Vault>,
     non_fungibles: HashMap<(Address, NonFungibleKey), NonFungible>,
     updated_packages: HashSet<Address>,
     updated_components: HashSet<Address>,
     updated_lazy_maps: HashSet<(Address, Mid)>,
     updated_resource_defs: HashSet<Address>,
-    updated_vaults: HashSet<(Address, Vid)>,
+    updated_vaults: HashSet<(Address, VaultId)>,
     updated_non_fungibles: HashSet<(Address, NonFungibleKey)>,
     new_entities: Vec<Address>,
-    code_cache: LruCache<Address, Module>, // TODO: move to ledger level
+    new_vids: Vec<VaultId>,
+    code_hashes: HashMap<(Address, u32), H256>,
+}
+
+/// The state updates of a track at some point within a transaction, to which it can revert.
+pub struct Checkpoint {
+    events: usize,
+    scheduled_calls: usize,
+    new_entities: usize,
+    new_vids: usize,
+    packages: HashMap<Address, Package>,
+    components: HashMap<Address, Component>,
+    resource_defs: HashMap<Address, ResourceDef>,
+    lazy_maps: HashMap<(Address, Mid), LazyMap>,
+    vaults: HashMap<(Address, VaultId), Vault>,
+    non_fungibles: HashMap<(Address, NonFungibleKey), NonFungible>,
+    updated_packages: HashSet<Address>,
+    updated_components: HashSet<Address>,
+    updated_lazy_maps: HashSet<(Address, Mid)>,
+    updated_resource_defs: HashSet<Address>,
+    updated_vaults: HashSet<(Address, VaultId)>,
+    updated_non_fungibles: HashSet<(Address, NonFungibleKey)>,
 }
 
 impl<'s, S: SubstateStore> Track<'s, S> {
@@ -50,7 +91,23 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             transaction_hash,
             transaction_signers,
             id_allocator: IdAllocator::new(IdSpace::Application),
+            random_counter: 0,
+            active_components: Vec::new(),
+            package_limits: PackageLimits::default(),
+            execution_limits: ExecutionLimits::default(),
+            cost_table: CostTable::default(),
+            cost_units: 0,
+            pending_cost: 0,
+            engine_call_limit: None,
+            address_book: AddressBook::default(),
+            wasm_engine: default_wasm_engine(),
+            #[cfg(feature = "native")]
+            native_packages: HashMap::new(),
             logs: Vec::new(),
+            events: Vec::new(),
+            scheduled_calls: Vec::new(),
+            royalties_due: Vec::new(),
+            call_tracer: CallTracer::default(),
             packages: HashMap::new(),
             components: HashMap::new(),
             resource_defs: HashMap::new(),
@@ -64,8 +121,215 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             updated_vaults: HashSet::new(),
             updated_non_fungibles: HashSet::new(),
             new_entities: Vec::new(),
-            code_cache: LruCache::new(1024),
+            new_vids: Vec::new(),
+            code_hashes: HashMap::new(),
+        }
+    }
+
+    /// Overrides the limits on packages published within this transaction.
+    pub fn with_package_limits(mut self, limits: PackageLimits) -> Self {
+        self.package_limits = limits;
+        self
+    }
+
+    /// Returns the limits on packages published within this transaction.
+    pub fn package_limits(&self) -> &PackageLimits {
+        &self.package_limits
+    }
+
+    /// Overrides the limits on running this transaction.
+    pub fn with_execution_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.execution_limits = limits;
+        self
+    }
+
+    /// Returns the limits on running this transaction.
+    pub fn execution_limits(&self) -> &ExecutionLimits {
+        &self.execution_limits
+    }
+
+    /// Overrides the prices of what this transaction does.
+    pub fn with_cost_table(mut self, cost_table: CostTable) -> Self {
+        self.cost_table = cost_table;
+        self
+    }
+
+    /// Returns the prices of what this transaction does.
+    pub fn cost_table(&self) -> &CostTable {
+        &self.cost_table
+    }
+
+    /// Returns the cost units charged so far.
+    pub fn cost_units(&self) -> u32 {
+        self.cost_units
+    }
+
+    /// Charges cost units, along with those pending, failing once there are more than the
+    /// limit allows.
+    pub fn charge(&mut self, units: u32) -> Result<(), RuntimeError> {
+        self.cost_units = self
+            .cost_units
+            .saturating_add(units)
+            .saturating_add(self.pending_cost);
+        self.pending_cost = 0;
+        if self.cost_units > self.execution_limits.max_engine_calls {
+            return Err(RuntimeError::EngineCallLimitExceeded(
+                self.execution_limits.max_engine_calls,
+            ));
+        }
+        if let Some(limit) = self.engine_call_limit {
+            if self.cost_units > limit {
+                return Err(RuntimeError::EngineCallLimitExceeded(limit));
+            }
         }
+        Ok(())
+    }
+
+    /// Charges an engine call, at the price of its name.
+    pub fn count_engine_call(&mut self, name: &str) -> Result<(), RuntimeError> {
+        self.charge(self.cost_table.engine_call_price(name))
+    }
+
+    /// Charges WASM instructions which have been run.
+    pub fn count_wasm_instructions(&mut self, instructions: u32) -> Result<(), RuntimeError> {
+        self.charge(instructions.saturating_mul(self.cost_table.wasm_instruction))
+    }
+
+    /// Charges the bytes of each substate updated by the transaction, along with any cost
+    /// pending.
+    pub fn charge_state_writes(&mut self) -> Result<(), RuntimeError> {
+        if self.cost_table.state_write_byte == 0 {
+            return self.charge(0);
+        }
+        let packages = self
+            .updated_packages
+            .iter()
+            .filter_map(|address| self.packages.get(address).map(substate_size));
+        let components = self
+            .updated_components
+            .iter()
+            .filter_map(|address| self.components.get(address).map(substate_size));
+        let resource_defs = self
+            .updated_resource_defs
+            .iter()
+            .filter_map(|address| self.resource_defs.get(address).map(substate_size));
+        let lazy_maps = self
+            .updated_lazy_maps
+            .iter()
+            .filter_map(|id| self.lazy_maps.get(id).map(substate_size));
+        let vaults = self
+            .updated_vaults
+            .iter()
+            .filter_map(|id| self.vaults.get(id).map(substate_size));
+        let non_fungibles = self
+            .updated_non_fungibles
+            .iter()
+            .filter_map(|id| self.non_fungibles.get(id).map(substate_size));
+        let bytes: u64 = packages
+            .chain(components)
+            .chain(resource_defs)
+            .chain(lazy_maps)
+            .chain(vaults)
+            .chain(non_fungibles)
+            .sum();
+        self.charge(self.cost_table.state_write_cost(bytes))
+    }
+
+    /// Adds the bytes of a substate loaded from the ledger to the cost charged next.
+    fn charge_state_read<T: Encode>(&mut self, substate: &T) {
+        if self.cost_table.state_read_byte != 0 {
+            let cost = self.cost_table.state_read_cost(substate_size(substate));
+            self.pending_cost = self.pending_cost.saturating_add(cost);
+        }
+    }
+
+    /// Allows at most `max_engine_calls` more cost units, on top of any limit already set,
+    /// until `restore_engine_call_limit()` is called with the returned previous limit.
+    pub fn limit_engine_calls(&mut self, max_engine_calls: u32) -> Option<u32> {
+        let previous = self.engine_call_limit;
+        let limit = self.cost_units.saturating_add(max_engine_calls);
+        self.engine_call_limit = Some(previous.map_or(limit, |p| p.min(limit)));
+        previous
+    }
+
+    /// Restores the limit on engine calls replaced by `limit_engine_calls()`.
+    pub fn restore_engine_call_limit(&mut self, previous: Option<u32>) {
+        self.engine_call_limit = previous;
+    }
+
+    /// Takes a checkpoint of all state updates so far.
+    ///
+    /// Logs, the call trace and allocated IDs are not part of it, so that they stay unique
+    /// and describe everything that ran.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            events: self.events.len(),
+            scheduled_calls: self.scheduled_calls.len(),
+            new_entities: self.new_entities.len(),
+            new_vids: self.new_vids.len(),
+            packages: self.packages.clone(),
+            components: self.components.clone(),
+            resource_defs: self.resource_defs.clone(),
+            lazy_maps: self.lazy_maps.clone(),
+            vaults: self.vaults.clone(),
+            non_fungibles: self.non_fungibles.clone(),
+            updated_packages: self.updated_packages.clone(),
+            updated_components: self.updated_components.clone(),
+            updated_lazy_maps: self.updated_lazy_maps.clone(),
+            updated_resource_defs: self.updated_resource_defs.clone(),
+            updated_vaults: self.updated_vaults.clone(),
+            updated_non_fungibles: self.updated_non_fungibles.clone(),
+        }
+    }
+
+    /// Reverts all state updates made since the given checkpoint.
+    pub fn revert(&mut self, checkpoint: Checkpoint) {
+        self.events.truncate(checkpoint.events);
+        self.scheduled_calls.truncate(checkpoint.scheduled_calls);
+        self.new_entities.truncate(checkpoint.new_entities);
+        self.new_vids.truncate(checkpoint.new_vids);
+        self.packages = checkpoint.packages;
+        self.components = checkpoint.components;
+        self.resource_defs = checkpoint.resource_defs;
+        self.lazy_maps = checkpoint.lazy_maps;
+        self.vaults = checkpoint.vaults;
+        self.non_fungibles = checkpoint.non_fungibles;
+        self.updated_packages = checkpoint.updated_packages;
+        self.updated_components = checkpoint.updated_components;
+        self.updated_lazy_maps = checkpoint.updated_lazy_maps;
+        self.updated_resource_defs = checkpoint.updated_resource_defs;
+        self.updated_vaults = checkpoint.updated_vaults;
+        self.updated_non_fungibles = checkpoint.updated_non_fungibles;
+    }
+
+    /// Overrides the addresses that well-known names resolve to within this transaction.
+    pub fn with_address_book(mut self, address_book: AddressBook) -> Self {
+        self.address_book = address_book;
+        self
+    }
+
+    /// Returns the addresses that well-known names resolve to.
+    pub fn address_book(&self) -> &AddressBook {
+        &self.address_book
+    }
+
+    /// Overrides the runtime which runs the WASM code of packages within this transaction.
+    pub fn with_wasm_engine(mut self, wasm_engine: Arc<dyn WasmEngine>) -> Self {
+        self.wasm_engine = wasm_engine;
+        self
+    }
+
+    /// Sets the packages whose blueprints are compiled natively.
+    #[cfg(feature = "native")]
+    pub fn with_native_packages(mut self, packages: HashMap<Address, NativePackage>) -> Self {
+        self.native_packages = packages;
+        self
+    }
+
+    /// Returns the native package at the given address, if any.
+    #[cfg(feature = "native")]
+    pub fn native_package(&self, address: Address) -> Option<&NativePackage> {
+        self.native_packages.get(&address)
     }
 
     /// Start a process.
@@ -75,7 +339,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
             .transaction_signers
             .clone()
             .into_iter()
-            .map(|key| NonFungibleKey::new(key.to_vec()))
+            .map(|key| NonFungibleAddress::from_public_key(&key).key())
             .collect();
         let mut process = Process::new(0, verbose, self);
 
@@ -101,11 +365,36 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.ledger.get_epoch()
     }
 
+    /// Returns the nonce of the last committed transaction signed by the given key, if any.
+    pub fn signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64> {
+        self.ledger.get_signer_nonce(signer)
+    }
+
     /// Returns the logs collected so far.
     pub fn logs(&self) -> &Vec<(LogLevel, String)> {
         &self.logs
     }
 
+    /// Returns the events emitted so far.
+    pub fn events(&self) -> &Vec<Event> {
+        &self.events
+    }
+
+    /// Returns the call frames traced so far.
+    pub fn call_trace(&self) -> &[CallFrame] {
+        self.call_tracer.frames()
+    }
+
+    /// Starts tracing a call frame, nested in the current one if any.
+    pub fn begin_call_frame(&mut self, kind: CallKind, input_size: usize) {
+        self.call_tracer.begin(kind, input_size);
+    }
+
+    /// Ends tracing the current call frame.
+    pub fn end_call_frame(&mut self, output_size: usize) {
+        self.call_tracer.end(output_size);
+    }
+
     /// Returns new entities created so far.
     pub fn new_entities(&self) -> &[Address] {
         &self.new_entities
@@ -116,21 +405,65 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.logs.push((level, message));
     }
 
-    /// Loads a module.
-    pub fn load_module(&mut self, address: Address) -> Option<(ModuleRef, MemoryRef)> {
-        match self.get_package(address).map(Clone::clone) {
-            Some(p) => {
-                if let Some(m) = self.code_cache.get(&address) {
-                    Some(instantiate_module(m).unwrap())
-                } else {
-                    let module = parse_module(p.code()).unwrap();
-                    let inst = instantiate_module(&module).unwrap();
-                    self.code_cache.put(address, module);
-                    Some(inst)
-                }
-            }
-            None => None,
-        }
+    /// Adds an event.
+    pub fn add_event(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    /// Schedules a method call, which is added to the ledger scheduler on commit.
+    pub fn schedule_call(&mut self, call: ScheduledCall) {
+        self.scheduled_calls.push(call);
+    }
+
+    /// Records a royalty owed for a call made by a blueprint, which the transaction pays
+    /// once the instruction being run returns.
+    ///
+    /// It's not part of checkpoints, so calls that are reverted are still paid for.
+    pub fn add_royalty_due(&mut self, address: Address, amount: Decimal) {
+        self.royalties_due.push((address, amount));
+    }
+
+    /// Takes the royalties owed so far.
+    pub fn take_royalties_due(&mut self) -> Vec<(Address, Decimal)> {
+        mem::take(&mut self.royalties_due)
+    }
+
+    /// Loads a module, of the given package version or the latest if none.
+    pub fn load_module(
+        &mut self,
+        address: Address,
+        version: Option<u32>,
+    ) -> Result<(ModuleRef, MemoryRef), RuntimeError> {
+        let latest = self
+            .get_package(address)
+            .ok_or(RuntimeError::PackageNotFound(address))?
+            .version();
+        let version = version.unwrap_or(latest);
+
+        // The package has just been loaded, so its code can be borrowed alongside the engine
+        let code = self
+            .packages
+            .get(&address)
+            .and_then(|p| p.code_at(version))
+            .ok_or(RuntimeError::PackageVersionNotFound(address, version))?;
+        // Metered code differs from the package code, and so does its hash
+        let metered;
+        let code = if self.cost_table.wasm_instruction != 0 {
+            metered = inject_metering(code).map_err(RuntimeError::WasmValidationError)?;
+            &metered[..]
+        } else {
+            code
+        };
+        // Hashes are kept, so that repeated calls into the same package don't hash the code
+        // again before looking it up in the engine cache.
+        let code_hash = *self
+            .code_hashes
+            .entry((address, version))
+            .or_insert_with(|| sha256(code));
+        Ok(self
+            .wasm_engine
+            .instantiate(address, code_hash, code)
+            .unwrap())
     }
 
     /// Returns an immutable reference to a package, if exists.
@@ -140,6 +473,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(package) = self.ledger.get_package(address) {
+            self.charge_state_read(&package);
             self.packages.insert(address, package);
             self.packages.get(&address)
         } else {
@@ -157,6 +491,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(package) = self.ledger.get_package(address) {
+            self.charge_state_read(&package);
             self.packages.insert(address, package);
             self.packages.get_mut(&address)
         } else {
@@ -178,6 +513,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(component) = self.ledger.get_component(address) {
+            self.charge_state_read(&component);
             self.components.insert(address, component);
             self.components.get(&address)
         } else {
@@ -193,6 +529,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(component) = self.ledger.get_component(address) {
+            self.charge_state_read(&component);
             self.components.insert(address, component);
             self.components.get_mut(&address)
         } else {
@@ -221,6 +558,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(non_fungible) = self.ledger.get_non_fungible(resource_address, key) {
+            self.charge_state_read(&non_fungible);
             self.non_fungibles
                 .insert((resource_address, key.clone()), non_fungible);
             self.non_fungibles.get(&(resource_address, key.clone()))
@@ -246,6 +584,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(non_fungible) = self.ledger.get_non_fungible(resource_address, key) {
+            self.charge_state_read(&non_fungible);
             self.non_fungibles
                 .insert((resource_address, key.clone()), non_fungible);
             self.non_fungibles.get_mut(&(resource_address, key.clone()))
@@ -277,6 +616,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(lazy_map) = self.ledger.get_lazy_map(component_address, mid) {
+            self.charge_state_read(&lazy_map);
             self.lazy_maps.insert(lazy_map_id, lazy_map);
             self.lazy_maps.get(&lazy_map_id)
         } else {
@@ -298,6 +638,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(lazy_map) = self.ledger.get_lazy_map(component_address, mid) {
+            self.charge_state_read(&lazy_map);
             self.lazy_maps.insert(lazy_map_id, lazy_map);
             self.lazy_maps.get_mut(&lazy_map_id)
         } else {
@@ -319,6 +660,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(resource_def) = self.ledger.get_resource_def(address) {
+            self.charge_state_read(&resource_def);
             self.resource_defs.insert(address, resource_def);
             self.resource_defs.get(&address)
         } else {
@@ -336,6 +678,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(resource_def) = self.ledger.get_resource_def(address) {
+            self.charge_state_read(&resource_def);
             self.resource_defs.insert(address, resource_def);
             self.resource_defs.get_mut(&address)
         } else {
@@ -351,7 +694,11 @@ impl<'s, S: SubstateStore> Track<'s, S> {
     }
 
     /// Returns a mutable reference to a vault, if exists.
-    pub fn get_vault_mut(&mut self, component_address: &Address, vid: &Vid) -> Option<&mut Vault> {
+    pub fn get_vault_mut(
+        &mut self,
+        component_address: &Address,
+        vid: &VaultId,
+    ) -> Option<&mut Vault> {
         let vault_id = (component_address.clone(), vid.clone());
         self.updated_vaults.insert(vault_id.clone());
 
@@ -360,6 +707,7 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
 
         if let Some(vault) = self.ledger.get_vault(component_address, vid) {
+            self.charge_state_read(&vault);
             self.vaults.insert(vault_id, vault);
             self.vaults.get_mut(&vault_id)
         } else {
@@ -367,13 +715,43 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         }
     }
 
+    /// Returns the total amount of a resource held by a component, including pending updates.
+    pub fn balance(&self, component_address: Address, resource_address: Address) -> Decimal {
+        let mut balance = Decimal::zero();
+        let mut loaded = HashSet::new();
+        for ((address, vid), vault) in &self.vaults {
+            if *address == component_address {
+                loaded.insert(*vid);
+                if vault.resource_address() == resource_address {
+                    balance += vault.amount();
+                }
+            }
+        }
+        for (vid, vault) in self.ledger.get_vaults(&component_address) {
+            if !loaded.contains(&vid) && vault.resource_address() == resource_address {
+                balance += vault.amount();
+            }
+        }
+        balance
+    }
+
     /// Inserts a new vault.
-    pub fn put_vault(&mut self, component_address: Address, vid: Vid, vault: Vault) {
+    pub fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault) {
         let vault_id = (component_address, vid);
         self.updated_vaults.insert(vault_id);
         self.vaults.insert(vault_id, vault);
     }
 
+    /// Checks that every vault created in this transaction is owned by a component, so that
+    /// no resources are committed without a way to reach them.
+    pub fn check_vaults(&self) -> Result<(), RuntimeError> {
+        let owned_vids: HashSet<VaultId> = self.vaults.keys().map(|(_, vid)| *vid).collect();
+        match self.new_vids.iter().find(|vid| !owned_vids.contains(vid)) {
+            Some(vid) => Err(RuntimeError::DanglingVault(*vid)),
+            None => Ok(()),
+        }
+    }
+
     /// Creates a new package address.
     pub fn new_package_address(&mut self) -> Address {
         // Security Alert: ensure ID allocating will practically never fail
@@ -410,14 +788,50 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.id_allocator.new_uuid(self.transaction_hash()).unwrap()
     }
 
+    /// Generates pseudo-random bytes, deterministically derived from the transaction hash.
+    ///
+    /// A separate counter is used, so that generating random bytes does not change the
+    /// addresses of entities created afterwards.
+    pub fn random_bytes(&mut self, len: usize) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(len);
+        while bytes.len() < len {
+            let mut data = self.transaction_hash.as_ref().to_vec();
+            data.extend(b"random");
+            data.extend(self.random_counter.to_le_bytes());
+            self.random_counter += 1;
+
+            let block = sha256_twice(data);
+            let n = (len - bytes.len()).min(block.as_ref().len());
+            bytes.extend(&block.as_ref()[..n]);
+        }
+        bytes
+    }
+
+    /// Returns whether the component has a method running on the call stack.
+    pub fn is_component_active(&self, address: Address) -> bool {
+        self.active_components.contains(&address)
+    }
+
+    /// Marks a component as being called into.
+    pub fn enter_component(&mut self, address: Address) {
+        self.active_components.push(address);
+    }
+
+    /// Marks the innermost component call as returned.
+    pub fn exit_component(&mut self) {
+        self.active_components.pop();
+    }
+
     /// Creates a new bucket ID.
-    pub fn new_bid(&mut self) -> Bid {
+    pub fn new_bid(&mut self) -> BucketId {
         self.id_allocator.new_bid().unwrap()
     }
 
     /// Creates a new vault ID.
-    pub fn new_vid(&mut self) -> Vid {
-        self.id_allocator.new_vid(self.transaction_hash()).unwrap()
+    pub fn new_vid(&mut self) -> VaultId {
+        let vid = self.id_allocator.new_vid(self.transaction_hash()).unwrap();
+        self.new_vids.push(vid);
+        vid
     }
 
     /// Creates a new reference id.
@@ -430,8 +844,121 @@ impl<'s, S: SubstateStore> Track<'s, S> {
         self.id_allocator.new_mid(self.transaction_hash()).unwrap()
     }
 
-    /// Commits changes to the underlying ledger.
+    /// Returns the pending state updates as a diff against the ledger.
+    pub fn diff(&self) -> StateDiff {
+        let mut balance_changes: Vec<BalanceChange> = Vec::new();
+        for (component_address, vid) in &self.updated_vaults {
+            let vault = self.vaults.get(&(*component_address, *vid)).unwrap();
+            let before = self
+                .ledger
+                .get_vault(component_address, vid)
+                .map(|v| v.amount())
+                .unwrap_or_else(Decimal::zero);
+            let delta = vault.amount() - before;
+
+            match balance_changes.iter_mut().find(|c| {
+                c.component_address == *component_address
+                    && c.resource_address == vault.resource_address()
+            }) {
+                Some(change) => change.delta += delta,
+                None => balance_changes.push(BalanceChange {
+                    component_address: *component_address,
+                    resource_address: vault.resource_address(),
+                    delta,
+                }),
+            }
+        }
+        balance_changes.retain(|c| !c.delta.is_zero());
+        balance_changes
+            .sort_by_key(|c| (c.component_address.to_vec(), c.resource_address.to_vec()));
+
+        let mut supply_changes: Vec<SupplyChange> = Vec::new();
+        for address in &self.updated_resource_defs {
+            let before = self
+                .ledger
+                .get_resource_def(*address)
+                .map(|r| r.total_supply())
+                .unwrap_or_else(Decimal::zero);
+            let delta = self.resource_defs.get(address).unwrap().total_supply() - before;
+            if !delta.is_zero() {
+                supply_changes.push(SupplyChange {
+                    resource_address: *address,
+                    delta,
+                });
+            }
+        }
+        supply_changes.sort_by_key(|c| c.resource_address.to_vec());
+
+        StateDiff {
+            new_entities: self.new_entities.clone(),
+            balance_changes,
+            supply_changes,
+            storage_changes: self.storage_changes(),
+        }
+    }
+
+    /// Returns the net change of the bytes taken by each component, against the ledger.
+    fn storage_changes(&self) -> Vec<StorageChange> {
+        let mut deltas: HashMap<Address, i64> = HashMap::new();
+        for address in &self.updated_components {
+            let after = substate_size(self.components.get(address).unwrap());
+            let before = self
+                .ledger
+                .get_component(*address)
+                .map(|c| substate_size(&c))
+                .unwrap_or(0);
+            *deltas.entry(*address).or_default() += after as i64 - before as i64;
+        }
+        for (component_address, mid) in &self.updated_lazy_maps {
+            let after = substate_size(self.lazy_maps.get(&(*component_address, *mid)).unwrap());
+            let before = self
+                .ledger
+                .get_lazy_map(component_address, mid)
+                .map(|m| substate_size(&m))
+                .unwrap_or(0);
+            *deltas.entry(*component_address).or_default() += after as i64 - before as i64;
+        }
+        for (component_address, vid) in &self.updated_vaults {
+            let after = substate_size(self.vaults.get(&(*component_address, *vid)).unwrap());
+            let before = self
+                .ledger
+                .get_vault(component_address, vid)
+                .map(|v| substate_size(&v))
+                .unwrap_or(0);
+            *deltas.entry(*component_address).or_default() += after as i64 - before as i64;
+        }
+
+        let mut storage_changes: Vec<StorageChange> = deltas
+            .into_iter()
+            .filter(|(_, delta)| *delta != 0)
+            .map(|(component_address, delta)| StorageChange {
+                component_address,
+                delta,
+            })
+            .collect();
+        storage_changes.sort_by_key(|c| c.component_address.to_vec());
+        storage_changes
+    }
+
+    /// Commits changes to the underlying ledger, as one commit of the ledger.
     pub fn commit(&mut self) {
+        self.commit_with(|_| {})
+    }
+
+    /// Commits changes to the underlying ledger, followed by the writes of `finish`, e.g. to
+    /// advance the ledger nonce, as one commit of the ledger.
+    pub fn commit_with<F: FnOnce(&mut S)>(&mut self, finish: F) {
+        self.ledger.begin_commit();
+
+        // measured before any substate is overwritten
+        for change in self.storage_changes() {
+            let footprint = self.ledger.get_storage_footprint(&change.component_address) as i64;
+            self.ledger.put_storage_footprint(
+                change.component_address,
+                (footprint + change.delta).max(0) as u64,
+            );
+        }
+
         for address in self.updated_packages.clone() {
             self.ledger
                 .put_package(address, self.packages.get(&address).unwrap().clone());
@@ -471,5 +998,16 @@ impl<'s, S: SubstateStore> Track<'s, S> {
                     .clone(),
             );
         }
+
+        if !self.scheduled_calls.is_empty() {
+            let mut scheduler = self.ledger.get_scheduler();
+            for call in self.scheduled_calls.clone() {
+                scheduler.schedule(call);
+            }
+            self.ledger.put_scheduler(scheduler);
+        }
+
+        finish(self.ledger);
+        self.ledger.end_commit();
     }
 }
@@ -0,0 +1,119 @@
+#[cfg(not(feature = "alloc"))]
+use lru::LruCache;
+use scrypto::rust::sync::Arc;
+#[cfg(not(feature = "alloc"))]
+use scrypto::rust::sync::Mutex;
+use scrypto::types::*;
+use wasmi::*;
+
+use crate::engine::*;
+use crate::model::*;
+
+/// A WASM runtime, which turns package code into instances for processes to run.
+pub trait WasmEngine: Send + Sync {
+    /// Instantiates the code of a package.
+    ///
+    /// The code is identified by the package address and its hash, so that the work of
+    /// parsing and validating it can be reused, even across transactions.
+    fn instantiate(
+        &self,
+        package_address: Address,
+        code_hash: H256,
+        code: &[u8],
+    ) -> Result<(ModuleRef, MemoryRef), WasmValidationError>;
+}
+
+/// Parsed modules, by package address and code hash.
+#[cfg(not(feature = "alloc"))]
+type ModuleCache = LruCache<(Address, H256), Arc<Module>>;
+
+/// The `wasmi` interpreter.
+///
+/// Parsed modules are kept in a cache shared by clones of the engine. In `alloc` builds,
+/// which have no locks, modules are parsed on every call instead.
+#[derive(Clone)]
+pub struct WasmiEngine {
+    #[cfg(not(feature = "alloc"))]
+    modules: Arc<Mutex<ModuleCache>>,
+}
+
+impl WasmiEngine {
+    /// Creates an engine, with room for the given number of modules in its cache.
+    pub fn new(capacity: usize) -> Self {
+        #[cfg(feature = "alloc")]
+        let _ = capacity;
+        Self {
+            #[cfg(not(feature = "alloc"))]
+            modules: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// Returns the number of modules in the cache.
+    pub fn cached_modules(&self) -> usize {
+        #[cfg(not(feature = "alloc"))]
+        return self.modules.lock().expect("Poisoned module cache").len();
+        #[cfg(feature = "alloc")]
+        0
+    }
+}
+
+impl Default for WasmiEngine {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl WasmEngine for WasmiEngine {
+    #[cfg(not(feature = "alloc"))]
+    fn instantiate(
+        &self,
+        package_address: Address,
+        code_hash: H256,
+        code: &[u8],
+    ) -> Result<(ModuleRef, MemoryRef), WasmValidationError> {
+        let key = (package_address, code_hash);
+        // The lock isn't held while parsing, so a module may occasionally be parsed twice
+        let cached = self
+            .modules
+            .lock()
+            .expect("Poisoned module cache")
+            .get(&key)
+            .cloned();
+        let module = match cached {
+            Some(module) => module,
+            None => {
+                let module = Arc::new(parse_module(code)?);
+                self.modules
+                    .lock()
+                    .expect("Poisoned module cache")
+                    .put(key, module.clone());
+                module
+            }
+        };
+        instantiate_module(&module)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn instantiate(
+        &self,
+        _package_address: Address,
+        _code_hash: H256,
+        code: &[u8],
+    ) -> Result<(ModuleRef, MemoryRef), WasmValidationError> {
+        instantiate_module(&parse_module(code)?)
+    }
+}
+
+/// Returns the engine used by tracks unless overridden, whose cache is shared by all of
+/// them within the process.
+pub fn default_wasm_engine() -> Arc<dyn WasmEngine> {
+    #[cfg(not(feature = "alloc"))]
+    {
+        static ENGINE: std::sync::OnceLock<Arc<WasmiEngine>> = std::sync::OnceLock::new();
+        ENGINE
+            .get_or_init(|| Arc::new(WasmiEngine::default()))
+            .clone()
+    }
+    #[cfg(feature = "alloc")]
+    Arc::new(WasmiEngine::default())
+}
@@ -0,0 +1,122 @@
+use parity_wasm::elements::*;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
+
+use crate::engine::METERING_FUNCTION_NAME;
+use crate::model::WasmValidationError;
+
+/// Instruments a WASM module so that it reports the instructions it runs, by calling the
+/// metering function of the engine with the number of instructions of each block of code
+/// before running it.
+///
+/// Blocks end at control flow instructions, so that a block always runs to its end unless it
+/// traps, and the count of a block is reported even if it ends with a branch.
+pub fn inject_metering(code: &[u8]) -> Result<Vec<u8>, WasmValidationError> {
+    let invalid =
+        |e: Error| WasmValidationError::InvalidModule(wasmi::Error::Validation(e.to_string()));
+    let mut module: Module = parity_wasm::deserialize_buffer(code).map_err(invalid)?;
+
+    // The metering function is imported after all other functions, which shifts the index of
+    // every function the module defines by one
+    let metering_index = module.import_count(ImportCountType::Function) as u32;
+    let metering_type = add_metering_type(&mut module).map_err(invalid)?;
+    if module.import_section().is_none() {
+        module
+            .insert_section(Section::Import(ImportSection::with_entries(Vec::new())))
+            .map_err(invalid)?;
+    }
+    let imports = module.import_section_mut().unwrap().entries_mut();
+    let position = imports
+        .iter()
+        .rposition(|entry| matches!(entry.external(), External::Function(_)))
+        .map_or(0, |i| i + 1);
+    imports.insert(
+        position,
+        ImportEntry::new(
+            "env".to_string(),
+            METERING_FUNCTION_NAME.to_string(),
+            External::Function(metering_type),
+        ),
+    );
+    let shift = |index: &mut u32| {
+        if *index >= metering_index {
+            *index += 1;
+        }
+    };
+
+    if let Some(section) = module.code_section_mut() {
+        for body in section.bodies_mut() {
+            let instructions = body.code_mut().elements_mut();
+            for instruction in instructions.iter_mut() {
+                if let Instruction::Call(index) = instruction {
+                    shift(index);
+                }
+            }
+            *instructions = meter_blocks(instructions, metering_index);
+        }
+    }
+    if let Some(section) = module.export_section_mut() {
+        for entry in section.entries_mut() {
+            if let Internal::Function(index) = entry.internal_mut() {
+                shift(index);
+            }
+        }
+    }
+    if let Some(section) = module.elements_section_mut() {
+        for segment in section.entries_mut() {
+            segment.members_mut().iter_mut().for_each(shift);
+        }
+    }
+    if let Some(mut start) = module.start_section() {
+        shift(&mut start);
+        module.set_start_section(start);
+    }
+    // Function names would be off by one
+    module.clear_custom_section("name");
+
+    module.to_bytes().map_err(invalid)
+}
+
+/// Returns the index of the type of the metering function, adding it if missing.
+fn add_metering_type(module: &mut Module) -> Result<u32, Error> {
+    if module.type_section().is_none() {
+        module.insert_section(Section::Type(TypeSection::with_types(Vec::new())))?;
+    }
+    let types = module.type_section_mut().unwrap().types_mut();
+    let metering_type = Type::Function(FunctionType::new(vec![ValueType::I32], Vec::new()));
+    Ok(match types.iter().position(|t| *t == metering_type) {
+        Some(index) => index as u32,
+        None => {
+            types.push(metering_type);
+            types.len() as u32 - 1
+        }
+    })
+}
+
+/// Prefixes each block of the given code with a call reporting its number of instructions.
+fn meter_blocks(instructions: &[Instruction], metering_index: u32) -> Vec<Instruction> {
+    let mut metered = Vec::with_capacity(instructions.len() * 2);
+    let mut start = 0;
+    for (i, instruction) in instructions.iter().enumerate() {
+        let ends_block = matches!(
+            instruction,
+            Instruction::Block(_)
+                | Instruction::Loop(_)
+                | Instruction::If(_)
+                | Instruction::Else
+                | Instruction::End
+                | Instruction::Br(_)
+                | Instruction::BrIf(_)
+                | Instruction::BrTable(_)
+                | Instruction::Return
+        );
+        if ends_block || i + 1 == instructions.len() {
+            metered.push(Instruction::I32Const((i + 1 - start) as i32));
+            metered.push(Instruction::Call(metering_index));
+            metered.extend_from_slice(&instructions[start..=i]);
+            start = i + 1;
+        }
+    }
+    metered
+}
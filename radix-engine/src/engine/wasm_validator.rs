@@ -1,17 +1,111 @@
+use parity_wasm::elements;
+use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec::Vec;
 use wasmi::*;
 
 use crate::engine::*;
 use crate::model::*;
 
+/// Limits on the size of a WASM module, which are enforced when publishing a package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackageLimits {
+    /// The maximum size of the code, in bytes.
+    pub max_code_size: usize,
+    /// The maximum number of exports.
+    pub max_exports: usize,
+    /// The maximum number of functions defined by the module.
+    pub max_functions: usize,
+}
+
+impl Default for PackageLimits {
+    fn default() -> Self {
+        Self {
+            max_code_size: 4 * 1024 * 1024,
+            max_exports: 1024,
+            max_functions: 32 * 1024,
+        }
+    }
+}
+
 /// Parses a WASM module.
 pub fn parse_module(code: &[u8]) -> Result<Module, WasmValidationError> {
     Module::from_buffer(code).map_err(WasmValidationError::InvalidModule)
 }
 
-/// Validates a WASM module.
+/// Returns the names of the blueprints in a WASM module, i.e. those with an ABI export.
+pub fn blueprint_names(code: &[u8]) -> Vec<String> {
+    export_names_with_suffix(code, "_abi")
+}
+
+/// Returns the names of the blueprints a WASM module depends on, i.e. those imported with
+/// `import!`.
+pub fn dependency_names(code: &[u8]) -> Vec<String> {
+    export_names_with_suffix(code, "_dependency")
+}
+
+fn export_names_with_suffix(code: &[u8], suffix: &str) -> Vec<String> {
+    parity_wasm::deserialize_buffer::<elements::Module>(code)
+        .ok()
+        .and_then(|module| {
+            module.export_section().map(|section| {
+                section
+                    .entries()
+                    .iter()
+                    .filter_map(|entry| entry.field().strip_suffix(suffix))
+                    .map(ToString::to_string)
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Validates a WASM module, with the default package limits.
 pub fn validate_module(code: &[u8]) -> Result<(), WasmValidationError> {
+    validate_module_with_limits(code, &PackageLimits::default())
+}
+
+/// Validates a WASM module.
+pub fn validate_module_with_limits(
+    code: &[u8],
+    limits: &PackageLimits,
+) -> Result<(), WasmValidationError> {
+    // Check code size before parsing
+    if code.len() > limits.max_code_size {
+        return Err(WasmValidationError::PackageTooLarge {
+            limit: limits.max_code_size,
+            actual: code.len(),
+        });
+    }
+
     // Parse
-    let parsed = parse_module(code)?;
+    let elements: elements::Module =
+        parity_wasm::deserialize_buffer(code).map_err(|e: elements::Error| {
+            WasmValidationError::InvalidModule(Error::Validation(e.to_string()))
+        })?;
+
+    // Check exports and functions
+    let exports = elements
+        .export_section()
+        .map_or(0, |section| section.entries().len());
+    if exports > limits.max_exports {
+        return Err(WasmValidationError::TooManyExports {
+            limit: limits.max_exports,
+            actual: exports,
+        });
+    }
+    let functions = elements
+        .function_section()
+        .map_or(0, |section| section.entries().len());
+    if functions > limits.max_functions {
+        return Err(WasmValidationError::TooManyFunctions {
+            limit: limits.max_functions,
+            actual: functions,
+        });
+    }
+
+    let parsed =
+        Module::from_parity_wasm_module(elements).map_err(WasmValidationError::InvalidModule)?;
 
     // check floating point
     parsed
@@ -0,0 +1,220 @@
+use scrypto::buffer::*;
+use scrypto::rust::collections::HashSet;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::ledger::*;
+use crate::model::*;
+
+/// Identifies a substate of a ledger.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubstateId {
+    Package(Address),
+    Component(Address),
+    ResourceDef(Address),
+    LazyMap(Address, Mid),
+    Vault(Address, VaultId),
+    NonFungible(Address, NonFungibleKey),
+    StorageFootprint(Address),
+    SignerNonce(EcdsaPublicKey),
+    Scheduler,
+}
+
+/// The value of a substate.
+#[derive(Debug, Clone)]
+pub enum Substate {
+    Package(Package),
+    Component(Component),
+    ResourceDef(ResourceDef),
+    LazyMap(LazyMap),
+    Vault(Vault),
+    NonFungible(NonFungible),
+    StorageFootprint(u64),
+    SignerNonce(u64),
+    Scheduler(Scheduler),
+}
+
+/// A ledger whose substates can be listed, so that it can be diffed against another.
+pub trait ListableSubstateStore: SubstateStore {
+    /// Returns the IDs of all substates, in no particular order.
+    fn list_substates(&self) -> Vec<SubstateId>;
+}
+
+/// Represents an error when applying a diff to a ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// Substates can't be removed from a ledger.
+    CannotRemove(SubstateId),
+    /// The nonce of a ledger can't go back.
+    CannotDecreaseNonce { current: u64, target: u64 },
+}
+
+/// The substate-level differences between two ledgers, which turn the first into the second.
+#[derive(Debug, Clone, Default)]
+pub struct SubstateDiff {
+    /// Substates only the second ledger has, with their values.
+    pub created: Vec<(SubstateId, Substate)>,
+    /// Substates whose value differs, with their values in the second ledger.
+    pub updated: Vec<(SubstateId, Substate)>,
+    /// Substates only the first ledger has.
+    pub removed: Vec<SubstateId>,
+    /// The epoch of the second ledger, if different.
+    pub epoch: Option<u64>,
+    /// The nonce of the second ledger, if different.
+    pub nonce: Option<u64>,
+}
+
+impl SubstateDiff {
+    /// Diffs two ledgers, e.g. a ledger and a snapshot taken before running a transaction.
+    ///
+    /// Substates are compared by their encoding.
+    pub fn between<A: ListableSubstateStore, B: ListableSubstateStore>(from: &A, to: &B) -> Self {
+        let mut diff = Self::default();
+
+        let from_ids: HashSet<SubstateId> = from.list_substates().into_iter().collect();
+        let to_ids: HashSet<SubstateId> = to.list_substates().into_iter().collect();
+        for id in &to_ids {
+            let new = get_substate(to, id).expect("Listed substate not found");
+            if !from_ids.contains(id) {
+                diff.created.push((id.clone(), new));
+            } else {
+                let old = get_substate(from, id).expect("Listed substate not found");
+                if encode_substate(&old) != encode_substate(&new) {
+                    diff.updated.push((id.clone(), new));
+                }
+            }
+        }
+        diff.removed = from_ids.difference(&to_ids).cloned().collect();
+
+        if from.get_epoch() != to.get_epoch() {
+            diff.epoch = Some(to.get_epoch());
+        }
+        if from.get_nonce() != to.get_nonce() {
+            diff.nonce = Some(to.get_nonce());
+        }
+        diff
+    }
+
+    /// Returns whether the ledgers are the same.
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.epoch.is_none()
+            && self.nonce.is_none()
+    }
+
+    /// Returns the IDs of all created, updated and removed substates.
+    pub fn changed_substates(&self) -> Vec<&SubstateId> {
+        self.created
+            .iter()
+            .chain(self.updated.iter())
+            .map(|(id, _)| id)
+            .chain(self.removed.iter())
+            .collect()
+    }
+
+    /// Applies this diff to a ledger, as a patch.
+    ///
+    /// Nothing is written unless the whole diff can be applied.
+    pub fn apply<S: SubstateStore>(&self, ledger: &mut S) -> Result<(), PatchError> {
+        if let Some(id) = self.removed.first() {
+            return Err(PatchError::CannotRemove(id.clone()));
+        }
+        if let Some(nonce) = self.nonce {
+            if nonce < ledger.get_nonce() {
+                return Err(PatchError::CannotDecreaseNonce {
+                    current: ledger.get_nonce(),
+                    target: nonce,
+                });
+            }
+        }
+
+        ledger.begin_commit();
+        for (id, substate) in self.created.iter().chain(self.updated.iter()) {
+            put_substate(ledger, id, substate.clone());
+        }
+        if let Some(epoch) = self.epoch {
+            ledger.set_epoch(epoch);
+        }
+        if let Some(nonce) = self.nonce {
+            while ledger.get_nonce() < nonce {
+                ledger.increase_nonce();
+            }
+        }
+        ledger.end_commit();
+        Ok(())
+    }
+}
+
+/// Reads a substate of a ledger.
+pub fn get_substate<S: SubstateStore>(ledger: &S, id: &SubstateId) -> Option<Substate> {
+    match id {
+        SubstateId::Package(address) => ledger.get_package(*address).map(Substate::Package),
+        SubstateId::Component(address) => ledger.get_component(*address).map(Substate::Component),
+        SubstateId::ResourceDef(address) => {
+            ledger.get_resource_def(*address).map(Substate::ResourceDef)
+        }
+        SubstateId::LazyMap(address, mid) => {
+            ledger.get_lazy_map(address, mid).map(Substate::LazyMap)
+        }
+        SubstateId::Vault(address, vid) => ledger.get_vault(address, vid).map(Substate::Vault),
+        SubstateId::NonFungible(address, key) => ledger
+            .get_non_fungible(*address, key)
+            .map(Substate::NonFungible),
+        SubstateId::StorageFootprint(address) => Some(Substate::StorageFootprint(
+            ledger.get_storage_footprint(address),
+        )),
+        SubstateId::SignerNonce(signer) => {
+            ledger.get_signer_nonce(signer).map(Substate::SignerNonce)
+        }
+        SubstateId::Scheduler => Some(Substate::Scheduler(ledger.get_scheduler())),
+    }
+}
+
+/// Writes a substate to a ledger.
+///
+/// # Panics
+/// Panics if the substate doesn't match the kind of ID.
+pub fn put_substate<S: SubstateStore>(ledger: &mut S, id: &SubstateId, substate: Substate) {
+    match (id, substate) {
+        (SubstateId::Package(address), Substate::Package(p)) => ledger.put_package(*address, p),
+        (SubstateId::Component(address), Substate::Component(c)) => {
+            ledger.put_component(*address, c)
+        }
+        (SubstateId::ResourceDef(address), Substate::ResourceDef(r)) => {
+            ledger.put_resource_def(*address, r)
+        }
+        (SubstateId::LazyMap(address, mid), Substate::LazyMap(m)) => {
+            ledger.put_lazy_map(*address, *mid, m)
+        }
+        (SubstateId::Vault(address, vid), Substate::Vault(v)) => {
+            ledger.put_vault(*address, *vid, v)
+        }
+        (SubstateId::NonFungible(address, key), Substate::NonFungible(n)) => {
+            ledger.put_non_fungible(*address, key, n)
+        }
+        (SubstateId::StorageFootprint(address), Substate::StorageFootprint(size)) => {
+            ledger.put_storage_footprint(*address, size)
+        }
+        (SubstateId::SignerNonce(signer), Substate::SignerNonce(nonce)) => {
+            ledger.put_signer_nonce(*signer, nonce)
+        }
+        (SubstateId::Scheduler, Substate::Scheduler(s)) => ledger.put_scheduler(s),
+        (id, substate) => panic!("Substate {:?} doesn't match ID {:?}", substate, id),
+    }
+}
+
+fn encode_substate(substate: &Substate) -> Vec<u8> {
+    match substate {
+        Substate::Package(p) => scrypto_encode(p),
+        Substate::Component(c) => scrypto_encode(c),
+        Substate::ResourceDef(r) => scrypto_encode(r),
+        Substate::LazyMap(m) => scrypto_encode(m),
+        Substate::Vault(v) => scrypto_encode(v),
+        Substate::NonFungible(n) => scrypto_encode(n),
+        Substate::StorageFootprint(size) => scrypto_encode(size),
+        Substate::SignerNonce(nonce) => scrypto_encode(nonce),
+        Substate::Scheduler(s) => scrypto_encode(s),
+    }
+}
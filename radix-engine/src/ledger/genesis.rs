@@ -0,0 +1,140 @@
+use scrypto::rust::collections::*;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::engine::validate_module;
+use crate::model::*;
+
+/// A fungible resource created at genesis, whose whole supply is held by genesis accounts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisResource {
+    pub address: Address,
+    pub divisibility: u8,
+    pub metadata: HashMap<String, String>,
+}
+
+/// An account created at genesis, owned by the given key.
+///
+/// XRD balances are taken from the system component, and balances of genesis resources are
+/// minted for the account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisAccount {
+    pub address: Address,
+    pub public_key: EcdsaPublicKey,
+    pub balances: Vec<(Address, Decimal)>,
+}
+
+/// A package published at genesis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenesisPackage {
+    pub address: Address,
+    pub code: Vec<u8>,
+}
+
+/// Specifies the entities a ledger is bootstrapped with, on top of the system packages,
+/// components and resources.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Genesis {
+    pub resources: Vec<GenesisResource>,
+    pub accounts: Vec<GenesisAccount>,
+    pub packages: Vec<GenesisPackage>,
+}
+
+/// Represents an error when bootstrapping a store with a genesis specification.
+#[derive(Debug)]
+pub enum GenesisError {
+    /// The address is not of the kind of entity created at it.
+    InvalidAddress(Address),
+    /// The address is used by another entity.
+    AddressAlreadyUsed(Address),
+    /// A balance is of a resource which is neither XRD nor a genesis resource.
+    ResourceDefNotFound(Address),
+    /// An account has more than one balance of the same resource.
+    DuplicateBalance(Address, Address),
+    /// The genesis accounts hold more XRD than the system component has.
+    NotEnoughXrd {
+        required: Decimal,
+        available: Decimal,
+    },
+    ResourceDefError(Address, ResourceDefError),
+    InvalidPackage(Address, WasmValidationError),
+}
+
+impl Genesis {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the total amount of a resource held by genesis accounts.
+    pub fn total_balance(&self, resource_address: Address) -> Decimal {
+        self.accounts
+            .iter()
+            .flat_map(|account| &account.balances)
+            .filter(|(address, _)| *address == resource_address)
+            .map(|(_, amount)| amount)
+            .sum()
+    }
+
+    /// Checks the entities are at addresses of the right kind, which are unique, and the
+    /// balances are of known resources.
+    ///
+    /// Conflicts with the system entities and the XRD supply are checked when bootstrapping.
+    pub fn validate(&self) -> Result<(), GenesisError> {
+        let mut addresses = HashSet::new();
+        let entities = self
+            .resources
+            .iter()
+            .map(|r| (r.address, r.address.is_resource_def()))
+            .chain(
+                self.accounts
+                    .iter()
+                    .map(|a| (a.address, a.address.is_component())),
+            )
+            .chain(
+                self.packages
+                    .iter()
+                    .map(|p| (p.address, p.address.is_package())),
+            );
+        for (address, valid) in entities {
+            if !valid {
+                return Err(GenesisError::InvalidAddress(address));
+            }
+            if !addresses.insert(address) {
+                return Err(GenesisError::AddressAlreadyUsed(address));
+            }
+        }
+
+        for account in &self.accounts {
+            let mut resources = HashSet::new();
+            for (resource_address, amount) in &account.balances {
+                if !resources.insert(*resource_address) {
+                    return Err(GenesisError::DuplicateBalance(
+                        account.address,
+                        *resource_address,
+                    ));
+                }
+                if *resource_address != RADIX_TOKEN
+                    && !self
+                        .resources
+                        .iter()
+                        .any(|r| r.address == *resource_address)
+                {
+                    return Err(GenesisError::ResourceDefNotFound(*resource_address));
+                }
+                if amount.is_negative() {
+                    return Err(GenesisError::ResourceDefError(
+                        *resource_address,
+                        ResourceDefError::InvalidAmount(*amount),
+                    ));
+                }
+            }
+        }
+
+        for package in &self.packages {
+            validate_module(&package.code)
+                .map_err(|e| GenesisError::InvalidPackage(package.address, e))?;
+        }
+        Ok(())
+    }
+}
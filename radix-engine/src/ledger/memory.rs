@@ -1,4 +1,5 @@
 use scrypto::rust::collections::HashMap;
+use scrypto::rust::vec::Vec;
 use scrypto::types::*;
 
 use crate::ledger::*;
@@ -11,10 +12,37 @@ pub struct InMemorySubstateStore {
     components: HashMap<Address, Component>,
     lazy_maps: HashMap<(Address, Mid), LazyMap>,
     resource_defs: HashMap<Address, ResourceDef>,
-    vaults: HashMap<(Address, Vid), Vault>,
+    vaults: HashMap<(Address, VaultId), Vault>,
     non_fungibles: HashMap<(Address, NonFungibleKey), NonFungible>,
+    storage_footprints: HashMap<Address, u64>,
+    signer_nonces: HashMap<EcdsaPublicKey, u64>,
+    scheduler: Option<Scheduler>,
     current_epoch: u64,
     nonce: u64,
+    history: Option<History>,
+}
+
+/// Prior versions of substates, each list ordered by version.
+#[derive(Debug, Clone, Default)]
+struct History {
+    components: HashMap<Address, Vec<(u64, Component)>>,
+    vaults: HashMap<(Address, VaultId), Vec<(u64, Vault)>>,
+}
+
+fn record<T>(versions: &mut Vec<(u64, T)>, version: u64, value: T) {
+    match versions.last_mut() {
+        Some((v, last)) if *v == version => *last = value,
+        _ => versions.push((version, value)),
+    }
+}
+
+fn lookup<T: Clone>(versions: &[(u64, T)], version: u64) -> Option<T> {
+    let n = versions.partition_point(|(v, _)| *v <= version);
+    if n == 0 {
+        None
+    } else {
+        Some(versions[n - 1].1.clone())
+    }
 }
 
 impl InMemorySubstateStore {
@@ -26,16 +54,37 @@ impl InMemorySubstateStore {
             resource_defs: HashMap::new(),
             vaults: HashMap::new(),
             non_fungibles: HashMap::new(),
+            storage_footprints: HashMap::new(),
+            signer_nonces: HashMap::new(),
+            scheduler: None,
             current_epoch: 0,
             nonce: 0,
+            history: None,
         }
     }
 
+    /// Retains prior versions of components and vaults, for `get_component_at()` and
+    /// `get_vault_at()`.
+    ///
+    /// Only substates written after this call are recorded, so it should be enabled before
+    /// bootstrapping.
+    pub fn with_history(mut self) -> Self {
+        self.history = Some(History::default());
+        self
+    }
+
     pub fn with_bootstrap() -> Self {
         let mut ledger = Self::new();
         ledger.bootstrap();
         ledger
     }
+
+    /// Creates a store bootstrapped with the entities of a genesis specification.
+    pub fn with_genesis(genesis: &Genesis) -> Result<Self, GenesisError> {
+        let mut ledger = Self::new();
+        ledger.bootstrap_with_genesis(genesis)?;
+        Ok(ledger)
+    }
 }
 
 impl Default for InMemorySubstateStore {
@@ -66,6 +115,13 @@ impl SubstateStore for InMemorySubstateStore {
     }
 
     fn put_component(&mut self, address: Address, component: Component) {
+        if let Some(history) = &mut self.history {
+            record(
+                history.components.entry(address).or_default(),
+                self.nonce,
+                component.clone(),
+            );
+        }
         self.components.insert(address, component);
     }
 
@@ -79,16 +135,31 @@ impl SubstateStore for InMemorySubstateStore {
         self.lazy_maps.insert((component_address, mid), lazy_map);
     }
 
-    fn get_vault(&self, component_address: &Address, vid: &Vid) -> Option<Vault> {
+    fn get_vault(&self, component_address: &Address, vid: &VaultId) -> Option<Vault> {
         self.vaults
             .get(&(component_address.clone(), vid.clone()))
             .map(Clone::clone)
     }
 
-    fn put_vault(&mut self, component_address: Address, vid: Vid, vault: Vault) {
+    fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault) {
+        if let Some(history) = &mut self.history {
+            record(
+                history.vaults.entry((component_address, vid)).or_default(),
+                self.nonce,
+                vault.clone(),
+            );
+        }
         self.vaults.insert((component_address, vid), vault);
     }
 
+    fn get_vaults(&self, component_address: &Address) -> Vec<(VaultId, Vault)> {
+        self.vaults
+            .iter()
+            .filter(|((address, _), _)| address == component_address)
+            .map(|((_, vid), vault)| (*vid, vault.clone()))
+            .collect()
+    }
+
     fn get_non_fungible(
         &self,
         resource_address: Address,
@@ -109,6 +180,70 @@ impl SubstateStore for InMemorySubstateStore {
             .insert((resource_address, key.clone()), non_fungible);
     }
 
+    fn scan_components(
+        &self,
+        package_address: Address,
+        cursor: Option<Address>,
+        limit: usize,
+    ) -> Page<Address, Component> {
+        let components = self
+            .components
+            .iter()
+            .filter(|(_, component)| component.package_address() == package_address)
+            .map(|(address, component)| (*address, component.clone()));
+        Page::from_entries(components, cursor.as_ref(), limit)
+    }
+
+    fn scan_vaults(
+        &self,
+        component_address: Address,
+        cursor: Option<VaultId>,
+        limit: usize,
+    ) -> Page<VaultId, Vault> {
+        Page::from_entries(self.get_vaults(&component_address), cursor.as_ref(), limit)
+    }
+
+    fn scan_non_fungibles(
+        &self,
+        resource_address: Address,
+        cursor: Option<NonFungibleKey>,
+        limit: usize,
+    ) -> Page<NonFungibleKey, NonFungible> {
+        let non_fungibles = self
+            .non_fungibles
+            .iter()
+            .filter(|((address, _), _)| *address == resource_address)
+            .map(|((_, key), non_fungible)| (key.clone(), non_fungible.clone()));
+        Page::from_entries(non_fungibles, cursor.as_ref(), limit)
+    }
+
+    fn get_storage_footprint(&self, component_address: &Address) -> u64 {
+        self.storage_footprints
+            .get(component_address)
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    fn put_storage_footprint(&mut self, component_address: Address, size: u64) {
+        self.storage_footprints.insert(component_address, size);
+    }
+
+    fn get_signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64> {
+        self.signer_nonces.get(signer).cloned()
+    }
+
+    fn put_signer_nonce(&mut self, signer: EcdsaPublicKey, nonce: u64) {
+        self.signer_nonces.insert(signer, nonce);
+    }
+
+    fn get_scheduler(&self) -> Scheduler {
+        self.scheduler.clone().unwrap_or_default()
+    }
+
+    fn put_scheduler(&mut self, scheduler: Scheduler) {
+        self.scheduler = Some(scheduler);
+    }
+
     fn get_epoch(&self) -> u64 {
         self.current_epoch
     }
@@ -124,4 +259,66 @@ impl SubstateStore for InMemorySubstateStore {
     fn increase_nonce(&mut self) {
         self.nonce += 1;
     }
+
+    fn get_component_at(&self, address: Address, version: u64) -> Option<Component> {
+        self.history
+            .as_ref()
+            .and_then(|h| h.components.get(&address))
+            .and_then(|versions| lookup(versions, version))
+    }
+
+    fn get_vault_at(
+        &self,
+        component_address: &Address,
+        vid: &VaultId,
+        version: u64,
+    ) -> Option<Vault> {
+        self.history
+            .as_ref()
+            .and_then(|h| h.vaults.get(&(*component_address, *vid)))
+            .and_then(|versions| lookup(versions, version))
+    }
+}
+
+impl ListableSubstateStore for InMemorySubstateStore {
+    fn list_substates(&self) -> Vec<SubstateId> {
+        let packages = self.packages.keys().map(|a| SubstateId::Package(*a));
+        let components = self.components.keys().map(|a| SubstateId::Component(*a));
+        let resource_defs = self
+            .resource_defs
+            .keys()
+            .map(|a| SubstateId::ResourceDef(*a));
+        let lazy_maps = self
+            .lazy_maps
+            .keys()
+            .map(|(a, mid)| SubstateId::LazyMap(*a, *mid));
+        let vaults = self
+            .vaults
+            .keys()
+            .map(|(a, vid)| SubstateId::Vault(*a, *vid));
+        let non_fungibles = self
+            .non_fungibles
+            .keys()
+            .map(|(a, key)| SubstateId::NonFungible(*a, key.clone()));
+        let storage_footprints = self
+            .storage_footprints
+            .keys()
+            .map(|a| SubstateId::StorageFootprint(*a));
+        let signer_nonces = self
+            .signer_nonces
+            .keys()
+            .map(|k| SubstateId::SignerNonce(*k));
+        // Listed once written, so that it's never seen as removed
+        let scheduler = self.scheduler.iter().map(|_| SubstateId::Scheduler);
+        packages
+            .chain(components)
+            .chain(resource_defs)
+            .chain(lazy_maps)
+            .chain(vaults)
+            .chain(non_fungibles)
+            .chain(storage_footprints)
+            .chain(signer_nonces)
+            .chain(scheduler)
+            .collect()
+    }
 }
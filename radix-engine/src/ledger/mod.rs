@@ -1,5 +1,18 @@
+mod diff;
+mod genesis;
 mod memory;
+mod query;
+#[cfg(not(feature = "alloc"))]
+mod shared;
 mod traits;
 
+pub use diff::{
+    get_substate, put_substate, ListableSubstateStore, PatchError, Substate, SubstateDiff,
+    SubstateId,
+};
+pub use genesis::{Genesis, GenesisAccount, GenesisError, GenesisPackage, GenesisResource};
 pub use memory::InMemorySubstateStore;
-pub use traits::SubstateStore;
+pub use query::{ComponentInfo, LedgerQuery, ResourceInfo, VaultContent};
+#[cfg(not(feature = "alloc"))]
+pub use shared::{SharedInMemorySubstateStore, SubstateStoreView};
+pub use traits::{substate_size, BootstrapError, Page, SubstateStore};
@@ -0,0 +1,171 @@
+use scrypto::engine::ResourceType;
+use scrypto::resource::{
+    DESCRIPTION_METADATA_KEY, ICON_URL_METADATA_KEY, NAME_METADATA_KEY, SYMBOL_METADATA_KEY,
+};
+use scrypto::rust::collections::*;
+use scrypto::rust::string::{String, ToString};
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::engine::validate_data;
+use crate::ledger::*;
+use crate::model::*;
+
+/// A component, as seen from outside of the engine.
+#[derive(Debug, Clone)]
+pub struct ComponentInfo {
+    pub address: Address,
+    pub package_address: Address,
+    pub blueprint_name: String,
+    /// The package version the component is pinned to, if any.
+    pub package_version: Option<u32>,
+    pub royalty: Option<Royalty>,
+    /// The SBOR-encoded component state.
+    pub state: Vec<u8>,
+}
+
+/// A resource definition, as seen from outside of the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceInfo {
+    pub address: Address,
+    pub resource_type: ResourceType,
+    pub metadata: HashMap<String, String>,
+    pub flags: u64,
+    pub mutable_flags: u64,
+    pub authorities: HashMap<Address, u64>,
+    pub total_supply: Decimal,
+}
+
+impl ResourceInfo {
+    /// Returns the `symbol` metadata, e.g. `XRD`, if set.
+    pub fn symbol(&self) -> Option<&str> {
+        self.metadata.get(SYMBOL_METADATA_KEY).map(String::as_str)
+    }
+
+    /// Returns the `name` metadata, if set.
+    pub fn name(&self) -> Option<&str> {
+        self.metadata.get(NAME_METADATA_KEY).map(String::as_str)
+    }
+
+    /// Returns the `description` metadata, if set.
+    pub fn description(&self) -> Option<&str> {
+        self.metadata
+            .get(DESCRIPTION_METADATA_KEY)
+            .map(String::as_str)
+    }
+
+    /// Returns the `icon_url` metadata, if set.
+    pub fn icon_url(&self) -> Option<&str> {
+        self.metadata.get(ICON_URL_METADATA_KEY).map(String::as_str)
+    }
+}
+
+/// The content of a vault owned by a component.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultContent {
+    pub vid: VaultId,
+    pub resource_address: Address,
+    pub amount: Decimal,
+    /// The keys of the non-fungibles in the vault, empty for fungible resources.
+    pub non_fungible_keys: BTreeSet<NonFungibleKey>,
+    pub frozen: bool,
+}
+
+/// Answers queries about the state of a substate store with typed models, so that embedders
+/// don't have to decode substates themselves.
+pub struct LedgerQuery<'s, S: SubstateStore> {
+    store: &'s S,
+}
+
+impl<'s, S: SubstateStore> LedgerQuery<'s, S> {
+    pub fn new(store: &'s S) -> Self {
+        Self { store }
+    }
+
+    /// Returns the component at the given address.
+    pub fn component(&self, address: Address) -> Option<ComponentInfo> {
+        let component = self.store.get_component(address)?;
+        Some(ComponentInfo {
+            address,
+            package_address: component.package_address(),
+            blueprint_name: component.blueprint_name().to_string(),
+            package_version: component.package_version(),
+            royalty: component.royalty().cloned(),
+            state: component.state().to_vec(),
+        })
+    }
+
+    /// Returns the resource definition at the given address.
+    pub fn resource(&self, address: Address) -> Option<ResourceInfo> {
+        let resource_def = self.store.get_resource_def(address)?;
+        Some(ResourceInfo {
+            address,
+            resource_type: resource_def.resource_type(),
+            metadata: resource_def.metadata().clone(),
+            flags: resource_def.flags(),
+            mutable_flags: resource_def.mutable_flags(),
+            authorities: resource_def.authorities().clone(),
+            total_supply: resource_def.total_supply(),
+        })
+    }
+
+    /// Returns the lazy maps and vaults reachable from the state of a component.
+    pub fn owned_substates(&self, address: Address) -> Option<(HashSet<Mid>, HashSet<VaultId>)> {
+        let component = self.store.get_component(address)?;
+        let state = validate_data(component.state()).unwrap();
+        let mut maps = HashSet::new();
+        let mut vaults: HashSet<VaultId> = state.vaults.iter().cloned().collect();
+
+        // Traverse lazy maps using BFS
+        let mut queue: Vec<Mid> = state.lazy_maps.clone();
+        let mut i = 0;
+        while i < queue.len() {
+            let mid = queue[i];
+            i += 1;
+            if !maps.insert(mid) {
+                continue;
+            }
+            if let Some(map) = self.store.get_lazy_map(&address, &mid) {
+                for (k, v) in map.map() {
+                    // Keys of an ordered store are not SBOR-encoded
+                    let key = validate_data(k).ok();
+                    for data in key.into_iter().chain([validate_data(v).unwrap()]) {
+                        queue.extend(data.lazy_maps);
+                        vaults.extend(data.vaults);
+                    }
+                }
+            }
+        }
+
+        Some((maps, vaults))
+    }
+
+    /// Returns the content of the vault with the given id.
+    pub fn vault(&self, component_address: Address, vid: VaultId) -> Option<VaultContent> {
+        let vault = self.store.get_vault(&component_address, &vid)?;
+        let non_fungible_keys = match vault.total_supply() {
+            Supply::NonFungible { keys } => keys,
+            Supply::Fungible { .. } => BTreeSet::new(),
+        };
+        Some(VaultContent {
+            vid,
+            resource_address: vault.resource_address(),
+            amount: vault.amount(),
+            non_fungible_keys,
+            frozen: vault.is_frozen(),
+        })
+    }
+
+    /// Returns the content of the vaults reachable from the state of a component.
+    ///
+    /// Vault IDs are not ordered, so vaults are sorted by their string form.
+    pub fn vaults(&self, address: Address) -> Option<Vec<VaultContent>> {
+        let (_, vids) = self.owned_substates(address)?;
+        let mut vaults: Vec<VaultContent> = vids
+            .into_iter()
+            .filter_map(|vid| self.vault(address, vid))
+            .collect();
+        vaults.sort_by_key(|vault| vault.vid.to_string());
+        Some(vaults)
+    }
+}
@@ -0,0 +1,300 @@
+use std::ops::Deref;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, RwLock, RwLockReadGuard};
+
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::ledger::*;
+use crate::model::*;
+
+/// An in-memory ledger which can be shared by threads, e.g. parallel test harnesses.
+///
+/// Clones share the same state. Writes aren't isolated from each other, so executors running
+/// on clones at the same time should touch disjoint substates; use `fork()` to get a store
+/// of their own instead.
+///
+/// A fork copies the state lazily, on its first write, so forking is cheap.
+///
+/// Queries running alongside an executor, e.g. of a gateway, should read from a `view()`,
+/// which only ever has whole transactions.
+#[derive(Debug, Clone, Default)]
+pub struct SharedInMemorySubstateStore {
+    state: Arc<RwLock<Arc<InMemorySubstateStore>>>,
+    commits: Arc<(Mutex<Commits>, Condvar)>,
+}
+
+/// The commits of a shared store, where a write outside of a commit is a commit of its own.
+#[derive(Debug, Default)]
+struct Commits {
+    sequence: u64,
+    in_progress: bool,
+}
+
+/// A read-only view of the committed state of a `SharedInMemorySubstateStore`, which isn't
+/// affected by later writes.
+///
+/// Holding a view makes the next write to the store copy its state.
+#[derive(Debug, Clone)]
+pub struct SubstateStoreView {
+    sequence: u64,
+    state: Arc<InMemorySubstateStore>,
+}
+
+impl SubstateStoreView {
+    /// Returns the number of commits made to the store before this view was taken.
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+}
+
+impl Deref for SubstateStoreView {
+    type Target = InMemorySubstateStore;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+impl SharedInMemorySubstateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bootstrap() -> Self {
+        InMemorySubstateStore::with_bootstrap().into()
+    }
+
+    /// Creates a store bootstrapped with the entities of a genesis specification.
+    pub fn with_genesis(genesis: &Genesis) -> Result<Self, GenesisError> {
+        InMemorySubstateStore::with_genesis(genesis).map(Into::into)
+    }
+
+    /// Returns a store starting from the current state of this one, which diverges from it
+    /// as either is written.
+    pub fn fork(&self) -> Self {
+        let view = self.view();
+        Self {
+            state: Arc::new(RwLock::new(view.state)),
+            commits: Arc::new((
+                Mutex::new(Commits {
+                    sequence: view.sequence,
+                    in_progress: false,
+                }),
+                Condvar::new(),
+            )),
+        }
+    }
+
+    /// Returns a copy of the last committed state.
+    pub fn snapshot(&self) -> InMemorySubstateStore {
+        self.view().state.as_ref().clone()
+    }
+
+    /// Returns a view of the last committed state, waiting for the commit in progress, if
+    /// any, to end.
+    pub fn view(&self) -> SubstateStoreView {
+        let (_, ended) = self.commits.as_ref();
+        let commits = ended
+            .wait_while(self.commits(), |commits| commits.in_progress)
+            .expect("Poisoned ledger lock");
+        SubstateStoreView {
+            sequence: commits.sequence,
+            state: self.read().clone(),
+        }
+    }
+
+    /// Returns the number of commits made to this store.
+    pub fn sequence(&self) -> u64 {
+        self.commits().sequence
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Arc<InMemorySubstateStore>> {
+        self.state.read().expect("Poisoned ledger lock")
+    }
+
+    fn commits(&self) -> MutexGuard<'_, Commits> {
+        self.commits.0.lock().expect("Poisoned ledger lock")
+    }
+
+    fn write<F: FnOnce(&mut InMemorySubstateStore)>(&mut self, f: F) {
+        // Held until written, so that views are taken either before or after
+        let mut commits = self.commits();
+        let mut state = self.state.write().expect("Poisoned ledger lock");
+        f(Arc::make_mut(&mut state));
+        if !commits.in_progress {
+            commits.sequence += 1;
+        }
+    }
+}
+
+impl From<InMemorySubstateStore> for SharedInMemorySubstateStore {
+    fn from(store: InMemorySubstateStore) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(Arc::new(store))),
+            commits: Arc::default(),
+        }
+    }
+}
+
+impl SubstateStore for SharedInMemorySubstateStore {
+    fn get_resource_def(&self, address: Address) -> Option<ResourceDef> {
+        self.read().get_resource_def(address)
+    }
+
+    fn put_resource_def(&mut self, address: Address, resource_def: ResourceDef) {
+        self.write(|s| s.put_resource_def(address, resource_def))
+    }
+
+    fn get_package(&self, address: Address) -> Option<Package> {
+        self.read().get_package(address)
+    }
+
+    fn put_package(&mut self, address: Address, package: Package) {
+        self.write(|s| s.put_package(address, package))
+    }
+
+    fn get_component(&self, address: Address) -> Option<Component> {
+        self.read().get_component(address)
+    }
+
+    fn put_component(&mut self, address: Address, component: Component) {
+        self.write(|s| s.put_component(address, component))
+    }
+
+    fn get_lazy_map(&self, component_address: &Address, mid: &Mid) -> Option<LazyMap> {
+        self.read().get_lazy_map(component_address, mid)
+    }
+
+    fn put_lazy_map(&mut self, component_address: Address, mid: Mid, lazy_map: LazyMap) {
+        self.write(|s| s.put_lazy_map(component_address, mid, lazy_map))
+    }
+
+    fn get_vault(&self, component_address: &Address, vid: &VaultId) -> Option<Vault> {
+        self.read().get_vault(component_address, vid)
+    }
+
+    fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault) {
+        self.write(|s| s.put_vault(component_address, vid, vault))
+    }
+
+    fn get_vaults(&self, component_address: &Address) -> Vec<(VaultId, Vault)> {
+        self.read().get_vaults(component_address)
+    }
+
+    fn get_non_fungible(
+        &self,
+        resource_address: Address,
+        key: &NonFungibleKey,
+    ) -> Option<NonFungible> {
+        self.read().get_non_fungible(resource_address, key)
+    }
+
+    fn put_non_fungible(
+        &mut self,
+        resource_address: Address,
+        key: &NonFungibleKey,
+        non_fungible: NonFungible,
+    ) {
+        self.write(|s| s.put_non_fungible(resource_address, key, non_fungible))
+    }
+
+    fn scan_components(
+        &self,
+        package_address: Address,
+        cursor: Option<Address>,
+        limit: usize,
+    ) -> Page<Address, Component> {
+        self.read().scan_components(package_address, cursor, limit)
+    }
+
+    fn scan_vaults(
+        &self,
+        component_address: Address,
+        cursor: Option<VaultId>,
+        limit: usize,
+    ) -> Page<VaultId, Vault> {
+        self.read().scan_vaults(component_address, cursor, limit)
+    }
+
+    fn scan_non_fungibles(
+        &self,
+        resource_address: Address,
+        cursor: Option<NonFungibleKey>,
+        limit: usize,
+    ) -> Page<NonFungibleKey, NonFungible> {
+        self.read()
+            .scan_non_fungibles(resource_address, cursor, limit)
+    }
+
+    fn get_storage_footprint(&self, component_address: &Address) -> u64 {
+        self.read().get_storage_footprint(component_address)
+    }
+
+    fn put_storage_footprint(&mut self, component_address: Address, size: u64) {
+        self.write(|s| s.put_storage_footprint(component_address, size))
+    }
+
+    fn get_signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64> {
+        self.read().get_signer_nonce(signer)
+    }
+
+    fn put_signer_nonce(&mut self, signer: EcdsaPublicKey, nonce: u64) {
+        self.write(|s| s.put_signer_nonce(signer, nonce))
+    }
+
+    fn get_scheduler(&self) -> Scheduler {
+        self.read().get_scheduler()
+    }
+
+    fn put_scheduler(&mut self, scheduler: Scheduler) {
+        self.write(|s| s.put_scheduler(scheduler))
+    }
+
+    fn get_epoch(&self) -> u64 {
+        self.read().get_epoch()
+    }
+
+    fn set_epoch(&mut self, epoch: u64) {
+        self.write(|s| s.set_epoch(epoch))
+    }
+
+    fn get_nonce(&self) -> u64 {
+        self.read().get_nonce()
+    }
+
+    fn increase_nonce(&mut self) {
+        self.write(|s| s.increase_nonce())
+    }
+
+    fn begin_commit(&mut self) {
+        self.commits().in_progress = true;
+    }
+
+    fn end_commit(&mut self) {
+        let mut commits = self.commits();
+        if commits.in_progress {
+            commits.in_progress = false;
+            commits.sequence += 1;
+            self.commits.1.notify_all();
+        }
+    }
+
+    fn get_component_at(&self, address: Address, version: u64) -> Option<Component> {
+        self.read().get_component_at(address, version)
+    }
+
+    fn get_vault_at(
+        &self,
+        component_address: &Address,
+        vid: &VaultId,
+        version: u64,
+    ) -> Option<Vault> {
+        self.read().get_vault_at(component_address, vid, version)
+    }
+}
+
+impl ListableSubstateStore for SharedInMemorySubstateStore {
+    fn list_substates(&self) -> Vec<SubstateId> {
+        self.read().list_substates()
+    }
+}
@@ -1,24 +1,154 @@
 use sbor::*;
 use scrypto::buffer::*;
 use scrypto::engine::*;
+use scrypto::resource::{DESCRIPTION_METADATA_KEY, NAME_METADATA_KEY, SYMBOL_METADATA_KEY};
 use scrypto::rust::borrow::ToOwned;
 use scrypto::rust::collections::*;
+use scrypto::rust::vec;
+use scrypto::rust::vec::Vec;
 use scrypto::types::*;
+use scrypto::utils::*;
 
+use crate::ledger::{Genesis, GenesisError};
 use crate::model::*;
 
+const SYSTEM_PACKAGE_CODE: &[u8] = include_bytes!("../../../assets/system.wasm");
+const ACCOUNT_PACKAGE_CODE: &[u8] = include_bytes!("../../../assets/account.wasm");
+
 const XRD_SYMBOL: &str = "XRD";
 const XRD_NAME: &str = "Radix";
 const XRD_DESCRIPTION: &str = "The Radix Public Network's native token, used to pay the network's required transaction fees and to secure the network through staking to its validator nodes.";
 const XRD_URL: &str = "https://tokens.radixdlt.com";
 const XRD_MAX_SUPPLY: i128 = 24_000_000_000_000i128;
-const XRD_VAULT_ID: Vid = Vid(H256([0u8; 32]), 0);
+const XRD_VAULT_ID: VaultId = VaultId::new(H256([0u8; 32]), 0);
 
 const SYSTEM_COMPONENT_NAME: &str = "System";
 
+const FAUCET_COMPONENT_NAME: &str = "Faucet";
+const FAUCET_SUPPLY: i128 = 1_000_000_000i128;
+const FAUCET_AMOUNT: i128 = 10_000i128;
+const FAUCET_COOLDOWN: u64 = 1;
+const FAUCET_VAULT_ID: VaultId = VaultId::new(H256([0u8; 32]), 1);
+const FAUCET_LAST_CLAIMS_ID: Mid = Mid(H256([0u8; 32]), 2);
+
+const STAKING_COMPONENT_NAME: &str = "Staking";
+const STAKING_UNSTAKE_DELAY: u64 = 2;
+const STAKING_VAULT_ID: VaultId = VaultId::new(H256([0u8; 32]), 3);
+const STAKING_STAKES_ID: Mid = Mid(H256([0u8; 32]), 4);
+const STAKING_UNSTAKES_ID: Mid = Mid(H256([0u8; 32]), 5);
+
+const ACCOUNT_BLUEPRINT_NAME: &str = "Account";
+/// The `Account` methods marked `#[auth(owner)]`, which genesis accounts must protect too.
+const ACCOUNT_OWNER_METHODS: [&str; 7] = [
+    "set_deposit_hook",
+    "set_guardian",
+    "withdraw",
+    "withdraw_with_auth",
+    "withdraw_non_fungibles",
+    "withdraw_non_fungibles_with_auth",
+    "present",
+];
+/// The index of the `owner` field of the account state.
+const ACCOUNT_OWNER_FIELD: u32 = 5;
+/// The first ID of the vaults and lazy maps of genesis accounts, after the system ones.
+const GENESIS_FIRST_ID: u32 = 6;
+
+const SYSTEM_ADDRESSES: [Address; 7] = [
+    SYSTEM_PACKAGE,
+    ACCOUNT_PACKAGE,
+    RADIX_TOKEN,
+    ECDSA_TOKEN,
+    SYSTEM_COMPONENT,
+    FAUCET_COMPONENT,
+    STAKING_COMPONENT,
+];
+
 #[derive(TypeId, Encode, Decode)]
 struct SystemComponentState {
-    xrd: Vid,
+    xrd: VaultId,
+}
+
+#[derive(TypeId, Encode, Decode)]
+struct FaucetComponentState {
+    xrd: VaultId,
+    amount: Decimal,
+    cooldown: u64,
+    last_claims: Mid,
+}
+
+#[derive(TypeId, Encode, Decode)]
+struct StakingComponentState {
+    xrd: VaultId,
+    unstake_delay: u64,
+    validators: Vec<(Address, Decimal)>,
+    validator_set: Vec<(Address, Decimal)>,
+    validator_set_epoch: u64,
+    stakes: Mid,
+    unstakes: Mid,
+}
+
+#[derive(TypeId, Encode, Decode)]
+struct AccountComponentState {
+    public_key: EcdsaPublicKey,
+    vaults: Mid,
+    frozen: bool,
+    guardian: Option<Address>,
+    deposit_hook: Option<Address>,
+    owner: NonFungibleAddress,
+}
+
+/// Represents an error when checking the substates created by bootstrapping a store, which
+/// indicates the store was created by another version of the engine or got corrupted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapError {
+    PackageNotFound(Address),
+    PackageCodeMismatch {
+        package_address: Address,
+        expected: H256,
+        actual: H256,
+    },
+    ResourceDefNotFound(Address),
+    ResourceDefMismatch(Address),
+    ComponentNotFound(Address),
+    ComponentMismatch(Address),
+    VaultNotFound(Address, VaultId),
+}
+
+/// Returns the number of bytes a substate takes in storage, i.e. the length of its encoding.
+pub fn substate_size<T: Encode>(substate: &T) -> u64 {
+    scrypto_encode(substate).len() as u64
+}
+
+/// A page of the entries found by a scan, ordered by the SBOR encoding of their keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<K, V> {
+    pub entries: Vec<(K, V)>,
+    /// The cursor for the next page, i.e. the key of its first entry, or `None` if this is
+    /// the last page.
+    pub next_cursor: Option<K>,
+}
+
+impl<K: Encode, V> Page<K, V> {
+    /// Sorts the given entries and returns up to `limit` of them, starting from the cursor.
+    pub fn from_entries<I: IntoIterator<Item = (K, V)>>(
+        entries: I,
+        cursor: Option<&K>,
+        limit: usize,
+    ) -> Self {
+        let start = cursor.map(scrypto_encode);
+        let mut sorted: Vec<(Vec<u8>, K, V)> = entries
+            .into_iter()
+            .map(|(k, v)| (scrypto_encode(&k), k, v))
+            .filter(|(encoded, _, _)| start.iter().all(|s| encoded >= s))
+            .collect();
+        sorted.sort_by(|a, b| a.0.cmp(&b.0));
+        let rest = sorted.split_off(limit.min(sorted.len()));
+
+        Self {
+            entries: sorted.into_iter().map(|(_, k, v)| (k, v)).collect(),
+            next_cursor: rest.into_iter().next().map(|(_, k, _)| k),
+        }
+    }
 }
 
 /// A ledger stores all transactions and substates.
@@ -39,9 +169,12 @@ pub trait SubstateStore {
 
     fn put_lazy_map(&mut self, component_address: Address, vid: Mid, lazy_map: LazyMap);
 
-    fn get_vault(&self, component_address: &Address, vid: &Vid) -> Option<Vault>;
+    fn get_vault(&self, component_address: &Address, vid: &VaultId) -> Option<Vault>;
+
+    fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault);
 
-    fn put_vault(&mut self, component_address: Address, vid: Vid, vault: Vault);
+    /// Returns all vaults owned by the given component.
+    fn get_vaults(&self, component_address: &Address) -> Vec<(VaultId, Vault)>;
 
     fn get_non_fungible(
         &self,
@@ -56,25 +189,65 @@ pub trait SubstateStore {
         non_fungible: NonFungible,
     );
 
+    // Scans enumerate entities without knowing their addresses, a page at a time. Pass the
+    // `next_cursor` of a page to get the next one.
+
+    /// Returns a page of the components instantiated from the given package.
+    fn scan_components(
+        &self,
+        package_address: Address,
+        cursor: Option<Address>,
+        limit: usize,
+    ) -> Page<Address, Component>;
+
+    /// Returns a page of the vaults owned by the given component.
+    fn scan_vaults(
+        &self,
+        component_address: Address,
+        cursor: Option<VaultId>,
+        limit: usize,
+    ) -> Page<VaultId, Vault>;
+
+    /// Returns a page of the non-fungibles of the given resource definition.
+    fn scan_non_fungibles(
+        &self,
+        resource_address: Address,
+        cursor: Option<NonFungibleKey>,
+        limit: usize,
+    ) -> Page<NonFungibleKey, NonFungible>;
+
+    /// Returns the number of bytes taken by a component, i.e. its state and the vaults and
+    /// lazy maps it owns, see `substate_size`.
+    fn get_storage_footprint(&self, component_address: &Address) -> u64;
+
+    fn put_storage_footprint(&mut self, component_address: Address, size: u64);
+
+    /// Returns the nonce of the last committed transaction signed by the given key, if any.
+    fn get_signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64>;
+
+    fn put_signer_nonce(&mut self, signer: EcdsaPublicKey, nonce: u64);
+
+    /// Returns the method calls scheduled by components.
+    fn get_scheduler(&self) -> Scheduler;
+
+    fn put_scheduler(&mut self, scheduler: Scheduler);
+
     fn bootstrap(&mut self) {
         if self.get_package(SYSTEM_PACKAGE).is_none() {
             // System package
-            self.put_package(
-                SYSTEM_PACKAGE,
-                Package::new(include_bytes!("../../../assets/system.wasm").to_vec()),
-            );
+            self.put_package(SYSTEM_PACKAGE, Package::new(SYSTEM_PACKAGE_CODE.to_vec()));
 
             // Account package
-            self.put_package(
-                ACCOUNT_PACKAGE,
-                Package::new(include_bytes!("../../../assets/account.wasm").to_vec()),
-            );
+            self.put_package(ACCOUNT_PACKAGE, Package::new(ACCOUNT_PACKAGE_CODE.to_vec()));
 
             // Radix token resource definition
             let mut metadata = HashMap::new();
-            metadata.insert("symbol".to_owned(), XRD_SYMBOL.to_owned());
-            metadata.insert("name".to_owned(), XRD_NAME.to_owned());
-            metadata.insert("description".to_owned(), XRD_DESCRIPTION.to_owned());
+            metadata.insert(SYMBOL_METADATA_KEY.to_owned(), XRD_SYMBOL.to_owned());
+            metadata.insert(NAME_METADATA_KEY.to_owned(), XRD_NAME.to_owned());
+            metadata.insert(
+                DESCRIPTION_METADATA_KEY.to_owned(),
+                XRD_DESCRIPTION.to_owned(),
+            );
             metadata.insert("url".to_owned(), XRD_URL.to_owned());
             self.put_resource_def(
                 RADIX_TOKEN,
@@ -105,28 +278,263 @@ pub trait SubstateStore {
             );
 
             // Instantiate system component
-            self.put_vault(
-                SYSTEM_COMPONENT,
-                XRD_VAULT_ID,
-                Vault::new(Bucket::new(
-                    RADIX_TOKEN,
-                    ResourceType::Fungible { divisibility: 18 },
-                    Supply::Fungible {
-                        amount: XRD_MAX_SUPPLY.into(),
-                    },
-                )),
+            let vault = Vault::new(Bucket::new(
+                RADIX_TOKEN,
+                ResourceType::Fungible { divisibility: 18 },
+                Supply::Fungible {
+                    amount: (XRD_MAX_SUPPLY - FAUCET_SUPPLY).into(),
+                },
+            ));
+            let component = Component::new(
+                SYSTEM_PACKAGE,
+                SYSTEM_COMPONENT_NAME.to_owned(),
+                scrypto_encode(&SystemComponentState { xrd: XRD_VAULT_ID }),
             );
-            self.put_component(
+            self.put_storage_footprint(
                 SYSTEM_COMPONENT,
-                Component::new(
-                    SYSTEM_PACKAGE,
-                    SYSTEM_COMPONENT_NAME.to_owned(),
-                    scrypto_encode(&SystemComponentState { xrd: XRD_VAULT_ID }),
-                ),
+                substate_size(&vault) + substate_size(&component),
+            );
+            self.put_vault(SYSTEM_COMPONENT, XRD_VAULT_ID, vault);
+            self.put_component(SYSTEM_COMPONENT, component);
+
+            // Instantiate faucet component
+            let vault = Vault::new(Bucket::new(
+                RADIX_TOKEN,
+                ResourceType::Fungible { divisibility: 18 },
+                Supply::Fungible {
+                    amount: FAUCET_SUPPLY.into(),
+                },
+            ));
+            let lazy_map = LazyMap::new();
+            let component = Component::new(
+                SYSTEM_PACKAGE,
+                FAUCET_COMPONENT_NAME.to_owned(),
+                scrypto_encode(&FaucetComponentState {
+                    xrd: FAUCET_VAULT_ID,
+                    amount: FAUCET_AMOUNT.into(),
+                    cooldown: FAUCET_COOLDOWN,
+                    last_claims: FAUCET_LAST_CLAIMS_ID,
+                }),
+            );
+            self.put_storage_footprint(
+                FAUCET_COMPONENT,
+                substate_size(&vault) + substate_size(&lazy_map) + substate_size(&component),
             );
+            self.put_vault(FAUCET_COMPONENT, FAUCET_VAULT_ID, vault);
+            self.put_lazy_map(FAUCET_COMPONENT, FAUCET_LAST_CLAIMS_ID, lazy_map);
+            self.put_component(FAUCET_COMPONENT, component);
+
+            // Instantiate staking component
+            let vault = Vault::new(Bucket::new(
+                RADIX_TOKEN,
+                ResourceType::Fungible { divisibility: 18 },
+                Supply::Fungible {
+                    amount: Decimal::zero(),
+                },
+            ));
+            let stakes = LazyMap::new();
+            let unstakes = LazyMap::new();
+            let component = Component::new(
+                SYSTEM_PACKAGE,
+                STAKING_COMPONENT_NAME.to_owned(),
+                scrypto_encode(&StakingComponentState {
+                    xrd: STAKING_VAULT_ID,
+                    unstake_delay: STAKING_UNSTAKE_DELAY,
+                    validators: Vec::new(),
+                    validator_set: Vec::new(),
+                    validator_set_epoch: 0,
+                    stakes: STAKING_STAKES_ID,
+                    unstakes: STAKING_UNSTAKES_ID,
+                }),
+            );
+            self.put_storage_footprint(
+                STAKING_COMPONENT,
+                substate_size(&vault)
+                    + substate_size(&stakes)
+                    + substate_size(&unstakes)
+                    + substate_size(&component),
+            );
+            self.put_vault(STAKING_COMPONENT, STAKING_VAULT_ID, vault);
+            self.put_lazy_map(STAKING_COMPONENT, STAKING_STAKES_ID, stakes);
+            self.put_lazy_map(STAKING_COMPONENT, STAKING_UNSTAKES_ID, unstakes);
+            self.put_component(STAKING_COMPONENT, component);
         }
     }
 
+    /// Bootstraps this store along with the entities of a genesis specification, which are
+    /// all checked before the store is written.
+    ///
+    /// A store which has already been bootstrapped is left as is.
+    fn bootstrap_with_genesis(&mut self, genesis: &Genesis) -> Result<(), GenesisError> {
+        if self.get_package(SYSTEM_PACKAGE).is_some() {
+            return Ok(());
+        }
+
+        genesis.validate()?;
+        let addresses = genesis
+            .resources
+            .iter()
+            .map(|r| r.address)
+            .chain(genesis.accounts.iter().map(|a| a.address))
+            .chain(genesis.packages.iter().map(|p| p.address));
+        for address in addresses {
+            if SYSTEM_ADDRESSES.contains(&address) {
+                return Err(GenesisError::AddressAlreadyUsed(address));
+            }
+        }
+        let required = genesis.total_balance(RADIX_TOKEN);
+        let available: Decimal = (XRD_MAX_SUPPLY - FAUCET_SUPPLY).into();
+        if required > available {
+            return Err(GenesisError::NotEnoughXrd {
+                required,
+                available,
+            });
+        }
+        let mut resource_defs = Vec::new();
+        for resource in &genesis.resources {
+            let resource_def = ResourceDef::new(
+                ResourceType::Fungible {
+                    divisibility: resource.divisibility,
+                },
+                resource.metadata.clone(),
+                0,
+                0,
+                HashMap::new(),
+                &Some(NewSupply::Fungible {
+                    amount: genesis.total_balance(resource.address),
+                }),
+            )
+            .map_err(|e| GenesisError::ResourceDefError(resource.address, e))?;
+            for (_, amount) in genesis
+                .accounts
+                .iter()
+                .flat_map(|account| &account.balances)
+                .filter(|(address, _)| *address == resource.address)
+            {
+                resource_def
+                    .check_amount(*amount)
+                    .map_err(|e| GenesisError::ResourceDefError(resource.address, e))?;
+            }
+            resource_defs.push((resource.address, resource_def));
+        }
+
+        self.bootstrap();
+
+        for package in &genesis.packages {
+            self.put_package(package.address, Package::new(package.code.clone()));
+        }
+        for (address, resource_def) in resource_defs {
+            self.put_resource_def(address, resource_def);
+        }
+
+        let mut system_vault = self.get_vault(&SYSTEM_COMPONENT, &XRD_VAULT_ID).unwrap();
+        let mut next_id = GENESIS_FIRST_ID;
+        for account in &genesis.accounts {
+            let vaults_id = Mid(H256([0u8; 32]), next_id);
+            next_id += 1;
+            let mut vaults = LazyMap::new();
+            let mut footprint = 0;
+            for (resource_address, amount) in &account.balances {
+                let bucket = if *resource_address == RADIX_TOKEN {
+                    system_vault.take(*amount).unwrap()
+                } else {
+                    let resource_def = self.get_resource_def(*resource_address).unwrap();
+                    Bucket::new(
+                        *resource_address,
+                        resource_def.resource_type(),
+                        Supply::Fungible { amount: *amount },
+                    )
+                };
+                let vid = VaultId::new(H256([0u8; 32]), next_id);
+                next_id += 1;
+                let vault = Vault::new(bucket);
+                footprint += substate_size(&vault);
+                self.put_vault(account.address, vid, vault);
+                vaults.set_entry(scrypto_encode(resource_address), scrypto_encode(&vid));
+            }
+            let mut component = Component::new(
+                ACCOUNT_PACKAGE,
+                ACCOUNT_BLUEPRINT_NAME.to_owned(),
+                scrypto_encode(&AccountComponentState {
+                    public_key: account.public_key,
+                    vaults: vaults_id,
+                    frozen: false,
+                    guardian: None,
+                    deposit_hook: None,
+                    owner: NonFungibleAddress::from_public_key(&account.public_key),
+                }),
+            );
+            for method in ACCOUNT_OWNER_METHODS {
+                component.set_method_auth(method.to_owned(), vec![ACCOUNT_OWNER_FIELD]);
+            }
+            self.put_storage_footprint(
+                account.address,
+                footprint + substate_size(&vaults) + substate_size(&component),
+            );
+            self.put_lazy_map(account.address, vaults_id, vaults);
+            self.put_component(account.address, component);
+        }
+        self.put_vault(SYSTEM_COMPONENT, XRD_VAULT_ID, system_vault);
+        Ok(())
+    }
+
+    /// Checks the substates created by `bootstrap` are present and match this version of the
+    /// engine.
+    fn check_bootstrap(&self) -> Result<(), BootstrapError> {
+        for (package_address, code) in [
+            (SYSTEM_PACKAGE, SYSTEM_PACKAGE_CODE),
+            (ACCOUNT_PACKAGE, ACCOUNT_PACKAGE_CODE),
+        ] {
+            let package = self
+                .get_package(package_address)
+                .ok_or(BootstrapError::PackageNotFound(package_address))?;
+            let expected = sha256(code);
+            let actual = sha256(package.code());
+            if actual != expected {
+                return Err(BootstrapError::PackageCodeMismatch {
+                    package_address,
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        for (resource_address, resource_type) in [
+            (RADIX_TOKEN, ResourceType::Fungible { divisibility: 18 }),
+            (ECDSA_TOKEN, ResourceType::NonFungible),
+        ] {
+            let resource_def = self
+                .get_resource_def(resource_address)
+                .ok_or(BootstrapError::ResourceDefNotFound(resource_address))?;
+            if resource_def.resource_type() != resource_type
+                || resource_def.flags() != 0
+                || resource_def.mutable_flags() != 0
+            {
+                return Err(BootstrapError::ResourceDefMismatch(resource_address));
+            }
+        }
+
+        for (component_address, blueprint_name, vid) in [
+            (SYSTEM_COMPONENT, SYSTEM_COMPONENT_NAME, XRD_VAULT_ID),
+            (FAUCET_COMPONENT, FAUCET_COMPONENT_NAME, FAUCET_VAULT_ID),
+            (STAKING_COMPONENT, STAKING_COMPONENT_NAME, STAKING_VAULT_ID),
+        ] {
+            let component = self
+                .get_component(component_address)
+                .ok_or(BootstrapError::ComponentNotFound(component_address))?;
+            if component.package_address() != SYSTEM_PACKAGE
+                || component.blueprint_name() != blueprint_name
+            {
+                return Err(BootstrapError::ComponentMismatch(component_address));
+            }
+            match self.get_vault(&component_address, &vid) {
+                Some(vault) if vault.resource_address() == RADIX_TOKEN => {}
+                _ => return Err(BootstrapError::VaultNotFound(component_address, vid)),
+            }
+        }
+        Ok(())
+    }
+
     fn get_epoch(&self) -> u64;
 
     fn set_epoch(&mut self, epoch: u64);
@@ -137,4 +545,31 @@ pub trait SubstateStore {
     fn get_nonce(&self) -> u64;
 
     fn increase_nonce(&mut self);
+
+    /// Marks the start of the commit of a transaction, whose writes should be seen by readers
+    /// of a consistent view of this store all at once, e.g. by
+    /// `SharedInMemorySubstateStore::view()`.
+    fn begin_commit(&mut self) {}
+
+    /// Marks the end of the commit started by `begin_commit()`.
+    fn end_commit(&mut self) {}
+
+    // Stores that retain prior substate versions can answer queries about past state. Versions
+    // are keyed by the nonce at the time of writing, so the state before the transaction with
+    // nonce `n` is the state at version `n - 1`.
+
+    /// Returns the component as of the given version, if this store retains history.
+    fn get_component_at(&self, _address: Address, _version: u64) -> Option<Component> {
+        None
+    }
+
+    /// Returns the vault as of the given version, if this store retains history.
+    fn get_vault_at(
+        &self,
+        _component_address: &Address,
+        _vid: &VaultId,
+        _version: u64,
+    ) -> Option<Vault> {
+        None
+    }
 }
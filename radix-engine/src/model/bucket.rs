@@ -14,6 +14,7 @@ pub enum BucketError {
     InvalidAmount(Decimal),
     UnsupportedOperation,
     NonFungibleNotFound,
+    BalanceOverflow,
 }
 
 /// Represents the supply of resource.
@@ -33,15 +34,22 @@ pub struct Bucket {
 }
 
 /// A bucket becomes locked after a borrow operation.
-#[derive(Debug, Clone, TypeId, Encode, Decode)]
+#[derive(Debug, Clone)]
 pub struct LockedBucket {
-    bucket_id: Bid,
+    bucket_id: BucketId,
     bucket: Bucket,
+    /// The component whose vault the bucket was taken from, for bucket refs to a vault
+    origin: Option<Address>,
+    /// The whole bucket, for references scoped to part of it, which keeps it locked
+    parent: Option<BucketRef>,
 }
 
 /// A reference to a bucket.
 pub type BucketRef = Rc<LockedBucket>;
 
+/// A reference to several buckets at once, e.g. to present multiple badges as one.
+pub type CompositeBucketRef = Vec<BucketRef>;
+
 impl Bucket {
     pub fn new(resource_address: Address, resource_type: ResourceType, supply: Supply) -> Self {
         Self {
@@ -63,7 +71,9 @@ impl Bucket {
                             panic!("Illegal state!")
                         }
                     };
-                    *amount = *amount + other_amount;
+                    *amount = amount
+                        .checked_add(other_amount)
+                        .ok_or(BucketError::BalanceOverflow)?;
                 }
                 Supply::NonFungible { ref mut keys } => {
                     let other_keys = match other.supply() {
@@ -79,6 +89,16 @@ impl Bucket {
         }
     }
 
+    /// Takes as much resource as available, up to the given quantity rounded down to the
+    /// divisibility of the resource.
+    pub fn take_up_to(&mut self, quantity: Decimal) -> Result<Self, BucketError> {
+        if quantity.is_negative() {
+            return Err(BucketError::InvalidAmount(quantity));
+        }
+        let unit = 10i128.pow((18 - self.resource_type.divisibility()).into());
+        self.take(Decimal(quantity.0 - quantity.0 % unit).min(self.amount()))
+    }
+
     pub fn take(&mut self, quantity: Decimal) -> Result<Self, BucketError> {
         Self::check_amount(quantity, self.resource_type.divisibility())?;
 
@@ -88,7 +108,9 @@ impl Bucket {
             match &mut self.supply {
                 Supply::Fungible { amount } => {
                     self.supply = Supply::Fungible {
-                        amount: *amount - quantity,
+                        amount: amount
+                            .checked_sub(quantity)
+                            .ok_or(BucketError::BalanceOverflow)?,
                     };
                     Ok(Self::new(
                         self.resource_address,
@@ -123,10 +145,12 @@ impl Bucket {
         match &mut self.supply {
             Supply::Fungible { .. } => Err(BucketError::UnsupportedOperation),
             Supply::NonFungible { ref mut keys } => {
+                // check all keys up front, so a failed take leaves the bucket untouched
+                if !set.is_subset(keys) {
+                    return Err(BucketError::NonFungibleNotFound);
+                }
                 for key in set {
-                    if !keys.remove(&key) {
-                        return Err(BucketError::NonFungibleNotFound);
-                    }
+                    keys.remove(key);
                 }
                 Ok(Self::new(
                     self.resource_address,
@@ -137,6 +161,22 @@ impl Bucket {
         }
     }
 
+    /// Returns a copy of the given amount of this bucket, for a reference scoped to it.
+    pub fn scoped_to_amount(&self, amount: Decimal) -> Result<Self, BucketError> {
+        if amount.is_negative() {
+            return Err(BucketError::InvalidAmount(amount));
+        }
+        self.clone().take(amount)
+    }
+
+    /// Returns a copy of the given non-fungibles of this bucket, for a reference scoped to them.
+    pub fn scoped_to_non_fungibles(
+        &self,
+        keys: &BTreeSet<NonFungibleKey>,
+    ) -> Result<Self, BucketError> {
+        self.clone().take_non_fungibles(keys)
+    }
+
     pub fn get_non_fungible_keys(&self) -> Result<Vec<NonFungibleKey>, BucketError> {
         match &self.supply {
             Supply::Fungible { .. } => Err(BucketError::UnsupportedOperation),
@@ -169,17 +209,47 @@ impl Bucket {
 }
 
 impl LockedBucket {
-    pub fn new(bucket_id: Bid, bucket: Bucket) -> Self {
-        Self { bucket_id, bucket }
+    pub fn new(bucket_id: BucketId, bucket: Bucket) -> Self {
+        Self {
+            bucket_id,
+            bucket,
+            origin: None,
+            parent: None,
+        }
+    }
+
+    /// Creates a locked bucket holding part of the given one, which stays locked for as long
+    /// as this does.
+    pub fn scoped(parent: &BucketRef, bucket: Bucket) -> Self {
+        Self {
+            bucket_id: parent.bucket_id,
+            bucket,
+            origin: parent.origin,
+            parent: Some(parent.clone()),
+        }
     }
 
-    pub fn bucket_id(&self) -> Bid {
+    pub fn with_origin(mut self, origin: Option<Address>) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    pub fn bucket_id(&self) -> BucketId {
         self.bucket_id
     }
 
     pub fn bucket(&self) -> &Bucket {
         &self.bucket
     }
+
+    pub fn origin(&self) -> Option<Address> {
+        self.origin
+    }
+
+    /// Returns the whole bucket, if this holds only part of it.
+    pub fn parent(&self) -> Option<&BucketRef> {
+        self.parent.as_ref()
+    }
 }
 
 impl From<LockedBucket> for Bucket {
@@ -0,0 +1,28 @@
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+/// What a traced call frame represents.
+#[derive(Debug, Clone)]
+pub enum CallKind {
+    /// An invocation of a blueprint function or component method.
+    Invocation { actor: Actor, function: String },
+
+    /// A call into the engine, e.g. `CallMethod` or `GetComponentState`.
+    EngineCall { op: u32, name: &'static str },
+}
+
+/// A node of the call tree recorded when a transaction is run with tracing enabled.
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+    pub kind: CallKind,
+    /// The size of the encoded input, in bytes.
+    pub input_size: usize,
+    /// The size of the encoded output, in bytes, or zero if the call failed.
+    pub output_size: usize,
+    /// The time spent in this frame, including its children, in microseconds.
+    pub time: Option<u128>,
+    /// The number of engine calls made within this frame, including by its children.
+    pub cost: u32,
+    pub children: Vec<CallFrame>,
+}
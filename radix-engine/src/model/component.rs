@@ -1,14 +1,25 @@
+use sbor::any::*;
 use sbor::*;
+use scrypto::abi::Access;
+use scrypto::buffer::*;
+use scrypto::rust::collections::*;
 use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
 
+use crate::model::{Royalty, RoyaltyError};
+
 /// A component is an instance of blueprint.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Component {
     package_address: Address,
     blueprint_name: String,
     state: Vec<u8>,
+    royalty: Option<Royalty>,
+    reentrant: bool,
+    method_access: BTreeMap<String, Access>,
+    method_auth: BTreeMap<String, Vec<u32>>,
+    package_version: Option<u32>,
 }
 
 impl Component {
@@ -17,6 +28,11 @@ impl Component {
             package_address,
             blueprint_name,
             state,
+            royalty: None,
+            reentrant: false,
+            method_access: BTreeMap::new(),
+            method_auth: BTreeMap::new(),
+            package_version: None,
         }
     }
 
@@ -24,6 +40,15 @@ impl Component {
         self.package_address
     }
 
+    /// Returns the package version this component is pinned to, or none if it runs the latest.
+    pub fn package_version(&self) -> Option<u32> {
+        self.package_version
+    }
+
+    pub fn set_package_version(&mut self, version: Option<u32>) {
+        self.package_version = version;
+    }
+
     pub fn blueprint_name(&self) -> &str {
         &self.blueprint_name
     }
@@ -35,4 +60,90 @@ impl Component {
     pub fn set_state(&mut self, new_state: Vec<u8>) {
         self.state = new_state;
     }
+
+    /// Returns whether methods of this component may be called while another method of it
+    /// is still running.
+    pub fn is_reentrant(&self) -> bool {
+        self.reentrant
+    }
+
+    pub fn set_reentrant(&mut self, reentrant: bool) {
+        self.reentrant = reentrant;
+    }
+
+    /// Returns who may call the given method of this component.
+    pub fn method_access(&self, method: &str) -> Access {
+        self.method_access
+            .get(method)
+            .cloned()
+            .unwrap_or(Access::Public)
+    }
+
+    pub fn set_method_access(&mut self, method: String, access: Access) {
+        self.method_access.insert(method, access);
+    }
+
+    /// Returns the badges of which callers of the given method must present any, or none if
+    /// the method requires no badge.
+    ///
+    /// Badges are read from the fields of the current state, so they follow any update made
+    /// by the component. A non-fungible badge also carries the key of the required unit.
+    pub fn method_auth(
+        &self,
+        method: &str,
+    ) -> Result<Vec<(Address, Option<NonFungibleKey>)>, DecodeError> {
+        let indices = match self.method_auth.get(method) {
+            Some(indices) => indices,
+            None => return Ok(Vec::new()),
+        };
+
+        let state = decode_any(&self.state)?;
+        let mut badges = Vec::new();
+        for index in indices {
+            match state.get(*index as usize) {
+                Some(Value::Custom(SCRYPTO_TYPE_ADDRESS, bytes)) => {
+                    let address = Address::try_from(bytes.as_slice())
+                        .map_err(|_| DecodeError::InvalidCustomData(SCRYPTO_TYPE_ADDRESS))?;
+                    badges.push((address, None));
+                }
+                Some(Value::Custom(SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS, bytes)) => {
+                    let address = NonFungibleAddress::try_from(bytes.as_slice()).map_err(|_| {
+                        DecodeError::InvalidCustomData(SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS)
+                    })?;
+                    badges.push((address.resource_address(), Some(address.key())));
+                }
+                _ => return Err(DecodeError::InvalidCustomData(SCRYPTO_TYPE_ADDRESS)),
+            }
+        }
+        Ok(badges)
+    }
+
+    /// Requires callers of the given method to present any of the badges held in the fields
+    /// at the given indices.
+    pub fn set_method_auth(&mut self, method: String, indices: Vec<u32>) {
+        self.method_auth.insert(method, indices);
+    }
+
+    pub fn royalty(&self) -> Option<&Royalty> {
+        self.royalty.as_ref()
+    }
+
+    pub fn royalty_mut(&mut self) -> Option<&mut Royalty> {
+        self.royalty.as_mut()
+    }
+
+    /// Configures the royalty charged for each call, keeping any royalties already collected.
+    pub fn set_royalty(
+        &mut self,
+        amount: Decimal,
+        owner_badge: Address,
+    ) -> Result<(), RoyaltyError> {
+        match &mut self.royalty {
+            Some(royalty) => royalty.update(amount, owner_badge),
+            None => {
+                self.royalty = Some(Royalty::new(amount, owner_badge)?);
+                Ok(())
+            }
+        }
+    }
 }
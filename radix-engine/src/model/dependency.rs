@@ -0,0 +1,109 @@
+use scrypto::abi;
+use scrypto::rust::str::FromStr;
+use scrypto::rust::string::String;
+use scrypto::types::*;
+
+use crate::model::RuntimeError;
+
+/// Represents an error when checking the blueprints a package depends on, as declared by
+/// `import!`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyError {
+    /// The package address in the declaration can't be parsed.
+    InvalidPackageAddress(String),
+
+    /// The package doesn't exist.
+    PackageNotFound(Address),
+
+    /// The package has no blueprint of the given name.
+    BlueprintNotFound(Address, String),
+
+    /// The blueprint has no function of the given name.
+    FunctionNotFound(Address, String, String),
+
+    /// The function takes or returns other types than expected.
+    FunctionMismatch(Address, String, String),
+
+    /// The blueprint has no method of the given name.
+    MethodNotFound(Address, String, String),
+
+    /// The method takes or returns other types than expected.
+    MethodMismatch(Address, String, String),
+}
+
+/// Checks a blueprint a package depends on, given the ABI the package expects and a way to
+/// export the ABI of the blueprint as published.
+pub fn check_dependency<F>(expected: &abi::Blueprint, export_abi: F) -> Result<(), RuntimeError>
+where
+    F: FnOnce(Address, &str) -> Result<abi::Blueprint, RuntimeError>,
+{
+    let package_address = Address::from_str(&expected.package).map_err(|_| {
+        RuntimeError::DependencyError(DependencyError::InvalidPackageAddress(
+            expected.package.clone(),
+        ))
+    })?;
+    let actual = export_abi(package_address, &expected.name).map_err(|e| match e {
+        RuntimeError::PackageNotFound(_) => {
+            RuntimeError::DependencyError(DependencyError::PackageNotFound(package_address))
+        }
+        // Same as calling a blueprint which doesn't exist
+        RuntimeError::InvokeError(_) => RuntimeError::DependencyError(
+            DependencyError::BlueprintNotFound(package_address, expected.name.clone()),
+        ),
+        e => e,
+    })?;
+
+    check_signatures(package_address, expected, &actual).map_err(RuntimeError::DependencyError)
+}
+
+/// Checks that a blueprint has all the functions and methods a dependent package expects,
+/// with the same signatures. Additional functions and methods are allowed.
+fn check_signatures(
+    package_address: Address,
+    expected: &abi::Blueprint,
+    actual: &abi::Blueprint,
+) -> Result<(), DependencyError> {
+    for function in &expected.functions {
+        let found = actual
+            .functions
+            .iter()
+            .find(|f| f.name == function.name)
+            .ok_or_else(|| {
+                DependencyError::FunctionNotFound(
+                    package_address,
+                    expected.name.clone(),
+                    function.name.clone(),
+                )
+            })?;
+        if found.inputs != function.inputs || found.output != function.output {
+            return Err(DependencyError::FunctionMismatch(
+                package_address,
+                expected.name.clone(),
+                function.name.clone(),
+            ));
+        }
+    }
+
+    for method in &expected.methods {
+        let found = actual
+            .methods
+            .iter()
+            .find(|m| m.name == method.name)
+            .ok_or_else(|| {
+                DependencyError::MethodNotFound(
+                    package_address,
+                    expected.name.clone(),
+                    method.name.clone(),
+                )
+            })?;
+        if found.inputs != method.inputs || found.output != method.output {
+            return Err(DependencyError::MethodMismatch(
+                package_address,
+                expected.name.clone(),
+                method.name.clone(),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use sbor::*;
 use scrypto::rust::fmt;
+use scrypto::rust::string::String;
 use scrypto::types::*;
 use wasmi::*;
 
@@ -20,6 +21,15 @@ pub enum WasmValidationError {
 
     /// The wasm module does not have memory export.
     NoValidMemoryExport,
+
+    /// The wasm module is larger than allowed.
+    PackageTooLarge { limit: usize, actual: usize },
+
+    /// The wasm module has more exports than allowed.
+    TooManyExports { limit: usize, actual: usize },
+
+    /// The wasm module defines more functions than allowed.
+    TooManyFunctions { limit: usize, actual: usize },
 }
 
 /// Represents an error when parsing a value from a byte array.
@@ -31,11 +41,13 @@ pub enum DataValidationError {
     InvalidBigDecimal(ParseBigDecimalError),
     InvalidAddress(ParseAddressError),
     InvalidH256(ParseH256Error),
-    InvalidBid(ParseBidError),
+    InvalidBid(ParseBucketIdError),
     InvalidRid(ParseRidError),
     InvalidMid(ParseMidError),
-    InvalidVid(ParseVidError),
+    InvalidVid(ParseVaultIdError),
     InvalidNonFungibleKey(ParseNonFungibleKeyError),
+    InvalidNonFungibleAddress(ParseNonFungibleAddressError),
+    InvalidUrl(ParseUrlError),
 }
 
 /// Represents an error when validating a transaction.
@@ -45,6 +57,39 @@ pub enum TransactionValidationError {
     IdValidatorError(IdValidatorError),
     InvalidSignature,
     UnexpectedEnd,
+    /// A bucket taken from the worktop by the instruction at the given index is neither
+    /// passed to a call nor returned to the worktop.
+    DanglingBucket {
+        bid: BucketId,
+        instruction_index: usize,
+    },
+    /// The instruction at the given index calls a function on an address which is not a
+    /// package.
+    ExpectedPackageAddress {
+        got: AddressKind,
+        instruction_index: usize,
+    },
+    /// The instruction at the given index calls a method on an address which is not a
+    /// component.
+    ExpectedComponentAddress {
+        got: AddressKind,
+        instruction_index: usize,
+    },
+    /// The message of the transaction is larger than `MAX_MESSAGE_SIZE`.
+    MessageTooLarge {
+        size: usize,
+        max_size: usize,
+    },
+    /// A system transaction carries signatures, while it acts on behalf of no one.
+    SignedSystemTransaction,
+    /// A system transaction is run as a user transaction, or the other way round.
+    UnexpectedTransactionKind(TransactionKind),
+    /// The instruction at the given index burns a resource from the worktop which is not
+    /// freely burnable, while transactions have no badge to authorize the burn with.
+    ResourceNotFreelyBurnable {
+        resource_address: Address,
+        instruction_index: usize,
+    },
 }
 
 /// Represents an error when executing a transaction.
@@ -86,6 +131,9 @@ pub enum RuntimeError {
     /// Invalid request data.
     InvalidRequestData(DecodeError),
 
+    /// A batch of engine calls contains another batch.
+    NestedBatchEngineCalls,
+
     /// The requested host function does not exist.
     HostFunctionNotFound(usize),
 
@@ -104,6 +152,15 @@ pub enum RuntimeError {
     /// Package does not exist.
     PackageNotFound(Address),
 
+    /// Package version does not exist.
+    PackageVersionNotFound(Address, u32),
+
+    /// Package was published without an owner badge, and can't be upgraded.
+    PackageNotUpgradeable(Address),
+
+    /// Package upgrade is not authorized by the owner badge.
+    PackageUpgradeNotAllowed(Address),
+
     /// System call not allowed in given context.
     IllegalSystemCall(),
 
@@ -116,6 +173,27 @@ pub enum RuntimeError {
     /// Component is already loaded
     ComponentAlreadyLoaded(Address),
 
+    /// Component is called into while one of its methods is running, and is not reentrant.
+    ReentrantCall(Address),
+
+    /// The package version of a pinned component can only be changed by the component itself.
+    PackageVersionUpdateNotAllowed(Address),
+
+    /// Method is restricted to the component itself or to its package, and the caller is neither.
+    MethodNotAccessible(Address, String),
+
+    /// Method requires a badge, and none was presented among the arguments.
+    MethodNotAuthorized(Address, String),
+
+    /// Buckets or bucket refs are passed to a method called on a best-effort basis.
+    ResourcesInTryCall(Address, String),
+
+    /// Component is not an instance of the expected blueprint.
+    BlueprintNotMatching(Address, String),
+
+    /// Component is not an instance of a blueprint from the expected package.
+    PackageNotMatching(Address, Address),
+
     /// Resource definition does not exist.
     ResourceDefNotFound(Address),
 
@@ -131,6 +209,25 @@ pub enum RuntimeError {
     /// Lazy map removed.
     LazyMapRemoved(Mid),
 
+    /// Too many entries requested in a single lazy map scan.
+    ScanLimitExceeded(u32),
+
+    /// Too many random bytes requested in a single call.
+    RandomLengthExceeded(u32),
+
+    /// A call is scheduled for an epoch which is not after the current one.
+    ScheduledEpochNotInFuture(u64),
+
+    /// Blueprint calls are nested deeper than the limit.
+    CallDepthExceeded(usize),
+
+    /// More cost units charged within the transaction, or within a method called with bounded
+    /// cost, than the limit. With the default `CostTable`, cost units are engine calls.
+    EngineCallLimitExceeded(u32),
+
+    /// The memory of a WASM instance has grown past the limit, in pages.
+    MemoryLimitExceeded(u32),
+
     /// Duplicate LazyMap added
     DuplicateLazyMap(Mid),
 
@@ -138,20 +235,26 @@ pub enum RuntimeError {
     CyclicLazyMap(Mid),
 
     /// Vault does not exist.
-    VaultNotFound(Vid),
+    VaultNotFound(VaultId),
 
     /// Vault removed.
-    VaultRemoved(Vid),
+    VaultRemoved(VaultId),
 
     /// Duplicate Vault added
-    DuplicateVault(Vid),
+    DuplicateVault(VaultId),
+
+    /// Vault not owned by any component at the end of a call or transaction.
+    DanglingVault(VaultId),
 
     /// Bucket does not exist.
-    BucketNotFound(Bid),
+    BucketNotFound(BucketId),
 
     /// Bucket ref does not exist.
     BucketRefNotFound(Rid),
 
+    /// Only empty buckets can be dropped.
+    BucketNotEmpty(BucketId),
+
     /// Not a package address.
     InvalidPackageAddress(Address),
 
@@ -164,6 +267,12 @@ pub enum RuntimeError {
     /// The referenced bucket contains no resource.
     EmptyBucketRef,
 
+    /// A composite bucket ref has no bucket refs in it.
+    EmptyCompositeBucketRef,
+
+    /// A composite bucket ref is used where a reference to a single bucket is expected.
+    CompositeBucketRefNotAllowed(Rid),
+
     /// Bucket access error.
     BucketError(BucketError),
 
@@ -173,17 +282,32 @@ pub enum RuntimeError {
     /// Vault access error.
     VaultError(VaultError),
 
-    /// Bucket is not allowed.
-    BucketNotAllowed,
+    /// Royalty access error.
+    RoyaltyError(RoyaltyError),
+
+    /// A blueprint the package depends on is missing or doesn't match the declared ABI.
+    DependencyError(DependencyError),
+
+    /// No royalty is configured on the package or component.
+    RoyaltyNotConfigured(Address),
+
+    /// Not enough XRD on the worktop to pay the royalty of the package or component.
+    InsufficientRoyalty(Address),
+
+    /// The royalty of the package or component can only be set by its own package.
+    RoyaltyUpdateNotAllowed(Address),
+
+    /// Bucket is not allowed where the value is passed.
+    BucketNotAllowed(DataContext),
 
-    /// BucketRef is not allowed.
-    BucketRefNotAllowed,
+    /// BucketRef is not allowed where the value is passed.
+    BucketRefNotAllowed(DataContext),
 
-    /// Vault is not allowed
-    VaultNotAllowed,
+    /// Vault is not allowed where the value is passed.
+    VaultNotAllowed(DataContext),
 
-    /// Lazy Map is not allowed
-    LazyMapNotAllowed,
+    /// Lazy Map is not allowed where the value is passed.
+    LazyMapNotAllowed(DataContext),
 
     /// Interpreter is not started.
     InterpreterNotStarted,
@@ -0,0 +1,12 @@
+use scrypto::rust::string::String;
+use scrypto::types::*;
+
+use crate::model::ValidatedData;
+
+/// An event emitted by a blueprint or component during a transaction.
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub actor: Actor,
+    pub name: String,
+    pub data: ValidatedData,
+}
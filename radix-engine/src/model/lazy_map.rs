@@ -1,22 +1,25 @@
 use sbor::*;
 use scrypto::rust::collections::*;
+use scrypto::rust::ops::Bound;
 use scrypto::rust::vec::Vec;
 
 /// A key-value map where keys and values are lazily loaded on-demand.
+///
+/// Entries are sorted by key bytes, which allows range scans.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct LazyMap {
-    map: HashMap<Vec<u8>, Vec<u8>>,
+    map: BTreeMap<Vec<u8>, Vec<u8>>,
 }
 
 impl LazyMap {
     pub fn new() -> Self {
         Self {
-            map: HashMap::new(),
+            map: BTreeMap::new(),
         }
     }
 
     // for diagnosis purpose only
-    pub fn map(&self) -> &HashMap<Vec<u8>, Vec<u8>> {
+    pub fn map(&self) -> &BTreeMap<Vec<u8>, Vec<u8>> {
         &self.map
     }
 
@@ -27,4 +30,26 @@ impl LazyMap {
     pub fn set_entry(&mut self, key: Vec<u8>, value: Vec<u8>) {
         self.map.insert(key, value);
     }
+
+    /// Returns up to `limit` entries with keys in `[from, to)`, in ascending key order.
+    pub fn range(
+        &self,
+        from: Option<&[u8]>,
+        to: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(&[u8], &[u8])> {
+        if let (Some(from), Some(to)) = (from, to) {
+            if from >= to {
+                return Vec::new();
+            }
+        }
+
+        let start = from.map_or(Bound::Unbounded, Bound::Included);
+        let end = to.map_or(Bound::Unbounded, Bound::Excluded);
+        self.map
+            .range::<[u8], _>((start, end))
+            .take(limit)
+            .map(|(k, v)| (k.as_slice(), v.as_slice()))
+            .collect()
+    }
 }
@@ -1,27 +1,43 @@
 mod bucket;
+mod call_frame;
 mod component;
+mod dependency;
 mod error;
+mod event;
 mod lazy_map;
 mod non_fungible;
 mod package;
 mod receipt;
 mod resource_def;
+mod royalty;
+mod scheduler;
+mod state_diff;
+mod status;
 mod transaction;
 mod validated_data;
 mod validated_transaction;
 mod vault;
+mod warning;
 
-pub use bucket::{Bucket, BucketError, BucketRef, LockedBucket, Supply};
+pub use bucket::{Bucket, BucketError, BucketRef, CompositeBucketRef, LockedBucket, Supply};
+pub use call_frame::{CallFrame, CallKind};
 pub use component::Component;
+pub use dependency::{check_dependency, DependencyError};
 pub use error::{
     DataValidationError, RuntimeError, TransactionValidationError, WasmValidationError,
 };
+pub use event::Event;
 pub use lazy_map::LazyMap;
 pub use non_fungible::NonFungible;
 pub use package::Package;
 pub use receipt::Receipt;
 pub use resource_def::{ResourceDef, ResourceDefError};
-pub use transaction::{Instruction, Transaction};
+pub use royalty::{Royalty, RoyaltyError};
+pub use scheduler::{ScheduledCall, Scheduler};
+pub use state_diff::{BalanceChange, StateDiff, StorageChange, SupplyChange};
+pub use status::{FailureCategory, RejectionReason, TransactionStatus};
+pub use transaction::{Instruction, Transaction, TransactionKind, MAX_MESSAGE_SIZE};
 pub use validated_data::*;
 pub use validated_transaction::{ValidatedInstruction, ValidatedTransaction};
 pub use vault::{Vault, VaultError};
+pub use warning::TransactionWarning;
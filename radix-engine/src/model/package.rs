@@ -1,18 +1,99 @@
 use sbor::*;
+use scrypto::rust::collections::BTreeMap;
+use scrypto::rust::string::String;
+use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::model::{Royalty, RoyaltyError};
 
 /// A collection of blueprints, compiled and published as a single unit.
+///
+/// A package published with an owner badge may be upgraded by the badge holder. All versions
+/// are kept, so that components pinned to an older version keep running its code.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Package {
-    code: Vec<u8>,
+    versions: Vec<Vec<u8>>,
+    royalty: Option<Royalty>,
+    owner_badge: Option<Address>,
+    metadata: BTreeMap<String, BTreeMap<String, String>>,
 }
 
 impl Package {
     pub fn new(code: Vec<u8>) -> Self {
-        Self { code }
+        Self {
+            versions: vec![code],
+            royalty: None,
+            owner_badge: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_owner_badge(code: Vec<u8>, owner_badge: Address) -> Self {
+        Self {
+            versions: vec![code],
+            royalty: None,
+            owner_badge: Some(owner_badge),
+            metadata: BTreeMap::new(),
+        }
     }
 
+    /// Returns the code of the latest version.
     pub fn code(&self) -> &[u8] {
-        &self.code
+        self.versions.last().unwrap()
+    }
+
+    /// Returns the code of the given version, if exists.
+    pub fn code_at(&self, version: u32) -> Option<&[u8]> {
+        self.versions.get(version as usize).map(Vec::as_slice)
+    }
+
+    /// Returns the latest version, starting from 0 for the initial publication.
+    pub fn version(&self) -> u32 {
+        (self.versions.len() - 1) as u32
+    }
+
+    /// Returns the badge required for upgrading this package, or none if it's immutable.
+    pub fn owner_badge(&self) -> Option<Address> {
+        self.owner_badge
+    }
+
+    /// Publishes a new version, and returns its number.
+    pub fn upgrade(&mut self, code: Vec<u8>) -> u32 {
+        self.versions.push(code);
+        self.version()
+    }
+
+    /// Returns the metadata declared by each blueprint of the latest version, keyed by
+    /// blueprint name. Blueprints without metadata are omitted.
+    pub fn metadata(&self) -> &BTreeMap<String, BTreeMap<String, String>> {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: BTreeMap<String, BTreeMap<String, String>>) {
+        self.metadata = metadata;
+    }
+
+    pub fn royalty(&self) -> Option<&Royalty> {
+        self.royalty.as_ref()
+    }
+
+    pub fn royalty_mut(&mut self) -> Option<&mut Royalty> {
+        self.royalty.as_mut()
+    }
+
+    /// Configures the royalty charged for each call, keeping any royalties already collected.
+    pub fn set_royalty(
+        &mut self,
+        amount: Decimal,
+        owner_badge: Address,
+    ) -> Result<(), RoyaltyError> {
+        match &mut self.royalty {
+            Some(royalty) => royalty.update(amount, owner_badge),
+            None => {
+                self.royalty = Some(Royalty::new(amount, owner_badge)?);
+                Ok(())
+            }
+        }
     }
 }
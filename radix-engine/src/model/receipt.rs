@@ -1,6 +1,9 @@
 use colored::*;
+use sbor::Decode;
+use scrypto::buffer::*;
 use scrypto::engine::*;
 use scrypto::rust::fmt;
+use scrypto::rust::format;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
@@ -11,36 +14,80 @@ use crate::model::*;
 /// Represents a transaction receipt.
 pub struct Receipt {
     pub transaction: ValidatedTransaction,
-    pub result: Result<(), RuntimeError>,
+    pub warnings: Vec<TransactionWarning>,
+    pub status: TransactionStatus,
     pub outputs: Vec<ValidatedData>,
     pub logs: Vec<(LogLevel, String)>,
+    pub events: Vec<Event>,
     pub new_entities: Vec<Address>,
+    pub storage_changes: Vec<StorageChange>,
     pub execution_time: Option<u128>,
+    /// The cost units charged while running the transaction, see `CostTable`.
+    pub cost_units: u32,
+    pub call_trace: Vec<CallFrame>,
 }
 
 impl Receipt {
     pub fn package(&self, nth: usize) -> Option<Address> {
-        self.new_entities
-            .iter()
-            .filter(|a| matches!(a, Address::Package(_)))
-            .map(Clone::clone)
-            .nth(nth)
+        self.new_package_addresses().get(nth).copied()
     }
 
     pub fn component(&self, nth: usize) -> Option<Address> {
-        self.new_entities
-            .iter()
-            .filter(|a| matches!(a, Address::Component(_)))
-            .map(Clone::clone)
-            .nth(nth)
+        self.new_component_addresses().get(nth).copied()
     }
 
     pub fn resource_def(&self, nth: usize) -> Option<Address> {
+        self.new_resource_addresses().get(nth).copied()
+    }
+
+    /// Panics, showing this receipt, unless the transaction is committed.
+    pub fn expect_commit_success(&self) -> &Self {
+        if !self.status.is_committed() {
+            panic!("Expected the transaction to be committed\n{:?}", self);
+        }
+        self
+    }
+
+    /// Returns the addresses of the packages published, in creation order.
+    pub fn new_package_addresses(&self) -> Vec<Address> {
+        self.new_entities_of(|a| matches!(a, Address::Package(_)))
+    }
+
+    /// Returns the addresses of the components instantiated, in creation order.
+    pub fn new_component_addresses(&self) -> Vec<Address> {
+        self.new_entities_of(|a| matches!(a, Address::Component(_)))
+    }
+
+    /// Returns the addresses of the resources created, in creation order.
+    pub fn new_resource_addresses(&self) -> Vec<Address> {
+        self.new_entities_of(|a| matches!(a, Address::ResourceDef(_)))
+    }
+
+    /// Decodes the value returned by the instruction at the given index.
+    ///
+    /// Panics if the instruction didn't run, or returned a value of another type.
+    pub fn output<T: Decode>(&self, instruction_index: usize) -> T {
+        let output = self.outputs.get(instruction_index).unwrap_or_else(|| {
+            panic!(
+                "Instruction {} has no output, as only {} instructions ran",
+                instruction_index,
+                self.outputs.len()
+            )
+        });
+        scrypto_decode(&output.raw).unwrap_or_else(|e| {
+            panic!(
+                "Failed to decode the output of instruction {}: {:?}",
+                instruction_index, e
+            )
+        })
+    }
+
+    fn new_entities_of<F: Fn(&Address) -> bool>(&self, filter: F) -> Vec<Address> {
         self.new_entities
             .iter()
-            .filter(|a| matches!(a, Address::ResourceDef(_)))
-            .map(Clone::clone)
-            .nth(nth)
+            .copied()
+            .filter(|a| filter(a))
+            .collect()
     }
 }
 
@@ -60,9 +107,9 @@ impl fmt::Debug for Receipt {
             f,
             "{} {}",
             "Transaction Status:".bold().green(),
-            match &self.result {
-                Ok(()) => "SUCCESS".blue(),
-                Err(e) => e.to_string().red(),
+            match &self.status {
+                TransactionStatus::Committed => "SUCCESS".blue(),
+                status => status.to_string().red(),
             }
             .bold()
         )?;
@@ -76,6 +123,29 @@ impl fmt::Debug for Receipt {
                 .unwrap_or(String::from("?"))
         )?;
 
+        write!(f, "\n{} {}", "Cost Units:".bold().green(), self.cost_units)?;
+
+        if self.transaction.kind == TransactionKind::System {
+            write!(f, "\n{} System", "Transaction Kind:".bold().green())?;
+        }
+
+        if let Some(message) = &self.transaction.message {
+            write!(
+                f,
+                "\n{} {}",
+                "Message:".bold().green(),
+                String::from_utf8_lossy(message)
+            )?;
+        }
+
+        if let Some(nonce) = self.transaction.nonce {
+            write!(f, "\n{} {}", "Nonce:".bold().green(), nonce)?;
+        }
+
+        if let Some(epoch) = self.transaction.expiration_epoch {
+            write!(f, "\n{} {}", "Expiration Epoch:".bold().green(), epoch)?;
+        }
+
         write!(f, "\n{}", "Instructions:".bold().green())?;
         for (i, inst) in self.transaction.instructions.iter().enumerate() {
             write!(
@@ -86,6 +156,18 @@ impl fmt::Debug for Receipt {
             )?;
         }
 
+        if !self.warnings.is_empty() {
+            write!(f, "\n{}", "Warnings:".bold().green())?;
+            for (i, warning) in self.warnings.iter().enumerate() {
+                write!(
+                    f,
+                    "\n{} {}",
+                    prefix!(i, self.warnings),
+                    format!("{:?}", warning).yellow()
+                )?;
+            }
+        }
+
         write!(f, "\n{}", "Instruction Outputs:".bold().green())?;
         for (i, result) in self.outputs.iter().enumerate() {
             write!(f, "\n{} {:?}", prefix!(i, self.outputs), result)?;
@@ -103,6 +185,24 @@ impl fmt::Debug for Receipt {
             write!(f, "\n{} [{:5}] {}", prefix!(i, self.logs), l, m)?;
         }
 
+        write!(f, "\n{} {}", "Events:".bold().green(), self.events.len())?;
+        for (i, event) in self.events.iter().enumerate() {
+            let emitter = match &event.actor {
+                Actor::Blueprint(package_address, blueprint_name) => {
+                    format!("{}::{}", package_address, blueprint_name)
+                }
+                Actor::Component(component_address) => component_address.to_string(),
+            };
+            write!(
+                f,
+                "\n{} {} {} {}",
+                prefix!(i, self.events),
+                event.name.cyan(),
+                emitter,
+                event.data
+            )?;
+        }
+
         write!(
             f,
             "\n{} {}",
@@ -118,6 +218,69 @@ impl fmt::Debug for Receipt {
             write!(f, "\n{} {}: {}", prefix!(i, self.new_entities), ty, address)?;
         }
 
+        write!(
+            f,
+            "\n{} {}",
+            "Storage Changes:".bold().green(),
+            self.storage_changes.len()
+        )?;
+        for (i, change) in self.storage_changes.iter().enumerate() {
+            write!(
+                f,
+                "\n{} {}: {:+} bytes",
+                prefix!(i, self.storage_changes),
+                change.component_address,
+                change.delta
+            )?;
+        }
+
+        if !self.call_trace.is_empty() {
+            write!(f, "\n{}", "Call Trace:".bold().green())?;
+            for (i, frame) in self.call_trace.iter().enumerate() {
+                fmt_call_frame(f, frame, "", i == self.call_trace.len() - 1)?;
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Writes a call frame and, indented below it, its children.
+fn fmt_call_frame(
+    f: &mut fmt::Formatter<'_>,
+    frame: &CallFrame,
+    indent: &str,
+    last: bool,
+) -> fmt::Result {
+    let name = match &frame.kind {
+        CallKind::Invocation {
+            actor: Actor::Blueprint(package_address, blueprint_name),
+            function,
+        } => format!("{}::{}::{}", package_address, blueprint_name, function).cyan(),
+        CallKind::Invocation {
+            actor: Actor::Component(component_address),
+            function,
+        } => format!("{}::{}", component_address, function).cyan(),
+        CallKind::EngineCall { name, .. } => name.normal(),
+    };
+    write!(
+        f,
+        "\n{}{} {} (in: {} B, out: {} B, time: {} µs, cost: {})",
+        indent,
+        if last { "└─" } else { "├─" },
+        name,
+        frame.input_size,
+        frame.output_size,
+        frame
+            .time
+            .map(|v| v.to_string())
+            .unwrap_or(String::from("?")),
+        frame.cost
+    )?;
+
+    let indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+    for (i, child) in frame.children.iter().enumerate() {
+        fmt_call_frame(f, child, &indent, i == frame.children.len() - 1)?;
+    }
+    Ok(())
+}
@@ -2,7 +2,12 @@ use sbor::*;
 use scrypto::engine::*;
 use scrypto::resource::resource_flags::*;
 use scrypto::resource::resource_permissions::*;
-use scrypto::rust::collections::HashMap;
+use scrypto::resource::{
+    MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, NAME_METADATA_KEY, SYMBOL_METADATA_KEY, URL_METADATA_KEYS,
+};
+use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::{HashMap, HashSet};
+use scrypto::rust::str::FromStr;
 use scrypto::rust::string::String;
 use scrypto::types::*;
 
@@ -24,6 +29,18 @@ pub enum ResourceDefError {
         new_flags: u64,
         new_mutable_flags: u64,
     },
+    SupplyOverflow,
+    DepositNotAllowed(Address),
+    /// The metadata value under a URL key, see `URL_METADATA_KEYS`, is not a valid URL.
+    InvalidMetadataUrl {
+        key: String,
+        error: ParseUrlError,
+    },
+    /// The symbol is empty, too long or has characters other than ASCII letters and digits,
+    /// see `MAX_SYMBOL_LENGTH`.
+    InvalidMetadataSymbol(String),
+    /// The name is empty, too long or has control characters, see `MAX_NAME_LENGTH`.
+    InvalidMetadataName(String),
 }
 
 /// The definition of a resource.
@@ -35,6 +52,7 @@ pub struct ResourceDef {
     mutable_flags: u64,
     authorities: HashMap<Address, u64>,
     total_supply: Decimal,
+    allowed_depositors: HashSet<Address>,
 }
 
 impl ResourceDef {
@@ -53,6 +71,7 @@ impl ResourceDef {
             mutable_flags,
             authorities,
             total_supply: Decimal::zero(),
+            allowed_depositors: HashSet::new(),
         };
 
         if !resource_flags_are_valid(flags) {
@@ -63,6 +82,8 @@ impl ResourceDef {
             return Err(ResourceDefError::InvalidResourceFlags(mutable_flags));
         }
 
+        validate_metadata(&resource_def.metadata)?;
+
         for (_, permission) in &resource_def.authorities {
             if !resource_permissions_are_valid(*permission) {
                 return Err(ResourceDefError::InvalidResourcePermission(*permission));
@@ -127,7 +148,10 @@ impl ResourceDef {
             ResourceType::Fungible { .. } => {
                 if let Supply::Fungible { amount } = supply {
                     self.check_amount(*amount)?;
-                    self.total_supply += *amount;
+                    self.total_supply = self
+                        .total_supply
+                        .checked_add(*amount)
+                        .ok_or(ResourceDefError::SupplyOverflow)?;
                     Ok(())
                 } else {
                     Err(ResourceDefError::TypeAndSupplyNotMatching)
@@ -135,7 +159,10 @@ impl ResourceDef {
             }
             ResourceType::NonFungible => {
                 if let Supply::NonFungible { keys } = supply {
-                    self.total_supply += keys.len();
+                    self.total_supply = self
+                        .total_supply
+                        .checked_add(keys.len())
+                        .ok_or(ResourceDefError::SupplyOverflow)?;
                     Ok(())
                 } else {
                     Err(ResourceDefError::TypeAndSupplyNotMatching)
@@ -151,7 +178,10 @@ impl ResourceDef {
             ResourceType::Fungible { .. } => {
                 if let Supply::Fungible { amount } = supply {
                     self.check_amount(amount)?;
-                    self.total_supply -= amount;
+                    self.total_supply = self
+                        .total_supply
+                        .checked_sub(amount)
+                        .ok_or(ResourceDefError::SupplyOverflow)?;
                     Ok(())
                 } else {
                     Err(ResourceDefError::TypeAndSupplyNotMatching)
@@ -163,7 +193,10 @@ impl ResourceDef {
                     // This is not an issue when integrated with UTXO-based state model, where
                     // the UP state should have been spun down when the non-fungibles are withdrawn from
                     // the vault.
-                    self.total_supply -= keys.len();
+                    self.total_supply = self
+                        .total_supply
+                        .checked_sub(keys.len())
+                        .ok_or(ResourceDefError::SupplyOverflow)?;
                     Ok(())
                 } else {
                     Err(ResourceDefError::TypeAndSupplyNotMatching)
@@ -230,12 +263,41 @@ impl ResourceDef {
         badge: Option<Address>,
     ) -> Result<(), ResourceDefError> {
         self.check_update_metadata_auth(badge)?;
+        validate_metadata(&new_metadata)?;
 
         self.metadata = new_metadata;
 
         Ok(())
     }
 
+    pub fn update_allowed_depositor(
+        &mut self,
+        component_address: Address,
+        allowed: bool,
+        badge: Option<Address>,
+    ) -> Result<(), ResourceDefError> {
+        self.check_permission(badge, MAY_MANAGE_DEPOSITORS)?;
+
+        if allowed {
+            self.allowed_depositors.insert(component_address);
+        } else {
+            self.allowed_depositors.remove(&component_address);
+        }
+
+        Ok(())
+    }
+
+    /// Checks whether the vaults of a component may receive this resource.
+    pub fn check_deposit(&self, component_address: Address) -> Result<(), ResourceDefError> {
+        if !self.is_flag_on(RESTRICTED_DEPOSIT)
+            || self.allowed_depositors.contains(&component_address)
+        {
+            Ok(())
+        } else {
+            Err(ResourceDefError::DepositNotAllowed(component_address))
+        }
+    }
+
     pub fn check_take_from_vault_auth(
         &self,
         badge: Option<Address>,
@@ -289,6 +351,22 @@ impl ResourceDef {
         }
     }
 
+    pub fn check_recall_auth(&self, badge: Option<Address>) -> Result<(), ResourceDefError> {
+        if self.is_flag_on(RECALLABLE) {
+            self.check_permission(badge, MAY_RECALL)
+        } else {
+            Err(ResourceDefError::OperationNotAllowed)
+        }
+    }
+
+    pub fn check_freeze_auth(&self, badge: Option<Address>) -> Result<(), ResourceDefError> {
+        if self.is_flag_on(FREEZABLE) {
+            self.check_permission(badge, MAY_FREEZE)
+        } else {
+            Err(ResourceDefError::OperationNotAllowed)
+        }
+    }
+
     pub fn check_manage_flags_auth(&self, badge: Option<Address>) -> Result<(), ResourceDefError> {
         self.check_permission(badge, MAY_MANAGE_RESOURCE_FLAGS)
     }
@@ -319,3 +397,31 @@ impl ResourceDef {
         Err(ResourceDefError::PermissionNotAllowed)
     }
 }
+
+fn validate_metadata(metadata: &HashMap<String, String>) -> Result<(), ResourceDefError> {
+    for key in URL_METADATA_KEYS {
+        if let Some(value) = metadata.get(key) {
+            Url::from_str(value).map_err(|error| ResourceDefError::InvalidMetadataUrl {
+                key: key.to_owned(),
+                error,
+            })?;
+        }
+    }
+    if let Some(symbol) = metadata.get(SYMBOL_METADATA_KEY) {
+        if symbol.is_empty()
+            || symbol.len() > MAX_SYMBOL_LENGTH
+            || !symbol.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(ResourceDefError::InvalidMetadataSymbol(symbol.clone()));
+        }
+    }
+    if let Some(name) = metadata.get(NAME_METADATA_KEY) {
+        if name.is_empty()
+            || name.chars().count() > MAX_NAME_LENGTH
+            || name.chars().any(char::is_control)
+        {
+            return Err(ResourceDefError::InvalidMetadataName(name.clone()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use sbor::*;
+use scrypto::engine::*;
+use scrypto::types::*;
+
+use crate::model::{Bucket, Supply, Vault, VaultError};
+
+/// Represents an error when accessing a royalty.
+#[derive(Debug, Clone)]
+pub enum RoyaltyError {
+    /// The royalty amount is negative.
+    InvalidAmount,
+
+    /// The badge presented is not the royalty owner badge.
+    UnauthorizedAccess,
+
+    /// Failed to move XRD into or out of the royalty vault.
+    AccountingError(VaultError),
+}
+
+/// An amount of XRD charged for each call into a package or component, together with the
+/// royalties collected so far.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct Royalty {
+    amount: Decimal,
+    owner_badge: Address,
+    collected: Vault,
+}
+
+impl Royalty {
+    pub fn new(amount: Decimal, owner_badge: Address) -> Result<Self, RoyaltyError> {
+        if amount.is_negative() {
+            return Err(RoyaltyError::InvalidAmount);
+        }
+
+        Ok(Self {
+            amount,
+            owner_badge,
+            collected: Vault::new(Bucket::new(
+                RADIX_TOKEN,
+                ResourceType::Fungible { divisibility: 18 },
+                Supply::Fungible {
+                    amount: Decimal::zero(),
+                },
+            )),
+        })
+    }
+
+    /// Replaces the royalty configuration, keeping any royalties already collected.
+    pub fn update(&mut self, amount: Decimal, owner_badge: Address) -> Result<(), RoyaltyError> {
+        if amount.is_negative() {
+            return Err(RoyaltyError::InvalidAmount);
+        }
+
+        self.amount = amount;
+        self.owner_badge = owner_badge;
+        Ok(())
+    }
+
+    pub fn collect(&mut self, payment: Bucket) -> Result<(), RoyaltyError> {
+        self.collected
+            .put(payment)
+            .map_err(RoyaltyError::AccountingError)
+    }
+
+    pub fn claim(&mut self, badge: Option<Address>) -> Result<Bucket, RoyaltyError> {
+        if badge != Some(self.owner_badge) {
+            return Err(RoyaltyError::UnauthorizedAccess);
+        }
+
+        self.collected
+            .take_all()
+            .map_err(RoyaltyError::AccountingError)
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn owner_badge(&self) -> Address {
+        self.owner_badge
+    }
+
+    pub fn collected(&self) -> Decimal {
+        self.collected.amount()
+    }
+}
@@ -0,0 +1,55 @@
+use sbor::*;
+use scrypto::rust::string::String;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+/// A method call a component has scheduled for an epoch.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct ScheduledCall {
+    pub epoch: u64,
+    pub component_address: Address,
+    pub method: String,
+    pub args: Vec<Vec<u8>>,
+}
+
+/// The method calls scheduled by components, which are yet to be made.
+#[derive(Debug, Clone, Default, PartialEq, Eq, TypeId, Encode, Decode)]
+pub struct Scheduler {
+    calls: Vec<ScheduledCall>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the scheduled calls, in the order they were scheduled.
+    pub fn calls(&self) -> &[ScheduledCall] {
+        &self.calls
+    }
+
+    pub fn schedule(&mut self, call: ScheduledCall) {
+        self.calls.push(call);
+    }
+
+    /// Returns the calls due at the given epoch, i.e. those scheduled for it or an earlier one,
+    /// ordered by epoch and then by when they were scheduled.
+    pub fn due(&self, epoch: u64) -> Vec<ScheduledCall> {
+        let mut due: Vec<ScheduledCall> = self
+            .calls
+            .iter()
+            .filter(|call| call.epoch <= epoch)
+            .cloned()
+            .collect();
+        // stable, so calls for the same epoch stay in order
+        due.sort_by_key(|call| call.epoch);
+        due
+    }
+
+    /// Removes the first call equal to the given one, once it has been made.
+    pub fn remove(&mut self, call: &ScheduledCall) {
+        if let Some(index) = self.calls.iter().position(|c| c == call) {
+            self.calls.remove(index);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+/// The state updates made by a transaction, relative to the ledger.
+#[derive(Debug, Clone, Default)]
+pub struct StateDiff {
+    /// Entities created by the transaction, in creation order.
+    pub new_entities: Vec<Address>,
+    /// Net change of the resources held in vaults, per component.
+    pub balance_changes: Vec<BalanceChange>,
+    /// Net change of the total supply, per resource.
+    pub supply_changes: Vec<SupplyChange>,
+    /// Net change of the storage footprint, per component.
+    pub storage_changes: Vec<StorageChange>,
+}
+
+/// The net change of a resource held in the vaults of a component.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceChange {
+    pub component_address: Address,
+    pub resource_address: Address,
+    pub delta: Decimal,
+}
+
+/// The net change of the total supply of a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SupplyChange {
+    pub resource_address: Address,
+    pub delta: Decimal,
+}
+
+/// The net change of the bytes taken by a component, see `SubstateStore::get_storage_footprint`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageChange {
+    pub component_address: Address,
+    pub delta: i64,
+}
+
+impl StateDiff {
+    /// Returns the balance change of a resource in a component, if any.
+    pub fn balance_change(
+        &self,
+        component_address: Address,
+        resource_address: Address,
+    ) -> Option<Decimal> {
+        self.balance_changes
+            .iter()
+            .find(|c| {
+                c.component_address == component_address && c.resource_address == resource_address
+            })
+            .map(|c| c.delta)
+    }
+
+    /// Returns the supply change of a resource, if any.
+    pub fn supply_change(&self, resource_address: Address) -> Option<Decimal> {
+        self.supply_changes
+            .iter()
+            .find(|c| c.resource_address == resource_address)
+            .map(|c| c.delta)
+    }
+
+    /// Returns the storage footprint change of a component, if any.
+    pub fn storage_change(&self, component_address: Address) -> Option<i64> {
+        self.storage_changes
+            .iter()
+            .find(|c| c.component_address == component_address)
+            .map(|c| c.delta)
+    }
+}
@@ -0,0 +1,176 @@
+use scrypto::rust::fmt;
+use scrypto::types::*;
+
+use crate::model::*;
+
+/// Represents the outcome of a transaction.
+#[derive(Debug)]
+pub enum TransactionStatus {
+    /// The transaction succeeded, and its state updates have been committed (or would have been,
+    /// in the case of a preview).
+    Committed,
+
+    /// The transaction was executed but failed, and none of its state updates were made.
+    Failed {
+        category: FailureCategory,
+        error: RuntimeError,
+    },
+
+    /// The transaction was not accepted for execution.
+    Rejected(RejectionReason),
+}
+
+/// Represents the kind of error that caused a transaction to fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCategory {
+    /// A badge or permission check did not pass.
+    Auth,
+
+    /// Resource was misused, e.g. insufficient balance, a failed worktop assertion or a
+    /// dangling bucket.
+    Resource,
+
+    /// The blueprint code panicked or trapped.
+    ApplicationPanic,
+
+    /// An engine limit was exceeded.
+    Limit,
+
+    /// Any other engine error.
+    Other,
+}
+
+/// Represents the reason why a transaction was rejected.
+#[derive(Debug)]
+pub enum RejectionReason {
+    /// The transaction is malformed.
+    ValidationError(TransactionValidationError),
+
+    /// A signature of the transaction is invalid.
+    InvalidSignature,
+
+    /// The royalty due to the given package or component could not be paid.
+    InsufficientFee(Address),
+
+    /// The expiration epoch of the transaction has passed.
+    Expired {
+        expiration_epoch: u64,
+        current_epoch: u64,
+    },
+
+    /// The nonce of the transaction is not greater than that of the last transaction
+    /// committed by one of its signers, e.g. because it's a replay.
+    NonceAlreadyUsed {
+        signer: EcdsaPublicKey,
+        nonce: u64,
+        last_nonce: u64,
+    },
+}
+
+impl TransactionStatus {
+    /// Creates the status of a transaction that ended with the given error.
+    pub fn from_error(error: RuntimeError) -> Self {
+        if let RuntimeError::InsufficientRoyalty(address) = error.root_cause() {
+            return Self::Rejected(RejectionReason::InsufficientFee(*address));
+        }
+        Self::Failed {
+            category: error.category(),
+            error,
+        }
+    }
+
+    /// Returns whether the transaction has been committed.
+    pub fn is_committed(&self) -> bool {
+        matches!(self, Self::Committed)
+    }
+
+    /// Returns the execution error, if the transaction failed.
+    pub fn error(&self) -> Option<&RuntimeError> {
+        match self {
+            Self::Failed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<TransactionValidationError> for TransactionStatus {
+    fn from(error: TransactionValidationError) -> Self {
+        Self::Rejected(match error {
+            TransactionValidationError::InvalidSignature => RejectionReason::InvalidSignature,
+            e => RejectionReason::ValidationError(e),
+        })
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Committed => write!(f, "COMMITTED"),
+            Self::Failed { category, error } => write!(f, "FAILED ({:?}): {}", category, error),
+            Self::Rejected(reason) => write!(f, "REJECTED: {:?}", reason),
+        }
+    }
+}
+
+impl RuntimeError {
+    /// Returns the engine error behind this one, looking through errors raised from within
+    /// blueprint code.
+    pub fn root_cause(&self) -> &RuntimeError {
+        match self {
+            RuntimeError::InvokeError(e) => match e
+                .as_host_error()
+                .and_then(|e| e.downcast_ref::<RuntimeError>())
+            {
+                Some(e) => e.root_cause(),
+                None => self,
+            },
+            _ => self,
+        }
+    }
+
+    /// Returns the category of this error.
+    pub fn category(&self) -> FailureCategory {
+        match self.root_cause() {
+            RuntimeError::InvokeError(_) => FailureCategory::ApplicationPanic,
+            RuntimeError::ResourceDefError(
+                ResourceDefError::OperationNotAllowed
+                | ResourceDefError::PermissionNotAllowed
+                | ResourceDefError::DepositNotAllowed(_),
+            )
+            | RuntimeError::RoyaltyUpdateNotAllowed(_)
+            | RuntimeError::MethodNotAccessible(..)
+            | RuntimeError::MethodNotAuthorized(..)
+            | RuntimeError::PackageUpgradeNotAllowed(_)
+            | RuntimeError::PackageVersionUpdateNotAllowed(_)
+            | RuntimeError::EmptyBucketRef
+            | RuntimeError::EmptyCompositeBucketRef
+            | RuntimeError::CompositeBucketRefNotAllowed(_) => FailureCategory::Auth,
+            RuntimeError::AssertionFailed
+            | RuntimeError::ResourceDefError(_)
+            | RuntimeError::BucketError(_)
+            | RuntimeError::VaultError(_)
+            | RuntimeError::ResourceDefNotFound(_)
+            | RuntimeError::NonFungibleNotFound(..)
+            | RuntimeError::NonFungibleAlreadyExists(..)
+            | RuntimeError::BucketNotFound(_)
+            | RuntimeError::BucketRefNotFound(_)
+            | RuntimeError::BucketNotEmpty(_)
+            | RuntimeError::VaultNotFound(_)
+            | RuntimeError::VaultRemoved(_)
+            | RuntimeError::DuplicateVault(_)
+            | RuntimeError::DanglingVault(_)
+            | RuntimeError::ResourceCheckFailure => FailureCategory::Resource,
+            RuntimeError::ScanLimitExceeded(_)
+            | RuntimeError::RandomLengthExceeded(_)
+            | RuntimeError::CallDepthExceeded(_)
+            | RuntimeError::EngineCallLimitExceeded(_)
+            | RuntimeError::MemoryLimitExceeded(_)
+            | RuntimeError::WasmValidationError(
+                WasmValidationError::PackageTooLarge { .. }
+                | WasmValidationError::TooManyExports { .. }
+                | WasmValidationError::TooManyFunctions { .. },
+            ) => FailureCategory::Limit,
+            _ => FailureCategory::Other,
+        }
+    }
+}
@@ -4,10 +4,36 @@ use scrypto::rust::string::String;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
 
+/// The maximum size of a transaction message, in bytes.
+pub const MAX_MESSAGE_SIZE: usize = 1024;
+
 /// Represents an unvalidated transaction.
 #[derive(Debug, Clone, TypeId, Encode, Decode, PartialEq, Eq)]
 pub struct Transaction {
     pub instructions: Vec<Instruction>,
+    /// An optional message, e.g. a memo labelling the transaction, which is kept with its
+    /// receipt but has no effect on execution.
+    pub message: Option<Vec<u8>>,
+    /// An optional nonce, which must be greater than that of any transaction committed before
+    /// by the same signers, so that replaying a transaction is rejected.
+    pub nonce: Option<u64>,
+    /// The last epoch in which the transaction may be committed, if any.
+    pub expiration_epoch: Option<u64>,
+    /// Whether the transaction is submitted by a user or performed by the system.
+    pub kind: TransactionKind,
+}
+
+/// The origin of a transaction.
+#[derive(Debug, Clone, Copy, Default, TypeId, Encode, Decode, PartialEq, Eq)]
+pub enum TransactionKind {
+    /// A transaction submitted by a user, and signed by the keys it acts on behalf of.
+    #[default]
+    User,
+
+    /// A transaction performed by the system itself, e.g. to fund a new account in a
+    /// simulator, or to make a scheduled call. It's unsigned, isn't charged for what it does,
+    /// and can only be run through `TransactionExecutor::run_system`.
+    System,
 }
 
 /// Represents an unvalidated instruction in transaction
@@ -28,8 +54,16 @@ pub enum Instruction {
         resource_address: Address,
     },
 
+    /// Takes at most this amount of resource from worktop.
+    ///
+    /// The bucket returned is empty if the worktop contains none of the resource.
+    TakeUpToFromWorktop {
+        amount: Decimal,
+        resource_address: Address,
+    },
+
     /// Returns resource to worktop.
-    ReturnToWorktop { bid: Bid },
+    ReturnToWorktop { bid: BucketId },
 
     /// Asserts worktop contains at least this amount.
     AssertWorktopContains {
@@ -37,8 +71,25 @@ pub enum Instruction {
         resource_address: Address,
     },
 
+    /// Asserts worktop contains all of the given non-fungibles.
+    AssertWorktopContainsNonFungibles {
+        keys: BTreeSet<NonFungibleKey>,
+        resource_address: Address,
+    },
+
+    /// Burns all of a given resource on worktop.
+    BurnAllFromWorktop { resource_address: Address },
+
+    /// Asserts that, once all other instructions have run, the given account (or any other
+    /// component) holds at least this amount of resource.
+    AssertAccountBalanceAtLeast {
+        account: Address,
+        amount: Decimal,
+        resource_address: Address,
+    },
+
     /// Creates a bucket ref.
-    CreateBucketRef { bid: Bid },
+    CreateBucketRef { bid: BucketId },
 
     /// Clones a bucket ref.
     CloneBucketRef { rid: Rid },
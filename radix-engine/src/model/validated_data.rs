@@ -15,9 +15,9 @@ use scrypto::types::*;
 pub struct ValidatedData {
     pub raw: Vec<u8>,
     pub dom: Value,
-    pub buckets: Vec<Bid>,
+    pub buckets: Vec<BucketId>,
     pub bucket_refs: Vec<Rid>,
-    pub vaults: Vec<Vid>,
+    pub vaults: Vec<VaultId>,
     pub lazy_maps: Vec<Mid>,
 }
 
@@ -43,7 +43,7 @@ impl fmt::Display for ValidatedData {
 
 pub fn format_value(
     value: &Value,
-    bids: &HashMap<Bid, String>,
+    bids: &HashMap<BucketId, String>,
     rids: &HashMap<Rid, String>,
 ) -> String {
     match value {
@@ -163,6 +163,8 @@ pub fn format_kind(kind: u8) -> String {
         SCRYPTO_TYPE_MID => "LazyMap",
         SCRYPTO_TYPE_VID => "Vault",
         SCRYPTO_TYPE_NON_FUNGIBLE_KEY => "NonFungibleKey",
+        SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS => "NonFungibleAddress",
+        SCRYPTO_TYPE_URL => "Url",
         _ => panic!("Illegal state"),
     }
     .to_string()
@@ -170,7 +172,7 @@ pub fn format_kind(kind: u8) -> String {
 
 pub fn format_fields(
     fields: &Fields,
-    bids: &HashMap<Bid, String>,
+    bids: &HashMap<BucketId, String>,
     rids: &HashMap<Rid, String>,
 ) -> String {
     match fields {
@@ -184,7 +186,7 @@ pub fn format_fields(
 
 pub fn format_elements(
     values: &[Value],
-    bids: &HashMap<Bid, String>,
+    bids: &HashMap<BucketId, String>,
     rids: &HashMap<Rid, String>,
 ) -> String {
     let mut buf = String::new();
@@ -200,7 +202,7 @@ pub fn format_elements(
 pub fn format_custom(
     ty: u8,
     data: &[u8],
-    bids: &HashMap<Bid, String>,
+    bids: &HashMap<BucketId, String>,
     rids: &HashMap<Rid, String>,
 ) -> String {
     match ty {
@@ -212,11 +214,11 @@ pub fn format_custom(
         SCRYPTO_TYPE_H256 => format!("Hash(\"{}\")", H256::try_from(data).unwrap()),
         SCRYPTO_TYPE_MID => format!("LazyMap(\"{}\")", Mid::try_from(data).unwrap()),
         SCRYPTO_TYPE_BID => {
-            let bid = Bid::try_from(data).unwrap();
+            let bid = BucketId::try_from(data).unwrap();
             if let Some(name) = bids.get(&bid) {
                 format!("Bucket(\"{}\")", name)
             } else {
-                format!("Bucket({}u32)", bid.0)
+                format!("Bucket({}u32)", bid.id())
             }
         }
         SCRYPTO_TYPE_RID => {
@@ -227,11 +229,16 @@ pub fn format_custom(
                 format!("BucketRef({}u32)", rid.0)
             }
         }
-        SCRYPTO_TYPE_VID => format!("Vault(\"{}\")", Vid::try_from(data).unwrap()),
+        SCRYPTO_TYPE_VID => format!("Vault(\"{}\")", VaultId::try_from(data).unwrap()),
         SCRYPTO_TYPE_NON_FUNGIBLE_KEY => format!(
             "NonFungibleKey(\"{}\")",
             NonFungibleKey::try_from(data).unwrap()
         ),
+        SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS => format!(
+            "NonFungibleAddress(\"{}\")",
+            NonFungibleAddress::try_from(data).unwrap()
+        ),
+        SCRYPTO_TYPE_URL => format!("Url(\"{}\")", Url::try_from(data).unwrap()),
         _ => panic!("Illegal state"),
     }
 }
@@ -9,6 +9,10 @@ use crate::model::*;
 pub struct ValidatedTransaction {
     pub instructions: Vec<ValidatedInstruction>,
     pub signers: Vec<EcdsaPublicKey>,
+    pub message: Option<Vec<u8>>,
+    pub nonce: Option<u64>,
+    pub expiration_epoch: Option<u64>,
+    pub kind: TransactionKind,
 }
 
 #[derive(Debug, Clone)]
@@ -24,15 +28,31 @@ pub enum ValidatedInstruction {
         keys: BTreeSet<NonFungibleKey>,
         resource_address: Address,
     },
+    TakeUpToFromWorktop {
+        amount: Decimal,
+        resource_address: Address,
+    },
     ReturnToWorktop {
-        bid: Bid,
+        bid: BucketId,
     },
     AssertWorktopContains {
         amount: Decimal,
         resource_address: Address,
     },
+    AssertWorktopContainsNonFungibles {
+        keys: BTreeSet<NonFungibleKey>,
+        resource_address: Address,
+    },
+    BurnAllFromWorktop {
+        resource_address: Address,
+    },
+    AssertAccountBalanceAtLeast {
+        account: Address,
+        amount: Decimal,
+        resource_address: Address,
+    },
     CreateBucketRef {
-        bid: Bid,
+        bid: BucketId,
     },
     CloneBucketRef {
         rid: Rid,
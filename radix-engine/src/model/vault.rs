@@ -1,4 +1,5 @@
 use sbor::*;
+use scrypto::rust::collections::BTreeSet;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
 
@@ -8,35 +9,72 @@ use crate::model::{Bucket, BucketError, Supply};
 #[derive(Debug, Clone)]
 pub enum VaultError {
     AccountingError(BucketError),
+    /// The vault is frozen, and no resource can be put into or taken from it.
+    Frozen,
 }
 
 /// A persistent resource container on ledger state.
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct Vault {
     bucket: Bucket,
+    frozen: bool,
 }
 
 impl Vault {
     pub fn new(bucket: Bucket) -> Self {
-        Self { bucket }
+        Self {
+            bucket,
+            frozen: false,
+        }
     }
 
     pub fn put(&mut self, other: Bucket) -> Result<(), VaultError> {
+        self.check_not_frozen()?;
         self.bucket.put(other).map_err(VaultError::AccountingError)
     }
 
     pub fn take(&mut self, amount: Decimal) -> Result<Bucket, VaultError> {
+        self.check_not_frozen()?;
+        self.recall(amount)
+    }
+
+    /// Takes as much resource as available, up to the given amount rounded down to the
+    /// divisibility of the resource.
+    pub fn take_up_to(&mut self, amount: Decimal) -> Result<Bucket, VaultError> {
+        self.check_not_frozen()?;
+        self.bucket
+            .take_up_to(amount)
+            .map_err(VaultError::AccountingError)
+    }
+
+    /// Takes resource from the vault, even if it's frozen.
+    pub fn recall(&mut self, amount: Decimal) -> Result<Bucket, VaultError> {
         self.bucket
             .take(amount)
             .map_err(VaultError::AccountingError)
     }
 
     pub fn take_non_fungible(&mut self, key: &NonFungibleKey) -> Result<Bucket, VaultError> {
+        self.check_not_frozen()?;
         self.bucket
             .take_non_fungible(key)
             .map_err(VaultError::AccountingError)
     }
 
+    pub fn take_all(&mut self) -> Result<Bucket, VaultError> {
+        self.take(self.amount())
+    }
+
+    pub fn take_non_fungibles(
+        &mut self,
+        keys: &BTreeSet<NonFungibleKey>,
+    ) -> Result<Bucket, VaultError> {
+        self.check_not_frozen()?;
+        self.bucket
+            .take_non_fungibles(keys)
+            .map_err(VaultError::AccountingError)
+    }
+
     pub fn get_non_fungible_ids(&self) -> Result<Vec<NonFungibleKey>, VaultError> {
         self.bucket
             .get_non_fungible_keys()
@@ -54,4 +92,20 @@ impl Vault {
     pub fn resource_address(&self) -> Address {
         self.bucket.resource_address()
     }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+    }
+
+    fn check_not_frozen(&self) -> Result<(), VaultError> {
+        if self.frozen {
+            Err(VaultError::Frozen)
+        } else {
+            Ok(())
+        }
+    }
 }
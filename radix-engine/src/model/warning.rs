@@ -0,0 +1,15 @@
+use scrypto::types::*;
+
+/// Represents a likely mistake in a transaction, which doesn't stop it from running.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionWarning {
+    /// The transaction makes no function or method call.
+    NoCall,
+    /// The same key signs the transaction more than once.
+    DuplicateSigner { signer: EcdsaPublicKey },
+    /// The instruction at the given index takes or asserts a zero amount.
+    ZeroAmount { instruction_index: usize },
+    /// A bucket ref created by the instruction at the given index is neither passed to a
+    /// call nor dropped.
+    UnusedBucketRef { rid: Rid, instruction_index: usize },
+}
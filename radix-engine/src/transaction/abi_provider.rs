@@ -1,6 +1,9 @@
+use sbor::describe::Type;
+use sbor::*;
 use scrypto::abi;
 use scrypto::buffer::*;
 use scrypto::rust::borrow::ToOwned;
+use scrypto::rust::collections::BTreeMap;
 use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
 use scrypto::rust::vec::Vec;
@@ -74,9 +77,9 @@ impl AbiProvider for BasicAbiProvider {
         // Start a process and run abi generator
         let mut track = Track::new(&mut ledger, transaction_hash, Vec::new());
         let mut proc = track.start_process(self.trace);
-        let output: (Vec<abi::Function>, Vec<abi::Method>) = proc
+        let output = proc
             .call_abi(package_address, blueprint_name.as_ref())
-            .and_then(|rtn| scrypto_decode(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
+            .and_then(|rtn| decode_abi(&rtn.raw).map_err(RuntimeError::AbiValidationError))?;
 
         // Return ABI
         Ok(abi::Blueprint {
@@ -84,6 +87,9 @@ impl AbiProvider for BasicAbiProvider {
             name: blueprint_name.as_ref().to_owned(),
             functions: output.0,
             methods: output.1,
+            state: output.2,
+            metadata: output.3,
+            non_fungible_data: output.4,
         })
     }
 
@@ -101,3 +107,72 @@ impl AbiProvider for BasicAbiProvider {
         )
     }
 }
+
+/// A method, as exported by blueprints compiled before method access was introduced.
+#[derive(TypeId, Decode)]
+struct LegacyMethod {
+    name: String,
+    mutability: abi::Mutability,
+    inputs: Vec<Type>,
+    output: Type,
+}
+
+/// The output of an ABI generator, i.e. functions, methods, component state type, blueprint
+/// metadata and non-fungible data.
+type AbiOutput = (
+    Vec<abi::Function>,
+    Vec<abi::Method>,
+    Option<Type>,
+    BTreeMap<String, String>,
+    Vec<abi::NonFungibleData>,
+);
+
+// Decodes the output of an ABI generator, falling back to the formats of blueprints compiled
+// before the non-fungible data, metadata, state type or method access were introduced.
+pub(crate) fn decode_abi(raw: &[u8]) -> Result<AbiOutput, DecodeError> {
+    let current: Result<AbiOutput, _> =
+        scrypto_decode(raw).map(|(functions, methods, state, metadata, non_fungible_data)| {
+            (functions, methods, Some(state), metadata, non_fungible_data)
+        });
+    current
+        .or_else(|e| {
+            scrypto_decode(raw)
+                .map(
+                    |(functions, methods, state, metadata): (_, _, Type, BTreeMap<_, _>)| {
+                        (functions, methods, Some(state), metadata, Vec::new())
+                    },
+                )
+                .map_err(|_| e)
+        })
+        .or_else(|e| {
+            let unversioned: Result<(Vec<abi::Function>, Vec<abi::Method>, Type), _> =
+                scrypto_decode(raw);
+            unversioned
+                .map(|(functions, methods, state)| {
+                    (functions, methods, Some(state), BTreeMap::new(), Vec::new())
+                })
+                .map_err(|_| e)
+        })
+        .or_else(|e| {
+            let stateless: Result<(Vec<abi::Function>, Vec<abi::Method>), _> = scrypto_decode(raw);
+            stateless
+                .map(|(functions, methods)| (functions, methods, None, BTreeMap::new(), Vec::new()))
+                .map_err(|_| e)
+        })
+        .or_else(|e| {
+            let legacy: (Vec<abi::Function>, Vec<LegacyMethod>) =
+                scrypto_decode(raw).map_err(|_| e)?;
+            let methods = legacy
+                .1
+                .into_iter()
+                .map(|m| abi::Method {
+                    name: m.name,
+                    mutability: m.mutability,
+                    access: abi::Access::Public,
+                    inputs: m.inputs,
+                    output: m.output,
+                })
+                .collect();
+            Ok((legacy.0, methods, None, BTreeMap::new(), Vec::new()))
+        })
+}
@@ -0,0 +1,93 @@
+use scrypto::rust::collections::*;
+use scrypto::rust::vec::Vec;
+
+use crate::engine::*;
+use crate::model::*;
+
+/// Looks for likely mistakes in a validated transaction, without running it.
+pub fn analyze_transaction(transaction: &ValidatedTransaction) -> Vec<TransactionWarning> {
+    let mut warnings = Vec::new();
+
+    let mut signers = HashSet::new();
+    for signer in &transaction.signers {
+        if !signers.insert(signer) {
+            warnings.push(TransactionWarning::DuplicateSigner { signer: *signer });
+        }
+    }
+
+    // Replays id allocation, as done by the validator, to learn the bucket ref ids
+    let mut id_validator = IdValidator::new();
+    let mut bucket_refs = BTreeMap::new();
+    let mut has_call = false;
+    for (i, inst) in transaction.instructions.iter().enumerate() {
+        match inst {
+            ValidatedInstruction::TakeFromWorktop { amount, .. }
+            | ValidatedInstruction::TakeUpToFromWorktop { amount, .. } => {
+                if amount.is_zero() {
+                    warnings.push(TransactionWarning::ZeroAmount {
+                        instruction_index: i,
+                    });
+                }
+                id_validator.new_bucket().ok();
+            }
+            ValidatedInstruction::TakeAllFromWorktop { .. }
+            | ValidatedInstruction::TakeNonFungiblesFromWorktop { .. } => {
+                id_validator.new_bucket().ok();
+            }
+            ValidatedInstruction::ReturnToWorktop { bid } => {
+                id_validator.drop_bucket(*bid).ok();
+            }
+            ValidatedInstruction::AssertWorktopContains { amount, .. }
+            | ValidatedInstruction::AssertAccountBalanceAtLeast { amount, .. } => {
+                if amount.is_zero() {
+                    warnings.push(TransactionWarning::ZeroAmount {
+                        instruction_index: i,
+                    });
+                }
+            }
+            ValidatedInstruction::CreateBucketRef { bid } => {
+                if let Ok(rid) = id_validator.new_bucket_ref(*bid) {
+                    bucket_refs.insert(rid, i);
+                }
+            }
+            ValidatedInstruction::CloneBucketRef { rid } => {
+                if let Ok(rid) = id_validator.clone_bucket_ref(*rid) {
+                    bucket_refs.insert(rid, i);
+                }
+            }
+            ValidatedInstruction::DropBucketRef { rid } => {
+                id_validator.drop_bucket_ref(*rid).ok();
+                bucket_refs.remove(rid);
+            }
+            ValidatedInstruction::CallFunction { args, .. }
+            | ValidatedInstruction::CallMethod { args, .. } => {
+                for arg in args {
+                    id_validator.move_resources(arg).ok();
+                    for rid in &arg.bucket_refs {
+                        bucket_refs.remove(rid);
+                    }
+                }
+                has_call = true;
+            }
+            ValidatedInstruction::CallMethodWithAllResources { .. } => {
+                id_validator.move_all_resources().ok();
+                bucket_refs.clear();
+                has_call = true;
+            }
+            ValidatedInstruction::AssertWorktopContainsNonFungibles { .. }
+            | ValidatedInstruction::BurnAllFromWorktop { .. } => {}
+        }
+    }
+
+    for (rid, instruction_index) in bucket_refs {
+        warnings.push(TransactionWarning::UnusedBucketRef {
+            rid,
+            instruction_index,
+        });
+    }
+    if !has_call {
+        warnings.push(TransactionWarning::NoCall);
+    }
+
+    warnings
+}
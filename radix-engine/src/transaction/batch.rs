@@ -0,0 +1,373 @@
+use scrypto::rust::cell::RefCell;
+use scrypto::rust::collections::*;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+
+use crate::ledger::*;
+use crate::model::*;
+
+/// Identifies a substate read or written by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SubstateId {
+    Package(Address),
+    Component(Address),
+    /// The set of components instantiated from a package, which any component write changes.
+    Components(Address),
+    ResourceDef(Address),
+    LazyMap(Address, Mid),
+    Vault(Address, VaultId),
+    /// The set of vaults owned by a component, which any vault write changes.
+    Vaults(Address),
+    NonFungible(Address, NonFungibleKey),
+    /// The set of non-fungibles of a resource, which any non-fungible write changes.
+    NonFungibles(Address),
+    StorageFootprint(Address),
+    SignerNonce(EcdsaPublicKey),
+    Scheduler,
+    Epoch,
+}
+
+/// A store which runs a transaction against a read-only base store, recording the substates
+/// it reads and buffering the substates it writes.
+///
+/// The buffered writes are applied to the base store later, once it's known that none of
+/// the substates read was changed in the meantime.
+pub struct SpeculativeStore<'s, S: SubstateStore> {
+    base: &'s S,
+    nonce: u64,
+    reads: RefCell<HashSet<SubstateId>>,
+    packages: HashMap<Address, Package>,
+    components: HashMap<Address, Component>,
+    resource_defs: HashMap<Address, ResourceDef>,
+    lazy_maps: HashMap<(Address, Mid), LazyMap>,
+    vaults: HashMap<(Address, VaultId), Vault>,
+    non_fungibles: HashMap<(Address, NonFungibleKey), NonFungible>,
+    storage_footprints: HashMap<Address, u64>,
+    signer_nonces: HashMap<EcdsaPublicKey, u64>,
+    scheduler: Option<Scheduler>,
+}
+
+/// The substates read and written by a transaction run against a `SpeculativeStore`.
+pub struct SpeculativeWrites {
+    reads: HashSet<SubstateId>,
+    packages: HashMap<Address, Package>,
+    components: HashMap<Address, Component>,
+    resource_defs: HashMap<Address, ResourceDef>,
+    lazy_maps: HashMap<(Address, Mid), LazyMap>,
+    vaults: HashMap<(Address, VaultId), Vault>,
+    non_fungibles: HashMap<(Address, NonFungibleKey), NonFungible>,
+    storage_footprints: HashMap<Address, u64>,
+    signer_nonces: HashMap<EcdsaPublicKey, u64>,
+    scheduler: Option<Scheduler>,
+}
+
+impl<'s, S: SubstateStore> SpeculativeStore<'s, S> {
+    /// Creates a store on top of `base`, as if the ledger was at the given nonce.
+    pub fn new(base: &'s S, nonce: u64) -> Self {
+        Self {
+            base,
+            nonce,
+            reads: RefCell::new(HashSet::new()),
+            packages: HashMap::new(),
+            components: HashMap::new(),
+            resource_defs: HashMap::new(),
+            lazy_maps: HashMap::new(),
+            vaults: HashMap::new(),
+            non_fungibles: HashMap::new(),
+            storage_footprints: HashMap::new(),
+            signer_nonces: HashMap::new(),
+            scheduler: None,
+        }
+    }
+
+    pub fn into_writes(self) -> SpeculativeWrites {
+        SpeculativeWrites {
+            reads: self.reads.into_inner(),
+            packages: self.packages,
+            components: self.components,
+            resource_defs: self.resource_defs,
+            lazy_maps: self.lazy_maps,
+            vaults: self.vaults,
+            non_fungibles: self.non_fungibles,
+            storage_footprints: self.storage_footprints,
+            signer_nonces: self.signer_nonces,
+            scheduler: self.scheduler,
+        }
+    }
+
+    fn read(&self, id: SubstateId) {
+        self.reads.borrow_mut().insert(id);
+    }
+}
+
+impl SpeculativeWrites {
+    /// Returns whether any of the substates read is in the given set.
+    pub fn conflicts_with(&self, written: &HashSet<SubstateId>) -> bool {
+        self.reads.iter().any(|id| written.contains(id))
+    }
+
+    /// Writes the buffered substates to the given store, and returns their IDs.
+    pub fn apply<S: SubstateStore>(self, ledger: &mut S) -> HashSet<SubstateId> {
+        let mut written = HashSet::new();
+        for (address, size) in self.storage_footprints {
+            ledger.put_storage_footprint(address, size);
+            written.insert(SubstateId::StorageFootprint(address));
+        }
+        for (address, package) in self.packages {
+            ledger.put_package(address, package);
+            written.insert(SubstateId::Package(address));
+        }
+        for (address, component) in self.components {
+            written.insert(SubstateId::Component(address));
+            written.insert(SubstateId::Components(component.package_address()));
+            ledger.put_component(address, component);
+        }
+        for (address, resource_def) in self.resource_defs {
+            ledger.put_resource_def(address, resource_def);
+            written.insert(SubstateId::ResourceDef(address));
+        }
+        for ((component_address, mid), lazy_map) in self.lazy_maps {
+            ledger.put_lazy_map(component_address, mid, lazy_map);
+            written.insert(SubstateId::LazyMap(component_address, mid));
+        }
+        for ((component_address, vid), vault) in self.vaults {
+            ledger.put_vault(component_address, vid, vault);
+            written.insert(SubstateId::Vault(component_address, vid));
+            written.insert(SubstateId::Vaults(component_address));
+        }
+        for ((resource_address, key), non_fungible) in self.non_fungibles {
+            ledger.put_non_fungible(resource_address, &key, non_fungible);
+            written.insert(SubstateId::NonFungible(resource_address, key));
+            written.insert(SubstateId::NonFungibles(resource_address));
+        }
+        for (signer, nonce) in self.signer_nonces {
+            ledger.put_signer_nonce(signer, nonce);
+            written.insert(SubstateId::SignerNonce(signer));
+        }
+        if let Some(scheduler) = self.scheduler {
+            ledger.put_scheduler(scheduler);
+            written.insert(SubstateId::Scheduler);
+        }
+        written
+    }
+}
+
+impl<'s, S: SubstateStore> SubstateStore for SpeculativeStore<'s, S> {
+    fn get_resource_def(&self, address: Address) -> Option<ResourceDef> {
+        self.read(SubstateId::ResourceDef(address));
+        match self.resource_defs.get(&address) {
+            Some(resource_def) => Some(resource_def.clone()),
+            None => self.base.get_resource_def(address),
+        }
+    }
+
+    fn put_resource_def(&mut self, address: Address, resource_def: ResourceDef) {
+        self.resource_defs.insert(address, resource_def);
+    }
+
+    fn get_package(&self, address: Address) -> Option<Package> {
+        self.read(SubstateId::Package(address));
+        match self.packages.get(&address) {
+            Some(package) => Some(package.clone()),
+            None => self.base.get_package(address),
+        }
+    }
+
+    fn put_package(&mut self, address: Address, package: Package) {
+        self.packages.insert(address, package);
+    }
+
+    fn get_component(&self, address: Address) -> Option<Component> {
+        self.read(SubstateId::Component(address));
+        match self.components.get(&address) {
+            Some(component) => Some(component.clone()),
+            None => self.base.get_component(address),
+        }
+    }
+
+    fn put_component(&mut self, address: Address, component: Component) {
+        self.components.insert(address, component);
+    }
+
+    fn get_lazy_map(&self, component_address: &Address, mid: &Mid) -> Option<LazyMap> {
+        self.read(SubstateId::LazyMap(*component_address, *mid));
+        match self.lazy_maps.get(&(*component_address, *mid)) {
+            Some(lazy_map) => Some(lazy_map.clone()),
+            None => self.base.get_lazy_map(component_address, mid),
+        }
+    }
+
+    fn put_lazy_map(&mut self, component_address: Address, mid: Mid, lazy_map: LazyMap) {
+        self.lazy_maps.insert((component_address, mid), lazy_map);
+    }
+
+    fn get_vault(&self, component_address: &Address, vid: &VaultId) -> Option<Vault> {
+        self.read(SubstateId::Vault(*component_address, *vid));
+        match self.vaults.get(&(*component_address, *vid)) {
+            Some(vault) => Some(vault.clone()),
+            None => self.base.get_vault(component_address, vid),
+        }
+    }
+
+    fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault) {
+        self.vaults.insert((component_address, vid), vault);
+    }
+
+    fn get_vaults(&self, component_address: &Address) -> Vec<(VaultId, Vault)> {
+        self.read(SubstateId::Vaults(*component_address));
+        let mut vaults: Vec<(VaultId, Vault)> = self
+            .base
+            .get_vaults(component_address)
+            .into_iter()
+            .filter(|(vid, _)| !self.vaults.contains_key(&(*component_address, *vid)))
+            .collect();
+        vaults.extend(
+            self.vaults
+                .iter()
+                .filter(|((address, _), _)| address == component_address)
+                .map(|((_, vid), vault)| (*vid, vault.clone())),
+        );
+        vaults
+    }
+
+    fn get_non_fungible(
+        &self,
+        resource_address: Address,
+        key: &NonFungibleKey,
+    ) -> Option<NonFungible> {
+        self.read(SubstateId::NonFungible(resource_address, key.clone()));
+        match self.non_fungibles.get(&(resource_address, key.clone())) {
+            Some(non_fungible) => Some(non_fungible.clone()),
+            None => self.base.get_non_fungible(resource_address, key),
+        }
+    }
+
+    fn put_non_fungible(
+        &mut self,
+        resource_address: Address,
+        key: &NonFungibleKey,
+        non_fungible: NonFungible,
+    ) {
+        self.non_fungibles
+            .insert((resource_address, key.clone()), non_fungible);
+    }
+
+    // Buffered writes are merged into a page of the base store, which only needs one more
+    // entry than the limit, as buffered entries come in addition or replace base ones.
+
+    fn scan_components(
+        &self,
+        package_address: Address,
+        cursor: Option<Address>,
+        limit: usize,
+    ) -> Page<Address, Component> {
+        self.read(SubstateId::Components(package_address));
+        let mut components: HashMap<Address, Component> = self
+            .base
+            .scan_components(package_address, cursor, limit + 1)
+            .entries
+            .into_iter()
+            .collect();
+        for (address, component) in &self.components {
+            if component.package_address() == package_address {
+                components.insert(*address, component.clone());
+            }
+        }
+        Page::from_entries(components, cursor.as_ref(), limit)
+    }
+
+    fn scan_vaults(
+        &self,
+        component_address: Address,
+        cursor: Option<VaultId>,
+        limit: usize,
+    ) -> Page<VaultId, Vault> {
+        self.read(SubstateId::Vaults(component_address));
+        let mut vaults: HashMap<VaultId, Vault> = self
+            .base
+            .scan_vaults(component_address, cursor, limit + 1)
+            .entries
+            .into_iter()
+            .collect();
+        for ((address, vid), vault) in &self.vaults {
+            if *address == component_address {
+                vaults.insert(*vid, vault.clone());
+            }
+        }
+        Page::from_entries(vaults, cursor.as_ref(), limit)
+    }
+
+    fn scan_non_fungibles(
+        &self,
+        resource_address: Address,
+        cursor: Option<NonFungibleKey>,
+        limit: usize,
+    ) -> Page<NonFungibleKey, NonFungible> {
+        self.read(SubstateId::NonFungibles(resource_address));
+        let mut non_fungibles: HashMap<NonFungibleKey, NonFungible> = self
+            .base
+            .scan_non_fungibles(resource_address, cursor.clone(), limit + 1)
+            .entries
+            .into_iter()
+            .collect();
+        for ((address, key), non_fungible) in &self.non_fungibles {
+            if *address == resource_address {
+                non_fungibles.insert(key.clone(), non_fungible.clone());
+            }
+        }
+        Page::from_entries(non_fungibles, cursor.as_ref(), limit)
+    }
+
+    fn get_storage_footprint(&self, component_address: &Address) -> u64 {
+        self.read(SubstateId::StorageFootprint(*component_address));
+        match self.storage_footprints.get(component_address) {
+            Some(size) => *size,
+            None => self.base.get_storage_footprint(component_address),
+        }
+    }
+
+    fn put_storage_footprint(&mut self, component_address: Address, size: u64) {
+        self.storage_footprints.insert(component_address, size);
+    }
+
+    fn get_signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64> {
+        self.read(SubstateId::SignerNonce(*signer));
+        match self.signer_nonces.get(signer) {
+            Some(nonce) => Some(*nonce),
+            None => self.base.get_signer_nonce(signer),
+        }
+    }
+
+    fn put_signer_nonce(&mut self, signer: EcdsaPublicKey, nonce: u64) {
+        self.signer_nonces.insert(signer, nonce);
+    }
+
+    fn get_scheduler(&self) -> Scheduler {
+        self.read(SubstateId::Scheduler);
+        match &self.scheduler {
+            Some(scheduler) => scheduler.clone(),
+            None => self.base.get_scheduler(),
+        }
+    }
+
+    fn put_scheduler(&mut self, scheduler: Scheduler) {
+        self.scheduler = Some(scheduler);
+    }
+
+    fn get_epoch(&self) -> u64 {
+        self.read(SubstateId::Epoch);
+        self.base.get_epoch()
+    }
+
+    fn set_epoch(&mut self, _epoch: u64) {
+        panic!("Epoch can't be set by a speculative transaction");
+    }
+
+    fn get_nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    fn increase_nonce(&mut self) {
+        self.nonce += 1;
+    }
+}
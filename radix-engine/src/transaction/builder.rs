@@ -1,4 +1,5 @@
 use sbor::describe::*;
+use sbor::type_id::*;
 use sbor::*;
 use scrypto::abi;
 use scrypto::buffer::*;
@@ -10,6 +11,7 @@ use scrypto::rust::collections::*;
 use scrypto::rust::fmt;
 use scrypto::rust::str::FromStr;
 use scrypto::rust::string::String;
+use scrypto::rust::string::ToString;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
@@ -129,6 +131,12 @@ pub struct TransactionBuilder<'a, A: AbiProvider> {
     id_validator: IdValidator,
     /// Instructions generated.
     instructions: Vec<Instruction>,
+    /// Message attached to the transaction.
+    message: Option<Vec<u8>>,
+    /// Nonce of the transaction.
+    nonce: Option<u64>,
+    /// Last epoch in which the transaction may be committed.
+    expiration_epoch: Option<u64>,
     /// Collected Errors
     errors: Vec<BuildTransactionError>,
 }
@@ -140,13 +148,41 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             abi_provider,
             id_validator: IdValidator::new(),
             instructions: Vec::new(),
+            message: None,
+            nonce: None,
+            expiration_epoch: None,
             errors: Vec::new(),
         }
     }
 
+    /// Attaches a message to the transaction, e.g. a memo for humans and indexers.
+    ///
+    /// The message is kept with the receipt, and may be at most `MAX_MESSAGE_SIZE` bytes.
+    pub fn message<T: Into<Vec<u8>>>(&mut self, message: T) -> &mut Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Sets the nonce of the transaction, which must be greater than that of any transaction
+    /// committed before by the same signers, so that the built transaction can only be
+    /// committed once.
+    pub fn nonce(&mut self, nonce: u64) -> &mut Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    /// Sets the last epoch in which the transaction may be committed.
+    pub fn expiration_epoch(&mut self, epoch: u64) -> &mut Self {
+        self.expiration_epoch = Some(epoch);
+        self
+    }
+
     /// Adds a raw instruction.
-    pub fn add_instruction(&mut self, inst: Instruction) -> (&mut Self, Option<Bid>, Option<Rid>) {
-        let mut new_bid: Option<Bid> = None;
+    pub fn add_instruction(
+        &mut self,
+        inst: Instruction,
+    ) -> (&mut Self, Option<BucketId>, Option<Rid>) {
+        let mut new_bid: Option<BucketId> = None;
         let mut new_rid: Option<Rid> = None;
 
         match inst.clone() {
@@ -159,10 +195,16 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             Instruction::TakeNonFungiblesFromWorktop { .. } => {
                 new_bid = Some(self.id_validator.new_bucket().unwrap());
             }
+            Instruction::TakeUpToFromWorktop { .. } => {
+                new_bid = Some(self.id_validator.new_bucket().unwrap());
+            }
             Instruction::ReturnToWorktop { bid } => {
                 self.id_validator.drop_bucket(bid).unwrap();
             }
             Instruction::AssertWorktopContains { .. } => {}
+            Instruction::AssertWorktopContainsNonFungibles { .. } => {}
+            Instruction::BurnAllFromWorktop { .. } => {}
+            Instruction::AssertAccountBalanceAtLeast { .. } => {}
             Instruction::CreateBucketRef { bid } => {
                 new_rid = Some(self.id_validator.new_bucket_ref(bid).unwrap());
             }
@@ -192,7 +234,7 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
     /// Takes resources from worktop.
     pub fn take_from_worktop<F>(&mut self, resource: &Resource, then: F) -> &mut Self
     where
-        F: FnOnce(&mut Self, Bid) -> &mut Self,
+        F: FnOnce(&mut Self, BucketId) -> &mut Self,
     {
         let (builder, bid, _) = match resource.clone() {
             Resource::Fungible {
@@ -216,6 +258,23 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         then(builder, bid.unwrap())
     }
 
+    /// Takes at most this amount of resource from worktop.
+    pub fn take_up_to_from_worktop<F>(
+        &mut self,
+        amount: Decimal,
+        resource_address: Address,
+        then: F,
+    ) -> &mut Self
+    where
+        F: FnOnce(&mut Self, BucketId) -> &mut Self,
+    {
+        let (builder, bid, _) = self.add_instruction(Instruction::TakeUpToFromWorktop {
+            amount,
+            resource_address,
+        });
+        then(builder, bid.unwrap())
+    }
+
     /// Asserts that worktop contains at least this amount of resource.
     pub fn assert_worktop_contains(
         &mut self,
@@ -229,8 +288,43 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         .0
     }
 
+    /// Asserts that worktop contains all of the given non-fungibles.
+    pub fn assert_worktop_contains_non_fungibles(
+        &mut self,
+        keys: &BTreeSet<NonFungibleKey>,
+        resource_address: Address,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertWorktopContainsNonFungibles {
+            keys: keys.clone(),
+            resource_address,
+        })
+        .0
+    }
+
+    /// Burns all of a given resource on worktop.
+    pub fn burn_all_from_worktop(&mut self, resource_address: Address) -> &mut Self {
+        self.add_instruction(Instruction::BurnAllFromWorktop { resource_address })
+            .0
+    }
+
+    /// Asserts that the given account holds at least this amount of resource at the end of the
+    /// transaction.
+    pub fn assert_account_balance_at_least(
+        &mut self,
+        account: Address,
+        amount: Decimal,
+        resource_address: Address,
+    ) -> &mut Self {
+        self.add_instruction(Instruction::AssertAccountBalanceAtLeast {
+            account,
+            amount,
+            resource_address,
+        })
+        .0
+    }
+
     /// Creates a bucket ref.
-    pub fn create_bucket_ref<F>(&mut self, bid: Bid, then: F) -> &mut Self
+    pub fn create_bucket_ref<F>(&mut self, bid: BucketId, then: F) -> &mut Self
     where
         F: FnOnce(&mut Self, Rid) -> &mut Self,
     {
@@ -358,18 +452,46 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
     pub fn build(
         &mut self,
         signers: Vec<EcdsaPublicKey>,
+    ) -> Result<Transaction, BuildTransactionError> {
+        self.build_with_signer(&signers)
+    }
+
+    /// Builds a transaction, which is signed by the given signer.
+    pub fn build_with_signer<S: TransactionSigner + ?Sized>(
+        &mut self,
+        signer: &S,
+    ) -> Result<Transaction, BuildTransactionError> {
+        self.build_with_kind(TransactionKind::User, signer)
+    }
+
+    /// Builds an unsigned transaction performed by the system, which can only be run through
+    /// `TransactionExecutor::run_system`.
+    pub fn build_system(&mut self) -> Result<Transaction, BuildTransactionError> {
+        self.build_with_kind(TransactionKind::System, &Vec::<EcdsaPublicKey>::new())
+    }
+
+    fn build_with_kind<S: TransactionSigner + ?Sized>(
+        &mut self,
+        kind: TransactionKind,
+        signer: &S,
     ) -> Result<Transaction, BuildTransactionError> {
         if !self.errors.is_empty() {
             return Err(self.errors[0].clone());
         }
 
-        let mut v = Vec::new();
-        v.extend(self.instructions.clone());
-        v.push(Instruction::End {
-            signatures: signers, // TODO sign
-        });
+        let mut transaction = Transaction {
+            instructions: self.instructions.clone(),
+            message: self.message.clone(),
+            nonce: self.nonce,
+            expiration_epoch: self.expiration_epoch,
+            kind,
+        };
+        let signatures = signer.sign(&transaction);
+        transaction
+            .instructions
+            .push(Instruction::End { signatures });
 
-        Ok(Transaction { instructions: v })
+        Ok(transaction)
     }
 
     //===============================
@@ -387,6 +509,64 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         .0
     }
 
+    /// Publishes a package, which can be upgraded by the holder of the owner badge.
+    pub fn publish_package_with_owner(&mut self, code: &[u8], owner_badge: Address) -> &mut Self {
+        self.add_instruction(Instruction::CallFunction {
+            package_address: SYSTEM_PACKAGE,
+            blueprint_name: "System".to_owned(),
+            function: "publish_package_with_owner".to_owned(),
+            args: vec![scrypto_encode(&code.to_vec()), scrypto_encode(&owner_badge)],
+        })
+        .0
+    }
+
+    /// Publishes a new version of a package, with the owner badge taken from worktop.
+    pub fn upgrade_package(
+        &mut self,
+        package_address: Address,
+        code: &[u8],
+        owner_badge: Address,
+    ) -> &mut Self {
+        self.take_from_worktop(
+            &Resource::Fungible {
+                amount: 1.into(),
+                resource_address: owner_badge,
+            },
+            |builder, bid| {
+                builder.create_bucket_ref(bid, |builder, rid| {
+                    builder
+                        .add_instruction(Instruction::CallFunction {
+                            package_address: SYSTEM_PACKAGE,
+                            blueprint_name: "System".to_owned(),
+                            function: "upgrade_package".to_owned(),
+                            args: vec![
+                                scrypto_encode(&package_address),
+                                scrypto_encode(&code.to_vec()),
+                                scrypto_encode(&rid),
+                            ],
+                        })
+                        .0
+                })
+            },
+        )
+    }
+
+    /// Encodes resource metadata with its entries in key order, as a hash map iterates in an
+    /// order which differs between runs, so that the same metadata always builds the same
+    /// transaction.
+    fn encode_metadata(metadata: &HashMap<String, String>) -> Vec<u8> {
+        let mut encoder = Encoder::with_type(Vec::new());
+        encoder.write_type(TYPE_HASH_MAP);
+        encoder.write_type(String::type_id());
+        encoder.write_type(String::type_id());
+        encoder.write_len(metadata.len());
+        for (key, value) in metadata.iter().collect::<BTreeMap<_, _>>() {
+            key.encode_value(&mut encoder);
+            value.encode_value(&mut encoder);
+        }
+        encoder.into()
+    }
+
     fn single_authority(badge: Address, permission: u64) -> HashMap<Address, u64> {
         let mut map = HashMap::new();
         map.insert(badge, permission);
@@ -405,7 +585,7 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             function: "new_resource".to_owned(),
             args: vec![
                 scrypto_encode(&ResourceType::Fungible { divisibility: 18 }),
-                scrypto_encode(&metadata),
+                Self::encode_metadata(&metadata),
                 scrypto_encode(&(MINTABLE | BURNABLE)),
                 scrypto_encode(&0u64),
                 scrypto_encode(&Self::single_authority(
@@ -430,7 +610,7 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             function: "new_resource".to_owned(),
             args: vec![
                 scrypto_encode(&ResourceType::Fungible { divisibility: 18 }),
-                scrypto_encode(&metadata),
+                Self::encode_metadata(&metadata),
                 scrypto_encode(&0u64),
                 scrypto_encode(&0u64),
                 scrypto_encode(&HashMap::<Address, u64>::new()),
@@ -454,7 +634,7 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             function: "new_resource".to_owned(),
             args: vec![
                 scrypto_encode(&ResourceType::Fungible { divisibility: 0 }),
-                scrypto_encode(&metadata),
+                Self::encode_metadata(&metadata),
                 scrypto_encode(&(MINTABLE | BURNABLE)),
                 scrypto_encode(&0u64),
                 scrypto_encode(&Self::single_authority(
@@ -479,7 +659,7 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             function: "new_resource".to_owned(),
             args: vec![
                 scrypto_encode(&ResourceType::Fungible { divisibility: 0 }),
-                scrypto_encode(&metadata),
+                Self::encode_metadata(&metadata),
                 scrypto_encode(&0u64),
                 scrypto_encode(&0u64),
                 scrypto_encode(&HashMap::<Address, u64>::new()),
@@ -522,6 +702,108 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         )
     }
 
+    /// Recalls resource from a vault.
+    pub fn recall(
+        &mut self,
+        amount: Decimal,
+        resource_address: Address,
+        component_address: Address,
+        vid: VaultId,
+        recall_badge_address: Address,
+    ) -> &mut Self {
+        self.take_from_worktop(
+            &Resource::Fungible {
+                amount: 1.into(),
+                resource_address: recall_badge_address,
+            },
+            |builder, bid| {
+                builder.create_bucket_ref(bid, |builder, rid| {
+                    builder
+                        .add_instruction(Instruction::CallFunction {
+                            package_address: SYSTEM_PACKAGE,
+                            blueprint_name: "System".to_owned(),
+                            function: "recall".to_owned(),
+                            args: vec![
+                                scrypto_encode(&resource_address),
+                                scrypto_encode(&component_address),
+                                scrypto_encode(&vid.to_string()),
+                                scrypto_encode(&amount),
+                                scrypto_encode(&rid),
+                            ],
+                        })
+                        .0
+                })
+            },
+        )
+    }
+
+    /// Freezes a vault, so that no resource can be put into or taken from it.
+    pub fn freeze_vault(
+        &mut self,
+        resource_address: Address,
+        component_address: Address,
+        vid: VaultId,
+        freeze_badge_address: Address,
+    ) -> &mut Self {
+        self.update_vault_frozen(
+            "freeze_vault",
+            resource_address,
+            component_address,
+            vid,
+            freeze_badge_address,
+        )
+    }
+
+    /// Unfreezes a vault.
+    pub fn unfreeze_vault(
+        &mut self,
+        resource_address: Address,
+        component_address: Address,
+        vid: VaultId,
+        freeze_badge_address: Address,
+    ) -> &mut Self {
+        self.update_vault_frozen(
+            "unfreeze_vault",
+            resource_address,
+            component_address,
+            vid,
+            freeze_badge_address,
+        )
+    }
+
+    fn update_vault_frozen(
+        &mut self,
+        function: &str,
+        resource_address: Address,
+        component_address: Address,
+        vid: VaultId,
+        freeze_badge_address: Address,
+    ) -> &mut Self {
+        self.take_from_worktop(
+            &Resource::Fungible {
+                amount: 1.into(),
+                resource_address: freeze_badge_address,
+            },
+            |builder, bid| {
+                builder.create_bucket_ref(bid, |builder, rid| {
+                    builder
+                        .add_instruction(Instruction::CallFunction {
+                            package_address: SYSTEM_PACKAGE,
+                            blueprint_name: "System".to_owned(),
+                            function: function.to_owned(),
+                            args: vec![
+                                scrypto_encode(&resource_address),
+                                scrypto_encode(&component_address),
+                                scrypto_encode(&vid.to_string()),
+                                scrypto_encode(&rid),
+                            ],
+                        })
+                        .0
+                })
+            },
+        )
+    }
+
     /// Creates an account.
     pub fn new_account(&mut self, key: Address) -> &mut Self {
         self.add_instruction(Instruction::CallFunction {
@@ -560,6 +842,37 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         )
     }
 
+    /// Creates an account funded with resources withdrawn from another account.
+    ///
+    /// The transaction must be signed by the owner of the funding account.
+    pub fn new_account_with_resources(
+        &mut self,
+        key: EcdsaPublicKey,
+        resources: Vec<(Decimal, Address)>,
+        funding_account: Address,
+    ) -> &mut Self {
+        let mut bids = Vec::new();
+        for (amount, resource_address) in resources {
+            let resource = Resource::Fungible {
+                amount,
+                resource_address,
+            };
+            self.withdraw_from_account(&resource, funding_account);
+            self.take_from_worktop(&resource, |builder, bid| {
+                bids.push(bid);
+                builder
+            });
+        }
+
+        self.add_instruction(Instruction::CallFunction {
+            package_address: ACCOUNT_PACKAGE,
+            blueprint_name: "Account".to_owned(),
+            function: "with_buckets".to_owned(),
+            args: vec![scrypto_encode(&key), scrypto_encode(&bids)],
+        })
+        .0
+    }
+
     /// Withdraws resource from an account.
     pub fn withdraw_from_account(&mut self, resource: &Resource, account: Address) -> &mut Self {
         self.clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| match resource {
@@ -601,6 +914,87 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
         })
     }
 
+    /// Offers resource withdrawn from an account in exchange for another resource, through
+    /// an escrow component created by the account.
+    ///
+    /// The transaction must be signed by the owner of the account.
+    pub fn create_escrow(
+        &mut self,
+        offer: &Resource,
+        account: Address,
+        want_resource: Address,
+        want_amount: Decimal,
+    ) -> &mut Self {
+        self.withdraw_from_account(offer, account);
+        self.take_from_worktop(offer, |builder, bid| {
+            builder
+                .add_instruction(Instruction::CallMethod {
+                    component_address: account,
+                    method: "create_escrow".to_owned(),
+                    args: vec![
+                        scrypto_encode(&bid),
+                        scrypto_encode(&want_resource),
+                        scrypto_encode(&want_amount),
+                    ],
+                })
+                .0
+        })
+    }
+
+    /// Accepts an escrow with payment withdrawn from an account, and deposits the offer and
+    /// any change into the account.
+    ///
+    /// The transaction must be signed by the owner of the account.
+    pub fn accept_escrow(
+        &mut self,
+        escrow: Address,
+        payment: &Resource,
+        account: Address,
+    ) -> &mut Self {
+        self.withdraw_from_account(payment, account);
+        self.take_from_worktop(payment, |builder, bid| {
+            builder
+                .add_instruction(Instruction::CallMethod {
+                    component_address: escrow,
+                    method: "accept".to_owned(),
+                    args: vec![scrypto_encode(&bid)],
+                })
+                .0
+        });
+        self.call_method_with_all_resources(account, "deposit_batch")
+    }
+
+    /// Prepares the `i`-th argument of a call from its command-line form, e.g. `5` for a `u32`
+    /// or `amount,resource_address` for a `Bucket`.
+    ///
+    /// If an account address is provided, resources will be withdrawn from the given account;
+    /// otherwise, they will be taken from transaction worktop.
+    pub fn prepare_arg(
+        &mut self,
+        i: usize,
+        t: &Type,
+        arg: &str,
+        account: Option<Address>,
+    ) -> Result<Vec<u8>, BuildArgsError> {
+        match t {
+            Type::Bool => self.prepare_basic_ty::<bool>(i, t, arg),
+            Type::I8 => self.prepare_basic_ty::<i8>(i, t, arg),
+            Type::I16 => self.prepare_basic_ty::<i16>(i, t, arg),
+            Type::I32 => self.prepare_basic_ty::<i32>(i, t, arg),
+            Type::I64 => self.prepare_basic_ty::<i64>(i, t, arg),
+            Type::I128 => self.prepare_basic_ty::<i128>(i, t, arg),
+            Type::U8 => self.prepare_basic_ty::<u8>(i, t, arg),
+            Type::U16 => self.prepare_basic_ty::<u16>(i, t, arg),
+            Type::U32 => self.prepare_basic_ty::<u32>(i, t, arg),
+            Type::U64 => self.prepare_basic_ty::<u64>(i, t, arg),
+            Type::U128 => self.prepare_basic_ty::<u128>(i, t, arg),
+            Type::String => self.prepare_basic_ty::<String>(i, t, arg),
+            Type::Option { value } => self.prepare_option_ty(i, value, arg, account),
+            Type::Custom { name, .. } => self.prepare_custom_ty(i, t, arg, name, account),
+            _ => Err(BuildArgsError::UnsupportedType(i, t.clone())),
+        }
+    }
+
     //===============================
     // private methods below
     //===============================
@@ -639,28 +1033,30 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
             let arg = args
                 .get(i)
                 .ok_or_else(|| BuildArgsError::MissingArgument(i, t.clone()))?;
-            let res = match t {
-                Type::Bool => self.prepare_basic_ty::<bool>(i, t, arg),
-                Type::I8 => self.prepare_basic_ty::<i8>(i, t, arg),
-                Type::I16 => self.prepare_basic_ty::<i16>(i, t, arg),
-                Type::I32 => self.prepare_basic_ty::<i32>(i, t, arg),
-                Type::I64 => self.prepare_basic_ty::<i64>(i, t, arg),
-                Type::I128 => self.prepare_basic_ty::<i128>(i, t, arg),
-                Type::U8 => self.prepare_basic_ty::<u8>(i, t, arg),
-                Type::U16 => self.prepare_basic_ty::<u16>(i, t, arg),
-                Type::U32 => self.prepare_basic_ty::<u32>(i, t, arg),
-                Type::U64 => self.prepare_basic_ty::<u64>(i, t, arg),
-                Type::U128 => self.prepare_basic_ty::<u128>(i, t, arg),
-                Type::String => self.prepare_basic_ty::<String>(i, t, arg),
-                Type::Custom { name, .. } => self.prepare_custom_ty(i, t, arg, name, account),
-                _ => Err(BuildArgsError::UnsupportedType(i, t.clone())),
-            };
-            encoded.push(res?);
+            encoded.push(self.prepare_arg(i, t, arg, account)?);
         }
 
         Ok(encoded)
     }
 
+    /// An empty argument stands for `None`; anything else is parsed as the inner type, so that
+    /// e.g. an `Option<Bucket>` is only created when resource is actually passed.
+    fn prepare_option_ty(
+        &mut self,
+        i: usize,
+        value: &Type,
+        arg: &str,
+        account: Option<Address>,
+    ) -> Result<Vec<u8>, BuildArgsError> {
+        if arg.is_empty() {
+            return Ok(scrypto_encode::<Option<()>>(&None));
+        }
+
+        let mut encoded = vec![TYPE_OPTION, OPTION_TYPE_SOME];
+        encoded.extend(self.prepare_arg(i, value, arg, account)?);
+        Ok(encoded)
+    }
+
     fn prepare_basic_ty<T>(
         &mut self,
         i: usize,
@@ -716,6 +1112,18 @@ impl<'a, A: AbiProvider> TransactionBuilder<'a, A> {
                     .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
                 Ok(scrypto_encode(&value))
             }
+            SCRYPTO_NAME_NON_FUNGIBLE_ADDRESS => {
+                let value = arg
+                    .parse::<NonFungibleAddress>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
+            SCRYPTO_NAME_URL => {
+                let value = arg
+                    .parse::<Url>()
+                    .map_err(|_| BuildArgsError::FailedToParse(i, ty.clone(), arg.to_owned()))?;
+                Ok(scrypto_encode(&value))
+            }
             SCRYPTO_NAME_BID | SCRYPTO_NAME_BUCKET => {
                 let resource = parse_resource(i, ty, arg)?;
 
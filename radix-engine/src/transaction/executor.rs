@@ -1,5 +1,14 @@
+use sbor::any::{decode_any, Fields, Value};
+use sbor::describe::{self, Type};
 use scrypto::abi;
+use scrypto::buffer::*;
+use scrypto::resource::{BURNABLE, FREELY_BURNABLE};
+use scrypto::rust::boxed::Box;
+#[cfg(any(feature = "native", not(feature = "alloc")))]
+use scrypto::rust::collections::*;
+use scrypto::rust::string::String;
 use scrypto::rust::string::ToString;
+use scrypto::rust::sync::Arc;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
 use scrypto::types::*;
@@ -8,12 +17,27 @@ use scrypto::utils::*;
 use crate::engine::*;
 use crate::ledger::*;
 use crate::model::*;
+#[cfg(not(feature = "alloc"))]
+use crate::transaction::batch::*;
+// Batches track reads and writes of pseudo-substates too, e.g. the epoch
+#[cfg(not(feature = "alloc"))]
+use crate::transaction::batch::SubstateId;
 use crate::transaction::*;
 
 /// An executor that runs transactions.
 pub struct TransactionExecutor<'l, L: SubstateStore> {
     ledger: &'l mut L,
     trace: bool,
+    epoch_interval: Option<u64>,
+    package_limits: PackageLimits,
+    execution_limits: ExecutionLimits,
+    cost_table: CostTable,
+    address_book: AddressBook,
+    wasm_engine: Arc<dyn WasmEngine>,
+    intent_hasher: Box<dyn IntentHasher>,
+    #[cfg(feature = "native")]
+    native_packages: HashMap<Address, NativePackage>,
+    committed_transactions: u64,
 }
 
 impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
@@ -22,6 +46,11 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
         package_address: Address,
         blueprint_name: A,
     ) -> Result<abi::Blueprint, RuntimeError> {
+        #[cfg(feature = "native")]
+        if let Some(package) = self.native_packages.get(&package_address) {
+            return package.export_abi(package_address, blueprint_name.as_ref());
+        }
+
         let p = self
             .ledger
             .get_package(package_address)
@@ -40,19 +69,95 @@ impl<'l, L: SubstateStore> AbiProvider for TransactionExecutor<'l, L> {
             .ledger
             .get_component(component_address)
             .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+        #[cfg(feature = "native")]
+        if let Some(package) = self.native_packages.get(&c.package_address()) {
+            return package.export_abi(c.package_address(), c.blueprint_name());
+        }
+
         let p = self
             .ledger
             .get_package(c.package_address())
             .ok_or(RuntimeError::PackageNotFound(c.package_address()))?;
+        // Pinned components expose the ABI of the version they run
+        let code = match c.package_version() {
+            Some(version) => p
+                .code_at(version)
+                .ok_or(RuntimeError::PackageVersionNotFound(
+                    c.package_address(),
+                    version,
+                ))?,
+            None => p.code(),
+        };
         BasicAbiProvider::new(self.trace)
-            .with_package(c.package_address(), p.code().to_vec())
+            .with_package(c.package_address(), code.to_vec())
             .export_abi(c.package_address(), c.blueprint_name())
     }
 }
 
 impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
     pub fn new(ledger: &'l mut L, trace: bool) -> Self {
-        Self { ledger, trace }
+        Self {
+            ledger,
+            trace,
+            epoch_interval: None,
+            package_limits: PackageLimits::default(),
+            execution_limits: ExecutionLimits::default(),
+            cost_table: CostTable::default(),
+            address_book: AddressBook::default(),
+            wasm_engine: default_wasm_engine(),
+            intent_hasher: Box::new(NonceHasher),
+            #[cfg(feature = "native")]
+            native_packages: HashMap::new(),
+            committed_transactions: 0,
+        }
+    }
+
+    /// Advances the ledger epoch by one after every `interval` transactions committed by
+    /// this executor, which helps testing time-locked blueprints.
+    pub fn with_epoch_interval(mut self, interval: u64) -> Self {
+        assert!(interval > 0, "Epoch interval must be positive");
+        self.epoch_interval = Some(interval);
+        self
+    }
+
+    /// Overrides the limits on the code size, exports and functions of published packages.
+    pub fn with_package_limits(mut self, limits: PackageLimits) -> Self {
+        self.package_limits = limits;
+        self
+    }
+
+    /// Overrides the limits on the call depth, engine calls and WASM memory of transactions.
+    pub fn with_execution_limits(mut self, limits: ExecutionLimits) -> Self {
+        self.execution_limits = limits;
+        self
+    }
+
+    /// Overrides the prices charged against the engine call limits, e.g. to try out another
+    /// fee model.
+    pub fn with_cost_table(mut self, cost_table: CostTable) -> Self {
+        self.cost_table = cost_table;
+        self
+    }
+
+    /// Overrides the addresses that blueprints get for well-known names, e.g. to run packages
+    /// against a ledger bootstrapped with different system addresses.
+    pub fn with_address_book(mut self, address_book: AddressBook) -> Self {
+        self.address_book = address_book;
+        self
+    }
+
+    /// Overrides the runtime which runs the WASM code of packages. By default, all executors
+    /// share one, so that modules parsed by any of them are reused by the others.
+    pub fn with_wasm_engine(mut self, wasm_engine: Arc<dyn WasmEngine>) -> Self {
+        self.wasm_engine = wasm_engine;
+        self
+    }
+
+    /// Overrides how transaction hashes are computed, e.g. to test blueprints against
+    /// particular addresses.
+    pub fn with_intent_hasher<H: IntentHasher + 'static>(mut self, hasher: H) -> Self {
+        self.intent_hasher = Box::new(hasher);
+        self
     }
 
     /// Returns an immutable reference to the ledger.
@@ -65,6 +170,75 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         self.ledger
     }
 
+    /// Returns the state of a component as of the given ledger version, if the ledger
+    /// retains history.
+    pub fn get_component_state_at(&self, address: Address, version: u64) -> Option<Vec<u8>> {
+        self.ledger
+            .get_component_at(address, version)
+            .map(|c| c.state().to_vec())
+    }
+
+    /// Exports the ABI of the blueprint, from which the given component is instantiated,
+    /// together with the fields of the component state.
+    ///
+    /// Fields are named after the state type in the ABI, or by their index if the blueprint
+    /// doesn't export it, e.g. because it was compiled before the state type was introduced.
+    pub fn export_abi_with_state(
+        &self,
+        component_address: Address,
+    ) -> Result<(abi::Blueprint, Vec<(String, Value)>), RuntimeError> {
+        let abi = self.export_abi_component(component_address)?;
+        let component = self
+            .ledger
+            .get_component(component_address)
+            .ok_or(RuntimeError::ComponentNotFound(component_address))?;
+        let state = decode_any(component.state())
+            .map_err(|e| RuntimeError::DataValidationError(DataValidationError::DecodeError(e)))?;
+
+        let values = match state {
+            Value::Struct(Fields::Named(values)) | Value::Struct(Fields::Unnamed(values)) => values,
+            _ => Vec::new(),
+        };
+        let names: Vec<String> = match &abi.state {
+            Some(Type::Struct {
+                fields: describe::Fields::Named { named },
+                ..
+            }) => named.iter().map(|(name, _)| name.clone()).collect(),
+            _ => Vec::new(),
+        };
+        let fields = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| {
+                (
+                    names.get(i).cloned().unwrap_or_else(|| i.to_string()),
+                    value,
+                )
+            })
+            .collect();
+
+        Ok((abi, fields))
+    }
+
+    /// Returns the number of bytes taken by a component, i.e. its state and the vaults and
+    /// lazy maps it owns.
+    pub fn storage_footprint(&self, component_address: Address) -> u64 {
+        self.ledger.get_storage_footprint(&component_address)
+    }
+
+    /// Returns the amount in a vault as of the given ledger version, if the ledger retains
+    /// history.
+    pub fn get_vault_amount_at(
+        &self,
+        component_address: Address,
+        vid: VaultId,
+        version: u64,
+    ) -> Option<Decimal> {
+        self.ledger
+            .get_vault_at(&component_address, &vid, version)
+            .map(|v| v.amount())
+    }
+
     /// Generates a new public key.
     pub fn new_public_key(&mut self) -> EcdsaPublicKey {
         let mut raw = [0u8; 33];
@@ -75,18 +249,39 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
 
     /// Creates an account with 1,000,000 XRD in balance.
     pub fn new_account(&mut self, key: EcdsaPublicKey) -> Address {
+        let transaction = self.new_account_transaction(key);
+        self.run_system(transaction).unwrap().component(0).unwrap()
+    }
+
+    /// Returns the system transaction which creates an account with 1,000,000 XRD in balance.
+    pub fn new_account_transaction(&self, key: EcdsaPublicKey) -> Transaction {
         let free_xrd_amount = Decimal::from(1_000_000);
 
+        TransactionBuilder::new(self)
+            .call_method(
+                SYSTEM_COMPONENT,
+                "free_xrd",
+                vec![free_xrd_amount.to_string()],
+                None,
+            )
+            .new_account_with_resource(key, free_xrd_amount, RADIX_TOKEN)
+            .build_system()
+            .unwrap()
+    }
+
+    /// Creates an account holding the given resources, which are withdrawn from the funding
+    /// account owned by `funding_key`.
+    pub fn new_account_with_resources(
+        &mut self,
+        key: EcdsaPublicKey,
+        resources: Vec<(Decimal, Address)>,
+        funding_account: Address,
+        funding_key: EcdsaPublicKey,
+    ) -> Address {
         self.run(
             TransactionBuilder::new(self)
-                .call_method(
-                    SYSTEM_COMPONENT,
-                    "free_xrd",
-                    vec![free_xrd_amount.to_string()],
-                    None,
-                )
-                .new_account_with_resource(key, free_xrd_amount, RADIX_TOKEN)
-                .build(Vec::new())
+                .new_account_with_resources(key, resources, funding_account)
+                .build(vec![funding_key])
                 .unwrap(),
         )
         .unwrap()
@@ -94,6 +289,14 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         .unwrap()
     }
 
+    /// Returns the hash of the next transaction to be executed.
+    ///
+    /// Together with `derive_component_address` and friends, this can be used to compute the
+    /// addresses that a transaction will create before running it.
+    pub fn next_transaction_hash(&self) -> H256 {
+        self.intent_hasher.hash_intent(self.ledger.get_nonce())
+    }
+
     /// Publishes a package.
     pub fn publish_package(&mut self, code: &[u8]) -> Result<Address, RuntimeError> {
         let receipt = self
@@ -105,11 +308,66 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             )
             .unwrap();
 
-        if receipt.result.is_ok() {
-            Ok(receipt.package(0).unwrap())
-        } else {
-            Err(receipt.result.err().unwrap())
+        match receipt.status {
+            TransactionStatus::Committed => Ok(receipt.package(0).unwrap()),
+            TransactionStatus::Failed { error, .. } => Err(error),
+            TransactionStatus::Rejected(reason) => {
+                panic!("Package publication rejected: {:?}", reason)
+            }
+        }
+    }
+
+    /// Publishes a package whose blueprints are compiled natively, which can be called like
+    /// any other package by the transactions run by this executor.
+    #[cfg(feature = "native")]
+    pub fn publish_native_package(&mut self, package: NativePackage) -> Address {
+        self.try_publish_native_package(package)
+            .expect("Native package dependencies should be met")
+    }
+
+    /// Publishes a package whose blueprints are compiled natively, failing if a blueprint it
+    /// depends on is missing or doesn't match the declared ABI.
+    #[cfg(feature = "native")]
+    pub fn try_publish_native_package(
+        &mut self,
+        package: NativePackage,
+    ) -> Result<Address, RuntimeError> {
+        for name in package.dependency_names() {
+            let expected = package.export_dependency(&name)?;
+            check_dependency(&expected, |address, blueprint_name| {
+                self.export_abi(address, blueprint_name)
+            })?;
+        }
+
+        let mut track = Track::new(self.ledger, self.next_transaction_hash(), Vec::new());
+        let address = track.new_package_address();
+        let mut metadata = BTreeMap::new();
+        for name in package.blueprint_names() {
+            let abi = package
+                .export_abi(address, &name)
+                .expect("Native blueprint should export a valid ABI");
+            if !abi.metadata.is_empty() {
+                metadata.insert(name, abi.metadata);
+            }
         }
+        let mut native_package = Package::new(Vec::new());
+        native_package.set_metadata(metadata);
+        track.put_package(address, native_package);
+        track.commit_with(|ledger| ledger.increase_nonce());
+
+        self.native_packages.insert(address, package);
+        Ok(address)
+    }
+
+    /// Runs the system and account packages natively, from the same source as the WASM the
+    /// ledger is bootstrapped with, which is much faster and picks up changes to them without
+    /// rebuilding the WASM.
+    ///
+    /// Native code isn't metered, so the WASM instructions of these packages aren't charged.
+    #[cfg(feature = "native")]
+    pub fn with_native_system_packages(mut self) -> Self {
+        self.native_packages.extend(system_packages());
+        self
     }
 
     /// Publishes a package to a specified address.
@@ -118,6 +376,12 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
             .put_package(address, Package::new(code.to_vec()));
     }
 
+    /// Replaces the package at a specified address with natively compiled blueprints.
+    #[cfg(feature = "native")]
+    pub fn overwrite_native_package(&mut self, address: Address, package: NativePackage) {
+        self.native_packages.insert(address, package);
+    }
+
     /// This is a convenience method that validates and runs a transaction in one shot.
     ///
     /// You might also consider `validate()` and `execute()` in this implementation.
@@ -127,107 +391,485 @@ impl<'l, L: SubstateStore> TransactionExecutor<'l, L> {
         Ok(receipt)
     }
 
+    /// Validates and runs a transaction performed by the system, see `TransactionKind`.
+    pub fn run_system(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<Receipt, TransactionValidationError> {
+        let validated_transaction =
+            validate_transaction_of_kind(&transaction, TransactionKind::System)?;
+        self.check_burns(&transaction)?;
+        let receipt = self.execute(validated_transaction);
+        Ok(receipt)
+    }
+
+    /// Makes the method calls scheduled for the given epoch or an earlier one, each in its own
+    /// system transaction, and returns their receipts in the order they were due.
+    ///
+    /// A call is removed from the scheduler once it has been made, so a call that fails is not
+    /// retried. If a call is rejected instead, it and the calls due after it are left scheduled.
+    pub fn run_due_callbacks(
+        &mut self,
+        epoch: u64,
+    ) -> Result<Vec<Receipt>, TransactionValidationError> {
+        let mut receipts = Vec::new();
+        for call in self.ledger.get_scheduler().due(epoch) {
+            let transaction = Transaction {
+                instructions: vec![
+                    Instruction::CallMethod {
+                        component_address: call.component_address,
+                        method: call.method.clone(),
+                        args: call.args.clone(),
+                    },
+                    Instruction::End {
+                        signatures: Vec::new(),
+                    },
+                ],
+                message: None,
+                nonce: None,
+                expiration_epoch: None,
+                kind: TransactionKind::System,
+            };
+            receipts.push(self.run_system(transaction)?);
+
+            let mut scheduler = self.ledger.get_scheduler();
+            scheduler.remove(&call);
+            self.ledger.put_scheduler(scheduler);
+        }
+        Ok(receipts)
+    }
+
     pub fn validate(
         &mut self,
         transaction: Transaction,
     ) -> Result<ValidatedTransaction, TransactionValidationError> {
-        validate_transaction(&transaction)
+        let validated_transaction =
+            validate_transaction_of_kind(&transaction, TransactionKind::User)?;
+        self.check_burns(&transaction)?;
+        Ok(validated_transaction)
     }
 
-    pub fn execute(&mut self, transaction: ValidatedTransaction) -> Receipt {
-        #[cfg(not(feature = "alloc"))]
-        let now = std::time::Instant::now();
-
-        let transaction_hash = sha256(self.ledger.get_nonce().to_string());
-        sha256(self.ledger.get_nonce().to_string());
-        let mut track = Track::new(self.ledger, transaction_hash, transaction.signers.clone());
-        let mut proc = track.start_process(self.trace);
-
-        let mut error: Option<RuntimeError> = None;
-        let mut outputs = vec![];
-        for inst in transaction.clone().instructions {
-            let result = match inst {
-                ValidatedInstruction::TakeFromWorktop {
-                    amount,
-                    resource_address,
-                } => proc.take_from_worktop(Resource::Fungible {
-                    amount,
-                    resource_address,
-                }),
-                ValidatedInstruction::TakeAllFromWorktop { resource_address } => {
-                    proc.take_from_worktop(Resource::All { resource_address })
+    /// Rejects burning resources from the worktop which can't be burnt without a badge, as
+    /// transactions have none to present.
+    ///
+    /// Resources created by the transaction itself are checked by the engine when burnt.
+    fn check_burns(&self, transaction: &Transaction) -> Result<(), TransactionValidationError> {
+        for (i, instruction) in transaction.instructions.iter().enumerate() {
+            if let Instruction::BurnAllFromWorktop { resource_address } = instruction {
+                let freely_burnable = self
+                    .ledger
+                    .get_resource_def(*resource_address)
+                    .map_or(true, |r| {
+                        r.is_flag_on(BURNABLE) && r.is_flag_on(FREELY_BURNABLE)
+                    });
+                if !freely_burnable {
+                    return Err(TransactionValidationError::ResourceNotFreelyBurnable {
+                        resource_address: *resource_address,
+                        instruction_index: i,
+                    });
                 }
-                ValidatedInstruction::TakeNonFungiblesFromWorktop {
-                    keys,
-                    resource_address,
-                } => proc.take_from_worktop(Resource::NonFungible {
-                    keys,
-                    resource_address,
-                }),
-                ValidatedInstruction::ReturnToWorktop { bid } => proc.return_to_worktop(bid),
-                ValidatedInstruction::AssertWorktopContains {
-                    amount,
-                    resource_address,
-                } => proc.assert_worktop_contains(amount, resource_address),
-                ValidatedInstruction::CreateBucketRef { bid } => proc.create_bucket_ref(bid),
-                ValidatedInstruction::CloneBucketRef { rid } => proc.clone_bucket_ref(rid),
-                ValidatedInstruction::DropBucketRef { rid } => proc.drop_bucket_ref(rid),
-                ValidatedInstruction::CallFunction {
-                    package_address,
-                    blueprint_name,
-                    function,
-                    args,
-                } => proc.call_function(package_address, &blueprint_name, &function, args),
-                ValidatedInstruction::CallMethod {
-                    component_address,
-                    method,
-                    args,
-                } => proc.call_method(component_address, &method, args),
-                ValidatedInstruction::CallMethodWithAllResources {
-                    component_address,
-                    method,
-                } => proc.call_method_with_all_resources(component_address, &method),
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a transaction without committing it, and returns the receipt together with the
+    /// state updates it would have made.
+    pub fn preview(
+        &mut self,
+        transaction: Transaction,
+    ) -> Result<(Receipt, StateDiff), TransactionValidationError> {
+        let validated_transaction = self.validate(transaction)?;
+        Ok(self.execute_with(validated_transaction, false))
+    }
+
+    pub fn execute(&mut self, transaction: ValidatedTransaction) -> Receipt {
+        self.execute_with(transaction, true).0
+    }
+
+    fn execute_with(
+        &mut self,
+        transaction: ValidatedTransaction,
+        commit: bool,
+    ) -> (Receipt, StateDiff) {
+        let transaction_hash = self.next_transaction_hash();
+        // System transactions are not charged for what they do
+        let cost_table = match transaction.kind {
+            TransactionKind::User => self.cost_table.clone(),
+            TransactionKind::System => CostTable::free(),
+        };
+        let track = Track::new(self.ledger, transaction_hash, transaction.signers.clone())
+            .with_package_limits(self.package_limits)
+            .with_execution_limits(self.execution_limits)
+            .with_cost_table(cost_table)
+            .with_address_book(self.address_book)
+            .with_wasm_engine(self.wasm_engine.clone());
+        #[cfg(feature = "native")]
+        let track = track.with_native_packages(self.native_packages.clone());
+
+        let committed_transactions = &mut self.committed_transactions;
+        let epoch_interval = self.epoch_interval;
+        execute_in_track(track, transaction, self.trace, commit, |ledger| {
+            advance_ledger(ledger, committed_transactions, epoch_interval)
+        })
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'l, L: SubstateStore + Sync> TransactionExecutor<'l, L> {
+    /// Runs a batch of transactions in parallel threads, and returns their receipts in order.
+    ///
+    /// The outcome is the same as running them one after another. Each transaction is run
+    /// against the ledger state before the batch, recording the substates it reads, and its
+    /// state updates are then committed in order. A transaction that read a substate updated
+    /// by an earlier transaction of the batch, or whose hash changed because an earlier one
+    /// didn't commit, is run again against the current state before being committed.
+    pub fn run_batch(
+        &mut self,
+        transactions: Vec<Transaction>,
+    ) -> Vec<Result<Receipt, TransactionValidationError>> {
+        let validated: Vec<Result<ValidatedTransaction, TransactionValidationError>> = transactions
+            .iter()
+            .map(|t| {
+                let validated = validate_transaction_of_kind(t, TransactionKind::User)?;
+                self.check_burns(t)?;
+                Ok(validated)
+            })
+            .collect();
+
+        // Assume every valid transaction commits, and thus takes the next nonce
+        let jobs: Vec<(u64, H256, ValidatedTransaction)> = (self.ledger.get_nonce()..)
+            .zip(validated.iter().flatten())
+            .map(|(nonce, transaction)| {
+                (
+                    nonce,
+                    self.intent_hasher.hash_intent(nonce),
+                    transaction.clone(),
+                )
+            })
+            .collect();
+
+        let trace = self.trace;
+        let package_limits = self.package_limits;
+        let execution_limits = self.execution_limits;
+        let cost_table = self.cost_table.clone();
+        let address_book = self.address_book;
+        let wasm_engine = self.wasm_engine.clone();
+        #[cfg(feature = "native")]
+        let native_packages = self.native_packages.clone();
+        let speculate =
+            |ledger: &L, nonce: u64, transaction_hash: H256, transaction: ValidatedTransaction| {
+                let mut store = SpeculativeStore::new(ledger, nonce);
+                let track = Track::new(&mut store, transaction_hash, transaction.signers.clone())
+                    .with_package_limits(package_limits)
+                    .with_execution_limits(execution_limits)
+                    .with_cost_table(cost_table.clone())
+                    .with_address_book(address_book)
+                    .with_wasm_engine(wasm_engine.clone());
+                #[cfg(feature = "native")]
+                let track = track.with_native_packages(native_packages.clone());
+                let (receipt, _) = execute_in_track(track, transaction, trace, true, |_| {});
+                (nonce, receipt, store.into_writes())
             };
-            match result {
-                Ok(data) => {
-                    outputs.push(data);
-                }
-                Err(e) => {
-                    error = Some(e);
-                    break;
+
+        let workers = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        let chunk_size = ((jobs.len() + workers - 1) / workers).max(1);
+        let ledger: &L = self.ledger;
+        let speculations: Vec<(u64, Receipt, SpeculativeWrites)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    let speculate = &speculate;
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|(nonce, transaction_hash, transaction)| {
+                                speculate(ledger, *nonce, *transaction_hash, transaction.clone())
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|h| h.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        });
+
+        // Commit in order, re-running the transactions whose speculation is stale
+        let mut speculations = speculations.into_iter();
+        let mut written = HashSet::new();
+        let mut results = Vec::new();
+        for transaction in validated {
+            if let Err(e) = transaction {
+                results.push(Err(e));
+                continue;
+            }
+
+            let (nonce, mut receipt, mut writes) = speculations.next().unwrap();
+            if nonce != self.ledger.get_nonce() || writes.conflicts_with(&written) {
+                let (_, r, w) = speculate(
+                    self.ledger,
+                    self.ledger.get_nonce(),
+                    self.next_transaction_hash(),
+                    receipt.transaction,
+                );
+                receipt = r;
+                writes = w;
+            }
+
+            if receipt.status.is_committed() {
+                self.ledger.begin_commit();
+                written.extend(writes.apply(self.ledger));
+                let epoch = self.ledger.get_epoch();
+                advance_ledger(
+                    self.ledger,
+                    &mut self.committed_transactions,
+                    self.epoch_interval,
+                );
+                self.ledger.end_commit();
+                if self.ledger.get_epoch() != epoch {
+                    written.insert(SubstateId::Epoch);
                 }
             }
+            results.push(Ok(receipt));
         }
+        results
+    }
+}
 
-        // check resource
-        error = error.or_else(|| match proc.check_resource() {
-            Ok(_) => None,
-            Err(e) => Some(e),
-        });
-        let new_entities = track.new_entities().to_vec();
-        let logs = track.logs().clone();
+/// Advances the nonce and, if configured, the epoch of a ledger after a transaction is
+/// committed.
+fn advance_ledger<S: SubstateStore>(
+    ledger: &mut S,
+    committed_transactions: &mut u64,
+    epoch_interval: Option<u64>,
+) {
+    ledger.increase_nonce();
 
-        // commit state updates
-        if error.is_none() {
-            track.commit();
-            self.ledger.increase_nonce();
+    *committed_transactions += 1;
+    if let Some(interval) = epoch_interval {
+        if *committed_transactions % interval == 0 {
+            ledger.set_epoch(ledger.get_epoch() + 1);
         }
+    }
+}
+
+/// Validates a transaction, which must be of the given kind.
+fn validate_transaction_of_kind(
+    transaction: &Transaction,
+    kind: TransactionKind,
+) -> Result<ValidatedTransaction, TransactionValidationError> {
+    if transaction.kind != kind {
+        return Err(TransactionValidationError::UnexpectedTransactionKind(
+            transaction.kind,
+        ));
+    }
+    validate_transaction(transaction)
+}
 
-        #[cfg(feature = "alloc")]
-        let execution_time = None;
-        #[cfg(not(feature = "alloc"))]
-        let execution_time = Some(now.elapsed().as_millis());
+/// Runs a transaction against the given track, and commits its state updates to the ledger
+/// of the track if requested and the transaction succeeds, followed by the writes of
+/// `on_commit` as part of the same commit.
+fn execute_in_track<S: SubstateStore, F: FnOnce(&mut S)>(
+    mut track: Track<S>,
+    transaction: ValidatedTransaction,
+    trace: bool,
+    commit: bool,
+    on_commit: F,
+) -> (Receipt, StateDiff) {
+    #[cfg(not(feature = "alloc"))]
+    let now = std::time::Instant::now();
 
-        Receipt {
+    if let Err(reason) = check_replay(&track, &transaction) {
+        let receipt = Receipt {
+            warnings: analyze_transaction(&transaction),
             transaction,
-            result: match error {
-                Some(error) => Err(error),
-                None => Ok(()),
-            },
-            outputs,
-            logs,
-            new_entities,
-            execution_time,
+            status: TransactionStatus::Rejected(reason),
+            outputs: Vec::new(),
+            logs: Vec::new(),
+            events: Vec::new(),
+            new_entities: Vec::new(),
+            storage_changes: Vec::new(),
+            execution_time: None,
+            cost_units: 0,
+            call_trace: Vec::new(),
+        };
+        return (receipt, StateDiff::default());
+    }
+
+    let mut proc = track.start_process(trace);
+
+    let mut error: Option<RuntimeError> = None;
+    let mut outputs = vec![];
+    let mut balance_assertions = vec![];
+    for inst in transaction.clone().instructions {
+        let result = match inst {
+            ValidatedInstruction::TakeFromWorktop {
+                amount,
+                resource_address,
+            } => proc.take_from_worktop(Resource::Fungible {
+                amount,
+                resource_address,
+            }),
+            ValidatedInstruction::TakeAllFromWorktop { resource_address } => {
+                proc.take_from_worktop(Resource::All { resource_address })
+            }
+            ValidatedInstruction::TakeNonFungiblesFromWorktop {
+                keys,
+                resource_address,
+            } => proc.take_from_worktop(Resource::NonFungible {
+                keys,
+                resource_address,
+            }),
+            ValidatedInstruction::TakeUpToFromWorktop {
+                amount,
+                resource_address,
+            } => proc.take_up_to_from_worktop(amount, resource_address),
+            ValidatedInstruction::ReturnToWorktop { bid } => proc.return_to_worktop(bid),
+            ValidatedInstruction::AssertWorktopContains {
+                amount,
+                resource_address,
+            } => proc.assert_worktop_contains(amount, resource_address),
+            ValidatedInstruction::AssertWorktopContainsNonFungibles {
+                keys,
+                resource_address,
+            } => proc.assert_worktop_contains_non_fungibles(keys, resource_address),
+            ValidatedInstruction::BurnAllFromWorktop { resource_address } => {
+                proc.burn_all_from_worktop(resource_address)
+            }
+            ValidatedInstruction::AssertAccountBalanceAtLeast {
+                account,
+                amount,
+                resource_address,
+            } => {
+                // checked once all other instructions have run
+                balance_assertions.push((account, amount, resource_address));
+                Ok(validate_data(&scrypto_encode(&())).unwrap())
+            }
+            ValidatedInstruction::CreateBucketRef { bid } => proc.create_bucket_ref(bid),
+            ValidatedInstruction::CloneBucketRef { rid } => proc.clone_bucket_ref(rid),
+            ValidatedInstruction::DropBucketRef { rid } => proc.drop_bucket_ref(rid),
+            ValidatedInstruction::CallFunction {
+                package_address,
+                blueprint_name,
+                function,
+                args,
+            } => proc.call_function(package_address, &blueprint_name, &function, args),
+            ValidatedInstruction::CallMethod {
+                component_address,
+                method,
+                args,
+            } => proc.call_method(component_address, &method, args),
+            ValidatedInstruction::CallMethodWithAllResources {
+                component_address,
+                method,
+            } => proc.call_method_with_all_resources(component_address, &method),
+        };
+        match result {
+            Ok(data) => {
+                outputs.push(data);
+            }
+            Err(e) => {
+                error = Some(e);
+                break;
+            }
+        }
+    }
+
+    // check resource
+    error = error.or_else(|| proc.check_resource().err());
+    error = error.or_else(|| track.check_vaults().err());
+    error = error.or_else(|| {
+        balance_assertions
+            .into_iter()
+            .find(|(account, amount, resource_address)| {
+                track.balance(*account, *resource_address) < *amount
+            })
+            .map(|_| RuntimeError::AssertionFailed)
+    });
+    error = error.or_else(|| track.charge_state_writes().err());
+    let new_entities = track.new_entities().to_vec();
+    let logs = track.logs().clone();
+    let events = track.events().clone();
+    let call_trace = track.call_trace().to_vec();
+    let cost_units = track.cost_units();
+    let diff = track.diff();
+
+    // commit state updates
+    if commit && error.is_none() {
+        let signers = &transaction.signers;
+        let nonce = transaction.nonce;
+        track.commit_with(|ledger| {
+            // the nonce of each signer is recorded, so that the transaction can't be replayed
+            if let Some(nonce) = nonce {
+                for signer in signers {
+                    ledger.put_signer_nonce(*signer, nonce);
+                }
+            }
+            on_commit(ledger);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    let execution_time = None;
+    #[cfg(not(feature = "alloc"))]
+    let execution_time = Some(now.elapsed().as_millis());
+
+    // a failed transaction leaves the storage untouched
+    let storage_changes = match error {
+        Some(_) => Vec::new(),
+        None => diff.storage_changes.clone(),
+    };
+
+    let receipt = Receipt {
+        warnings: analyze_transaction(&transaction),
+        transaction,
+        status: match error {
+            Some(error) => TransactionStatus::from_error(error),
+            None => TransactionStatus::Committed,
+        },
+        outputs,
+        logs,
+        events,
+        new_entities,
+        storage_changes,
+        execution_time,
+        cost_units,
+        call_trace,
+    };
+    (receipt, diff)
+}
+
+/// Checks that a transaction has neither expired nor been committed before, going by the
+/// nonces its signers last used.
+fn check_replay<S: SubstateStore>(
+    track: &Track<S>,
+    transaction: &ValidatedTransaction,
+) -> Result<(), RejectionReason> {
+    if let Some(expiration_epoch) = transaction.expiration_epoch {
+        let current_epoch = track.current_epoch();
+        if current_epoch > expiration_epoch {
+            return Err(RejectionReason::Expired {
+                expiration_epoch,
+                current_epoch,
+            });
+        }
+    }
+    if let Some(nonce) = transaction.nonce {
+        for signer in &transaction.signers {
+            if let Some(last_nonce) = track.signer_nonce(signer) {
+                if nonce <= last_nonce {
+                    return Err(RejectionReason::NonceAlreadyUsed {
+                        signer: *signer,
+                        nonce,
+                        last_nonce,
+                    });
+                }
+            }
         }
     }
+    Ok(())
 }
@@ -1,11 +1,24 @@
 mod abi_provider;
+mod analyzer;
+#[cfg(not(feature = "alloc"))]
+mod batch;
 mod builder;
 mod error;
 mod executor;
+mod signer;
+#[cfg(not(feature = "alloc"))]
+mod snapshot;
 mod validator;
 
+pub(crate) use abi_provider::decode_abi;
 pub use abi_provider::{AbiProvider, BasicAbiProvider};
+pub use analyzer::analyze_transaction;
+#[cfg(not(feature = "alloc"))]
+pub use batch::{SpeculativeStore, SpeculativeWrites};
 pub use builder::{ParseResourceError, Resource, TransactionBuilder};
 pub use error::{BuildArgsError, BuildTransactionError};
 pub use executor::TransactionExecutor;
+pub use signer::{IntentHasher, NonceHasher, TransactionSigner};
+#[cfg(not(feature = "alloc"))]
+pub use snapshot::{assert_receipt_snapshot, diff_lines, receipt_snapshot, UPDATE_SNAPSHOTS_ENV};
 pub use validator::validate_transaction;
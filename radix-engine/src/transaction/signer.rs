@@ -0,0 +1,36 @@
+use scrypto::rust::string::ToString;
+use scrypto::rust::vec::Vec;
+use scrypto::types::*;
+use scrypto::utils::*;
+
+use crate::model::*;
+
+/// Computes the hash of a transaction, from which the IDs and addresses it allocates are derived.
+pub trait IntentHasher {
+    /// Returns the hash of the transaction run when the ledger is at the given nonce.
+    fn hash_intent(&self, nonce: u64) -> H256;
+}
+
+/// The default intent hasher, which hashes the nonce in decimal with SHA-256.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NonceHasher;
+
+impl IntentHasher for NonceHasher {
+    fn hash_intent(&self, nonce: u64) -> H256 {
+        sha256(nonce.to_string())
+    }
+}
+
+/// Signs transactions, e.g. with keys held by a hardware security module or a remote service.
+pub trait TransactionSigner {
+    /// Signs a transaction, which has no `End` instruction yet, and returns the public keys
+    /// of the signatures.
+    fn sign(&self, transaction: &Transaction) -> Vec<EcdsaPublicKey>;
+}
+
+/// A list of public keys signs every transaction with all of them.
+impl TransactionSigner for Vec<EcdsaPublicKey> {
+    fn sign(&self, _transaction: &Transaction) -> Vec<EcdsaPublicKey> {
+        self.clone()
+    }
+}
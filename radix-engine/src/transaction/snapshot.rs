@@ -0,0 +1,177 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use scrypto::engine::*;
+use scrypto::types::*;
+
+use crate::model::*;
+
+/// Set this environment variable to rewrite snapshots instead of comparing against them.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Lines of unchanged text shown around each difference.
+const DIFF_CONTEXT: usize = 3;
+
+/// Renders a receipt into a canonical text form suitable for checking in.
+///
+/// Execution time is left out, and the addresses of entities created by the transaction are
+/// replaced by aliases in creation order, e.g. `component#0`, so that snapshots stay stable
+/// when address derivation changes.
+pub fn receipt_snapshot(receipt: &Receipt) -> String {
+    let mut lines = Vec::new();
+
+    lines.push(format!(
+        "Status: {}",
+        match &receipt.status {
+            TransactionStatus::Committed => "SUCCESS".to_owned(),
+            status => status.to_string(),
+        }
+    ));
+
+    lines.push("Signers:".to_owned());
+    for signer in &receipt.transaction.signers {
+        lines.push(format!("- {:?}", signer));
+    }
+
+    lines.push("Instructions:".to_owned());
+    for inst in &receipt.transaction.instructions {
+        lines.push(format!("- {:?}", inst));
+    }
+
+    if !receipt.warnings.is_empty() {
+        lines.push("Warnings:".to_owned());
+        for warning in &receipt.warnings {
+            lines.push(format!("- {:?}", warning));
+        }
+    }
+
+    lines.push("Outputs:".to_owned());
+    for output in &receipt.outputs {
+        lines.push(format!("- {:?}", output));
+    }
+
+    lines.push("Logs:".to_owned());
+    for (level, msg) in &receipt.logs {
+        let level = match level {
+            LogLevel::Error => "ERROR",
+            LogLevel::Warn => "WARN",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        };
+        lines.push(format!("- [{}] {}", level, msg));
+    }
+
+    lines.push("Events:".to_owned());
+    for event in &receipt.events {
+        let emitter = match &event.actor {
+            Actor::Blueprint(package_address, blueprint_name) => {
+                format!("{}::{}", package_address, blueprint_name)
+            }
+            Actor::Component(component_address) => component_address.to_string(),
+        };
+        lines.push(format!("- {} {} {}", event.name, emitter, event.data));
+    }
+
+    lines.push("New Entities:".to_owned());
+    for address in &receipt.new_entities {
+        lines.push(format!("- {}", address));
+    }
+
+    let mut snapshot = lines.join("\n");
+    snapshot.push('\n');
+
+    let mut counts = [0usize; 3];
+    for address in &receipt.new_entities {
+        let (i, ty) = match address {
+            Address::Package(_) => (0, "package"),
+            Address::Component(_) => (1, "component"),
+            Address::ResourceDef(_) => (2, "resource_def"),
+        };
+        snapshot = snapshot.replace(&address.to_string(), &format!("{}#{}", ty, counts[i]));
+        counts[i] += 1;
+    }
+    snapshot
+}
+
+/// Compares a receipt against the snapshot at the given path, panicking with a line diff if
+/// they differ.
+///
+/// The snapshot is written instead when it does not exist yet, or when the
+/// `UPDATE_SNAPSHOTS` environment variable is set.
+pub fn assert_receipt_snapshot<P: AsRef<Path>>(receipt: &Receipt, path: P) {
+    let path = path.as_ref();
+    let actual = receipt_snapshot(receipt);
+
+    if env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(path).unwrap();
+    if expected != actual {
+        panic!(
+            "Receipt does not match snapshot {}\n{}\nRerun with {}=1 to update the snapshot.",
+            path.display(),
+            diff_lines(&expected, &actual),
+            UPDATE_SNAPSHOTS_ENV
+        );
+    }
+}
+
+/// Produces a line diff, with removed lines prefixed by `-` and added lines by `+`.
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+
+    // longest common subsequence, computed from the back
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            ops.push((' ', a[i]));
+            i += 1;
+            j += 1;
+        } else if i < a.len() && (j == b.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', a[i]));
+            i += 1;
+        } else {
+            ops.push(('+', b[j]));
+            j += 1;
+        }
+    }
+
+    // only keep unchanged lines close to a change
+    let changed: Vec<usize> = (0..ops.len()).filter(|k| ops[*k].0 != ' ').collect();
+    let mut out = Vec::new();
+    let mut last = None;
+    for (k, (tag, line)) in ops.iter().enumerate() {
+        let near = changed
+            .iter()
+            .any(|c| k + DIFF_CONTEXT >= *c && k <= c + DIFF_CONTEXT);
+        if !near {
+            continue;
+        }
+        if matches!(last, Some(l) if l + 1 != k) {
+            out.push("...".to_owned());
+        }
+        out.push(format!("{} {}", tag, line));
+        last = Some(k);
+    }
+    out.join("\n")
+}
@@ -1,5 +1,7 @@
+use scrypto::rust::collections::*;
 use scrypto::rust::vec;
 use scrypto::rust::vec::Vec;
+use scrypto::types::*;
 
 use crate::engine::*;
 use crate::model::*;
@@ -10,40 +12,66 @@ pub fn validate_transaction(
     let mut instructions = vec![];
     let mut signers = vec![];
 
+    if let Some(message) = &transaction.message {
+        if message.len() > MAX_MESSAGE_SIZE {
+            return Err(TransactionValidationError::MessageTooLarge {
+                size: message.len(),
+                max_size: MAX_MESSAGE_SIZE,
+            });
+        }
+    }
+
     // semantic analysis
     let mut id_validator = IdValidator::new();
+    let mut bucket_origins = HashMap::new();
     for (i, inst) in transaction.instructions.iter().enumerate() {
         match inst.clone() {
             Instruction::TakeFromWorktop {
                 amount,
                 resource_address,
             } => {
-                id_validator
+                let bid = id_validator
                     .new_bucket()
                     .map_err(TransactionValidationError::IdValidatorError)?;
+                bucket_origins.insert(bid, i);
                 instructions.push(ValidatedInstruction::TakeFromWorktop {
                     amount,
                     resource_address,
                 });
             }
             Instruction::TakeAllFromWorktop { resource_address } => {
-                id_validator
+                let bid = id_validator
                     .new_bucket()
                     .map_err(TransactionValidationError::IdValidatorError)?;
+                bucket_origins.insert(bid, i);
                 instructions.push(ValidatedInstruction::TakeAllFromWorktop { resource_address });
             }
             Instruction::TakeNonFungiblesFromWorktop {
                 keys,
                 resource_address,
             } => {
-                id_validator
+                let bid = id_validator
                     .new_bucket()
                     .map_err(TransactionValidationError::IdValidatorError)?;
+                bucket_origins.insert(bid, i);
                 instructions.push(ValidatedInstruction::TakeNonFungiblesFromWorktop {
                     keys,
                     resource_address,
                 });
             }
+            Instruction::TakeUpToFromWorktop {
+                amount,
+                resource_address,
+            } => {
+                let bid = id_validator
+                    .new_bucket()
+                    .map_err(TransactionValidationError::IdValidatorError)?;
+                bucket_origins.insert(bid, i);
+                instructions.push(ValidatedInstruction::TakeUpToFromWorktop {
+                    amount,
+                    resource_address,
+                });
+            }
             Instruction::ReturnToWorktop { bid } => {
                 id_validator
                     .drop_bucket(bid)
@@ -59,6 +87,29 @@ pub fn validate_transaction(
                     resource_address,
                 });
             }
+            Instruction::AssertWorktopContainsNonFungibles {
+                keys,
+                resource_address,
+            } => {
+                instructions.push(ValidatedInstruction::AssertWorktopContainsNonFungibles {
+                    keys,
+                    resource_address,
+                });
+            }
+            Instruction::BurnAllFromWorktop { resource_address } => {
+                instructions.push(ValidatedInstruction::BurnAllFromWorktop { resource_address });
+            }
+            Instruction::AssertAccountBalanceAtLeast {
+                account,
+                amount,
+                resource_address,
+            } => {
+                instructions.push(ValidatedInstruction::AssertAccountBalanceAtLeast {
+                    account,
+                    amount,
+                    resource_address,
+                });
+            }
             Instruction::CreateBucketRef { bid } => {
                 id_validator
                     .new_bucket_ref(bid)
@@ -83,6 +134,12 @@ pub fn validate_transaction(
                 function,
                 args,
             } => {
+                if !package_address.is_package() {
+                    return Err(TransactionValidationError::ExpectedPackageAddress {
+                        got: package_address.kind(),
+                        instruction_index: i,
+                    });
+                }
                 instructions.push(ValidatedInstruction::CallFunction {
                     package_address,
                     blueprint_name,
@@ -95,6 +152,7 @@ pub fn validate_transaction(
                 method,
                 args,
             } => {
+                check_component_address(component_address, i)?;
                 instructions.push(ValidatedInstruction::CallMethod {
                     component_address,
                     method,
@@ -105,6 +163,7 @@ pub fn validate_transaction(
                 component_address,
                 method,
             } => {
+                check_component_address(component_address, i)?;
                 id_validator
                     .move_all_resources()
                     .map_err(TransactionValidationError::IdValidatorError)?;
@@ -122,12 +181,43 @@ pub fn validate_transaction(
         }
     }
 
+    if transaction.kind == TransactionKind::System && !signers.is_empty() {
+        return Err(TransactionValidationError::SignedSystemTransaction);
+    }
+
+    // reject buckets which are neither passed to a call nor returned to the worktop, as they
+    // would otherwise fail the transaction at the very end
+    if let Some(bid) = id_validator.buckets().first() {
+        return Err(TransactionValidationError::DanglingBucket {
+            bid: *bid,
+            instruction_index: bucket_origins[bid],
+        });
+    }
+
     Ok(ValidatedTransaction {
         instructions,
         signers,
+        message: transaction.message.clone(),
+        nonce: transaction.nonce,
+        expiration_epoch: transaction.expiration_epoch,
+        kind: transaction.kind,
     })
 }
 
+fn check_component_address(
+    address: Address,
+    instruction_index: usize,
+) -> Result<(), TransactionValidationError> {
+    if address.is_component() {
+        Ok(())
+    } else {
+        Err(TransactionValidationError::ExpectedComponentAddress {
+            got: address.kind(),
+            instruction_index,
+        })
+    }
+}
+
 fn validate_args(
     args: Vec<Vec<u8>>,
     id_validator: &mut IdValidator,
@@ -1,3 +1,4 @@
+use radix_engine::engine::*;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
 use radix_engine::transaction::*;
@@ -45,7 +46,7 @@ fn can_withdraw_from_my_account() {
     let result = executor.run(transaction);
 
     // Assert
-    assert!(result.unwrap().result.is_ok());
+    assert!(result.unwrap().status.is_committed());
 }
 
 #[test]
@@ -86,7 +87,149 @@ fn can_withdraw_non_fungible_from_my_account() {
 
     // Assert
     println!("{:?}", result);
-    assert!(result.unwrap().result.is_ok());
+    assert!(result.unwrap().status.is_committed());
+}
+
+#[test]
+fn cannot_withdraw_soulbound_badge_from_my_account() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("resource_def")).unwrap();
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_soulbound_badge",
+            vec![],
+            Some(account),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    let badge_address = receipt.resource_def(1).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal::one(),
+                resource_address: badge_address,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn cannot_deposit_restricted_token_into_other_account() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("resource_def")).unwrap();
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "ResourceTest",
+            "create_restricted_token",
+            vec![account.to_string()],
+            Some(account),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    assert!(receipt.status.is_committed());
+    let token_address = receipt.resource_def(1).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal::one(),
+                resource_address: token_address,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(ResourceDefError::DepositNotAllowed(address))) if *address == other_account
+    ));
+}
+
+#[test]
+fn restricted_deposit_should_only_reach_allowed_accounts() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+    let admin_badge = Address::ResourceDef([1u8; 26]);
+    let total_supply = executor
+        .ledger()
+        .get_resource_def(RADIX_TOKEN)
+        .unwrap()
+        .total_supply();
+    let mut resource_def = radix_engine::model::ResourceDef::new(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        RESTRICTED_DEPOSIT,
+        0,
+        [(admin_badge, MAY_MANAGE_DEPOSITORS)].into_iter().collect(),
+        &Some(NewSupply::Fungible {
+            amount: total_supply,
+        }),
+    )
+    .unwrap();
+    resource_def
+        .update_allowed_depositor(account, true, Some(admin_badge))
+        .unwrap();
+    executor
+        .ledger_mut()
+        .put_resource_def(RADIX_TOKEN, resource_def);
+
+    // Act
+    let mut transfer = |to: Address| {
+        let transaction = TransactionBuilder::new(&executor)
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+            .call_method_with_all_resources(to, "deposit_batch")
+            .build(vec![])
+            .unwrap();
+        executor.run(transaction).unwrap().status
+    };
+    let allowed = transfer(account);
+    let denied = transfer(other_account);
+
+    // Assert
+    assert!(allowed.is_committed());
+    assert_eq!(denied.error().unwrap().category(), FailureCategory::Auth);
+    assert!(matches!(
+        denied.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(ResourceDefError::DepositNotAllowed(address))) if *address == other_account
+    ));
 }
 
 #[test]
@@ -108,7 +251,7 @@ fn cannot_withdraw_from_other_account() {
     let result = executor.run(transaction);
 
     // Assert
-    assert!(!result.unwrap().result.is_ok());
+    assert!(!result.unwrap().status.is_committed());
 }
 
 #[test]
@@ -137,5 +280,156 @@ fn account_to_bucket_to_account() {
     let result = executor.run(transaction);
 
     // Assert
-    assert!(result.unwrap().result.is_ok());
+    assert!(result.unwrap().status.is_committed());
+}
+
+fn call_with_owner_auth(
+    executor: &mut TransactionExecutor<InMemorySubstateStore>,
+    account: Address,
+    method: &str,
+    mut args: Vec<Vec<u8>>,
+    key: EcdsaPublicKey,
+) -> Receipt {
+    let transaction = TransactionBuilder::new(executor)
+        .clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| {
+            args.push(scrypto_encode(&rid));
+            builder
+                .add_instruction(Instruction::CallMethod {
+                    component_address: account,
+                    method: method.to_owned(),
+                    args,
+                })
+                .0
+        })
+        .build(vec![key])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+fn withdraw_to_self(
+    executor: &mut TransactionExecutor<InMemorySubstateStore>,
+    account: Address,
+    key: EcdsaPublicKey,
+) -> Receipt {
+    let transaction = TransactionBuilder::new(executor)
+        .withdraw_from_account(&fungible_amount(), account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn frozen_account_should_reject_withdrawals_until_unfrozen() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+
+    // Act
+    let freeze_receipt = call_with_owner_auth(&mut executor, account, "freeze", vec![], key);
+    let frozen_receipt = withdraw_to_self(&mut executor, account, key);
+    let unfreeze_receipt = call_with_owner_auth(&mut executor, account, "unfreeze", vec![], key);
+    let unfrozen_receipt = withdraw_to_self(&mut executor, account, key);
+
+    // Assert
+    assert!(freeze_receipt.status.is_committed(), "{:?}", freeze_receipt);
+    assert!(!frozen_receipt.status.is_committed());
+    assert!(
+        unfreeze_receipt.status.is_committed(),
+        "{:?}",
+        unfreeze_receipt
+    );
+    assert!(
+        unfrozen_receipt.status.is_committed(),
+        "{:?}",
+        unfrozen_receipt
+    );
+}
+
+#[test]
+fn frozen_account_should_reject_deposits() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+    call_with_owner_auth(&mut executor, account, "freeze", vec![], key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(&fungible_amount(), other_account)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![other_key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn guarded_account_can_only_be_unfrozen_by_guardian() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let guardian_key = executor.new_public_key();
+    let guardian_account = executor.new_account(guardian_key);
+    let transaction = TransactionBuilder::new(&executor)
+        .new_badge_fixed(HashMap::new(), 1.into())
+        .call_method_with_all_resources(guardian_account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    let guardian = executor.run(transaction).unwrap().resource_def(0).unwrap();
+    call_with_owner_auth(
+        &mut executor,
+        account,
+        "set_guardian",
+        vec![scrypto_encode(&Some(guardian))],
+        key,
+    );
+    call_with_owner_auth(&mut executor, account, "freeze", vec![], key);
+
+    // Act
+    let owner_receipt = call_with_owner_auth(&mut executor, account, "unfreeze", vec![], key);
+    let badge = Resource::Fungible {
+        amount: 1.into(),
+        resource_address: guardian,
+    };
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(&badge, guardian_account)
+        .take_from_worktop(&badge, |builder, bid| {
+            builder
+                .create_bucket_ref(bid, |builder, rid| {
+                    builder
+                        .add_instruction(Instruction::CallMethod {
+                            component_address: account,
+                            method: "unfreeze".to_owned(),
+                            args: vec![scrypto_encode(&rid)],
+                        })
+                        .0
+                })
+                .add_instruction(Instruction::ReturnToWorktop { bid })
+                .0
+        })
+        .call_method_with_all_resources(guardian_account, "deposit_batch")
+        .build(vec![guardian_key])
+        .unwrap();
+    let guardian_receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(!owner_receipt.status.is_committed());
+    assert!(
+        guardian_receipt.status.is_committed(),
+        "{:?}",
+        guardian_receipt
+    );
+    assert!(withdraw_to_self(&mut executor, account, key)
+        .status
+        .is_committed());
 }
@@ -0,0 +1,92 @@
+use radix_engine::ledger::*;
+use radix_engine::model::Instruction;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+fn new_executor(
+    ledger: &mut InMemorySubstateStore,
+) -> TransactionExecutor<'_, InMemorySubstateStore> {
+    TransactionExecutor::new(ledger, false).with_native_system_packages()
+}
+
+#[test]
+fn new_account_can_be_funded_in_one_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = new_executor(&mut ledger);
+    let funding_key = executor.new_public_key();
+    let funding_account = executor.new_account(funding_key);
+    let key = executor.new_public_key();
+
+    // Act
+    let account = executor.new_account_with_resources(
+        key,
+        vec![(Decimal::from(100), RADIX_TOKEN)],
+        funding_account,
+        funding_key,
+    );
+
+    // Assert
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_account_balance_at_least(account, Decimal::from(100), RADIX_TOKEN)
+        .assert_account_balance_at_least(funding_account, Decimal::from(999_900), RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    assert!(executor.run(transaction).unwrap().status.is_committed());
+}
+
+#[test]
+fn new_account_funding_requires_signature_of_funding_account() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = new_executor(&mut ledger);
+    let funding_key = executor.new_public_key();
+    let funding_account = executor.new_account(funding_key);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .new_account_with_resources(
+            key,
+            vec![(Decimal::from(100), RADIX_TOKEN)],
+            funding_account,
+        )
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn new_account_with_resources_should_pass_all_buckets() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let executor = new_executor(&mut ledger);
+    let key = EcdsaPublicKey([0; 33]);
+
+    // Act
+    let instructions = TransactionBuilder::new(&executor)
+        .new_account_with_resources(
+            key,
+            vec![
+                (Decimal::from(1), RADIX_TOKEN),
+                (Decimal::from(2), RADIX_TOKEN),
+            ],
+            SYSTEM_COMPONENT,
+        )
+        .build(vec![])
+        .unwrap()
+        .instructions;
+
+    // Assert
+    match &instructions[instructions.len() - 2] {
+        Instruction::CallFunction { function, args, .. } => {
+            assert_eq!(function, "with_buckets");
+            let bids: Vec<BucketId> = scrypto_decode(&args[1]).unwrap();
+            assert_eq!(bids.len(), 2);
+        }
+        _ => panic!("Unexpected instruction"),
+    }
+}
@@ -0,0 +1,148 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn address_derivation_is_stable() {
+    // Arrange
+    let transaction_hash = sha256("0");
+    let id = IdSpace::Application.range().start;
+
+    // Act
+    let package_address = derive_package_address(transaction_hash, id);
+    let component_address = derive_component_address(transaction_hash, id + 1);
+    let resource_address = derive_resource_address(transaction_hash, id + 2);
+
+    // Assert
+    assert_eq!(
+        package_address,
+        Address::from_str("0124c5afc33cf45c06633d8fc0b0dfba2c82f14ec82ff7eb13483c").unwrap()
+    );
+    assert_eq!(
+        component_address,
+        Address::from_str("02777c3b5cb0bb3a2b572ccf235228d08fb0952e907eab2d718739").unwrap()
+    );
+    assert_eq!(
+        resource_address,
+        Address::from_str("03662bb6bfc0b8d1987ae7e22014acf8704366f1a9e19c1182b2a1").unwrap()
+    );
+}
+
+#[test]
+fn can_precompute_account_address() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let transaction_hash = executor.next_transaction_hash();
+
+    // Act
+    let account = executor.new_account(key);
+
+    // Assert
+    assert_eq!(account, derive_component_address(transaction_hash, 1027));
+}
+
+#[test]
+fn vid_derivation_is_stable() {
+    // Arrange
+    let transaction_hash = sha256("0");
+    let id = IdSpace::Application.range().start;
+
+    // Act
+    let vid = derive_vid(transaction_hash, id);
+
+    // Assert
+    assert_eq!(
+        vid.to_string(),
+        "5feceb66ffc86f38d952786c6d696c79c2dbc239dd4e91b46729d73a27fb57e900040000"
+    );
+    assert_eq!(VaultId::from_str(&vid.to_string()).unwrap(), vid);
+}
+
+#[test]
+fn can_precompute_account_vault_id() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let transaction_hash = executor.next_transaction_hash();
+
+    // Act
+    let account = executor.new_account(key);
+
+    // Assert
+    assert!(executor
+        .ledger()
+        .get_vault(&account, &derive_vid(transaction_hash, 1026))
+        .is_some());
+}
+
+#[test]
+fn calling_method_on_package_address_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = Transaction {
+        instructions: vec![
+            Instruction::CallMethod {
+                component_address: SYSTEM_PACKAGE,
+                method: "free_xrd".to_owned(),
+                args: vec![scrypto_encode(&Decimal::from(100))],
+            },
+            Instruction::End { signatures: vec![] },
+        ],
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    };
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::ExpectedComponentAddress {
+            got: AddressKind::Package,
+            instruction_index: 0,
+        })
+    ));
+}
+
+#[test]
+fn calling_function_on_component_address_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = Transaction {
+        instructions: vec![
+            Instruction::CallFunction {
+                package_address: SYSTEM_COMPONENT,
+                blueprint_name: "System".to_owned(),
+                function: "new".to_owned(),
+                args: vec![],
+            },
+            Instruction::End { signatures: vec![] },
+        ],
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    };
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::ExpectedPackageAddress {
+            got: AddressKind::Component,
+            instruction_index: 0,
+        })
+    ));
+}
@@ -0,0 +1,68 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Registry {}
+
+    impl Registry {
+        pub fn radix_token() -> Address {
+            Context::well_known_address(WellKnownAddress::RadixToken)
+        }
+
+        pub fn account_package() -> Address {
+            Context::well_known_address(WellKnownAddress::AccountPackage)
+        }
+    }
+}
+
+fn call(executor: &mut TransactionExecutor<InMemorySubstateStore>, function: &str) -> Address {
+    let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+        "Registry",
+        Registry_main,
+        Registry_abi,
+    ));
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(package, "Registry", function, vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    receipt.expect_commit_success().output(0)
+}
+
+#[test]
+fn well_known_addresses_default_to_bootstrap_entities() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let radix_token = call(&mut executor, "radix_token");
+    let account_package = call(&mut executor, "account_package");
+
+    // Assert
+    assert_eq!(radix_token, RADIX_TOKEN);
+    assert_eq!(account_package, ACCOUNT_PACKAGE);
+}
+
+#[test]
+fn well_known_addresses_follow_executor_address_book() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let radix_token =
+        Address::from_str("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d").unwrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_address_book(AddressBook {
+            radix_token,
+            ..AddressBook::default()
+        });
+
+    // Act
+    let resolved_token = call(&mut executor, "radix_token");
+    let resolved_package = call(&mut executor, "account_package");
+
+    // Assert
+    assert_eq!(resolved_token, radix_token);
+    assert_eq!(resolved_package, ACCOUNT_PACKAGE);
+}
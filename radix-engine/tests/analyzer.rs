@@ -0,0 +1,137 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::buffer::*;
+use scrypto::types::*;
+
+fn analyze(instructions: Vec<Instruction>) -> Vec<TransactionWarning> {
+    let validated = validate_transaction(&Transaction {
+        instructions,
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    })
+    .unwrap();
+    analyze_transaction(&validated)
+}
+
+fn call_system(args: Vec<Vec<u8>>) -> Instruction {
+    Instruction::CallMethod {
+        component_address: SYSTEM_COMPONENT,
+        method: "free_xrd".to_owned(),
+        args,
+    }
+}
+
+#[test]
+fn transaction_without_call_should_be_flagged() {
+    let warnings = analyze(vec![Instruction::End { signatures: vec![] }]);
+
+    assert_eq!(warnings, vec![TransactionWarning::NoCall]);
+}
+
+#[test]
+fn duplicate_signer_should_be_flagged() {
+    let signer = EcdsaPublicKey([2u8; 33]);
+    let warnings = analyze(vec![
+        call_system(vec![]),
+        Instruction::End {
+            signatures: vec![signer, signer],
+        },
+    ]);
+
+    assert_eq!(
+        warnings,
+        vec![TransactionWarning::DuplicateSigner { signer }]
+    );
+}
+
+#[test]
+fn zero_amounts_should_be_flagged() {
+    let warnings = analyze(vec![
+        Instruction::AssertWorktopContains {
+            amount: Decimal::zero(),
+            resource_address: RADIX_TOKEN,
+        },
+        Instruction::TakeFromWorktop {
+            amount: Decimal::one(),
+            resource_address: RADIX_TOKEN,
+        },
+        call_system(vec![scrypto_encode(&BucketId::new(512))]),
+        Instruction::End { signatures: vec![] },
+    ]);
+
+    assert_eq!(
+        warnings,
+        vec![TransactionWarning::ZeroAmount {
+            instruction_index: 0
+        }]
+    );
+}
+
+#[test]
+fn unused_bucket_ref_should_be_flagged() {
+    let warnings = analyze(vec![
+        Instruction::CloneBucketRef {
+            rid: ECDSA_TOKEN_RID,
+        },
+        Instruction::CloneBucketRef {
+            rid: ECDSA_TOKEN_RID,
+        },
+        call_system(vec![scrypto_encode(&Rid(513))]),
+        Instruction::End { signatures: vec![] },
+    ]);
+
+    assert_eq!(
+        warnings,
+        vec![TransactionWarning::UnusedBucketRef {
+            rid: Rid(512),
+            instruction_index: 0
+        }]
+    );
+}
+
+#[test]
+fn receipt_should_include_validated_transaction_and_warnings() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_worktop_contains(Decimal::zero(), RADIX_TOKEN)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+        .take_from_worktop(
+            &Resource::Fungible {
+                amount: Decimal::one(),
+                resource_address: RADIX_TOKEN,
+            },
+            |builder, bid| {
+                builder
+                    .add_instruction(Instruction::ReturnToWorktop { bid })
+                    .0
+            },
+        )
+        .call_method_with_all_resources(SYSTEM_COMPONENT, "free_xrd")
+        .build(vec![])
+        .unwrap();
+
+    // Act
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.transaction.instructions[2],
+        ValidatedInstruction::TakeFromWorktop { .. }
+    ));
+    assert!(matches!(
+        receipt.transaction.instructions[3],
+        ValidatedInstruction::ReturnToWorktop { bid } if bid == BucketId::new(512)
+    ));
+    assert_eq!(
+        receipt.warnings,
+        vec![TransactionWarning::ZeroAmount {
+            instruction_index: 0
+        }]
+    );
+}
@@ -34,5 +34,15 @@ blueprint! {
             let bucket = Self::create_test_badge(100);
             (bucket.amount(), bucket.resource_address(), bucket)
         }
+
+        pub fn check_metadata() -> Bucket {
+            let bucket = Self::create_test_badge(1);
+            let bucket_ref = bucket.present();
+            assert!(bucket_ref.has_metadata("name", "TestBadge"));
+            assert!(!bucket_ref.has_metadata("name", "OtherBadge"));
+            assert!(!bucket_ref.has_metadata("role", "admin"));
+            bucket_ref.drop();
+            bucket
+        }
     }
 }
@@ -0,0 +1,79 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+#[test]
+fn balance_assertion_should_pass_if_balance_is_kept() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_account_balance_at_least(account, Decimal::from(1_000_000), RADIX_TOKEN)
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn balance_assertion_should_be_checked_after_all_instructions() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_account_balance_at_least(account, Decimal::from(1_000_100), RADIX_TOKEN)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn balance_assertion_should_fail_if_spent_too_much() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal::from(10),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .assert_account_balance_at_least(account, Decimal::from(999_995), RADIX_TOKEN)
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status,
+        TransactionStatus::Failed {
+            error: RuntimeError::AssertionFailed,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,242 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{
+    Instruction, NonFungible, Receipt, Transaction, TransactionKind, TransactionValidationError,
+};
+use radix_engine::transaction::*;
+use sbor::{Decode, TypeId};
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Counter {
+        count: u32,
+    }
+
+    impl Counter {
+        pub fn new() -> Component {
+            Self { count: 0 }.instantiate()
+        }
+
+        pub fn increment(&mut self) {
+            self.count += 1;
+        }
+
+        pub fn fail(&mut self) {
+            self.count += 1;
+            panic!("Failed on purpose");
+        }
+    }
+}
+
+/// The state of a `Counter`.
+#[derive(TypeId, Decode)]
+struct CounterState {
+    count: u32,
+}
+
+fn counter_package() -> NativePackage {
+    NativePackage::new().with_blueprint("Counter", Counter_main, Counter_abi)
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore, package: Address) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        executor.overwrite_native_package(package, counter_package());
+        Self { executor, package }
+    }
+
+    fn new_counter(&self) -> Transaction {
+        TransactionBuilder::new(&self.executor)
+            .call_function(self.package, "Counter", "new", vec![], None)
+            .build(vec![])
+            .unwrap()
+    }
+
+    fn call(&self, counter: Address, method: &str) -> Transaction {
+        TransactionBuilder::new(&self.executor)
+            .call_method(counter, method, vec![], None)
+            .build(vec![])
+            .unwrap()
+    }
+
+    fn count(&self, counter: Address) -> u32 {
+        let component = self.executor.ledger().get_component(counter).unwrap();
+        let state: CounterState = scrypto_decode(component.state()).unwrap();
+        state.count
+    }
+}
+
+/// Returns a bootstrapped ledger with the counter package and two counters.
+fn setup() -> (InMemorySubstateStore, Address, Address, Address) {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(counter_package());
+    let mut counters = Vec::new();
+    for _ in 0..2 {
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Counter", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        counters.push(executor.run(transaction).unwrap().component(0).unwrap());
+    }
+    (ledger, package, counters[0], counters[1])
+}
+
+#[test]
+fn independent_transactions_should_all_commit() {
+    // Arrange
+    let (mut ledger, package, first, second) = setup();
+    let mut env = TestEnv::new(&mut ledger, package);
+    let transactions = vec![env.call(first, "increment"), env.call(second, "increment")];
+
+    // Act
+    let receipts = env.executor.run_batch(transactions);
+
+    // Assert
+    assert!(receipts
+        .iter()
+        .all(|r| r.as_ref().unwrap().status.is_committed()));
+    assert_eq!(env.count(first), 1);
+    assert_eq!(env.count(second), 1);
+}
+
+#[test]
+fn conflicting_transactions_should_see_earlier_updates() {
+    // Arrange
+    let (mut ledger, package, first, _) = setup();
+    let mut env = TestEnv::new(&mut ledger, package);
+    let transactions = vec![
+        env.call(first, "increment"),
+        env.call(first, "increment"),
+        env.call(first, "increment"),
+    ];
+
+    // Act
+    let receipts = env.executor.run_batch(transactions);
+
+    // Assert
+    assert_eq!(receipts.len(), 3);
+    assert_eq!(env.count(first), 3);
+}
+
+#[test]
+fn batch_should_match_sequential_execution() {
+    // Arrange
+    let (mut ledger, package, first, second) = setup();
+    let mut sequential_ledger = ledger.clone();
+    let mut env = TestEnv::new(&mut ledger, package);
+    let transactions = vec![
+        env.new_counter(),
+        env.call(first, "increment"),
+        env.call(second, "fail"),
+        env.new_counter(),
+        env.call(second, "increment"),
+        env.call(first, "increment"),
+    ];
+
+    // Act
+    let receipts = env.executor.run_batch(transactions.clone());
+    let mut sequential_env = TestEnv::new(&mut sequential_ledger, package);
+    let expected: Vec<Receipt> = transactions
+        .into_iter()
+        .map(|t| sequential_env.executor.run(t).unwrap())
+        .collect();
+
+    // Assert
+    for (receipt, expected) in receipts.iter().zip(&expected) {
+        let receipt = receipt.as_ref().unwrap();
+        assert_eq!(
+            receipt.status.is_committed(),
+            expected.status.is_committed()
+        );
+        assert_eq!(receipt.new_entities, expected.new_entities);
+    }
+    assert!(!expected[2].status.is_committed());
+    for counter in [first, second, expected[3].component(0).unwrap()] {
+        assert_eq!(env.count(counter), sequential_env.count(counter));
+    }
+    assert_eq!(
+        env.executor.ledger().get_nonce(),
+        sequential_env.executor.ledger().get_nonce()
+    );
+}
+
+#[test]
+fn invalid_transaction_should_be_rejected_in_place() {
+    // Arrange
+    let (mut ledger, package, first, _) = setup();
+    let mut env = TestEnv::new(&mut ledger, package);
+    let invalid = Transaction {
+        instructions: vec![
+            Instruction::End { signatures: vec![] },
+            Instruction::End { signatures: vec![] },
+        ],
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    };
+    let transactions = vec![
+        env.call(first, "increment"),
+        invalid,
+        env.call(first, "increment"),
+    ];
+
+    // Act
+    let receipts = env.executor.run_batch(transactions);
+
+    // Assert
+    assert!(receipts[0].as_ref().unwrap().status.is_committed());
+    assert!(matches!(
+        receipts[1],
+        Err(TransactionValidationError::UnexpectedEnd)
+    ));
+    assert!(receipts[2].as_ref().unwrap().status.is_committed());
+    assert_eq!(env.count(first), 2);
+}
+
+#[test]
+fn component_instantiated_after_scan_should_conflict_with_it() {
+    // Arrange
+    let (mut ledger, package, first, _) = setup();
+    let nonce = ledger.get_nonce();
+    let scanner = SpeculativeStore::new(&ledger, nonce);
+    scanner.scan_components(package, None, 10);
+    let scanned = scanner.into_writes();
+
+    // Act
+    let mut writer = SpeculativeStore::new(&ledger, nonce);
+    let component = writer.get_component(first).unwrap();
+    writer.put_component(Address::Component([1u8; 26]), component);
+    let written = writer.into_writes().apply(&mut ledger);
+
+    // Assert
+    assert!(scanned.conflicts_with(&written));
+}
+
+#[test]
+fn non_fungible_minted_after_scan_should_conflict_with_it() {
+    // Arrange
+    let (mut ledger, _, _, _) = setup();
+    let nonce = ledger.get_nonce();
+    let scanner = SpeculativeStore::new(&ledger, nonce);
+    scanner.scan_non_fungibles(ECDSA_TOKEN, None, 10);
+    let scanned = scanner.into_writes();
+
+    // Act
+    let mut writer = SpeculativeStore::new(&ledger, nonce);
+    writer.put_non_fungible(
+        ECDSA_TOKEN,
+        &NonFungibleKey::from(1u128),
+        NonFungible::new(Vec::new(), Vec::new()),
+    );
+    let written = writer.into_writes().apply(&mut ledger);
+
+    // Assert
+    assert!(scanned.conflicts_with(&written));
+}
@@ -0,0 +1,72 @@
+use radix_engine::ledger::*;
+use radix_engine::model::{Package, ResourceDef};
+use scrypto::prelude::*;
+
+#[test]
+fn bootstrapped_store_should_pass_check() {
+    // Arrange
+    let ledger = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let result = ledger.check_bootstrap();
+
+    // Assert
+    assert_eq!(result, Ok(()));
+}
+
+#[test]
+fn empty_store_should_fail_check() {
+    // Arrange
+    let ledger = InMemorySubstateStore::new();
+
+    // Act
+    let result = ledger.check_bootstrap();
+
+    // Assert
+    assert_eq!(result, Err(BootstrapError::PackageNotFound(SYSTEM_PACKAGE)));
+}
+
+#[test]
+fn stale_system_package_should_fail_check() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let stale_code = include_bytes!("../../assets/account.wasm").to_vec();
+    ledger.put_package(SYSTEM_PACKAGE, Package::new(stale_code.clone()));
+
+    // Act
+    let result = ledger.check_bootstrap();
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(BootstrapError::PackageCodeMismatch { package_address, actual, .. })
+            if package_address == SYSTEM_PACKAGE && actual == sha256(&stale_code)
+    ));
+}
+
+#[test]
+fn tampered_resource_def_should_fail_check() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    ledger.put_resource_def(
+        ECDSA_TOKEN,
+        ResourceDef::new(
+            ResourceType::NonFungible,
+            HashMap::new(),
+            MINTABLE,
+            0,
+            HashMap::new(),
+            &None,
+        )
+        .unwrap(),
+    );
+
+    // Act
+    let result = ledger.check_bootstrap();
+
+    // Assert
+    assert_eq!(
+        result,
+        Err(BootstrapError::ResourceDefMismatch(ECDSA_TOKEN))
+    );
+}
@@ -0,0 +1,128 @@
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use sbor::describe::*;
+use sbor::type_id::*;
+use sbor::{Encode, Encoder};
+use scrypto::abi;
+use scrypto::buffer::*;
+use scrypto::prelude::*;
+
+/// Provides a single blueprint with a function taking an optional bucket.
+struct OptionalBucketAbiProvider;
+
+impl AbiProvider for OptionalBucketAbiProvider {
+    fn export_abi<S: AsRef<str>>(
+        &self,
+        package_address: Address,
+        blueprint_name: S,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        Ok(abi::Blueprint {
+            package: package_address.to_string(),
+            name: blueprint_name.as_ref().to_owned(),
+            functions: vec![abi::Function {
+                name: "deposit".to_owned(),
+                inputs: vec![Option::<scrypto::resource::Bucket>::describe()],
+                output: Type::Unit,
+            }],
+            methods: vec![],
+            state: None,
+            metadata: BTreeMap::new(),
+            non_fungible_data: vec![],
+        })
+    }
+
+    fn export_abi_component(
+        &self,
+        component_address: Address,
+    ) -> Result<abi::Blueprint, RuntimeError> {
+        Err(RuntimeError::ComponentNotFound(component_address))
+    }
+}
+
+fn build_deposit(arg: &str) -> Vec<Instruction> {
+    TransactionBuilder::new(&OptionalBucketAbiProvider)
+        .call_function(
+            SYSTEM_PACKAGE,
+            "Depositor",
+            "deposit",
+            vec![arg.to_owned()],
+            None,
+        )
+        .build(vec![])
+        .unwrap()
+        .instructions
+}
+
+#[test]
+fn empty_argument_should_be_passed_as_none() {
+    // Act
+    let instructions = build_deposit("");
+
+    // Assert
+    assert_eq!(instructions.len(), 2);
+    match &instructions[0] {
+        Instruction::CallFunction { args, .. } => {
+            let arg: Option<BucketId> = scrypto_decode(&args[0]).unwrap();
+            assert_eq!(arg, None);
+        }
+        i => panic!("Unexpected instruction: {:?}", i),
+    }
+}
+
+#[test]
+fn resource_argument_should_be_passed_as_some_bucket() {
+    // Act
+    let instructions = build_deposit(&format!("1,{}", RADIX_TOKEN));
+
+    // Assert
+    assert!(matches!(
+        instructions[0],
+        Instruction::TakeFromWorktop { resource_address, .. } if resource_address == RADIX_TOKEN
+    ));
+    match &instructions[1] {
+        Instruction::CallFunction { args, .. } => {
+            let arg: Option<BucketId> = scrypto_decode(&args[0]).unwrap();
+            assert!(arg.is_some());
+        }
+        i => panic!("Unexpected instruction: {:?}", i),
+    }
+}
+
+#[test]
+fn metadata_should_be_encoded_in_key_order() {
+    // Arrange
+    let mut metadata = HashMap::new();
+    for (key, value) in [
+        ("symbol", "FOO"),
+        ("name", "Foo"),
+        ("url", "https://foo.io"),
+    ] {
+        metadata.insert(key.to_owned(), value.to_owned());
+    }
+
+    // Act
+    let instructions = TransactionBuilder::new(&OptionalBucketAbiProvider)
+        .new_token_fixed(metadata.clone(), Decimal::from(100))
+        .build(vec![])
+        .unwrap()
+        .instructions;
+
+    // Assert
+    match &instructions[0] {
+        Instruction::CallFunction { args, .. } => {
+            let mut expected = Encoder::with_type(Vec::new());
+            expected.write_type(TYPE_HASH_MAP);
+            expected.write_type(TYPE_STRING);
+            expected.write_type(TYPE_STRING);
+            expected.write_len(3);
+            for (key, value) in metadata.iter().collect::<BTreeMap<_, _>>() {
+                key.encode_value(&mut expected);
+                value.encode_value(&mut expected);
+            }
+            assert_eq!(args[1], Vec::<u8>::from(expected));
+            let decoded: HashMap<String, String> = scrypto_decode(&args[1]).unwrap();
+            assert_eq!(decoded, metadata);
+        }
+        i => panic!("Unexpected instruction: {:?}", i),
+    }
+}
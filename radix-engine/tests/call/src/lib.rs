@@ -1 +1,3 @@
 pub mod moving;
+pub mod reentrancy;
+pub mod reentrant;
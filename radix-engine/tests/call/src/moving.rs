@@ -1,5 +1,9 @@
+use core::sync::atomic::{AtomicU32, Ordering};
 use scrypto::prelude::*;
 
+// Lives in the memory of the module instance, so it counts the calls made in that instance.
+static CALLS: AtomicU32 = AtomicU32::new(0);
+
 blueprint! {
     struct MoveTest {
         vaults: Vec<Vault>,
@@ -37,5 +41,42 @@ blueprint! {
 
             bucket
         }
+
+        pub fn move_buckets_in_batch() {
+            let components = [
+                MoveTest { vaults: Vec::new() }.instantiate(),
+                MoveTest { vaults: Vec::new() }.instantiate(),
+            ];
+            let calls = (0..3)
+                .map(|i| {
+                    (
+                        components[i % 2].address(),
+                        "receive_bucket".to_owned(),
+                        args!(Self::create_test_token(1000)),
+                    )
+                })
+                .collect();
+            let rtns = call_methods(calls);
+            assert_eq!(rtns.len(), 3);
+        }
+
+        pub fn count_call(&self) -> u32 {
+            CALLS.fetch_add(1, Ordering::Relaxed) + 1
+        }
+
+        pub fn count_calls_in_batch() -> Vec<u32> {
+            let components = [
+                MoveTest { vaults: Vec::new() }.instantiate(),
+                MoveTest { vaults: Vec::new() }.instantiate(),
+            ];
+            let calls = [0, 1, 0]
+                .iter()
+                .map(|i| (components[*i].address(), "count_call".to_owned(), args!()))
+                .collect();
+            call_methods(calls)
+                .iter()
+                .map(|rtn| scrypto_decode(rtn).unwrap())
+                .collect()
+        }
     }
 }
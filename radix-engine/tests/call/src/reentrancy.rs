@@ -0,0 +1,24 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct ReentrancyTest {
+        calls: u32,
+    }
+
+    impl ReentrancyTest {
+        pub fn new() -> Component {
+            Self { calls: 0 }.instantiate()
+        }
+
+        pub fn call_self(&mut self) {
+            self.calls += 1;
+            if let Actor::Component(address) = Context::actor() {
+                call_method(address, "get_calls", args!());
+            }
+        }
+
+        pub fn get_calls(&self) -> u32 {
+            self.calls
+        }
+    }
+}
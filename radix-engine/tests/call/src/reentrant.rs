@@ -0,0 +1,25 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    #[reentrant]
+    struct ReentrantTest {
+        calls: u32,
+    }
+
+    impl ReentrantTest {
+        pub fn new() -> Component {
+            Self { calls: 0 }.instantiate()
+        }
+
+        pub fn call_self(&mut self) {
+            self.calls += 1;
+            if let Actor::Component(address) = Context::actor() {
+                call_method(address, "get_calls", args!());
+            }
+        }
+
+        pub fn get_calls(&self) -> u32 {
+            self.calls
+        }
+    }
+}
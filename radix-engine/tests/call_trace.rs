@@ -0,0 +1,121 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{CallFrame, CallKind};
+use radix_engine::transaction::*;
+use scrypto::engine::CALL_FUNCTION;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Outer {}
+
+    impl Outer {
+        pub fn call_inner(package: Address) -> u32 {
+            let rtn = call_function(package, "Inner", "answer", vec![]);
+            scrypto_decode(&rtn).unwrap()
+        }
+    }
+}
+
+blueprint! {
+    struct Inner {}
+
+    impl Inner {
+        pub fn answer() -> u32 {
+            info!("Answering");
+            42
+        }
+    }
+}
+
+fn run(trace: bool) -> (Address, Vec<CallFrame>) {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, trace);
+    let package = executor.publish_native_package(
+        NativePackage::new()
+            .with_blueprint("Outer", Outer_main, Outer_abi)
+            .with_blueprint("Inner", Inner_main, Inner_abi),
+    );
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "Outer",
+            "call_inner",
+            vec![package.to_string()],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    (package, receipt.call_trace)
+}
+
+fn invocation(frame: &CallFrame) -> (Actor, String) {
+    match &frame.kind {
+        CallKind::Invocation { actor, function } => (actor.clone(), function.clone()),
+        kind => panic!("Expected an invocation, got {:?}", kind),
+    }
+}
+
+fn engine_call(frame: &CallFrame) -> &'static str {
+    match &frame.kind {
+        CallKind::EngineCall { name, .. } => name,
+        kind => panic!("Expected an engine call, got {:?}", kind),
+    }
+}
+
+#[test]
+fn call_trace_should_be_empty_without_tracing() {
+    // Arrange & Act
+    let (_, call_trace) = run(false);
+
+    // Assert
+    assert!(call_trace.is_empty());
+}
+
+#[test]
+fn call_trace_should_nest_invocations_under_engine_calls() {
+    // Arrange & Act
+    let (package, call_trace) = run(true);
+
+    // Assert
+    assert_eq!(call_trace.len(), 1);
+    let outer = &call_trace[0];
+    match invocation(outer) {
+        (Actor::Blueprint(address, name), function) => {
+            assert_eq!((address, name.as_str()), (package, "Outer"));
+            assert_eq!(function, "call_inner");
+        }
+        actor => panic!("Unexpected actor: {:?}", actor),
+    }
+    let call = outer
+        .children
+        .iter()
+        .find(|c| engine_call(c) == "CallFunction")
+        .unwrap();
+    assert!(matches!(call.kind, CallKind::EngineCall { op, .. } if op == CALL_FUNCTION));
+    assert_eq!(call.children.len(), 1);
+    let inner = &call.children[0];
+    assert_eq!(invocation(inner).1, "answer");
+    assert!(inner.children.iter().any(|c| engine_call(c) == "EmitLog"));
+    assert_eq!(inner.output_size, scrypto_encode(&42u32).len());
+}
+
+#[test]
+fn call_trace_cost_should_include_children() {
+    // Arrange & Act
+    let (_, call_trace) = run(true);
+
+    // Assert
+    fn check(frame: &CallFrame) {
+        let own = matches!(frame.kind, CallKind::EngineCall { .. }) as u32;
+        assert_eq!(
+            frame.cost,
+            own + frame.children.iter().map(|c| c.cost).sum::<u32>()
+        );
+        assert!(frame.time.is_some());
+        frame.children.iter().for_each(check);
+    }
+    check(&call_trace[0]);
+    assert!(call_trace[0].cost > call_trace[0].children.len() as u32);
+}
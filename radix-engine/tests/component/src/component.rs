@@ -25,6 +25,12 @@ blueprint! {
             Component::from(address).blueprint()
         }
 
+        pub fn read_component_state(address: Address) -> String {
+            Component::from(address)
+                .state::<Self>(Context::package_address())
+                .secret
+        }
+
         pub fn get_component_state(&self) -> String {
             self.secret.clone()
         }
@@ -0,0 +1,249 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Guard {
+        a: ResourceDef,
+        b: ResourceDef,
+    }
+
+    impl Guard {
+        pub fn new(a: Address, b: Address) -> Component {
+            Self {
+                a: a.into(),
+                b: b.into(),
+            }
+            .instantiate()
+        }
+
+        #[auth(a)]
+        pub fn open_with_a(&self) -> bool {
+            true
+        }
+
+        pub fn open_with_both(&self, auth: BucketRef) -> bool {
+            let authorized = auth.contains(self.a.clone()) && auth.contains(self.b.clone());
+            auth.drop();
+            authorized
+        }
+    }
+}
+
+blueprint! {
+    struct Holder {
+        a: Vault,
+        b: Vault,
+    }
+
+    impl Holder {
+        pub fn new() -> Component {
+            Self {
+                a: Vault::with_bucket(ResourceBuilder::new_badge().initial_supply_fungible(1)),
+                b: Vault::with_bucket(ResourceBuilder::new_badge().initial_supply_fungible(1)),
+            }
+            .instantiate()
+        }
+
+        pub fn badges(&self) -> (Address, Address) {
+            (self.a.resource_address(), self.b.resource_address())
+        }
+
+        pub fn holds_badges(&self) -> bool {
+            !self.a.is_empty() && !self.b.is_empty()
+        }
+
+        pub fn open_with_both(&self, guard: Address) -> bool {
+            let proof = BucketRef::compose(vec![self.a.present(), self.b.present()]);
+            Component::from(guard).call("open_with_both", args!(proof))
+        }
+
+        pub fn open_with_a(&self, guard: Address) -> bool {
+            let proof = BucketRef::compose(vec![self.b.present(), self.a.present()]);
+            Component::from(guard).call("open_with_a", args!(proof))
+        }
+
+        pub fn open_with_b_only(&self, guard: Address) -> bool {
+            let proof = BucketRef::compose(vec![self.b.present()]);
+            Component::from(guard).call("open_with_both", args!(proof))
+        }
+
+        pub fn open_with_nested(&self, guard: Address) -> bool {
+            let inner = BucketRef::compose(vec![self.a.present()]);
+            let proof = BucketRef::compose(vec![inner, self.b.present()]);
+            Component::from(guard).call("open_with_both", args!(proof))
+        }
+
+        pub fn open_with_clone(&self, guard: Address) -> bool {
+            let proof = BucketRef::compose(vec![self.a.present(), self.b.present()]);
+            let clone = proof.clone();
+            proof.drop();
+            Component::from(guard).call("open_with_both", args!(clone))
+        }
+
+        pub fn compose_none(&self) {
+            BucketRef::compose(vec![]).drop();
+        }
+
+        pub fn composite_amount(&self) -> Decimal {
+            let proof = BucketRef::compose(vec![self.a.present(), self.b.present()]);
+            let amount = proof.amount();
+            proof.drop();
+            amount
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    holder: Address,
+    guard: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(
+            NativePackage::new()
+                .with_blueprint("Guard", Guard_main, Guard_abi)
+                .with_blueprint("Holder", Holder_main, Holder_abi),
+        );
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Holder", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let holder = executor.run(transaction).unwrap().component(0).unwrap();
+
+        let transaction = TransactionBuilder::new(&executor)
+            .call_method(holder, "badges", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let (a, b): (Address, Address) = executor
+            .run(transaction)
+            .unwrap()
+            .expect_commit_success()
+            .output(0);
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(
+                package,
+                "Guard",
+                "new",
+                vec![a.to_string(), b.to_string()],
+                None,
+            )
+            .build(vec![])
+            .unwrap();
+        let guard = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self {
+            executor,
+            holder,
+            guard,
+        }
+    }
+
+    fn call(&mut self, method: &str) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(self.holder, method, vec![self.guard.to_string()], None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn composite_ref_should_prove_all_badges() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("open_with_both");
+
+    // Assert
+    let opened: bool = receipt.expect_commit_success().output(0);
+    assert!(opened);
+    let holds_badges: bool = env.call("holds_badges").expect_commit_success().output(0);
+    assert!(holds_badges);
+}
+
+#[test]
+fn composite_ref_should_pass_auth_of_any_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("open_with_a");
+
+    // Assert
+    let opened: bool = receipt.expect_commit_success().output(0);
+    assert!(opened);
+}
+
+#[test]
+fn composite_ref_should_not_prove_missing_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("open_with_b_only");
+
+    // Assert
+    let opened: bool = receipt.expect_commit_success().output(0);
+    assert!(!opened);
+}
+
+#[test]
+fn nested_and_cloned_composite_refs_should_prove_all_badges() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let nested = env.call("open_with_nested");
+    let cloned = env.call("open_with_clone");
+
+    // Assert
+    let opened: bool = nested.expect_commit_success().output(0);
+    assert!(opened);
+    let opened: bool = cloned.expect_commit_success().output(0);
+    assert!(opened);
+    let holds_badges: bool = env.call("holds_badges").expect_commit_success().output(0);
+    assert!(holds_badges);
+}
+
+#[test]
+fn empty_composite_ref_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("compose_none");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::EmptyCompositeBucketRef)
+    ));
+}
+
+#[test]
+fn composite_ref_should_not_be_used_as_single_bucket_ref() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("composite_amount");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::CompositeBucketRefNotAllowed(_))
+    ));
+}
@@ -1,5 +1,11 @@
+use sbor::*;
 use scrypto::prelude::*;
 
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct Greeting {
+    message: String,
+}
+
 blueprint! {
     struct ContextTest;
 
@@ -13,5 +19,16 @@ blueprint! {
                 Uuid::generate(),
             )
         }
+
+        pub fn roll_dice() -> (u8, u128) {
+            let die = Context::random_bytes(1)[0] % 6 + 1;
+            (die, Context::random_u128())
+        }
+
+        pub fn greet() {
+            emit_event(Greeting {
+                message: "Hello".to_owned(),
+            });
+        }
     }
 }
@@ -0,0 +1,218 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Logger {
+        lines: Vec<String>,
+    }
+
+    impl Logger {
+        pub fn instantiate(lines: u32) -> Component {
+            Self {
+                lines: (0..lines).map(|i| i.to_string()).collect(),
+            }
+            .instantiate()
+        }
+
+        pub fn log(times: u32) {
+            for _ in 0..times {
+                info!("Hello");
+            }
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore, cost_table: CostTable) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false).with_cost_table(cost_table);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Logger",
+            Logger_main,
+            Logger_abi,
+        ));
+
+        Self { executor, package }
+    }
+
+    fn call(&mut self, function: &str, arg: u32) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(
+                self.package,
+                "Logger",
+                function,
+                vec![arg.to_string()],
+                None,
+            )
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+fn cost_of(cost_table: CostTable, function: &str, arg: u32) -> u32 {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, cost_table);
+    let receipt = env.call(function, arg);
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    receipt.cost_units
+}
+
+#[test]
+fn cost_table_should_round_trip_through_text() {
+    // Arrange
+    let mut cost_table = CostTable::default();
+    cost_table.engine_calls.insert("CallMethod".to_owned(), 10);
+    cost_table.state_read_byte = 2;
+    cost_table.wasm_instruction = 1;
+
+    // Act
+    let parsed = CostTable::from_str(&cost_table.to_string());
+
+    // Assert
+    assert_eq!(parsed, Ok(cost_table));
+}
+
+#[test]
+fn partial_cost_table_should_keep_default_prices() {
+    // Act
+    let cost_table = CostTable::from_str("# comment\n\nstate_write_byte = 3\n").unwrap();
+
+    // Assert
+    assert_eq!(cost_table.state_write_byte, 3);
+    assert_eq!(cost_table.engine_call_price("CallMethod"), 1);
+}
+
+#[test]
+fn invalid_cost_table_should_not_parse() {
+    assert_eq!(
+        CostTable::from_str("engine_call = 1\nnot an entry"),
+        Err(ParseCostTableError::InvalidLine(2))
+    );
+    assert_eq!(
+        CostTable::from_str("storage = 1"),
+        Err(ParseCostTableError::UnknownKey("storage".to_owned()))
+    );
+    assert_eq!(
+        CostTable::from_str("engine_call = -1"),
+        Err(ParseCostTableError::InvalidPrice("engine_call".to_owned()))
+    );
+}
+
+#[test]
+fn default_cost_should_count_engine_calls() {
+    // Act
+    let three = cost_of(CostTable::default(), "log", 3);
+    let six = cost_of(CostTable::default(), "log", 6);
+
+    // Assert
+    assert_eq!(six - three, 3);
+}
+
+#[test]
+fn engine_call_should_be_charged_at_its_price() {
+    // Arrange
+    let mut cost_table = CostTable::default();
+    cost_table.engine_calls.insert("EmitLog".to_owned(), 10);
+
+    // Act
+    let default = cost_of(CostTable::default(), "log", 3);
+    let priced = cost_of(cost_table, "log", 3);
+
+    // Assert
+    assert_eq!(priced - default, 3 * 9);
+}
+
+#[test]
+fn priced_engine_calls_should_be_limited() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut cost_table = CostTable::default();
+    cost_table.engine_calls.insert("EmitLog".to_owned(), 1000);
+    let mut env = TestEnv::new(&mut ledger, cost_table);
+    env.executor = env.executor.with_execution_limits(ExecutionLimits {
+        max_engine_calls: 5000,
+        ..Default::default()
+    });
+
+    // Act
+    let receipt = env.call("log", 5);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::EngineCallLimitExceeded(5000))
+    ));
+}
+
+#[test]
+fn state_writes_should_be_charged_per_byte() {
+    // Arrange
+    let cost_table = CostTable {
+        state_write_byte: 1,
+        ..Default::default()
+    };
+
+    // Act
+    let small = cost_of(cost_table.clone(), "instantiate", 1)
+        - cost_of(CostTable::default(), "instantiate", 1);
+    let large =
+        cost_of(cost_table, "instantiate", 100) - cost_of(CostTable::default(), "instantiate", 100);
+
+    // Assert
+    assert!(small > 0);
+    assert!(large > small + 100);
+}
+
+#[test]
+fn state_reads_should_be_charged_per_byte() {
+    // Arrange
+    let cost_table = CostTable {
+        state_read_byte: 1,
+        ..Default::default()
+    };
+
+    // Act
+    let default = cost_of(CostTable::default(), "log", 0);
+    let priced = cost_of(cost_table, "log", 0);
+
+    // Assert
+    assert!(priced > default);
+}
+
+#[test]
+fn wasm_instructions_should_be_metered() {
+    // Arrange
+    let cost_table = CostTable {
+        wasm_instruction: 1,
+        ..Default::default()
+    };
+    let new_account = |cost_table: CostTable| {
+        let mut ledger = InMemorySubstateStore::with_bootstrap();
+        let mut executor = TransactionExecutor::new(&mut ledger, false).with_cost_table(cost_table);
+        let key = executor.new_public_key();
+        let transaction = TransactionBuilder::new(&executor)
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+            .new_account_with_resource(key, Decimal::from(100), RADIX_TOKEN)
+            .build(vec![])
+            .unwrap();
+        executor.run(transaction).unwrap()
+    };
+
+    // Act
+    let default = new_account(CostTable::default());
+    let metered = new_account(cost_table);
+
+    // Assert
+    assert!(default.status.is_committed(), "{:?}", default);
+    assert!(metered.status.is_committed(), "{:?}", metered);
+    assert!(metered.cost_units > default.cost_units + 1000);
+    assert_eq!(metered.new_entities, default.new_entities);
+}
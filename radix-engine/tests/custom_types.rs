@@ -0,0 +1,329 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use sbor::{Decode, Describe, Encode, TypeId};
+use scrypto::prelude::*;
+
+type Values = (
+    Decimal,
+    BigDecimal,
+    Address,
+    H256,
+    NonFungibleKey,
+    NonFungibleAddress,
+    Url,
+);
+
+/// A value of each custom type which is allowed everywhere.
+fn values() -> Values {
+    (
+        Decimal::from_str("1.5").unwrap(),
+        BigDecimal::from_str("-12345678901234567890.5").unwrap(),
+        RADIX_TOKEN,
+        sha256("scrypto"),
+        NonFungibleKey::from(7u128),
+        NonFungibleAddress::new(RADIX_TOKEN, NonFungibleKey::from(7u128)),
+        Url::from_str("https://radixdlt.com/").unwrap(),
+    )
+}
+
+#[derive(NonFungibleData)]
+pub struct Deed {
+    vault: Vault,
+}
+
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct Transfer {
+    bucket: Bucket,
+}
+
+blueprint! {
+    struct Boundary {}
+
+    impl Boundary {
+        pub fn new() -> Component {
+            Self {}.instantiate()
+        }
+
+        pub fn echo(&self, values: Values) -> Values {
+            values
+        }
+
+        pub fn forward(&self, target: Address) -> Values {
+            Component::from(target).call("echo", args![values()])
+        }
+
+        pub fn take_vault(&self, _vault: Vault) {}
+
+        pub fn pass_vault(&self, target: Address) {
+            Component::from(target).call::<()>("take_vault", args![Vault::new(RADIX_TOKEN)]);
+        }
+
+        pub fn take_lazy_map(&self, _map: LazyMap<u32, u32>) {}
+
+        pub fn pass_lazy_map(&self, target: Address) {
+            Component::from(target).call::<()>("take_lazy_map", args![LazyMap::<u32, u32>::new()]);
+        }
+
+        pub fn return_vault() -> Vault {
+            Vault::new(RADIX_TOKEN)
+        }
+
+        pub fn return_lazy_map() -> LazyMap<u32, u32> {
+            LazyMap::new()
+        }
+
+        pub fn return_bucket_ref() -> BucketRef {
+            ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .initial_supply_fungible(1)
+                .present()
+        }
+
+        pub fn vault_as_key() {
+            let map = LazyMap::new();
+            map.insert(Vault::new(RADIX_TOKEN), 1u32);
+        }
+
+        pub fn vault_in_non_fungible_data() {
+            let deed = Deed {
+                vault: Vault::new(RADIX_TOKEN),
+            };
+            ResourceBuilder::new_non_fungible()
+                .initial_supply_non_fungible([(NonFungibleKey::from(1u128), deed)])
+                .burn();
+        }
+
+        pub fn bucket_in_event() {
+            let bucket = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            emit_event(Transfer { bucket });
+        }
+    }
+}
+
+blueprint! {
+    struct Holder {
+        bucket: Bucket,
+    }
+
+    impl Holder {
+        pub fn new() -> Component {
+            Self {
+                bucket: ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1),
+            }
+            .instantiate()
+        }
+    }
+}
+
+blueprint! {
+    struct Owner {
+        vaults: LazyMap<u32, Vault>,
+    }
+
+    impl Owner {
+        pub fn new() -> Component {
+            let vaults = LazyMap::new();
+            vaults.insert(1, Vault::new(RADIX_TOKEN));
+            Self { vaults }.instantiate()
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(
+            NativePackage::new()
+                .with_blueprint("Boundary", Boundary_main, Boundary_abi)
+                .with_blueprint("Holder", Holder_main, Holder_abi)
+                .with_blueprint("Owner", Owner_main, Owner_abi),
+        );
+
+        Self { executor, package }
+    }
+
+    fn call_function(&mut self, blueprint_name: &str, function: &str) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(self.package, blueprint_name, function, vec![], None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn new_boundary(&mut self) -> Address {
+        self.call_function("Boundary", "new").component(0).unwrap()
+    }
+
+    fn call_method(&mut self, component: Address, method: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(component, method, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+fn root_cause(receipt: &Receipt) -> Option<&RuntimeError> {
+    receipt.status.error().map(RuntimeError::root_cause)
+}
+
+#[test]
+fn plain_values_should_round_trip_between_components() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let caller = env.new_boundary();
+    let callee = env.new_boundary();
+
+    // Act
+    let receipt = env.call_method(caller, "forward", vec![callee.to_string()]);
+
+    // Assert
+    let output: Values = receipt.expect_commit_success().output(0);
+    assert_eq!(output, values());
+}
+
+#[test]
+fn vault_should_not_be_passed_as_argument() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let caller = env.new_boundary();
+    let callee = env.new_boundary();
+
+    // Act
+    let receipt = env.call_method(caller, "pass_vault", vec![callee.to_string()]);
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::VaultNotAllowed(DataContext::Argument))
+    ));
+}
+
+#[test]
+fn lazy_map_should_not_be_passed_as_argument() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let caller = env.new_boundary();
+    let callee = env.new_boundary();
+
+    // Act
+    let receipt = env.call_method(caller, "pass_lazy_map", vec![callee.to_string()]);
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::LazyMapNotAllowed(DataContext::Argument))
+    ));
+}
+
+#[test]
+fn owned_objects_and_bucket_refs_should_not_be_returned() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let vault = env.call_function("Boundary", "return_vault");
+    let lazy_map = env.call_function("Boundary", "return_lazy_map");
+    let bucket_ref = env.call_function("Boundary", "return_bucket_ref");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&vault),
+        Some(RuntimeError::VaultNotAllowed(DataContext::Return))
+    ));
+    assert!(matches!(
+        root_cause(&lazy_map),
+        Some(RuntimeError::LazyMapNotAllowed(DataContext::Return))
+    ));
+    assert!(matches!(
+        root_cause(&bucket_ref),
+        Some(RuntimeError::BucketRefNotAllowed(DataContext::Return))
+    ));
+}
+
+#[test]
+fn bucket_should_not_be_stored_in_state() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call_function("Holder", "new");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::BucketNotAllowed(DataContext::ComponentState))
+    ));
+}
+
+#[test]
+fn owned_objects_should_be_stored_in_state_and_lazy_map_values() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call_function("Owner", "new");
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+}
+
+#[test]
+fn vault_should_not_be_used_as_lazy_map_key() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call_function("Boundary", "vault_as_key");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::VaultNotAllowed(DataContext::LazyMapKey))
+    ));
+}
+
+#[test]
+fn vault_should_not_be_stored_in_non_fungible_data() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call_function("Boundary", "vault_in_non_fungible_data");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::VaultNotAllowed(DataContext::NonFungibleData))
+    ));
+}
+
+#[test]
+fn bucket_should_not_be_emitted_in_event() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call_function("Boundary", "bucket_in_event");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::BucketNotAllowed(DataContext::Event))
+    ));
+}
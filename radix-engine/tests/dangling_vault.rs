@@ -0,0 +1,73 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Safe {
+        tokens: Vault,
+    }
+
+    impl Safe {
+        pub fn new_safe() -> Component {
+            Self {
+                tokens: Vault::with_bucket(ResourceBuilder::new_fungible(0).initial_supply_fungible(1)),
+            }
+            .instantiate()
+        }
+
+        pub fn dangling_vault() {
+            let _vault = Vault::with_bucket(ResourceBuilder::new_fungible(0).initial_supply_fungible(1));
+        }
+
+        pub fn dangling_empty_vault() {
+            let _vault = Vault::new(ResourceBuilder::new_fungible(0).no_initial_supply());
+        }
+    }
+}
+
+fn run(function: &str) -> Receipt {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor
+        .publish_native_package(NativePackage::new().with_blueprint("Safe", Safe_main, Safe_abi));
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(package, "Safe", function, vec![], None)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn vault_owned_by_component_should_be_committed() {
+    // Act
+    let receipt = run("new_safe");
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn dangling_vault_should_fail() {
+    // Act
+    let receipt = run("dangling_vault");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::DanglingVault(_))
+    ));
+}
+
+#[test]
+fn dangling_empty_vault_should_fail() {
+    // Act
+    let receipt = run("dangling_empty_vault");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::DanglingVault(_))
+    ));
+}
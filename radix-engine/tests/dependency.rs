@@ -0,0 +1,180 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{DependencyError, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+// The address the library package is published at, on a freshly bootstrapped ledger
+const LIBRARY_PACKAGE: &str = "0124c5afc33cf45c06633d8fc0b0dfba2c82f14ec82ff7eb13483c";
+
+mod library {
+    use scrypto::prelude::*;
+
+    blueprint! {
+        struct Greeter {}
+
+        impl Greeter {
+            pub fn new() -> Component {
+                Self {}.instantiate()
+            }
+
+            pub fn say_hello(&self, name: String) -> String {
+                format!("Hello, {}", name)
+            }
+        }
+    }
+
+    blueprint! {
+        struct Counter {}
+
+        impl Counter {
+            pub fn count(&self) -> u32 {
+                0
+            }
+        }
+    }
+}
+
+mod dependencies {
+    use scrypto::prelude::*;
+
+    import! {
+        r#"
+        {
+            "package": "0124c5afc33cf45c06633d8fc0b0dfba2c82f14ec82ff7eb13483c",
+            "name": "Greeter",
+            "functions": [
+                {
+                    "name": "new",
+                    "inputs": [],
+                    "output": {
+                        "type": "Custom",
+                        "name": "scrypto::core::Component",
+                        "generics": []
+                    }
+                }
+            ],
+            "methods": [
+                {
+                    "name": "say_hello",
+                    "mutability": "Immutable",
+                    "inputs": [
+                        {
+                            "type": "String"
+                        }
+                    ],
+                    "output": {
+                        "type": "String"
+                    }
+                }
+            ]
+        }
+        "#
+    }
+
+    import! {
+        r#"
+        {
+            "package": "0124c5afc33cf45c06633d8fc0b0dfba2c82f14ec82ff7eb13483c",
+            "name": "Counter",
+            "functions": [],
+            "methods": [
+                {
+                    "name": "count",
+                    "mutability": "Immutable",
+                    "inputs": [],
+                    "output": {
+                        "type": "U64"
+                    }
+                }
+            ]
+        }
+        "#
+    }
+
+    import! {
+        r#"
+        {
+            "package": "0124c5afc33cf45c06633d8fc0b0dfba2c82f14ec82ff7eb13483c",
+            "name": "Printer",
+            "functions": [],
+            "methods": []
+        }
+        "#
+    }
+}
+
+fn publish_library(executor: &mut TransactionExecutor<InMemorySubstateStore>) {
+    let address = executor.publish_native_package(
+        NativePackage::new()
+            .with_blueprint("Greeter", library::Greeter_main, library::Greeter_abi)
+            .with_blueprint("Counter", library::Counter_main, library::Counter_abi),
+    );
+    assert_eq!(address, Address::from_str(LIBRARY_PACKAGE).unwrap());
+}
+
+#[test]
+fn test_matching_dependency_is_accepted() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    publish_library(&mut executor);
+
+    let result = executor.try_publish_native_package(
+        NativePackage::new().with_dependency("Greeter", dependencies::Greeter_dependency),
+    );
+
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_dependency_on_missing_package_is_rejected() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    let result = executor.try_publish_native_package(
+        NativePackage::new().with_dependency("Greeter", dependencies::Greeter_dependency),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::DependencyError(
+            DependencyError::PackageNotFound(_)
+        ))
+    ));
+}
+
+#[test]
+fn test_dependency_on_missing_blueprint_is_rejected() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    publish_library(&mut executor);
+
+    let result = executor.try_publish_native_package(
+        NativePackage::new().with_dependency("Printer", dependencies::Printer_dependency),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::DependencyError(
+            DependencyError::BlueprintNotFound(_, ref blueprint)
+        )) if blueprint == "Printer"
+    ));
+}
+
+#[test]
+fn test_dependency_with_mismatched_method_is_rejected() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    publish_library(&mut executor);
+
+    let result = executor.try_publish_native_package(
+        NativePackage::new().with_dependency("Counter", dependencies::Counter_dependency),
+    );
+
+    assert!(matches!(
+        result,
+        Err(RuntimeError::DependencyError(
+            DependencyError::MethodMismatch(_, ref blueprint, ref method)
+        )) if blueprint == "Counter" && method == "count"
+    ));
+}
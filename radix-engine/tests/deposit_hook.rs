@@ -0,0 +1,216 @@
+use std::fs;
+use std::process::Command;
+
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Instruction, Receipt};
+use radix_engine::transaction::*;
+use sbor::{Decode, TypeId};
+use scrypto::prelude::*;
+
+pub fn compile(name: &str) -> Vec<u8> {
+    Command::new("cargo")
+        .current_dir(format!("./tests/{}", name))
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    fs::read(format!(
+        "./tests/{}/target/wasm32-unknown-unknown/release/{}.wasm",
+        name,
+        name.replace("-", "_")
+    ))
+    .unwrap()
+}
+
+/// The state of a `Notifier`.
+#[derive(TypeId, Decode)]
+struct NotifierState {
+    #[allow(dead_code)]
+    mode: String,
+    deposits: u32,
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+    key: EcdsaPublicKey,
+    account: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_package(&compile("deposit_hook")).unwrap();
+        let key = executor.new_public_key();
+        let account = executor.new_account(key);
+
+        Self {
+            executor,
+            package,
+            key,
+            account,
+        }
+    }
+
+    fn new_notifier(&mut self, mode: &str) -> Address {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(self.package, "Notifier", "new", vec![mode.to_owned()], None)
+            .build(vec![])
+            .unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .component(0)
+            .unwrap()
+    }
+
+    fn set_deposit_hook(&mut self, hook: Option<Address>, key: EcdsaPublicKey) -> Receipt {
+        let account = self.account;
+        let transaction = TransactionBuilder::new(&self.executor)
+            .clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| {
+                builder
+                    .add_instruction(Instruction::CallMethod {
+                        component_address: account,
+                        method: "set_deposit_hook".to_owned(),
+                        args: vec![scrypto_encode(&hook), scrypto_encode(&rid)],
+                    })
+                    .0
+            })
+            .build(vec![key])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn deposit(&mut self) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn deposits(&mut self, notifier: Address) -> u32 {
+        let component = self.executor.ledger().get_component(notifier).unwrap();
+        let state: NotifierState = scrypto_decode(component.state()).unwrap();
+        state.deposits
+    }
+
+    fn has_balance(&mut self, amount: u32) -> bool {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .assert_account_balance_at_least(self.account, amount.into(), RADIX_TOKEN)
+            .build(vec![])
+            .unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .status
+            .is_committed()
+    }
+}
+
+#[test]
+fn deposit_hook_should_be_notified() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("count");
+    let receipt = env.set_deposit_hook(Some(notifier), env.key);
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+
+    // Act
+    let receipt = env.deposit();
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert_eq!(env.deposits(notifier), 1);
+}
+
+#[test]
+fn deposit_hook_should_not_be_notified_once_removed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("count");
+    env.set_deposit_hook(Some(notifier), env.key);
+    let receipt = env.set_deposit_hook(None, env.key);
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+
+    // Act
+    let receipt = env.deposit();
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert_eq!(env.deposits(notifier), 0);
+}
+
+#[test]
+fn failing_deposit_hook_should_be_reverted() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("panic");
+    env.set_deposit_hook(Some(notifier), env.key);
+
+    // Act
+    let receipt = env.deposit();
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert!(receipt.new_entities.is_empty());
+    assert!(receipt
+        .logs
+        .iter()
+        .any(|(_, message)| message.contains("Deposit hook")));
+    assert_eq!(env.deposits(notifier), 0);
+    assert!(env.has_balance(1_000_100));
+}
+
+#[test]
+fn deposit_hook_calling_back_into_account_should_be_reverted() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("reenter");
+    env.set_deposit_hook(Some(notifier), env.key);
+
+    // Act
+    let receipt = env.deposit();
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert_eq!(env.deposits(notifier), 0);
+    assert!(env.has_balance(1_000_100));
+}
+
+#[test]
+fn deposit_hook_should_be_bounded() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("spin");
+    env.set_deposit_hook(Some(notifier), env.key);
+
+    // Act
+    let receipt = env.deposit();
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert_eq!(env.deposits(notifier), 0);
+    assert!(env.has_balance(1_000_100));
+}
+
+#[test]
+fn deposit_hook_can_only_be_set_by_owner() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let notifier = env.new_notifier("count");
+    let other_key = env.executor.new_public_key();
+
+    // Act
+    let receipt = env.set_deposit_hook(Some(notifier), other_key);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
@@ -0,0 +1,31 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Notifier {
+        mode: String,
+        deposits: u32,
+    }
+
+    impl Notifier {
+        pub fn new(mode: String) -> Component {
+            Self { mode, deposits: 0 }.instantiate()
+        }
+
+        pub fn on_deposit(&mut self, account: Address, _resource_address: Address, _amount: Decimal) {
+            self.deposits += 1;
+            match self.mode.as_str() {
+                "panic" => {
+                    Self::new("count".to_owned());
+                    panic!("Notifier failed");
+                }
+                "reenter" => {
+                    Component::from(account).call::<Option<Address>>("deposit_hook", vec![]);
+                }
+                "spin" => loop {
+                    Context::transaction_hash();
+                },
+                _ => {}
+            }
+        }
+    }
+}
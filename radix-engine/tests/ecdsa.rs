@@ -0,0 +1,114 @@
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::Instruction;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Verifier {}
+
+    impl Verifier {
+        pub fn verify(message: Vec<u8>, signature: Vec<u8>, public_key: EcdsaPublicKey) -> bool {
+            Context::verify_ecdsa(&message, &signature, public_key)
+        }
+    }
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[1u8; 32]).unwrap()
+}
+
+fn public_key(key: &SigningKey) -> EcdsaPublicKey {
+    let mut bytes = [0u8; 33];
+    bytes.copy_from_slice(&key.verifying_key().to_bytes());
+    EcdsaPublicKey(bytes)
+}
+
+fn verify(message: &[u8], signature: &[u8], public_key: EcdsaPublicKey) -> bool {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+        "Verifier",
+        Verifier_main,
+        Verifier_abi,
+    ));
+
+    let transaction = TransactionBuilder::new(&executor)
+        .add_instruction(Instruction::CallFunction {
+            package_address: package,
+            blueprint_name: "Verifier".to_owned(),
+            function: "verify".to_owned(),
+            args: vec![
+                scrypto_encode(&message.to_vec()),
+                scrypto_encode(&signature.to_vec()),
+                scrypto_encode(&public_key),
+            ],
+        })
+        .0
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    receipt.expect_commit_success().output(0)
+}
+
+#[test]
+fn valid_signature_should_be_accepted() {
+    // Arrange
+    let key = signing_key();
+    let signature: Signature = key.sign(b"voucher #1");
+
+    // Act
+    let compact = verify(b"voucher #1", signature.as_ref(), public_key(&key));
+    let der = verify(
+        b"voucher #1",
+        signature.to_der().as_bytes(),
+        public_key(&key),
+    );
+
+    // Assert
+    assert!(compact);
+    assert!(der);
+}
+
+#[test]
+fn signature_of_other_message_should_be_rejected() {
+    // Arrange
+    let key = signing_key();
+    let signature: Signature = key.sign(b"voucher #1");
+
+    // Act
+    let is_valid = verify(b"voucher #2", signature.as_ref(), public_key(&key));
+
+    // Assert
+    assert!(!is_valid);
+}
+
+#[test]
+fn signature_of_other_key_should_be_rejected() {
+    // Arrange
+    let key = signing_key();
+    let other_key = SigningKey::from_bytes(&[2u8; 32]).unwrap();
+    let signature: Signature = other_key.sign(b"voucher #1");
+
+    // Act
+    let is_valid = verify(b"voucher #1", signature.as_ref(), public_key(&key));
+
+    // Assert
+    assert!(!is_valid);
+}
+
+#[test]
+fn malformed_input_should_be_rejected() {
+    // Arrange
+    let key = signing_key();
+
+    // Act
+    let bad_signature = verify(b"voucher #1", &[7u8; 10], public_key(&key));
+    let bad_key = verify(b"voucher #1", &[7u8; 64], EcdsaPublicKey([0u8; 33]));
+
+    // Assert
+    assert!(!bad_signature);
+    assert!(!bad_key);
+}
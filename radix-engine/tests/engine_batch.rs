@@ -0,0 +1,159 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::engine::*;
+use scrypto::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct Ticket {
+    row: u32,
+}
+
+blueprint! {
+    struct Batcher {
+        tickets: Vault,
+    }
+
+    impl Batcher {
+        pub fn instantiate_batcher() -> Component {
+            let tickets = ResourceBuilder::new_non_fungible().initial_supply_non_fungible([
+                (NonFungibleKey::from(1u128), Ticket { row: 1 }),
+                (NonFungibleKey::from(2u128), Ticket { row: 2 }),
+            ]);
+
+            Self {
+                tickets: Vault::with_bucket(tickets),
+            }
+            .instantiate()
+        }
+
+        pub fn vault_tickets(&self) -> Vec<NonFungibleAddress> {
+            self.tickets
+                .get_non_fungibles::<Ticket>()
+                .iter()
+                .map(NonFungible::address)
+                .collect()
+        }
+
+        pub fn bucket_tickets(&mut self) -> Vec<NonFungibleAddress> {
+            let bucket = self.tickets.take_all();
+            let tickets = bucket
+                .get_non_fungibles::<Ticket>()
+                .iter()
+                .map(NonFungible::address)
+                .collect();
+            self.tickets.put(bucket);
+            tickets
+        }
+
+        pub fn current_epochs(times: u32) -> Vec<u64> {
+            let calls = (0..times)
+                .map(|_| (GET_CURRENT_EPOCH, scrypto_encode(&GetCurrentEpochInput {})))
+                .collect();
+            call_engine_batch(calls)
+                .iter()
+                .map(|output| scrypto_decode::<GetCurrentEpochOutput>(output).unwrap().current_epoch)
+                .collect()
+        }
+
+        pub fn nested_batch() {
+            let inner = BatchEngineCallsInput { calls: Vec::new() };
+            call_engine_batch(vec![(BATCH_ENGINE_CALLS, scrypto_encode(&inner))]);
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore, cost_table: CostTable) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false).with_cost_table(cost_table);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Batcher",
+            Batcher_main,
+            Batcher_abi,
+        ));
+
+        Self { executor, package }
+    }
+
+    fn call_function(&mut self, function: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(self.package, "Batcher", function, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn call_method(&mut self, component: Address, method: &str) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(component, method, vec![], None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn non_fungibles_should_be_listed_in_one_batch() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, CostTable::default());
+    let receipt = env.call_function("instantiate_batcher", vec![]);
+    let component = receipt.expect_commit_success().component(0).unwrap();
+    let resource = receipt.resource_def(0).unwrap();
+
+    // Act
+    let receipt = env.call_method(component, "vault_tickets");
+    let vault_tickets: Vec<NonFungibleAddress> = receipt.expect_commit_success().output(0);
+    let receipt = env.call_method(component, "bucket_tickets");
+    let bucket_tickets: Vec<NonFungibleAddress> = receipt.expect_commit_success().output(0);
+
+    // Assert
+    let expected = vec![
+        NonFungibleAddress::new(resource, NonFungibleKey::from(1u128)),
+        NonFungibleAddress::new(resource, NonFungibleKey::from(2u128)),
+    ];
+    assert_eq!(vault_tickets, expected);
+    assert_eq!(bucket_tickets, expected);
+}
+
+#[test]
+fn batched_calls_should_be_charged_individually() {
+    // Arrange
+    let mut cost_table = CostTable::default();
+    cost_table
+        .engine_calls
+        .insert("GetCurrentEpoch".to_owned(), 1000);
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, cost_table);
+
+    // Act
+    let one = env.call_function("current_epochs", vec!["1".to_owned()]);
+    let three = env.call_function("current_epochs", vec!["3".to_owned()]);
+
+    // Assert
+    let epochs: Vec<u64> = three.expect_commit_success().output(0);
+    assert_eq!(epochs, vec![0, 0, 0]);
+    assert!(three.cost_units - one.expect_commit_success().cost_units >= 2000);
+}
+
+#[test]
+fn nested_batch_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, CostTable::default());
+
+    // Act
+    let receipt = env.call_function("nested_batch", vec![]);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::NestedBatchEngineCalls)
+    ));
+}
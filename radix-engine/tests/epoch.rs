@@ -0,0 +1,36 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+
+#[test]
+fn epoch_is_not_advanced_by_default() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    executor.new_account(key);
+    executor.new_account(key);
+
+    // Assert
+    assert_eq!(executor.ledger().get_epoch(), 0);
+}
+
+#[test]
+fn epoch_is_advanced_every_interval() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_epoch_interval(2);
+    let key = executor.new_public_key();
+
+    // Act
+    executor.new_account(key);
+    let epoch_after_one = executor.ledger().get_epoch();
+    executor.new_account(key);
+    executor.new_account(key);
+    executor.new_account(key);
+
+    // Assert
+    assert_eq!(epoch_after_one, 0);
+    assert_eq!(executor.ledger().get_epoch(), 2);
+}
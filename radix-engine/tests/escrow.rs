@@ -0,0 +1,167 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Instruction, Receipt};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    maker_key: EcdsaPublicKey,
+    maker: Address,
+    taker_key: EcdsaPublicKey,
+    taker: Address,
+    token: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let maker_key = executor.new_public_key();
+        let maker = executor.new_account(maker_key);
+        let taker_key = executor.new_public_key();
+        let taker = executor.new_account(taker_key);
+        let transaction = TransactionBuilder::new(&executor)
+            .new_token_fixed(HashMap::new(), 1000.into())
+            .call_method_with_all_resources(maker, "deposit_batch")
+            .build(vec![])
+            .unwrap();
+        let token = executor.run(transaction).unwrap().resource_def(0).unwrap();
+
+        Self {
+            executor,
+            maker_key,
+            maker,
+            taker_key,
+            taker,
+            token,
+        }
+    }
+
+    fn create_escrow(&mut self, offer: u32, want: u32) -> Address {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .create_escrow(
+                &Resource::Fungible {
+                    amount: offer.into(),
+                    resource_address: self.token,
+                },
+                self.maker,
+                RADIX_TOKEN,
+                want.into(),
+            )
+            .build(vec![self.maker_key])
+            .unwrap();
+        let receipt = self.executor.run(transaction).unwrap();
+        receipt.expect_commit_success().new_component_addresses()[0]
+    }
+
+    fn accept_escrow(&mut self, escrow: Address, payment: u32) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .accept_escrow(
+                escrow,
+                &Resource::Fungible {
+                    amount: payment.into(),
+                    resource_address: RADIX_TOKEN,
+                },
+                self.taker,
+            )
+            .build(vec![self.taker_key])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn cancel_escrow(&mut self, escrow: Address, key: EcdsaPublicKey) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| {
+                builder
+                    .add_instruction(Instruction::CallMethod {
+                        component_address: escrow,
+                        method: "cancel".to_owned(),
+                        args: vec![scrypto_encode(&rid)],
+                    })
+                    .0
+            })
+            .build(vec![key])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn has_balance(&mut self, account: Address, amount: u32, resource_address: Address) -> bool {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .assert_account_balance_at_least(account, amount.into(), resource_address)
+            .build(vec![])
+            .unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .status
+            .is_committed()
+    }
+}
+
+#[test]
+fn escrow_should_swap_resources() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let escrow = env.create_escrow(100, 50);
+
+    // Act
+    let receipt = env.accept_escrow(escrow, 80);
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    let (maker, taker, token) = (env.maker, env.taker, env.token);
+    assert!(env.has_balance(taker, 100, token));
+    assert!(env.has_balance(taker, 999_950, RADIX_TOKEN));
+    assert!(!env.has_balance(taker, 999_951, RADIX_TOKEN));
+    assert!(env.has_balance(maker, 1_000_050, RADIX_TOKEN));
+    assert!(!env.has_balance(maker, 901, token));
+}
+
+#[test]
+fn escrow_should_reject_insufficient_payment() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let escrow = env.create_escrow(100, 50);
+
+    // Act
+    let receipt = env.accept_escrow(escrow, 49);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn escrow_should_only_be_accepted_once() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let escrow = env.create_escrow(100, 50);
+    assert!(env.accept_escrow(escrow, 50).status.is_committed());
+
+    // Act
+    let receipt = env.accept_escrow(escrow, 50);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn escrow_can_only_be_cancelled_by_maker() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let escrow = env.create_escrow(100, 50);
+
+    // Act
+    let taker_receipt = env.cancel_escrow(escrow, env.taker_key);
+    let maker_receipt = env.cancel_escrow(escrow, env.maker_key);
+
+    // Assert
+    assert!(!taker_receipt.status.is_committed());
+    assert!(maker_receipt.status.is_committed(), "{:?}", maker_receipt);
+    let (maker, token) = (env.maker, env.token);
+    assert!(env.has_balance(maker, 1000, token));
+    assert!(!env.accept_escrow(escrow, 50).status.is_committed());
+}
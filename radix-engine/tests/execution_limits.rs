@@ -0,0 +1,166 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{FailureCategory, Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Recursor {}
+
+    impl Recursor {
+        pub fn recurse(depth: u32) {
+            if depth > 0 {
+                call_function(Context::package_address(), "Recursor", "recurse", args![depth - 1]);
+            }
+        }
+
+        pub fn log(times: u32) {
+            for _ in 0..times {
+                info!("Hello");
+            }
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore, limits: ExecutionLimits) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false).with_execution_limits(limits);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Recursor",
+            Recursor_main,
+            Recursor_abi,
+        ));
+
+        Self { executor, package }
+    }
+
+    fn call(&mut self, function: &str, arg: u32) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(
+                self.package,
+                "Recursor",
+                function,
+                vec![arg.to_string()],
+                None,
+            )
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+fn root_cause(receipt: &Receipt) -> &RuntimeError {
+    receipt
+        .status
+        .error()
+        .expect("Transaction not failed")
+        .root_cause()
+}
+
+#[test]
+fn calls_within_depth_limit_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let limits = ExecutionLimits {
+        max_call_depth: 4,
+        ..Default::default()
+    };
+    let mut env = TestEnv::new(&mut ledger, limits);
+
+    // Act
+    let receipt = env.call("recurse", 3);
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+}
+
+#[test]
+fn calls_past_depth_limit_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let limits = ExecutionLimits {
+        max_call_depth: 4,
+        ..Default::default()
+    };
+    let mut env = TestEnv::new(&mut ledger, limits);
+
+    // Act
+    let receipt = env.call("recurse", 4);
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        RuntimeError::CallDepthExceeded(4)
+    ));
+    assert_eq!(
+        receipt.status.error().unwrap().category(),
+        FailureCategory::Limit
+    );
+}
+
+#[test]
+fn unbounded_recursion_should_fail_with_default_limits() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, ExecutionLimits::default());
+
+    // Act
+    let receipt = env.call("recurse", u32::MAX);
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        RuntimeError::CallDepthExceeded(_)
+    ));
+}
+
+#[test]
+fn engine_calls_past_limit_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let limits = ExecutionLimits {
+        max_engine_calls: 100,
+        ..Default::default()
+    };
+    let mut env = TestEnv::new(&mut ledger, limits);
+
+    // Act
+    let within = env.call("log", 10);
+    let past = env.call("log", 200);
+
+    // Assert
+    assert!(within.status.is_committed(), "{:?}", within);
+    assert!(matches!(
+        root_cause(&past),
+        RuntimeError::EngineCallLimitExceeded(100)
+    ));
+}
+
+#[test]
+fn wasm_memory_past_limit_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_execution_limits(ExecutionLimits {
+            max_memory_pages: 1,
+            ..Default::default()
+        });
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        RuntimeError::MemoryLimitExceeded(1)
+    ));
+}
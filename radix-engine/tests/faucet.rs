@@ -0,0 +1,99 @@
+use radix_engine::ledger::*;
+use radix_engine::model::{Instruction, Receipt};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    account: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let key = executor.new_public_key();
+        let transaction = TransactionBuilder::new(&executor)
+            .add_instruction(Instruction::CallFunction {
+                package_address: ACCOUNT_PACKAGE,
+                blueprint_name: "Account".to_owned(),
+                function: "new".to_owned(),
+                args: vec![scrypto_encode(&key)],
+            })
+            .0
+            .build(vec![])
+            .unwrap();
+        let account = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self { executor, account }
+    }
+
+    fn claim(&mut self) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(
+                FAUCET_COMPONENT,
+                "claim",
+                vec![self.account.to_string()],
+                None,
+            )
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn balance_at_least(&mut self, amount: Decimal) -> bool {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .assert_account_balance_at_least(self.account, amount, RADIX_TOKEN)
+            .build(vec![])
+            .unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .status
+            .is_committed()
+    }
+}
+
+#[test]
+fn faucet_deposits_xrd_into_account() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.claim();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert!(env.balance_at_least(Decimal::from(10_000)));
+}
+
+#[test]
+fn faucet_claim_should_fail_during_cooldown() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    env.claim();
+
+    // Act
+    let receipt = env.claim();
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+    assert!(receipt.logs[0].1.contains("until epoch 1"));
+}
+
+#[test]
+fn faucet_can_be_claimed_again_after_cooldown() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    env.claim();
+    env.executor.ledger_mut().set_epoch(1);
+
+    // Act
+    let receipt = env.claim();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert!(env.balance_at_least(Decimal::from(20_000)));
+}
@@ -0,0 +1,238 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Instruction, Receipt, ResourceDefError, RuntimeError, VaultError};
+use radix_engine::transaction::*;
+use sbor::{Decode, TypeId};
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Holder {
+        tokens: Vault,
+        freeze_badge: Vault,
+    }
+
+    impl Holder {
+        pub fn instantiate_holder(flags: u64) -> Component {
+            let freeze_badge = ResourceBuilder::new_badge().initial_supply_fungible(1);
+            let tokens = ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM)
+                .flags(flags)
+                .badge(freeze_badge.resource_def(), MAY_FREEZE | MAY_RECALL)
+                .initial_supply_fungible(100);
+
+            Self {
+                tokens: Vault::with_bucket(tokens),
+                freeze_badge: Vault::with_bucket(freeze_badge),
+            }
+            .instantiate()
+        }
+
+        pub fn freeze(&mut self, component_address: Address, vault_id: String) {
+            let vid: VaultId = vault_id.parse().unwrap();
+            let resource_def = self.tokens.resource_def();
+            self.freeze_badge
+                .authorize(|auth| resource_def.freeze_vault(component_address, vid, auth))
+        }
+
+        pub fn unfreeze(&mut self, component_address: Address, vault_id: String) {
+            let vid: VaultId = vault_id.parse().unwrap();
+            let resource_def = self.tokens.resource_def();
+            self.freeze_badge
+                .authorize(|auth| resource_def.unfreeze_vault(component_address, vid, auth))
+        }
+
+        pub fn seize(&mut self, component_address: Address, vault_id: String) -> Bucket {
+            let vid: VaultId = vault_id.parse().unwrap();
+            let resource_def = self.tokens.resource_def();
+            self.freeze_badge
+                .authorize(|auth| resource_def.recall(component_address, vid, 1, auth))
+        }
+
+        pub fn take(&mut self) -> Bucket {
+            self.tokens.take(1)
+        }
+
+        pub fn put(&mut self, bucket: Bucket) {
+            self.tokens.put(bucket)
+        }
+    }
+}
+
+/// The state of a `Holder`, with vaults decoded as their IDs.
+#[derive(TypeId, Decode)]
+struct HolderState {
+    tokens: VaultId,
+    #[allow(dead_code)]
+    freeze_badge: VaultId,
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    key: EcdsaPublicKey,
+    account: Address,
+    holder: Address,
+    tokens: VaultId,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore, flags: u64) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let key = executor.new_public_key();
+        let account = executor.new_account(key);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Holder",
+            Holder_main,
+            Holder_abi,
+        ));
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(
+                package,
+                "Holder",
+                "instantiate_holder",
+                vec![flags.to_string()],
+                None,
+            )
+            .build(vec![])
+            .unwrap();
+        let holder = executor.run(transaction).unwrap().component(0).unwrap();
+        let state: HolderState =
+            scrypto_decode(executor.ledger().get_component(holder).unwrap().state()).unwrap();
+
+        Self {
+            executor,
+            key,
+            account,
+            holder,
+            tokens: state.tokens,
+        }
+    }
+
+    fn call(&mut self, method: &str, args: Vec<Vec<u8>>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .add_instruction(Instruction::CallMethod {
+                component_address: self.holder,
+                method: method.to_owned(),
+                args,
+            })
+            .0
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![self.key])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn put_from_account(&mut self) -> Receipt {
+        let resource = Resource::Fungible {
+            amount: 1.into(),
+            resource_address: self
+                .executor
+                .ledger()
+                .get_vault(&self.holder, &self.tokens)
+                .unwrap()
+                .resource_address(),
+        };
+        let holder = self.holder;
+        let transaction = TransactionBuilder::new(&self.executor)
+            .withdraw_from_account(&resource, self.account)
+            .take_from_worktop(&resource, |builder, bid| {
+                builder
+                    .add_instruction(Instruction::CallMethod {
+                        component_address: holder,
+                        method: "put".to_owned(),
+                        args: vec![scrypto_encode(&bid)],
+                    })
+                    .0
+            })
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![self.key])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn call_on_tokens(&mut self, method: &str) -> Receipt {
+        // Vaults can't be passed to calls, so they're identified by the string form of their ID
+        let args = vec![
+            scrypto_encode(&self.holder),
+            scrypto_encode(&self.tokens.to_string()),
+        ];
+        self.call(method, args)
+    }
+}
+
+fn root_cause(receipt: &Receipt) -> Option<&RuntimeError> {
+    receipt.status.error().map(RuntimeError::root_cause)
+}
+
+#[test]
+fn frozen_vault_should_reject_take_and_put() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, FREEZABLE);
+    let receipt = env.call("take", vec![]);
+    assert!(receipt.status.is_committed());
+
+    // Act
+    let receipt = env.call_on_tokens("freeze");
+    assert!(receipt.status.is_committed());
+    let take_receipt = env.call("take", vec![]);
+    let put_receipt = env.put_from_account();
+
+    // Assert
+    assert!(matches!(
+        root_cause(&take_receipt),
+        Some(RuntimeError::VaultError(VaultError::Frozen))
+    ));
+    assert!(matches!(
+        root_cause(&put_receipt),
+        Some(RuntimeError::VaultError(VaultError::Frozen))
+    ));
+}
+
+#[test]
+fn unfrozen_vault_should_allow_take() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, FREEZABLE);
+    let receipt = env.call_on_tokens("freeze");
+    assert!(receipt.status.is_committed());
+
+    // Act
+    let receipt = env.call_on_tokens("unfreeze");
+    assert!(receipt.status.is_committed());
+    let receipt = env.call("take", vec![]);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn frozen_vault_can_be_recalled_from() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, FREEZABLE | RECALLABLE);
+    let receipt = env.call_on_tokens("freeze");
+    assert!(receipt.status.is_committed());
+
+    // Act
+    let receipt = env.call_on_tokens("seize");
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn freezing_non_freezable_resource_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger, 0);
+
+    // Act
+    let receipt = env.call_on_tokens("freeze");
+
+    // Assert
+    assert!(matches!(
+        root_cause(&receipt),
+        Some(RuntimeError::ResourceDefError(
+            ResourceDefError::OperationNotAllowed
+        ))
+    ));
+}
@@ -0,0 +1,172 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+const USD: Address = Address::ResourceDef([1; 26]);
+const ALICE: Address = Address::Component([1; 26]);
+const ALICE_KEY: EcdsaPublicKey = EcdsaPublicKey([1; 33]);
+
+fn genesis() -> Genesis {
+    let mut metadata = HashMap::new();
+    metadata.insert("symbol".to_owned(), "USD".to_owned());
+    Genesis {
+        resources: vec![GenesisResource {
+            address: USD,
+            divisibility: 2,
+            metadata,
+        }],
+        accounts: vec![GenesisAccount {
+            address: ALICE,
+            public_key: ALICE_KEY,
+            balances: vec![(RADIX_TOKEN, Decimal::from(1000)), (USD, Decimal::from(50))],
+        }],
+        packages: Vec::new(),
+    }
+}
+
+#[test]
+fn genesis_accounts_should_hold_their_balances() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_genesis(&genesis()).unwrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_native_system_packages();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_account_balance_at_least(ALICE, Decimal::from(1000), RADIX_TOKEN)
+        .assert_account_balance_at_least(ALICE, Decimal::from(50), USD)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    receipt.expect_commit_success();
+    assert_eq!(ledger.check_bootstrap(), Ok(()));
+    let usd = ledger.get_resource_def(USD).unwrap();
+    assert_eq!(usd.total_supply(), Decimal::from(50));
+    assert_eq!(usd.metadata().get("symbol"), Some(&"USD".to_owned()));
+}
+
+#[test]
+fn genesis_accounts_should_be_owned_by_their_key() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_genesis(&genesis()).unwrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_native_system_packages();
+    let key = executor.new_public_key();
+
+    // Act
+    let account =
+        executor.new_account_with_resources(key, vec![(Decimal::from(20), USD)], ALICE, ALICE_KEY);
+
+    // Assert
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_account_balance_at_least(account, Decimal::from(20), USD)
+        .assert_account_balance_at_least(ALICE, Decimal::from(30), USD)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().expect_commit_success();
+}
+
+#[test]
+fn genesis_xrd_should_be_taken_from_system_component() {
+    // Arrange
+    let bootstrapped = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let ledger = InMemorySubstateStore::with_genesis(&genesis()).unwrap();
+
+    // Assert
+    let system_xrd = |ledger: &InMemorySubstateStore| -> Decimal {
+        ledger
+            .get_vaults(&SYSTEM_COMPONENT)
+            .iter()
+            .map(|(_, vault)| vault.amount())
+            .sum()
+    };
+    assert_eq!(
+        system_xrd(&bootstrapped) - system_xrd(&ledger),
+        Decimal::from(1000)
+    );
+    assert_eq!(
+        ledger.get_resource_def(RADIX_TOKEN).unwrap().total_supply(),
+        bootstrapped
+            .get_resource_def(RADIX_TOKEN)
+            .unwrap()
+            .total_supply()
+    );
+}
+
+#[test]
+fn genesis_packages_should_be_published() {
+    // Arrange
+    let code = include_bytes!("../../assets/account.wasm").to_vec();
+    let package = Address::Package([1; 26]);
+    let mut genesis = genesis();
+    genesis.packages.push(GenesisPackage {
+        address: package,
+        code: code.clone(),
+    });
+
+    // Act
+    let ledger = InMemorySubstateStore::with_genesis(&genesis).unwrap();
+
+    // Assert
+    assert_eq!(ledger.get_package(package).unwrap().code(), code.as_slice());
+}
+
+#[test]
+fn genesis_entity_at_system_address_should_fail() {
+    // Arrange
+    let mut genesis = genesis();
+    genesis.accounts[0].address = SYSTEM_COMPONENT;
+
+    // Act
+    let result = InMemorySubstateStore::with_genesis(&genesis);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(GenesisError::AddressAlreadyUsed(SYSTEM_COMPONENT))
+    ));
+}
+
+#[test]
+fn genesis_entity_at_address_of_other_kind_should_fail() {
+    // Arrange
+    let mut genesis = genesis();
+    genesis.resources[0].address = ALICE;
+
+    // Act
+    let result = InMemorySubstateStore::with_genesis(&genesis);
+
+    // Assert
+    assert!(matches!(result, Err(GenesisError::InvalidAddress(ALICE))));
+}
+
+#[test]
+fn genesis_balance_of_unknown_resource_should_fail() {
+    // Arrange
+    let mut genesis = genesis();
+    genesis.resources.clear();
+
+    // Act
+    let result = InMemorySubstateStore::with_genesis(&genesis);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(GenesisError::ResourceDefNotFound(USD))
+    ));
+}
+
+#[test]
+fn genesis_holding_more_xrd_than_system_should_fail() {
+    // Arrange
+    let mut genesis = genesis();
+    genesis.accounts[0].balances[0].1 = Decimal::from(24_000_000_000_000i128);
+
+    // Act
+    let result = InMemorySubstateStore::with_genesis(&genesis);
+
+    // Assert
+    assert!(matches!(result, Err(GenesisError::NotEnoughXrd { .. })));
+}
@@ -0,0 +1,73 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+const SYSTEM_XRD_VAULT: VaultId = VaultId::new(H256([0u8; 32]), 0);
+
+#[test]
+fn vault_amount_should_be_queryable_at_past_versions() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new().with_history();
+    ledger.bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let before = executor.ledger().get_nonce();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    assert!(receipt.status.is_committed());
+
+    // Assert
+    let old_amount = executor
+        .get_vault_amount_at(SYSTEM_COMPONENT, SYSTEM_XRD_VAULT, before - 1)
+        .unwrap();
+    let new_amount = executor
+        .get_vault_amount_at(SYSTEM_COMPONENT, SYSTEM_XRD_VAULT, before)
+        .unwrap();
+    assert_eq!(old_amount - new_amount, Decimal::from(100));
+    assert_eq!(
+        executor
+            .ledger()
+            .get_vault(&SYSTEM_COMPONENT, &SYSTEM_XRD_VAULT)
+            .unwrap()
+            .amount(),
+        new_amount
+    );
+}
+
+#[test]
+fn component_should_not_exist_before_creation() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new().with_history();
+    ledger.bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let before = executor.ledger().get_nonce();
+
+    // Act
+    let account = executor.new_account(key);
+
+    // Assert
+    assert!(executor
+        .get_component_state_at(account, before - 1)
+        .is_none());
+    assert!(executor.get_component_state_at(account, before).is_some());
+}
+
+#[test]
+fn store_without_history_should_not_answer() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Assert
+    assert!(executor
+        .get_component_state_at(SYSTEM_COMPONENT, 0)
+        .is_none());
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::process::Command;
 
 use radix_engine::ledger::*;
+use radix_engine::model::RuntimeError;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -38,7 +39,7 @@ fn test_package() {
         .build(vec![])
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
 }
 
 #[test]
@@ -54,7 +55,41 @@ fn test_context() {
         .build(vec![])
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
+}
+
+#[test]
+fn test_emit_event() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_package(&compile("context")).unwrap();
+
+    let transaction1 = TransactionBuilder::new(&executor)
+        .call_function(package, "ContextTest", "greet", vec![], Some(account))
+        .build(vec![])
+        .unwrap();
+    let receipt1 = executor.run(transaction1).unwrap();
+    assert!(receipt1.status.is_committed());
+    assert_eq!(receipt1.events.len(), 1);
+    assert_eq!(receipt1.events[0].name, "Greeting");
+}
+
+#[test]
+fn test_random() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_package(&compile("context")).unwrap();
+
+    let transaction1 = TransactionBuilder::new(&executor)
+        .call_function(package, "ContextTest", "roll_dice", vec![], Some(account))
+        .build(vec![])
+        .unwrap();
+    let receipt1 = executor.run(transaction1).unwrap();
+    assert!(receipt1.status.is_committed());
 }
 
 #[test]
@@ -77,7 +112,7 @@ fn test_component() {
         .build(vec![])
         .unwrap();
     let receipt1 = executor.run(transaction1).unwrap();
-    assert!(receipt1.result.is_ok());
+    assert!(receipt1.status.is_committed());
 
     // Find the component address from receipt
     let component = receipt1.component(0).unwrap();
@@ -91,13 +126,63 @@ fn test_component() {
             vec![component.to_string()],
             Some(account),
         )
+        .call_function(
+            package,
+            "ComponentTest",
+            "read_component_state",
+            vec![component.to_string()],
+            Some(account),
+        )
         .call_method(component, "get_component_state", vec![], Some(account))
         .call_method(component, "put_component_state", vec![], Some(account))
         .call_method_with_all_resources(account, "deposit_batch")
         .build(vec![key])
         .unwrap();
     let receipt2 = executor.run(transaction2).unwrap();
-    assert!(receipt2.result.is_ok());
+    assert!(receipt2.status.is_committed());
+}
+
+#[test]
+fn reading_component_state_should_reject_same_named_blueprint_from_other_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let code = compile("component");
+    let package = executor.publish_package(&code).unwrap();
+    let other_package = executor.publish_package(&code).unwrap();
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            other_package,
+            "ComponentTest",
+            "create_component",
+            vec![],
+            Some(account),
+        )
+        .build(vec![])
+        .unwrap();
+    let component = executor.run(transaction).unwrap().component(0).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "ComponentTest",
+            "read_component_state",
+            vec![component.to_string()],
+            Some(account),
+        )
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::PackageNotMatching(address, expected))
+            if *address == component && *expected == package
+    ));
 }
 
 #[test]
@@ -137,7 +222,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 
     let transaction = TransactionBuilder::new(&executor)
         .call_function(
@@ -152,7 +237,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 
     let transaction = TransactionBuilder::new(&executor)
         .call_function(
@@ -167,7 +252,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 
     let transaction = TransactionBuilder::new(&executor)
         .call_function(
@@ -182,7 +267,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 
     let transaction = TransactionBuilder::new(&executor)
         .call_function(
@@ -197,7 +282,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 
     let transaction = TransactionBuilder::new(&executor)
         .call_function(
@@ -212,7 +297,7 @@ fn test_resource_def() {
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 }
 
 #[test]
@@ -247,7 +332,7 @@ fn test_bucket() {
         .build(vec![key])
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -263,11 +348,18 @@ fn test_badge() {
         .call_function(package, "BadgeTest", "split", vec![], Some(account))
         .call_function(package, "BadgeTest", "borrow", vec![], Some(account))
         .call_function(package, "BadgeTest", "query", vec![], Some(account))
+        .call_function(
+            package,
+            "BadgeTest",
+            "check_metadata",
+            vec![],
+            Some(account),
+        )
         .call_method_with_all_resources(account, "deposit_batch")
         .build(vec![key])
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -287,11 +379,33 @@ fn test_call() {
             vec![],
             Some(account),
         )
+        .call_function(
+            package,
+            "MoveTest",
+            "move_buckets_in_batch",
+            vec![],
+            Some(account),
+        )
         .call_method_with_all_resources(account, "deposit_batch")
         .build(vec![key])
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn test_call_methods_should_not_share_memory_across_components() {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("call")).unwrap();
+
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(package, "MoveTest", "count_calls_in_batch", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    let counts: Vec<u32> = receipt.expect_commit_success().output(0);
+    assert_eq!(counts, vec![1, 1, 2]);
 }
 
 #[test]
@@ -352,10 +466,24 @@ fn test_non_fungible() {
             vec![],
             Some(account),
         )
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "get_non_fungibles_with_data_bucket",
+            vec![],
+            Some(account),
+        )
+        .call_function(
+            package,
+            "NonFungibleTest",
+            "get_non_fungibles_with_data_vault",
+            vec![],
+            Some(account),
+        )
         .call_method_with_all_resources(account, "deposit_batch")
         .build(vec![key])
         .unwrap();
     let receipt = executor.run(transaction).unwrap();
     println!("{:?}", receipt);
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
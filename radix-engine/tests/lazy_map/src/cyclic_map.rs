@@ -1,5 +1,5 @@
-use scrypto::prelude::*;
 use scrypto::engine::*;
+use scrypto::prelude::*;
 
 blueprint! {
     struct CyclicMap {
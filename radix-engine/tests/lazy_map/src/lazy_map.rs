@@ -65,5 +65,34 @@ blueprint! {
         pub fn clear_vector(&mut self) -> () {
             self.vector.clear()
         }
+
+        pub fn new_lazy_map_with_keys_in_pages() -> Component {
+            let map = LazyMap::new();
+            for key in ["a", "b", "c", "d", "e"] {
+                map.insert(key.to_owned(), key.to_uppercase());
+            }
+
+            let mut keys = Vec::new();
+            let mut cursor = None;
+            loop {
+                let (page, next) = map.keys(cursor, 2);
+                assert!(page.len() <= 2);
+                keys.extend(page);
+                match next {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            keys.sort();
+            assert_eq!(keys, vec!["a", "b", "c", "d", "e"]);
+
+            let (entries, _) = map.iter(None, 10);
+            assert_eq!(entries.len(), 5);
+            for (k, v) in entries {
+                assert_eq!(k.to_uppercase(), v);
+            }
+
+            LazyMapTest { map, vector: Vec::new(), lazy_maps: LazyMap::new() }.instantiate()
+        }
     }
 }
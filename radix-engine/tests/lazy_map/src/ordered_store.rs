@@ -0,0 +1,30 @@
+use scrypto::prelude::*;
+use scrypto::rust::ops::Bound;
+
+blueprint! {
+    struct OrderedStoreTest {
+        orders: OrderedStore<(Decimal, u64), String>,
+    }
+
+    impl OrderedStoreTest {
+        pub fn scan_in_pages() -> Component {
+            let orders = OrderedStore::new();
+            orders.insert((Decimal::from(3), 1), "c".to_owned());
+            orders.insert((Decimal::from(-1), 2), "a".to_owned());
+            orders.insert((Decimal::from(2), 3), "b".to_owned());
+
+            let first_page = orders.range(.., 2);
+            let last = first_page.last().unwrap().0.clone();
+            let second_page = orders.range((Bound::Excluded(last), Bound::Unbounded), 2);
+
+            let values: Vec<String> = first_page
+                .into_iter()
+                .chain(second_page)
+                .map(|(_, v)| v)
+                .collect();
+            assert_eq!(values, vec!["a", "b", "c"]);
+
+            OrderedStoreTest { orders }.instantiate()
+        }
+    }
+}
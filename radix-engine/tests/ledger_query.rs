@@ -0,0 +1,100 @@
+use radix_engine::ledger::*;
+use scrypto::prelude::*;
+
+const USD: Address = Address::ResourceDef([1; 26]);
+const ALICE: Address = Address::Component([1; 26]);
+const ALICE_KEY: EcdsaPublicKey = EcdsaPublicKey([1; 33]);
+
+fn ledger() -> InMemorySubstateStore {
+    let mut metadata = HashMap::new();
+    metadata.insert("symbol".to_owned(), "USD".to_owned());
+    let genesis = Genesis {
+        resources: vec![GenesisResource {
+            address: USD,
+            divisibility: 2,
+            metadata,
+        }],
+        accounts: vec![GenesisAccount {
+            address: ALICE,
+            public_key: ALICE_KEY,
+            balances: vec![(RADIX_TOKEN, Decimal::from(1000)), (USD, Decimal::from(50))],
+        }],
+        packages: Vec::new(),
+    };
+    InMemorySubstateStore::with_genesis(&genesis).unwrap()
+}
+
+#[test]
+fn query_should_return_component_blueprint() {
+    // Arrange
+    let ledger = ledger();
+    let query = LedgerQuery::new(&ledger);
+
+    // Act
+    let component = query.component(ALICE).unwrap();
+
+    // Assert
+    assert_eq!(component.address, ALICE);
+    assert_eq!(component.package_address, ACCOUNT_PACKAGE);
+    assert_eq!(component.blueprint_name, "Account");
+}
+
+#[test]
+fn query_should_return_resource_metadata_and_supply() {
+    // Arrange
+    let ledger = ledger();
+    let query = LedgerQuery::new(&ledger);
+
+    // Act
+    let resource = query.resource(USD).unwrap();
+
+    // Assert
+    assert_eq!(
+        resource.resource_type,
+        ResourceType::Fungible { divisibility: 2 }
+    );
+    assert_eq!(resource.metadata.get("symbol"), Some(&"USD".to_owned()));
+    assert_eq!(resource.symbol(), Some("USD"));
+    assert_eq!(resource.name(), None);
+    assert_eq!(resource.total_supply, Decimal::from(50));
+}
+
+#[test]
+fn query_should_return_vaults_owned_by_component() {
+    // Arrange
+    let ledger = ledger();
+    let query = LedgerQuery::new(&ledger);
+
+    // Act
+    let vaults = query.vaults(ALICE).unwrap();
+
+    // Assert
+    let mut balances: Vec<(Address, Decimal)> = vaults
+        .iter()
+        .map(|vault| (vault.resource_address, vault.amount))
+        .collect();
+    balances.sort_by_key(|(address, _)| address.to_string());
+    let mut expected = vec![(RADIX_TOKEN, Decimal::from(1000)), (USD, Decimal::from(50))];
+    expected.sort_by_key(|(address, _)| address.to_string());
+    assert_eq!(balances, expected);
+    assert!(vaults
+        .iter()
+        .all(|vault| vault.non_fungible_keys.is_empty() && !vault.frozen));
+}
+
+#[test]
+fn query_of_missing_entity_should_return_none() {
+    // Arrange
+    let ledger = ledger();
+    let query = LedgerQuery::new(&ledger);
+
+    // Act
+    let component = query.component(Address::Component([2; 26]));
+    let resource = query.resource(Address::ResourceDef([2; 26]));
+    let vaults = query.vaults(Address::Component([2; 26]));
+
+    // Assert
+    assert!(component.is_none());
+    assert!(resource.is_none());
+    assert!(vaults.is_none());
+}
@@ -0,0 +1,62 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn message_should_be_kept_in_receipt() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .message("scenario step 1")
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(
+        receipt.transaction.message,
+        Some(b"scenario step 1".to_vec())
+    );
+}
+
+#[test]
+fn transaction_without_message_should_have_none() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor).build(vec![]).unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert_eq!(receipt.transaction.message, None);
+}
+
+#[test]
+fn oversized_message_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .message(vec![0u8; MAX_MESSAGE_SIZE + 1])
+        .build(vec![])
+        .unwrap();
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::MessageTooLarge {
+            size,
+            max_size: MAX_MESSAGE_SIZE,
+        }) if size == MAX_MESSAGE_SIZE + 1
+    ));
+}
@@ -0,0 +1,89 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    #[metadata(
+        version = "1.2.0",
+        author = "Radix",
+        description = "Keeps a greeting",
+        repository = "https://github.com/radixdlt/radixdlt-scrypto"
+    )]
+    struct Greeter {
+        greeting: String,
+    }
+
+    impl Greeter {
+        pub fn new() -> Component {
+            Self {
+                greeting: "Hello".to_owned(),
+            }
+            .instantiate()
+        }
+    }
+}
+
+mod plain {
+    use scrypto::prelude::*;
+
+    blueprint! {
+        struct Plain {}
+
+        impl Plain {
+            pub fn new() -> Component {
+                Self {}.instantiate()
+            }
+        }
+    }
+}
+
+fn package() -> NativePackage {
+    NativePackage::new()
+        .with_blueprint("Greeter", Greeter_main, Greeter_abi)
+        .with_blueprint("Plain", plain::Plain_main, plain::Plain_abi)
+}
+
+fn expected_metadata() -> BTreeMap<String, String> {
+    vec![
+        ("version", "1.2.0"),
+        ("author", "Radix"),
+        ("description", "Keeps a greeting"),
+        ("repository", "https://github.com/radixdlt/radixdlt-scrypto"),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+    .collect()
+}
+
+#[test]
+fn test_metadata_is_exported_in_abi() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(package());
+
+    // Act
+    let greeter = executor.export_abi(package, "Greeter").unwrap();
+    let plain = executor.export_abi(package, "Plain").unwrap();
+
+    // Assert
+    assert_eq!(greeter.metadata, expected_metadata());
+    assert!(plain.metadata.is_empty());
+}
+
+#[test]
+fn test_metadata_is_stored_with_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let package = executor.publish_native_package(package());
+
+    // Assert
+    let package = executor.ledger().get_package(package).unwrap();
+    let metadata = package.metadata();
+    assert_eq!(metadata.len(), 1);
+    assert_eq!(metadata.get("Greeter"), Some(&expected_metadata()));
+}
@@ -0,0 +1,203 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{FailureCategory, Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::abi;
+use scrypto::prelude::*;
+
+blueprint! {
+    #[reentrant]
+    struct Restricted {}
+
+    impl Restricted {
+        pub fn instantiate_restricted() -> Component {
+            Self {}.instantiate()
+        }
+
+        #[access(component)]
+        pub fn internal(&self) -> u32 {
+            1
+        }
+
+        #[access(package)]
+        pub fn friendly(&self) -> u32 {
+            2
+        }
+
+        pub fn call_internal(&self) -> u32 {
+            match Context::actor() {
+                Actor::Component(address) => Component::from(address).call("internal", vec![]),
+                Actor::Blueprint(..) => panic!("Not a component"),
+            }
+        }
+    }
+}
+
+mod caller {
+    use scrypto::prelude::*;
+
+    blueprint! {
+        struct Caller {}
+
+        impl Caller {
+            pub fn call_internal(target: Address) -> u32 {
+                Component::from(target).call("internal", vec![])
+            }
+
+            pub fn call_friendly(target: Address) -> u32 {
+                Component::from(target).call("friendly", vec![])
+            }
+        }
+    }
+}
+
+fn native_package() -> NativePackage {
+    NativePackage::new()
+        .with_blueprint("Restricted", Restricted_main, Restricted_abi)
+        .with_blueprint("Caller", caller::Caller_main, caller::Caller_abi)
+}
+
+fn instantiate_restricted<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    package: Address,
+) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(
+            package,
+            "Restricted",
+            "instantiate_restricted",
+            vec![],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().component(0).unwrap()
+}
+
+fn call_function<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    package: Address,
+    function: &str,
+    target: Address,
+) -> Receipt {
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(package, "Caller", function, vec![target.to_string()], None)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn method_access_should_be_exported_in_abi() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+
+    // Act
+    let abi = executor.export_abi(package, "Restricted").unwrap();
+
+    // Assert
+    let access = |name: &str| abi.methods.iter().find(|m| m.name == name).unwrap().access;
+    assert_eq!(access("internal"), abi::Access::Component);
+    assert_eq!(access("friendly"), abi::Access::Package);
+    assert_eq!(access("call_internal"), abi::Access::Public);
+}
+
+#[test]
+fn component_method_can_be_called_by_component_itself() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_restricted(&mut executor, package);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "call_internal", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn component_method_cannot_be_called_by_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_restricted(&mut executor, package);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "internal", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error(),
+        Some(RuntimeError::MethodNotAccessible(address, method))
+            if *address == component && method == "internal"
+    ));
+    assert_eq!(
+        receipt.status.error().unwrap().category(),
+        FailureCategory::Auth
+    );
+}
+
+#[test]
+fn component_method_cannot_be_called_by_same_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_restricted(&mut executor, package);
+
+    // Act
+    let receipt = call_function(&mut executor, package, "call_internal", component);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::MethodNotAccessible(..))
+    ));
+}
+
+#[test]
+fn package_method_can_be_called_by_same_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_restricted(&mut executor, package);
+
+    // Act
+    let receipt = call_function(&mut executor, package, "call_friendly", component);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn package_method_cannot_be_called_by_other_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let other_package = executor.publish_native_package(native_package());
+    let component = instantiate_restricted(&mut executor, package);
+
+    // Act
+    let receipt = call_function(&mut executor, other_package, "call_friendly", component);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::MethodNotAccessible(..))
+    ));
+}
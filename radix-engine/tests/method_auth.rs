@@ -0,0 +1,135 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{FailureCategory, Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Gate {
+        admin: ResourceDef,
+    }
+
+    impl Gate {
+        #[auth(admin)]
+        pub fn open(&self) -> u32 {
+            1
+        }
+
+        /// Calls `open` with a bucket ref to either the admin badge or another token.
+        pub fn try_open(with_badge: bool) -> Vec<Bucket> {
+            let admin = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let other = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let gate = Self {
+                admin: admin.resource_def(),
+            }
+            .instantiate();
+
+            let proof = if with_badge {
+                admin.present()
+            } else {
+                other.present()
+            };
+            gate.call::<u32>("open", vec![scrypto_encode(&proof)]);
+            vec![admin, other]
+        }
+
+        #[auth(admin)]
+        pub fn set_admin(&mut self, admin: ResourceDef) {
+            self.admin = admin;
+        }
+
+        /// Hands the gate over to a new admin badge, then calls `open` with a bucket ref to
+        /// either the new or the old badge.
+        pub fn try_open_after_handover(with_new_badge: bool) -> Vec<Bucket> {
+            let old = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let new = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let gate = Self {
+                admin: old.resource_def(),
+            }
+            .instantiate();
+            gate.call::<()>(
+                "set_admin",
+                vec![
+                    scrypto_encode(&new.resource_def()),
+                    scrypto_encode(&old.present()),
+                ],
+            );
+
+            let proof = if with_new_badge {
+                new.present()
+            } else {
+                old.present()
+            };
+            gate.call::<u32>("open", vec![scrypto_encode(&proof)]);
+            vec![old, new]
+        }
+    }
+}
+
+fn run(function: &str, with_badge: bool) -> Receipt {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor
+        .publish_native_package(NativePackage::new().with_blueprint("Gate", Gate_main, Gate_abi));
+
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "Gate",
+            function,
+            vec![with_badge.to_string()],
+            Some(account),
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn method_can_be_called_with_badge() {
+    // Act
+    let receipt = run("try_open", true);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn method_without_badge_should_be_rejected_by_engine() {
+    // Act
+    let receipt = run("try_open", false);
+
+    // Assert
+    let error = receipt.status.error().unwrap();
+    assert_eq!(error.category(), FailureCategory::Auth);
+    assert!(matches!(
+        error.root_cause(),
+        RuntimeError::MethodNotAuthorized(_, method) if method == "open"
+    ));
+    assert!(receipt.logs.is_empty());
+}
+
+#[test]
+fn method_can_be_called_with_badge_set_after_instantiation() {
+    // Act
+    let receipt = run("try_open_after_handover", true);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn method_with_badge_replaced_after_instantiation_should_be_rejected_by_engine() {
+    // Act
+    let receipt = run("try_open_after_handover", false);
+
+    // Assert
+    let error = receipt.status.error().unwrap();
+    assert!(matches!(
+        error.root_cause(),
+        RuntimeError::MethodNotAuthorized(_, method) if method == "open"
+    ));
+}
@@ -0,0 +1,123 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use sbor::{Decode, Describe, Encode, TypeId};
+use scrypto::prelude::*;
+
+// A type shared by both blueprints
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct PoolInfo {
+    fee: Decimal,
+}
+
+blueprint! {
+    struct Factory {
+        pools: Vec<Pool>,
+    }
+
+    impl Factory {
+        pub fn new() -> Component {
+            Self { pools: Vec::new() }.instantiate()
+        }
+
+        pub fn create_pool(&mut self, fee: Decimal) -> Address {
+            let address: Address = Pool::new(PoolInfo { fee }, self.pools.len() as u32).into();
+            self.pools.push(Pool::from(address));
+            address
+        }
+
+        pub fn total_fee(&self) -> Decimal {
+            self.pools
+                .iter()
+                .fold(Decimal::zero(), |total, pool| total + pool.info().fee)
+        }
+    }
+}
+
+blueprint! {
+    struct Pool {
+        info: PoolInfo,
+        index: u32,
+    }
+
+    impl Pool {
+        pub fn new(info: PoolInfo, index: u32) -> Component {
+            Self { info, index }.instantiate()
+        }
+
+        pub fn info(&self) -> PoolInfo {
+            PoolInfo {
+                fee: self.info.fee,
+            }
+        }
+
+        pub fn index(&self) -> u32 {
+            self.index
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    factory: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(
+            NativePackage::new()
+                .with_blueprint("Factory", Factory_main, Factory_abi)
+                .with_blueprint("Pool", Pool_main, Pool_abi),
+        );
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Factory", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let factory = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self { executor, factory }
+    }
+
+    fn call<T: Decode>(&mut self, component: Address, method: &str, args: Vec<String>) -> T {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(component, method, args, None)
+            .build(vec![])
+            .unwrap();
+        let receipt = self.executor.run(transaction).unwrap();
+        receipt.expect_commit_success().output(0)
+    }
+}
+
+#[test]
+fn blueprint_can_instantiate_other_blueprint_of_package() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let factory = env.factory;
+    env.call::<Address>(factory, "create_pool", vec!["1".to_owned()]);
+    let pool: Address = env.call(factory, "create_pool", vec!["2".to_owned()]);
+
+    // Assert
+    let component = env.executor.ledger().get_component(pool).unwrap();
+    assert_eq!(component.blueprint_name(), "Pool");
+    assert_eq!(env.call::<u32>(pool, "index", vec![]), 1);
+}
+
+#[test]
+fn blueprint_can_call_other_blueprint_with_shared_type() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let factory = env.factory;
+    env.call::<Address>(factory, "create_pool", vec!["1.5".to_owned()]);
+    env.call::<Address>(factory, "create_pool", vec!["2".to_owned()]);
+
+    // Act
+    let total_fee: Decimal = env.call(factory, "total_fee", vec![]);
+
+    // Assert
+    assert_eq!(total_fee, Decimal::from_str("3.5").unwrap());
+}
@@ -0,0 +1,189 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{FailureCategory, RuntimeError};
+use radix_engine::transaction::*;
+use sbor::describe::Type;
+use sbor::Value;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Hello {
+        sample_vault: Vault,
+    }
+
+    impl Hello {
+        pub fn instantiate_hello() -> Component {
+            let my_bucket: Bucket = ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM)
+                .metadata("name", "HelloToken")
+                .initial_supply_fungible(1000);
+
+            Self {
+                sample_vault: Vault::with_bucket(my_bucket),
+            }
+            .instantiate()
+        }
+
+        pub fn free_token(&mut self) -> Bucket {
+            self.sample_vault.take(1)
+        }
+
+        pub fn greedy_token(&mut self) -> Bucket {
+            self.sample_vault.take(1001)
+        }
+
+        pub fn fail() {
+            panic!("Intentional failure");
+        }
+    }
+}
+
+fn native_package() -> NativePackage {
+    NativePackage::new().with_blueprint("Hello", Hello_main, Hello_abi)
+}
+
+fn instantiate_hello<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    package: Address,
+) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(package, "Hello", "instantiate_hello", vec![], None)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().component(0).unwrap()
+}
+
+#[test]
+fn native_blueprint_can_be_called() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_hello(&mut executor, package);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "free_token", vec![], Some(account))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn native_blueprint_panic_should_fail_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(package, "Hello", "fail", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert_eq!(
+        receipt.status.error().unwrap().category(),
+        FailureCategory::ApplicationPanic
+    );
+    assert!(receipt.logs[0].1.contains("Intentional failure"));
+}
+
+#[test]
+fn native_blueprint_engine_error_should_fail_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_hello(&mut executor, package);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "greedy_token", vec![], Some(account))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::VaultError(_))
+    ));
+}
+
+#[test]
+fn component_state_can_be_decoded_with_field_names() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_native_package(native_package());
+    let component = instantiate_hello(&mut executor, package);
+
+    // Act
+    let (abi, fields) = executor.export_abi_with_state(component).unwrap();
+
+    // Assert
+    assert!(matches!(abi.state, Some(Type::Struct { ref name, .. }) if name == "Hello"));
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].0, "sample_vault");
+    assert!(matches!(fields[0].1, Value::Custom(_, _)));
+}
+
+#[test]
+fn system_packages_can_run_natively() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_native_system_packages();
+    let key = executor.new_public_key();
+
+    // Act
+    let account = executor.new_account(key);
+
+    // Assert
+    let abi = executor.export_abi(ACCOUNT_PACKAGE, "Account").unwrap();
+    assert!(abi.methods.iter().any(|m| m.name == "deposit_batch"));
+    let component = executor.ledger().get_component(account).unwrap();
+    assert_eq!(component.package_address(), ACCOUNT_PACKAGE);
+    assert_eq!(component.blueprint_name(), "Account");
+    assert!(executor
+        .export_abi(SYSTEM_PACKAGE, "Staking")
+        .unwrap()
+        .functions
+        .iter()
+        .any(|f| f.name == "instantiate_staking"));
+}
+
+#[test]
+fn system_packages_should_have_the_same_abi_as_the_bundled_wasm() {
+    // Arrange
+    let mut wasm_ledger = InMemorySubstateStore::with_bootstrap();
+    let wasm_executor = TransactionExecutor::new(&mut wasm_ledger, false);
+    let mut native_ledger = InMemorySubstateStore::with_bootstrap();
+    let native_executor =
+        TransactionExecutor::new(&mut native_ledger, false).with_native_system_packages();
+
+    for (package, blueprint) in [
+        (SYSTEM_PACKAGE, "System"),
+        (SYSTEM_PACKAGE, "Faucet"),
+        (SYSTEM_PACKAGE, "Staking"),
+        (ACCOUNT_PACKAGE, "Account"),
+        (ACCOUNT_PACKAGE, "Escrow"),
+    ] {
+        // Act
+        let wasm_abi = wasm_executor.export_abi(package, blueprint).unwrap();
+        let native_abi = native_executor.export_abi(package, blueprint).unwrap();
+
+        // Assert
+        assert_eq!(format!("{:?}", native_abi), format!("{:?}", wasm_abi));
+    }
+}
@@ -125,5 +125,38 @@ blueprint! {
             non_fungible
         }
 
+        pub fn get_non_fungibles_with_data_bucket() -> Bucket {
+            let bucket = Self::create_non_fungible_fixed();
+            let non_fungibles: Vec<(NonFungibleKey, Sandwich)> = bucket.non_fungibles_with_data();
+            let names: Vec<String> = non_fungibles.into_iter().map(|(_, data)| data.name).collect();
+            assert_eq!(names, vec!["One", "Two", "Three"]);
+            bucket
+        }
+
+        pub fn get_non_fungibles_with_data_vault() {
+            let vault = Vault::with_bucket(Self::create_non_fungible_fixed());
+            let non_fungibles: Vec<(NonFungibleKey, Sandwich)> = vault.non_fungibles_with_data();
+            assert_eq!(non_fungibles.len(), 3);
+            for (key, data) in non_fungibles {
+                let expected: Sandwich = vault.get_non_fungible_data(&key);
+                assert_eq!(data.name, expected.name);
+                assert_eq!(data.available, expected.available);
+            }
+
+            NonFungibleTest { vault }.instantiate();
+        }
+
+        pub fn create_non_fungible_with<T: NonFungibleData>(key: NonFungibleKey, data: T) -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .metadata("name", "Generic")
+                .initial_supply_non_fungible([(key, data)])
+        }
+
+        pub fn enumerate_non_fungibles(count: u32) -> Vec<(NonFungibleKey, String)> {
+            (0..count)
+                .map(|i| (NonFungibleKey::from(i as u128), format!("Sandwich #{}", i)))
+                .collect()
+        }
+
     }
 }
@@ -0,0 +1,123 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Instruction, Receipt, Transaction, TransactionKind};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct Ticket {
+    seat: u32,
+}
+
+blueprint! {
+    struct Venue {}
+
+    impl Venue {
+        pub fn issue_ticket() -> (Bucket, NonFungibleAddress) {
+            let ticket = ResourceBuilder::new_non_fungible()
+                .initial_supply_non_fungible([(NonFungibleKey::from(7), Ticket { seat: 7 })]);
+            let address = ticket.get_non_fungible_address();
+            (ticket, address)
+        }
+
+        pub fn admit(proof: BucketRef, ticket: NonFungibleAddress) {
+            proof.check_non_fungible(&ticket);
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    key: EcdsaPublicKey,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let key = executor.new_public_key();
+        let package = executor.publish_native_package(
+            NativePackage::new().with_blueprint("Venue", Venue_main, Venue_abi),
+        );
+
+        Self {
+            executor,
+            key,
+            package,
+        }
+    }
+
+    fn admit_signer(&mut self, ticket: NonFungibleAddress) -> Receipt {
+        // The signers are presented by the virtual bucket ref of ECDSA badges
+        let transaction = Transaction {
+            instructions: vec![
+                Instruction::CallFunction {
+                    package_address: self.package,
+                    blueprint_name: "Venue".to_owned(),
+                    function: "admit".to_owned(),
+                    args: vec![scrypto_encode(&ECDSA_TOKEN_RID), scrypto_encode(&ticket)],
+                },
+                Instruction::End {
+                    signatures: vec![self.key],
+                },
+            ],
+            message: None,
+            nonce: None,
+            expiration_epoch: None,
+            kind: TransactionKind::User,
+        };
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn signer_badge_should_match_address_of_public_key() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let ticket = NonFungibleAddress::from_public_key(&env.key);
+
+    // Act
+    let receipt = env.admit_signer(ticket);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn signer_badge_should_not_match_address_of_other_key() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let other_key = env.executor.new_public_key();
+    let ticket = NonFungibleAddress::from_public_key(&other_key);
+
+    // Act
+    let receipt = env.admit_signer(ticket);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
+
+#[test]
+fn non_fungible_address_should_be_returned_from_call() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let account = env.executor.new_account(env.key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&env.executor)
+        .call_function(env.package, "Venue", "issue_ticket", vec![], None)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![env.key])
+        .unwrap();
+    let receipt = env.executor.run(transaction).unwrap();
+
+    // Assert
+    let (_, ticket): (BucketId, NonFungibleAddress) = receipt.expect_commit_success().output(0);
+    assert_eq!(
+        ticket,
+        NonFungibleAddress::new(receipt.resource_def(0).unwrap(), NonFungibleKey::from(7))
+    );
+}
@@ -0,0 +1,133 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn replayed_transaction_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let transaction = TransactionBuilder::new(&executor)
+        .nonce(1)
+        .build(vec![key])
+        .unwrap();
+
+    // Act
+    let first = executor.run(transaction.clone()).unwrap();
+    let replay = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(first.status.is_committed());
+    assert!(matches!(
+        replay.status,
+        TransactionStatus::Rejected(RejectionReason::NonceAlreadyUsed {
+            nonce: 1,
+            last_nonce: 1,
+            ..
+        })
+    ));
+    assert_eq!(executor.ledger().get_signer_nonce(&key), Some(1));
+}
+
+#[test]
+fn greater_nonce_should_be_accepted() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let first = TransactionBuilder::new(&executor)
+        .nonce(5)
+        .build(vec![key])
+        .unwrap();
+    executor.run(first).unwrap();
+
+    // Act
+    let lower = TransactionBuilder::new(&executor)
+        .nonce(3)
+        .build(vec![key])
+        .unwrap();
+    let lower = executor.run(lower).unwrap();
+    let greater = TransactionBuilder::new(&executor)
+        .nonce(6)
+        .build(vec![key])
+        .unwrap();
+    let greater = executor.run(greater).unwrap();
+
+    // Assert
+    assert!(matches!(
+        lower.status,
+        TransactionStatus::Rejected(RejectionReason::NonceAlreadyUsed { .. })
+    ));
+    assert!(greater.status.is_committed());
+    assert_eq!(executor.ledger().get_signer_nonce(&key), Some(6));
+}
+
+#[test]
+fn nonce_should_not_be_recorded_for_failed_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let transaction = TransactionBuilder::new(&executor)
+        .nonce(1)
+        .assert_worktop_contains(Decimal::from(1), RADIX_TOKEN)
+        .build(vec![key])
+        .unwrap();
+
+    // Act
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.error().is_some());
+    assert_eq!(executor.ledger().get_signer_nonce(&key), None);
+}
+
+#[test]
+fn expired_transaction_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    ledger.set_epoch(10);
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let current = TransactionBuilder::new(&executor)
+        .expiration_epoch(10)
+        .build(vec![])
+        .unwrap();
+    let current = executor.run(current).unwrap();
+    let expired = TransactionBuilder::new(&executor)
+        .expiration_epoch(9)
+        .build(vec![])
+        .unwrap();
+    let expired = executor.run(expired).unwrap();
+
+    // Assert
+    assert!(current.status.is_committed());
+    assert!(matches!(
+        expired.status,
+        TransactionStatus::Rejected(RejectionReason::Expired {
+            expiration_epoch: 9,
+            current_epoch: 10,
+        })
+    ));
+}
+
+#[test]
+fn transaction_without_nonce_should_be_replayable() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let transaction = TransactionBuilder::new(&executor).build(vec![key]).unwrap();
+
+    // Act
+    let first = executor.run(transaction.clone()).unwrap();
+    let second = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(first.status.is_committed());
+    assert!(second.status.is_committed());
+    assert_eq!(executor.ledger().get_signer_nonce(&key), None);
+}
@@ -0,0 +1,106 @@
+use radix_engine::model::*;
+use scrypto::engine::*;
+use scrypto::resource::resource_flags::*;
+use scrypto::resource::resource_permissions::*;
+use scrypto::rust::collections::HashMap;
+use scrypto::types::*;
+
+fn max_supply_resource_def() -> ResourceDef {
+    let mut authorities = HashMap::new();
+    authorities.insert(ECDSA_TOKEN, MAY_MINT | MAY_BURN);
+    ResourceDef::new(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        MINTABLE | BURNABLE,
+        0,
+        authorities,
+        &Some(NewSupply::Fungible {
+            amount: Decimal::MAX,
+        }),
+    )
+    .unwrap()
+}
+
+#[test]
+fn minting_beyond_max_supply_should_fail() {
+    // Arrange
+    let mut resource_def = max_supply_resource_def();
+
+    // Act
+    let result = resource_def.mint(&Supply::Fungible { amount: Decimal(1) }, Some(ECDSA_TOKEN));
+
+    // Assert
+    assert!(matches!(result, Err(ResourceDefError::SupplyOverflow)));
+    assert_eq!(resource_def.total_supply(), Decimal::MAX);
+}
+
+#[test]
+fn burning_max_supply_should_succeed() {
+    // Arrange
+    let mut resource_def = max_supply_resource_def();
+
+    // Act
+    let result = resource_def.burn(
+        Supply::Fungible {
+            amount: Decimal::MAX,
+        },
+        Some(ECDSA_TOKEN),
+    );
+
+    // Assert
+    assert!(result.is_ok());
+    assert_eq!(resource_def.total_supply(), Decimal::zero());
+}
+
+#[test]
+fn putting_beyond_max_balance_should_fail() {
+    // Arrange
+    let resource_type = ResourceType::Fungible { divisibility: 18 };
+    let mut bucket = Bucket::new(
+        RADIX_TOKEN,
+        resource_type,
+        Supply::Fungible {
+            amount: Decimal::MAX,
+        },
+    );
+    let other = Bucket::new(
+        RADIX_TOKEN,
+        resource_type,
+        Supply::Fungible { amount: Decimal(1) },
+    );
+
+    // Act
+    let result = bucket.put(other);
+
+    // Assert
+    assert!(matches!(result, Err(BucketError::BalanceOverflow)));
+    assert_eq!(bucket.amount(), Decimal::MAX);
+}
+
+#[test]
+fn vault_put_beyond_max_balance_should_fail() {
+    // Arrange
+    let resource_type = ResourceType::Fungible { divisibility: 18 };
+    let mut vault = Vault::new(Bucket::new(
+        RADIX_TOKEN,
+        resource_type,
+        Supply::Fungible {
+            amount: Decimal::MAX,
+        },
+    ));
+
+    // Act
+    let result = vault.put(Bucket::new(
+        RADIX_TOKEN,
+        resource_type,
+        Supply::Fungible {
+            amount: Decimal::MAX,
+        },
+    ));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(VaultError::AccountingError(BucketError::BalanceOverflow))
+    ));
+}
@@ -0,0 +1,271 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{FailureCategory, Instruction, Receipt, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+const ACCOUNT_CODE: &[u8] = include_bytes!("../../assets/account.wasm");
+const SYSTEM_CODE: &[u8] = include_bytes!("../../assets/system.wasm");
+
+blueprint! {
+    struct Publisher {}
+
+    impl Publisher {
+        pub fn publish(code: Vec<u8>, owner_badge: Address) -> Address {
+            Package::with_owner(&code, owner_badge).into()
+        }
+
+        pub fn upgrade(package_address: Address, code: Vec<u8>, auth: BucketRef) -> u32 {
+            Package::from(package_address).upgrade(&code, auth)
+        }
+
+        pub fn instantiate_pinned() -> Component {
+            let component = Self {}.instantiate();
+            component.set_package_version(Some(0));
+            component
+        }
+
+        pub fn unpin(component_address: Address) {
+            Component::from(component_address).set_package_version(None);
+        }
+
+        pub fn unpin_self(&self) {
+            match Context::actor() {
+                Actor::Component(address) => Component::from(address).set_package_version(None),
+                Actor::Blueprint(..) => panic!("Not a component"),
+            }
+        }
+    }
+}
+
+fn native_package() -> NativePackage {
+    NativePackage::new().with_blueprint("Publisher", Publisher_main, Publisher_abi)
+}
+
+fn new_badge<L: SubstateStore>(executor: &mut TransactionExecutor<L>, account: Address) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .new_badge_fixed(HashMap::new(), 1.into())
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().resource_def(0).unwrap()
+}
+
+fn publish<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    publisher: Address,
+    owner_badge: Address,
+) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .add_instruction(Instruction::CallFunction {
+            package_address: publisher,
+            blueprint_name: "Publisher".to_owned(),
+            function: "publish".to_owned(),
+            args: vec![
+                scrypto_encode(&ACCOUNT_CODE.to_vec()),
+                scrypto_encode(&owner_badge),
+            ],
+        })
+        .0
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().package(0).unwrap()
+}
+
+fn upgrade<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    publisher: Address,
+    package: Address,
+    badge: Address,
+    account: Address,
+    key: EcdsaPublicKey,
+) -> Receipt {
+    let badge = Resource::Fungible {
+        amount: 1.into(),
+        resource_address: badge,
+    };
+    let transaction = TransactionBuilder::new(executor)
+        .withdraw_from_account(&badge, account)
+        .take_from_worktop(&badge, |builder, bid| {
+            builder.create_bucket_ref(bid, |builder, rid| {
+                builder
+                    .add_instruction(Instruction::CallFunction {
+                        package_address: publisher,
+                        blueprint_name: "Publisher".to_owned(),
+                        function: "upgrade".to_owned(),
+                        args: vec![
+                            scrypto_encode(&package),
+                            scrypto_encode(&SYSTEM_CODE.to_vec()),
+                            scrypto_encode(&rid),
+                        ],
+                    })
+                    .0
+            })
+        })
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+fn call<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    instruction: Instruction,
+) -> Receipt {
+    let transaction = TransactionBuilder::new(executor)
+        .add_instruction(instruction)
+        .0
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn package_can_be_upgraded_with_owner_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let badge = new_badge(&mut executor, account);
+    let publisher = executor.publish_native_package(native_package());
+    let package = publish(&mut executor, publisher, badge);
+
+    // Act
+    let receipt = upgrade(&mut executor, publisher, package, badge, account, key);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    let package = executor.ledger().get_package(package).unwrap();
+    assert_eq!(package.version(), 1);
+    assert_eq!(package.code(), SYSTEM_CODE);
+    assert_eq!(package.code_at(0), Some(ACCOUNT_CODE));
+}
+
+#[test]
+fn package_cannot_be_upgraded_with_other_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let badge = new_badge(&mut executor, account);
+    let other_badge = new_badge(&mut executor, account);
+    let publisher = executor.publish_native_package(native_package());
+    let package = publish(&mut executor, publisher, badge);
+
+    // Act
+    let receipt = upgrade(&mut executor, publisher, package, other_badge, account, key);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::PackageUpgradeNotAllowed(address)) if *address == package
+    ));
+    assert_eq!(executor.ledger().get_package(package).unwrap().version(), 0);
+}
+
+#[test]
+fn package_without_owner_cannot_be_upgraded() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let badge = new_badge(&mut executor, account);
+    let publisher = executor.publish_native_package(native_package());
+    let package = executor.publish_package(ACCOUNT_CODE).unwrap();
+
+    // Act
+    let receipt = upgrade(&mut executor, publisher, package, badge, account, key);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::PackageNotUpgradeable(_))
+    ));
+}
+
+#[test]
+fn pinned_component_should_run_pinned_version() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let pinned = executor.new_account(key);
+    let unpinned = executor.new_account(key);
+    let ledger = executor.ledger_mut();
+    let mut package = ledger.get_package(ACCOUNT_PACKAGE).unwrap();
+    package.upgrade(SYSTEM_CODE.to_vec());
+    ledger.put_package(ACCOUNT_PACKAGE, package);
+    let mut component = ledger.get_component(pinned).unwrap();
+    component.set_package_version(Some(0));
+    ledger.put_component(pinned, component);
+
+    // Act
+    let deposit = |component_address| Instruction::CallMethodWithAllResources {
+        component_address,
+        method: "deposit_batch".to_owned(),
+    };
+    let pinned_receipt = call(&mut executor, deposit(pinned));
+    let unpinned_receipt = call(&mut executor, deposit(unpinned));
+
+    // Assert
+    assert!(pinned_receipt.status.is_committed());
+    assert_eq!(
+        unpinned_receipt.status.error().unwrap().category(),
+        FailureCategory::ApplicationPanic
+    );
+}
+
+#[test]
+fn pinned_component_can_only_be_unpinned_by_itself() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let publisher = executor.publish_native_package(native_package());
+    let receipt = call(
+        &mut executor,
+        Instruction::CallFunction {
+            package_address: publisher,
+            blueprint_name: "Publisher".to_owned(),
+            function: "instantiate_pinned".to_owned(),
+            args: vec![],
+        },
+    );
+    let component = receipt.component(0).unwrap();
+
+    // Act
+    let unpin_receipt = call(
+        &mut executor,
+        Instruction::CallFunction {
+            package_address: publisher,
+            blueprint_name: "Publisher".to_owned(),
+            function: "unpin".to_owned(),
+            args: vec![scrypto_encode(&component)],
+        },
+    );
+    let unpin_self_receipt = call(
+        &mut executor,
+        Instruction::CallMethod {
+            component_address: component,
+            method: "unpin_self".to_owned(),
+            args: vec![],
+        },
+    );
+
+    // Assert
+    assert!(matches!(
+        unpin_receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::PackageVersionUpdateNotAllowed(_))
+    ));
+    assert!(unpin_self_receipt.status.is_committed());
+    assert_eq!(
+        executor
+            .ledger()
+            .get_component(component)
+            .unwrap()
+            .package_version(),
+        None
+    );
+}
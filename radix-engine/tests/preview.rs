@@ -0,0 +1,68 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn preview_should_report_balance_changes() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal::from(10),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let (receipt, diff) = executor.preview(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(
+        diff.balance_change(account, RADIX_TOKEN),
+        Some(Decimal::from(-10))
+    );
+    assert_eq!(
+        diff.balance_change(other_account, RADIX_TOKEN),
+        Some(Decimal::from(10))
+    );
+    assert!(diff.new_entities.is_empty());
+}
+
+#[test]
+fn preview_should_not_commit() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let nonce = executor.ledger().get_nonce();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+        .new_account_with_resource(key, Decimal::one(), RADIX_TOKEN)
+        .build(Vec::new())
+        .unwrap();
+    let (receipt, diff) = executor.preview(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    let account = receipt.component(0).unwrap();
+    assert_eq!(diff.new_entities, vec![account]);
+    assert_eq!(
+        diff.balance_change(account, RADIX_TOKEN),
+        Some(Decimal::one())
+    );
+    assert!(executor.ledger().get_component(account).is_none());
+    assert_eq!(executor.ledger().get_nonce(), nonce);
+}
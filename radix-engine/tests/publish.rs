@@ -0,0 +1,82 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+
+const CODE: &[u8] = include_bytes!("../../assets/account.wasm");
+
+#[test]
+fn publishing_package_within_limits_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let result = executor.publish_package(CODE);
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn publishing_too_large_package_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_package_limits(PackageLimits {
+            max_code_size: 1024,
+            ..PackageLimits::default()
+        });
+
+    // Act
+    let result = executor.publish_package(CODE);
+
+    // Assert
+    let error = match result {
+        Err(RuntimeError::InvokeError(error)) => error,
+        _ => panic!("Expected an invoke error"),
+    };
+    let host_error = error.as_host_error().unwrap();
+    assert!(matches!(
+        host_error.downcast_ref::<RuntimeError>(),
+        Some(RuntimeError::WasmValidationError(
+            WasmValidationError::PackageTooLarge { limit: 1024, actual }
+        )) if *actual == CODE.len()
+    ));
+}
+
+#[test]
+fn package_with_too_many_exports_should_be_rejected() {
+    // Act
+    let result = validate_module_with_limits(
+        CODE,
+        &PackageLimits {
+            max_exports: 1,
+            ..PackageLimits::default()
+        },
+    );
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(WasmValidationError::TooManyExports { limit: 1, actual }) if actual > 1
+    ));
+}
+
+#[test]
+fn package_with_too_many_functions_should_be_rejected() {
+    // Act
+    let result = validate_module_with_limits(
+        CODE,
+        &PackageLimits {
+            max_functions: 1,
+            ..PackageLimits::default()
+        },
+    );
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(WasmValidationError::TooManyFunctions { limit: 1, actual }) if actual > 1
+    ));
+}
@@ -0,0 +1,55 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use scrypto::types::*;
+
+#[test]
+fn random_bytes_should_be_deterministic() {
+    // Arrange
+    let mut ledger1 = InMemorySubstateStore::new();
+    let mut ledger2 = InMemorySubstateStore::new();
+    let mut track1 = Track::new(&mut ledger1, H256([1u8; 32]), vec![]);
+    let mut track2 = Track::new(&mut ledger2, H256([1u8; 32]), vec![]);
+
+    // Act
+    let bytes1 = track1.random_bytes(100);
+    let bytes2 = track2.random_bytes(100);
+
+    // Assert
+    assert_eq!(bytes1.len(), 100);
+    assert_eq!(bytes1, bytes2);
+}
+
+#[test]
+fn random_bytes_should_differ_between_calls_and_transactions() {
+    // Arrange
+    let mut ledger1 = InMemorySubstateStore::new();
+    let mut ledger2 = InMemorySubstateStore::new();
+    let mut track1 = Track::new(&mut ledger1, H256([1u8; 32]), vec![]);
+    let mut track2 = Track::new(&mut ledger2, H256([2u8; 32]), vec![]);
+
+    // Act
+    let first = track1.random_bytes(32);
+    let second = track1.random_bytes(32);
+    let other = track2.random_bytes(32);
+
+    // Assert
+    assert_ne!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn random_bytes_should_not_change_entity_addresses() {
+    // Arrange
+    let mut ledger1 = InMemorySubstateStore::new();
+    let mut ledger2 = InMemorySubstateStore::new();
+    let mut track1 = Track::new(&mut ledger1, H256([1u8; 32]), vec![]);
+    let mut track2 = Track::new(&mut ledger2, H256([1u8; 32]), vec![]);
+
+    // Act
+    track1.random_bytes(32);
+    let address1 = track1.new_component_address();
+    let address2 = track2.new_component_address();
+
+    // Assert
+    assert_eq!(address1, address2);
+}
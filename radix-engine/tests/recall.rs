@@ -0,0 +1,59 @@
+use radix_engine::model::*;
+use scrypto::engine::*;
+use scrypto::resource::resource_flags::*;
+use scrypto::resource::resource_permissions::*;
+use scrypto::rust::collections::HashMap;
+use scrypto::types::*;
+
+fn resource_def_with_flags(flags: u64) -> ResourceDef {
+    let mut authorities = HashMap::new();
+    authorities.insert(ECDSA_TOKEN, MAY_RECALL);
+    ResourceDef::new(
+        ResourceType::Fungible { divisibility: 18 },
+        HashMap::new(),
+        flags,
+        0,
+        authorities,
+        &None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn recall_with_authority_should_be_allowed() {
+    // Arrange
+    let resource_def = resource_def_with_flags(RECALLABLE);
+
+    // Act
+    let result = resource_def.check_recall_auth(Some(ECDSA_TOKEN));
+
+    // Assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn recall_without_authority_should_fail() {
+    // Arrange
+    let resource_def = resource_def_with_flags(RECALLABLE);
+
+    // Act
+    let result = resource_def.check_recall_auth(Some(RADIX_TOKEN));
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(ResourceDefError::PermissionNotAllowed)
+    ));
+}
+
+#[test]
+fn recall_of_non_recallable_resource_should_fail() {
+    // Arrange
+    let resource_def = resource_def_with_flags(0);
+
+    // Act
+    let result = resource_def.check_recall_auth(Some(ECDSA_TOKEN));
+
+    // Assert
+    assert!(matches!(result, Err(ResourceDefError::OperationNotAllowed)));
+}
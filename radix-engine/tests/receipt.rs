@@ -0,0 +1,70 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn receipt_should_list_new_entities_by_kind() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .new_token_fixed(HashMap::new(), Decimal::from(100))
+        .new_badge_fixed(HashMap::new(), Decimal::from(1))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    receipt.expect_commit_success();
+    let resources = receipt.new_resource_addresses();
+    assert_eq!(resources.len(), 2);
+    assert_eq!(receipt.resource_def(1), Some(resources[1]));
+    assert!(receipt.new_component_addresses().is_empty());
+    assert!(receipt.new_package_addresses().is_empty());
+    let (token, _): (Address, Option<BucketId>) = receipt.output(0);
+    assert_eq!(token, resources[0]);
+}
+
+#[test]
+#[should_panic(expected = "Expected the transaction to be committed")]
+fn failed_transaction_should_not_pass_expectation() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_worktop_contains(Decimal::from(1), RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+
+    // Act
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+#[should_panic(expected = "Failed to decode the output of instruction 0")]
+fn output_of_another_type_should_not_decode() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let transaction = TransactionBuilder::new(&executor)
+        .new_token_fixed(HashMap::new(), Decimal::from(100))
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+
+    // Act
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    let _: Decimal = receipt.output(0);
+}
@@ -0,0 +1,94 @@
+use std::fs;
+use std::process::Command;
+
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+pub fn compile(name: &str) -> Vec<u8> {
+    Command::new("cargo")
+        .current_dir(format!("./tests/{}", name))
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    fs::read(format!(
+        "./tests/{}/target/wasm32-unknown-unknown/release/{}.wasm",
+        name,
+        name.replace("-", "_")
+    ))
+    .unwrap()
+}
+
+fn instantiate<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    package: Address,
+    blueprint_name: &str,
+) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(package, blueprint_name, "new", vec![], None)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().component(0).unwrap()
+}
+
+#[test]
+fn calling_back_into_running_component_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("call")).unwrap();
+    let component = instantiate(&mut executor, package, "ReentrancyTest");
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "call_self", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ReentrantCall(address)) if *address == component
+    ));
+}
+
+#[test]
+fn calling_back_into_reentrant_component_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("call")).unwrap();
+    let component = instantiate(&mut executor, package, "ReentrantTest");
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "call_self", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn calling_same_component_in_sequence_should_succeed() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("call")).unwrap();
+    let component = instantiate(&mut executor, package, "ReentrancyTest");
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(component, "get_calls", vec![], None)
+        .call_method(component, "get_calls", vec![], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
@@ -4,6 +4,29 @@ blueprint! {
     struct ResourceTest;
 
     impl ResourceTest {
+        pub fn create_soulbound_badge() -> (Bucket, Bucket) {
+            let admin = ResourceBuilder::new_badge().initial_supply_fungible(1);
+            let badge = ResourceBuilder::new_badge()
+                .metadata("name", "Soulbound")
+                .badge(admin.resource_address(), MAY_TRANSFER)
+                .soulbound()
+                .initial_supply_fungible(1);
+            (admin, badge)
+        }
+
+        pub fn create_restricted_token(depositor: Address) -> (Bucket, Bucket) {
+            let admin = ResourceBuilder::new_badge().initial_supply_fungible(1);
+            let tokens = ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM)
+                .metadata("name", "Restricted")
+                .badge(admin.resource_address(), MAY_MANAGE_DEPOSITORS)
+                .restrict_deposit()
+                .initial_supply_fungible(100);
+            tokens
+                .resource_def()
+                .allow_depositor(depositor, admin.present());
+            (admin, tokens)
+        }
+
         pub fn create_fungible() -> (Bucket, ResourceDef) {
             let badge = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
             let token_resource_def = ResourceBuilder::new_fungible(DIVISIBILITY_MAXIMUM)
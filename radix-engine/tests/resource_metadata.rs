@@ -0,0 +1,99 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, ResourceDefError, RuntimeError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Minter {}
+
+    impl Minter {
+        pub fn new_token(symbol: String, name: String) -> Bucket {
+            let bucket = ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .metadata(SYMBOL_METADATA_KEY, symbol.clone())
+                .metadata(NAME_METADATA_KEY, name.clone())
+                .initial_supply_fungible(1);
+            let resource_def = bucket.resource_def();
+            assert_eq!(resource_def.symbol(), Some(symbol));
+            assert_eq!(resource_def.name(), Some(name));
+            assert_eq!(resource_def.description(), None);
+            assert_eq!(resource_def.icon_url(), None);
+            bucket
+        }
+    }
+}
+
+fn run(symbol: &str, name: &str) -> Receipt {
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+        "Minter",
+        Minter_main,
+        Minter_abi,
+    ));
+    let transaction = TransactionBuilder::new(&executor)
+        .call_function(
+            package,
+            "Minter",
+            "new_token",
+            vec![symbol.to_owned(), name.to_owned()],
+            None,
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    executor.run(transaction).unwrap()
+}
+
+#[test]
+fn well_known_metadata_should_be_readable() {
+    // Act
+    let receipt = run("GUM", "Gumball");
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn symbol_with_invalid_characters_should_fail() {
+    // Act
+    let receipt = run("GUM$", "Gumball");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(
+            ResourceDefError::InvalidMetadataSymbol(_)
+        ))
+    ));
+}
+
+#[test]
+fn too_long_symbol_should_fail() {
+    // Act
+    let receipt = run(&"G".repeat(MAX_SYMBOL_LENGTH + 1), "Gumball");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(
+            ResourceDefError::InvalidMetadataSymbol(_)
+        ))
+    ));
+}
+
+#[test]
+fn name_with_control_characters_should_fail() {
+    // Act
+    let receipt = run("GUM", "Gum\nball");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(
+            ResourceDefError::InvalidMetadataName(_)
+        ))
+    ));
+}
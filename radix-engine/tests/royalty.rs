@@ -0,0 +1,177 @@
+use std::fs;
+use std::process::Command;
+
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+pub fn compile(name: &str) -> Vec<u8> {
+    Command::new("cargo")
+        .current_dir(format!("./tests/{}", name))
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    fs::read(format!(
+        "./tests/{}/target/wasm32-unknown-unknown/release/{}.wasm",
+        name,
+        name.replace("-", "_")
+    ))
+    .unwrap()
+}
+
+fn instantiate_wrapper<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    package: Address,
+) -> Address {
+    let transaction = TransactionBuilder::new(executor)
+        .call_function(package, "Wrapper", "new", vec![], None)
+        .build(vec![])
+        .unwrap();
+    executor.run(transaction).unwrap().component(0).unwrap()
+}
+
+fn set_component_royalty<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    component_address: Address,
+    amount: Decimal,
+) {
+    let mut component = executor.ledger().get_component(component_address).unwrap();
+    component.set_royalty(amount, RADIX_TOKEN).unwrap();
+    executor
+        .ledger_mut()
+        .put_component(component_address, component);
+}
+
+fn collected_royalty<L: SubstateStore>(
+    executor: &TransactionExecutor<L>,
+    component_address: Address,
+) -> Decimal {
+    executor
+        .ledger()
+        .get_component(component_address)
+        .unwrap()
+        .royalty()
+        .unwrap()
+        .collected()
+}
+
+#[test]
+fn calling_method_should_pay_component_royalty() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+    set_component_royalty(&mut executor, other_account, Decimal(1));
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal(10),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(collected_royalty(&executor, other_account), Decimal(1));
+}
+
+#[test]
+fn calling_method_without_xrd_on_worktop_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    set_component_royalty(&mut executor, account, Decimal(1));
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status,
+        TransactionStatus::Rejected(RejectionReason::InsufficientFee(address)) if address == account
+    ));
+    assert_eq!(collected_royalty(&executor, account), Decimal::zero());
+}
+
+#[test]
+fn calling_method_through_wrapper_component_should_pay_royalty() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let package = executor.publish_package(&compile("royalty")).unwrap();
+    let wrapper = instantiate_wrapper(&mut executor, package);
+    let target = instantiate_wrapper(&mut executor, package);
+    set_component_royalty(&mut executor, target, Decimal(1));
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal(10),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method(wrapper, "call_through", vec![target.to_string()], None)
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(collected_royalty(&executor, target), Decimal(1));
+}
+
+#[test]
+fn calling_method_through_wrapper_component_without_xrd_on_worktop_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let package = executor.publish_package(&compile("royalty")).unwrap();
+    let wrapper = instantiate_wrapper(&mut executor, package);
+    let target = instantiate_wrapper(&mut executor, package);
+    set_component_royalty(&mut executor, target, Decimal(1));
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(wrapper, "call_through", vec![target.to_string()], None)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status,
+        TransactionStatus::Rejected(RejectionReason::InsufficientFee(address)) if address == target
+    ));
+    assert_eq!(collected_royalty(&executor, target), Decimal::zero());
+}
+
+#[test]
+fn negative_royalty_should_be_rejected() {
+    // Act
+    let result = Royalty::new(Decimal(-1), RADIX_TOKEN);
+
+    // Assert
+    assert!(matches!(result, Err(RoyaltyError::InvalidAmount)));
+}
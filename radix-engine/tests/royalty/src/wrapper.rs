@@ -0,0 +1,22 @@
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Wrapper {
+        calls: u32,
+    }
+
+    impl Wrapper {
+        pub fn new() -> Component {
+            Self { calls: 0 }.instantiate()
+        }
+
+        pub fn ping(&mut self) {
+            self.calls += 1;
+        }
+
+        /// Calls `ping` on another component.
+        pub fn call_through(&self, component: Address) {
+            Component::from(component).call::<()>("ping", vec![]);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use scrypto::buffer::*;
+use scrypto::engine::*;
+use scrypto::types::*;
+
+/// Collects all entries by following the cursors, checking no page exceeds the limit.
+fn scan_all<K: Clone, V>(limit: usize, scan: impl Fn(Option<K>) -> Page<K, V>) -> Vec<K> {
+    let mut keys = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = scan(cursor);
+        assert!(page.entries.len() <= limit);
+        keys.extend(page.entries.into_iter().map(|(k, _)| k));
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return keys,
+        }
+    }
+}
+
+fn new_vault(amount: i32) -> Vault {
+    Vault::new(Bucket::new(
+        RADIX_TOKEN,
+        ResourceType::Fungible { divisibility: 18 },
+        Supply::Fungible {
+            amount: amount.into(),
+        },
+    ))
+}
+
+#[test]
+fn components_should_be_scanned_by_package() {
+    // Arrange
+    let ledger = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let first = ledger.scan_components(SYSTEM_PACKAGE, None, 1);
+    let second = ledger.scan_components(SYSTEM_PACKAGE, first.next_cursor, 1);
+    let third = ledger.scan_components(SYSTEM_PACKAGE, second.next_cursor, 1);
+    let account_components = ledger.scan_components(ACCOUNT_PACKAGE, None, 10);
+
+    // Assert
+    assert_eq!(first.entries.len(), 1);
+    assert_eq!(second.entries.len(), 1);
+    assert_eq!(third.entries.len(), 1);
+    assert_eq!(third.next_cursor, None);
+    let mut found = vec![first.entries[0].0, second.entries[0].0, third.entries[0].0];
+    found.sort_by_key(|address| address.to_string());
+    assert_eq!(
+        found,
+        vec![SYSTEM_COMPONENT, FAUCET_COMPONENT, STAKING_COMPONENT]
+    );
+    assert_eq!(third.entries[0].1.package_address(), SYSTEM_PACKAGE);
+    assert!(account_components.entries.is_empty());
+    assert_eq!(account_components.next_cursor, None);
+}
+
+#[test]
+fn vaults_should_be_paged_through() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+    let component = Address::Component([1u8; 26]);
+    let other = Address::Component([2u8; 26]);
+    let vids: Vec<VaultId> = (0..5).map(|i| VaultId::new(H256([7u8; 32]), i)).collect();
+    for (i, vid) in vids.iter().enumerate() {
+        ledger.put_vault(component, *vid, new_vault(i as i32));
+    }
+    ledger.put_vault(other, VaultId::new(H256([7u8; 32]), 9), new_vault(9));
+
+    // Act
+    let scanned = scan_all(2, |cursor| ledger.scan_vaults(component, cursor, 2));
+
+    // Assert
+    assert_eq!(scanned, vids);
+}
+
+#[test]
+fn non_fungibles_should_be_scanned_by_resource_def() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::new();
+    let resource_address = Address::ResourceDef([1u8; 26]);
+    let other = Address::ResourceDef([2u8; 26]);
+    for i in 0..7u128 {
+        ledger.put_non_fungible(
+            resource_address,
+            &NonFungibleKey::from(i),
+            NonFungible::new(scrypto_encode(&i), scrypto_encode(&())),
+        );
+    }
+    ledger.put_non_fungible(
+        other,
+        &NonFungibleKey::from(100u128),
+        NonFungible::new(scrypto_encode(&()), scrypto_encode(&())),
+    );
+
+    // Act
+    let mut scanned = scan_all(3, |cursor| {
+        ledger.scan_non_fungibles(resource_address, cursor, 3)
+    });
+    let page = ledger.scan_non_fungibles(resource_address, None, 1);
+
+    // Assert
+    scanned.sort();
+    assert_eq!(
+        scanned,
+        (0..7u128).map(NonFungibleKey::from).collect::<Vec<_>>()
+    );
+    let (key, non_fungible) = &page.entries[0];
+    assert_eq!(
+        non_fungible.immutable_data(),
+        ledger
+            .get_non_fungible(resource_address, key)
+            .unwrap()
+            .immutable_data()
+    );
+}
+
+#[test]
+#[cfg(not(feature = "alloc"))]
+fn shared_store_should_scan_its_state() {
+    // Arrange
+    let ledger = SharedInMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let page = ledger.scan_vaults(FAUCET_COMPONENT, None, 10);
+
+    // Assert
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries[0].1.resource_address(), RADIX_TOKEN);
+    assert_eq!(page.next_cursor, None);
+}
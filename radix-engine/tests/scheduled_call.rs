@@ -0,0 +1,209 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{Receipt, RuntimeError, ScheduledCall, TransactionValidationError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Auction {
+        settlements: Vec<(u32, u64)>,
+    }
+
+    impl Auction {
+        pub fn new() -> Component {
+            Self {
+                settlements: Vec::new(),
+            }
+            .instantiate()
+        }
+
+        pub fn schedule(&self, epoch: u64, id: u32) {
+            Context::schedule_call(epoch, "settle", args!(id));
+        }
+
+        pub fn schedule_with_bucket(&self, epoch: u64) {
+            let bucket = ResourceBuilder::new_fungible(0).initial_supply_fungible(1);
+            Context::schedule_call(epoch, "deposit", args!(bucket));
+        }
+
+        pub fn schedule_and_fail(&self, epoch: u64) {
+            Context::schedule_call(epoch, "settle", args!(0u32));
+            panic!("Auction cancelled");
+        }
+
+        pub fn settle(&mut self, id: u32) {
+            self.settlements.push((id, Context::current_epoch()));
+        }
+
+        pub fn deposit(&mut self, bucket: Bucket) {
+            bucket.burn();
+        }
+
+        pub fn settlements(&self) -> Vec<(u32, u64)> {
+            self.settlements.clone()
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    auction: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Auction",
+            Auction_main,
+            Auction_abi,
+        ));
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Auction", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let auction = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self { executor, auction }
+    }
+
+    fn call(&mut self, method: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(self.auction, method, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn settlements(&mut self) -> Vec<(u32, u64)> {
+        self.call("settlements", vec![])
+            .expect_commit_success()
+            .output(0)
+    }
+
+    fn advance_to(&mut self, epoch: u64) -> Vec<Receipt> {
+        self.executor.ledger_mut().set_epoch(epoch);
+        self.executor.run_due_callbacks(epoch).unwrap()
+    }
+}
+
+#[test]
+fn scheduled_call_should_run_once_due() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    env.call("schedule", vec!["5".to_owned(), "1".to_owned()])
+        .expect_commit_success();
+
+    // Act
+    let early = env.advance_to(4);
+    let due = env.advance_to(5);
+
+    // Assert
+    assert!(early.is_empty());
+    assert_eq!(due.len(), 1);
+    due[0].expect_commit_success();
+    assert_eq!(env.settlements(), vec![(1, 5)]);
+}
+
+#[test]
+fn scheduled_calls_of_skipped_epochs_should_run_in_order() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    for (epoch, id) in [("5", "1"), ("3", "2"), ("5", "3"), ("20", "4")] {
+        env.call("schedule", vec![epoch.to_owned(), id.to_owned()])
+            .expect_commit_success();
+    }
+
+    // Act
+    let due = env.advance_to(10);
+    let again = env.advance_to(11);
+
+    // Assert
+    assert_eq!(due.len(), 3);
+    assert!(again.is_empty());
+    assert_eq!(env.settlements(), vec![(2, 10), (1, 10), (3, 10)]);
+    let scheduler = env.executor.ledger().get_scheduler();
+    assert_eq!(scheduler.calls().len(), 1);
+    assert_eq!(scheduler.calls()[0].epoch, 20);
+}
+
+#[test]
+fn rejected_scheduled_call_should_stay_scheduled() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let mut scheduler = env.executor.ledger().get_scheduler();
+    scheduler.schedule(ScheduledCall {
+        epoch: 5,
+        component_address: env.auction,
+        method: "settle".to_owned(),
+        args: vec![vec![0xff]],
+    });
+    env.executor.ledger_mut().put_scheduler(scheduler);
+    env.call("schedule", vec!["6".to_owned(), "1".to_owned()])
+        .expect_commit_success();
+
+    // Act
+    env.executor.ledger_mut().set_epoch(6);
+    let result = env.executor.run_due_callbacks(6);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::DataValidationError(_))
+    ));
+    assert_eq!(env.executor.ledger().get_scheduler().calls().len(), 2);
+    assert!(env.settlements().is_empty());
+}
+
+#[test]
+fn scheduling_for_current_epoch_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    env.advance_to(3);
+
+    // Act
+    let receipt = env.call("schedule", vec!["3".to_owned(), "1".to_owned()]);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ScheduledEpochNotInFuture(3))
+    ));
+}
+
+#[test]
+fn scheduling_with_bucket_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("schedule_with_bucket", vec!["5".to_owned()]);
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::BucketNotAllowed(
+            DataContext::ScheduledArgument
+        ))
+    ));
+}
+
+#[test]
+fn calls_scheduled_by_failed_transaction_should_be_discarded() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("schedule_and_fail", vec!["5".to_owned()]);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+    assert!(env.advance_to(5).is_empty());
+    assert!(env.settlements().is_empty());
+}
@@ -0,0 +1,151 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::Receipt;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct Ticket {
+    seat: u32,
+}
+
+blueprint! {
+    struct Prover {
+        tokens: Vault,
+        tickets: Vault,
+    }
+
+    impl Prover {
+        pub fn new() -> Component {
+            let tokens = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(10);
+            let tickets = ResourceBuilder::new_non_fungible().initial_supply_non_fungible(
+                (1..=3).map(|seat| (NonFungibleKey::from(seat as u128), Ticket { seat })),
+            );
+            Self {
+                tokens: Vault::with_bucket(tokens),
+                tickets: Vault::with_bucket(tickets),
+            }
+            .instantiate()
+        }
+
+        pub fn prove_amount(&mut self, amount: Decimal) -> Decimal {
+            let bucket = self.tokens.take_all();
+            let proof = bucket.present_amount(amount);
+            let proven = proof.amount();
+            proof.drop();
+            self.tokens.put(bucket);
+            proven
+        }
+
+        pub fn prove_non_fungible(&mut self, key: NonFungibleKey) -> Vec<NonFungibleKey> {
+            let bucket = self.tickets.take_all();
+            let proof = bucket.present_non_fungibles(&BTreeSet::from([key]));
+            let proven = proof.get_non_fungible_keys();
+            proof.drop();
+            self.tickets.put(bucket);
+            proven
+        }
+
+        pub fn put_while_proving(&mut self) {
+            let bucket = self.tokens.take_all();
+            let proof = bucket.present_amount(1);
+            self.tokens.put(bucket);
+            proof.drop();
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    prover: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Prover",
+            Prover_main,
+            Prover_abi,
+        ));
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Prover", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let prover = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self { executor, prover }
+    }
+
+    fn call(&mut self, method: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(self.prover, method, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn scoped_ref_should_prove_only_amount() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("prove_amount", vec!["3".to_owned()]);
+
+    // Assert
+    let proven: Decimal = receipt.expect_commit_success().output(0);
+    assert_eq!(proven, 3.into());
+}
+
+#[test]
+fn scoped_ref_should_not_exceed_bucket() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let too_much = env.call("prove_amount", vec!["11".to_owned()]);
+    let negative = env.call("prove_amount", vec!["-1".to_owned()]);
+
+    // Assert
+    assert!(!too_much.status.is_committed());
+    assert!(!negative.status.is_committed());
+}
+
+#[test]
+fn scoped_ref_should_prove_only_non_fungibles() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call(
+        "prove_non_fungible",
+        vec![NonFungibleKey::from(2u128).to_string()],
+    );
+    let missing = env.call(
+        "prove_non_fungible",
+        vec![NonFungibleKey::from(4u128).to_string()],
+    );
+
+    // Assert
+    let proven: Vec<NonFungibleKey> = receipt.expect_commit_success().output(0);
+    assert_eq!(proven, vec![NonFungibleKey::from(2u128)]);
+    assert!(!missing.status.is_committed());
+}
+
+#[test]
+fn scoped_ref_should_keep_bucket_locked() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("put_while_proving", vec![]);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
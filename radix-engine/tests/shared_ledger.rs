@@ -0,0 +1,161 @@
+// The shared store is only available with std
+#![cfg(not(feature = "alloc"))]
+
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::Package;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+use std::time::Duration;
+
+fn new_account(ledger: &mut SharedInMemorySubstateStore) -> Address {
+    let mut executor = TransactionExecutor::new(ledger, false);
+    let key = executor.new_public_key();
+    executor.new_account(key)
+}
+
+#[test]
+fn clones_should_share_state() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let mut other = ledger.clone();
+
+    // Act
+    let account = new_account(&mut ledger);
+
+    // Assert
+    assert!(other.get_component(account).is_some());
+    assert_eq!(other.get_nonce(), ledger.get_nonce());
+    other.set_epoch(5);
+    assert_eq!(ledger.get_epoch(), 5);
+}
+
+#[test]
+fn forks_should_not_see_each_others_writes() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let account = new_account(&mut ledger);
+    let mut fork = ledger.fork();
+
+    // Act
+    let forked_account = new_account(&mut fork);
+    ledger.set_epoch(5);
+
+    // Assert
+    assert!(fork.get_component(account).is_some());
+    assert!(ledger.get_component(forked_account).is_none());
+    assert_eq!(fork.get_epoch(), 0);
+}
+
+#[test]
+fn snapshot_should_not_change_with_store() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let snapshot = ledger.snapshot();
+
+    // Act
+    let account = new_account(&mut ledger);
+
+    // Assert
+    assert!(snapshot.get_component(account).is_none());
+    assert!(SharedInMemorySubstateStore::from(snapshot)
+        .get_component(account)
+        .is_none());
+}
+
+#[test]
+fn forks_should_run_in_parallel() {
+    // Arrange
+    let ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let mut forks: Vec<SharedInMemorySubstateStore> = (0..4).map(|_| ledger.fork()).collect();
+
+    // Act
+    let accounts: Vec<Address> = std::thread::scope(|scope| {
+        let handles: Vec<_> = forks
+            .iter_mut()
+            .map(|fork| scope.spawn(move || new_account(fork)))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    // Assert
+    for (fork, account) in forks.iter().zip(&accounts) {
+        assert!(fork.get_component(*account).is_some());
+        assert_eq!(fork.get_nonce(), forks[0].get_nonce());
+    }
+    assert!(ledger.get_component(accounts[0]).is_none());
+}
+
+#[test]
+fn view_should_not_change_with_store() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let view = ledger.view();
+
+    // Act
+    let account = new_account(&mut ledger);
+
+    // Assert
+    assert!(view.get_component(account).is_none());
+    assert!(ledger.view().get_component(account).is_some());
+    assert!(ledger.view().sequence() > view.sequence());
+}
+
+#[test]
+fn transaction_should_be_committed_at_once() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let sequence = executor.ledger().sequence();
+
+    // Act
+    executor.new_account(key);
+
+    // Assert
+    assert_eq!(ledger.sequence(), sequence + 1);
+}
+
+#[test]
+fn view_should_wait_for_commit_in_progress() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let reader = ledger.clone();
+    let sequence = ledger.sequence();
+    ledger.begin_commit();
+    ledger.set_epoch(5);
+
+    // Act
+    let view = std::thread::scope(|scope| {
+        let handle = scope.spawn(|| reader.view());
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        ledger.increase_nonce();
+        ledger.end_commit();
+        handle.join().unwrap()
+    });
+
+    // Assert
+    assert_eq!(view.get_epoch(), 5);
+    assert_eq!(view.get_nonce(), ledger.get_nonce());
+    assert_eq!(view.sequence(), sequence + 1);
+}
+
+#[test]
+fn track_commit_should_end_its_commit() {
+    // Arrange
+    let mut ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let reader = ledger.clone();
+    let sequence = ledger.sequence();
+    let mut track = Track::new(&mut ledger, H256([0u8; 32]), Vec::new());
+    let address = track.new_package_address();
+    track.put_package(address, Package::new(Vec::new()));
+
+    // Act
+    track.commit();
+
+    // Assert
+    let view = reader.view();
+    assert!(view.get_package(address).is_some());
+    assert_eq!(view.sequence(), sequence + 1);
+}
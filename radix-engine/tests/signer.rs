@@ -0,0 +1,101 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+use std::cell::RefCell;
+
+/// Hashes every transaction to the same value.
+struct FixedHasher(H256);
+
+impl IntentHasher for FixedHasher {
+    fn hash_intent(&self, _nonce: u64) -> H256 {
+        self.0
+    }
+}
+
+/// Signs with a single key and records the number of instructions it was asked to sign.
+struct RecordingSigner {
+    key: EcdsaPublicKey,
+    signed: RefCell<Vec<usize>>,
+}
+
+impl TransactionSigner for RecordingSigner {
+    fn sign(&self, transaction: &Transaction) -> Vec<EcdsaPublicKey> {
+        self.signed
+            .borrow_mut()
+            .push(transaction.instructions.len());
+        vec![self.key]
+    }
+}
+
+#[test]
+fn custom_intent_hasher_determines_addresses() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let transaction_hash = sha256("custom");
+    let mut executor = TransactionExecutor::new(&mut ledger, false)
+        .with_intent_hasher(FixedHasher(transaction_hash));
+    let key = executor.new_public_key();
+
+    // Act
+    let account = executor.new_account(key);
+
+    // Assert
+    assert_eq!(executor.next_transaction_hash(), transaction_hash);
+    assert_eq!(account, derive_component_address(transaction_hash, 1027));
+}
+
+#[test]
+fn default_intent_hasher_hashes_nonce() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction_hash = executor.next_transaction_hash();
+
+    // Assert
+    assert_eq!(
+        transaction_hash,
+        NonceHasher.hash_intent(executor.ledger().get_nonce())
+    );
+}
+
+#[test]
+fn transaction_signer_provides_signatures() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let signer = RecordingSigner {
+        key,
+        signed: RefCell::new(Vec::new()),
+    };
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: 1.into(),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build_with_signer(&signer)
+        .unwrap();
+    let receipt = executor.run(transaction.clone()).unwrap();
+
+    // Assert
+    assert_eq!(
+        *signer.signed.borrow(),
+        vec![transaction.instructions.len() - 1]
+    );
+    assert!(matches!(
+        transaction.instructions.last(),
+        Some(Instruction::End { signatures }) if signatures == &vec![key]
+    ));
+    assert!(receipt.status.is_committed());
+}
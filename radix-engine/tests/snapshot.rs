@@ -0,0 +1,65 @@
+// Receipt snapshots are only available with std
+#![cfg(not(feature = "alloc"))]
+
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn transfer_receipt_should_match_snapshot() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+    let other_key = executor.new_public_key();
+    let other_account = executor.new_account(other_key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .withdraw_from_account(
+            &Resource::Fungible {
+                amount: Decimal(10),
+                resource_address: RADIX_TOKEN,
+            },
+            account,
+        )
+        .call_method_with_all_resources(other_account, "deposit_batch")
+        .build(vec![key])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert_receipt_snapshot(&receipt, "tests/snapshots/transfer.snap");
+}
+
+#[test]
+fn new_entities_should_be_aliased_in_creation_order() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+        .new_account_with_resource(key, Decimal(1), RADIX_TOKEN)
+        .build(Vec::new())
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+    let snapshot = receipt_snapshot(&receipt);
+
+    // Assert
+    let account = receipt.component(0).unwrap();
+    assert!(snapshot.contains("component#0"));
+    assert!(!snapshot.contains(&account.to_string()));
+}
+
+#[test]
+fn diff_should_show_changed_lines_with_context() {
+    // Act
+    let diff = diff_lines("a\nb\nc\nd\ne\nf\ng\nh\ni\n", "a\nb\nc\nd\ne\nf\ng\nH\ni\n");
+
+    // Assert
+    assert_eq!(diff, "  e\n  f\n  g\n- h\n+ H\n  i");
+}
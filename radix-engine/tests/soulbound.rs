@@ -0,0 +1,233 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::Instruction;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Registry {
+        credential: Address,
+        holder: Address,
+        admin: Vault,
+    }
+
+    impl Registry {
+        /// Issues a credential, which is only transferable with the admin badge.
+        pub fn instantiate(holder: Address, restricted: bool) -> (Component, Bucket) {
+            let admin = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let credential = ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .flags(if restricted { RESTRICTED_TRANSFER } else { 0 })
+                .badge(admin.resource_address(), MAY_TRANSFER)
+                .initial_supply_fungible(1);
+
+            let component = Self {
+                credential: credential.resource_address(),
+                holder,
+                admin: Vault::with_bucket(admin),
+            }
+            .instantiate();
+            (component, credential)
+        }
+
+        pub fn verify(&self, proof: BucketRef) {
+            proof.check_owned_by(self.credential, self.holder);
+        }
+
+        /// Presents a freshly issued credential to `verify` without depositing it first.
+        pub fn present_unowned(registry: Address) -> Vec<Bucket> {
+            let admin = ResourceBuilder::new_fungible(DIVISIBILITY_NONE).initial_supply_fungible(1);
+            let credential = ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .flags(RESTRICTED_TRANSFER)
+                .badge(admin.resource_address(), MAY_TRANSFER)
+                .initial_supply_fungible(1);
+            Component::from(registry).call::<()>("verify", vec![scrypto_encode(&credential.present())]);
+            vec![admin, credential]
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    package: Address,
+    key: EcdsaPublicKey,
+    account: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false).with_native_system_packages();
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Registry",
+            Registry_main,
+            Registry_abi,
+        ));
+        let key = executor.new_public_key();
+        let transaction = TransactionBuilder::new(&executor)
+            .add_instruction(Instruction::CallFunction {
+                package_address: ACCOUNT_PACKAGE,
+                blueprint_name: "Account".to_owned(),
+                function: "new".to_owned(),
+                args: vec![scrypto_encode(&key)],
+            })
+            .0
+            .build(vec![])
+            .unwrap();
+        let account = executor.run(transaction).unwrap().component(0).unwrap();
+
+        Self {
+            executor,
+            package,
+            key,
+            account,
+        }
+    }
+
+    /// Instantiates a registry and deposits its credential into the account.
+    fn new_registry(&mut self, holder: Address, restricted: bool) -> (Address, Address) {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .add_instruction(Instruction::CallFunction {
+                package_address: self.package,
+                blueprint_name: "Registry".to_owned(),
+                function: "instantiate".to_owned(),
+                args: vec![scrypto_encode(&holder), scrypto_encode(&restricted)],
+            })
+            .0
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![])
+            .unwrap();
+        let receipt = self.executor.run(transaction).unwrap();
+        receipt.expect_commit_success();
+        (
+            receipt.new_component_addresses()[0],
+            receipt.new_resource_addresses()[1],
+        )
+    }
+
+    fn freeze_account(&mut self) {
+        let account = self.account;
+        let transaction = TransactionBuilder::new(&self.executor)
+            .clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| {
+                builder
+                    .add_instruction(Instruction::CallMethod {
+                        component_address: account,
+                        method: "freeze".to_owned(),
+                        args: vec![scrypto_encode(&rid)],
+                    })
+                    .0
+            })
+            .build(vec![self.key])
+            .unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .expect_commit_success();
+    }
+
+    fn present(&mut self, credential: Address, registry: Address, times: usize) -> bool {
+        let mut builder = TransactionBuilder::new(&self.executor);
+        for _ in 0..times {
+            builder.clone_bucket_ref(ECDSA_TOKEN_RID, |builder, rid| {
+                builder
+                    .add_instruction(Instruction::CallMethod {
+                        component_address: self.account,
+                        method: "present".to_owned(),
+                        args: vec![
+                            scrypto_encode(&credential),
+                            scrypto_encode(&registry),
+                            scrypto_encode(&"verify".to_owned()),
+                            scrypto_encode(&rid),
+                        ],
+                    })
+                    .0
+            });
+        }
+        let transaction = builder.build(vec![self.key]).unwrap();
+        self.executor
+            .run(transaction)
+            .unwrap()
+            .status
+            .is_committed()
+    }
+}
+
+#[test]
+fn owner_can_present_non_transferable_resource() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let (registry, credential) = env.new_registry(env.account, true);
+
+    // Act
+    let presented = env.present(credential, registry, 2);
+
+    // Assert
+    assert!(presented);
+}
+
+#[test]
+fn frozen_account_should_not_present() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let (registry, credential) = env.new_registry(env.account, true);
+    env.freeze_account();
+
+    // Act
+    let presented = env.present(credential, registry, 1);
+
+    // Assert
+    assert!(!presented);
+}
+
+#[test]
+fn presenting_to_check_of_other_owner_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let (registry, credential) = env.new_registry(SYSTEM_COMPONENT, true);
+
+    // Act
+    let presented = env.present(credential, registry, 1);
+
+    // Assert
+    assert!(!presented);
+}
+
+#[test]
+fn presenting_transferable_resource_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let (registry, credential) = env.new_registry(env.account, false);
+
+    // Act
+    let presented = env.present(credential, registry, 1);
+
+    // Assert
+    assert!(!presented);
+}
+
+#[test]
+fn presenting_bucket_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let (registry, _) = env.new_registry(env.account, true);
+
+    // Act
+    let transaction = TransactionBuilder::new(&env.executor)
+        .call_function(
+            env.package,
+            "Registry",
+            "present_unowned",
+            vec![registry.to_string()],
+            None,
+        )
+        .call_method_with_all_resources(env.account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    let receipt = env.executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+}
@@ -0,0 +1,196 @@
+use std::fs;
+use std::process::Command;
+
+use radix_engine::ledger::*;
+use radix_engine::model::Receipt;
+use radix_engine::transaction::*;
+use sbor::Decode;
+use scrypto::prelude::*;
+
+pub fn compile(name: &str) -> Vec<u8> {
+    Command::new("cargo")
+        .current_dir(format!("./tests/{}", name))
+        .args(["build", "--target", "wasm32-unknown-unknown", "--release"])
+        .status()
+        .unwrap();
+    fs::read(format!(
+        "./tests/{}/target/wasm32-unknown-unknown/release/{}.wasm",
+        name,
+        name.replace("-", "_")
+    ))
+    .unwrap()
+}
+
+/// Returns the address identifying a validator, e.g. of its account.
+fn validator(id: u8) -> Address {
+    Address::Component([id; 26])
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    key: EcdsaPublicKey,
+    account: Address,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let key = executor.new_public_key();
+        let account = executor.new_account(key);
+        let package = executor.publish_package(&compile("staking")).unwrap();
+
+        Self {
+            executor,
+            key,
+            account,
+            package,
+        }
+    }
+
+    fn new_staker(&mut self, xrd: u32) -> Address {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_function(
+                self.package,
+                "Staker",
+                "instantiate_staker",
+                vec![format!("{},{}", xrd, RADIX_TOKEN)],
+                Some(self.account),
+            )
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![self.key])
+            .unwrap();
+        let receipt = self.executor.run(transaction).unwrap();
+        assert!(receipt.status.is_committed(), "{:?}", receipt);
+        receipt.component(0).unwrap()
+    }
+
+    fn call(&mut self, component: Address, method: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(component, method, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn query<T: Decode>(&mut self, component: Address, method: &str, args: Vec<String>) -> T {
+        let receipt = self.call(component, method, args);
+        receipt.expect_commit_success().output(0)
+    }
+}
+
+#[test]
+fn stake_should_count_towards_validator_set_from_next_epoch() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let staker = env.new_staker(1000);
+    let owner: Address = env.query(staker, "owner", vec![]);
+    let validator = validator(1).to_string();
+
+    // Act
+    let receipt = env.call(staker, "stake", vec![validator.clone(), "100".to_owned()]);
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    let stake: Decimal = env.query(
+        STAKING_COMPONENT,
+        "get_stake",
+        vec![validator.clone(), owner.to_string()],
+    );
+    assert_eq!(stake, Decimal::from(100));
+    let balance: Decimal = env.query(staker, "balance", vec![]);
+    assert_eq!(balance, Decimal::from(900));
+    let current: Vec<(Address, Decimal)> =
+        env.query(STAKING_COMPONENT, "get_validator_set", vec![]);
+    assert_eq!(current, vec![]);
+    env.executor.ledger_mut().set_epoch(1);
+    let next: Vec<(Address, Decimal)> = env.query(STAKING_COMPONENT, "get_validator_set", vec![]);
+    assert_eq!(
+        next,
+        vec![(Address::from_str(&validator).unwrap(), Decimal::from(100))]
+    );
+}
+
+#[test]
+fn validator_set_should_be_ordered_by_stake() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let staker = env.new_staker(1000);
+    let small = validator(1);
+    let large = validator(2);
+
+    // Act
+    env.call(staker, "stake", vec![small.to_string(), "10".to_owned()]);
+    env.call(staker, "stake", vec![large.to_string(), "20".to_owned()]);
+    env.executor.ledger_mut().set_epoch(1);
+
+    // Assert
+    let validator_set: Vec<(Address, Decimal)> =
+        env.query(STAKING_COMPONENT, "get_validator_set", vec![]);
+    assert_eq!(
+        validator_set,
+        vec![(large, Decimal::from(20)), (small, Decimal::from(10))]
+    );
+}
+
+#[test]
+fn unstaked_xrd_should_be_claimable_after_delay() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let staker = env.new_staker(1000);
+    let owner: Address = env.query(staker, "owner", vec![]);
+    let validator = validator(1).to_string();
+    env.call(staker, "stake", vec![validator.clone(), "100".to_owned()]);
+
+    // Act
+    let receipt = env.call(staker, "unstake", vec![validator, "40".to_owned()]);
+    let unstakes: Vec<(u64, Decimal)> =
+        env.query(STAKING_COMPONENT, "get_unstakes", vec![owner.to_string()]);
+    env.call(staker, "claim", vec![]);
+    let before_delay: Decimal = env.query(staker, "balance", vec![]);
+    env.executor.ledger_mut().set_epoch(unstakes[0].0);
+    env.call(staker, "claim", vec![]);
+    let after_delay: Decimal = env.query(staker, "balance", vec![]);
+
+    // Assert
+    assert!(receipt.status.is_committed(), "{:?}", receipt);
+    assert_eq!(unstakes, vec![(2, Decimal::from(40))]);
+    assert_eq!(before_delay, Decimal::from(900));
+    assert_eq!(after_delay, Decimal::from(940));
+}
+
+#[test]
+fn unstaking_more_than_staked_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let staker = env.new_staker(1000);
+    let validator = validator(1).to_string();
+    env.call(staker, "stake", vec![validator.clone(), "100".to_owned()]);
+
+    // Act
+    let receipt = env.call(staker, "unstake", vec![validator, "101".to_owned()]);
+
+    // Assert
+    assert!(receipt.status.error().is_some());
+}
+
+#[test]
+fn stake_should_be_owned_by_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let alice = env.new_staker(1000);
+    let bob = env.new_staker(1000);
+    let validator = validator(1).to_string();
+    env.call(alice, "stake", vec![validator.clone(), "100".to_owned()]);
+
+    // Act
+    let receipt = env.call(bob, "unstake", vec![validator, "1".to_owned()]);
+
+    // Assert
+    assert!(receipt.status.error().is_some());
+}
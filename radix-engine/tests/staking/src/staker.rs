@@ -0,0 +1,48 @@
+use scrypto::prelude::*;
+
+// A liquid staking component would stake the same way, owning its stake with a badge.
+blueprint! {
+    struct Staker {
+        badge: Vault,
+        xrd: Vault,
+    }
+
+    impl Staker {
+        pub fn instantiate_staker(xrd: Bucket) -> Component {
+            Self {
+                badge: Vault::with_bucket(ResourceBuilder::new_badge().initial_supply_fungible(1)),
+                xrd: Vault::with_bucket(xrd),
+            }
+            .instantiate()
+        }
+
+        pub fn stake(&mut self, validator: Address, amount: Decimal) {
+            let xrd = self.xrd.take(amount);
+            self.badge.authorize(|owner| {
+                Component::from(STAKING_COMPONENT).call::<()>("stake", args!(validator, xrd, owner))
+            })
+        }
+
+        pub fn unstake(&mut self, validator: Address, amount: Decimal) {
+            self.badge.authorize(|owner| {
+                Component::from(STAKING_COMPONENT)
+                    .call::<()>("unstake", args!(validator, amount, owner))
+            })
+        }
+
+        pub fn claim(&mut self) {
+            let xrd = self.badge.authorize(|owner| {
+                Component::from(STAKING_COMPONENT).call::<Bucket>("claim_unstaked", args!(owner))
+            });
+            self.xrd.put(xrd)
+        }
+
+        pub fn owner(&self) -> Address {
+            self.badge.resource_address()
+        }
+
+        pub fn balance(&self) -> Decimal {
+            self.xrd.amount()
+        }
+    }
+}
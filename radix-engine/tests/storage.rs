@@ -0,0 +1,142 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::Receipt;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Hoarder {
+        items: Vec<String>,
+        index: LazyMap<u32, String>,
+    }
+
+    impl Hoarder {
+        pub fn new() -> Component {
+            Self {
+                items: Vec::new(),
+                index: LazyMap::new(),
+            }
+            .instantiate()
+        }
+
+        pub fn add(&mut self, item: String) {
+            self.index.insert(self.items.len() as u32, item.clone());
+            self.items.push(item);
+        }
+
+        pub fn clear(&mut self) {
+            self.items.clear();
+        }
+
+        pub fn fail(&mut self, item: String) {
+            self.items.push(item);
+            panic!("Failed on purpose");
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    hoarder: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> (Self, Receipt) {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Hoarder",
+            Hoarder_main,
+            Hoarder_abi,
+        ));
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Hoarder", "new", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let receipt = executor.run(transaction).unwrap();
+        let hoarder = receipt.component(0).unwrap();
+
+        (Self { executor, hoarder }, receipt)
+    }
+
+    fn call(&mut self, method: &str, args: Vec<String>) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(self.hoarder, method, args, None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+
+    fn storage_change(&self, receipt: &Receipt) -> Option<i64> {
+        receipt
+            .storage_changes
+            .iter()
+            .find(|c| c.component_address == self.hoarder)
+            .map(|c| c.delta)
+    }
+}
+
+#[test]
+fn new_component_should_report_its_footprint() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let (env, receipt) = TestEnv::new(&mut ledger);
+
+    // Assert
+    let footprint = env.executor.storage_footprint(env.hoarder);
+    assert!(footprint > 0);
+    assert_eq!(env.storage_change(&receipt), Some(footprint as i64));
+}
+
+#[test]
+fn footprint_should_follow_state_and_lazy_map_growth() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut env, _) = TestEnv::new(&mut ledger);
+    let before = env.executor.storage_footprint(env.hoarder);
+
+    // Act
+    let add_receipt = env.call("add", vec!["apple".to_owned()]);
+    let after_add = env.executor.storage_footprint(env.hoarder);
+    let clear_receipt = env.call("clear", vec![]);
+    let after_clear = env.executor.storage_footprint(env.hoarder);
+
+    // Assert
+    let added = env.storage_change(&add_receipt).unwrap();
+    assert!(added > "apple".len() as i64 * 2);
+    assert_eq!(after_add, before + added as u64);
+    let cleared = env.storage_change(&clear_receipt).unwrap();
+    assert!(cleared < 0);
+    assert_eq!(after_clear as i64, after_add as i64 + cleared);
+}
+
+#[test]
+fn failed_transaction_should_not_change_footprint() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let (mut env, _) = TestEnv::new(&mut ledger);
+    let before = env.executor.storage_footprint(env.hoarder);
+
+    // Act
+    let receipt = env.call("fail", vec!["apple".to_owned()]);
+
+    // Assert
+    assert!(!receipt.status.is_committed());
+    assert!(receipt.storage_changes.is_empty());
+    assert_eq!(env.executor.storage_footprint(env.hoarder), before);
+}
+
+#[test]
+fn bootstrapped_components_should_have_footprint() {
+    // Arrange
+    let ledger = InMemorySubstateStore::with_bootstrap();
+
+    // Act
+    let system = ledger.get_storage_footprint(&SYSTEM_COMPONENT);
+    let faucet = ledger.get_storage_footprint(&FAUCET_COMPONENT);
+
+    // Assert
+    assert!(system > 0);
+    assert!(faucet > system);
+}
@@ -0,0 +1,107 @@
+use radix_engine::ledger::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+fn new_account<L: SubstateStore>(ledger: &mut L) -> Address {
+    let mut executor = TransactionExecutor::new(ledger, false);
+    let key = executor.new_public_key();
+    executor.new_account(key)
+}
+
+#[test]
+fn identical_ledgers_should_have_empty_diff() {
+    // Arrange
+    let ledger = InMemorySubstateStore::with_bootstrap();
+    let snapshot = ledger.clone();
+
+    // Act
+    let diff = SubstateDiff::between(&snapshot, &ledger);
+
+    // Assert
+    assert!(diff.is_empty(), "{:?}", diff);
+}
+
+#[test]
+fn diff_should_list_substates_changed_by_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let snapshot = ledger.clone();
+
+    // Act
+    let account = new_account(&mut ledger);
+    let diff = SubstateDiff::between(&snapshot, &ledger);
+
+    // Assert
+    assert!(diff
+        .created
+        .iter()
+        .any(|(id, _)| *id == SubstateId::Component(account)));
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.nonce, Some(ledger.get_nonce()));
+    assert_eq!(diff.epoch, None);
+    // New accounts are funded from the system vault
+    assert!(diff.changed_substates().iter().all(|id| match id {
+        SubstateId::Vault(address, _) => *address == account || *address == SYSTEM_COMPONENT,
+        SubstateId::Component(address)
+        | SubstateId::LazyMap(address, _)
+        | SubstateId::StorageFootprint(address) => *address == account,
+        _ => false,
+    }));
+}
+
+#[test]
+fn applied_diff_should_reproduce_ledger() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut snapshot = ledger.clone();
+    new_account(&mut ledger);
+    ledger.set_epoch(3);
+    let diff = SubstateDiff::between(&snapshot, &ledger);
+
+    // Act
+    let result = diff.apply(&mut snapshot);
+
+    // Assert
+    assert_eq!(result, Ok(()));
+    assert!(SubstateDiff::between(&snapshot, &ledger).is_empty());
+}
+
+#[test]
+fn diff_removing_substates_should_not_apply() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let snapshot = ledger.clone();
+    let account = new_account(&mut ledger);
+    let diff = SubstateDiff::between(&ledger, &snapshot);
+    let nonce = ledger.get_nonce();
+
+    // Act
+    let result = diff.apply(&mut ledger);
+
+    // Assert
+    assert!(matches!(result, Err(PatchError::CannotRemove(_))));
+    assert!(diff.removed.contains(&SubstateId::Component(account)));
+    assert_eq!(ledger.get_nonce(), nonce);
+}
+
+#[test]
+#[cfg(not(feature = "alloc"))]
+fn shared_ledger_should_diff_against_its_fork() {
+    // Arrange
+    let ledger = SharedInMemorySubstateStore::with_bootstrap();
+    let mut fork = ledger.fork();
+
+    // Act
+    let account = new_account(&mut fork);
+    let diff = SubstateDiff::between(&ledger, &fork);
+
+    // Assert
+    assert!(diff
+        .created
+        .iter()
+        .any(|(id, _)| *id == SubstateId::Component(account)));
+    assert!(matches!(
+        get_substate(&fork, &SubstateId::Component(account)),
+        Some(Substate::Component(_))
+    ));
+}
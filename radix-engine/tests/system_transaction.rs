@@ -0,0 +1,129 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+#[test]
+fn system_transaction_should_be_committed_with_its_kind() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = executor.new_account_transaction(key);
+    let receipt = executor.run_system(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(receipt.transaction.kind, TransactionKind::System);
+    assert!(receipt.transaction.signers.is_empty());
+}
+
+#[test]
+fn system_transaction_should_be_rejected_as_user_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = executor.new_account_transaction(key);
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::UnexpectedTransactionKind(
+            TransactionKind::System
+        ))
+    ));
+}
+
+#[test]
+fn user_transaction_should_be_rejected_as_system_transaction() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor).build(vec![key]).unwrap();
+    let result = executor.run_system(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::UnexpectedTransactionKind(
+            TransactionKind::User
+        ))
+    ));
+}
+
+#[test]
+fn signed_system_transaction_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+
+    // Act
+    let mut transaction = executor.new_account_transaction(key);
+    transaction.instructions.pop();
+    transaction.instructions.push(Instruction::End {
+        signatures: vec![key],
+    });
+    let result = executor.run_system(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::SignedSystemTransaction)
+    ));
+}
+
+#[test]
+fn system_transaction_should_not_be_charged() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let cost_table = CostTable {
+        engine_call: 10,
+        state_write_byte: 1,
+        ..CostTable::default()
+    };
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_cost_table(cost_table);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = executor.new_account_transaction(key);
+    let receipt = executor.run_system(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(receipt.cost_units, 0);
+}
+
+#[test]
+fn user_transaction_should_be_charged() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let cost_table = CostTable {
+        engine_call: 10,
+        ..CostTable::default()
+    };
+    let mut executor = TransactionExecutor::new(&mut ledger, false).with_cost_table(cost_table);
+    let key = executor.new_public_key();
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["1".to_owned()], None)
+        .new_account_with_resource(key, Decimal::one(), RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert!(receipt.cost_units > 0);
+}
@@ -0,0 +1,210 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{
+    DataValidationError, Instruction, Receipt, ResourceDefError, RuntimeError, Transaction,
+    TransactionKind, TransactionValidationError,
+};
+use radix_engine::transaction::*;
+use sbor::Encoder;
+use scrypto::buffer::SCRYPTO_TYPE_URL;
+use scrypto::prelude::*;
+
+#[derive(NonFungibleData)]
+pub struct Poster {
+    image: Url,
+}
+
+blueprint! {
+    struct Gallery {}
+
+    impl Gallery {
+        pub fn new_token(url: Url) -> Bucket {
+            ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .url(url)
+                .initial_supply_fungible(1)
+        }
+
+        pub fn new_token_with_metadata(key: String, value: String) -> Bucket {
+            ResourceBuilder::new_fungible(DIVISIBILITY_NONE)
+                .metadata(key, value)
+                .initial_supply_fungible(1)
+        }
+
+        pub fn new_poster(image: Url) -> Bucket {
+            ResourceBuilder::new_non_fungible()
+                .initial_supply_non_fungible([(NonFungibleKey::from(1), Poster { image })])
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    key: EcdsaPublicKey,
+    account: Address,
+    package: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let key = executor.new_public_key();
+        let account = executor.new_account(key);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Gallery",
+            Gallery_main,
+            Gallery_abi,
+        ));
+
+        Self {
+            executor,
+            key,
+            account,
+            package,
+        }
+    }
+
+    fn call(
+        &mut self,
+        function: &str,
+        args: Vec<&str>,
+    ) -> Result<Transaction, BuildTransactionError> {
+        TransactionBuilder::new(&self.executor)
+            .call_function(
+                self.package,
+                "Gallery",
+                function,
+                args.into_iter().map(str::to_owned).collect(),
+                None,
+            )
+            .call_method_with_all_resources(self.account, "deposit_batch")
+            .build(vec![self.key])
+    }
+
+    fn run(&mut self, function: &str, args: Vec<&str>) -> Receipt {
+        let transaction = self.call(function, args).unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn url_metadata_should_be_stored() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.run("new_token", vec!["https://www.radixdlt.com"]);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    let resource_def = env
+        .executor
+        .ledger()
+        .get_resource_def(receipt.resource_def(0).unwrap())
+        .unwrap();
+    assert_eq!(
+        resource_def.metadata().get("url").map(String::as_str),
+        Some("https://www.radixdlt.com")
+    );
+}
+
+#[test]
+fn invalid_url_metadata_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.run(
+        "new_token_with_metadata",
+        vec!["icon_url", "javascript:alert(1)"],
+    );
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::ResourceDefError(
+            ResourceDefError::InvalidMetadataUrl {
+                error: ParseUrlError::InvalidScheme,
+                ..
+            }
+        ))
+    ));
+}
+
+#[test]
+fn invalid_url_argument_should_fail_to_build() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let result = env.call("new_token", vec!["ftp://radixdlt.com"]);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(BuildTransactionError::FailedToBuildArgs(
+            BuildArgsError::FailedToParse(0, _, _)
+        ))
+    ));
+}
+
+#[test]
+fn invalid_url_in_call_data_should_be_rejected() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+    let mut encoder = Encoder::with_type(Vec::new());
+    encoder.write_type(SCRYPTO_TYPE_URL);
+    encoder.write_len(3);
+    encoder.write_slice(b"abc");
+    // The builder validates call data too, so the transaction is assembled by hand
+    let transaction = Transaction {
+        instructions: vec![
+            Instruction::CallFunction {
+                package_address: env.package,
+                blueprint_name: "Gallery".to_owned(),
+                function: "new_token".to_owned(),
+                args: vec![encoder.into()],
+            },
+            Instruction::End { signatures: vec![] },
+        ],
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    };
+
+    // Act
+    let result = env.executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::DataValidationError(
+            DataValidationError::InvalidUrl(ParseUrlError::InvalidScheme)
+        ))
+    ));
+}
+
+#[test]
+fn non_fungible_data_can_hold_url() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.run("new_poster", vec!["https://www.radixdlt.com/poster.png"]);
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    let non_fungible = env
+        .executor
+        .ledger()
+        .get_non_fungible(receipt.resource_def(0).unwrap(), &NonFungibleKey::from(1))
+        .unwrap();
+    let poster =
+        Poster::decode(&non_fungible.immutable_data(), &non_fungible.mutable_data()).unwrap();
+    assert_eq!(poster.image.as_str(), "https://www.radixdlt.com/poster.png");
+}
@@ -2,6 +2,7 @@ use std::fs;
 use std::process::Command;
 
 use radix_engine::ledger::*;
+use radix_engine::model::RuntimeError;
 use radix_engine::transaction::*;
 use scrypto::prelude::*;
 
@@ -42,7 +43,10 @@ fn dangling_vault_should_fail() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(!receipt.result.is_ok());
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::DanglingVault(_))
+    ));
 }
 
 #[test]
@@ -60,7 +64,7 @@ fn create_mutable_vault_into_map() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -84,7 +88,7 @@ fn invalid_double_ownership_of_vault() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 }
 
 #[test]
@@ -108,7 +112,7 @@ fn create_mutable_vault_into_map_and_referencing_before_storing() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -137,7 +141,7 @@ fn cannot_overwrite_vault_in_map() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 }
 
 #[test]
@@ -155,7 +159,7 @@ fn create_mutable_vault_into_vector() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -184,7 +188,7 @@ fn cannot_remove_vaults() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(!receipt.result.is_ok());
+    assert!(!receipt.status.is_committed());
 }
 
 #[test]
@@ -213,7 +217,7 @@ fn can_push_vault_into_vector() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -231,7 +235,7 @@ fn create_mutable_vault_with_take() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -255,7 +259,55 @@ fn create_mutable_vault_with_take_non_fungible() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn create_mutable_vault_with_take_all() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut sut = TransactionExecutor::new(&mut ledger, false);
+    let package = sut.publish_package(&compile("vault")).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&sut)
+        .call_function(
+            package,
+            "VaultTest",
+            "new_vault_with_take_all",
+            vec![],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+    let receipt = sut.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn create_mutable_vault_with_take_non_fungibles() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut sut = TransactionExecutor::new(&mut ledger, false);
+    let package = sut.publish_package(&compile("vault")).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&sut)
+        .call_function(
+            package,
+            "VaultTest",
+            "new_vault_with_take_non_fungibles",
+            vec![],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+    let receipt = sut.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -279,7 +331,7 @@ fn create_mutable_vault_with_get_nonfungible_keys() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -303,7 +355,7 @@ fn create_mutable_vault_with_get_amount() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
 }
 
 #[test]
@@ -327,5 +379,29 @@ fn create_mutable_vault_with_get_resource_def() {
     let receipt = sut.run(transaction).unwrap();
 
     // Assert
-    assert!(receipt.result.is_ok());
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn create_mutable_vault_with_take_or_none() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut sut = TransactionExecutor::new(&mut ledger, false);
+    let package = sut.publish_package(&compile("vault")).unwrap();
+
+    // Act
+    let transaction = TransactionBuilder::new(&sut)
+        .call_function(
+            package,
+            "VaultTest",
+            "new_vault_with_take_or_none",
+            vec![],
+            None,
+        )
+        .build(vec![])
+        .unwrap();
+    let receipt = sut.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
 }
@@ -117,7 +117,9 @@ blueprint! {
             let bucket = ResourceBuilder::new_non_fungible()
                 .metadata("name", "TestToken")
                 .initial_supply_non_fungible([
-                    (NonFungibleKey::from(1u128), Data {})
+                    (NonFungibleKey::from(1u128), Data {}),
+                    (NonFungibleKey::from(2u128), Data {}),
+                    (NonFungibleKey::from(3u128), Data {})
                 ]);
             Vault::with_bucket(bucket)
         }
@@ -135,6 +137,39 @@ blueprint! {
             }.instantiate()
         }
 
+        pub fn new_vault_with_take_all() -> Component {
+            let mut vault = Self::create_non_fungible_vault();
+            let bucket = vault.take_all();
+            assert!(vault.is_empty());
+            assert_eq!(bucket.amount(), 3.into());
+            vault.put(bucket);
+            let vaults = LazyMap::new();
+            let vault_vector = Vec::new();
+            VaultTest {
+                vault,
+                vaults,
+                vault_vector
+            }.instantiate()
+        }
+
+        pub fn new_vault_with_take_non_fungibles() -> Component {
+            let mut vault = Self::create_non_fungible_vault();
+            let mut keys = BTreeSet::new();
+            keys.insert(NonFungibleKey::from(1u128));
+            keys.insert(NonFungibleKey::from(3u128));
+            let bucket = vault.take_non_fungibles(&keys);
+            assert_eq!(vault.get_non_fungible_keys(), vec![NonFungibleKey::from(2u128)]);
+            assert_eq!(bucket.amount(), 2.into());
+            vault.put(bucket);
+            let vaults = LazyMap::new();
+            let vault_vector = Vec::new();
+            VaultTest {
+                vault,
+                vaults,
+                vault_vector
+            }.instantiate()
+        }
+
         pub fn new_vault_with_get_non_fungible_keys() -> Component {
             let vault = Self::create_non_fungible_vault();
             let _keys = vault.get_non_fungible_keys();
@@ -170,5 +205,24 @@ blueprint! {
                 vault_vector
             }.instantiate()
         }
+
+        pub fn new_vault_with_take_or_none() -> Component {
+            let bucket = Self::new_fungible();
+            let mut vault = Vault::with_bucket(bucket);
+            assert!(vault.take_or_none(0).is_none());
+            let (bucket, shortfall) = vault.take_up_to(3);
+            assert_eq!(bucket.amount(), 1.into());
+            assert_eq!(shortfall, 2.into());
+            vault.put(bucket);
+            let empty = vault.take(0);
+            assert!(empty.into_option().is_none());
+            let vaults = LazyMap::new();
+            let vault_vector = Vec::new();
+            VaultTest {
+                vault,
+                vaults,
+                vault_vector
+            }.instantiate()
+        }
     }
 }
@@ -0,0 +1,154 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::{BucketError, Receipt, RuntimeError, VaultError};
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+
+blueprint! {
+    struct Reserve {
+        tokens: Vault,
+    }
+
+    impl Reserve {
+        pub fn instantiate_reserve() -> Component {
+            Self {
+                tokens: Vault::with_bucket(ResourceBuilder::new_fungible(2).initial_supply_fungible(10)),
+            }
+            .instantiate()
+        }
+
+        /// Returns the amount taken and the shortfall, and puts the tokens back.
+        pub fn take_up_to(&mut self, amount: Decimal) -> (Decimal, Decimal) {
+            let (bucket, shortfall) = self.tokens.take_up_to(amount);
+            let taken = bucket.amount();
+            self.tokens.put(bucket);
+            (taken, shortfall)
+        }
+
+        pub fn take_exact(&mut self, amount: Decimal) -> Decimal {
+            let bucket = self.tokens.take_exact(amount);
+            let taken = bucket.amount();
+            self.tokens.put(bucket);
+            taken
+        }
+    }
+}
+
+struct TestEnv<'a> {
+    executor: TransactionExecutor<'a, InMemorySubstateStore>,
+    reserve: Address,
+}
+
+impl<'a> TestEnv<'a> {
+    fn new(ledger: &'a mut InMemorySubstateStore) -> Self {
+        let mut executor = TransactionExecutor::new(ledger, false);
+        let package = executor.publish_native_package(NativePackage::new().with_blueprint(
+            "Reserve",
+            Reserve_main,
+            Reserve_abi,
+        ));
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(package, "Reserve", "instantiate_reserve", vec![], None)
+            .build(vec![])
+            .unwrap();
+        let receipt = executor.run(transaction).unwrap();
+        let reserve = receipt.expect_commit_success().component(0).unwrap();
+
+        Self { executor, reserve }
+    }
+
+    fn call(&mut self, method: &str, amount: &str) -> Receipt {
+        let transaction = TransactionBuilder::new(&self.executor)
+            .call_method(self.reserve, method, vec![amount.to_owned()], None)
+            .build(vec![])
+            .unwrap();
+        self.executor.run(transaction).unwrap()
+    }
+}
+
+#[test]
+fn take_up_to_should_take_what_is_available() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("take_up_to", "25");
+
+    // Assert
+    let (taken, shortfall): (Decimal, Decimal) = receipt.expect_commit_success().output(0);
+    assert_eq!(taken, Decimal::from(10));
+    assert_eq!(shortfall, Decimal::from(15));
+}
+
+#[test]
+fn take_up_to_should_round_down_to_divisibility() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("take_up_to", "1.239");
+
+    // Assert
+    let (taken, shortfall): (Decimal, Decimal) = receipt.expect_commit_success().output(0);
+    assert_eq!(taken, Decimal::from_str("1.23").unwrap());
+    assert_eq!(shortfall, Decimal::from_str("0.009").unwrap());
+}
+
+#[test]
+fn take_up_to_negative_amount_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("take_up_to", "-1");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::VaultError(VaultError::AccountingError(
+            BucketError::InvalidAmount(_)
+        )))
+    ));
+}
+
+#[test]
+fn take_exact_should_not_round_amount() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let exact = env.call("take_exact", "1.23");
+    let truncated = env.call("take_exact", "1.239");
+
+    // Assert
+    let taken: Decimal = exact.expect_commit_success().output(0);
+    assert_eq!(taken, Decimal::from_str("1.23").unwrap());
+    assert!(matches!(
+        truncated.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::VaultError(VaultError::AccountingError(
+            BucketError::InvalidAmount(_)
+        )))
+    ));
+}
+
+#[test]
+fn take_exact_more_than_available_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut env = TestEnv::new(&mut ledger);
+
+    // Act
+    let receipt = env.call("take_exact", "11");
+
+    // Assert
+    assert!(matches!(
+        receipt.status.error().map(RuntimeError::root_cause),
+        Some(RuntimeError::VaultError(VaultError::AccountingError(
+            BucketError::InsufficientBalance
+        )))
+    ));
+}
@@ -0,0 +1,86 @@
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
+use radix_engine::model::WasmValidationError;
+use radix_engine::transaction::*;
+use scrypto::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use wasmi::{MemoryRef, ModuleRef};
+
+/// Counts the instances created by the wrapped engine.
+#[derive(Default)]
+struct CountingEngine {
+    inner: WasmiEngine,
+    instances: AtomicUsize,
+}
+
+impl WasmEngine for CountingEngine {
+    fn instantiate(
+        &self,
+        package_address: Address,
+        code_hash: H256,
+        code: &[u8],
+    ) -> Result<(ModuleRef, MemoryRef), WasmValidationError> {
+        self.instances.fetch_add(1, Ordering::SeqCst);
+        self.inner.instantiate(package_address, code_hash, code)
+    }
+}
+
+fn new_account(executor: &mut TransactionExecutor<InMemorySubstateStore>) -> Address {
+    let key = executor.new_public_key();
+    executor.new_account(key)
+}
+
+#[test]
+fn modules_should_be_cached_across_transactions() {
+    // Arrange
+    let engine = WasmiEngine::new(16);
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_wasm_engine(Arc::new(engine.clone()));
+    new_account(&mut executor);
+    let cached = engine.cached_modules();
+
+    // Act
+    new_account(&mut executor);
+
+    // Assert
+    assert!(cached > 0);
+    assert_eq!(engine.cached_modules(), cached);
+}
+
+#[test]
+fn modules_should_be_shared_by_executors() {
+    // Arrange
+    let engine = WasmiEngine::new(16);
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut other_ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_wasm_engine(Arc::new(engine.clone()));
+    new_account(&mut executor);
+    let cached = engine.cached_modules();
+
+    // Act
+    let mut other_executor = TransactionExecutor::new(&mut other_ledger, false)
+        .with_wasm_engine(Arc::new(engine.clone()));
+    new_account(&mut other_executor);
+
+    // Assert
+    assert_eq!(engine.cached_modules(), cached);
+}
+
+#[test]
+fn executor_should_run_code_through_given_engine() {
+    // Arrange
+    let engine = Arc::new(CountingEngine::default());
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor =
+        TransactionExecutor::new(&mut ledger, false).with_wasm_engine(engine.clone());
+
+    // Act
+    let account = new_account(&mut executor);
+
+    // Assert
+    assert!(executor.ledger().get_component(account).is_some());
+    assert!(engine.instances.load(Ordering::SeqCst) > 0);
+}
@@ -0,0 +1,247 @@
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use scrypto::engine::*;
+use scrypto::resource::{BURNABLE, FREELY_BURNABLE};
+use scrypto::rust::collections::*;
+use scrypto::types::*;
+
+#[test]
+fn take_up_to_from_empty_worktop_should_return_empty_bucket() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .take_up_to_from_worktop(Decimal(100), RADIX_TOKEN, |builder, bid| {
+            builder
+                .add_instruction(Instruction::ReturnToWorktop { bid })
+                .0
+        })
+        .assert_worktop_contains(Decimal::zero(), RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
+
+#[test]
+fn take_up_to_unknown_resource_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let unknown = Address::ResourceDef([1u8; 26]);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .take_up_to_from_worktop(Decimal(1), unknown, |builder, bid| {
+            builder
+                .add_instruction(Instruction::ReturnToWorktop { bid })
+                .0
+        })
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status,
+        TransactionStatus::Failed {
+            error: RuntimeError::ResourceDefNotFound(address),
+            ..
+        } if address == unknown
+    ));
+}
+
+#[test]
+fn assert_missing_non_fungibles_should_fail() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let keys = BTreeSet::from([NonFungibleKey::from(1u128)]);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .assert_worktop_contains_non_fungibles(&keys, ECDSA_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(matches!(
+        receipt.status,
+        TransactionStatus::Failed {
+            category: FailureCategory::Resource,
+            error: RuntimeError::AssertionFailed,
+        }
+    ));
+}
+
+#[test]
+fn burn_all_from_worktop_should_reduce_supply() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let total_supply = ledger.get_resource_def(RADIX_TOKEN).unwrap().total_supply();
+    ledger.put_resource_def(
+        RADIX_TOKEN,
+        ResourceDef::new(
+            ResourceType::Fungible { divisibility: 18 },
+            HashMap::new(),
+            BURNABLE | FREELY_BURNABLE,
+            0,
+            HashMap::new(),
+            &Some(NewSupply::Fungible {
+                amount: total_supply,
+            }),
+        )
+        .unwrap(),
+    );
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .burn_all_from_worktop(RADIX_TOKEN)
+        .assert_worktop_contains(Decimal::zero(), RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+    assert_eq!(
+        executor
+            .ledger()
+            .get_resource_def(RADIX_TOKEN)
+            .unwrap()
+            .total_supply(),
+        total_supply - 100
+    );
+}
+
+#[test]
+fn burn_all_from_worktop_should_be_rejected_if_not_burnable() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .burn_all_from_worktop(RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::ResourceNotFreelyBurnable {
+            resource_address: RADIX_TOKEN,
+            instruction_index: 1,
+        })
+    ));
+}
+
+#[test]
+fn burn_all_from_worktop_should_be_rejected_if_burning_requires_badge() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let total_supply = ledger.get_resource_def(RADIX_TOKEN).unwrap().total_supply();
+    ledger.put_resource_def(
+        RADIX_TOKEN,
+        ResourceDef::new(
+            ResourceType::Fungible { divisibility: 18 },
+            HashMap::new(),
+            BURNABLE,
+            0,
+            HashMap::new(),
+            &Some(NewSupply::Fungible {
+                amount: total_supply,
+            }),
+        )
+        .unwrap(),
+    );
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .burn_all_from_worktop(RADIX_TOKEN)
+        .build(vec![])
+        .unwrap();
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::ResourceNotFreelyBurnable {
+            resource_address: RADIX_TOKEN,
+            instruction_index: 1,
+        })
+    ));
+}
+
+#[test]
+fn dangling_bucket_should_be_rejected_before_execution() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .take_from_worktop(
+            &Resource::Fungible {
+                amount: Decimal(100),
+                resource_address: RADIX_TOKEN,
+            },
+            |builder, _| builder,
+        )
+        .build(vec![])
+        .unwrap();
+    let result = executor.run(transaction);
+
+    // Assert
+    assert!(matches!(
+        result,
+        Err(TransactionValidationError::DanglingBucket {
+            instruction_index: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn returned_bucket_should_pass_validation() {
+    // Arrange
+    let mut ledger = InMemorySubstateStore::with_bootstrap();
+    let mut executor = TransactionExecutor::new(&mut ledger, false);
+    let key = executor.new_public_key();
+    let account = executor.new_account(key);
+
+    // Act
+    let transaction = TransactionBuilder::new(&executor)
+        .call_method(SYSTEM_COMPONENT, "free_xrd", vec!["100".to_owned()], None)
+        .take_from_worktop(
+            &Resource::Fungible {
+                amount: Decimal(100),
+                resource_address: RADIX_TOKEN,
+            },
+            |builder, bid| {
+                builder
+                    .add_instruction(Instruction::ReturnToWorktop { bid })
+                    .0
+            },
+        )
+        .call_method_with_all_resources(account, "deposit_batch")
+        .build(vec![])
+        .unwrap();
+    let receipt = executor.run(transaction).unwrap();
+
+    // Assert
+    assert!(receipt.status.is_committed());
+}
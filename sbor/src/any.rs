@@ -51,6 +51,114 @@ pub enum Fields {
     Unit,
 }
 
+impl Value {
+    /// Returns the SBOR type id of this value.
+    pub fn type_id(&self) -> u8 {
+        match self {
+            Value::Unit => TYPE_UNIT,
+            Value::Bool(_) => TYPE_BOOL,
+            Value::I8(_) => TYPE_I8,
+            Value::I16(_) => TYPE_I16,
+            Value::I32(_) => TYPE_I32,
+            Value::I64(_) => TYPE_I64,
+            Value::I128(_) => TYPE_I128,
+            Value::U8(_) => TYPE_U8,
+            Value::U16(_) => TYPE_U16,
+            Value::U32(_) => TYPE_U32,
+            Value::U64(_) => TYPE_U64,
+            Value::U128(_) => TYPE_U128,
+            Value::String(_) => TYPE_STRING,
+            Value::Struct(_) => TYPE_STRUCT,
+            Value::Enum(_, _) => TYPE_ENUM,
+            Value::Option(_) => TYPE_OPTION,
+            Value::Box(_) => TYPE_BOX,
+            Value::Array(_, _) => TYPE_ARRAY,
+            Value::Tuple(_) => TYPE_TUPLE,
+            Value::Result(_) => TYPE_RESULT,
+            Value::Vec(_, _) => TYPE_VEC,
+            Value::TreeSet(_, _) => TYPE_TREE_SET,
+            Value::TreeMap(_, _, _) => TYPE_TREE_MAP,
+            Value::HashSet(_, _) => TYPE_HASH_SET,
+            Value::HashMap(_, _, _) => TYPE_HASH_MAP,
+            Value::Custom(ty, _) => *ty,
+        }
+    }
+
+    /// Encodes this value into byte array, with type info included.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut enc = Encoder::with_type(Vec::new());
+        encode_any(None, self, &mut enc);
+        enc.into()
+    }
+
+    /// Returns the child value at the given index.
+    ///
+    /// Children are the fields of a struct or enum variant, the elements of an array, tuple
+    /// or collection, or the content of an option, box or result. Maps are flattened into
+    /// alternating keys and values.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        match self {
+            Value::Struct(fields) | Value::Enum(_, fields) => match fields {
+                Fields::Named(v) | Fields::Unnamed(v) => v.get(index),
+                Fields::Unit => None,
+            },
+            Value::Option(v) => match v.borrow() {
+                Some(x) if index == 0 => Some(x),
+                _ => None,
+            },
+            Value::Box(v) if index == 0 => Some(v),
+            Value::Result(v) if index == 0 => match v.borrow() {
+                Ok(x) | Err(x) => Some(x),
+            },
+            Value::Array(_, elements)
+            | Value::Tuple(elements)
+            | Value::Vec(_, elements)
+            | Value::TreeSet(_, elements)
+            | Value::TreeMap(_, _, elements)
+            | Value::HashSet(_, elements)
+            | Value::HashMap(_, _, elements) => elements.get(index),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the child value at the given index.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Value> {
+        match self {
+            Value::Struct(fields) | Value::Enum(_, fields) => match fields {
+                Fields::Named(v) | Fields::Unnamed(v) => v.get_mut(index),
+                Fields::Unit => None,
+            },
+            Value::Option(v) => match v.as_mut() {
+                Some(x) if index == 0 => Some(x),
+                _ => None,
+            },
+            Value::Box(v) if index == 0 => Some(v),
+            Value::Result(v) if index == 0 => match v.as_mut() {
+                Ok(x) | Err(x) => Some(x),
+            },
+            Value::Array(_, elements)
+            | Value::Tuple(elements)
+            | Value::Vec(_, elements)
+            | Value::TreeSet(_, elements)
+            | Value::TreeMap(_, _, elements)
+            | Value::HashSet(_, elements)
+            | Value::HashMap(_, _, elements) => elements.get_mut(index),
+            _ => None,
+        }
+    }
+
+    /// Returns the value at the given path, where each step is a child index.
+    pub fn get_path(&self, path: &[usize]) -> Option<&Value> {
+        path.iter().try_fold(self, |value, index| value.get(*index))
+    }
+
+    /// Returns a mutable reference to the value at the given path.
+    pub fn get_path_mut(&mut self, path: &[usize]) -> Option<&mut Value> {
+        path.iter()
+            .try_fold(self, |value, index| value.get_mut(*index))
+    }
+}
+
 /// Encodes any SBOR value into byte array.
 pub fn encode_any(ty_ctx: Option<u8>, value: &Value, enc: &mut Encoder) {
     match value {
@@ -642,6 +750,47 @@ mod tests {
         assert_eq!(bytes2, bytes);
     }
 
+    #[test]
+    pub fn test_path_access() {
+        let data = TestStruct { x: 5 };
+        let mut value = decode_any(&encode_with_type(Vec::new(), &data)).unwrap();
+
+        assert_eq!(value.get_path(&[0]), Some(&Value::U32(5)));
+        assert_eq!(value.get_path(&[1]), None);
+        assert_eq!(value.get_path(&[0, 0]), None);
+
+        *value.get_path_mut(&[0]).unwrap() = Value::U32(6);
+        assert_eq!(
+            value.to_bytes(),
+            encode_with_type(Vec::new(), &TestStruct { x: 6 })
+        );
+    }
+
+    #[test]
+    pub fn test_build_value() {
+        let elements = vec![
+            Value::String(String::from("a")),
+            Value::String(String::from("b")),
+        ];
+        let value = Value::Tuple(vec![
+            Value::Option(Box::new(Some(Value::U8(1)))),
+            Value::Vec(elements[0].type_id(), elements),
+        ]);
+
+        assert_eq!(
+            value.to_bytes(),
+            encode_with_type(
+                Vec::new(),
+                &(Some(1u8), vec![String::from("a"), String::from("b")])
+            )
+        );
+        assert_eq!(value.get_path(&[0, 0]), Some(&Value::U8(1)));
+        assert_eq!(
+            value.get_path(&[1, 1]),
+            Some(&Value::String(String::from("b")))
+        );
+    }
+
     #[test]
     pub fn test_parse_custom() {
         let bytes: Vec<u8> = vec![0x80, 0x02, 0x00, 0x00, 0x00, 0x01, 0x02];
@@ -1,9 +1,13 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
 pub use alloc::string::String;
 #[cfg(feature = "alloc")]
 pub use alloc::vec::Vec;
+#[cfg(not(feature = "alloc"))]
+use std::collections::BTreeMap;
 
 #[cfg(any(feature = "serde_std", feature = "serde_alloc"))]
 use serde::{Deserialize, Serialize};
@@ -22,6 +26,27 @@ pub struct Blueprint {
     pub name: String,
     pub functions: Vec<Function>,
     pub methods: Vec<Method>,
+    /// The type of the component state, or `None` if exported by an older blueprint.
+    #[cfg_attr(any(feature = "serde_std", feature = "serde_alloc"), serde(default))]
+    pub state: Option<Type>,
+    /// The metadata declared by the blueprint, e.g. its version and author.
+    #[cfg_attr(any(feature = "serde_std", feature = "serde_alloc"), serde(default))]
+    pub metadata: BTreeMap<String, String>,
+    /// The data of the non-fungibles declared by the blueprint.
+    #[cfg_attr(any(feature = "serde_std", feature = "serde_alloc"), serde(default))]
+    pub non_fungible_data: Vec<NonFungibleData>,
+}
+
+/// Represents the data of a non-fungible, split into its immutable and mutable parts.
+#[cfg_attr(
+    any(feature = "serde_std", feature = "serde_alloc"),
+    derive(Serialize, Deserialize)
+)]
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct NonFungibleData {
+    pub name: String,
+    pub immutable_data: Type,
+    pub mutable_data: Type,
 }
 
 /// Represents a function.
@@ -45,6 +70,8 @@ pub struct Function {
 pub struct Method {
     pub name: String,
     pub mutability: Mutability,
+    #[cfg_attr(any(feature = "serde_std", feature = "serde_alloc"), serde(default))]
+    pub access: Access,
     pub inputs: Vec<Type>,
     pub output: Type,
 }
@@ -62,3 +89,21 @@ pub enum Mutability {
     /// A mutable method requires a mutable reference to component state.
     Mutable,
 }
+
+/// Who is allowed to call a method.
+#[cfg_attr(
+    any(feature = "serde_std", feature = "serde_alloc"),
+    derive(Serialize, Deserialize)
+)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, TypeId, Encode, Decode)]
+pub enum Access {
+    /// Anyone may call the method.
+    #[default]
+    Public,
+
+    /// Only blueprints and components of the same package may call the method.
+    Package,
+
+    /// Only the component itself may call the method, e.g. as a callback.
+    Component,
+}
@@ -0,0 +1,145 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::*;
+
+macro_rules! trace {
+    ($($arg:expr),*) => {{
+        #[cfg(feature = "trace")]
+        println!($($arg),*);
+    }};
+}
+
+/// The kind of address a macro produces, with the type byte it expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Package,
+    Component,
+    ResourceDef,
+}
+
+impl AddressKind {
+    fn type_byte(&self) -> u8 {
+        match self {
+            Self::Package => 1,
+            Self::Component => 2,
+            Self::ResourceDef => 3,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Package => "package",
+            Self::Component => "component",
+            Self::ResourceDef => "resource definition",
+        }
+    }
+}
+
+pub fn handle_address(input: TokenStream, kind: AddressKind) -> Result<TokenStream> {
+    trace!("Started processing address macro: {:?}", kind);
+
+    let literal = parse2::<LitStr>(input)?;
+    let bytes =
+        parse_address(&literal.value(), kind).map_err(|msg| Error::new(literal.span(), msg))?;
+    let bytes = &bytes[1..];
+
+    let output = match kind {
+        AddressKind::Package => quote! {
+            ::scrypto::core::Package::from(::scrypto::types::Address::Package([#(#bytes),*]))
+        },
+        AddressKind::Component => quote! {
+            ::scrypto::core::Component::from(::scrypto::types::Address::Component([#(#bytes),*]))
+        },
+        AddressKind::ResourceDef => quote! {
+            ::scrypto::resource::ResourceDef::from(
+                ::scrypto::types::Address::ResourceDef([#(#bytes),*])
+            )
+        },
+    };
+    trace!("Finished processing address macro");
+
+    #[cfg(feature = "trace")]
+    crate::utils::print_generated_code("address!", &output);
+
+    Ok(output)
+}
+
+/// Decodes a hex address and checks its length and type byte.
+fn parse_address(s: &str, kind: AddressKind) -> core::result::Result<Vec<u8>, String> {
+    let bytes = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2).unwrap_or("-"), 16))
+        .collect::<core::result::Result<Vec<u8>, _>>()
+        .map_err(|_| format!("Invalid address {:?}: not a hex string", s))?;
+    if bytes.len() != 27 {
+        return Err(format!(
+            "Invalid address {:?}: expected 27 bytes, found {}",
+            s,
+            bytes.len()
+        ));
+    }
+    if bytes[0] != kind.type_byte() {
+        return Err(format!(
+            "Invalid address {:?}: not a {} address",
+            s,
+            kind.name()
+        ));
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn assert_code_eq(a: TokenStream, b: TokenStream) {
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn test_resource_def() {
+        let input =
+            TokenStream::from_str("\"03000000000000000000000000000000000000000000000000000a\"")
+                .unwrap();
+        let output = handle_address(input, AddressKind::ResourceDef).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                ::scrypto::resource::ResourceDef::from(
+                    ::scrypto::types::Address::ResourceDef([
+                        0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8,
+                        0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 0u8, 10u8
+                    ])
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_wrong_kind() {
+        let input =
+            TokenStream::from_str("\"03000000000000000000000000000000000000000000000000000a\"")
+                .unwrap();
+        let err = handle_address(input, AddressKind::Component).unwrap_err();
+
+        assert!(err.to_string().contains("not a component address"));
+    }
+
+    #[test]
+    fn test_malformed() {
+        for s in [
+            "\"0200000000000000000000000000000000000000000000000000\"",
+            "\"02000000000000000000000000000000000000000000000000000\"",
+            "\"02000000000000000000000000000000000000000000000000000z\"",
+            "\"02000000000000000000000000000000000000000000000000000é\"",
+            "42",
+        ] {
+            let input = TokenStream::from_str(s).unwrap();
+            assert!(handle_address(input, AddressKind::Component).is_err());
+        }
+    }
+}
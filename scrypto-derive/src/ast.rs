@@ -1,7 +1,9 @@
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::token::Comma;
-use syn::{ItemImpl, ItemStruct, Path, Result};
+use syn::token::{Comma, Eq, Paren};
+use syn::{
+    braced, parenthesized, Ident, ItemImpl, ItemStruct, LitStr, Path, Result, TraitItemMethod,
+};
 
 /// Represents the AST of blueprint.
 pub struct Blueprint {
@@ -18,9 +20,72 @@ impl Parse for Blueprint {
     }
 }
 
+/// Represents the AST of an external blueprint, i.e. its package address, name and the
+/// signatures of its functions and methods.
+pub struct ExternalBlueprint {
+    pub package: LitStr,
+    pub name: Ident,
+    pub items: Vec<TraitItemMethod>,
+}
+
+impl Parse for ExternalBlueprint {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let package = input.parse()?;
+        input.parse::<Comma>()?;
+        let name = input.parse()?;
+        let content;
+        braced!(content in input);
+        let mut items = Vec::new();
+        while !content.is_empty() {
+            items.push(content.parse()?);
+        }
+        Ok(Self {
+            package,
+            name,
+            items,
+        })
+    }
+}
+
 /// Represents the AST of allowed badges for authorization.
 pub struct Auth {
-    pub allowed: Punctuated<Path, Comma>,
+    pub allowed: Punctuated<AuthRule, Comma>,
+}
+
+/// Represents a single allowed badge, optionally restricted by resource metadata,
+/// e.g. `admin_badge` or `staff_badge(role = "admin")`.
+pub struct AuthRule {
+    pub badge: Path,
+    pub metadata: Punctuated<MetadataRequirement, Comma>,
+}
+
+impl Parse for AuthRule {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let badge = input.parse()?;
+        let metadata = if input.peek(Paren) {
+            let content;
+            parenthesized!(content in input);
+            Punctuated::parse_terminated(&content)?
+        } else {
+            Punctuated::new()
+        };
+        Ok(Self { badge, metadata })
+    }
+}
+
+/// Represents a `key = "value"` requirement on resource metadata.
+pub struct MetadataRequirement {
+    pub key: Ident,
+    pub value: LitStr,
+}
+
+impl Parse for MetadataRequirement {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Eq>()?;
+        let value = input.parse()?;
+        Ok(Self { key, value })
+    }
 }
 
 impl Parse for Auth {
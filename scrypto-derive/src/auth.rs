@@ -20,10 +20,17 @@ pub fn handle_auth(attr: TokenStream, item: TokenStream) -> Result<TokenStream>
     let auth = parse2::<ast::Auth>(attr)?;
     let mut allowed_badges = Vec::new();
     for a in auth.allowed {
-        if let Some(ident) = a.get_ident() {
-            allowed_badges.push(ident.clone());
+        if let Some(ident) = a.badge.get_ident() {
+            let metadata_checks = a.metadata.iter().map(|m| {
+                let key = m.key.to_string();
+                let value = &m.value;
+                quote! { && auth.has_metadata(#key, #value) }
+            });
+            allowed_badges.push(quote! {
+                (::scrypto::resource::AuthBadge::is_presented_by(&self.#ident, &auth) #(#metadata_checks)*)
+            });
         } else {
-            return Err(Error::new(a.span(), "Only path value is allowed"));
+            return Err(Error::new(a.badge.span(), "Only path value is allowed"));
         }
     }
 
@@ -58,7 +65,7 @@ pub fn handle_auth(attr: TokenStream, item: TokenStream) -> Result<TokenStream>
     let output = quote! {
         #(#f_attrs)*
         #f_vis fn #f_ident (#(#f_inputs),*) #f_output {
-            if !(#(auth.contains(self.#allowed_badges.clone()))||*) {
+            if !(#(#allowed_badges)||*) {
                 panic!("Not authorized!")
             }
 
@@ -95,7 +102,28 @@ mod tests {
             quote! {
                 #[other]
                 pub fn x(&self, auth: ::scrypto::resource::BucketRef) -> u32 {
-                    if !(auth.contains(self.foo.clone()) || auth.contains(self.bar.clone())) {
+                    if !((::scrypto::resource::AuthBadge::is_presented_by(&self.foo, &auth)) || (::scrypto::resource::AuthBadge::is_presented_by(&self.bar, &auth))) {
+                        panic!("Not authorized!")
+                    }
+                    {
+                        self.a
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_auth_with_metadata() {
+        let attr = TokenStream::from_str("foo(role = \"admin\")").unwrap();
+        let item = TokenStream::from_str("pub fn x(&self) -> u32 { self.a }").unwrap();
+        let output = handle_auth(attr, item).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                pub fn x(&self, auth: ::scrypto::resource::BucketRef) -> u32 {
+                    if !((::scrypto::resource::AuthBadge::is_presented_by(&self.foo, &auth) && auth.has_metadata("role", "admin"))) {
                         panic!("Not authorized!")
                     }
                     {
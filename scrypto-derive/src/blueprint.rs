@@ -5,6 +5,7 @@ use syn::token::Brace;
 use syn::*;
 
 use crate::ast;
+use crate::bucket_lint::dangling_bucket_warnings;
 
 macro_rules! trace {
     ($($arg:expr),*) => {{
@@ -27,6 +28,115 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     let bp_name = bp_ident.to_string();
     trace!("Blueprint name: {}", bp_name);
 
+    // `#[reentrant]` allows calling back into a component while one of its methods is running
+    let bp_reentrant = if bp_strut
+        .attrs
+        .iter()
+        .any(|attr| attr.path.is_ident("reentrant"))
+    {
+        quote! {
+            fn reentrant() -> bool {
+                true
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // `#[invariant(expr)]` conditions are checked after every method that mutates the state
+    let bp_invariants = bp_strut
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("invariant"))
+        .map(|attr| attr.parse_args::<Expr>())
+        .collect::<Result<Vec<Expr>>>()?;
+    let bp_check_invariants = if bp_invariants.is_empty() {
+        quote! {}
+    } else {
+        let conditions = bp_invariants.iter().map(|e| quote! { #e }.to_string());
+        quote! {
+            impl #bp_ident {
+                #[doc(hidden)]
+                pub fn __check_invariants(&self, method: &str) {
+                    #(
+                        if !(#bp_invariants) {
+                            panic!(
+                                "Invariant `{}` of blueprint `{}` violated after calling `{}`",
+                                #conditions,
+                                #bp_name,
+                                method
+                            );
+                        }
+                    )*
+                }
+            }
+        }
+    };
+
+    // `#[metadata(key = "value", ...)]` describes the blueprint, e.g. its version and author
+    let (bp_metadata_keys, bp_metadata_values): (Vec<String>, Vec<LitStr>) =
+        blueprint_metadata(&bp_strut.attrs)?.into_iter().unzip();
+
+    // `#[non_fungible_data(Type, ...)]` exports the schema of the non-fungibles it mints
+    let (bp_non_fungible_names, bp_non_fungible_types): (Vec<String>, Vec<Path>) =
+        blueprint_non_fungible_data(&bp_strut.attrs)?
+            .into_iter()
+            .unzip();
+
+    // `#[access(package)]` and `#[access(component)]` restrict who may call a method
+    let mut bp_access = vec![];
+    for item in bp_items {
+        if let ImplItem::Method(m) = item {
+            if let Some(access) = method_access(m)? {
+                let name = m.sig.ident.to_string();
+                bp_access.push(quote! { (#name.to_owned(), #access) });
+            }
+        }
+    }
+    let bp_method_access = if bp_access.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn method_access() -> ::scrypto::rust::vec::Vec<(::scrypto::rust::string::String, ::scrypto::abi::Access)> {
+                use ::scrypto::rust::borrow::ToOwned;
+                ::scrypto::rust::vec![ #(#bp_access),* ]
+            }
+        }
+    };
+    // `#[auth(badge_field, ...)]` is also enforced by the engine, which reads the badges from
+    // the fields of the current state and rejects calls presenting none of them
+    let mut bp_auth = vec![];
+    for item in bp_items {
+        if let ImplItem::Method(m) = item {
+            if let Some(indices) = method_auth(m, bp_fields)? {
+                let name = m.sig.ident.to_string();
+                bp_auth.push(quote! {
+                    (#name.to_owned(), ::scrypto::rust::vec![ #(#indices),* ])
+                });
+            }
+        }
+    }
+    let bp_method_auth = if bp_auth.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn method_auth() -> ::scrypto::rust::vec::Vec<(::scrypto::rust::string::String, ::scrypto::rust::vec::Vec<u32>)> {
+                use ::scrypto::rust::borrow::ToOwned;
+                ::scrypto::rust::vec![ #(#bp_auth),* ]
+            }
+        }
+    };
+    let bp_items_stripped = bp_items.iter().map(|item| match item {
+        ImplItem::Method(m) => {
+            let mut m = m.clone();
+            m.attrs.retain(|attr| !attr.path.is_ident("access"));
+            let warnings = dangling_bucket_warnings(&m);
+            m.block.stmts.splice(0..0, warnings);
+            ImplItem::Method(m)
+        }
+        _ => item.clone(),
+    });
+
     let impl_ident_matches = match &*bp_impl.self_ty {
         Type::Path(p) => p
             .path
@@ -42,21 +152,29 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         ));
     }
 
+    // Named after the blueprint, so that several blueprints can be defined in one module
+    let bp_mod = blueprint_mod_ident(bp_ident);
     let output_mod = quote! {
-        mod blueprint {
+        #[allow(non_snake_case)]
+        mod #bp_mod {
             use super::*;
 
-            #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+            #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
             pub struct #bp_ident #bp_fields #bp_semi_token
 
             impl #bp_ident {
-                #(#bp_items)*
+                #(#bp_items_stripped)*
             }
 
+            #bp_check_invariants
+
             impl ::scrypto::core::ComponentState for #bp_ident {
                 fn blueprint_name() -> &'static str {
                     #bp_name
                 }
+                #bp_reentrant
+                #bp_method_access
+                #bp_method_auth
                 fn instantiate(self) -> ::scrypto::core::Component {
                     ::scrypto::core::Component::new(self)
                 }
@@ -66,30 +184,33 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     trace!("Generated mod: \n{}", quote! { #output_mod });
 
     let dispatcher_ident = format_ident!("{}_main", bp_ident);
-    let (arm_guards, arm_bodies) = generate_dispatcher(bp_ident, bp_items)?;
+    let (arm_guards, arm_bodies) =
+        generate_dispatcher(bp_ident, bp_items, !bp_invariants.is_empty())?;
     let output_dispatcher = quote! {
         #[no_mangle]
         pub extern "C" fn #dispatcher_ident() -> *mut u8 {
             // Set up panic hook
             ::scrypto::utils::scrypto_setup_panic_hook();
 
-            // Retrieve call data
-            let calldata: ::scrypto::engine::GetCallDataOutput = ::scrypto::engine::call_engine(
-                ::scrypto::engine::GET_CALL_DATA,
-                ::scrypto::engine::GetCallDataInput {},
-            );
-
-            // Dispatch the call
-            let rtn;
-            match calldata.function.as_str() {
-                #( #arm_guards => #arm_bodies )*
-                _ => {
-                    panic!("Function/method not fund")
+            ::scrypto::utils::scrypto_catch_panic(|| {
+                // Retrieve call data
+                let calldata: ::scrypto::engine::GetCallDataOutput = ::scrypto::engine::call_engine(
+                    ::scrypto::engine::GET_CALL_DATA,
+                    ::scrypto::engine::GetCallDataInput {},
+                );
+
+                // Dispatch the call
+                let rtn;
+                match calldata.function.as_str() {
+                    #( #arm_guards => #arm_bodies )*
+                    _ => {
+                        panic!("Function/method not fund")
+                    }
                 }
-            }
 
-            // Return
-            ::scrypto::buffer::scrypto_wrap(rtn)
+                // Return
+                ::scrypto::buffer::scrypto_wrap(rtn)
+            })
         }
     };
     trace!("Generated dispatcher: \n{}", quote! { #output_dispatcher });
@@ -102,12 +223,27 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             use ::sbor::Describe;
             use ::scrypto::abi::{Function, Method};
             use ::scrypto::rust::borrow::ToOwned;
+            use ::scrypto::rust::collections::BTreeMap;
+            use ::scrypto::rust::string::String;
             use ::scrypto::rust::vec;
             use ::scrypto::rust::vec::Vec;
 
             let functions: Vec<Function> = vec![ #(#abi_functions),* ];
             let methods: Vec<Method> = vec![ #(#abi_methods),* ];
-            let output = (functions, methods);
+            let state = <#bp_mod::#bp_ident>::describe();
+            let metadata: BTreeMap<String, String> = vec![
+                #((#bp_metadata_keys.to_owned(), #bp_metadata_values.to_owned())),*
+            ]
+            .into_iter()
+            .collect();
+            let non_fungible_data: Vec<::scrypto::abi::NonFungibleData> = vec![
+                #(::scrypto::abi::NonFungibleData {
+                    name: #bp_non_fungible_names.to_owned(),
+                    immutable_data: <#bp_non_fungible_types as ::scrypto::resource::NonFungibleData>::immutable_data_schema(),
+                    mutable_data: <#bp_non_fungible_types as ::scrypto::resource::NonFungibleData>::mutable_data_schema(),
+                }),*
+            ];
+            let output = (functions, methods, state, metadata, non_fungible_data);
 
             // serialize the output
             let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
@@ -142,7 +278,12 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
 // Parses function items in an `Impl` and returns the arm guards and bodies
 // used for call matching.
-fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<Expr>)> {
+fn generate_dispatcher(
+    bp_ident: &Ident,
+    items: &[ImplItem],
+    check_invariants: bool,
+) -> Result<(Vec<Expr>, Vec<Expr>)> {
+    let bp_mod = blueprint_mod_ident(bp_ident);
     let mut arm_guards = Vec::<Expr>::new();
     let mut arm_bodies = Vec::<Expr>::new();
 
@@ -153,11 +294,13 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
             if let Visibility::Public(_) = &m.vis {
                 let fn_name = &m.sig.ident.to_string();
                 let fn_ident = &m.sig.ident;
+                let generics = generic_type_params(m)?;
 
                 let mut args: Vec<Expr> = vec![];
                 let mut stmts: Vec<Stmt> = vec![];
                 let mut get_state: Option<Stmt> = None;
                 let mut put_state: Option<Stmt> = None;
+                let mut check_state: Option<Stmt> = None;
                 for (i, input) in (&m.sig.inputs).into_iter().enumerate() {
                     let arg = format_ident!("arg{}", i);
                     match input {
@@ -183,7 +326,7 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
                             // Generate a `Stmt` for loading the component state
                             assert!(get_state.is_none(), "Can have at most 1 self reference");
                             get_state = Some(parse_quote! {
-                                let #mutability state: blueprint::#bp_ident = #arg.get_state();
+                                let #mutability state: #bp_mod::#bp_ident = #arg.get_state();
                             });
 
                             // Generate a `Stmt` for writing back component state
@@ -191,11 +334,25 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
                                 put_state = Some(parse_quote! {
                                     #arg.put_state(state);
                                 });
+
+                                // Invariants are always checked with the `invariants` feature,
+                                // otherwise only in debug builds
+                                if check_invariants {
+                                    let gate = if cfg!(feature = "invariants") {
+                                        quote! {}
+                                    } else {
+                                        quote! { #[cfg(debug_assertions)] }
+                                    };
+                                    check_state = Some(parse_quote! {
+                                        #gate
+                                        state.__check_invariants(#fn_name);
+                                    });
+                                }
                             }
                         }
                         FnArg::Typed(ref t) => {
                             // Generate an `Arg` and a loading `Stmt` for the i-th argument
-                            let ty = &t.ty;
+                            let ty = replace_generics_with(&t.ty, &generics);
                             let stmt: Stmt = parse_quote! {
                                 let #arg = ::scrypto::utils::scrypto_unwrap(
                                     ::scrypto::buffer::scrypto_decode::<#ty>(&calldata.args[#i])
@@ -233,13 +390,26 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr
                     stmts.push(stmt);
                 }
                 // call the function
+                let turbofish = if generics.is_empty() {
+                    quote! {}
+                } else {
+                    let raw = generics
+                        .iter()
+                        .map(|_| quote! { ::scrypto::resource::RawNonFungibleData });
+                    quote! { ::<#(#raw),*> }
+                };
                 let stmt: Stmt = parse_quote! {
                     rtn = ::scrypto::buffer::scrypto_encode_for_radix_engine(
-                        &blueprint::#bp_ident::#fn_ident(#(#args),*)
+                        &#bp_mod::#bp_ident::#fn_ident #turbofish(#(#args),*)
                     );
                 };
                 trace!("Generated stmt: {}", quote! { #stmt });
                 stmts.push(stmt);
+                // check invariants
+                if let Some(stmt) = check_state {
+                    trace!("Generated stmt: {}", quote! { #stmt });
+                    stmts.push(stmt);
+                }
                 // update state
                 if let Some(stmt) = put_state {
                     trace!("Generated stmt: {}", quote! { #stmt });
@@ -275,6 +445,7 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
             ImplItem::Method(ref m) => {
                 if let Visibility::Public(_) = &m.vis {
                     let name = m.sig.ident.to_string();
+                    let generics = generic_type_params(m)?;
                     let mut mutability = None;
                     let mut inputs = vec![];
                     for input in &m.sig.inputs {
@@ -294,7 +465,10 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
                                 }
                             }
                             FnArg::Typed(ref t) => {
-                                let ty = replace_self_with(&t.ty, &bp_ident.to_string());
+                                let ty = replace_generics_with(
+                                    &replace_self_with(&t.ty, &bp_ident.to_string()),
+                                    &generics,
+                                );
                                 inputs.push(quote! {
                                     <#ty>::describe()
                                 });
@@ -319,13 +493,19 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
                             ::sbor::describe::Type::Unit
                         },
                         ReturnType::Type(_, t) => {
-                            let ty = replace_self_with(t, &bp_ident.to_string());
+                            let ty = replace_generics_with(
+                                &replace_self_with(t, &bp_ident.to_string()),
+                                &generics,
+                            );
                             quote! {
                                 <#ty>::describe()
                             }
                         }
                     };
 
+                    let access =
+                        method_access(m)?.unwrap_or(quote! { ::scrypto::abi::Access::Public });
+
                     if mutability.is_none() {
                         functions.push(parse_quote! {
                             ::scrypto::abi::Function {
@@ -339,6 +519,7 @@ fn generate_abi(bp_ident: &Ident, items: &[ImplItem]) -> Result<(Vec<Expr>, Vec<
                             ::scrypto::abi::Method {
                                 name: #name.to_owned(),
                                 mutability: #mutability,
+                                access: #access,
                                 inputs: vec![#(#inputs),*],
                                 output: #output,
                             }
@@ -371,6 +552,7 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
                 if let Visibility::Public(_) = &m.vis {
                     let ident = &m.sig.ident;
                     let name = ident.to_string();
+                    let generics = generic_type_params(m)?;
                     let mut mutable = None;
                     let mut input_types = vec![];
                     let mut input_args = vec![];
@@ -393,7 +575,10 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
                                 let arg = format_ident!("arg{}", input_len.to_string());
                                 input_args.push(arg);
 
-                                let ty = replace_self_with(&t.ty, &bp_ident.to_string());
+                                let ty = replace_generics_with(
+                                    &replace_self_with(&t.ty, &bp_ident.to_string()),
+                                    &generics,
+                                );
                                 input_types.push(ty);
 
                                 input_len += 1;
@@ -412,7 +597,10 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
 
                     let output = match &m.sig.output {
                         ReturnType::Default => parse_quote! { () },
-                        ReturnType::Type(_, t) => replace_self_with(t, &bp_ident.to_string()),
+                        ReturnType::Type(_, t) => replace_generics_with(
+                            &replace_self_with(t, &bp_ident.to_string()),
+                            &generics,
+                        ),
                     };
 
                     if mutable.is_none() {
@@ -452,7 +640,7 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
     }
 
     let output = quote! {
-        #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+        #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
         pub struct #bp_ident {
             address: ::scrypto::types::Address,
         }
@@ -495,6 +683,126 @@ fn generate_stubs(bp_ident: &Ident, items: &[ImplItem]) -> Result<TokenStream> {
     Ok(output)
 }
 
+// Returns the ident of the module which holds the blueprint state and implementation.
+fn blueprint_mod_ident(bp_ident: &Ident) -> Ident {
+    format_ident!("{}_blueprint", bp_ident)
+}
+
+// Returns who may call a method, as declared by its `#[access(...)]` attribute.
+fn method_access(m: &ImplItemMethod) -> Result<Option<TokenStream>> {
+    let attr = match m.attrs.iter().find(|a| a.path.is_ident("access")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    if !matches!(m.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+        return Err(Error::new(
+            attr.span(),
+            "`#[access]` is only supported on methods",
+        ));
+    }
+
+    let access = attr.parse_args::<Ident>()?;
+    match access.to_string().as_str() {
+        "package" => Ok(Some(quote! { ::scrypto::abi::Access::Package })),
+        "component" => Ok(Some(quote! { ::scrypto::abi::Access::Component })),
+        _ => Err(Error::new(
+            access.span(),
+            "Expected `#[access(package)]` or `#[access(component)]`",
+        )),
+    }
+}
+
+// Returns the key-value pairs declared by the `#[metadata(...)]` attributes of a blueprint.
+fn blueprint_metadata(attrs: &[Attribute]) -> Result<Vec<(String, LitStr)>> {
+    let mut metadata: Vec<(String, LitStr)> = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("metadata")) {
+        let pairs = attr.parse_args_with(
+            punctuated::Punctuated::<MetaNameValue, Token![,]>::parse_terminated,
+        )?;
+        for pair in pairs {
+            let key = match pair.path.get_ident() {
+                Some(ident) => ident.to_string(),
+                None => return Err(Error::new(pair.path.span(), "Expected a metadata key")),
+            };
+            let value = match pair.lit {
+                Lit::Str(s) => s,
+                lit => return Err(Error::new(lit.span(), "Expected a string value")),
+            };
+            if metadata.iter().any(|(k, _)| *k == key) {
+                return Err(Error::new(
+                    pair.path.span(),
+                    format!("Duplicate metadata key `{}`", key),
+                ));
+            }
+            metadata.push((key, value));
+        }
+    }
+    Ok(metadata)
+}
+
+// Returns the types, by name, declared by the `#[non_fungible_data(...)]` attributes of a
+// blueprint.
+fn blueprint_non_fungible_data(attrs: &[Attribute]) -> Result<Vec<(String, Path)>> {
+    let mut types: Vec<(String, Path)> = Vec::new();
+    for attr in attrs
+        .iter()
+        .filter(|a| a.path.is_ident("non_fungible_data"))
+    {
+        let paths =
+            attr.parse_args_with(punctuated::Punctuated::<Path, Token![,]>::parse_terminated)?;
+        for path in paths {
+            let name = match path.segments.last() {
+                Some(segment) => segment.ident.to_string(),
+                None => return Err(Error::new(path.span(), "Expected a type")),
+            };
+            if types.iter().any(|(n, _)| *n == name) {
+                return Err(Error::new(
+                    path.span(),
+                    format!("Duplicate non-fungible data `{}`", name),
+                ));
+            }
+            types.push((name, path));
+        }
+    }
+    Ok(types)
+}
+
+// Returns the indices of the fields holding the badges a method requires, as declared by its
+// `#[auth(...)]` attribute.
+fn method_auth(m: &ImplItemMethod, fields: &Fields) -> Result<Option<Vec<u32>>> {
+    let attr = match m.attrs.iter().find(|a| a.path.is_ident("auth")) {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    if !matches!(m.sig.inputs.first(), Some(FnArg::Receiver(_))) {
+        return Err(Error::new(
+            attr.span(),
+            "`#[auth]` is only supported on methods",
+        ));
+    }
+
+    let auth = attr.parse_args::<ast::Auth>()?;
+    let mut indices = Vec::new();
+    for rule in auth.allowed {
+        let ident = match rule.badge.get_ident() {
+            Some(ident) => ident,
+            None => return Err(Error::new(rule.badge.span(), "Only path value is allowed")),
+        };
+        match fields.iter().position(|f| f.ident.as_ref() == Some(ident)) {
+            Some(index) => indices.push(index as u32),
+            None => {
+                return Err(Error::new(
+                    ident.span(),
+                    format!("No field named `{}` to read the badge from", ident),
+                ))
+            }
+        }
+    }
+    Ok(Some(indices))
+}
+
 fn replace_self_with(t: &Type, name: &str) -> Type {
     match t {
         Type::Path(tp) => {
@@ -510,6 +818,94 @@ fn replace_self_with(t: &Type, name: &str) -> Type {
     }
 }
 
+// Returns the generic type parameters of a method.
+//
+// Only parameters bound by `NonFungibleData` are supported, as they can be monomorphized to
+// `RawNonFungibleData` in the dispatcher, ABI and stubs.
+fn generic_type_params(m: &ImplItemMethod) -> Result<Vec<Ident>> {
+    let is_non_fungible_data = |bound: &TypeParamBound| match bound {
+        TypeParamBound::Trait(t) => t
+            .path
+            .segments
+            .last()
+            .filter(|s| s.ident == "NonFungibleData")
+            .is_some(),
+        _ => false,
+    };
+
+    let mut params = vec![];
+    for param in &m.sig.generics.params {
+        match param {
+            GenericParam::Type(t) => {
+                let mut bounded = t.bounds.iter().any(is_non_fungible_data);
+                if let Some(w) = &m.sig.generics.where_clause {
+                    for predicate in &w.predicates {
+                        if let WherePredicate::Type(p) = predicate {
+                            if matches!(&p.bounded_ty, Type::Path(tp) if tp.path.is_ident(&t.ident))
+                            {
+                                bounded |= p.bounds.iter().any(is_non_fungible_data);
+                            }
+                        }
+                    }
+                }
+                if !bounded {
+                    return Err(Error::new(
+                        t.span(),
+                        "Generic type parameters must be bound by `NonFungibleData`",
+                    ));
+                }
+                params.push(t.ident.clone());
+            }
+            GenericParam::Lifetime(_) => {}
+            GenericParam::Const(c) => {
+                return Err(Error::new(
+                    c.span(),
+                    "Const generic parameters are not supported",
+                ));
+            }
+        }
+    }
+    Ok(params)
+}
+
+fn replace_generics_with(t: &Type, generics: &[Ident]) -> Type {
+    if generics.is_empty() {
+        return t.clone();
+    }
+
+    match t {
+        Type::Path(tp) => {
+            if tp.qself.is_none() && generics.iter().any(|g| tp.path.is_ident(g)) {
+                return parse_quote! { ::scrypto::resource::RawNonFungibleData };
+            }
+            let mut tp2 = tp.clone();
+            tp2.path.segments.iter_mut().for_each(|s| {
+                if let PathArguments::AngleBracketed(args) = &mut s.arguments {
+                    args.args.iter_mut().for_each(|a| {
+                        if let GenericArgument::Type(ty) = a {
+                            *ty = replace_generics_with(ty, generics);
+                        }
+                    });
+                }
+            });
+            Type::Path(tp2)
+        }
+        Type::Tuple(tt) => {
+            let mut tt2 = tt.clone();
+            tt2.elems
+                .iter_mut()
+                .for_each(|e| *e = replace_generics_with(e, generics));
+            Type::Tuple(tt2)
+        }
+        Type::Array(ta) => {
+            let mut ta2 = ta.clone();
+            *ta2.elem = replace_generics_with(&ta.elem, generics);
+            Type::Array(ta2)
+        }
+        _ => t.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proc_macro2::TokenStream;
@@ -528,6 +924,182 @@ mod tests {
         handle_blueprint(input).unwrap();
     }
 
+    #[test]
+    #[should_panic]
+    fn test_unbound_generic_method_should_fail() {
+        let input = TokenStream::from_str("struct A {} impl A { pub fn x<T>(t: T) {} }").unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    fn test_method_access() {
+        let input = TokenStream::from_str(
+            "struct A {} impl A { #[access(component)] pub fn x(&self) {} pub fn y(&self) {} }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output
+            .contains(&quote! { ("x".to_owned(), ::scrypto::abi::Access::Component) }.to_string()));
+        assert!(output.contains(&quote! { access: ::scrypto::abi::Access::Public, }.to_string()));
+        assert!(!output.contains(&quote! { #[access(component)] }.to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_function_access_should_fail() {
+        let input =
+            TokenStream::from_str("struct A {} impl A { #[access(package)] pub fn x() {} }")
+                .unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    fn test_method_auth() {
+        let input = TokenStream::from_str(
+            "struct A { a: Address, b: ResourceDef } impl A { #[auth(a, b(role = \"admin\"))] pub fn x(&self) {} pub fn y(&self) {} }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output
+            .contains(&quote! { ("x".to_owned(), ::scrypto::rust::vec![0u32, 1u32]) }.to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_method_auth_unknown_field_should_fail() {
+        let input = TokenStream::from_str(
+            "struct A { a: Address } impl A { #[auth(b)] pub fn x(&self) {} }",
+        )
+        .unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_function_auth_should_fail() {
+        let input =
+            TokenStream::from_str("struct A { a: Address } impl A { #[auth(a)] pub fn x() {} }")
+                .unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    fn test_reentrant_blueprint() {
+        let input = TokenStream::from_str("#[reentrant] struct A {} impl A { }").unwrap();
+        let output = handle_blueprint(input).unwrap();
+
+        assert!(output
+            .to_string()
+            .contains(&quote! { fn reentrant() -> bool { true } }.to_string()));
+    }
+
+    #[test]
+    fn test_metadata_blueprint() {
+        let input = TokenStream::from_str(
+            "#[metadata(version = \"1.0.0\", author = \"Radix\")] struct A {} impl A { }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains(
+            &quote! { ("version".to_owned(), "1.0.0".to_owned()), ("author".to_owned(), "Radix".to_owned()) }
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_non_fungible_data_blueprint() {
+        let input = TokenStream::from_str(
+            "#[non_fungible_data(Ticket, nft::Badge)] struct A {} impl A { }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains(&quote! { name: "Ticket".to_owned() }.to_string()));
+        assert!(output.contains(
+            &quote! { <nft::Badge as ::scrypto::resource::NonFungibleData>::mutable_data_schema() }
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_malformed_non_fungible_data_should_fail() {
+        for input in [
+            "#[non_fungible_data(\"Ticket\")] struct A {} impl A { }",
+            "#[non_fungible_data(Ticket, other::Ticket)] struct A {} impl A { }",
+        ] {
+            assert!(handle_blueprint(TokenStream::from_str(input).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_malformed_metadata_should_fail() {
+        for input in [
+            "#[metadata(version = 1)] struct A {} impl A { }",
+            "#[metadata(version)] struct A {} impl A { }",
+            "#[metadata(a = \"1\", a = \"2\")] struct A {} impl A { }",
+        ] {
+            assert!(handle_blueprint(TokenStream::from_str(input).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_invariant_blueprint() {
+        let input = TokenStream::from_str(
+            "#[invariant(self.a > 0)] struct A { a: u32 } impl A { pub fn x(&self) {} pub fn y(&mut self) {} }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains(&quote! { if !(self.a > 0) }.to_string()));
+        assert!(!output.contains(&quote! { state.__check_invariants("x"); }.to_string()));
+        assert!(output.contains(&quote! { state.__check_invariants("y"); }.to_string()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_malformed_invariant_should_fail() {
+        let input = TokenStream::from_str("#[invariant] struct A {} impl A { }").unwrap();
+        handle_blueprint(input).unwrap();
+    }
+
+    #[test]
+    fn test_dangling_bucket_warning() {
+        let input = TokenStream::from_str(
+            "struct A { v: Vault } impl A { pub fn x(&mut self, b: Bucket) -> Decimal { let c = self.v.take(1); c.amount() + b.amount() } }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains("__dangling_bucket_b"));
+        assert!(output.contains("__dangling_bucket_c"));
+    }
+
+    #[test]
+    fn test_handled_buckets_should_not_warn() {
+        let input = TokenStream::from_str(
+            "struct A { v: Vault, o: Option<u32> } impl A { pub fn x(&mut self, b: Bucket) -> Bucket { let c = self.v.take(1); let d = self.o.take(); self.v.put(b); c } }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(!output.contains("__dangling_bucket"));
+    }
+
+    #[test]
+    fn test_replace_generics() {
+        let generics = vec![format_ident!("T")];
+        let ty: Type = parse_quote! { (NonFungibleKey, Vec<T>) };
+        let replaced = replace_generics_with(&ty, &generics);
+
+        assert_code_eq(
+            quote! { #replaced },
+            quote! { (NonFungibleKey, Vec<::scrypto::resource::RawNonFungibleData>) },
+        );
+    }
+
     #[test]
     fn test_blueprint() {
         let input = TokenStream::from_str(
@@ -539,10 +1111,11 @@ mod tests {
         assert_code_eq(
             output,
             quote! {
-                mod blueprint {
+                #[allow(non_snake_case)]
+                mod Test_blueprint {
                     use super::*;
 
-                    #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+                    #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
                     pub struct Test {
                         a: u32,
                         admin: ResourceDef
@@ -559,6 +1132,10 @@ mod tests {
                         fn blueprint_name() -> &'static str {
                             "Test"
                         }
+                        fn method_auth() -> ::scrypto::rust::vec::Vec<(::scrypto::rust::string::String, ::scrypto::rust::vec::Vec<u32>)> {
+                            use ::scrypto::rust::borrow::ToOwned;
+                            ::scrypto::rust::vec![("x".to_owned(), ::scrypto::rust::vec![1u32])]
+                        }
                         fn instantiate(self) -> ::scrypto::core::Component {
                             ::scrypto::core::Component::new(self)
                         }
@@ -567,6 +1144,7 @@ mod tests {
                 #[no_mangle]
                 pub extern "C" fn Test_main() -> *mut u8 {
                     ::scrypto::utils::scrypto_setup_panic_hook();
+                    ::scrypto::utils::scrypto_catch_panic(|| {
                     let calldata: ::scrypto::engine::GetCallDataOutput = ::scrypto::engine::call_engine(
                         ::scrypto::engine::GET_CALL_DATA,
                         ::scrypto::engine::GetCallDataInput {},
@@ -582,36 +1160,43 @@ mod tests {
                             let auth = ::scrypto::utils::scrypto_unwrap(
                                 ::scrypto::buffer::scrypto_decode::<::scrypto::resource::BucketRef>(&calldata.args[1usize])
                             );
-                            let state: blueprint::Test = arg0.get_state();
-                            rtn = ::scrypto::buffer::scrypto_encode_for_radix_engine(&blueprint::Test::x(&state, auth));
+                            let state: Test_blueprint::Test = arg0.get_state();
+                            rtn = ::scrypto::buffer::scrypto_encode_for_radix_engine(&Test_blueprint::Test::x(&state, auth));
                         }
                         _ => {
                             panic!("Function/method not fund")
                         }
                     }
                     ::scrypto::buffer::scrypto_wrap(rtn)
+                    })
                 }
                 #[no_mangle]
                 pub extern "C" fn Test_abi() -> *mut u8 {
                     use ::sbor::Describe;
                     use ::scrypto::abi::{Function, Method};
                     use ::scrypto::rust::borrow::ToOwned;
+                    use ::scrypto::rust::collections::BTreeMap;
+                    use ::scrypto::rust::string::String;
                     use ::scrypto::rust::vec;
                     use ::scrypto::rust::vec::Vec;
                     let functions: Vec<Function> = vec![];
                     let methods: Vec<Method> = vec![::scrypto::abi::Method {
                         name: "x".to_owned(),
                         mutability: ::scrypto::abi::Mutability::Immutable,
+                        access: ::scrypto::abi::Access::Public,
                         inputs: vec![
                             <::scrypto::resource::BucketRef>::describe()
                         ],
                         output: <u32>::describe(),
                     }];
-                    let output = (functions, methods);
+                    let state = <Test_blueprint::Test>::describe();
+                    let metadata: BTreeMap<String, String> = vec![].into_iter().collect();
+                    let non_fungible_data: Vec<::scrypto::abi::NonFungibleData> = vec![];
+                    let output = (functions, methods, state, metadata, non_fungible_data);
                     let output_bytes = ::scrypto::buffer::scrypto_encode_for_radix_engine(&output);
                     ::scrypto::buffer::scrypto_wrap(output_bytes)
                 }
-                #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+                #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
                 pub struct Test {
                     address: ::scrypto::types::Address,
                 }
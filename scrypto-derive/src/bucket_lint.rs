@@ -0,0 +1,186 @@
+use proc_macro2::{Delimiter, Ident, Spacing, TokenTree};
+use quote::format_ident;
+use syn::*;
+
+/// Bucket methods which leave the bucket in place.
+const BUCKET_ACCESSORS: [&str; 16] = [
+    "amount",
+    "resource_def",
+    "resource_address",
+    "is_empty",
+    "put",
+    "take",
+    "take_non_fungible",
+    "present",
+    "authorize",
+    "get_non_fungible_key",
+    "get_non_fungible_keys",
+    "get_non_fungible_address",
+    "get_non_fungibles",
+    "non_fungibles_with_data",
+    "get_non_fungible_data",
+    "update_non_fungible_data",
+];
+
+/// Methods of vaults, buckets and resource definitions which return a new bucket, and
+/// whether they take arguments; `Option::take()` doesn't.
+const BUCKET_PRODUCERS: [(&str, bool); 6] = [
+    ("take", true),
+    ("take_all", false),
+    ("take_non_fungible", true),
+    ("take_non_fungibles", true),
+    ("mint", true),
+    ("mint_non_fungible", true),
+];
+
+/// Finds buckets which a method neither returns, passes on nor burns, and returns statements
+/// raising a compile-time warning for each.
+///
+/// This is best-effort: buckets are recognized by the parameter or `let` type, or by the
+/// method creating them, and any use other than calling a method which leaves the bucket in
+/// place counts as handling it. Bucket refs aren't checked, as the engine drops them when
+/// the call returns. The warnings can be silenced with `#[allow(deprecated)]`.
+pub fn dangling_bucket_warnings(m: &ImplItemMethod) -> Vec<Stmt> {
+    let body: Vec<TokenTree> = m
+        .block
+        .stmts
+        .iter()
+        .flat_map(|s| quote::quote! { #s })
+        .collect();
+
+    let mut dangling = Vec::new();
+    for input in &m.sig.inputs {
+        if let FnArg::Typed(PatType { pat, ty, .. }) = input {
+            if let Pat::Ident(PatIdent { ident, .. }) = pat.as_ref() {
+                if is_bucket_type(ty) && !is_handled(ident, &body) {
+                    dangling.push(ident.clone());
+                }
+            }
+        }
+    }
+    find_dangling_lets(&body, &mut dangling);
+
+    dangling
+        .iter()
+        .map(|ident| {
+            let name = format_ident!("__dangling_bucket_{}", ident);
+            let note = format!(
+                "bucket `{}` is neither returned, passed on nor burnt by the end of this method",
+                ident
+            );
+            // Calling a deprecated function is the only way for a macro to raise a warning
+            parse_quote_spanned! {ident.span()=>
+                {
+                    #[deprecated(note = #note)]
+                    fn #name() {}
+                    #name();
+                }
+            }
+        })
+        .collect()
+}
+
+fn is_bucket_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(p) => matches!(
+            p.path.segments.last(),
+            Some(s) if s.ident == "Bucket" && matches!(s.arguments, PathArguments::None)
+        ),
+        _ => false,
+    }
+}
+
+/// Looks for `let` bindings of buckets, at any depth, which aren't handled in the rest of
+/// their block.
+fn find_dangling_lets(tokens: &[TokenTree], dangling: &mut Vec<Ident>) {
+    for (i, tt) in tokens.iter().enumerate() {
+        match tt {
+            TokenTree::Ident(ident) if ident == "let" => {
+                let mut j = i + 1;
+                if matches!(tokens.get(j), Some(TokenTree::Ident(m)) if m == "mut") {
+                    j += 1;
+                }
+                let name = match tokens.get(j) {
+                    Some(TokenTree::Ident(name)) => name,
+                    _ => continue,
+                };
+                let end = tokens[j..]
+                    .iter()
+                    .position(|t| is_punct(t, ';'))
+                    .map_or(tokens.len(), |p| j + p);
+                if declares_bucket(&tokens[j + 1..end]) && !is_handled(name, &tokens[end..]) {
+                    dangling.push(name.clone());
+                }
+            }
+            TokenTree::Group(group) => {
+                let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+                find_dangling_lets(&inner, dangling);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Checks whether the tokens following the name in a `let` declare a bucket.
+fn declares_bucket(tokens: &[TokenTree]) -> bool {
+    let eq = tokens.iter().position(|t| is_punct(t, '='));
+    // An explicit type, e.g. `let b: Bucket = ...`
+    if let Some(TokenTree::Punct(p)) = tokens.first() {
+        if p.as_char() == ':' && p.spacing() == Spacing::Alone {
+            let ty = &tokens[1..eq.unwrap_or(tokens.len())];
+            return matches!(ty.last(), Some(TokenTree::Ident(i)) if i == "Bucket");
+        }
+    }
+
+    // Otherwise, an initializer ending with a call which returns a bucket
+    let init = match eq {
+        Some(eq) => &tokens[eq + 1..],
+        None => return false,
+    };
+    match init {
+        [.., TokenTree::Ident(ty), TokenTree::Punct(c1), TokenTree::Punct(c2), TokenTree::Ident(f), TokenTree::Group(args)]
+            if ty == "Bucket"
+                && c1.as_char() == ':'
+                && c2.as_char() == ':'
+                && f == "new"
+                && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            true
+        }
+        [.., TokenTree::Punct(dot), TokenTree::Ident(f), TokenTree::Group(args)]
+            if dot.as_char() == '.' && args.delimiter() == Delimiter::Parenthesis =>
+        {
+            let has_args = !args.stream().is_empty();
+            BUCKET_PRODUCERS
+                .iter()
+                .any(|(name, with_args)| f == name && *with_args == has_args)
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether any mention of the bucket, at any depth, may consume it.
+fn is_handled(name: &Ident, tokens: &[TokenTree]) -> bool {
+    tokens.iter().enumerate().any(|(i, tt)| match tt {
+        TokenTree::Ident(ident) if ident == name => {
+            let is_field = i > 0 && is_punct(&tokens[i - 1], '.');
+            let is_accessor = matches!(
+                &tokens[i + 1..],
+                [TokenTree::Punct(dot), TokenTree::Ident(f), TokenTree::Group(args), ..]
+                    if dot.as_char() == '.'
+                        && args.delimiter() == Delimiter::Parenthesis
+                        && BUCKET_ACCESSORS.iter().any(|a| f == a)
+            );
+            !is_field && !is_accessor
+        }
+        TokenTree::Group(group) => {
+            let inner: Vec<TokenTree> = group.stream().into_iter().collect();
+            is_handled(name, &inner)
+        }
+        _ => false,
+    })
+}
+
+fn is_punct(tt: &TokenTree, c: char) -> bool {
+    matches!(tt, TokenTree::Punct(p) if p.as_char() == c)
+}
@@ -0,0 +1,203 @@
+use proc_macro2::TokenStream;
+use syn::spanned::Spanned;
+use syn::*;
+
+use crate::ast;
+use crate::import::generate_stub;
+
+macro_rules! trace {
+    ($($arg:expr),*) => {{
+        #[cfg(feature = "trace")]
+        println!($($arg),*);
+    }};
+}
+
+pub fn handle_external_blueprint(input: TokenStream) -> Result<TokenStream> {
+    trace!("Started processing external_blueprint macro");
+
+    let bp = parse2::<ast::ExternalBlueprint>(input)?;
+    let package = &bp.package;
+    let ident = &bp.name;
+    let name = ident.to_string();
+    trace!("Blueprint name: {}", name);
+
+    let mut functions = Vec::<ItemFn>::new();
+    let mut methods = Vec::<ItemFn>::new();
+    for item in &bp.items {
+        trace!("Processing item: {}", item.sig.ident);
+
+        if let Some(default) = &item.default {
+            return Err(Error::new(
+                default.span(),
+                "External blueprint functions can't have a body",
+            ));
+        }
+        let sig = &item.sig;
+        if !sig.generics.params.is_empty() {
+            return Err(Error::new(
+                sig.generics.span(),
+                "External blueprint functions can't be generic",
+            ));
+        }
+
+        let fn_name = sig.ident.to_string();
+        let fn_ident = &sig.ident;
+        let fn_output = match &sig.output {
+            ReturnType::Default => parse_quote! { () },
+            ReturnType::Type(_, t) => t.as_ref().clone(),
+        };
+        let mut fn_args = Vec::<Ident>::new();
+        let mut fn_types = Vec::<Type>::new();
+        let mut is_method = false;
+        for input in &sig.inputs {
+            match input {
+                FnArg::Receiver(_) => {
+                    is_method = true;
+                }
+                FnArg::Typed(t) => match t.pat.as_ref() {
+                    Pat::Ident(p) if p.by_ref.is_none() && p.subpat.is_none() => {
+                        fn_args.push(p.ident.clone());
+                        fn_types.push(t.ty.as_ref().clone());
+                    }
+                    _ => {
+                        return Err(Error::new(t.pat.span(), "Expected an argument name"));
+                    }
+                },
+            }
+        }
+
+        if is_method {
+            methods.push(parse_quote! {
+                pub fn #fn_ident(&self #(, #fn_args: #fn_types)*) -> #fn_output {
+                    let rtn = ::scrypto::core::call_method(
+                        self.address,
+                        #fn_name,
+                        ::scrypto::args!(#(#fn_args),*)
+                    );
+                    ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                }
+            });
+        } else {
+            functions.push(parse_quote! {
+                pub fn #fn_ident(#(#fn_args: #fn_types),*) -> #fn_output {
+                    let package = ::scrypto::utils::scrypto_unwrap(
+                        ::scrypto::types::Address::from_str(#package)
+                    );
+                    let rtn = ::scrypto::core::call_function(
+                        package,
+                        #name,
+                        #fn_name,
+                        ::scrypto::args!(#(#fn_args),*)
+                    );
+                    ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                }
+            });
+        }
+    }
+
+    let output = generate_stub(ident, functions, methods);
+    trace!("Finished processing external_blueprint macro");
+
+    #[cfg(feature = "trace")]
+    crate::utils::print_generated_code("external_blueprint!", &output);
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::TokenStream;
+    use quote::quote;
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn assert_code_eq(a: TokenStream, b: TokenStream) {
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn test_external_blueprint() {
+        let input = TokenStream::from_str(
+            r#"
+                "056967d3d49213394892980af59be76e9b3e7cc4cb78237460d0c7",
+                Simple {
+                    fn new(price: Decimal) -> Component;
+                    fn get_price(&self) -> Decimal;
+                    fn buy(&mut self, payment: Bucket, count: u32) -> Bucket;
+                    fn reset(&mut self);
+                }
+            "#,
+        )
+        .unwrap();
+        let output = handle_external_blueprint(input).unwrap();
+
+        assert_code_eq(
+            output,
+            quote! {
+                #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
+                pub struct Simple {
+                    address: ::scrypto::types::Address,
+                }
+                impl Simple {
+                    pub fn new(price: Decimal) -> Component {
+                        let package = ::scrypto::utils::scrypto_unwrap(::scrypto::types::Address::from_str(
+                            "056967d3d49213394892980af59be76e9b3e7cc4cb78237460d0c7"
+                        ));
+                        let rtn = ::scrypto::core::call_function(package, "Simple", "new", ::scrypto::args!(price));
+                        ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                    }
+                    pub fn get_price(&self) -> Decimal {
+                        let rtn = ::scrypto::core::call_method(self.address, "get_price", ::scrypto::args!());
+                        ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                    }
+                    pub fn buy(&self, payment: Bucket, count: u32) -> Bucket {
+                        let rtn = ::scrypto::core::call_method(self.address, "buy", ::scrypto::args!(payment, count));
+                        ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                    }
+                    pub fn reset(&self) -> () {
+                        let rtn = ::scrypto::core::call_method(self.address, "reset", ::scrypto::args!());
+                        ::scrypto::utils::scrypto_unwrap(::scrypto::buffer::scrypto_decode(&rtn))
+                    }
+                }
+                impl From<::scrypto::types::Address> for Simple {
+                    fn from(address: ::scrypto::types::Address) -> Self {
+                        Self { address }
+                    }
+                }
+                impl From<Simple> for ::scrypto::types::Address {
+                    fn from(a: Simple) -> ::scrypto::types::Address {
+                        a.address
+                    }
+                }
+                impl From<::scrypto::core::Component> for Simple {
+                    fn from(component: ::scrypto::core::Component) -> Self {
+                        Self {
+                            address: component.into()
+                        }
+                    }
+                }
+                impl From<Simple> for ::scrypto::core::Component {
+                    fn from(a: Simple) -> ::scrypto::core::Component {
+                        a.address.into()
+                    }
+                }
+            },
+        );
+    }
+
+    #[test]
+    fn test_external_blueprint_with_body_should_fail() {
+        let input = TokenStream::from_str(
+            r#"
+                "056967d3d49213394892980af59be76e9b3e7cc4cb78237460d0c7",
+                Simple {
+                    fn get_price(&self) -> Decimal { Decimal::zero() }
+                }
+            "#,
+        )
+        .unwrap();
+
+        assert!(handle_external_blueprint(input).is_err());
+    }
+}
@@ -24,8 +24,8 @@ pub fn handle_import(input: TokenStream) -> Result<TokenStream> {
     };
     trace!("Parsed ABI: {:?}", blueprint);
 
-    let package = blueprint.package;
-    let name = blueprint.name;
+    let package = blueprint.package.clone();
+    let name = blueprint.name.clone();
     let ident = format_ident!("{}", name);
     trace!("Blueprint name: {}", name);
 
@@ -98,10 +98,47 @@ pub fn handle_import(input: TokenStream) -> Result<TokenStream> {
         methods.push(m);
     }
 
+    // The engine checks the imported blueprint against this ABI when the package is published
+    let dependency_ident = format_ident!("{}_dependency", name);
+    let dependency_bytes = encode_dependency(&blueprint);
+    let dependency = quote! {
+        #[no_mangle]
+        pub extern "C" fn #dependency_ident() -> *mut u8 {
+            let output_bytes = [#(#dependency_bytes),*].to_vec();
+            ::scrypto::buffer::scrypto_wrap(output_bytes)
+        }
+    };
+
+    let stub = generate_stub(&ident, functions, methods);
     let output = quote! {
         #(#structs)*
 
-        #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+        #stub
+
+        #dependency
+    };
+    trace!("Finished processing import macro");
+
+    #[cfg(feature = "trace")]
+    crate::utils::print_generated_code("import!", &output);
+
+    Ok(output)
+}
+
+/// Encodes the ABI of an imported blueprint as returned to the engine, i.e. prefixed with its
+/// length.
+fn encode_dependency(blueprint: &abi::Blueprint) -> Vec<u8> {
+    let mut bytes = sbor::encode_with_type(vec![0u8; 4], blueprint);
+    let len = (bytes.len() - 4) as u32;
+    bytes[0..4].copy_from_slice(&len.to_le_bytes());
+    bytes
+}
+
+/// Generates a stub struct which holds the address of a component, with the given functions
+/// and methods as its associated items.
+pub fn generate_stub(ident: &Ident, functions: Vec<ItemFn>, methods: Vec<ItemFn>) -> TokenStream {
+    quote! {
+        #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
         pub struct #ident {
             address: ::scrypto::types::Address,
         }
@@ -139,13 +176,7 @@ pub fn handle_import(input: TokenStream) -> Result<TokenStream> {
                 a.address.into()
             }
         }
-    };
-    trace!("Finished processing import macro");
-
-    #[cfg(feature = "trace")]
-    crate::utils::print_generated_code("import!", &output);
-
-    Ok(output)
+    }
 }
 
 fn get_native_type(ty: &des::Type) -> Result<(Type, Vec<Item>)> {
@@ -410,12 +441,15 @@ mod tests {
             "###,
         )
         .unwrap();
-        let output = handle_import(input).unwrap();
+        let output = handle_import(input.clone()).unwrap();
+        let blueprint: abi::Blueprint =
+            serde_json::from_str(&parse2::<LitStr>(input).unwrap().value()).unwrap();
+        let dependency_bytes = encode_dependency(&blueprint);
 
         assert_code_eq(
             output,
             quote! {
-                #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode)]
+                #[derive(::sbor::TypeId, ::sbor::Encode, ::sbor::Decode, ::sbor::Describe)]
                 pub struct Simple {
                     address: ::scrypto::types::Address,
                 }
@@ -454,6 +488,11 @@ mod tests {
                         a.address.into()
                     }
                 }
+                #[no_mangle]
+                pub extern "C" fn Simple_dependency() -> *mut u8 {
+                    let output_bytes = [#(#dependency_bytes),*].to_vec();
+                    ::scrypto::buffer::scrypto_wrap(output_bytes)
+                }
             },
         );
     }
@@ -1,12 +1,17 @@
+mod address;
 mod ast;
 mod auth;
 mod blueprint;
+mod bucket_lint;
+mod external_blueprint;
 mod import;
 mod non_fungible_data;
 mod utils;
 
 use proc_macro::TokenStream;
 
+use address::AddressKind;
+
 /// Declares a blueprint.
 ///
 /// The `blueprint!` macro is a convenient way to define a new blueprint. It takes
@@ -17,6 +22,43 @@ use proc_macro::TokenStream;
 /// This macro will derive the dispatcher method responsible for handling invocation
 /// according to Scrypto ABI.
 ///
+/// The ABI also describes the struct, so that tools can decode the component state with
+/// field names; the types of its fields must therefore implement `Describe`.
+///
+/// Methods may be generic over types bound by `NonFungibleData`, in which case they are
+/// exported with the type replaced by `RawNonFungibleData`.
+///
+/// By default, the engine rejects calls into a component while one of its methods is
+/// running. Annotate the struct with `#[reentrant]` to allow them; the component state
+/// should then be written back before calling out.
+///
+/// Methods annotated with `#[access(component)]` may only be called by the component itself,
+/// e.g. as callbacks, and those annotated with `#[access(package)]` only by blueprints and
+/// components of the same package. The restriction is enforced by the engine and recorded
+/// in the ABI.
+///
+/// Methods annotated with `#[auth(badge_field, ...)]` may only be called with a bucket ref
+/// to any of the badges held in the given fields, which must be `Address`, `ResourceDef` or
+/// `NonFungibleAddress`, among the arguments. The engine checks it before the method runs,
+/// against the badges the fields hold in the current state of the component.
+///
+/// The struct may also carry `#[invariant(condition)]` attributes. Each condition is checked
+/// after every method taking `&mut self`, and a violation aborts the call with a message
+/// naming the condition and the method. Checks are compiled in debug builds only, unless the
+/// `invariants` feature of `scrypto` is enabled.
+///
+/// The struct may be described with `#[metadata(key = "value", ...)]`, e.g. its version,
+/// author, description and links. The pairs are exported in the ABI and stored with the
+/// package when published.
+///
+/// The types of the non-fungibles a blueprint mints may be listed with
+/// `#[non_fungible_data(Type, ...)]`, so that the schemas of their immutable and mutable data
+/// are exported in the ABI, e.g. for wallets and explorers to display them.
+///
+/// A method which may leave a `Bucket` neither returned, passed on nor burnt, and so fail at
+/// runtime with a dangling bucket, raises a deprecation warning naming the bucket. The check
+/// is best-effort, and can be silenced with `#[allow(deprecated)]` on the method.
+///
 /// # Example
 /// ```ignore
 /// use scrypto::prelude::*;
@@ -53,6 +95,9 @@ pub fn blueprint(input: TokenStream) -> TokenStream {
 /// This macro will generate stubs for accessing the blueprint according to
 /// its ABI specification.
 ///
+/// The ABI is also exported as a dependency of the package, so that publishing
+/// fails if the blueprint doesn't exist or its functions and methods don't match.
+///
 /// # Example
 /// ```ignore
 /// use scrypto::prelude::*;
@@ -99,12 +144,40 @@ pub fn import(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Declares the functions and methods of a blueprint in another package.
+///
+/// Unlike `import!`, the signatures are written in Rust and the types are resolved in the
+/// calling scope. A stub struct is generated, which wraps the address of a component and
+/// exposes typed functions and methods.
+///
+/// # Example
+/// ```ignore
+/// use scrypto::prelude::*;
+///
+/// external_blueprint! {
+///     "01a405d3129b61e86c51c3168d553d2ffd7a3f0bd2f66b5a3e9876",
+///     GumballMachine {
+///         fn new(price: Decimal) -> Component;
+///         fn get_gumball(&mut self, payment: Bucket) -> Bucket;
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn external_blueprint(input: TokenStream) -> TokenStream {
+    external_blueprint::handle_external_blueprint(proc_macro2::TokenStream::from(input))
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Defines the authorization rule for a method.
 ///
 /// A list of component fields of type `ResourceDef` or `Address` should be provided.
 ///
 /// Caller must provide a bucket ref containing any of the associated resource.
 ///
+/// A field may be followed by `key = "value"` pairs, in which case the metadata of the
+/// badge resource must also match, as checked by the engine.
+///
 /// # Example
 /// ```ignore
 /// #[auth(admin, user)]
@@ -113,6 +186,11 @@ pub fn import(input: TokenStream) -> TokenStream {
 ///
 ///     // To retrieve auth resource address, use `auth.resource_address()`.
 /// }
+///
+/// #[auth(staff(role = "admin"))]
+/// pub fn admin_method(&self) {
+///     // Only a `staff` badge whose metadata has `role=admin` is accepted
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn auth(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -126,6 +204,10 @@ pub fn auth(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Derive code that describe a non-fungible data structure.
 ///
+/// Fields annotated with `#[mutable]`, or `#[scrypto(mutable)]`, make up the mutable data,
+/// which can be updated after minting; all other fields make up the immutable data. Both
+/// parts are described by a schema, which blueprints can export in their ABI.
+///
 /// # Example
 ///
 /// ```ignore
@@ -134,13 +216,68 @@ pub fn auth(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// #[derive(NonFungibleData)]
 /// pub struct AwesomeNonFungible {
 ///     pub field_1: u32,
-///     #[scrypto(mutable)]
+///     #[mutable]
 ///     pub field_2: String,
 /// }
 /// ```
-#[proc_macro_derive(NonFungibleData, attributes(scrypto))]
+#[proc_macro_derive(NonFungibleData, attributes(scrypto, mutable))]
 pub fn non_fungible_data(input: TokenStream) -> TokenStream {
     non_fungible_data::handle_non_fungible_data(proc_macro2::TokenStream::from(input))
         .unwrap_or_else(|err| err.to_compile_error())
         .into()
 }
+
+/// Creates a `Package` from a hex-encoded package address.
+///
+/// The address is validated at compile time, so a malformed literal, or one which isn't
+/// a package address, fails the build instead of panicking at runtime.
+///
+/// # Example
+/// ```ignore
+/// use scrypto::prelude::*;
+///
+/// let package: Package = package!("01a405d3129b61e86c51c3168d553d2ffd7a3f0bd2f66b5a3e9876");
+/// ```
+#[proc_macro]
+pub fn package(input: TokenStream) -> TokenStream {
+    address::handle_address(proc_macro2::TokenStream::from(input), AddressKind::Package)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Creates a `Component` from a hex-encoded component address, validated at compile time.
+///
+/// # Example
+/// ```ignore
+/// use scrypto::prelude::*;
+///
+/// let component: Component = component!("02b61acea4378e307342b2b684fc35acf0238a4accb9f91e8a4364");
+/// ```
+#[proc_macro]
+pub fn component(input: TokenStream) -> TokenStream {
+    address::handle_address(
+        proc_macro2::TokenStream::from(input),
+        AddressKind::Component,
+    )
+    .unwrap_or_else(|err| err.to_compile_error())
+    .into()
+}
+
+/// Creates a `ResourceDef` from a hex-encoded resource definition address, validated at
+/// compile time.
+///
+/// # Example
+/// ```ignore
+/// use scrypto::prelude::*;
+///
+/// let xrd: ResourceDef = resource_def!("030000000000000000000000000000000000000000000000000004");
+/// ```
+#[proc_macro]
+pub fn resource_def(input: TokenStream) -> TokenStream {
+    address::handle_address(
+        proc_macro2::TokenStream::from(input),
+        AddressKind::ResourceDef,
+    )
+    .unwrap_or_else(|err| err.to_compile_error())
+    .into()
+}
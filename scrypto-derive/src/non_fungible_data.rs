@@ -1,5 +1,6 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
+use syn::spanned::Spanned;
 use syn::*;
 
 macro_rules! trace {
@@ -9,19 +10,31 @@ macro_rules! trace {
     }};
 }
 
-fn is_mutable(f: &syn::Field) -> bool {
+// Returns whether a field is annotated with `#[mutable]`, or `#[scrypto(mutable)]`.
+fn is_mutable(f: &syn::Field) -> Result<bool> {
     let mut mutable = false;
     for att in &f.attrs {
-        if att.path.is_ident("scrypto")
-            && att
-                .parse_args::<syn::Path>()
+        let valid = if att.path.is_ident("mutable") {
+            att.tokens.is_empty()
+        } else if att.path.is_ident("scrypto") {
+            att.parse_args::<syn::Path>()
                 .map(|p| p.is_ident("mutable"))
                 .unwrap_or(false)
-        {
-            mutable = true;
+        } else {
+            continue;
+        };
+        if !valid {
+            return Err(Error::new(
+                att.span(),
+                "Expected `#[mutable]` or `#[scrypto(mutable)]`",
+            ));
         }
+        if mutable {
+            return Err(Error::new(att.span(), "Field is already marked as mutable"));
+        }
+        mutable = true;
     }
-    mutable
+    Ok(mutable)
 }
 
 pub fn handle_non_fungible_data(input: TokenStream) -> Result<TokenStream> {
@@ -34,8 +47,17 @@ pub fn handle_non_fungible_data(input: TokenStream) -> Result<TokenStream> {
     let output = match data {
         Data::Struct(s) => match s.fields {
             syn::Fields::Named(FieldsNamed { named, .. }) => {
+                let mut im: Vec<&Field> = Vec::new();
+                let mut m: Vec<&Field> = Vec::new();
+                for f in &named {
+                    if is_mutable(f)? {
+                        m.push(f);
+                    } else {
+                        im.push(f);
+                    }
+                }
+
                 // immutable
-                let im: Vec<&Field> = named.iter().filter(|f| !is_mutable(f)).collect();
                 let im_n = Index::from(im.len());
                 let im_ids = im.iter().map(|f| &f.ident);
                 let im_ids2 = im_ids.clone();
@@ -45,7 +67,6 @@ pub fn handle_non_fungible_data(input: TokenStream) -> Result<TokenStream> {
                     .iter()
                     .map(|f| f.ident.clone().expect("Illegal State!").to_string());
                 // mutable
-                let m: Vec<&Field> = named.iter().filter(|f| is_mutable(f)).collect();
                 let m_n = Index::from(m.len());
                 let m_ids = m.iter().map(|f| &f.ident);
                 let m_ids2 = m_ids.clone();
@@ -260,4 +281,32 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn test_mutable_shorthand() {
+        let input = TokenStream::from_str(
+            "pub struct AwesomeNonFungibleData { pub field_1: u32, #[mutable] pub field_2: String, }",
+        )
+        .unwrap();
+        let expected = TokenStream::from_str(
+            "pub struct AwesomeNonFungibleData { pub field_1: u32, #[scrypto(mutable)] pub field_2: String, }",
+        )
+        .unwrap();
+
+        assert_code_eq(
+            handle_non_fungible_data(input).unwrap(),
+            handle_non_fungible_data(expected).unwrap(),
+        );
+    }
+
+    #[test]
+    fn test_malformed_mutable_should_fail() {
+        for input in [
+            "struct A { #[mutable(true)] a: u32 }",
+            "struct A { #[scrypto(mutabel)] a: u32 }",
+            "struct A { #[mutable] #[scrypto(mutable)] a: u32 }",
+        ] {
+            assert!(handle_non_fungible_data(TokenStream::from_str(input).unwrap()).is_err());
+        }
+    }
 }
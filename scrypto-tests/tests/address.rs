@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scrypto::core::{Component, Package};
+use scrypto::resource::ResourceDef;
+use scrypto::types::*;
+use scrypto::{component, package, resource_def};
+
+#[test]
+fn test_address_macros() {
+    let package: Package = package!("010000000000000000000000000000000000000000000000000001");
+    let component: Component = component!("020000000000000000000000000000000000000000000000000002");
+    let resource_def: ResourceDef =
+        resource_def!("030000000000000000000000000000000000000000000000000004");
+
+    assert_eq!(package.address(), SYSTEM_PACKAGE);
+    assert_eq!(component.address(), SYSTEM_COMPONENT);
+    assert_eq!(resource_def.address(), RADIX_TOKEN);
+}
@@ -1,11 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use sbor::describe::Type;
 use scrypto::abi;
 use scrypto::buffer::*;
 use scrypto::prelude::*;
 use serde::Serialize;
 use serde_json::{json, to_value, Value};
 
+/// The output of an ABI generator.
+type Abi = (
+    Vec<abi::Function>,
+    Vec<abi::Method>,
+    Type,
+    BTreeMap<String, String>,
+    Vec<abi::NonFungibleData>,
+);
+
 blueprint! {
     struct SimpleAuth {
         admin: ResourceDef,
@@ -51,11 +61,13 @@ fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
 #[test]
 fn test_simple_auth() {
     let ptr = SimpleAuth_abi();
-    let abi: (Vec<abi::Function>, Vec<abi::Method>) =
+    let (functions, methods, state, metadata, non_fungible_data): Abi =
         unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+    assert!(metadata.is_empty());
+    assert!(non_fungible_data.is_empty());
 
     assert_json_eq(
-        abi,
+        (functions, methods, state),
         json!([
             [
                 {
@@ -83,6 +95,7 @@ fn test_simple_auth() {
                 {
                     "name": "pump",
                     "mutability": "Mutable",
+                    "access": "Public",
                     "inputs": [
                         {
                             "type": "Custom",
@@ -102,6 +115,7 @@ fn test_simple_auth() {
                 {
                     "name": "airdrop",
                     "mutability": "Mutable",
+                    "access": "Public",
                     "inputs": [
                         {
                             "type": "Custom",
@@ -118,6 +132,7 @@ fn test_simple_auth() {
                 {
                     "name": "airdrop_mut",
                     "mutability": "Mutable",
+                    "access": "Public",
                     "inputs": [
                         {
                             "type": "Custom",
@@ -131,7 +146,40 @@ fn test_simple_auth() {
                         "generics": []
                     }
                 }
-            ]
+            ],
+            {
+                "type": "Struct",
+                "name": "SimpleAuth",
+                "fields": {
+                    "type": "Named",
+                    "named": [
+                        [
+                            "admin",
+                            {
+                                "type": "Custom",
+                                "name": "scrypto::resource::ResourceDef",
+                                "generics": []
+                            }
+                        ],
+                        [
+                            "user",
+                            {
+                                "type": "Custom",
+                                "name": "scrypto::types::Address",
+                                "generics": []
+                            }
+                        ],
+                        [
+                            "reserves",
+                            {
+                                "type": "Custom",
+                                "name": "scrypto::resource::Vault",
+                                "generics": []
+                            }
+                        ]
+                    ]
+                }
+            }
         ]),
     );
 }
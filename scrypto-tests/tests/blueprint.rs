@@ -1,11 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use sbor::describe::Type;
 use scrypto::abi;
 use scrypto::buffer::*;
 use scrypto::prelude::*;
 use serde::Serialize;
 use serde_json::{json, to_value, Value};
 
+/// The output of an ABI generator.
+type Abi = (
+    Vec<abi::Function>,
+    Vec<abi::Method>,
+    Type,
+    BTreeMap<String, String>,
+    Vec<abi::NonFungibleData>,
+);
+
 blueprint! {
     struct Simple {
         state: u32,
@@ -26,7 +36,7 @@ blueprint! {
             self.state = new_state;
         }
 
-        pub fn custom_types_1() -> (Decimal, Address, H256, Bid, Rid, Mid, Vid) {
+        pub fn custom_types_1() -> (Decimal, Address, H256, BucketId, Rid, Mid, VaultId) {
             todo!()
         }
 
@@ -47,11 +57,13 @@ fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
 #[test]
 fn test_simple_abi() {
     let ptr = Simple_abi();
-    let abi: (Vec<abi::Function>, Vec<abi::Method>) =
+    let (functions, methods, state, metadata, non_fungible_data): Abi =
         unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+    assert!(metadata.is_empty());
+    assert!(non_fungible_data.is_empty());
 
     assert_json_eq(
-        abi,
+        (functions, methods, state),
         json!([
             [
                 {
@@ -86,7 +98,7 @@ fn test_simple_abi() {
                             },
                             {
                                 "type": "Custom",
-                                "name": "scrypto::types::Bid",
+                                "name": "scrypto::types::BucketId",
                                 "generics": []
                             },
                             {
@@ -101,7 +113,7 @@ fn test_simple_abi() {
                             },
                             {
                                 "type": "Custom",
-                                "name": "scrypto::types::Vid",
+                                "name": "scrypto::types::VaultId",
                                 "generics": []
                             }
                         ]
@@ -172,6 +184,7 @@ fn test_simple_abi() {
                 {
                     "name": "get_state",
                     "mutability": "Immutable",
+                    "access": "Public",
                     "inputs": [],
                     "output": {
                         "type": "U32"
@@ -180,6 +193,7 @@ fn test_simple_abi() {
                 {
                     "name": "set_state",
                     "mutability": "Mutable",
+                    "access": "Public",
                     "inputs": [
                         {
                             "type": "U32"
@@ -189,7 +203,22 @@ fn test_simple_abi() {
                         "type": "Unit"
                     }
                 }
-            ]
+            ],
+            {
+                "type": "Struct",
+                "name": "Simple",
+                "fields": {
+                    "type": "Named",
+                    "named": [
+                        [
+                            "state",
+                            {
+                                "type": "U32"
+                            }
+                        ]
+                    ]
+                }
+            }
         ]),
     );
 }
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scrypto::core::Component;
+use scrypto::external_blueprint;
+use scrypto::resource::Bucket;
+use scrypto::rust::str::FromStr;
+use scrypto::rust::string::String;
+use scrypto::types::*;
+
+external_blueprint! {
+    "056967d3d49213394892980af59be76e9b3e7cc4cb78237460d0c7",
+    GumballMachine {
+        fn new(price: Decimal, flavor: String) -> Component;
+        fn get_price(&self) -> Decimal;
+        fn buy_gumball(&mut self, payment: Bucket) -> Bucket;
+    }
+}
+
+#[test]
+#[should_panic] // asserts it compiles
+fn test_external_blueprint() {
+    let instance = GumballMachine::from(Address::from_str("").unwrap());
+
+    let _: Decimal = instance.get_price();
+}
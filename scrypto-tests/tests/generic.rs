@@ -0,0 +1,95 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sbor::describe::Type;
+use scrypto::abi;
+use scrypto::buffer::*;
+use scrypto::prelude::*;
+use serde::Serialize;
+use serde_json::{json, to_value, Value};
+
+/// The output of an ABI generator.
+type Abi = (
+    Vec<abi::Function>,
+    Vec<abi::Method>,
+    Type,
+    BTreeMap<String, String>,
+    Vec<abi::NonFungibleData>,
+);
+
+blueprint! {
+    struct Generic {}
+
+    impl Generic {
+        pub fn get_immutable_data<T: NonFungibleData>(data: T) -> Vec<u8> {
+            data.immutable_data()
+        }
+    }
+}
+
+fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
+    assert_eq!(to_value(&actual).unwrap(), expected);
+}
+
+#[test]
+fn test_generic_abi() {
+    let ptr = Generic_abi();
+    let (functions, methods, state, metadata, non_fungible_data): Abi =
+        unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+    assert!(metadata.is_empty());
+    assert!(non_fungible_data.is_empty());
+
+    assert_json_eq(
+        (functions, methods, state),
+        json!([
+            [
+                {
+                    "name": "get_immutable_data",
+                    "inputs": [
+                        {
+                            "type": "Struct",
+                            "name": "RawNonFungibleData",
+                            "fields": {
+                                "type": "Named",
+                                "named": [
+                                    [
+                                        "immutable_data",
+                                        {
+                                            "type": "Vec",
+                                            "element": {
+                                                "type": "U8"
+                                            }
+                                        }
+                                    ],
+                                    [
+                                        "mutable_data",
+                                        {
+                                            "type": "Vec",
+                                            "element": {
+                                                "type": "U8"
+                                            }
+                                        }
+                                    ]
+                                ]
+                            }
+                        }
+                    ],
+                    "output": {
+                        "type": "Vec",
+                        "element": {
+                            "type": "U8"
+                        }
+                    }
+                }
+            ],
+            [],
+            {
+                "type": "Struct",
+                "name": "Generic",
+                "fields": {
+                    "type": "Named",
+                    "named": []
+                }
+            }
+        ]),
+    );
+}
@@ -41,7 +41,7 @@ r#"
                 },
                 {
                     "type": "Custom",
-                    "name": "scrypto::types::Bid",
+                    "name": "scrypto::types::BucketId",
                     "generics": []
                 },
                 {
@@ -56,7 +56,7 @@ r#"
                 },
                 {
                     "type": "Custom",
-                    "name": "scrypto::types::Vid",
+                    "name": "scrypto::types::VaultId",
                     "generics": []
                 },
                 {
@@ -0,0 +1,43 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use scrypto::prelude::*;
+
+blueprint! {
+    #[invariant(self.withdrawn <= self.deposited)]
+    struct Tally {
+        pub deposited: u32,
+        pub withdrawn: u32,
+    }
+
+    impl Tally {
+        pub fn new() -> Component {
+            Self {
+                deposited: 0,
+                withdrawn: 0
+            }.instantiate()
+        }
+
+        pub fn withdraw(&mut self, amount: u32) {
+            self.withdrawn += amount;
+        }
+    }
+}
+
+#[test]
+fn test_invariant_holds() {
+    let state = Tally_blueprint::Tally {
+        deposited: 2,
+        withdrawn: 1,
+    };
+    state.__check_invariants("withdraw");
+}
+
+#[test]
+#[should_panic(expected = "violated after calling `withdraw`")]
+fn test_invariant_violated() {
+    let state = Tally_blueprint::Tally {
+        deposited: 1,
+        withdrawn: 2,
+    };
+    state.__check_invariants("withdraw");
+}
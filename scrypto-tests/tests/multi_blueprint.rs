@@ -0,0 +1,172 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sbor::describe::Type;
+use sbor::{Decode, Describe, Encode, TypeId};
+use scrypto::abi;
+use scrypto::buffer::*;
+use scrypto::prelude::*;
+use serde::Serialize;
+use serde_json::{json, to_value, Value};
+
+// A type shared by both blueprints
+#[derive(TypeId, Encode, Decode, Describe)]
+pub struct PoolInfo {
+    token: Address,
+    fee: Decimal,
+}
+
+blueprint! {
+    struct Factory {
+        pools: LazyMap<u32, Pool>,
+        count: u32,
+    }
+
+    impl Factory {
+        pub fn new() -> Component {
+            Self {
+                pools: LazyMap::new(),
+                count: 0
+            }.instantiate()
+        }
+
+        pub fn create_pool(&mut self, info: PoolInfo) -> Pool {
+            let address: Address = Pool::new(info).into();
+            self.pools.insert(self.count, Pool::from(address));
+            self.count += 1;
+            Pool::from(address)
+        }
+    }
+}
+
+blueprint! {
+    struct Pool {
+        info: PoolInfo,
+    }
+
+    impl Pool {
+        pub fn new(info: PoolInfo) -> Component {
+            Self {
+                info
+            }.instantiate()
+        }
+
+        pub fn info(&self) -> PoolInfo {
+            PoolInfo {
+                token: self.info.token,
+                fee: self.info.fee
+            }
+        }
+    }
+}
+
+fn assert_json_eq<T: Serialize>(actual: T, expected: Value) {
+    assert_eq!(to_value(&actual).unwrap(), expected);
+}
+
+fn export_abi(ptr: *mut u8) -> (Vec<abi::Function>, Vec<abi::Method>, Type) {
+    let (functions, methods, state, _, _): (
+        _,
+        _,
+        _,
+        BTreeMap<String, String>,
+        Vec<abi::NonFungibleData>,
+    ) = unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+    (functions, methods, state)
+}
+
+#[test]
+fn test_blueprints_export_own_abi() {
+    let (factory_functions, factory_methods, _) = export_abi(Factory_abi());
+    let (pool_functions, pool_methods, _) = export_abi(Pool_abi());
+
+    assert_eq!(factory_functions[0].name, "new");
+    assert_eq!(factory_methods[0].name, "create_pool");
+    assert_eq!(pool_functions[0].name, "new");
+    assert_eq!(pool_methods[0].name, "info");
+}
+
+#[test]
+fn test_cross_reference_is_described_as_stub() {
+    let (_, factory_methods, _) = export_abi(Factory_abi());
+
+    assert_json_eq(
+        &factory_methods[0].output,
+        json!({
+            "type": "Struct",
+            "name": "Pool",
+            "fields": {
+                "type": "Named",
+                "named": [
+                    [
+                        "address",
+                        {
+                            "type": "Custom",
+                            "name": "scrypto::types::Address",
+                            "generics": []
+                        }
+                    ]
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_shared_type_is_described_alike() {
+    let (_, factory_methods, _) = export_abi(Factory_abi());
+    let (pool_functions, pool_methods, pool_state) = export_abi(Pool_abi());
+
+    let info = PoolInfo::describe();
+    assert_eq!(factory_methods[0].inputs, vec![info.clone()]);
+    assert_eq!(pool_functions[0].inputs, vec![info.clone()]);
+    assert_eq!(pool_methods[0].output, info);
+    assert_json_eq(
+        pool_state,
+        json!({
+            "type": "Struct",
+            "name": "Pool",
+            "fields": {
+                "type": "Named",
+                "named": [
+                    [
+                        "info",
+                        {
+                            "type": "Struct",
+                            "name": "PoolInfo",
+                            "fields": {
+                                "type": "Named",
+                                "named": [
+                                    [
+                                        "token",
+                                        {
+                                            "type": "Custom",
+                                            "name": "scrypto::types::Address",
+                                            "generics": []
+                                        }
+                                    ],
+                                    [
+                                        "fee",
+                                        {
+                                            "type": "Custom",
+                                            "name": "scrypto::types::Decimal",
+                                            "generics": []
+                                        }
+                                    ]
+                                ]
+                            }
+                        }
+                    ]
+                ]
+            }
+        }),
+    );
+}
+
+#[test]
+fn test_stub_converts_from_address() {
+    let address =
+        Address::from_str("0292566c83de7fd6b04fcc92b5e04b03228ccff040785673278ef1").unwrap();
+    let pool = Pool::from(address);
+
+    assert_eq!(Address::from(pool), address);
+}
@@ -1,8 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sbor::describe::*;
+use scrypto::abi;
+use scrypto::buffer::*;
 use scrypto::prelude::*;
 
+/// The output of an ABI generator.
+type Abi = (
+    Vec<abi::Function>,
+    Vec<abi::Method>,
+    Type,
+    BTreeMap<String, String>,
+    Vec<abi::NonFungibleData>,
+);
+
 #[derive(NonFungibleData, Debug, Eq, PartialEq)]
 pub struct Sample {
     pub a: u32,
@@ -10,6 +21,24 @@ pub struct Sample {
     pub b: String,
 }
 
+#[derive(NonFungibleData, Debug, Eq, PartialEq)]
+pub struct Shorthand {
+    pub a: u32,
+    #[mutable]
+    pub b: String,
+}
+
+blueprint! {
+    #[non_fungible_data(Sample, Shorthand)]
+    struct Minter {}
+
+    impl Minter {
+        pub fn instantiate_minter() -> Component {
+            Self {}.instantiate()
+        }
+    }
+}
+
 #[test]
 fn test_non_fungible_data() {
     let instance = Sample {
@@ -42,3 +71,58 @@ fn test_non_fungible_data() {
         }
     );
 }
+
+#[test]
+fn test_raw_non_fungible_data() {
+    let instance = Sample {
+        a: 1,
+        b: "Test".to_owned(),
+    };
+    let raw = RawNonFungibleData::new(&instance);
+
+    assert_eq!(raw.immutable_data(), instance.immutable_data());
+    assert_eq!(raw.mutable_data(), instance.mutable_data());
+    assert_eq!(raw.to_data::<Sample>().unwrap(), instance);
+}
+
+#[test]
+fn test_mutable_shorthand() {
+    let instance = Shorthand {
+        a: 1,
+        b: "Test".to_owned(),
+    };
+    let sample = Sample {
+        a: 1,
+        b: "Test".to_owned(),
+    };
+
+    assert_eq!(instance.immutable_data(), sample.immutable_data());
+    assert_eq!(instance.mutable_data(), sample.mutable_data());
+    assert_eq!(
+        Shorthand::decode(&instance.immutable_data(), &instance.mutable_data()).unwrap(),
+        instance
+    );
+}
+
+#[test]
+fn test_non_fungible_data_abi() {
+    let ptr = Minter_abi();
+    let (_, _, _, _, non_fungible_data): Abi =
+        unsafe { scrypto_consume(ptr, |slice| scrypto_decode(slice).unwrap()) };
+
+    assert_eq!(non_fungible_data.len(), 2);
+    assert_eq!(non_fungible_data[0].name, "Sample");
+    assert_eq!(
+        non_fungible_data[0].immutable_data,
+        Sample::immutable_data_schema()
+    );
+    assert_eq!(
+        non_fungible_data[0].mutable_data,
+        Sample::mutable_data_schema()
+    );
+    assert_eq!(non_fungible_data[1].name, "Shorthand");
+    assert_eq!(
+        non_fungible_data[1].mutable_data,
+        Shorthand::mutable_data_schema()
+    );
+}
@@ -7,22 +7,27 @@ pub const SCRYPTO_TYPE_RID: u8 = 0x85;
 pub const SCRYPTO_TYPE_MID: u8 = 0x86;
 pub const SCRYPTO_TYPE_VID: u8 = 0x87;
 pub const SCRYPTO_TYPE_NON_FUNGIBLE_KEY: u8 = 0x88;
+pub const SCRYPTO_TYPE_URL: u8 = 0x89;
+pub const SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS: u8 = 0x8a;
 
 pub const SCRYPTO_NAME_DECIMAL: &str = "scrypto::types::Decimal";
 pub const SCRYPTO_NAME_BIG_DECIMAL: &str = "scrypto::types::BigDecimal";
 pub const SCRYPTO_NAME_ADDRESS: &str = "scrypto::types::Address";
 pub const SCRYPTO_NAME_H256: &str = "scrypto::types::H256";
-pub const SCRYPTO_NAME_BID: &str = "scrypto::types::Bid";
+pub const SCRYPTO_NAME_BID: &str = "scrypto::types::BucketId";
 pub const SCRYPTO_NAME_RID: &str = "scrypto::types::Rid";
 pub const SCRYPTO_NAME_MID: &str = "scrypto::types::Mid";
-pub const SCRYPTO_NAME_VID: &str = "scrypto::types::Vid";
+pub const SCRYPTO_NAME_VID: &str = "scrypto::types::VaultId";
 pub const SCRYPTO_NAME_NON_FUNGIBLE_KEY: &str = "scrypto::types::NonFungibleKey";
+pub const SCRYPTO_NAME_URL: &str = "scrypto::types::Url";
+pub const SCRYPTO_NAME_NON_FUNGIBLE_ADDRESS: &str = "scrypto::types::NonFungibleAddress";
 
 pub const SCRYPTO_NAME_ACCOUNT: &str = "scrypto::core::Account";
 pub const SCRYPTO_NAME_PACKAGE: &str = "scrypto::core::Package";
 pub const SCRYPTO_NAME_BLUEPRINT: &str = "scrypto::core::Blueprint";
 pub const SCRYPTO_NAME_COMPONENT: &str = "scrypto::core::Component";
 pub const SCRYPTO_NAME_LAZY_MAP: &str = "scrypto::core::LazyMap";
+pub const SCRYPTO_NAME_ORDERED_STORE: &str = "scrypto::core::OrderedStore";
 
 pub const SCRYPTO_NAME_RESOURCE_DEF: &str = "scrypto::resource::ResourceDef";
 pub const SCRYPTO_NAME_BUCKET: &str = "scrypto::resource::Bucket";
@@ -19,10 +19,10 @@ pub unsafe extern "C" fn scrypto_alloc(len: u32) -> *mut u8 {
 /// Wraps a byte array into a pointer.
 ///
 /// This function assumes the input byte array has the layout specified by `scrypto_alloc`.
-pub fn scrypto_wrap(mut buf: Vec<u8>) -> *mut u8 {
-    let ptr = buf.as_mut_ptr();
-    forget(buf);
-    ptr
+pub fn scrypto_wrap(buf: Vec<u8>) -> *mut u8 {
+    // The buffer is consumed as one of exact capacity, which is only guaranteed for
+    // a boxed slice.
+    crate::rust::boxed::Box::into_raw(buf.into_boxed_slice()) as *mut u8
 }
 
 /// Consumes a memory chunk.
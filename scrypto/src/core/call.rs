@@ -1,5 +1,6 @@
 use crate::engine::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::string::String;
 use crate::rust::vec::Vec;
 use crate::types::*;
 
@@ -32,3 +33,35 @@ pub fn call_method(component_address: Address, method: &str, args: Vec<Vec<u8>>)
 
     output.rtn
 }
+
+/// Invokes a method on a component, which may make at most `max_engine_calls` calls into
+/// the engine, including those of any calls it makes in turn.
+///
+/// If the method fails, all its changes are reverted and `None` is returned, instead of
+/// failing the caller. The arguments must not contain buckets or bucket refs.
+pub fn try_call_method(
+    component_address: Address,
+    method: &str,
+    args: Vec<Vec<u8>>,
+    max_engine_calls: u32,
+) -> Option<Vec<u8>> {
+    let input = TryCallMethodInput {
+        component_address,
+        method: method.to_owned(),
+        args,
+        max_engine_calls,
+    };
+    let output: TryCallMethodOutput = call_engine(TRY_CALL_METHOD, input);
+
+    output.rtn
+}
+
+/// Invokes multiple methods in sequence, with a single call into the engine.
+///
+/// Returns the return values of the methods, in order.
+pub fn call_methods(calls: Vec<(Address, String, Vec<Vec<u8>>)>) -> Vec<Vec<u8>> {
+    let input = CallMethodsInput { calls };
+    let output: CallMethodsOutput = call_engine(CALL_METHODS, input);
+
+    output.rtns
+}
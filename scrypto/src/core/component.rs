@@ -1,9 +1,12 @@
 use sbor::{describe::Type, *};
 
+use crate::abi::Access;
 use crate::buffer::*;
 use crate::core::*;
 use crate::engine::*;
+use crate::resource::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::string::String;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
 use crate::types::*;
@@ -14,6 +17,23 @@ pub trait ComponentState: sbor::Encode + sbor::Decode {
     /// Returns the blueprint name.
     fn blueprint_name() -> &'static str;
 
+    /// Returns whether components of this blueprint may be called back into while one of
+    /// their methods is running.
+    fn reentrant() -> bool {
+        false
+    }
+
+    /// Returns the methods which are not callable by anyone, together with who may call them.
+    fn method_access() -> Vec<(String, Access)> {
+        Vec::new()
+    }
+
+    /// Returns the methods which require a badge, together with the indices of the fields
+    /// holding the badges of which callers must present any.
+    fn method_auth() -> Vec<(String, Vec<u32>)> {
+        Vec::new()
+    }
+
     /// Instantiates a component from this data structure.
     fn instantiate(self) -> Component;
 }
@@ -46,12 +66,43 @@ impl Component {
         let input = CreateComponentInput {
             blueprint_name: T::blueprint_name().to_owned(),
             state: scrypto_encode(&state),
+            method_auth: T::method_auth(),
         };
         let output: CreateComponentOutput = call_engine(CREATE_COMPONENT, input);
 
+        if T::reentrant() {
+            let input = SetComponentReentrantInput {
+                component_address: output.component_address,
+                reentrant: true,
+            };
+            let _: SetComponentReentrantOutput = call_engine(SET_COMPONENT_REENTRANT, input);
+        }
+
+        let methods = T::method_access();
+        if !methods.is_empty() {
+            let input = SetComponentMethodAccessInput {
+                component_address: output.component_address,
+                methods,
+            };
+            let _: SetComponentMethodAccessOutput = call_engine(SET_COMPONENT_METHOD_ACCESS, input);
+        }
+
         output.component_address.into()
     }
 
+    /// Pins this component to a version of its package, or makes it run the latest version
+    /// if `None`.
+    ///
+    /// Only blueprints of the component's package may pin it, and once pinned, only the
+    /// component itself may change the version.
+    pub fn set_package_version(&self, version: Option<u32>) {
+        let input = SetComponentPackageVersionInput {
+            component_address: self.address,
+            version,
+        };
+        let _: SetComponentPackageVersionOutput = call_engine(SET_COMPONENT_PACKAGE_VERSION, input);
+    }
+
     /// Invokes a method on this component.
     pub fn call<T: Decode>(&self, method: &str, args: Vec<Vec<u8>>) -> T {
         let output = call_method(self.address, method, args);
@@ -59,6 +110,20 @@ impl Component {
         scrypto_unwrap(scrypto_decode(&output))
     }
 
+    /// Invokes a method on this component on a best-effort basis, with at most
+    /// `max_engine_calls` calls into the engine.
+    ///
+    /// Returns `None`, with all changes made by the method reverted, if it fails.
+    pub fn try_call<T: Decode>(
+        &self,
+        method: &str,
+        args: Vec<Vec<u8>>,
+        max_engine_calls: u32,
+    ) -> Option<T> {
+        try_call_method(self.address, method, args, max_engine_calls)
+            .map(|output| scrypto_unwrap(scrypto_decode(&output)))
+    }
+
     /// Returns the state of this component.
     pub fn get_state<T: ComponentState>(&self) -> T {
         let input = GetComponentStateInput {};
@@ -75,6 +140,23 @@ impl Component {
         let _: PutComponentStateOutput = call_engine(PUT_COMPONENT_STATE, input);
     }
 
+    /// Reads the state of this component, which is not necessarily the running one.
+    ///
+    /// The engine checks that this component is an instance of the blueprint `T` is
+    /// declared for, from the given package, as any package may declare a blueprint of the
+    /// same name. The returned state is a read-only snapshot, and any vaults or lazy maps
+    /// within can't be accessed from the caller.
+    pub fn state<T: ComponentState>(&self, package_address: Address) -> T {
+        let input = ReadComponentStateInput {
+            component_address: self.address,
+            package_address,
+            blueprint_name: T::blueprint_name().to_owned(),
+        };
+        let output: ReadComponentStateOutput = call_engine(READ_COMPONENT_STATE, input);
+
+        scrypto_unwrap(scrypto_decode(&output.state))
+    }
+
     /// Returns the blueprint that this component is instantiated from.
     pub fn blueprint(&self) -> Blueprint {
         let input = GetComponentInfoInput {
@@ -85,6 +167,31 @@ impl Component {
         Blueprint::from((output.package_address, output.blueprint_name))
     }
 
+    /// Sets the amount of XRD charged for each transaction call into this component, on top
+    /// of the package royalty.
+    ///
+    /// Only blueprints of the component's package may set its royalty. Collected royalties can
+    /// be claimed with the `owner_badge`.
+    pub fn set_royalty(&self, amount: Decimal, owner_badge: Address) {
+        let input = SetRoyaltyInput {
+            address: self.address,
+            amount,
+            owner_badge,
+        };
+        let _: SetRoyaltyOutput = call_engine(SET_ROYALTY, input);
+    }
+
+    /// Claims all royalties collected by this component.
+    pub fn claim_royalty(&self, auth: BucketRef) -> Bucket {
+        let input = ClaimRoyaltyInput {
+            address: self.address,
+            auth: auth.into(),
+        };
+        let output: ClaimRoyaltyOutput = call_engine(CLAIM_ROYALTY, input);
+
+        output.bid.into()
+    }
+
     /// Returns the component address.
     pub fn address(&self) -> Address {
         self.address
@@ -1,4 +1,7 @@
 use crate::engine::*;
+use crate::rust::convert::TryInto;
+use crate::rust::string::ToString;
+use crate::rust::vec::Vec;
 use crate::types::*;
 
 /// A utility for accessing transaction context.
@@ -34,4 +37,59 @@ impl Context {
         let output: GetCurrentEpochOutput = call_engine(GET_CURRENT_EPOCH, input);
         output.current_epoch
     }
+
+    /// Returns `len` pseudo-random bytes.
+    ///
+    /// The bytes are derived from the transaction hash and a counter maintained by the engine,
+    /// so they are deterministic and known to whoever submits the transaction. Do not use them
+    /// where real unpredictability is required.
+    pub fn random_bytes(len: usize) -> Vec<u8> {
+        let input = GenerateRandomInput { len: len as u32 };
+        let output: GenerateRandomOutput = call_engine(GENERATE_RANDOM, input);
+        output.bytes
+    }
+
+    /// Returns a pseudo-random `u128`; see `random_bytes` for how it's derived.
+    pub fn random_u128() -> u128 {
+        u128::from_le_bytes(Self::random_bytes(16).try_into().unwrap())
+    }
+
+    /// Checks an ECDSA (secp256k1) signature of a message.
+    ///
+    /// The message is hashed with SHA-256 before verification, and the signature is expected
+    /// in either the 64-byte compact `r || s` form or DER. Malformed signatures are reported as
+    /// invalid rather than failing the transaction.
+    pub fn verify_ecdsa(message: &[u8], signature: &[u8], public_key: EcdsaPublicKey) -> bool {
+        let input = VerifyEcdsaInput {
+            message: message.to_vec(),
+            signature: signature.to_vec(),
+            public_key,
+        };
+        let output: VerifyEcdsaOutput = call_engine(VERIFY_ECDSA, input);
+        output.is_valid
+    }
+
+    /// Returns the address of a well-known entity, as configured by the engine.
+    ///
+    /// Prefer this over constants like `RADIX_TOKEN` in packages that may be deployed to
+    /// ledgers other than the default one.
+    pub fn well_known_address(name: WellKnownAddress) -> Address {
+        let input = GetWellKnownAddressInput { name };
+        let output: GetWellKnownAddressOutput = call_engine(GET_WELL_KNOWN_ADDRESS, input);
+        output.address
+    }
+
+    /// Schedules a call of a method of the running component, which is made once the ledger
+    /// reaches the given epoch, e.g. to settle an auction or release vested tokens.
+    ///
+    /// Scheduled calls are made without signers, and their arguments may not contain buckets
+    /// or bucket refs. The epoch must be after the current one.
+    pub fn schedule_call(epoch: u64, method: &str, args: Vec<Vec<u8>>) {
+        let input = ScheduleCallInput {
+            epoch,
+            method: method.to_string(),
+            args,
+        };
+        let _: ScheduleCallOutput = call_engine(SCHEDULE_CALL, input);
+    }
 }
@@ -0,0 +1,22 @@
+use sbor::describe::Type;
+use sbor::*;
+
+use crate::buffer::*;
+use crate::engine::*;
+use crate::rust::any::type_name;
+use crate::rust::borrow::ToOwned;
+
+/// Emits an event, which is recorded in the transaction receipt.
+///
+/// The event is named after its type, e.g. `Deposited` for `struct Deposited { .. }`.
+pub fn emit_event<T: Encode + Describe>(event: T) {
+    let name = match T::describe() {
+        Type::Struct { name, .. } | Type::Enum { name, .. } | Type::Custom { name, .. } => name,
+        _ => type_name::<T>().to_owned(),
+    };
+    let input = EmitEventInput {
+        name,
+        data: scrypto_encode(&event),
+    };
+    let _: EmitEventOutput = call_engine(EMIT_EVENT, input);
+}
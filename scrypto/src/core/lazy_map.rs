@@ -5,9 +5,14 @@ use crate::engine::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::marker::PhantomData;
 use crate::rust::vec;
+use crate::rust::vec::Vec;
 use crate::types::*;
 use crate::utils::*;
 
+/// A position within a `LazyMap`, from which to continue enumerating its entries.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+pub struct LazyMapCursor(Vec<u8>);
+
 /// A scalable key-value map which loads values on demand.
 #[derive(Debug, Clone)]
 pub struct LazyMap<K: Encode + Decode, V: Encode + Decode> {
@@ -62,6 +67,60 @@ impl<K: Encode + Decode, V: Encode + Decode> LazyMap<K, V> {
         let _: PutLazyMapEntryOutput = call_engine(PUT_LAZY_MAP_ENTRY, input);
     }
 
+    /// Returns up to `limit` keys of this map, starting at the given cursor.
+    ///
+    /// Keys are returned in the order of their SBOR encoding, which is stable but not
+    /// necessarily the natural order of `K`; use `OrderedStore` when that matters. A cursor is
+    /// returned whenever the page is full, to be passed to the next call. The engine caps
+    /// `limit` at `MAX_SCAN_LIMIT`.
+    pub fn keys(
+        &self,
+        cursor: Option<LazyMapCursor>,
+        limit: u32,
+    ) -> (Vec<K>, Option<LazyMapCursor>) {
+        let (entries, next) = self.iter(cursor, limit);
+        (entries.into_iter().map(|(k, _)| k).collect(), next)
+    }
+
+    /// Returns up to `limit` entries of this map, starting at the given cursor.
+    ///
+    /// See `keys` for ordering and paging.
+    pub fn iter(
+        &self,
+        cursor: Option<LazyMapCursor>,
+        limit: u32,
+    ) -> (Vec<(K, V)>, Option<LazyMapCursor>) {
+        let input = ScanLazyMapInput {
+            mid: self.mid,
+            from: cursor.map(|c| c.0),
+            to: None,
+            limit,
+        };
+        let output: ScanLazyMapOutput = call_engine(SCAN_LAZY_MAP, input);
+
+        // Appending a zero byte to a key gives the smallest byte string after it.
+        let next = match output.entries.last() {
+            Some((k, _)) if output.entries.len() as u32 >= limit => {
+                let mut bytes = k.clone();
+                bytes.push(0);
+                Some(LazyMapCursor(bytes))
+            }
+            _ => None,
+        };
+        let entries = output
+            .entries
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    scrypto_unwrap(scrypto_decode(&k)),
+                    scrypto_unwrap(scrypto_decode(&v)),
+                )
+            })
+            .collect();
+
+        (entries, next)
+    }
+
     /// Returns the identifier of this map.
     pub fn mid(&self) -> Mid {
         self.mid
@@ -2,16 +2,22 @@ mod blueprint;
 mod call;
 mod component;
 mod context;
+mod event;
 mod lazy_map;
 mod logger;
+mod ordered_key;
+mod ordered_store;
 mod package;
 mod uuid;
 
 pub use blueprint::Blueprint;
-pub use call::{call_function, call_method};
+pub use call::{call_function, call_method, call_methods, try_call_method};
 pub use component::{Component, ComponentState};
 pub use context::Context;
-pub use lazy_map::LazyMap;
+pub use event::emit_event;
+pub use lazy_map::{LazyMap, LazyMapCursor};
 pub use logger::Logger;
+pub use ordered_key::OrderedKey;
+pub use ordered_store::OrderedStore;
 pub use package::Package;
 pub use uuid::Uuid;
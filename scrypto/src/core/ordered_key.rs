@@ -0,0 +1,209 @@
+use crate::rust::convert::TryFrom;
+use crate::rust::convert::TryInto;
+use crate::rust::mem::size_of;
+use crate::rust::string::String;
+use crate::rust::vec::Vec;
+use crate::types::*;
+
+/// A key whose byte encoding sorts in the same order as the key itself.
+///
+/// Encodings are self-delimiting, so that keys can be combined into tuples.
+pub trait OrderedKey: Sized {
+    /// Appends the encoding of this key to the buffer.
+    fn encode_ordered(&self, buf: &mut Vec<u8>);
+
+    /// Decodes a key from the start of the buffer and advances the buffer past it.
+    fn decode_ordered(buf: &mut &[u8]) -> Self;
+
+    /// Encodes this key into a byte array.
+    fn to_ordered_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        self.encode_ordered(&mut buf);
+        buf
+    }
+
+    /// Decodes a key from a byte array.
+    fn from_ordered_bytes(bytes: &[u8]) -> Self {
+        let mut buf = bytes;
+        Self::decode_ordered(&mut buf)
+    }
+}
+
+fn split<'a>(buf: &mut &'a [u8], n: usize) -> &'a [u8] {
+    let (head, tail) = buf.split_at(n);
+    *buf = tail;
+    head
+}
+
+macro_rules! ordered_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl OrderedKey for $t {
+                fn encode_ordered(&self, buf: &mut Vec<u8>) {
+                    buf.extend(self.to_be_bytes());
+                }
+
+                fn decode_ordered(buf: &mut &[u8]) -> Self {
+                    <$t>::from_be_bytes(split(buf, size_of::<$t>()).try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+// Signed integers are encoded with the sign bit flipped, so that negative numbers come first.
+macro_rules! ordered_key_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl OrderedKey for $t {
+                fn encode_ordered(&self, buf: &mut Vec<u8>) {
+                    ((*self as $u) ^ (1 as $u).rotate_right(1)).encode_ordered(buf);
+                }
+
+                fn decode_ordered(buf: &mut &[u8]) -> Self {
+                    (<$u>::decode_ordered(buf) ^ (1 as $u).rotate_right(1)) as $t
+                }
+            }
+        )*
+    };
+}
+
+ordered_key_unsigned!(u8, u16, u32, u64, u128);
+ordered_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+// Strings are terminated by `0x00 0x00`, with any `0x00` byte within escaped as `0x00 0xff`.
+impl OrderedKey for String {
+    fn encode_ordered(&self, buf: &mut Vec<u8>) {
+        for b in self.as_bytes() {
+            if *b == 0 {
+                buf.extend([0x00, 0xff]);
+            } else {
+                buf.push(*b);
+            }
+        }
+        buf.extend([0x00, 0x00]);
+    }
+
+    fn decode_ordered(buf: &mut &[u8]) -> Self {
+        let mut bytes = Vec::new();
+        loop {
+            match split(buf, 1)[0] {
+                0x00 => {
+                    if split(buf, 1)[0] == 0x00 {
+                        break;
+                    }
+                    bytes.push(0x00);
+                }
+                b => bytes.push(b),
+            }
+        }
+        String::from_utf8(bytes).unwrap()
+    }
+}
+
+impl OrderedKey for Decimal {
+    fn encode_ordered(&self, buf: &mut Vec<u8>) {
+        self.0.encode_ordered(buf);
+    }
+
+    fn decode_ordered(buf: &mut &[u8]) -> Self {
+        Decimal(i128::decode_ordered(buf))
+    }
+}
+
+impl OrderedKey for Address {
+    fn encode_ordered(&self, buf: &mut Vec<u8>) {
+        buf.extend(self.to_vec());
+    }
+
+    fn decode_ordered(buf: &mut &[u8]) -> Self {
+        Address::try_from(split(buf, 27)).unwrap()
+    }
+}
+
+impl<A: OrderedKey, B: OrderedKey> OrderedKey for (A, B) {
+    fn encode_ordered(&self, buf: &mut Vec<u8>) {
+        self.0.encode_ordered(buf);
+        self.1.encode_ordered(buf);
+    }
+
+    fn decode_ordered(buf: &mut &[u8]) -> Self {
+        let a = A::decode_ordered(buf);
+        let b = B::decode_ordered(buf);
+        (a, b)
+    }
+}
+
+impl<A: OrderedKey, B: OrderedKey, C: OrderedKey> OrderedKey for (A, B, C) {
+    fn encode_ordered(&self, buf: &mut Vec<u8>) {
+        self.0.encode_ordered(buf);
+        self.1.encode_ordered(buf);
+        self.2.encode_ordered(buf);
+    }
+
+    fn decode_ordered(buf: &mut &[u8]) -> Self {
+        let a = A::decode_ordered(buf);
+        let b = B::decode_ordered(buf);
+        let c = C::decode_ordered(buf);
+        (a, b, c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::fmt::Debug;
+    use crate::rust::str::FromStr;
+    use crate::rust::string::ToString;
+    use crate::rust::vec;
+
+    fn assert_sorted<K: OrderedKey + PartialEq + Debug>(keys: Vec<K>) {
+        let encoded: Vec<Vec<u8>> = keys.iter().map(OrderedKey::to_ordered_bytes).collect();
+        for pair in encoded.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        for (key, bytes) in keys.iter().zip(encoded.iter()) {
+            assert_eq!(&K::from_ordered_bytes(bytes), key);
+        }
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_sorted(vec![0u32, 1, 255, 256, u32::MAX]);
+        assert_sorted(vec![i64::MIN, -256, -1, 0, 1, 256, i64::MAX]);
+    }
+
+    #[test]
+    fn test_decimal() {
+        assert_sorted(vec![
+            Decimal::from(-100),
+            Decimal::from_str("-0.5").unwrap(),
+            Decimal::zero(),
+            Decimal::from_str("0.5").unwrap(),
+            Decimal::from(100),
+        ]);
+    }
+
+    #[test]
+    fn test_string() {
+        assert_sorted(vec![
+            "".to_string(),
+            "\0".to_string(),
+            "\0a".to_string(),
+            "a".to_string(),
+            "a\0".to_string(),
+            "ab".to_string(),
+            "b".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_tuple() {
+        assert_sorted(vec![
+            ("a".to_string(), 2u8),
+            ("a".to_string(), 10u8),
+            ("ab".to_string(), 1u8),
+            ("b".to_string(), 0u8),
+        ]);
+    }
+}
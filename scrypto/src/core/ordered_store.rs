@@ -0,0 +1,156 @@
+use sbor::{describe::Type, *};
+
+use crate::buffer::*;
+use crate::core::*;
+use crate::engine::*;
+use crate::rust::borrow::ToOwned;
+use crate::rust::marker::PhantomData;
+use crate::rust::ops::{Bound, RangeBounds};
+use crate::rust::vec;
+use crate::rust::vec::Vec;
+use crate::types::*;
+use crate::utils::*;
+
+/// A scalable key-value map which keeps its entries sorted by key and supports range scans.
+///
+/// Like `LazyMap`, values are loaded on demand. Keys are stored in their `OrderedKey`
+/// encoding, so that the engine can scan them in order.
+#[derive(Debug, Clone)]
+pub struct OrderedStore<K: OrderedKey, V: Encode + Decode> {
+    mid: Mid,
+    key: PhantomData<K>,
+    value: PhantomData<V>,
+}
+
+impl<K: OrderedKey, V: Encode + Decode> From<Mid> for OrderedStore<K, V> {
+    fn from(mid: Mid) -> Self {
+        Self {
+            mid,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<K: OrderedKey, V: Encode + Decode> From<OrderedStore<K, V>> for Mid {
+    fn from(a: OrderedStore<K, V>) -> Mid {
+        a.mid
+    }
+}
+
+impl<K: OrderedKey, V: Encode + Decode> OrderedStore<K, V> {
+    /// Creates a new ordered store.
+    pub fn new() -> Self {
+        let input = CreateLazyMapInput {};
+        let output: CreateLazyMapOutput = call_engine(CREATE_LAZY_MAP, input);
+
+        output.mid.into()
+    }
+
+    /// Returns the value that is associated with the given key.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let input = GetLazyMapEntryInput {
+            mid: self.mid,
+            key: key.to_ordered_bytes(),
+        };
+        let output: GetLazyMapEntryOutput = call_engine(GET_LAZY_MAP_ENTRY, input);
+
+        output.value.map(|v| scrypto_unwrap(scrypto_decode(&v)))
+    }
+
+    /// Inserts a new key-value pair into this store.
+    pub fn insert(&self, key: K, value: V) {
+        let input = PutLazyMapEntryInput {
+            mid: self.mid,
+            key: key.to_ordered_bytes(),
+            value: scrypto_encode(&value),
+        };
+        let _: PutLazyMapEntryOutput = call_engine(PUT_LAZY_MAP_ENTRY, input);
+    }
+
+    /// Returns up to `limit` entries within the given key range, in ascending key order.
+    ///
+    /// To page through a large range, start the next scan right after the last key returned,
+    /// e.g. `(Bound::Excluded(last), Bound::Unbounded)`. The engine caps `limit` at
+    /// `MAX_SCAN_LIMIT`.
+    pub fn range<R: RangeBounds<K>>(&self, range: R, limit: u32) -> Vec<(K, V)> {
+        // All encodings are self-delimiting, so appending a zero byte to a key gives
+        // the smallest encoding after it.
+        let successor = |key: &K| {
+            let mut bytes = key.to_ordered_bytes();
+            bytes.push(0);
+            bytes
+        };
+        let from = match range.start_bound() {
+            Bound::Included(key) => Some(key.to_ordered_bytes()),
+            Bound::Excluded(key) => Some(successor(key)),
+            Bound::Unbounded => None,
+        };
+        let to = match range.end_bound() {
+            Bound::Included(key) => Some(successor(key)),
+            Bound::Excluded(key) => Some(key.to_ordered_bytes()),
+            Bound::Unbounded => None,
+        };
+
+        let input = ScanLazyMapInput {
+            mid: self.mid,
+            from,
+            to,
+            limit,
+        };
+        let output: ScanLazyMapOutput = call_engine(SCAN_LAZY_MAP, input);
+
+        output
+            .entries
+            .into_iter()
+            .map(|(k, v)| {
+                (
+                    K::from_ordered_bytes(&k),
+                    scrypto_unwrap(scrypto_decode(&v)),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the identifier of this store.
+    pub fn mid(&self) -> Mid {
+        self.mid
+    }
+}
+
+impl<K: OrderedKey, V: Encode + Decode> Default for OrderedStore<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//========
+// SBOR
+//========
+
+impl<K: OrderedKey, V: Encode + Decode> TypeId for OrderedStore<K, V> {
+    fn type_id() -> u8 {
+        Mid::type_id()
+    }
+}
+
+impl<K: OrderedKey, V: Encode + Decode> Encode for OrderedStore<K, V> {
+    fn encode_value(&self, encoder: &mut Encoder) {
+        self.mid.encode_value(encoder);
+    }
+}
+
+impl<K: OrderedKey, V: Encode + Decode> Decode for OrderedStore<K, V> {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        Mid::decode_value(decoder).map(Into::into)
+    }
+}
+
+impl<K: OrderedKey + Describe, V: Encode + Decode + Describe> Describe for OrderedStore<K, V> {
+    fn describe() -> Type {
+        Type::Custom {
+            name: SCRYPTO_NAME_ORDERED_STORE.to_owned(),
+            generics: vec![K::describe(), V::describe()],
+        }
+    }
+}
@@ -2,6 +2,7 @@ use sbor::{describe::Type, *};
 
 use crate::buffer::*;
 use crate::engine::*;
+use crate::resource::*;
 use crate::rust::borrow::ToOwned;
 use crate::rust::vec;
 use crate::types::*;
@@ -39,6 +40,55 @@ impl Package {
         output.package_address.into()
     }
 
+    /// Creates a new package, which can be upgraded by the holder of the `owner_badge`.
+    pub fn with_owner(code: &[u8], owner_badge: Address) -> Self {
+        let input = PublishPackageWithOwnerInput {
+            code: code.to_vec(),
+            owner_badge,
+        };
+        let output: PublishPackageWithOwnerOutput = call_engine(PUBLISH_PACKAGE_WITH_OWNER, input);
+
+        output.package_address.into()
+    }
+
+    /// Publishes a new version of this package, and returns its number.
+    ///
+    /// Components which are not pinned to a version run the new code from now on.
+    pub fn upgrade(&self, code: &[u8], auth: BucketRef) -> u32 {
+        let input = PublishPackageUpgradeInput {
+            package_address: self.address,
+            code: code.to_vec(),
+            auth: auth.into(),
+        };
+        let output: PublishPackageUpgradeOutput = call_engine(PUBLISH_PACKAGE_UPGRADE, input);
+
+        output.version
+    }
+
+    /// Sets the amount of XRD charged for each transaction call into a blueprint of this package.
+    ///
+    /// Only blueprints of this package may set its royalty. Collected royalties can be claimed
+    /// with the `owner_badge`.
+    pub fn set_royalty(&self, amount: Decimal, owner_badge: Address) {
+        let input = SetRoyaltyInput {
+            address: self.address,
+            amount,
+            owner_badge,
+        };
+        let _: SetRoyaltyOutput = call_engine(SET_ROYALTY, input);
+    }
+
+    /// Claims all royalties collected by this package.
+    pub fn claim_royalty(&self, auth: BucketRef) -> Bucket {
+        let input = ClaimRoyaltyInput {
+            address: self.address,
+            auth: auth.into(),
+        };
+        let output: ClaimRoyaltyOutput = call_engine(CLAIM_ROYALTY, input);
+
+        output.bid.into()
+    }
+
     /// Returns the package address.
     pub fn address(&self) -> Address {
         self.address
@@ -1,6 +1,8 @@
 use sbor::{Decode, Encode, TypeId};
 
+use crate::abi::Access;
 use crate::engine::*;
+use crate::rust::collections::BTreeSet;
 use crate::rust::collections::HashMap;
 use crate::rust::string::String;
 use crate::rust::vec::Vec;
@@ -18,6 +20,18 @@ pub const PUBLISH_PACKAGE: u32 = 0x00;
 pub const CALL_FUNCTION: u32 = 0x01;
 /// Call a method
 pub const CALL_METHOD: u32 = 0x02;
+/// Set the royalty charged for calling into a package or component
+pub const SET_ROYALTY: u32 = 0x03;
+/// Claim the royalties collected by a package or component
+pub const CLAIM_ROYALTY: u32 = 0x04;
+/// Call multiple methods in sequence
+pub const CALL_METHODS: u32 = 0x05;
+/// Publish a code package which can be upgraded by the holder of an owner badge
+pub const PUBLISH_PACKAGE_WITH_OWNER: u32 = 0x06;
+/// Publish a new version of a package
+pub const PUBLISH_PACKAGE_UPGRADE: u32 = 0x07;
+/// Call a method with bounded cost, reverting its changes instead of failing if it fails
+pub const TRY_CALL_METHOD: u32 = 0x08;
 
 /// Create a component
 pub const CREATE_COMPONENT: u32 = 0x10;
@@ -27,6 +41,14 @@ pub const GET_COMPONENT_INFO: u32 = 0x11;
 pub const GET_COMPONENT_STATE: u32 = 0x12;
 /// Update component state
 pub const PUT_COMPONENT_STATE: u32 = 0x13;
+/// Read the state of another component
+pub const READ_COMPONENT_STATE: u32 = 0x14;
+/// Allow or disallow calling back into a component while it's running
+pub const SET_COMPONENT_REENTRANT: u32 = 0x15;
+/// Restrict who may call certain methods of a component
+pub const SET_COMPONENT_METHOD_ACCESS: u32 = 0x16;
+/// Pin a component to a package version, or make it follow the latest
+pub const SET_COMPONENT_PACKAGE_VERSION: u32 = 0x17;
 
 /// Create a lazy map
 pub const CREATE_LAZY_MAP: u32 = 0x20;
@@ -34,6 +56,11 @@ pub const CREATE_LAZY_MAP: u32 = 0x20;
 pub const GET_LAZY_MAP_ENTRY: u32 = 0x21;
 /// Insert a key-value pair into a lazy map
 pub const PUT_LAZY_MAP_ENTRY: u32 = 0x22;
+/// Scan the entries of a lazy map within a key range
+pub const SCAN_LAZY_MAP: u32 = 0x23;
+
+/// The maximum number of entries returned by a single lazy map scan
+pub const MAX_SCAN_LIMIT: u32 = 1000;
 
 /// Create resource
 pub const CREATE_RESOURCE: u32 = 0x30;
@@ -61,6 +88,10 @@ pub const GET_NON_FUNGIBLE_DATA: u32 = 0x3a;
 pub const UPDATE_NON_FUNGIBLE_MUTABLE_DATA: u32 = 0x3b;
 /// Update resource metadata
 pub const UPDATE_RESOURCE_METADATA: u32 = 0x3c;
+/// Recall resource from a vault
+pub const RECALL_RESOURCE: u32 = 0x3d;
+/// Allow or disallow a component to hold resource
+pub const UPDATE_ALLOWED_DEPOSITOR: u32 = 0x3e;
 
 /// Create an empty vault
 pub const CREATE_EMPTY_VAULT: u32 = 0x40;
@@ -76,6 +107,18 @@ pub const GET_VAULT_RESOURCE_ADDRESS: u32 = 0x44;
 pub const TAKE_NON_FUNGIBLE_FROM_VAULT: u32 = 0x45;
 /// Get the IDs of all non-fungibles in this vault
 pub const GET_NON_FUNGIBLE_KEYS_IN_VAULT: u32 = 0x46;
+/// Take all resource from this vault
+pub const TAKE_ALL_FROM_VAULT: u32 = 0x47;
+/// Take non-fungibles from this vault, by keys
+pub const TAKE_NON_FUNGIBLES_FROM_VAULT: u32 = 0x48;
+/// Get the IDs and data of all non-fungibles in this vault
+pub const GET_NON_FUNGIBLES_IN_VAULT: u32 = 0x49;
+/// Freeze a vault, so that no resource can be put into or taken from it
+pub const FREEZE_VAULT: u32 = 0x4a;
+/// Unfreeze a vault
+pub const UNFREEZE_VAULT: u32 = 0x4b;
+/// Take as much fungible resource as available from this vault, up to some amount
+pub const TAKE_UP_TO_FROM_VAULT: u32 = 0x4c;
 
 /// Create an empty bucket
 pub const CREATE_EMPTY_BUCKET: u32 = 0x50;
@@ -91,6 +134,10 @@ pub const GET_BUCKET_RESOURCE_ADDRESS: u32 = 0x54;
 pub const TAKE_NON_FUNGIBLE_FROM_BUCKET: u32 = 0x55;
 /// Get the IDs of all non-fungibles in this bucket
 pub const GET_NON_FUNGIBLE_KEYS_IN_BUCKET: u32 = 0x56;
+/// Get the IDs and data of all non-fungibles in this bucket
+pub const GET_NON_FUNGIBLES_IN_BUCKET: u32 = 0x57;
+/// Drop an empty bucket
+pub const DROP_EMPTY_BUCKET: u32 = 0x58;
 
 /// Obtain a bucket ref
 pub const CREATE_BUCKET_REF: u32 = 0x60;
@@ -104,6 +151,20 @@ pub const GET_BUCKET_REF_RESOURCE_DEF: u32 = 0x63;
 pub const GET_NON_FUNGIBLE_KEYS_IN_BUCKET_REF: u32 = 0x64;
 /// Clone bucket ref
 pub const CLONE_BUCKET_REF: u32 = 0x65;
+/// Check a metadata field of the resource behind a bucket ref
+pub const CHECK_BUCKET_REF_METADATA: u32 = 0x66;
+/// Get the component whose vault the resource behind a bucket ref was taken from
+pub const GET_BUCKET_REF_ORIGIN: u32 = 0x67;
+/// Obtain a bucket ref to all resources in a vault
+pub const CREATE_VAULT_BUCKET_REF: u32 = 0x68;
+/// Obtain a bucket ref to some amount of a bucket
+pub const CREATE_BUCKET_REF_FOR_AMOUNT: u32 = 0x69;
+/// Obtain a bucket ref to some non-fungibles of a bucket
+pub const CREATE_BUCKET_REF_FOR_NON_FUNGIBLES: u32 = 0x6a;
+/// Combine bucket refs into a composite one, which proves all of them at once
+pub const COMPOSE_BUCKET_REFS: u32 = 0x6b;
+/// Check if a bucket ref, or any bucket ref in a composite one, contains a resource
+pub const CHECK_BUCKET_REF_CONTAINS: u32 = 0x6c;
 
 /// Log a message
 pub const EMIT_LOG: u32 = 0xf0;
@@ -119,6 +180,21 @@ pub const GET_TRANSACTION_HASH: u32 = 0xf4;
 pub const GENERATE_UUID: u32 = 0xf5;
 /// Retrieve the running entity
 pub const GET_ACTOR: u32 = 0xf6;
+/// Emit an event
+pub const EMIT_EVENT: u32 = 0xf7;
+/// Generate pseudo-random bytes
+pub const GENERATE_RANDOM: u32 = 0xf8;
+/// Verify an ECDSA signature
+pub const VERIFY_ECDSA: u32 = 0xf9;
+/// Resolve a well-known address
+pub const GET_WELL_KNOWN_ADDRESS: u32 = 0xfa;
+/// Make multiple engine calls at once
+pub const BATCH_ENGINE_CALLS: u32 = 0xfb;
+/// Schedule a method call on the running component for a future epoch
+pub const SCHEDULE_CALL: u32 = 0xfc;
+
+/// The maximum number of pseudo-random bytes generated by a single call
+pub const MAX_RANDOM_LEN: u32 = 1024;
 
 //==========
 // blueprint
@@ -134,6 +210,29 @@ pub struct PublishPackageOutput {
     pub package_address: Address,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct PublishPackageWithOwnerInput {
+    pub code: Vec<u8>,
+    pub owner_badge: Address,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct PublishPackageWithOwnerOutput {
+    pub package_address: Address,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct PublishPackageUpgradeInput {
+    pub package_address: Address,
+    pub code: Vec<u8>,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct PublishPackageUpgradeOutput {
+    pub version: u32,
+}
+
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct CallFunctionInput {
     pub package_address: Address,
@@ -159,6 +258,50 @@ pub struct CallMethodOutput {
     pub rtn: Vec<u8>,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CallMethodsInput {
+    pub calls: Vec<(Address, String, Vec<Vec<u8>>)>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CallMethodsOutput {
+    pub rtns: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TryCallMethodInput {
+    pub component_address: Address,
+    pub method: String,
+    pub args: Vec<Vec<u8>>,
+    pub max_engine_calls: u32,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TryCallMethodOutput {
+    pub rtn: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetRoyaltyInput {
+    pub address: Address,
+    pub amount: Decimal,
+    pub owner_badge: Address,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetRoyaltyOutput {}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ClaimRoyaltyInput {
+    pub address: Address,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ClaimRoyaltyOutput {
+    pub bid: BucketId,
+}
+
 //==========
 // component
 //==========
@@ -167,6 +310,7 @@ pub struct CallMethodOutput {
 pub struct CreateComponentInput {
     pub blueprint_name: String,
     pub state: Vec<u8>,
+    pub method_auth: Vec<(String, Vec<u32>)>,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -201,6 +345,45 @@ pub struct PutComponentStateInput {
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct PutComponentStateOutput {}
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ReadComponentStateInput {
+    pub component_address: Address,
+    pub package_address: Address,
+    pub blueprint_name: String,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ReadComponentStateOutput {
+    pub state: Vec<u8>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentReentrantInput {
+    pub component_address: Address,
+    pub reentrant: bool,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentReentrantOutput {}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentMethodAccessInput {
+    pub component_address: Address,
+    pub methods: Vec<(String, Access)>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentMethodAccessOutput {}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentPackageVersionInput {
+    pub component_address: Address,
+    pub version: Option<u32>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct SetComponentPackageVersionOutput {}
+
 //==========
 // LazyMap
 //==========
@@ -234,6 +417,19 @@ pub struct PutLazyMapEntryInput {
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct PutLazyMapEntryOutput {}
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ScanLazyMapInput {
+    pub mid: Mid,
+    pub from: Option<Vec<u8>>,
+    pub to: Option<Vec<u8>>,
+    pub limit: u32,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ScanLazyMapOutput {
+    pub entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
 //=========
 // resource
 //=========
@@ -251,7 +447,7 @@ pub struct CreateResourceInput {
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct CreateResourceOutput {
     pub resource_address: Address,
-    pub bucket: Option<Bid>,
+    pub bucket: Option<BucketId>,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -263,12 +459,12 @@ pub struct MintResourceInput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct MintResourceOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct BurnResourceInput {
-    pub bid: Bid,
+    pub bid: BucketId,
     pub auth: Option<Rid>,
 }
 
@@ -378,6 +574,31 @@ pub struct UpdateResourceMetadataInput {
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct UpdateResourceMetadataOutput {}
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct RecallResourceInput {
+    pub resource_address: Address,
+    pub component_address: Address,
+    pub vid: VaultId,
+    pub amount: Decimal,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct RecallResourceOutput {
+    pub bid: BucketId,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct UpdateAllowedDepositorInput {
+    pub resource_address: Address,
+    pub component_address: Address,
+    pub allowed: bool,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct UpdateAllowedDepositorOutput {}
+
 //==========
 // vault
 //==========
@@ -389,13 +610,13 @@ pub struct CreateEmptyVaultInput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct CreateEmptyVaultOutput {
-    pub vid: Vid,
+    pub vid: VaultId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct PutIntoVaultInput {
-    pub vid: Vid,
-    pub bid: Bid,
+    pub vid: VaultId,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -403,19 +624,19 @@ pub struct PutIntoVaultOutput {}
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeFromVaultInput {
-    pub vid: Vid,
+    pub vid: VaultId,
     pub amount: Decimal,
     pub auth: Option<Rid>,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeFromVaultOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetVaultDecimalInput {
-    pub vid: Vid,
+    pub vid: VaultId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -425,7 +646,7 @@ pub struct GetVaultDecimalOutput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetVaultResourceAddressInput {
-    pub vid: Vid,
+    pub vid: VaultId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -435,19 +656,55 @@ pub struct GetVaultResourceAddressOutput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeNonFungibleFromVaultInput {
-    pub vid: Vid,
+    pub vid: VaultId,
     pub key: NonFungibleKey,
     pub auth: Option<Rid>,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeNonFungibleFromVaultOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeAllFromVaultInput {
+    pub vid: VaultId,
+    pub auth: Option<Rid>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeAllFromVaultOutput {
+    pub bid: BucketId,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeUpToFromVaultInput {
+    pub vid: VaultId,
+    pub amount: Decimal,
+    pub auth: Option<Rid>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeUpToFromVaultOutput {
+    pub bid: BucketId,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeNonFungiblesFromVaultInput {
+    pub vid: VaultId,
+    pub keys: BTreeSet<NonFungibleKey>,
+    pub auth: Option<Rid>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TakeNonFungiblesFromVaultOutput {
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetNonFungibleKeysInVaultInput {
-    pub vid: Vid,
+    pub vid: VaultId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -455,6 +712,39 @@ pub struct GetNonFungibleKeysInVaultOutput {
     pub keys: Vec<NonFungibleKey>,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetNonFungiblesInVaultInput {
+    pub vid: VaultId,
+}
+
+/// Each entry is made of the key, the immutable data and the mutable data.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetNonFungiblesInVaultOutput {
+    pub non_fungibles: Vec<(NonFungibleKey, Vec<u8>, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct FreezeVaultInput {
+    pub resource_address: Address,
+    pub component_address: Address,
+    pub vid: VaultId,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct FreezeVaultOutput {}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct UnfreezeVaultInput {
+    pub resource_address: Address,
+    pub component_address: Address,
+    pub vid: VaultId,
+    pub auth: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct UnfreezeVaultOutput {}
+
 //==========
 // bucket
 //==========
@@ -466,13 +756,13 @@ pub struct CreateEmptyBucketInput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct CreateEmptyBucketOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct PutIntoBucketInput {
-    pub bid: Bid,
-    pub other: Bid,
+    pub bid: BucketId,
+    pub other: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -480,18 +770,18 @@ pub struct PutIntoBucketOutput {}
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeFromBucketInput {
-    pub bid: Bid,
+    pub bid: BucketId,
     pub amount: Decimal,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeFromBucketOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetBucketDecimalInput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -501,7 +791,7 @@ pub struct GetBucketDecimalOutput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetBucketResourceAddressInput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -511,18 +801,18 @@ pub struct GetBucketResourceAddressOutput {
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeNonFungibleFromBucketInput {
-    pub bid: Bid,
+    pub bid: BucketId,
     pub key: NonFungibleKey,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct TakeNonFungibleFromBucketOutput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetNonFungibleKeysInBucketInput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -530,13 +820,32 @@ pub struct GetNonFungibleKeysInBucketOutput {
     pub keys: Vec<NonFungibleKey>,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetNonFungiblesInBucketInput {
+    pub bid: BucketId,
+}
+
+/// Each entry is made of the key, the immutable data and the mutable data.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetNonFungiblesInBucketOutput {
+    pub non_fungibles: Vec<(NonFungibleKey, Vec<u8>, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct DropEmptyBucketInput {
+    pub bid: BucketId,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct DropEmptyBucketOutput {}
+
 //==========
 // bucket ref
 //==========
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct CreateBucketRefInput {
-    pub bid: Bid,
+    pub bid: BucketId,
 }
 
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
@@ -592,6 +901,81 @@ pub struct CloneBucketRefOutput {
     pub rid: Rid,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CheckBucketRefMetadataInput {
+    pub rid: Rid,
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CheckBucketRefMetadataOutput {
+    pub matches: bool,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetBucketRefOriginInput {
+    pub rid: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetBucketRefOriginOutput {
+    pub origin: Option<Address>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateVaultBucketRefInput {
+    pub vid: VaultId,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateVaultBucketRefOutput {
+    pub rid: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateBucketRefForAmountInput {
+    pub bid: BucketId,
+    pub amount: Decimal,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateBucketRefForAmountOutput {
+    pub rid: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateBucketRefForNonFungiblesInput {
+    pub bid: BucketId,
+    pub keys: BTreeSet<NonFungibleKey>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CreateBucketRefForNonFungiblesOutput {
+    pub rid: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ComposeBucketRefsInput {
+    pub rids: Vec<Rid>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ComposeBucketRefsOutput {
+    pub rid: Rid,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CheckBucketRefContainsInput {
+    pub rid: Rid,
+    pub resource_address: Address,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct CheckBucketRefContainsOutput {
+    pub contains: bool,
+}
+
 //=======
 // others
 //=======
@@ -605,6 +989,15 @@ pub struct EmitLogInput {
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct EmitLogOutput {}
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct EmitEventInput {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct EmitEventOutput {}
+
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetPackageAddressInput {}
 
@@ -649,6 +1042,58 @@ pub struct GenerateUuidOutput {
     pub uuid: u128,
 }
 
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GenerateRandomInput {
+    pub len: u32,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GenerateRandomOutput {
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct VerifyEcdsaInput {
+    pub message: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub public_key: EcdsaPublicKey,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct VerifyEcdsaOutput {
+    pub is_valid: bool,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetWellKnownAddressInput {
+    pub name: WellKnownAddress,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct GetWellKnownAddressOutput {
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct BatchEngineCallsInput {
+    pub calls: Vec<(u32, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct BatchEngineCallsOutput {
+    pub outputs: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ScheduleCallInput {
+    pub epoch: u64,
+    pub method: String,
+    pub args: Vec<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct ScheduleCallOutput {}
+
 #[derive(Debug, Clone, TypeId, Encode, Decode)]
 pub struct GetActorInput {}
 
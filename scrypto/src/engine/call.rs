@@ -2,6 +2,8 @@ use sbor::*;
 
 use crate::buffer::*;
 use crate::engine::*;
+use crate::rust::vec;
+use crate::rust::vec::Vec;
 use crate::utils::*;
 
 /// Utility function for making a radix engine call.
@@ -21,9 +23,33 @@ pub fn call_engine<T: Encode, V: Decode>(op: u32, input: T) -> V {
     }
 }
 
+/// Handles the radix engine calls of a blueprint compiled natively, e.g. for testing.
+///
+/// It takes an operation and the encoded input, and returns the encoded output.
+#[cfg(not(target_arch = "wasm32"))]
+pub type NativeEngine = fn(u32, Vec<u8>) -> Vec<u8>;
+
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "alloc")))]
+std::thread_local! {
+    static NATIVE_ENGINE: crate::rust::cell::Cell<Option<NativeEngine>> =
+        crate::rust::cell::Cell::new(None);
+}
+
+/// Sets the engine which handles the radix engine calls made on the current thread.
+#[cfg(all(not(target_arch = "wasm32"), not(feature = "alloc")))]
+pub fn set_native_engine(engine: Option<NativeEngine>) {
+    NATIVE_ENGINE.with(|e| e.set(engine));
+}
+
 /// Utility function for making a radix engine call.
 #[cfg(not(target_arch = "wasm32"))]
 pub fn call_engine<T: Encode, V: Decode>(op: u32, input: T) -> V {
+    #[cfg(not(feature = "alloc"))]
+    if let Some(engine) = NATIVE_ENGINE.with(|e| e.get()) {
+        let output_bytes = engine(op, scrypto_encode(&input));
+        return scrypto_unwrap(scrypto_decode::<V>(&output_bytes));
+    }
+
     if op == EMIT_LOG {
         let input_bytes = scrypto_encode(&input);
         #[allow(unused_variables)]
@@ -36,3 +62,29 @@ pub fn call_engine<T: Encode, V: Decode>(op: u32, input: T) -> V {
         todo!()
     }
 }
+
+/// Utility function for making multiple radix engine calls at once, which crosses the engine
+/// boundary only once.
+///
+/// Each call is an operation along with its encoded input, and the encoded outputs are returned
+/// in the same order.
+pub fn call_engine_batch(calls: Vec<(u32, Vec<u8>)>) -> Vec<Vec<u8>> {
+    let output: BatchEngineCallsOutput =
+        call_engine(BATCH_ENGINE_CALLS, BatchEngineCallsInput { calls });
+    output.outputs
+}
+
+/// Utility function for making two radix engine calls at once.
+pub fn call_engine_pair<T1: Encode, V1: Decode, T2: Encode, V2: Decode>(
+    first: (u32, T1),
+    second: (u32, T2),
+) -> (V1, V2) {
+    let outputs = call_engine_batch(vec![
+        (first.0, scrypto_encode(&first.1)),
+        (second.0, scrypto_encode(&second.1)),
+    ]);
+    (
+        scrypto_unwrap(scrypto_decode::<V1>(&outputs[0])),
+        scrypto_unwrap(scrypto_decode::<V2>(&outputs[1])),
+    )
+}
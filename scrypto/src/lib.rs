@@ -40,7 +40,9 @@ pub mod abi {
 
 // Re-export Scrypto derive.
 extern crate scrypto_derive;
-pub use scrypto_derive::{auth, blueprint, import, NonFungibleData};
+pub use scrypto_derive::{
+    auth, blueprint, component, external_blueprint, import, package, resource_def, NonFungibleData,
+};
 
 /// Encodes arguments according to Scrypto ABI.
 ///
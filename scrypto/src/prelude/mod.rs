@@ -1,12 +1,12 @@
 pub use crate::buffer::{scrypto_decode, scrypto_encode};
 pub use crate::core::*;
-pub use crate::engine::{call_engine, LogLevel, NewSupply, ResourceType};
+pub use crate::engine::{call_engine, call_engine_batch, LogLevel, NewSupply, ResourceType};
 pub use crate::resource::*;
 pub use crate::types::*;
 pub use crate::utils::*;
 pub use crate::{
-    args, auth, bdec, blueprint, debug, dec, error, import, include_code, info, trace, warn,
-    NonFungibleData,
+    args, auth, bdec, blueprint, component, debug, dec, error, external_blueprint, import,
+    include_code, info, package, resource_def, trace, warn, NonFungibleData,
 };
 
 pub use crate::rust::borrow::ToOwned;
@@ -4,24 +4,26 @@ use crate::buffer::*;
 use crate::engine::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::BTreeSet;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
 use crate::types::*;
+use crate::utils::*;
 
 /// Represents a transient resource container.
 #[derive(Debug)]
 pub struct Bucket {
-    bid: Bid,
+    bid: BucketId,
 }
 
-impl From<Bid> for Bucket {
-    fn from(bid: Bid) -> Self {
+impl From<BucketId> for Bucket {
+    fn from(bid: BucketId) -> Self {
         Self { bid }
     }
 }
 
-impl From<Bucket> for Bid {
-    fn from(a: Bucket) -> Bid {
+impl From<Bucket> for BucketId {
+    fn from(a: Bucket) -> BucketId {
         a.bid
     }
 }
@@ -65,6 +67,38 @@ impl Bucket {
         output.rid.into()
     }
 
+    /// Creates an immutable reference to some amount of this bucket, which proves ownership
+    /// of only that amount.
+    ///
+    /// # Panics
+    /// Panics if this bucket holds less than the amount.
+    pub fn present_amount<A: Into<Decimal>>(&self, amount: A) -> BucketRef {
+        let input = CreateBucketRefForAmountInput {
+            bid: self.bid,
+            amount: amount.into(),
+        };
+        let output: CreateBucketRefForAmountOutput =
+            call_engine(CREATE_BUCKET_REF_FOR_AMOUNT, input);
+
+        output.rid.into()
+    }
+
+    /// Creates an immutable reference to some non-fungibles of this bucket, which proves
+    /// ownership of only those non-fungibles.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible bucket or any of the non-fungibles is not found.
+    pub fn present_non_fungibles(&self, keys: &BTreeSet<NonFungibleKey>) -> BucketRef {
+        let input = CreateBucketRefForNonFungiblesInput {
+            bid: self.bid,
+            keys: keys.clone(),
+        };
+        let output: CreateBucketRefForNonFungiblesOutput =
+            call_engine(CREATE_BUCKET_REF_FOR_NON_FUNGIBLES, input);
+
+        output.rid.into()
+    }
+
     /// Returns the amount of resources in this bucket.
     pub fn amount(&self) -> Decimal {
         let input = GetBucketDecimalInput { bid: self.bid };
@@ -102,6 +136,25 @@ impl Bucket {
         self.amount() == 0.into()
     }
 
+    /// Drops this bucket.
+    ///
+    /// # Panics
+    /// Panics if this bucket is not empty.
+    pub fn drop_empty(self) {
+        let input = DropEmptyBucketInput { bid: self.bid };
+        let _: DropEmptyBucketOutput = call_engine(DROP_EMPTY_BUCKET, input);
+    }
+
+    /// Returns `None` and drops this bucket if it is empty, or `Some(self)` otherwise.
+    pub fn into_option(self) -> Option<Bucket> {
+        if self.is_empty() {
+            self.drop_empty();
+            None
+        } else {
+            Some(self)
+        }
+    }
+
     /// Uses resources in this bucket as authorization for an operation.
     pub fn authorize<F: FnOnce(BucketRef) -> O, O>(&self, f: F) -> O {
         f(self.present())
@@ -127,14 +180,43 @@ impl Bucket {
     /// # Panics
     /// Panics if this is not a non-fungible bucket.
     pub fn get_non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>> {
-        let input = GetNonFungibleKeysInBucketInput { bid: self.bid };
-        let output: GetNonFungibleKeysInBucketOutput =
-            call_engine(GET_NON_FUNGIBLE_KEYS_IN_BUCKET, input);
-        let resource_address = self.resource_address();
+        let (output, resource): (
+            GetNonFungibleKeysInBucketOutput,
+            GetBucketResourceAddressOutput,
+        ) = call_engine_pair(
+            (
+                GET_NON_FUNGIBLE_KEYS_IN_BUCKET,
+                GetNonFungibleKeysInBucketInput { bid: self.bid },
+            ),
+            (
+                GET_BUCKET_RESOURCE_ADDRESS,
+                GetBucketResourceAddressInput { bid: self.bid },
+            ),
+        );
         output
             .keys
-            .iter()
-            .map(|id| NonFungible::from((resource_address, id.clone())))
+            .into_iter()
+            .map(|id| NonFungible::from((resource.resource_address, id)))
+            .collect()
+    }
+
+    /// Returns the keys and data of all non-fungibles in this bucket, in a single engine call.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible bucket.
+    pub fn non_fungibles_with_data<T: NonFungibleData>(&self) -> Vec<(NonFungibleKey, T)> {
+        let input = GetNonFungiblesInBucketInput { bid: self.bid };
+        let output: GetNonFungiblesInBucketOutput = call_engine(GET_NON_FUNGIBLES_IN_BUCKET, input);
+
+        output
+            .non_fungibles
+            .into_iter()
+            .map(|(key, immutable_data, mutable_data)| {
+                (
+                    key,
+                    scrypto_unwrap(T::decode(&immutable_data, &mutable_data)),
+                )
+            })
             .collect()
     }
 
@@ -152,6 +234,14 @@ impl Bucket {
         keys[0].clone()
     }
 
+    /// Returns the address of a singleton non-fungible.
+    ///
+    /// # Panic
+    /// If this bucket is empty or contains more than one non-fungibles.
+    pub fn get_non_fungible_address(&self) -> NonFungibleAddress {
+        NonFungibleAddress::new(self.resource_address(), self.get_non_fungible_key())
+    }
+
     /// Returns the keys of all non-fungibles in this bucket.
     ///
     /// # Panics
@@ -193,7 +283,7 @@ impl Bucket {
 
 impl TypeId for Bucket {
     fn type_id() -> u8 {
-        Bid::type_id()
+        BucketId::type_id()
     }
 }
 
@@ -205,7 +295,7 @@ impl Encode for Bucket {
 
 impl Decode for Bucket {
     fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
-        Bid::decode_value(decoder).map(Into::into)
+        BucketId::decode_value(decoder).map(Into::into)
     }
 }
 
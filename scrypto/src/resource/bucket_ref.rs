@@ -36,6 +36,22 @@ impl Clone for BucketRef {
 }
 
 impl BucketRef {
+    /// Combines bucket refs into a composite one, which proves all of them at once, e.g. to
+    /// present several badges as a single `auth` argument.
+    ///
+    /// A composite bucket ref contains whatever any of its bucket refs contains, but can't be
+    /// used where a single bucket is referenced, e.g. for `amount` or `resource_def`.
+    ///
+    /// # Panics
+    /// If no bucket refs are given, or any of them is empty.
+    pub fn compose(bucket_refs: Vec<BucketRef>) -> Self {
+        let input = ComposeBucketRefsInput {
+            rids: bucket_refs.into_iter().map(Into::into).collect(),
+        };
+        let output: ComposeBucketRefsOutput = call_engine(COMPOSE_BUCKET_REFS, input);
+        output.rid.into()
+    }
+
     /// Checks if the referenced bucket contains the given resource, and aborts if not so.
     pub fn check<A: Into<ResourceDef>>(self, resource_def: A) {
         if !self.contains(resource_def) {
@@ -53,10 +69,84 @@ impl BucketRef {
         }
     }
 
-    /// Checks if the referenced bucket contains the given resource.
+    /// Checks if the referenced bucket contains the given non-fungible, and aborts if not so.
+    pub fn check_non_fungible(self, non_fungible_address: &NonFungibleAddress) {
+        if !self.contains_non_fungible(non_fungible_address) {
+            panic!("BucketRef check failed");
+        }
+    }
+
+    /// Checks if the referenced bucket contains the given resource and the resource metadata
+    /// has `key` set to `value`, and aborts if not so.
+    pub fn check_metadata<A: Into<ResourceDef>>(self, resource_def: A, key: &str, value: &str) {
+        if !self.contains(resource_def) || !self.has_metadata(key, value) {
+            panic!("BucketRef check failed");
+        }
+    }
+
+    /// Checks if the referenced bucket contains the given non-transferable resource, held by
+    /// the given component, and aborts if not so.
+    pub fn check_owned_by<A: Into<ResourceDef>>(self, resource_def: A, owner: Address) {
+        if !self.is_owned_by(resource_def, owner) {
+            panic!("BucketRef check failed");
+        }
+    }
+
+    /// Checks if the referenced bucket contains the given resource, the resource has
+    /// `RESTRICTED_TRANSFER` on, and the reference was created from a vault of the given
+    /// component, e.g. an account.
+    ///
+    /// Unlike `contains`, this can't be satisfied by a badge borrowed for the occasion, as
+    /// such a badge can't be moved into another component's vault without transfer auth.
+    pub fn is_owned_by<A: Into<ResourceDef>>(&self, resource_def: A, owner: Address) -> bool {
+        let resource_def: ResourceDef = resource_def.into();
+        self.origin() == Some(owner)
+            && self.contains(resource_def.clone())
+            && resource_def.flags() & RESTRICTED_TRANSFER != 0
+    }
+
+    /// Returns the component whose vault the referenced resource is held in, if this
+    /// reference was created with `Vault::present`.
+    pub fn origin(&self) -> Option<Address> {
+        let input = GetBucketRefOriginInput { rid: self.rid };
+        let output: GetBucketRefOriginOutput = call_engine(GET_BUCKET_REF_ORIGIN, input);
+
+        output.origin
+    }
+
+    /// Checks if the referenced bucket, or any bucket referenced by a composite bucket ref,
+    /// contains the given resource.
     pub fn contains<A: Into<ResourceDef>>(&self, resource_def: A) -> bool {
         let resource_def: ResourceDef = resource_def.into();
-        self.amount() > 0.into() && self.resource_def() == resource_def
+        let input = CheckBucketRefContainsInput {
+            rid: self.rid,
+            resource_address: resource_def.address(),
+        };
+        let output: CheckBucketRefContainsOutput = call_engine(CHECK_BUCKET_REF_CONTAINS, input);
+        output.contains
+    }
+
+    /// Checks if the referenced bucket contains the given non-fungible.
+    pub fn contains_non_fungible(&self, non_fungible_address: &NonFungibleAddress) -> bool {
+        self.contains(non_fungible_address.resource_address())
+            && self
+                .get_non_fungible_keys()
+                .contains(&non_fungible_address.key())
+    }
+
+    /// Checks if the referenced bucket is not empty and the metadata of its resource has
+    /// `key` set to `value`.
+    ///
+    /// The lookup is done by the engine, so no metadata is copied into the blueprint.
+    pub fn has_metadata(&self, key: &str, value: &str) -> bool {
+        let input = CheckBucketRefMetadataInput {
+            rid: self.rid,
+            key: key.to_owned(),
+            value: value.to_owned(),
+        };
+        let output: CheckBucketRefMetadataOutput = call_engine(CHECK_BUCKET_REF_METADATA, input);
+
+        output.matches
     }
 
     /// Returns the resource amount within the bucket.
@@ -95,6 +185,14 @@ impl BucketRef {
         keys[0].clone()
     }
 
+    /// Returns the address of a singleton non-fungible.
+    ///
+    /// # Panic
+    /// If the bucket is empty or contains more than one non-fungibles.
+    pub fn get_non_fungible_address(&self) -> NonFungibleAddress {
+        NonFungibleAddress::new(self.resource_address(), self.get_non_fungible_key())
+    }
+
     /// Returns the keys of all non-fungibles in this bucket.
     ///
     /// # Panics
@@ -119,6 +217,31 @@ impl BucketRef {
     }
 }
 
+/// A badge which `#[auth]` methods may require callers to present, held in a field of the
+/// component state.
+pub trait AuthBadge {
+    /// Checks if the bucket ref presents this badge.
+    fn is_presented_by(&self, bucket_ref: &BucketRef) -> bool;
+}
+
+impl AuthBadge for Address {
+    fn is_presented_by(&self, bucket_ref: &BucketRef) -> bool {
+        bucket_ref.contains(*self)
+    }
+}
+
+impl AuthBadge for ResourceDef {
+    fn is_presented_by(&self, bucket_ref: &BucketRef) -> bool {
+        bucket_ref.contains(self.clone())
+    }
+}
+
+impl AuthBadge for NonFungibleAddress {
+    fn is_presented_by(&self, bucket_ref: &BucketRef) -> bool {
+        bucket_ref.contains_non_fungible(self)
+    }
+}
+
 //========
 // SBOR
 //========
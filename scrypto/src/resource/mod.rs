@@ -13,10 +13,14 @@ pub mod resource_flags;
 pub mod resource_permissions;
 
 pub use bucket::Bucket;
-pub use bucket_ref::BucketRef;
+pub use bucket_ref::{AuthBadge, BucketRef};
 pub use non_fungible::NonFungible;
-pub use non_fungible_data::NonFungibleData;
-pub use resource_builder::{ResourceBuilder, DIVISIBILITY_MAXIMUM, DIVISIBILITY_NONE};
+pub use non_fungible_data::{NonFungibleData, RawNonFungibleData};
+pub use resource_builder::{
+    ResourceBuilder, DESCRIPTION_METADATA_KEY, DIVISIBILITY_MAXIMUM, DIVISIBILITY_NONE,
+    ICON_URL_METADATA_KEY, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, NAME_METADATA_KEY,
+    SYMBOL_METADATA_KEY, URL_METADATA_KEYS,
+};
 pub use resource_def::ResourceDef;
 pub use resource_flags::*;
 pub use resource_permissions::*;
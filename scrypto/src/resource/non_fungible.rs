@@ -20,7 +20,22 @@ impl<T: NonFungibleData> From<(Address, NonFungibleKey)> for NonFungible<T> {
     }
 }
 
+impl<T: NonFungibleData> From<NonFungibleAddress> for NonFungible<T> {
+    fn from(address: NonFungibleAddress) -> Self {
+        Self {
+            resource_address: address.resource_address(),
+            key: address.key(),
+            data: PhantomData,
+        }
+    }
+}
+
 impl<T: NonFungibleData> NonFungible<T> {
+    /// Returns the address of this unit.
+    pub fn address(&self) -> NonFungibleAddress {
+        NonFungibleAddress::new(self.resource_address, self.key.clone())
+    }
+
     /// Returns the resource address.
     pub fn resource_address(&self) -> Address {
         self.resource_address
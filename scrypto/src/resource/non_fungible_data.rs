@@ -1,5 +1,6 @@
 use sbor::{describe::*, *};
 
+use crate::rust::borrow::ToOwned;
 use crate::rust::vec::Vec;
 
 /// Represents the data structure of a non-fungible.
@@ -21,3 +22,59 @@ pub trait NonFungibleData {
     /// Returns the schema of the mutable data.
     fn mutable_data_schema() -> Type;
 }
+
+/// The serialized immutable and mutable parts of a non-fungible, whose schema is unknown.
+///
+/// Blueprint methods generic over `T: NonFungibleData` are exported with `T` replaced by this type.
+#[derive(Debug, Clone, PartialEq, Eq, TypeId, Encode, Decode, Describe)]
+pub struct RawNonFungibleData {
+    pub immutable_data: Vec<u8>,
+    pub mutable_data: Vec<u8>,
+}
+
+impl RawNonFungibleData {
+    /// Serializes the given non-fungible data.
+    pub fn new<T: NonFungibleData>(data: &T) -> Self {
+        Self {
+            immutable_data: data.immutable_data(),
+            mutable_data: data.mutable_data(),
+        }
+    }
+
+    /// Deserializes into the given non-fungible data type.
+    pub fn to_data<T: NonFungibleData>(&self) -> Result<T, DecodeError> {
+        T::decode(&self.immutable_data, &self.mutable_data)
+    }
+
+    fn unknown_schema() -> Type {
+        Type::Struct {
+            name: "RawNonFungibleData".to_owned(),
+            fields: Fields::Unit,
+        }
+    }
+}
+
+impl NonFungibleData for RawNonFungibleData {
+    fn decode(immutable_data: &[u8], mutable_data: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            immutable_data: immutable_data.to_vec(),
+            mutable_data: mutable_data.to_vec(),
+        })
+    }
+
+    fn immutable_data(&self) -> Vec<u8> {
+        self.immutable_data.clone()
+    }
+
+    fn mutable_data(&self) -> Vec<u8> {
+        self.mutable_data.clone()
+    }
+
+    fn immutable_data_schema() -> Type {
+        Self::unknown_schema()
+    }
+
+    fn mutable_data_schema() -> Type {
+        Self::unknown_schema()
+    }
+}
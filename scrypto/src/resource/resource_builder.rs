@@ -9,6 +9,20 @@ use crate::types::*;
 pub const DIVISIBILITY_NONE: u8 = 0;
 /// The maximum divisibility supported.
 pub const DIVISIBILITY_MAXIMUM: u8 = 18;
+/// The metadata keys whose values must be valid `Url`s.
+pub const URL_METADATA_KEYS: [&str; 2] = ["url", ICON_URL_METADATA_KEY];
+/// The metadata key of the short ticker of a resource, e.g. `XRD`.
+pub const SYMBOL_METADATA_KEY: &str = "symbol";
+/// The metadata key of the human-readable name of a resource.
+pub const NAME_METADATA_KEY: &str = "name";
+/// The metadata key of the description of a resource.
+pub const DESCRIPTION_METADATA_KEY: &str = "description";
+/// The metadata key of the image wallets display for a resource.
+pub const ICON_URL_METADATA_KEY: &str = "icon_url";
+/// The maximum length of a symbol, which may only contain ASCII letters and digits.
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+/// The maximum length of a name, in characters, which may not contain control characters.
+pub const MAX_NAME_LENGTH: usize = 100;
 
 /// Utility for creating resources.
 pub struct ResourceBuilder {
@@ -17,6 +31,8 @@ pub struct ResourceBuilder {
     flags: u64,
     mutable_flags: u64,
     authorities: HashMap<Address, u64>,
+    soulbound: bool,
+    deposit_restricted: bool,
 }
 
 impl ResourceBuilder {
@@ -28,6 +44,8 @@ impl ResourceBuilder {
             flags: 0,
             mutable_flags: 0,
             authorities: HashMap::new(),
+            soulbound: false,
+            deposit_restricted: false,
         }
     }
 
@@ -44,6 +62,18 @@ impl ResourceBuilder {
         Self::new(ResourceType::NonFungible)
     }
 
+    /// Starts a new builder to create a badge, i.e. a fungible resource that is not divisible.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let admin_badge = ResourceBuilder::new_badge()
+    ///     .metadata("name", "Admin Badge")
+    ///     .initial_supply_fungible(1);
+    /// ```
+    pub fn new_badge() -> Self {
+        Self::new_fungible(DIVISIBILITY_NONE)
+    }
+
     /// Adds a shared metadata.
     ///
     /// If a previous attribute with the same name has been set, it will be overwritten.
@@ -53,6 +83,16 @@ impl ResourceBuilder {
         self
     }
 
+    /// Sets the `url` metadata, e.g. the website of the project behind the resource.
+    pub fn url(&mut self, url: Url) -> &mut Self {
+        self.metadata("url", url)
+    }
+
+    /// Sets the `icon_url` metadata, i.e. the image wallets display for the resource.
+    pub fn icon_url(&mut self, url: Url) -> &mut Self {
+        self.metadata(ICON_URL_METADATA_KEY, url)
+    }
+
     /// Sets the feature flags.
     pub fn flags(&mut self, flags: u64) -> &mut Self {
         self.flags = flags;
@@ -72,6 +112,22 @@ impl ResourceBuilder {
         self
     }
 
+    /// Makes the resource soulbound, i.e. it can never be taken out of the vault it's put in.
+    ///
+    /// `RESTRICTED_TRANSFER` is turned on and can't be turned off, and no badge is granted
+    /// `MAY_TRANSFER`.
+    pub fn soulbound(&mut self) -> &mut Self {
+        self.soulbound = true;
+        self
+    }
+
+    /// Restricts the resource to the vaults of components allowed by a badge with the
+    /// `MAY_MANAGE_DEPOSITORS` permission, see `ResourceDef::allow_depositor`.
+    pub fn restrict_deposit(&mut self) -> &mut Self {
+        self.deposit_restricted = true;
+        self
+    }
+
     /// Creates resource with the given initial supply.
     pub fn initial_supply(&self, supply: NewSupply) -> Bucket {
         self.build(Some(supply)).1.unwrap()
@@ -116,12 +172,26 @@ impl ResourceBuilder {
     }
 
     fn build(&self, supply: Option<NewSupply>) -> (ResourceDef, Option<Bucket>) {
+        let mut flags = self.flags;
+        let mut mutable_flags = self.mutable_flags;
+        let mut authorities = self.authorities.clone();
+        if self.soulbound {
+            flags |= RESTRICTED_TRANSFER;
+            mutable_flags &= !RESTRICTED_TRANSFER;
+            for permissions in authorities.values_mut() {
+                *permissions &= !MAY_TRANSFER;
+            }
+        }
+        if self.deposit_restricted {
+            flags |= RESTRICTED_DEPOSIT;
+        }
+
         ResourceDef::new(
             self.resource_type,
             self.metadata.clone(),
-            self.flags,
-            self.mutable_flags,
-            self.authorities.clone(),
+            flags,
+            mutable_flags,
+            authorities,
             supply,
         )
     }
@@ -132,6 +132,29 @@ impl ResourceDef {
         output.metadata
     }
 
+    /// Returns the `symbol` metadata, e.g. `XRD`, if set.
+    pub fn symbol(&self) -> Option<String> {
+        self.metadata().remove(SYMBOL_METADATA_KEY)
+    }
+
+    /// Returns the `name` metadata, if set.
+    pub fn name(&self) -> Option<String> {
+        self.metadata().remove(NAME_METADATA_KEY)
+    }
+
+    /// Returns the `description` metadata, if set.
+    pub fn description(&self) -> Option<String> {
+        self.metadata().remove(DESCRIPTION_METADATA_KEY)
+    }
+
+    /// Returns the `icon_url` metadata, if set.
+    pub fn icon_url(&self) -> Option<Url> {
+        // validated by the engine when set
+        self.metadata()
+            .get(ICON_URL_METADATA_KEY)
+            .map(|url| url.parse().unwrap())
+    }
+
     /// Returns the feature flags.
     pub fn flags(&self) -> u64 {
         let input = GetResourceFlagsInput {
@@ -240,6 +263,78 @@ impl ResourceDef {
         };
         let _output: UpdateResourceMetadataOutput = call_engine(UPDATE_RESOURCE_METADATA, input);
     }
+
+    /// Recalls resources from a vault owned by any component.
+    ///
+    /// The resource must be `RECALLABLE` and the badge must have the `MAY_RECALL` permission.
+    pub fn recall<T: Into<Decimal>>(
+        &self,
+        component_address: Address,
+        vid: VaultId,
+        amount: T,
+        auth: BucketRef,
+    ) -> Bucket {
+        let input = RecallResourceInput {
+            resource_address: self.address,
+            component_address,
+            vid,
+            amount: amount.into(),
+            auth: auth.into(),
+        };
+        let output: RecallResourceOutput = call_engine(RECALL_RESOURCE, input);
+
+        output.bid.into()
+    }
+
+    /// Freezes a vault owned by any component, so that no resource can be put into or taken
+    /// from it until it's unfrozen.
+    ///
+    /// The resource must be `FREEZABLE` and the badge must have the `MAY_FREEZE` permission.
+    pub fn freeze_vault(&self, component_address: Address, vid: VaultId, auth: BucketRef) {
+        let input = FreezeVaultInput {
+            resource_address: self.address,
+            component_address,
+            vid,
+            auth: auth.into(),
+        };
+        let _output: FreezeVaultOutput = call_engine(FREEZE_VAULT, input);
+    }
+
+    /// Unfreezes a vault frozen by `freeze_vault`.
+    pub fn unfreeze_vault(&self, component_address: Address, vid: VaultId, auth: BucketRef) {
+        let input = UnfreezeVaultInput {
+            resource_address: self.address,
+            component_address,
+            vid,
+            auth: auth.into(),
+        };
+        let _output: UnfreezeVaultOutput = call_engine(UNFREEZE_VAULT, input);
+    }
+
+    /// Allows a component to hold this resource.
+    ///
+    /// Only matters if the resource is `RESTRICTED_DEPOSIT`; the badge must have the
+    /// `MAY_MANAGE_DEPOSITORS` permission.
+    pub fn allow_depositor(&self, component_address: Address, auth: BucketRef) {
+        self.update_allowed_depositor(component_address, true, auth)
+    }
+
+    /// Disallows a component from receiving more of this resource.
+    ///
+    /// Resources already held by the component are left in place.
+    pub fn disallow_depositor(&self, component_address: Address, auth: BucketRef) {
+        self.update_allowed_depositor(component_address, false, auth)
+    }
+
+    fn update_allowed_depositor(&self, component_address: Address, allowed: bool, auth: BucketRef) {
+        let input = UpdateAllowedDepositorInput {
+            resource_address: self.address,
+            component_address,
+            allowed,
+            auth: auth.into(),
+        };
+        let _output: UpdateAllowedDepositorOutput = call_engine(UPDATE_ALLOWED_DEPOSITOR, input);
+    }
 }
 
 //========
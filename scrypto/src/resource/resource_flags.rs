@@ -24,7 +24,7 @@ pub const FREELY_BURNABLE: u64 = resource_flags!(1u32 << 2);
 /// New supply can be minted.
 pub const MINTABLE: u64 = resource_flags!(1u32 << 3);
 
-/// (Not implemented) Resource can be seized from any vault if proper authority is presented.
+/// Resource can be recalled from any vault if proper authority is presented.
 pub const RECALLABLE: u64 = resource_flags!(1u32 << 4);
 
 /// Top-level resource metadata can be changed.
@@ -33,5 +33,11 @@ pub const SHARED_METADATA_MUTABLE: u64 = resource_flags!(1u32 << 5);
 /// The mutable data part of an individual non-fungible can be modified.
 pub const INDIVIDUAL_METADATA_MUTABLE: u64 = resource_flags!(1u32 << 6);
 
+/// Resource can only be deposited into vaults of allowed components.
+pub const RESTRICTED_DEPOSIT: u64 = resource_flags!(1u32 << 7);
+
+/// Individual vaults holding the resource can be frozen if proper authority is presented.
+pub const FREEZABLE: u64 = resource_flags!(1u32 << 8);
+
 /// All resources flags.
 pub const ALL_FLAGS: u64 = resource_flags!(!0u32);
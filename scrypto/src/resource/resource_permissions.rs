@@ -21,7 +21,7 @@ pub const MAY_BURN: u64 = resource_permissions!(1u32 << 2);
 /// May create new supply.
 pub const MAY_MINT: u64 = resource_permissions!(1u32 << 4);
 
-/// (Not implemented) May seize from any vault.
+/// May recall resources from any vault.
 pub const MAY_RECALL: u64 = resource_permissions!(1u32 << 5);
 
 /// May change top-level resource metadata, e.g. name and symbol.
@@ -33,5 +33,11 @@ pub const MAY_CHANGE_INDIVIDUAL_METADATA: u64 = resource_permissions!(1u32 << 7)
 /// May change mutable flags.
 pub const MAY_MANAGE_RESOURCE_FLAGS: u64 = resource_permissions!(1u32 << 8);
 
+/// May change the components allowed to hold a `RESTRICTED_DEPOSIT` resource.
+pub const MAY_MANAGE_DEPOSITORS: u64 = resource_permissions!(1u32 << 9);
+
+/// May freeze and unfreeze vaults holding a `FREEZABLE` resource.
+pub const MAY_FREEZE: u64 = resource_permissions!(1u32 << 10);
+
 /// All permissions.
 pub const ALL_PERMISSIONS: u64 = resource_permissions!(!0u32);
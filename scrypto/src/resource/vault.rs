@@ -4,24 +4,26 @@ use crate::buffer::*;
 use crate::engine::*;
 use crate::resource::*;
 use crate::rust::borrow::ToOwned;
+use crate::rust::collections::BTreeSet;
 use crate::rust::vec;
 use crate::rust::vec::Vec;
 use crate::types::*;
+use crate::utils::*;
 
 /// Represents a persistent resource container on ledger state.
 #[derive(Debug)]
 pub struct Vault {
-    vid: Vid,
+    vid: VaultId,
 }
 
-impl From<Vid> for Vault {
-    fn from(vid: Vid) -> Self {
+impl From<VaultId> for Vault {
+    fn from(vid: VaultId) -> Self {
         Self { vid }
     }
 }
 
-impl From<Vault> for Vid {
-    fn from(a: Vault) -> Vid {
+impl From<Vault> for VaultId {
+    fn from(a: Vault) -> VaultId {
         a.vid
     }
 }
@@ -65,6 +67,42 @@ impl Vault {
         output.bid.into()
     }
 
+    /// Takes some amount of resource from this vault into a bucket, or returns `None` without
+    /// creating a bucket if the amount is zero.
+    pub fn take_or_none<A: Into<Decimal>>(&mut self, amount: A) -> Option<Bucket> {
+        let amount = amount.into();
+        if amount.is_zero() {
+            None
+        } else {
+            Some(self.take(amount))
+        }
+    }
+
+    /// Takes exactly the given amount of resource from this vault into a bucket.
+    ///
+    /// # Panics
+    /// Panics if the vault doesn't hold enough resource, or if the amount has more decimal
+    /// places than the divisibility of the resource allows, rather than rounding it.
+    pub fn take_exact<A: Into<Decimal>>(&mut self, amount: A) -> Bucket {
+        self.take(amount)
+    }
+
+    /// Takes as much resource as available from this vault, up to the given amount, which is
+    /// rounded down to the divisibility of the resource.
+    ///
+    /// Returns the bucket taken, together with the amount that could not be covered.
+    pub fn take_up_to<A: Into<Decimal>>(&mut self, amount: A) -> (Bucket, Decimal) {
+        let amount = amount.into();
+        let input = TakeUpToFromVaultInput {
+            vid: self.vid,
+            amount,
+            auth: None,
+        };
+        let output: TakeUpToFromVaultOutput = call_engine(TAKE_UP_TO_FROM_VAULT, input);
+
+        (output.bid.into(), amount - output.amount)
+    }
+
     /// Takes some amount of resource from this vault into a bucket.
     ///
     /// This variant of `take` accepts an additional auth parameter to support resources
@@ -82,7 +120,13 @@ impl Vault {
 
     /// Takes all resource stored in this vault.
     pub fn take_all(&mut self) -> Bucket {
-        self.take(self.amount())
+        let input = TakeAllFromVaultInput {
+            vid: self.vid,
+            auth: None,
+        };
+        let output: TakeAllFromVaultOutput = call_engine(TAKE_ALL_FROM_VAULT, input);
+
+        output.bid.into()
     }
 
     /// Takes all resource stored in this vault.
@@ -90,7 +134,13 @@ impl Vault {
     /// This variant of `take_all` accepts an additional auth parameter to support resources
     /// with or without `RESTRICTED_TRANSFER` flag on.
     pub fn take_all_with_auth(&mut self, auth: BucketRef) -> Bucket {
-        self.take_with_auth(self.amount(), auth)
+        let input = TakeAllFromVaultInput {
+            vid: self.vid,
+            auth: Some(auth.into()),
+        };
+        let output: TakeAllFromVaultOutput = call_engine(TAKE_ALL_FROM_VAULT, input);
+
+        output.bid.into()
     }
 
     /// Takes a non-fungible from this vault, by id.
@@ -128,6 +178,47 @@ impl Vault {
         output.bid.into()
     }
 
+    /// Takes non-fungibles from this vault, by ids.
+    ///
+    /// Either all or none of the non-fungibles are taken.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault or any of the specified non-fungibles is not found.
+    pub fn take_non_fungibles(&mut self, keys: &BTreeSet<NonFungibleKey>) -> Bucket {
+        let input = TakeNonFungiblesFromVaultInput {
+            vid: self.vid,
+            keys: keys.clone(),
+            auth: None,
+        };
+        let output: TakeNonFungiblesFromVaultOutput =
+            call_engine(TAKE_NON_FUNGIBLES_FROM_VAULT, input);
+
+        output.bid.into()
+    }
+
+    /// Takes non-fungibles from this vault, by ids.
+    ///
+    /// This variant of `take_non_fungibles` accepts an additional auth parameter to support resources
+    /// with or without `RESTRICTED_TRANSFER` flag on.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault or any of the specified non-fungibles is not found.
+    pub fn take_non_fungibles_with_auth(
+        &mut self,
+        keys: &BTreeSet<NonFungibleKey>,
+        auth: BucketRef,
+    ) -> Bucket {
+        let input = TakeNonFungiblesFromVaultInput {
+            vid: self.vid,
+            keys: keys.clone(),
+            auth: Some(auth.into()),
+        };
+        let output: TakeNonFungiblesFromVaultOutput =
+            call_engine(TAKE_NON_FUNGIBLES_FROM_VAULT, input);
+
+        output.bid.into()
+    }
+
     /// This is a convenience method for using the contained resource for authorization.
     ///
     /// It conducts the following actions in one shot:
@@ -165,6 +256,19 @@ impl Vault {
         output
     }
 
+    /// Creates an immutable reference to all resources in this vault.
+    ///
+    /// Unlike taking resource out and presenting the bucket, this doesn't require transfer
+    /// auth for resources with `RESTRICTED_TRANSFER` on, and the reference records this
+    /// component as its origin, which the receiver can check with `BucketRef::is_owned_by`.
+    /// The vault is empty until the reference and all its clones are dropped.
+    pub fn present(&self) -> BucketRef {
+        let input = CreateVaultBucketRefInput { vid: self.vid };
+        let output: CreateVaultBucketRefOutput = call_engine(CREATE_VAULT_BUCKET_REF, input);
+
+        output.rid.into()
+    }
+
     /// Returns the amount of resources within this vault.
     pub fn amount(&self) -> Decimal {
         let input = GetVaultDecimalInput { vid: self.vid };
@@ -196,14 +300,23 @@ impl Vault {
     /// # Panics
     /// Panics if this is not a non-fungible vault.
     pub fn get_non_fungibles<T: NonFungibleData>(&self) -> Vec<NonFungible<T>> {
-        let input = GetNonFungibleKeysInVaultInput { vid: self.vid };
-        let output: GetNonFungibleKeysInVaultOutput =
-            call_engine(GET_NON_FUNGIBLE_KEYS_IN_VAULT, input);
-        let resource_address = self.resource_address();
+        let (output, resource): (
+            GetNonFungibleKeysInVaultOutput,
+            GetVaultResourceAddressOutput,
+        ) = call_engine_pair(
+            (
+                GET_NON_FUNGIBLE_KEYS_IN_VAULT,
+                GetNonFungibleKeysInVaultInput { vid: self.vid },
+            ),
+            (
+                GET_VAULT_RESOURCE_ADDRESS,
+                GetVaultResourceAddressInput { vid: self.vid },
+            ),
+        );
         output
             .keys
-            .iter()
-            .map(|id| NonFungible::from((resource_address, id.clone())))
+            .into_iter()
+            .map(|id| NonFungible::from((resource.resource_address, id)))
             .collect()
     }
 
@@ -219,6 +332,26 @@ impl Vault {
         output.keys
     }
 
+    /// Returns the keys and data of all non-fungibles in this vault, in a single engine call.
+    ///
+    /// # Panics
+    /// Panics if this is not a non-fungible vault.
+    pub fn non_fungibles_with_data<T: NonFungibleData>(&self) -> Vec<(NonFungibleKey, T)> {
+        let input = GetNonFungiblesInVaultInput { vid: self.vid };
+        let output: GetNonFungiblesInVaultOutput = call_engine(GET_NON_FUNGIBLES_IN_VAULT, input);
+
+        output
+            .non_fungibles
+            .into_iter()
+            .map(|(key, immutable_data, mutable_data)| {
+                (
+                    key,
+                    scrypto_unwrap(T::decode(&immutable_data, &mutable_data)),
+                )
+            })
+            .collect()
+    }
+
     /// Returns the key of a singleton non-fungible.
     ///
     /// # Panic
@@ -262,7 +395,7 @@ impl Vault {
 
 impl TypeId for Vault {
     fn type_id() -> u8 {
-        Vid::type_id()
+        VaultId::type_id()
     }
 }
 
@@ -274,7 +407,7 @@ impl Encode for Vault {
 
 impl Decode for Vault {
     fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
-        Vid::decode_value(decoder).map(Into::into)
+        VaultId::decode_value(decoder).map(Into::into)
     }
 }
 
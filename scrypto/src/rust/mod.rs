@@ -3,18 +3,26 @@ extern crate alloc;
 #[cfg(feature = "alloc")]
 pub use alloc::borrow;
 #[cfg(feature = "alloc")]
+pub use alloc::boxed;
+#[cfg(feature = "alloc")]
 pub use alloc::fmt;
 #[cfg(feature = "alloc")]
 pub use alloc::format;
 #[cfg(feature = "alloc")]
 pub use alloc::rc;
 #[cfg(feature = "alloc")]
+pub use alloc::slice;
+#[cfg(feature = "alloc")]
 pub use alloc::str;
 #[cfg(feature = "alloc")]
 pub use alloc::string;
 #[cfg(feature = "alloc")]
+pub use alloc::sync;
+#[cfg(feature = "alloc")]
 pub use alloc::vec;
 #[cfg(feature = "alloc")]
+pub use core::any;
+#[cfg(feature = "alloc")]
 pub use core::cell;
 #[cfg(feature = "alloc")]
 pub use core::convert;
@@ -27,9 +35,13 @@ pub use core::ops;
 #[cfg(feature = "alloc")]
 pub use core::ptr;
 
+#[cfg(not(feature = "alloc"))]
+pub use std::any;
 #[cfg(not(feature = "alloc"))]
 pub use std::borrow;
 #[cfg(not(feature = "alloc"))]
+pub use std::boxed;
+#[cfg(not(feature = "alloc"))]
 pub use std::cell;
 #[cfg(not(feature = "alloc"))]
 pub use std::convert;
@@ -48,10 +60,14 @@ pub use std::ptr;
 #[cfg(not(feature = "alloc"))]
 pub use std::rc;
 #[cfg(not(feature = "alloc"))]
+pub use std::slice;
+#[cfg(not(feature = "alloc"))]
 pub use std::str;
 #[cfg(not(feature = "alloc"))]
 pub use std::string;
 #[cfg(not(feature = "alloc"))]
+pub use std::sync;
+#[cfg(not(feature = "alloc"))]
 pub use std::vec;
 
 /// Collection types.
@@ -33,6 +33,16 @@ pub const ECDSA_TOKEN: Address = Address::ResourceDef([
     0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5,
 ]);
 
+/// The faucet component, which dispenses XRD for testing.
+pub const FAUCET_COMPONENT: Address = Address::Component([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 6,
+]);
+
+/// The staking component, which emulates staking XRD to validators for testing.
+pub const STAKING_COMPONENT: Address = Address::Component([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7,
+]);
+
 /// Represents an address.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Address {
@@ -46,6 +56,14 @@ pub enum Address {
     ResourceDef([u8; 26]),
 }
 
+/// The kind of entity an address refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressKind {
+    Package,
+    Component,
+    ResourceDef,
+}
+
 /// Represents an error when parsing Address.
 #[derive(Debug, Clone)]
 pub enum ParseAddressError {
@@ -72,6 +90,14 @@ impl Address {
         }
     }
 
+    pub fn kind(&self) -> AddressKind {
+        match self {
+            Self::Package(_) => AddressKind::Package,
+            Self::Component(_) => AddressKind::Component,
+            Self::ResourceDef(_) => AddressKind::ResourceDef,
+        }
+    }
+
     pub fn is_package(&self) -> bool {
         matches!(self, Address::Package(_))
     }
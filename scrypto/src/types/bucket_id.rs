@@ -0,0 +1,124 @@
+use sbor::{describe::Type, *};
+
+use crate::buffer::*;
+use crate::rust::borrow::ToOwned;
+use crate::rust::convert::TryFrom;
+use crate::rust::fmt;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+use crate::rust::string::ToString;
+use crate::rust::vec;
+use crate::rust::vec::Vec;
+use crate::types::*;
+
+/// Represents a bucket id.
+///
+/// Bucket ids are allocated in order within a transaction and are only meaningful in it.
+/// The string form is the decimal id, e.g. `1025`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BucketId(u32);
+
+/// Represents an error when parsing BucketId.
+#[derive(Debug, Clone)]
+pub enum ParseBucketIdError {
+    InvalidU32(String),
+    InvalidLength(usize),
+}
+
+impl fmt::Display for ParseBucketIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseBucketIdError {}
+
+impl BucketId {
+    /// Creates the id of the bucket allocated with the given id in a transaction.
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Returns the id allocated to the bucket within its transaction.
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.to_le_bytes().to_vec()
+    }
+}
+
+impl TryFrom<&[u8]> for BucketId {
+    type Error = ParseBucketIdError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() != 4 {
+            Err(ParseBucketIdError::InvalidLength(slice.len()))
+        } else {
+            Ok(Self(u32::from_le_bytes(copy_u8_array(slice))))
+        }
+    }
+}
+
+impl FromStr for BucketId {
+    type Err = ParseBucketIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        u32::from_str(s)
+            .map(Self)
+            .map_err(|_| ParseBucketIdError::InvalidU32(s.to_string()))
+    }
+}
+
+impl fmt::Display for BucketId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TypeId for BucketId {
+    #[inline]
+    fn type_id() -> u8 {
+        SCRYPTO_TYPE_BID
+    }
+}
+
+impl Encode for BucketId {
+    fn encode_value(&self, encoder: &mut Encoder) {
+        let bytes = self.to_vec();
+        encoder.write_len(bytes.len());
+        encoder.write_slice(&bytes);
+    }
+}
+
+impl Decode for BucketId {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        let slice = decoder.read_bytes(len)?;
+        Self::try_from(slice).map_err(|_| DecodeError::InvalidCustomData(SCRYPTO_TYPE_BID))
+    }
+}
+
+impl Describe for BucketId {
+    fn describe() -> Type {
+        Type::Custom {
+            name: SCRYPTO_NAME_BID.to_owned(),
+            generics: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_to_string() {
+        let bid = BucketId::from_str("1025").unwrap();
+        assert_eq!(bid, BucketId::new(1025));
+        assert_eq!(bid.to_string(), "1025");
+        assert!(BucketId::from_str("-1").is_err());
+    }
+}
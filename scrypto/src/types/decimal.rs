@@ -1,3 +1,4 @@
+use core::iter::{Product, Sum};
 use core::ops::*;
 
 use num_bigint::BigInt;
@@ -19,7 +20,8 @@ pub const PRECISION: i128 = 10i128.pow(18);
 
 /// Represents a **signed**, **bounded** fixed-point decimal, where the precision is 10^-18.
 ///
-/// Panic when there is an overflow.
+/// Panic when there is an overflow; use the `checked_*` or `saturating_*` methods to handle
+/// overflow instead. Results which don't fit in 18 decimal places are truncated towards zero.
 ///
 /// FIXME prevent RE from panicking caused by arithmetic overflow.
 ///
@@ -85,6 +87,119 @@ impl Decimal {
     pub fn abs(&self) -> Decimal {
         Decimal(self.0.abs())
     }
+
+    /// Adds another decimal, returning `None` on overflow.
+    pub fn checked_add<T: Into<Decimal>>(&self, other: T) -> Option<Decimal> {
+        self.0.checked_add(other.into().0).map(Decimal)
+    }
+
+    /// Subtracts another decimal, returning `None` on overflow.
+    pub fn checked_sub<T: Into<Decimal>>(&self, other: T) -> Option<Decimal> {
+        self.0.checked_sub(other.into().0).map(Decimal)
+    }
+
+    /// Multiplies by another decimal, returning `None` on overflow.
+    pub fn checked_mul<T: Into<Decimal>>(&self, other: T) -> Option<Decimal> {
+        let a = BigInt::from(self.0);
+        let b = BigInt::from(other.into().0);
+        big_int_to_decimal(a * b / PRECISION)
+    }
+
+    /// Divides by another decimal, returning `None` on overflow or division by zero.
+    pub fn checked_div<T: Into<Decimal>>(&self, other: T) -> Option<Decimal> {
+        let other = other.into();
+        if other.is_zero() {
+            return None;
+        }
+        let a = BigInt::from(self.0);
+        let b = BigInt::from(other.0);
+        big_int_to_decimal(a * PRECISION / b)
+    }
+
+    /// Adds another decimal, saturating at the bounds instead of overflowing.
+    pub fn saturating_add<T: Into<Decimal>>(&self, other: T) -> Decimal {
+        Decimal(self.0.saturating_add(other.into().0))
+    }
+
+    /// Subtracts another decimal, saturating at the bounds instead of overflowing.
+    pub fn saturating_sub<T: Into<Decimal>>(&self, other: T) -> Decimal {
+        Decimal(self.0.saturating_sub(other.into().0))
+    }
+
+    /// Multiplies by another decimal, saturating at the bounds instead of overflowing.
+    pub fn saturating_mul<T: Into<Decimal>>(&self, other: T) -> Decimal {
+        let other = other.into();
+        self.checked_mul(other)
+            .unwrap_or_else(|| Self::bound(self.is_negative() != other.is_negative()))
+    }
+
+    /// Divides by another decimal, saturating at the bounds instead of overflowing.
+    ///
+    /// # Panics
+    /// If `other` is zero.
+    pub fn saturating_div<T: Into<Decimal>>(&self, other: T) -> Decimal {
+        let other = other.into();
+        assert!(!other.is_zero(), "Division by zero");
+        self.checked_div(other)
+            .unwrap_or_else(|| Self::bound(self.is_negative() != other.is_negative()))
+    }
+
+    /// Raises to an integer power, returning `None` on overflow or if zero is raised to a
+    /// negative power.
+    ///
+    /// The power is computed by repeated squaring, where each intermediate product is
+    /// truncated to 18 decimal places, so results of large exponents may be off by a few
+    /// units in the last place.
+    pub fn checked_powi(&self, exp: i32) -> Option<Decimal> {
+        if exp < 0 {
+            return match self.checked_powi_unsigned(exp.unsigned_abs()) {
+                Some(pow) => Self::one().checked_div(pow),
+                // The inverse of anything beyond the bounds truncates to zero
+                None => Some(Self::zero()),
+            };
+        }
+        self.checked_powi_unsigned(exp as u32)
+    }
+
+    /// Raises to an integer power.
+    ///
+    /// # Panics
+    /// On overflow, or if zero is raised to a negative power.
+    pub fn powi(&self, exp: i32) -> Decimal {
+        self.checked_powi(exp).expect("Overflow")
+    }
+
+    /// Returns the square root, truncated to 18 decimal places, or `None` if negative.
+    pub fn sqrt(&self) -> Option<Decimal> {
+        if self.is_negative() {
+            return None;
+        }
+        let n = BigInt::from(self.0) * PRECISION;
+        big_int_to_decimal(n.sqrt())
+    }
+
+    fn checked_powi_unsigned(&self, mut exp: u32) -> Option<Decimal> {
+        let mut base = *self;
+        let mut result = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(result)
+    }
+
+    fn bound(negative: bool) -> Decimal {
+        if negative {
+            Self::MIN
+        } else {
+            Self::MAX
+        }
+    }
 }
 
 macro_rules! from_int {
@@ -130,6 +245,12 @@ impl From<bool> for Decimal {
     }
 }
 
+impl From<&Decimal> for Decimal {
+    fn from(val: &Decimal) -> Self {
+        *val
+    }
+}
+
 #[macro_export]
 macro_rules! dec {
     ($x:literal) => {
@@ -162,6 +283,14 @@ impl<T: Into<Decimal>> Add<T> for Decimal {
     }
 }
 
+impl<T: Into<Decimal>> Add<T> for &Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: T) -> Self::Output {
+        *self + other
+    }
+}
+
 //=====
 // Sub
 //=====
@@ -174,14 +303,22 @@ impl<T: Into<Decimal>> Sub<T> for Decimal {
     }
 }
 
+impl<T: Into<Decimal>> Sub<T> for &Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: T) -> Self::Output {
+        *self - other
+    }
+}
+
 //=====
 // Mul
 //=====
 
-fn big_int_to_decimal(v: BigInt) -> Decimal {
+fn big_int_to_decimal(v: BigInt) -> Option<Decimal> {
     let bytes = v.to_signed_bytes_le();
     if bytes.len() > 16 {
-        panic!("Overflow");
+        None
     } else {
         let mut buf = if v.is_negative() {
             [255u8; 16]
@@ -189,7 +326,7 @@ fn big_int_to_decimal(v: BigInt) -> Decimal {
             [0u8; 16]
         };
         buf[..bytes.len()].copy_from_slice(&bytes);
-        Decimal(i128::from_le_bytes(buf))
+        Some(Decimal(i128::from_le_bytes(buf)))
     }
 }
 
@@ -197,10 +334,15 @@ impl<T: Into<Decimal>> Mul<T> for Decimal {
     type Output = Decimal;
 
     fn mul(self, other: T) -> Self::Output {
-        let a = BigInt::from(self.0);
-        let b = BigInt::from(other.into().0);
-        let c = a * b / PRECISION;
-        big_int_to_decimal(c)
+        self.checked_mul(other).expect("Overflow")
+    }
+}
+
+impl<T: Into<Decimal>> Mul<T> for &Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: T) -> Self::Output {
+        *self * other
     }
 }
 
@@ -212,10 +354,17 @@ impl<T: Into<Decimal>> Div<T> for Decimal {
     type Output = Decimal;
 
     fn div(self, other: T) -> Self::Output {
-        let a = BigInt::from(self.0);
-        let b = BigInt::from(other.into().0);
-        let c = a * PRECISION / b;
-        big_int_to_decimal(c)
+        let other = other.into();
+        assert!(!other.is_zero(), "Division by zero");
+        self.checked_div(other).expect("Overflow")
+    }
+}
+
+impl<T: Into<Decimal>> Div<T> for &Decimal {
+    type Output = Decimal;
+
+    fn div(self, other: T) -> Self::Output {
+        *self / other
     }
 }
 
@@ -231,6 +380,14 @@ impl Neg for Decimal {
     }
 }
 
+impl Neg for &Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
 //===========
 // AddAssign
 //===========
@@ -271,6 +428,22 @@ impl<T: Into<Decimal>> DivAssign<T> for Decimal {
     }
 }
 
+//=============
+// Sum/Product
+//=============
+
+impl<T: Into<Decimal>> Sum<T> for Decimal {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Decimal::zero(), |sum, x| sum + x)
+    }
+}
+
+impl<T: Into<Decimal>> Product<T> for Decimal {
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Decimal::one(), |product, x| product * x)
+    }
+}
+
 fn read_digit(c: char) -> Result<i128, ParseDecimalError> {
     let n = c as i128;
     if n >= 48 && n <= 48 + 9 {
@@ -493,6 +666,71 @@ mod tests {
         assert_eq!((b - a).to_string(), "2");
     }
 
+    #[test]
+    fn test_checked_add_and_sub() {
+        let a = Decimal::from(5u32);
+        assert_eq!(a.checked_add(7).unwrap().to_string(), "12");
+        assert_eq!(a.checked_sub(7).unwrap().to_string(), "-2");
+        assert_eq!(Decimal::MAX.checked_add(Decimal(1)), None);
+        assert_eq!(Decimal::MIN.checked_sub(Decimal(1)), None);
+    }
+
+    #[test]
+    fn test_checked_mul_and_div() {
+        let a = Decimal::from(5u32);
+        assert_eq!(a.checked_mul(7).unwrap().to_string(), "35");
+        assert_eq!(a.checked_div(-2).unwrap().to_string(), "-2.5");
+        assert_eq!(a.checked_div(0), None);
+        assert_eq!(Decimal::MAX.checked_mul(2), None);
+        assert_eq!(Decimal::MIN.checked_div(dec!("0.5")), None);
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let a = Decimal::from(5u32);
+        assert_eq!(a.saturating_add(7).to_string(), "12");
+        assert_eq!(a.saturating_sub(7).to_string(), "-2");
+        assert_eq!(a.saturating_mul(7).to_string(), "35");
+        assert_eq!(a.saturating_div(2).to_string(), "2.5");
+        assert_eq!(Decimal::MAX.saturating_add(1), Decimal::MAX);
+        assert_eq!(Decimal::MIN.saturating_sub(1), Decimal::MIN);
+        assert_eq!(Decimal::MAX.saturating_mul(-2), Decimal::MIN);
+        assert_eq!(Decimal::MIN.saturating_mul(-2), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_div(dec!("0.5")), Decimal::MAX);
+        assert_eq!(Decimal::MAX.saturating_div(dec!("-0.5")), Decimal::MIN);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero")]
+    fn test_saturating_div_by_zero() {
+        Decimal::from(5u32).saturating_div(0);
+    }
+
+    #[test]
+    fn test_powi() {
+        assert_eq!(Decimal::from(2).powi(10).to_string(), "1024");
+        assert_eq!(Decimal::from(-3).powi(3).to_string(), "-27");
+        assert_eq!(Decimal::from(7).powi(0).to_string(), "1");
+        assert_eq!(Decimal::from(2).powi(-2).to_string(), "0.25");
+        assert_eq!(dec!("1.05").powi(2).to_string(), "1.1025");
+        assert_eq!(Decimal::from(10).powi(-19), Decimal::zero());
+        assert_eq!(Decimal::from(10).checked_powi(21), None);
+        assert_eq!(Decimal::zero().checked_powi(-1), None);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(Decimal::from(16).sqrt().unwrap().to_string(), "4");
+        assert_eq!(dec!("2.25").sqrt().unwrap().to_string(), "1.5");
+        assert_eq!(
+            Decimal::from(2).sqrt().unwrap().to_string(),
+            "1.414213562373095048"
+        );
+        assert_eq!(Decimal::zero().sqrt().unwrap(), Decimal::zero());
+        assert_eq!(Decimal::from(-1).sqrt(), None);
+        assert!(Decimal::MAX.sqrt().is_some());
+    }
+
     #[test]
     fn test_mul() {
         let a = Decimal::from(5u32);
@@ -526,6 +764,48 @@ mod tests {
         assert_eq!((a / b).to_string(), "-21");
     }
 
+    #[test]
+    fn test_ops_on_references() {
+        let values = [Decimal::from(5u32), Decimal::from(2u32)];
+        let (a, b) = (&values[0], &values[1]);
+        assert_eq!((a + b).to_string(), "7");
+        assert_eq!((a - *b).to_string(), "3");
+        assert_eq!((*a * b).to_string(), "10");
+        assert_eq!((a / 2).to_string(), "2.5");
+        assert_eq!((-a).to_string(), "-5");
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut a = Decimal::from(5u32);
+        let b = Decimal::from(2u32);
+        a += &b;
+        assert_eq!(a.to_string(), "7");
+        a -= 1;
+        assert_eq!(a.to_string(), "6");
+        a *= &b;
+        assert_eq!(a.to_string(), "12");
+        a /= b;
+        assert_eq!(a.to_string(), "6");
+    }
+
+    #[test]
+    fn test_sum_and_product() {
+        let values = vec![
+            Decimal::from(2u32),
+            Decimal::from(3u32),
+            Decimal::from(4u32),
+        ];
+        assert_eq!(values.iter().sum::<Decimal>().to_string(), "9");
+        assert_eq!(values.iter().product::<Decimal>().to_string(), "24");
+        assert_eq!(values.into_iter().sum::<Decimal>().to_string(), "9");
+        assert_eq!(
+            Vec::<Decimal>::new().iter().sum::<Decimal>(),
+            Decimal::zero()
+        );
+        assert_eq!((1..=3).product::<Decimal>().to_string(), "6");
+    }
+
     #[test]
     fn test_one_and_zero() {
         assert_eq!(Decimal::one().to_string(), "1");
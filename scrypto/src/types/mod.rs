@@ -1,29 +1,35 @@
 mod actor;
 mod address;
-mod bid;
 mod big_decimal;
+mod bucket_id;
 mod decimal;
 mod ecdsa_key;
 mod h256;
 mod mid;
+mod non_fungible_address;
 mod non_fungible_key;
 mod rid;
-mod vid;
+mod url;
+mod vault_id;
+mod well_known_address;
 
 pub use actor::Actor;
 pub use address::{
-    Address, ParseAddressError, ACCOUNT_PACKAGE, ECDSA_TOKEN, RADIX_TOKEN, SYSTEM_COMPONENT,
-    SYSTEM_PACKAGE,
+    Address, AddressKind, ParseAddressError, ACCOUNT_PACKAGE, ECDSA_TOKEN, FAUCET_COMPONENT,
+    RADIX_TOKEN, STAKING_COMPONENT, SYSTEM_COMPONENT, SYSTEM_PACKAGE,
 };
-pub use bid::{Bid, ParseBidError};
 pub use big_decimal::{BigDecimal, ParseBigDecimalError};
+pub use bucket_id::{BucketId, ParseBucketIdError};
 pub use decimal::{Decimal, ParseDecimalError};
 pub use ecdsa_key::EcdsaPublicKey;
 pub use h256::{ParseH256Error, H256};
 pub use mid::{Mid, ParseMidError};
+pub use non_fungible_address::{NonFungibleAddress, ParseNonFungibleAddressError};
 pub use non_fungible_key::{NonFungibleKey, ParseNonFungibleKeyError};
 pub use rid::{ParseRidError, Rid};
-pub use vid::{ParseVidError, Vid};
+pub use url::{ParseUrlError, Url, URL_MAX_LENGTH};
+pub use vault_id::{ParseVaultIdError, VaultId};
+pub use well_known_address::WellKnownAddress;
 
 use crate::rust::vec::Vec;
 
@@ -0,0 +1,176 @@
+use sbor::{describe::Type, *};
+
+use crate::buffer::*;
+use crate::rust::borrow::ToOwned;
+use crate::rust::convert::TryFrom;
+use crate::rust::fmt;
+use crate::rust::str::FromStr;
+use crate::rust::vec;
+use crate::rust::vec::Vec;
+use crate::types::*;
+
+/// Represents the address of a non-fungible unit, i.e. its resource definition address and
+/// its key.
+///
+/// The string form is the hex encoding of `to_vec()`, i.e. the resource definition address
+/// followed by the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonFungibleAddress {
+    resource_address: Address,
+    key: NonFungibleKey,
+}
+
+/// Represents an error when parsing NonFungibleAddress.
+#[derive(Debug, Clone)]
+pub enum ParseNonFungibleAddressError {
+    InvalidHex(hex::FromHexError),
+    InvalidLength(usize),
+    InvalidResourceDefAddress(ParseAddressError),
+    NotResourceDefAddress(Address),
+    InvalidNonFungibleKey(ParseNonFungibleKeyError),
+}
+
+impl fmt::Display for ParseNonFungibleAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseNonFungibleAddressError {}
+
+impl NonFungibleAddress {
+    /// Creates the address of the non-fungible with the given key.
+    ///
+    /// # Panics
+    /// If `resource_address` is not a resource definition address.
+    pub fn new(resource_address: Address, key: NonFungibleKey) -> Self {
+        assert!(
+            resource_address.is_resource_def(),
+            "Not a resource definition address: {}",
+            resource_address
+        );
+        Self {
+            resource_address,
+            key,
+        }
+    }
+
+    /// Returns the address of the non-fungible badge representing a transaction signer.
+    pub fn from_public_key(public_key: &EcdsaPublicKey) -> Self {
+        Self::new(ECDSA_TOKEN, NonFungibleKey::new(public_key.to_vec()))
+    }
+
+    /// Returns the resource definition address.
+    pub fn resource_address(&self) -> Address {
+        self.resource_address
+    }
+
+    /// Returns the non-fungible key.
+    pub fn key(&self) -> NonFungibleKey {
+        self.key.clone()
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut vec = self.resource_address.to_vec();
+        vec.extend(self.key.to_vec());
+        vec
+    }
+}
+
+impl TryFrom<&[u8]> for NonFungibleAddress {
+    type Error = ParseNonFungibleAddressError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        if slice.len() < 27 {
+            return Err(ParseNonFungibleAddressError::InvalidLength(slice.len()));
+        }
+        let resource_address = Address::try_from(&slice[..27])
+            .map_err(ParseNonFungibleAddressError::InvalidResourceDefAddress)?;
+        if !resource_address.is_resource_def() {
+            return Err(ParseNonFungibleAddressError::NotResourceDefAddress(
+                resource_address,
+            ));
+        }
+        let key = NonFungibleKey::try_from(&slice[27..])
+            .map_err(ParseNonFungibleAddressError::InvalidNonFungibleKey)?;
+        Ok(Self {
+            resource_address,
+            key,
+        })
+    }
+}
+
+impl FromStr for NonFungibleAddress {
+    type Err = ParseNonFungibleAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s).map_err(ParseNonFungibleAddressError::InvalidHex)?;
+        Self::try_from(bytes.as_slice())
+    }
+}
+
+impl fmt::Display for NonFungibleAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_vec()))
+    }
+}
+
+impl TypeId for NonFungibleAddress {
+    #[inline]
+    fn type_id() -> u8 {
+        SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS
+    }
+}
+
+impl Encode for NonFungibleAddress {
+    fn encode_value(&self, encoder: &mut Encoder) {
+        let bytes = self.to_vec();
+        encoder.write_len(bytes.len());
+        encoder.write_slice(&bytes);
+    }
+}
+
+impl Decode for NonFungibleAddress {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        let slice = decoder.read_bytes(len)?;
+        Self::try_from(slice)
+            .map_err(|_| DecodeError::InvalidCustomData(SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS))
+    }
+}
+
+impl Describe for NonFungibleAddress {
+    fn describe() -> Type {
+        Type::Custom {
+            name: SCRYPTO_NAME_NON_FUNGIBLE_ADDRESS.to_owned(),
+            generics: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::string::ToString;
+
+    #[test]
+    fn test_from_to_string() {
+        let s = "0300000000000000000000000000000000000000000000000000050102";
+        let address = NonFungibleAddress::from_str(s).unwrap();
+        assert_eq!(address.resource_address(), ECDSA_TOKEN);
+        assert_eq!(address.key(), NonFungibleKey::new(vec![1, 2]));
+        assert_eq!(address.to_string(), s);
+    }
+
+    #[test]
+    fn test_reject_non_resource_def_address() {
+        let s = "0200000000000000000000000000000000000000000000000000020102";
+        assert!(matches!(
+            NonFungibleAddress::from_str(s),
+            Err(ParseNonFungibleAddressError::NotResourceDefAddress(
+                SYSTEM_COMPONENT
+            ))
+        ));
+    }
+}
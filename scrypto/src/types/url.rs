@@ -0,0 +1,170 @@
+use crate::buffer::{SCRYPTO_NAME_URL, SCRYPTO_TYPE_URL};
+use crate::rust::borrow::ToOwned;
+use crate::rust::str::FromStr;
+use crate::rust::string::String;
+use crate::rust::vec;
+use crate::rust::vec::Vec;
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use sbor::{describe::Type, *};
+
+/// The maximum length of a URL, in bytes.
+pub const URL_MAX_LENGTH: usize = 1024;
+
+/// The schemes a URL may use.
+const URL_SCHEMES: [&str; 2] = ["https://", "http://"];
+
+/// Represents an error when parsing Url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseUrlError {
+    InvalidUtf8,
+    TooLong(usize),
+    InvalidScheme,
+    MissingHost,
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for ParseUrlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for ParseUrlError {}
+
+/// Represents an HTTP(S) URL of bounded length, e.g. for resource metadata or
+/// non-fungible data.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Url(String);
+
+impl Url {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.0.as_bytes().to_vec()
+    }
+}
+
+impl AsRef<str> for Url {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl TryFrom<&[u8]> for Url {
+    type Error = ParseUrlError;
+
+    fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
+        let s = core::str::from_utf8(slice).map_err(|_| ParseUrlError::InvalidUtf8)?;
+        Self::from_str(s)
+    }
+}
+
+impl TypeId for Url {
+    #[inline]
+    fn type_id() -> u8 {
+        SCRYPTO_TYPE_URL
+    }
+}
+
+impl Display for Url {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Encode for Url {
+    fn encode_value(&self, encoder: &mut Encoder) {
+        let bytes = self.to_vec();
+        encoder.write_len(bytes.len());
+        encoder.write_slice(&bytes);
+    }
+}
+
+impl Decode for Url {
+    fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
+        let len = decoder.read_len()?;
+        let slice = decoder.read_bytes(len)?;
+        Self::try_from(slice).map_err(|_| DecodeError::InvalidCustomData(SCRYPTO_TYPE_URL))
+    }
+}
+
+impl FromStr for Url {
+    type Err = ParseUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() > URL_MAX_LENGTH {
+            return Err(ParseUrlError::TooLong(s.len()));
+        }
+        let rest = URL_SCHEMES
+            .iter()
+            .find_map(|scheme| s.strip_prefix(scheme))
+            .ok_or(ParseUrlError::InvalidScheme)?;
+        if let Some(c) = s.chars().find(|c| c.is_whitespace() || c.is_control()) {
+            return Err(ParseUrlError::InvalidCharacter(c));
+        }
+        if rest.is_empty() || rest.starts_with('/') {
+            return Err(ParseUrlError::MissingHost);
+        }
+        Ok(Self(s.to_owned()))
+    }
+}
+
+impl Describe for Url {
+    fn describe() -> Type {
+        Type::Custom {
+            name: SCRYPTO_NAME_URL.to_owned(),
+            generics: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::format;
+    use crate::rust::string::ToString;
+
+    #[test]
+    fn test_from_to_string() {
+        let url = Url::from_str("https://www.radixdlt.com/logo.png").unwrap();
+        assert_eq!(url.to_string(), "https://www.radixdlt.com/logo.png");
+        assert_eq!(
+            Url::from_str("ftp://radixdlt.com"),
+            Err(ParseUrlError::InvalidScheme)
+        );
+        assert_eq!(Url::from_str("https://"), Err(ParseUrlError::MissingHost));
+        assert_eq!(
+            Url::from_str("https://radixdlt.com/a b"),
+            Err(ParseUrlError::InvalidCharacter(' '))
+        );
+    }
+
+    #[test]
+    fn test_max_length() {
+        let prefix = "https://radixdlt.com/";
+        let ok = format!("{}{}", prefix, "a".repeat(URL_MAX_LENGTH - prefix.len()));
+        assert!(Url::from_str(&ok).is_ok());
+        let too_long = format!("{}a", ok);
+        assert_eq!(
+            Url::from_str(&too_long),
+            Err(ParseUrlError::TooLong(URL_MAX_LENGTH + 1))
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_invalid_url() {
+        let mut encoder = Encoder::with_type(Vec::new());
+        encoder.write_type(SCRYPTO_TYPE_URL);
+        encoder.write_len(3);
+        encoder.write_slice(b"abc");
+        let bytes: Vec<u8> = encoder.into();
+        assert!(matches!(
+            decode_with_type::<Url>(&bytes),
+            Err(DecodeError::InvalidCustomData(SCRYPTO_TYPE_URL))
+        ));
+    }
+}
@@ -10,26 +10,45 @@ use crate::rust::vec::Vec;
 use crate::types::*;
 
 /// Represents a vault id.
+///
+/// A vault id is made of the hash of the transaction which created the vault and the id
+/// allocated to it within that transaction, so it stays the same across ledgers replaying
+/// the transaction. The string form is the hex encoding of `to_vec()`.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Vid(pub H256, pub u32);
+pub struct VaultId(H256, u32);
 
-/// Represents an error when parsing Vid.
+/// Represents an error when parsing VaultId.
 #[derive(Debug, Clone)]
-pub enum ParseVidError {
+pub enum ParseVaultIdError {
     InvalidHex(hex::FromHexError),
     InvalidLength(usize),
 }
 
-impl fmt::Display for ParseVidError {
+impl fmt::Display for ParseVaultIdError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
 #[cfg(not(feature = "alloc"))]
-impl std::error::Error for ParseVidError {}
+impl std::error::Error for ParseVaultIdError {}
+
+impl VaultId {
+    /// Creates the id of the vault allocated with the given id in a transaction.
+    pub const fn new(transaction_hash: H256, id: u32) -> Self {
+        Self(transaction_hash, id)
+    }
+
+    /// Returns the hash of the transaction which created the vault.
+    pub fn transaction_hash(&self) -> H256 {
+        self.0
+    }
+
+    /// Returns the id allocated to the vault within the transaction which created it.
+    pub fn id(&self) -> u32 {
+        self.1
+    }
 
-impl Vid {
     pub fn to_vec(&self) -> Vec<u8> {
         let mut vec = Vec::with_capacity(36);
         vec.extend(self.0.as_ref());
@@ -38,12 +57,12 @@ impl Vid {
     }
 }
 
-impl TryFrom<&[u8]> for Vid {
-    type Error = ParseVidError;
+impl TryFrom<&[u8]> for VaultId {
+    type Error = ParseVaultIdError;
 
     fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
         if slice.len() != 36 {
-            Err(ParseVidError::InvalidLength(slice.len()))
+            Err(ParseVaultIdError::InvalidLength(slice.len()))
         } else {
             Ok(Self(
                 H256(copy_u8_array(&slice[..32])),
@@ -53,35 +72,35 @@ impl TryFrom<&[u8]> for Vid {
     }
 }
 
-impl FromStr for Vid {
-    type Err = ParseVidError;
+impl FromStr for VaultId {
+    type Err = ParseVaultIdError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = hex::decode(s).map_err(ParseVidError::InvalidHex)?;
+        let bytes = hex::decode(s).map_err(ParseVaultIdError::InvalidHex)?;
         Self::try_from(bytes.as_slice())
     }
 }
 
-impl fmt::Debug for Vid {
+impl fmt::Debug for VaultId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", hex::encode(self.to_vec()))
     }
 }
 
-impl fmt::Display for Vid {
+impl fmt::Display for VaultId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", hex::encode(self.to_vec()))
     }
 }
 
-impl TypeId for Vid {
+impl TypeId for VaultId {
     #[inline]
     fn type_id() -> u8 {
         SCRYPTO_TYPE_VID
     }
 }
 
-impl Encode for Vid {
+impl Encode for VaultId {
     fn encode_value(&self, encoder: &mut Encoder) {
         let bytes = self.to_vec();
         encoder.write_len(bytes.len());
@@ -89,7 +108,7 @@ impl Encode for Vid {
     }
 }
 
-impl Decode for Vid {
+impl Decode for VaultId {
     fn decode_value(decoder: &mut Decoder) -> Result<Self, DecodeError> {
         let len = decoder.read_len()?;
         let slice = decoder.read_bytes(len)?;
@@ -97,7 +116,7 @@ impl Decode for Vid {
     }
 }
 
-impl Describe for Vid {
+impl Describe for VaultId {
     fn describe() -> Type {
         Type::Custom {
             name: SCRYPTO_NAME_VID.to_owned(),
@@ -105,3 +124,18 @@ impl Describe for Vid {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust::string::ToString;
+
+    #[test]
+    fn test_from_to_string() {
+        let s = "5feceb66ffc86f38d952786c6d696c79c2dbc239dd4e91b46729d73a27fb57e900040000";
+        let vid = VaultId::from_str(s).unwrap();
+        assert_eq!(vid.id(), 1024);
+        assert_eq!(vid.to_string(), s);
+        assert!(VaultId::from_str("00").is_err());
+    }
+}
@@ -0,0 +1,38 @@
+use crate::types::*;
+use sbor::*;
+
+/// Names an address that the engine provides, e.g. the Radix token or the account package.
+///
+/// Blueprints resolve these through `Context::well_known_address` instead of embedding the
+/// address bytes, so the same package keeps working on ledgers where they differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, TypeId, Encode, Decode, Describe)]
+pub enum WellKnownAddress {
+    SystemPackage,
+
+    SystemComponent,
+
+    AccountPackage,
+
+    RadixToken,
+
+    EcdsaToken,
+
+    FaucetComponent,
+
+    StakingComponent,
+}
+
+impl WellKnownAddress {
+    /// Returns the address this name resolves to on a default ledger.
+    pub fn default_address(&self) -> Address {
+        match self {
+            Self::SystemPackage => SYSTEM_PACKAGE,
+            Self::SystemComponent => SYSTEM_COMPONENT,
+            Self::AccountPackage => ACCOUNT_PACKAGE,
+            Self::RadixToken => RADIX_TOKEN,
+            Self::EcdsaToken => ECDSA_TOKEN,
+            Self::FaucetComponent => FAUCET_COMPONENT,
+            Self::StakingComponent => STAKING_COMPONENT,
+        }
+    }
+}
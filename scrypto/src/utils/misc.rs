@@ -9,6 +9,19 @@ pub fn scrypto_unwrap<T, E>(res: Result<T, E>) -> T {
     }
 }
 
+/// Runs the body of a blueprint export.
+///
+/// When the blueprint is compiled natively, a panic is caught and a null pointer is returned
+/// instead, so that the panic doesn't unwind into the engine.
+pub fn scrypto_catch_panic<F: FnOnce() -> *mut u8>(f: F) -> *mut u8 {
+    #[cfg(all(not(target_arch = "wasm32"), not(feature = "alloc")))]
+    return std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .unwrap_or(crate::rust::ptr::null_mut());
+
+    #[cfg(any(target_arch = "wasm32", feature = "alloc"))]
+    f()
+}
+
 /// Set up panic hook.
 pub fn scrypto_setup_panic_hook() {
     #[cfg(not(feature = "alloc"))]
@@ -1,5 +1,5 @@
 mod misc;
 mod sha;
 
-pub use misc::{scrypto_setup_panic_hook, scrypto_unwrap};
+pub use misc::{scrypto_catch_panic, scrypto_setup_panic_hook, scrypto_unwrap};
 pub use sha::{sha256, sha256_twice};
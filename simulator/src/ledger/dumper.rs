@@ -2,11 +2,17 @@ use colored::*;
 use radix_engine::engine::*;
 use radix_engine::ledger::*;
 use radix_engine::model::*;
-use scrypto::rust::collections::HashSet;
+use sbor::Value;
+use scrypto::engine::ResourceType;
+use scrypto::rust::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use scrypto::types::*;
 
 use crate::utils::*;
 
+/// The maximum number of components or non-fungibles listed for a package or resource
+/// definition.
+const MAX_LISTED_ENTITIES: usize = 100;
+
 /// Represents an error when displaying an entity.
 #[derive(Debug, Clone)]
 pub enum DisplayError {
@@ -22,15 +28,59 @@ pub fn dump_package<T: SubstateStore>(address: Address, ledger: &T) -> Result<()
         Some(b) => {
             println!("{}: {}", "Package".green().bold(), address.to_string());
             println!("{}: {} bytes", "Code size".green().bold(), b.code().len());
+            println!("{}: {}", "Version".green().bold(), b.version());
+            if let Some(owner_badge) = b.owner_badge() {
+                println!("{}: {}", "Owner badge".green().bold(), owner_badge);
+            }
+            dump_royalty(b.royalty());
+            for (blueprint, metadata) in b.metadata() {
+                println!("{}: {}", "Blueprint".green().bold(), blueprint);
+                for (i, (key, value)) in metadata.iter().enumerate() {
+                    let last = i == metadata.len() - 1;
+                    println!("{} {}: {}", list_item_prefix(last), key, value);
+                }
+            }
+
+            let page = ledger.scan_components(address, None, MAX_LISTED_ENTITIES);
+            println!("{}:", "Components".green().bold());
+            for (last, (component_address, component)) in page.entries.iter().identify_last() {
+                println!(
+                    "{} {} ({})",
+                    list_item_prefix(last && page.next_cursor.is_none()),
+                    component_address,
+                    component.blueprint_name()
+                );
+            }
+            dump_truncated(&page);
             Ok(())
         }
         None => Err(DisplayError::PackageNotFound),
     }
 }
 
+fn dump_royalty(royalty: Option<&Royalty>) {
+    if let Some(r) = royalty {
+        println!(
+            "{}: {{ amount: {}, owner_badge: {}, collected: {} }}",
+            "Royalty".green().bold(),
+            r.amount(),
+            r.owner_badge(),
+            r.collected()
+        );
+    }
+}
+
 /// Dump a component into console.
-pub fn dump_component<T: SubstateStore>(address: Address, ledger: &T) -> Result<(), DisplayError> {
-    let component = ledger.get_component(address);
+///
+/// The state is listed field by field if `state_fields` is given, e.g. as decoded with the
+/// blueprint ABI.
+pub fn dump_component<T: SubstateStore>(
+    address: Address,
+    ledger: &T,
+    state_fields: &[(String, Value)],
+) -> Result<(), DisplayError> {
+    let query = LedgerQuery::new(ledger);
+    let component = query.component(address);
     match component {
         Some(c) => {
             println!("{}: {}", "Component".green().bold(), address.to_string());
@@ -38,12 +88,32 @@ pub fn dump_component<T: SubstateStore>(address: Address, ledger: &T) -> Result<
             println!(
                 "{}: {{ package_address: {}, blueprint_name: \"{}\" }}",
                 "Blueprint".green().bold(),
-                c.package_address(),
-                c.blueprint_name()
+                c.package_address,
+                c.blueprint_name
+            );
+            if let Some(version) = c.package_version {
+                println!("{}: {}", "Pinned version".green().bold(), version);
+            }
+            dump_royalty(c.royalty.as_ref());
+            println!(
+                "{}: {} bytes",
+                "Storage".green().bold(),
+                ledger.get_storage_footprint(&address)
             );
-            let state = c.state();
-            let state_validated = validate_data(state).unwrap();
-            println!("{}: {}", "State".green().bold(), state_validated);
+            let state_validated = validate_data(&c.state).unwrap();
+            if state_fields.is_empty() {
+                println!("{}: {}", "State".green().bold(), state_validated);
+            } else {
+                println!("{}:", "State".green().bold());
+                for (last, (name, value)) in state_fields.iter().identify_last() {
+                    println!(
+                        "{} {}: {}",
+                        list_item_prefix(last),
+                        name,
+                        format_value(value, &HashMap::new(), &HashMap::new())
+                    );
+                }
+            }
 
             // TODO: check authorization
             // The current implementation recursively displays all referenced maps and vaults which
@@ -54,93 +124,141 @@ pub fn dump_component<T: SubstateStore>(address: Address, ledger: &T) -> Result<
             let mut queue: Vec<Mid> = state_validated.lazy_maps.clone();
             let mut i = 0;
             let mut maps_visited: HashSet<Mid> = HashSet::new();
-            let mut vaults_found: HashSet<Vid> = state_validated.vaults.iter().cloned().collect();
             while i < queue.len() {
                 let mid = queue[i];
                 i += 1;
                 if maps_visited.insert(mid) {
-                    let (maps, vaults) = dump_lazy_map(&address, &mid, ledger)?;
-                    queue.extend(maps);
-                    for v in vaults {
-                        vaults_found.insert(v);
-                    }
+                    queue.extend(dump_lazy_map(&address, &mid, ledger)?);
                 }
             }
 
             // Dump resources
-            dump_resources(address, &vaults_found, ledger)
+            let vaults = query.vaults(address).unwrap();
+            dump_resources(&vaults, ledger)
         }
         None => Err(DisplayError::ComponentNotFound),
     }
 }
 
+/// Dump the resources held by a component, summed up by resource, into console.
+pub fn dump_balances<T: SubstateStore>(address: Address, ledger: &T) -> Result<(), DisplayError> {
+    let query = LedgerQuery::new(ledger);
+    let vaults = query
+        .vaults(address)
+        .ok_or(DisplayError::ComponentNotFound)?;
+
+    // Addresses are not ordered, so balances are sorted by their string form
+    let mut balances: BTreeMap<String, (Address, Decimal, BTreeSet<NonFungibleKey>)> =
+        BTreeMap::new();
+    for vault in vaults {
+        let balance = balances
+            .entry(vault.resource_address.to_string())
+            .or_insert((vault.resource_address, Decimal::zero(), BTreeSet::new()));
+        balance.1 += vault.amount;
+        balance.2.extend(vault.non_fungible_keys);
+    }
+
+    println!("{}: {}", "Component".green().bold(), address);
+    println!("{}:", "Balances".green().bold());
+    for (last, (resource_address, amount, keys)) in balances.values().identify_last() {
+        let resource = query.resource(*resource_address).unwrap();
+        println!(
+            "{} {} {}{} {}",
+            list_item_prefix(last),
+            amount,
+            resource.symbol().unwrap_or("?"),
+            resource
+                .name()
+                .map(|name| format!(" (\"{}\")", name))
+                .unwrap_or_default(),
+            resource_address
+        );
+        for (inner_last, key) in keys.iter().identify_last() {
+            println!(
+                "{}  {} {}",
+                if last { " " } else { "│" },
+                list_item_prefix(inner_last),
+                key
+            );
+        }
+    }
+    Ok(())
+}
+
 fn dump_lazy_map<T: SubstateStore>(
     address: &Address,
     mid: &Mid,
     ledger: &T,
-) -> Result<(Vec<Mid>, Vec<Vid>), DisplayError> {
+) -> Result<Vec<Mid>, DisplayError> {
     let mut referenced_maps = Vec::new();
-    let mut referenced_vaults = Vec::new();
     let map = ledger.get_lazy_map(address, mid).unwrap();
     println!("{}: {:?}{:?}", "Lazy Map".green().bold(), address, mid);
     for (last, (k, v)) in map.map().iter().identify_last() {
-        let k_validated = validate_data(k).unwrap();
         let v_validated = validate_data(v).unwrap();
-        println!(
-            "{} {} => {}",
-            list_item_prefix(last),
-            k_validated,
-            v_validated
-        );
-        referenced_maps.extend(k_validated.lazy_maps);
+        // Keys of an ordered store are not SBOR-encoded
+        match validate_data(k) {
+            Ok(k_validated) => {
+                println!(
+                    "{} {} => {}",
+                    list_item_prefix(last),
+                    k_validated,
+                    v_validated
+                );
+                referenced_maps.extend(k_validated.lazy_maps);
+            }
+            Err(_) => {
+                println!(
+                    "{} {} => {}",
+                    list_item_prefix(last),
+                    hex::encode(k),
+                    v_validated
+                );
+            }
+        }
         referenced_maps.extend(v_validated.lazy_maps);
-        referenced_vaults.extend(k_validated.vaults);
-        referenced_vaults.extend(v_validated.vaults);
     }
-    Ok((referenced_maps, referenced_vaults))
+    Ok(referenced_maps)
 }
 
 fn dump_resources<T: SubstateStore>(
-    address: Address,
-    vaults: &HashSet<Vid>,
+    vaults: &[VaultContent],
     ledger: &T,
 ) -> Result<(), DisplayError> {
+    let query = LedgerQuery::new(ledger);
+
     println!("{}:", "Resources".green().bold());
-    for (last, vid) in vaults.iter().identify_last() {
-        let vault = ledger.get_vault(&address, vid).unwrap();
-        let amount = vault.amount();
-        let resource_address = vault.resource_address();
-        let resource_def = ledger.get_resource_def(resource_address).unwrap();
+    for (last, vault) in vaults.iter().identify_last() {
+        let resource = query.resource(vault.resource_address).unwrap();
         println!(
-            "{} {{ amount: {}, resource_def: {}{}{} }}",
+            "{} {{ vault: {}, amount: {}, resource_def: {}{}{}{} }}",
             list_item_prefix(last),
-            amount,
-            resource_address,
-            resource_def
-                .metadata()
-                .get("name")
+            vault.vid,
+            vault.amount,
+            vault.resource_address,
+            resource
+                .name()
                 .map(|name| format!(", name: \"{}\"", name))
-                .unwrap_or(String::new()),
-            resource_def
-                .metadata()
-                .get("symbol")
+                .unwrap_or_default(),
+            resource
+                .symbol()
                 .map(|symbol| format!(", symbol: \"{}\"", symbol))
-                .unwrap_or(String::new()),
+                .unwrap_or_default(),
+            if vault.frozen { ", frozen: true" } else { "" },
         );
-        if let Supply::NonFungible { keys } = vault.total_supply() {
-            for (inner_last, key) in keys.iter().identify_last() {
-                let non_fungible = ledger.get_non_fungible(resource_address, key).unwrap();
-                let immutable_data = validate_data(&non_fungible.immutable_data()).unwrap();
-                let mutable_data = validate_data(&non_fungible.mutable_data()).unwrap();
-                println!(
-                    "{}  {} NON_FUNGIBLE {{ id: {}, immutable_data: {}, mutable_data: {} }}",
-                    if last { " " } else { "│" },
-                    list_item_prefix(inner_last),
-                    key,
-                    immutable_data,
-                    mutable_data
-                );
-            }
+        for (inner_last, key) in vault.non_fungible_keys.iter().identify_last() {
+            let non_fungible = ledger
+                .get_non_fungible(vault.resource_address, key)
+                .unwrap();
+            let immutable_data = validate_data(&non_fungible.immutable_data()).unwrap();
+            let mutable_data = validate_data(&non_fungible.mutable_data()).unwrap();
+            println!(
+                "{}  {} NON_FUNGIBLE {{ id: {}, immutable_data: {}, mutable_data: {} }}",
+                if last { " " } else { "│" },
+                list_item_prefix(inner_last),
+                key,
+                immutable_data,
+                mutable_data
+            );
         }
     }
     Ok(())
@@ -151,24 +269,51 @@ pub fn dump_resource_def<T: SubstateStore>(
     address: Address,
     ledger: &T,
 ) -> Result<(), DisplayError> {
-    let resource_def = ledger.get_resource_def(address);
-    match resource_def {
+    let resource = LedgerQuery::new(ledger).resource(address);
+    match resource {
         Some(r) => {
+            println!("{}: {:?}", "Resource Type".green().bold(), r.resource_type);
+            println!("{}: {}", "Metadata".green().bold(), r.metadata.len());
+            let metadata: BTreeMap<&String, &String> = r.metadata.iter().collect();
+            for (last, e) in metadata.into_iter().identify_last() {
+                println!("{} {}: {}", list_item_prefix(last), e.0.green().bold(), e.1);
+            }
+            println!("{}: {}", "Flags".green().bold(), r.flags);
+            println!("{}: {}", "Mutable Flags".green().bold(), r.mutable_flags);
+            let mut authorities: Vec<String> = r
+                .authorities
+                .iter()
+                .map(|(address, permissions)| format!("{}: {}", address, permissions))
+                .collect();
+            authorities.sort();
             println!(
-                "{}: {:?}",
-                "Resource Type".green().bold(),
-                r.resource_type()
+                "{}: {{{}}}",
+                "Authorities".green().bold(),
+                authorities.join(", ")
             );
-            println!("{}: {}", "Metadata".green().bold(), r.metadata().len());
-            for (last, e) in r.metadata().iter().identify_last() {
-                println!("{} {}: {}", list_item_prefix(last), e.0.green().bold(), e.1);
+            println!("{}: {}", "Total Supply".green().bold(), r.total_supply);
+            if let ResourceType::NonFungible = r.resource_type {
+                let page = ledger.scan_non_fungibles(address, None, MAX_LISTED_ENTITIES);
+                println!("{}:", "Non-fungibles".green().bold());
+                for (last, (key, non_fungible)) in page.entries.iter().identify_last() {
+                    println!(
+                        "{} {{ id: {}, immutable_data: {}, mutable_data: {} }}",
+                        list_item_prefix(last && page.next_cursor.is_none()),
+                        key,
+                        validate_data(&non_fungible.immutable_data()).unwrap(),
+                        validate_data(&non_fungible.mutable_data()).unwrap()
+                    );
+                }
+                dump_truncated(&page);
             }
-            println!("{}: {}", "Flags".green().bold(), r.flags());
-            println!("{}: {}", "Mutable Flags".green().bold(), r.mutable_flags());
-            println!("{}: {:?}", "Authorities".green().bold(), r.authorities());
-            println!("{}: {}", "Total Supply".green().bold(), r.total_supply());
             Ok(())
         }
         None => Err(DisplayError::ResourceDefNotFound),
     }
 }
+
+fn dump_truncated<K, V>(page: &Page<K, V>) {
+    if page.next_cursor.is_some() {
+        println!("{} ...", list_item_prefix(true));
+    }
+}
@@ -0,0 +1,56 @@
+use scrypto::rust::collections::HashSet;
+use scrypto::types::*;
+
+use radix_engine::ledger::*;
+
+use crate::ledger::*;
+
+/// Lazy maps and vaults which are not reachable from any component state.
+#[derive(Debug, Clone, Default)]
+pub struct UnreachableSubstates {
+    pub lazy_maps: Vec<(Address, Mid)>,
+    pub vaults: Vec<(Address, VaultId)>,
+}
+
+impl UnreachableSubstates {
+    pub fn is_empty(&self) -> bool {
+        self.lazy_maps.is_empty() && self.vaults.is_empty()
+    }
+}
+
+/// Finds all lazy maps and vaults that can't be reached from the state of their component.
+pub fn find_unreachable_substates(ledger: &RadixEngineDB) -> UnreachableSubstates {
+    let mut reachable_maps = HashSet::new();
+    let mut reachable_vaults = HashSet::new();
+
+    let query = LedgerQuery::new(ledger);
+    for component_address in ledger.list_components() {
+        if let Some((maps, vaults)) = query.owned_substates(component_address) {
+            reachable_maps.extend(maps.into_iter().map(|mid| (component_address, mid)));
+            reachable_vaults.extend(vaults.into_iter().map(|vid| (component_address, vid)));
+        }
+    }
+
+    UnreachableSubstates {
+        lazy_maps: ledger
+            .list_lazy_maps()
+            .into_iter()
+            .filter(|key| !reachable_maps.contains(key))
+            .collect(),
+        vaults: ledger
+            .list_vaults()
+            .into_iter()
+            .filter(|key| !reachable_vaults.contains(key))
+            .collect(),
+    }
+}
+
+/// Deletes the given unreachable substates from the ledger.
+pub fn delete_unreachable_substates(ledger: &mut RadixEngineDB, substates: &UnreachableSubstates) {
+    for (component_address, mid) in &substates.lazy_maps {
+        ledger.delete_lazy_map(*component_address, *mid);
+    }
+    for (component_address, vid) in &substates.vaults {
+        ledger.delete_vault(*component_address, *vid);
+    }
+}
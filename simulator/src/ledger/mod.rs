@@ -1,5 +1,9 @@
 mod dumper;
+mod fsck;
 mod radix_engine_db;
+mod transaction_log;
 
 pub use dumper::*;
+pub use fsck::*;
 pub use radix_engine_db::RadixEngineDB;
+pub use transaction_log::{TransactionLog, TransactionRecord};
@@ -23,6 +23,14 @@ impl RadixEngineDB {
         ledger
     }
 
+    /// Opens the store, bootstrapping it with the entities of a genesis specification if it's
+    /// new.
+    pub fn with_genesis(root: PathBuf, genesis: &Genesis) -> Result<Self, GenesisError> {
+        let mut ledger = Self::new(root);
+        ledger.bootstrap_with_genesis(genesis)?;
+        Ok(ledger)
+    }
+
     pub fn list_packages(&self) -> Vec<Address> {
         self.list_items(Address::Package([0; 26]), Address::Package([255; 26]))
     }
@@ -38,6 +46,30 @@ impl RadixEngineDB {
         )
     }
 
+    pub fn list_lazy_maps(&self) -> Vec<(Address, Mid)> {
+        self.list_all_items()
+    }
+
+    pub fn list_vaults(&self) -> Vec<(Address, VaultId)> {
+        self.list_all_items()
+    }
+
+    pub fn delete_lazy_map(&mut self, component_address: Address, mid: Mid) {
+        self.delete((component_address, mid))
+    }
+
+    pub fn delete_vault(&mut self, component_address: Address, vid: VaultId) {
+        self.delete((component_address, vid))
+    }
+
+    /// Scans the whole store for keys of the given type.
+    fn list_all_items<K: Decode>(&self) -> Vec<K> {
+        self.db
+            .iterator(IteratorMode::Start)
+            .filter_map(|kv| scrypto_decode(kv.0.as_ref()).ok())
+            .collect()
+    }
+
     fn list_items<K: Encode + Decode>(&self, start: K, end: K) -> Vec<K> {
         let mut iter = self.db.iterator(IteratorMode::From(
             &scrypto_encode(&start),
@@ -65,6 +97,10 @@ impl RadixEngineDB {
             .put(scrypto_encode(&key), scrypto_encode(&value))
             .unwrap();
     }
+
+    fn delete<K: Encode>(&self, key: K) {
+        self.db.delete(scrypto_encode(&key)).unwrap();
+    }
 }
 
 impl SubstateStore for RadixEngineDB {
@@ -100,14 +136,24 @@ impl SubstateStore for RadixEngineDB {
         self.write((component_address, mid), lazy_map)
     }
 
-    fn get_vault(&self, component_address: &Address, vid: &Vid) -> Option<Vault> {
+    fn get_vault(&self, component_address: &Address, vid: &VaultId) -> Option<Vault> {
         self.read(&(component_address.clone(), vid.clone()))
     }
 
-    fn put_vault(&mut self, component_address: Address, vid: Vid, vault: Vault) {
+    fn put_vault(&mut self, component_address: Address, vid: VaultId, vault: Vault) {
         self.write((component_address, vid), vault)
     }
 
+    fn get_vaults(&self, component_address: &Address) -> Vec<(VaultId, Vault)> {
+        self.list_items(
+            (*component_address, VaultId::new(H256([0; 32]), 0)),
+            (*component_address, VaultId::new(H256([255; 32]), u32::MAX)),
+        )
+        .into_iter()
+        .map(|(address, vid): (Address, VaultId)| (vid, self.read(&(address, vid)).unwrap()))
+        .collect()
+    }
+
     fn get_non_fungible(
         &self,
         resource_address: Address,
@@ -125,6 +171,71 @@ impl SubstateStore for RadixEngineDB {
         self.write((resource_address, key.clone()), non_fungible)
     }
 
+    fn scan_components(
+        &self,
+        package_address: Address,
+        cursor: Option<Address>,
+        limit: usize,
+    ) -> Page<Address, Component> {
+        let components = self.list_components().into_iter().filter_map(|address| {
+            self.get_component(address)
+                .filter(|component| component.package_address() == package_address)
+                .map(|component| (address, component))
+        });
+        Page::from_entries(components, cursor.as_ref(), limit)
+    }
+
+    fn scan_vaults(
+        &self,
+        component_address: Address,
+        cursor: Option<VaultId>,
+        limit: usize,
+    ) -> Page<VaultId, Vault> {
+        Page::from_entries(self.get_vaults(&component_address), cursor.as_ref(), limit)
+    }
+
+    fn scan_non_fungibles(
+        &self,
+        resource_address: Address,
+        cursor: Option<NonFungibleKey>,
+        limit: usize,
+    ) -> Page<NonFungibleKey, NonFungible> {
+        let non_fungibles = self
+            .list_all_items::<(Address, NonFungibleKey)>()
+            .into_iter()
+            .filter(|(address, _)| *address == resource_address)
+            .map(|(address, key)| {
+                let non_fungible = self.read(&(address, key.clone())).unwrap();
+                (key, non_fungible)
+            });
+        Page::from_entries(non_fungibles, cursor.as_ref(), limit)
+    }
+
+    fn get_storage_footprint(&self, component_address: &Address) -> u64 {
+        self.read(&("storage_footprint", *component_address))
+            .unwrap_or(0)
+    }
+
+    fn put_storage_footprint(&mut self, component_address: Address, size: u64) {
+        self.write(("storage_footprint", component_address), size)
+    }
+
+    fn get_signer_nonce(&self, signer: &EcdsaPublicKey) -> Option<u64> {
+        self.read(&("signer_nonce", *signer))
+    }
+
+    fn put_signer_nonce(&mut self, signer: EcdsaPublicKey, nonce: u64) {
+        self.write(("signer_nonce", signer), nonce)
+    }
+
+    fn get_scheduler(&self) -> Scheduler {
+        self.read(&"scheduler").unwrap_or_default()
+    }
+
+    fn put_scheduler(&mut self, scheduler: Scheduler) {
+        self.write("scheduler", scheduler)
+    }
+
     fn get_epoch(&self) -> u64 {
         self.read(&"epoch").unwrap_or(0)
     }
@@ -141,3 +252,48 @@ impl SubstateStore for RadixEngineDB {
         self.write("nonce", self.get_nonce() + 1)
     }
 }
+
+impl ListableSubstateStore for RadixEngineDB {
+    fn list_substates(&self) -> Vec<SubstateId> {
+        self.db
+            .iterator(IteratorMode::Start)
+            .filter_map(|kv| decode_substate_id(kv.0.as_ref()))
+            .collect()
+    }
+}
+
+/// Recovers the substate ID from a key, or `None` for the epoch and nonce.
+fn decode_substate_id(key: &[u8]) -> Option<SubstateId> {
+    if let Ok(name) = scrypto_decode::<String>(key) {
+        return match name.as_str() {
+            "scheduler" => Some(SubstateId::Scheduler),
+            _ => None,
+        };
+    }
+    if let Ok(address) = scrypto_decode::<Address>(key) {
+        return match address {
+            Address::Package(_) => Some(SubstateId::Package(address)),
+            Address::Component(_) => Some(SubstateId::Component(address)),
+            Address::ResourceDef(_) => Some(SubstateId::ResourceDef(address)),
+        };
+    }
+    if let Ok((address, mid)) = scrypto_decode::<(Address, Mid)>(key) {
+        return Some(SubstateId::LazyMap(address, mid));
+    }
+    if let Ok((address, vid)) = scrypto_decode::<(Address, VaultId)>(key) {
+        return Some(SubstateId::Vault(address, vid));
+    }
+    if let Ok((address, key)) = scrypto_decode::<(Address, NonFungibleKey)>(key) {
+        return Some(SubstateId::NonFungible(address, key));
+    }
+    match scrypto_decode::<(String, Address)>(key) {
+        Ok((name, address)) if name == "storage_footprint" => {
+            Some(SubstateId::StorageFootprint(address))
+        }
+        Ok(_) => None,
+        Err(_) => match scrypto_decode::<(String, EcdsaPublicKey)>(key) {
+            Ok((name, signer)) if name == "signer_nonce" => Some(SubstateId::SignerNonce(signer)),
+            _ => None,
+        },
+    }
+}
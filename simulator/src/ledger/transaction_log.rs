@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use radix_engine::model::*;
+use rocksdb::{DBWithThreadMode, IteratorMode, SingleThreaded, DB};
+use sbor::*;
+use scrypto::buffer::*;
+use scrypto::engine::*;
+use scrypto::types::*;
+
+/// Represents an executed transaction, together with the outcome of its execution.
+#[derive(Debug, Clone, TypeId, Encode, Decode)]
+pub struct TransactionRecord {
+    pub hash: H256,
+    pub transaction: Transaction,
+    pub committed: bool,
+    pub status: String,
+    pub outputs: Vec<Vec<u8>>,
+    pub logs: Vec<(LogLevel, String)>,
+    pub new_entities: Vec<Address>,
+}
+
+impl TransactionRecord {
+    pub fn new(hash: H256, transaction: Transaction, receipt: &Receipt) -> Self {
+        Self {
+            hash,
+            transaction,
+            committed: receipt.status.is_committed(),
+            status: receipt.status.to_string(),
+            outputs: receipt.outputs.iter().map(|o| o.raw.clone()).collect(),
+            logs: receipt.logs.clone(),
+            new_entities: receipt.new_entities.clone(),
+        }
+    }
+}
+
+/// A log of all transactions executed by the simulator, in order of execution.
+///
+/// Failed transactions do not advance the ledger nonce, so they share their hash with the
+/// transaction executed right after them.
+pub struct TransactionLog {
+    db: DBWithThreadMode<SingleThreaded>,
+}
+
+impl TransactionLog {
+    pub fn new(root: PathBuf) -> Self {
+        let db = DB::open_default(root.as_path()).unwrap();
+        Self { db }
+    }
+
+    /// Appends a record to the log, and returns its index.
+    pub fn append(&mut self, record: &TransactionRecord) -> u64 {
+        let index = self.len();
+        self.db
+            .put(index.to_be_bytes(), scrypto_encode(record))
+            .unwrap();
+        index
+    }
+
+    /// Returns the number of records in the log.
+    pub fn len(&self) -> u64 {
+        self.db
+            .iterator(IteratorMode::End)
+            .next()
+            .map(|kv| u64::from_be_bytes(kv.0[..].try_into().unwrap()) + 1)
+            .unwrap_or(0)
+    }
+
+    /// Returns whether the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns all records, together with their indices.
+    pub fn list(&self) -> Vec<(u64, TransactionRecord)> {
+        self.db
+            .iterator(IteratorMode::Start)
+            .map(|kv| {
+                (
+                    u64::from_be_bytes(kv.0[..].try_into().unwrap()),
+                    scrypto_decode(kv.1.as_ref()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the records of the given transaction hash, in order of execution.
+    pub fn find(&self, hash: H256) -> Vec<(u64, TransactionRecord)> {
+        self.list()
+            .into_iter()
+            .filter(|(_, record)| record.hash == hash)
+            .collect()
+    }
+}
@@ -0,0 +1,165 @@
+use radix_engine::transaction::*;
+use sbor::describe::Type;
+use scrypto::buffer::{scrypto_encode, SCRYPTO_NAME_BUCKET_REF};
+use scrypto::types::*;
+use transaction_manifest::ast;
+use transaction_manifest::generator::{GeneratorError, NameResolver};
+use transaction_manifest::CompileError;
+
+use crate::resim::*;
+
+/// Prepares the arguments of a call against the types of the function or method inputs.
+///
+/// An argument may be written as a transaction manifest value, e.g.
+/// `Struct(("hello", Decimal("5")))` or `Vec<Bucket>(Bucket("5,<resource_address>"))`, where
+/// each nested `Bucket` or `BucketRef` is created from the resource it names, as for plain
+/// arguments. Plain arguments, e.g. `5` or `5,<resource_address>`, are parsed by the type they
+/// are passed as.
+///
+/// Resources are withdrawn from the given account.
+pub fn prepare_args<A: AbiProvider>(
+    builder: &mut TransactionBuilder<A>,
+    types: &[Type],
+    args: &[String],
+    account: Address,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let mut encoded = Vec::new();
+    for (i, t) in types.iter().enumerate() {
+        let arg = args.get(i).ok_or_else(|| {
+            Error::TransactionConstructionError(BuildTransactionError::FailedToBuildArgs(
+                BuildArgsError::MissingArgument(i, t.clone()),
+            ))
+        })?;
+        match parse_value(arg) {
+            Some(value) => encoded.push(prepare_value(builder, i, t, &value, account)?),
+            None => encoded.push(builder.prepare_arg(i, t, arg, Some(account)).map_err(|e| {
+                Error::TransactionConstructionError(BuildTransactionError::FailedToBuildArgs(e))
+            })?),
+        }
+    }
+    Ok(encoded)
+}
+
+/// Prepares bucket refs presenting the given badges, e.g. `1,<resource_address>`, for the last
+/// inputs of a call, which must all be bucket refs.
+///
+/// Each badge is withdrawn from the given account into its own bucket, which the bucket ref is
+/// then created from.
+pub fn prepare_proofs<A: AbiProvider>(
+    builder: &mut TransactionBuilder<A>,
+    types: &[Type],
+    proofs: &[String],
+    account: Address,
+) -> Result<Vec<Vec<u8>>, Error> {
+    let first = types
+        .len()
+        .checked_sub(proofs.len())
+        .ok_or(Error::UnexpectedProofs(proofs.len()))?;
+    let mut encoded = Vec::new();
+    for (i, (t, proof)) in types[first..].iter().zip(proofs).enumerate() {
+        let i = first + i;
+        let to_build_args_error =
+            |e| Error::TransactionConstructionError(BuildTransactionError::FailedToBuildArgs(e));
+        if !matches!(t, Type::Custom { name, .. } if name == SCRYPTO_NAME_BUCKET_REF) {
+            return Err(to_build_args_error(BuildArgsError::UnsupportedType(
+                i,
+                t.clone(),
+            )));
+        }
+        let resource = proof.parse::<Resource>().map_err(|_| {
+            to_build_args_error(BuildArgsError::FailedToParse(i, t.clone(), proof.clone()))
+        })?;
+
+        let mut created_rid = None;
+        builder
+            .withdraw_from_account(&resource, account)
+            .take_from_worktop(&resource, |builder, bid| {
+                builder.create_bucket_ref(bid, |builder, rid| {
+                    created_rid = Some(rid);
+                    builder
+                })
+            });
+        encoded.push(scrypto_encode(&created_rid.unwrap()));
+    }
+    Ok(encoded)
+}
+
+/// Parses an argument as a manifest value, unless it's a plain one.
+///
+/// Primitive literals, e.g. `true` or `"hello"`, are left to the plain parser, so that their
+/// meaning doesn't change.
+fn parse_value(arg: &str) -> Option<ast::Value> {
+    match transaction_manifest::parse_value(arg) {
+        Ok(value) => match value.kind() {
+            ast::Type::Unit
+            | ast::Type::Bool
+            | ast::Type::I8
+            | ast::Type::I16
+            | ast::Type::I32
+            | ast::Type::I64
+            | ast::Type::I128
+            | ast::Type::U8
+            | ast::Type::U16
+            | ast::Type::U32
+            | ast::Type::U64
+            | ast::Type::U128
+            | ast::Type::String => None,
+            _ => Some(value),
+        },
+        Err(_) => None,
+    }
+}
+
+fn prepare_value<A: AbiProvider>(
+    builder: &mut TransactionBuilder<A>,
+    i: usize,
+    t: &Type,
+    value: &ast::Value,
+    account: Address,
+) -> Result<Vec<u8>, Error> {
+    let mut resolver = NameResolver::new();
+
+    for bucket in value.buckets() {
+        let (name, is_ref) = match bucket {
+            ast::Value::Bucket(inner) => (inner, false),
+            ast::Value::BucketRef(inner) => (inner, true),
+            _ => continue,
+        };
+        // Buckets referred to by id are left to the compiler.
+        let spec = match &**name {
+            ast::Value::String(spec) => spec,
+            _ => continue,
+        };
+        let resource = spec.parse::<Resource>().map_err(|_| {
+            Error::TransactionConstructionError(BuildTransactionError::FailedToBuildArgs(
+                BuildArgsError::FailedToParse(i, t.clone(), spec.clone()),
+            ))
+        })?;
+
+        let mut created_bid = None;
+        builder
+            .withdraw_from_account(&resource, account)
+            .take_from_worktop(&resource, |builder, bid| {
+                created_bid = Some(bid);
+                builder
+            });
+        let bid = created_bid.unwrap();
+        let result = if is_ref {
+            let mut created_rid = None;
+            builder.create_bucket_ref(bid, |builder, rid| {
+                created_rid = Some(rid);
+                builder
+            });
+            resolver.insert_bucket_ref(spec.clone(), created_rid.unwrap())
+        } else {
+            resolver.insert_bucket(spec.clone(), bid)
+        };
+        result.map_err(|e| {
+            Error::CompileError(CompileError::GeneratorError(
+                GeneratorError::NameResolverError(e),
+            ))
+        })?;
+    }
+
+    transaction_manifest::compile_value(value, &mut resolver).map_err(Error::CompileError)
+}
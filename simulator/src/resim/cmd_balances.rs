@@ -0,0 +1,28 @@
+use clap::Parser;
+use scrypto::types::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Show the resources held by an account or component
+#[derive(Parser, Debug)]
+pub struct Balances {
+    /// The address of an account or component; defaults to the default account
+    address: Option<Address>,
+}
+
+impl Balances {
+    pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        let address = match self.address {
+            Some(address) => address,
+            None => get_default_account()?,
+        };
+        dump_balances(address, ledger).map_err(Error::LedgerDumpError)
+    }
+}
@@ -1,4 +1,5 @@
 use clap::Parser;
+use radix_engine::model::*;
 use radix_engine::transaction::*;
 use scrypto::types::*;
 
@@ -16,13 +17,17 @@ pub struct CallFunction {
     /// The function name
     function_name: String,
 
-    /// The call arguments, e.g. \"5\", \"hello\", \"amount,resource_address\" for Bucket, or \"#id1,#id2,..,resource_address\" for non-fungible Bucket
+    /// The call arguments, e.g. \"5\", \"hello\", \"amount,resource_address\" for Bucket, \"#id1,#id2,..,resource_address\" for non-fungible Bucket, or a manifest value like Vec<Bucket>(Bucket(\"amount,resource_address\"))
     arguments: Vec<String>,
 
     /// The transaction signers
     #[clap(short, long)]
     signers: Option<Vec<EcdsaPublicKey>>,
 
+    /// A message to attach to the transaction, e.g. a memo labelling it
+    #[clap(long)]
+    message: Option<String>,
+
     /// Output a transaction manifest without execution
     #[clap(short, long)]
     manifest: Option<PathBuf>,
@@ -33,23 +38,51 @@ pub struct CallFunction {
 }
 
 impl CallFunction {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
         let default_account = get_default_account()?;
         let default_signers = get_default_signers()?;
         let signatures = self.signers.clone().unwrap_or(default_signers);
-        let transaction = TransactionBuilder::new(&executor)
-            .call_function(
-                self.package_address,
-                &self.blueprint_name,
-                &self.function_name,
-                self.arguments.clone(),
-                Some(default_account),
-            )
+        let abi = executor
+            .export_abi(self.package_address, &self.blueprint_name)
+            .map_err(Error::AbiExportError)?;
+        let function = abi
+            .functions
+            .iter()
+            .find(|f| f.name == self.function_name)
+            .ok_or_else(|| {
+                Error::TransactionConstructionError(BuildTransactionError::FunctionNotFound(
+                    self.function_name.clone(),
+                ))
+            })?;
+        let mut builder = TransactionBuilder::new(&executor);
+        if let Some(message) = &self.message {
+            builder.message(message.as_str());
+        }
+        let args = prepare_args(
+            &mut builder,
+            &function.inputs,
+            &self.arguments,
+            default_account,
+        )?;
+        let transaction = builder
+            .add_instruction(Instruction::CallFunction {
+                package_address: self.package_address,
+                blueprint_name: self.blueprint_name.clone(),
+                function: self.function_name.clone(),
+                args,
+            })
+            .0
             .call_method_with_all_resources(default_account, "deposit_batch")
             .build(signatures)
             .map_err(Error::TransactionConstructionError)?;
-        process_transaction(transaction, &mut executor, &self.manifest)
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
     }
 }
@@ -1,4 +1,5 @@
 use clap::Parser;
+use radix_engine::model::*;
 use radix_engine::transaction::*;
 use scrypto::types::*;
 
@@ -13,13 +14,22 @@ pub struct CallMethod {
     /// The method name
     method_name: String,
 
-    /// The call arguments
+    /// The call arguments, either plain or manifest values, as for `call-function`
     arguments: Vec<String>,
 
+    /// Badges to present from the default account, e.g. `1,<resource_def>`, each withdrawn into
+    /// a bucket ref which is passed for one of the last parameters, after the call arguments
+    #[clap(short, long)]
+    proofs: Option<Vec<String>>,
+
     /// The transaction signers
     #[clap(short, long)]
     signers: Option<Vec<EcdsaPublicKey>>,
 
+    /// A message to attach to the transaction, e.g. a memo labelling it
+    #[clap(long)]
+    message: Option<String>,
+
     /// Output a transaction manifest without execution
     #[clap(short, long)]
     manifest: Option<PathBuf>,
@@ -30,22 +40,55 @@ pub struct CallMethod {
 }
 
 impl CallMethod {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
         let default_account = get_default_account()?;
         let default_signers = get_default_signers()?;
         let signatures = self.signers.clone().unwrap_or(default_signers);
-        let transaction = TransactionBuilder::new(&executor)
-            .call_method(
-                self.component_address,
-                &self.method_name,
-                self.arguments.clone(),
-                Some(default_account),
-            )
+        let proofs = self.proofs.clone().unwrap_or_default();
+        let abi = executor
+            .export_abi_component(self.component_address)
+            .map_err(Error::AbiExportError)?;
+        let method = abi
+            .methods
+            .iter()
+            .find(|m| m.name == self.method_name)
+            .ok_or_else(|| {
+                Error::TransactionConstructionError(BuildTransactionError::MethodNotFound(
+                    self.method_name.clone(),
+                ))
+            })?;
+        let mut builder = TransactionBuilder::new(&executor);
+        if let Some(message) = &self.message {
+            builder.message(message.as_str());
+        }
+        let (inputs, proof_inputs) = method
+            .inputs
+            .split_at(method.inputs.len().saturating_sub(proofs.len()));
+        let mut args = prepare_args(&mut builder, inputs, &self.arguments, default_account)?;
+        args.extend(prepare_proofs(
+            &mut builder,
+            proof_inputs,
+            &proofs,
+            default_account,
+        )?);
+        let transaction = builder
+            .add_instruction(Instruction::CallMethod {
+                component_address: self.component_address,
+                method: self.method_name.clone(),
+                args,
+            })
+            .0
             .call_method_with_all_resources(default_account, "deposit_batch")
             .build(signatures)
             .map_err(Error::TransactionConstructionError)?;
-        process_transaction(transaction, &mut executor, &self.manifest)
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
     }
 }
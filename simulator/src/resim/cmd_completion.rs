@@ -0,0 +1,25 @@
+use clap::{CommandFactory, Parser};
+use clap_complete::{generate, Shell};
+use std::io;
+
+use crate::resim::*;
+
+/// Print a script which completes the commands of this simulator in a shell
+#[derive(Parser, Debug)]
+pub struct Completion {
+    /// The shell to complete in
+    #[clap(arg_enum)]
+    shell: Shell,
+}
+
+impl Completion {
+    pub fn run(&self) -> Result<(), Error> {
+        generate(
+            self.shell,
+            &mut ResimCli::command(),
+            "resim",
+            &mut io::stdout(),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use clap::{Parser, Subcommand};
+
+use crate::resim::*;
+
+/// Show or change simulator configurations
+#[derive(Parser, Debug)]
+pub struct Config {
+    #[clap(subcommand)]
+    command: ConfigCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    SetCostTable(SetCostTable),
+    SetDefaultAccount(SetDefaultAccount),
+    SetDefaultSigners(SetDefaultSigners),
+    Show(ShowConfigs),
+    ShowCostTable(ShowCostTable),
+}
+
+impl Config {
+    pub fn run(&self) -> Result<(), Error> {
+        match &self.command {
+            ConfigCommand::SetCostTable(cmd) => cmd.run(),
+            ConfigCommand::SetDefaultAccount(cmd) => cmd.run(),
+            ConfigCommand::SetDefaultSigners(cmd) => cmd.run(),
+            ConfigCommand::Show(cmd) => cmd.run(),
+            ConfigCommand::ShowCostTable(cmd) => cmd.run(),
+        }
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        match &self.command {
+            ConfigCommand::SetCostTable(cmd) => cmd.run(),
+            ConfigCommand::SetDefaultAccount(cmd) => cmd.run_with(ledger),
+            ConfigCommand::SetDefaultSigners(cmd) => cmd.run_with(ledger),
+            ConfigCommand::Show(cmd) => cmd.run_with(ledger),
+            ConfigCommand::ShowCostTable(cmd) => cmd.run(),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use clap::Parser;
+use radix_engine::ledger::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Check the data directory was created by this version of the simulator
+#[derive(Parser, Debug)]
+pub struct Doctor {}
+
+impl Doctor {
+    pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        match ledger.check_bootstrap() {
+            Ok(()) => {
+                println!("No issues found!");
+                Ok(())
+            }
+            Err(e) => {
+                println!("The data directory is stale or corrupted: {:?}", e);
+                println!("Run `resim reset` to start over.");
+                Err(Error::LedgerCheckError(e))
+            }
+        }
+    }
+}
@@ -21,7 +21,12 @@ pub struct ExportAbi {
 impl ExportAbi {
     pub fn run(&self) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB) -> Result<(), Error> {
+        let executor = TransactionExecutor::new(ledger, self.trace);
         match executor.export_abi(self.package_address, &self.blueprint_name) {
             Ok(a) => {
                 println!(
@@ -0,0 +1,36 @@
+use clap::Parser;
+use colored::*;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Export an account, for it to be imported into another simulator
+#[derive(Parser, Debug)]
+pub struct ExportAccount {
+    /// The account component address, or the default account if not specified
+    address: Option<Address>,
+}
+
+impl ExportAccount {
+    pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        let address = match self.address {
+            Some(address) => address,
+            None => get_default_account()?,
+        };
+        let public_key = get_account_key(ledger, address)?;
+
+        println!("Account address: {}", address.to_string().green());
+        println!("Public key: {}", public_key.to_string().green());
+        println!(
+            "To import: resim import-account --public-key {}",
+            public_key
+        );
+        Ok(())
+    }
+}
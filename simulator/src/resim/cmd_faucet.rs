@@ -0,0 +1,42 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Get XRD from the faucet
+#[derive(Parser, Debug)]
+pub struct Faucet {
+    /// The account to fund, or the default account if not specified
+    account: Option<Address>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl Faucet {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        let account = match self.account {
+            Some(account) => account,
+            None => get_default_account()?,
+        };
+        let transaction = TransactionBuilder::new(&executor)
+            .call_method(FAUCET_COMPONENT, "claim", vec![account.to_string()], None)
+            .build(Vec::new())
+            .map_err(Error::TransactionConstructionError)?;
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
+    }
+}
@@ -0,0 +1,44 @@
+use clap::Parser;
+use colored::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// Find (and optionally delete) lazy maps and vaults not reachable from any component
+#[derive(Parser, Debug)]
+pub struct Fsck {
+    /// Delete the unreachable substates
+    #[clap(long)]
+    repair: bool,
+}
+
+impl Fsck {
+    pub fn run(&self) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB) -> Result<(), Error> {
+        let unreachable = find_unreachable_substates(ledger);
+
+        println!("{}:", "Unreachable Lazy Maps".green().bold());
+        for (last, (address, mid)) in unreachable.lazy_maps.iter().identify_last() {
+            println!("{} {}{}", list_item_prefix(last), address, mid);
+        }
+
+        println!("{}:", "Unreachable Vaults".green().bold());
+        for (last, (address, vid)) in unreachable.vaults.iter().identify_last() {
+            println!("{} {}{}", list_item_prefix(last), address, vid);
+        }
+
+        if unreachable.is_empty() {
+            println!("No issues found!");
+        } else if self.repair {
+            delete_unreachable_substates(ledger, &unreachable);
+            println!("Unreachable substates deleted!");
+        }
+        Ok(())
+    }
+}
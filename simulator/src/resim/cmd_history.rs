@@ -0,0 +1,38 @@
+use clap::Parser;
+use colored::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// List the transactions executed by this simulator
+#[derive(Parser, Debug)]
+pub struct History {}
+
+impl History {
+    pub fn run(&self) -> Result<(), Error> {
+        let log = TransactionLog::new(get_transaction_log_dir()?);
+
+        let records = log.list();
+        println!("{} ({}):", "Transactions".green().bold(), records.len());
+        for (last, (index, record)) in records.iter().identify_last() {
+            print!(
+                "{} #{} {} {}",
+                list_item_prefix(last),
+                index,
+                record.hash,
+                if record.committed {
+                    record.status.green()
+                } else {
+                    record.status.red()
+                }
+            );
+            match &record.transaction.message {
+                Some(message) => println!(" {}", String::from_utf8_lossy(message).cyan()),
+                None => println!(),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use clap::Parser;
+use colored::*;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Import an account from an identity or a public key, creating it if it doesn't exist yet
+#[derive(Parser, Debug)]
+pub struct ImportAccount {
+    /// Any words to derive a throwaway simulator identity from; this is not a BIP39 mnemonic,
+    /// and the derived key can't be used outside the simulator
+    #[clap(long, required_unless_present = "public-key")]
+    identity: Option<String>,
+
+    /// The index of the key derived from the identity
+    #[clap(long, default_value = "0")]
+    index: u32,
+
+    /// The public key, as printed by `resim export-account`
+    #[clap(long, conflicts_with = "identity")]
+    public_key: Option<EcdsaPublicKey>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl ImportAccount {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let public_key = match (&self.identity, self.public_key) {
+            (Some(identity), _) => derive_public_key(&normalize_identity(identity)?, self.index),
+            (None, Some(public_key)) => public_key,
+            (None, None) => return Err(Error::InvalidIdentity),
+        };
+
+        let existing = ledger
+            .list_components()
+            .into_iter()
+            .find(|address| get_account_key(ledger, *address).ok() == Some(public_key));
+        let account = match existing {
+            Some(account) => {
+                println!("The account already exists.");
+                account
+            }
+            None => {
+                let mut executor =
+                    TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+                let account = new_account(&mut executor, public_key, seed)?;
+                println!("A new account has been created!");
+                account
+            }
+        };
+
+        println!("Account address: {}", account.to_string().green());
+        println!("Public key: {}", public_key.to_string().green());
+        if get_configs()?.is_none() {
+            println!(
+                "No configuration found on system. will use the above account and public key as default."
+            );
+            set_configs(&Configs {
+                default_account: account,
+                default_signers: vec![public_key],
+            })?;
+        }
+
+        Ok(())
+    }
+}
@@ -13,8 +13,8 @@ pub struct Mint {
     /// The resource address
     resource_address: Address,
 
-    /// The minter badge address
-    badge_address: Address,
+    /// The minter badge address, or the name of a badge created by `new-simple-badge`
+    badge_address: String,
 
     /// The transaction signers
     #[clap(short, long)]
@@ -30,9 +30,16 @@ pub struct Mint {
 }
 
 impl Mint {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let badge_address = resolve_badge(&self.badge_address)?;
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
         let default_account = get_default_account()?;
         let default_signers = get_default_signers()?;
         let signatures = self.signers.clone().unwrap_or(default_signers);
@@ -40,14 +47,14 @@ impl Mint {
             .withdraw_from_account(
                 &Resource::Fungible {
                     amount: 1.into(),
-                    resource_address: self.badge_address,
+                    resource_address: badge_address,
                 },
                 default_account,
             )
-            .mint(self.amount, self.resource_address, self.badge_address)
+            .mint(self.amount, self.resource_address, badge_address)
             .call_method_with_all_resources(default_account, "deposit_batch")
             .build(signatures)
             .map_err(Error::TransactionConstructionError)?;
-        process_transaction(transaction, &mut executor, &self.manifest)
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
     }
 }
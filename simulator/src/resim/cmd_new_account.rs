@@ -12,11 +12,17 @@ pub struct NewAccount {
 }
 
 impl NewAccount {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
-        let public_key = executor.new_public_key();
-        let account = executor.new_account(public_key);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        let public_key = new_public_key(&mut executor, seed);
+        let account = new_account(&mut executor, public_key, seed)?;
 
         println!("A new account has been created!");
         println!("Account address: {}", account.to_string().green());
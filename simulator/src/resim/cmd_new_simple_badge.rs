@@ -0,0 +1,66 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::rust::collections::*;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Create a badge with a supply of 1, and register it by name
+#[derive(Parser, Debug)]
+pub struct NewSimpleBadge {
+    /// The name, by which the badge can be referred to in later commands
+    #[clap(long)]
+    name: Option<String>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// The transaction signers
+    #[clap(short, long)]
+    signers: Option<Vec<EcdsaPublicKey>>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl NewSimpleBadge {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        let default_account = get_default_account()?;
+        let default_signers = get_default_signers()?;
+        let mut metadata = HashMap::new();
+        if let Some(name) = self.name.clone() {
+            metadata.insert("name".to_string(), name);
+        }
+        let signatures = self.signers.clone().unwrap_or(default_signers);
+        let transaction = TransactionBuilder::new(&executor)
+            .new_badge_fixed(metadata, 1.into())
+            .call_method_with_all_resources(default_account, "deposit_batch")
+            .build(signatures)
+            .map_err(Error::TransactionConstructionError)?;
+
+        if let Some(path) = &self.manifest {
+            let decompiled = decompile(&transaction).map_err(Error::DecompileError)?;
+            return fs::write(path, decompiled).map_err(Error::IOError);
+        }
+
+        let receipt = run_transaction(transaction, &mut executor, seed)?;
+        let badge_address = receipt.resource_def(0);
+        check_status(receipt.status)?;
+
+        if let (Some(name), Some(address)) = (&self.name, badge_address) {
+            add_badge(name, address)?;
+            println!("Badge registered: {} => {}", name, address);
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
 use clap::Parser;
 use radix_engine::transaction::*;
+use scrypto::resource::{
+    DESCRIPTION_METADATA_KEY, ICON_URL_METADATA_KEY, NAME_METADATA_KEY, SYMBOL_METADATA_KEY,
+};
 use scrypto::rust::collections::*;
 use scrypto::types::*;
 
@@ -8,8 +11,8 @@ use crate::resim::*;
 /// Create a token with mutable supply
 #[derive(Parser, Debug)]
 pub struct NewTokenMutable {
-    /// The minter badge address
-    badge_address: Address,
+    /// The minter badge address, or the name of a badge created by `new-simple-badge`
+    badge_address: String,
 
     /// The symbol
     #[clap(long)]
@@ -25,11 +28,11 @@ pub struct NewTokenMutable {
 
     /// The website URL
     #[clap(long)]
-    url: Option<String>,
+    url: Option<Url>,
 
     /// The ICON url
     #[clap(long)]
-    icon_url: Option<String>,
+    icon_url: Option<Url>,
 
     /// Output a transaction manifest without execution
     #[clap(short, long)]
@@ -45,31 +48,38 @@ pub struct NewTokenMutable {
 }
 
 impl NewTokenMutable {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let badge_address = resolve_badge(&self.badge_address)?;
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
         let default_signers = get_default_signers()?;
         let mut metadata = HashMap::new();
         if let Some(symbol) = self.symbol.clone() {
-            metadata.insert("symbol".to_string(), symbol);
+            metadata.insert(SYMBOL_METADATA_KEY.to_string(), symbol);
         }
         if let Some(name) = self.name.clone() {
-            metadata.insert("name".to_string(), name);
+            metadata.insert(NAME_METADATA_KEY.to_string(), name);
         }
         if let Some(description) = self.description.clone() {
-            metadata.insert("description".to_string(), description);
+            metadata.insert(DESCRIPTION_METADATA_KEY.to_string(), description);
         }
         if let Some(url) = self.url.clone() {
-            metadata.insert("url".to_string(), url);
+            metadata.insert("url".to_string(), url.to_string());
         }
         if let Some(icon_url) = self.icon_url.clone() {
-            metadata.insert("icon_url".to_string(), icon_url);
+            metadata.insert(ICON_URL_METADATA_KEY.to_string(), icon_url.to_string());
         };
         let signatures = self.signers.clone().unwrap_or(default_signers);
         let transaction = TransactionBuilder::new(&executor)
-            .new_token_mutable(metadata, self.badge_address)
+            .new_token_mutable(metadata, badge_address)
             .build(signatures)
             .map_err(Error::TransactionConstructionError)?;
-        process_transaction(transaction, &mut executor, &self.manifest)
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
     }
 }
@@ -1,10 +1,14 @@
 use clap::Parser;
 use colored::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
 use radix_engine::transaction::*;
 use scrypto::types::*;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
 
 use crate::ledger::*;
 use crate::resim::*;
@@ -20,40 +24,158 @@ pub struct Publish {
     #[clap(long)]
     address: Option<Address>,
 
+    /// The package address, for publishing a new version with the package owner badge
+    #[clap(long)]
+    upgrade: Option<Address>,
+
+    /// The owner badge address, or the name of a badge created by `new-simple-badge`, which
+    /// is required for upgrading the package
+    #[clap(long)]
+    owner_badge: Option<String>,
+
     /// The transaction signers
     #[clap(short, long)]
-    signers: Option<Vec<Address>>,
+    signers: Option<Vec<EcdsaPublicKey>>,
 
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Watch the package source, and republish to the same address whenever it changes
+    #[clap(short, long)]
+    watch: bool,
+
+    /// A transaction manifest to run after every republish, in watch mode
+    #[clap(long)]
+    manifest: Option<PathBuf>,
 }
 
+/// How often the package source is checked for changes, in watch mode.
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
 impl Publish {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let address = self.publish(ledger, self.address, seed)?;
+        if self.watch {
+            self.watch(ledger, address, seed)?;
+        }
+        Ok(())
+    }
+
+    /// Builds and publishes the package, as a new version of the package being upgraded, or
+    /// overwriting the given address if any.
+    fn publish(
+        &self,
+        ledger: &mut RadixEngineDB,
+        address: Option<Address>,
+        seed: Option<u64>,
+    ) -> Result<Address, Error> {
         // Load wasm code
         let code = fs::read(if self.path.extension() != Some(OsStr::new("wasm")) {
-            build_package(&self.path, false).map_err(Error::CargoError)?
+            build_package(&self.path, false, false).map_err(Error::CargoError)?
         } else {
             self.path.clone()
         })
         .map_err(Error::IOError)?;
 
-        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
-        if let Some(address) = self.address.clone() {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        if let Some(address) = self.upgrade {
+            // Publish a new version
+            let owner_badge = executor
+                .ledger()
+                .get_package(address)
+                .ok_or(Error::TransactionExecutionError(
+                    RuntimeError::PackageNotFound(address),
+                ))?
+                .owner_badge()
+                .ok_or(Error::TransactionExecutionError(
+                    RuntimeError::PackageNotUpgradeable(address),
+                ))?;
+            let default_account = get_default_account()?;
+            let signatures = self.signers.clone().unwrap_or(get_default_signers()?);
+            let transaction = TransactionBuilder::new(&executor)
+                .withdraw_from_account(
+                    &Resource::Fungible {
+                        amount: 1.into(),
+                        resource_address: owner_badge,
+                    },
+                    default_account,
+                )
+                .upgrade_package(address, &code, owner_badge)
+                .call_method_with_all_resources(default_account, "deposit_batch")
+                .build(signatures)
+                .map_err(Error::TransactionConstructionError)?;
+            check_status(run_transaction(transaction, &mut executor, seed)?.status)?;
+            println!("Package upgraded!");
+            Ok(address)
+        } else if let Some(address) = address {
             // Overwrite package
             executor.overwrite_package(address, &code);
             println!("Package updated!");
-            Ok(())
+            Ok(address)
+        } else if let Some(owner_badge) = &self.owner_badge {
+            let signatures = self.signers.clone().unwrap_or(get_default_signers()?);
+            let transaction = TransactionBuilder::new(&executor)
+                .publish_package_with_owner(&code, resolve_badge(owner_badge)?)
+                .build(signatures)
+                .map_err(Error::TransactionConstructionError)?;
+            let receipt = run_transaction(transaction, &mut executor, seed)?;
+            let address = receipt.package(0);
+            check_status(receipt.status)?;
+            let address = address.unwrap();
+            println!("Success! New Package: {}", address.to_string().green());
+            Ok(address)
         } else {
             match executor.publish_package(&code) {
                 Ok(address) => {
                     println!("Success! New Package: {}", address.to_string().green());
-                    Ok(())
+                    Ok(address)
                 }
                 Err(error) => Err(Error::TransactionExecutionError(error)),
             }
         }
     }
+
+    /// Republishes the package to the given address, and reruns the manifest if any, every
+    /// time the package source changes. Runs until interrupted.
+    fn watch(
+        &self,
+        ledger: &mut RadixEngineDB,
+        address: Address,
+        seed: Option<u64>,
+    ) -> Result<(), Error> {
+        let mut last = last_modified(&self.path).map_err(Error::IOError)?;
+        loop {
+            println!("Watching {} for changes...", self.path.display());
+            loop {
+                thread::sleep(WATCH_INTERVAL);
+                if last_modified(&self.path).map_err(Error::IOError)? > last {
+                    break;
+                }
+            }
+
+            // A broken build should not stop the watch
+            let result =
+                self.publish(ledger, Some(address), seed)
+                    .and_then(|_| match &self.manifest {
+                        Some(manifest) => {
+                            Run::new(manifest.clone(), self.trace).run_with(ledger, seed)
+                        }
+                        None => Ok(()),
+                    });
+            if let Err(error) = result {
+                println!("{}: {:?}", "Error".red(), error);
+            }
+
+            // Changes made while building are picked up by the next round
+            last = last_modified(&self.path).map_err(Error::IOError)?;
+        }
+    }
 }
@@ -0,0 +1,213 @@
+use clap::{CommandFactory, Parser};
+use colored::*;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::iter;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Start an interactive session, which keeps the ledger open across commands
+#[derive(Parser, Debug)]
+pub struct Repl {}
+
+impl Repl {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut editor = Editor::<ReplHelper>::new();
+        editor.set_helper(Some(ReplHelper::new(&ledger)));
+        let history_file = get_repl_history_file()?;
+        if history_file.exists() {
+            editor
+                .load_history(&history_file)
+                .map_err(Error::ReplError)?;
+        }
+
+        println!(
+            "Enter commands without the leading `resim`, e.g. {}. Type {} to quit.",
+            "show-ledger".green(),
+            "exit".green()
+        );
+        loop {
+            let line = match editor.readline("resim> ") {
+                Ok(line) => line,
+                // Ctrl-C only discards the line being edited
+                Err(ReadlineError::Interrupted) => continue,
+                Err(ReadlineError::Eof) => break,
+                Err(error) => return Err(Error::ReplError(error)),
+            };
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            editor.add_history_entry(line);
+            if line == "exit" || line == "quit" {
+                break;
+            }
+
+            // A failed command should not end the session
+            if let Err(error) = run_line(line, &mut ledger, seed) {
+                println!("{}: {:?}", "Error".red(), error);
+            }
+
+            // Picks up the entities created by the command
+            if let Some(helper) = editor.helper_mut() {
+                helper.refresh(&ledger);
+            }
+        }
+
+        editor.save_history(&history_file).map_err(Error::ReplError)
+    }
+}
+
+/// Parses and runs a line entered in the REPL.
+fn run_line(line: &str, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+    let args = iter::once("resim".to_owned()).chain(split_line(line));
+    let cli = match ResimCli::try_parse_from(args) {
+        Ok(cli) => cli,
+        // Includes `--help`, which clap reports as an error too
+        Err(error) => return error.print().map_err(Error::IOError),
+    };
+    // A seed given to the session holds for each of its commands
+    let seed = cli.seed.or(seed);
+
+    match cli.command {
+        Command::Balances(cmd) => cmd.run_with(ledger),
+        Command::CallFunction(cmd) => cmd.run_with(ledger, seed),
+        Command::CallMethod(cmd) => cmd.run_with(ledger, seed),
+        Command::Completion(cmd) => cmd.run(),
+        Command::Config(cmd) => cmd.run_with(ledger),
+        Command::Doctor(cmd) => cmd.run_with(ledger),
+        Command::ExportAbi(cmd) => cmd.run_with(ledger),
+        Command::ExportAccount(cmd) => cmd.run_with(ledger),
+        Command::Faucet(cmd) => cmd.run_with(ledger, seed),
+        Command::FreezeVault(cmd) => cmd.run_with(ledger, seed),
+        Command::Fsck(cmd) => cmd.run_with(ledger),
+        Command::History(cmd) => cmd.run(),
+        Command::ImportAccount(cmd) => cmd.run_with(ledger, seed),
+        Command::Mint(cmd) => cmd.run_with(ledger, seed),
+        Command::NewAccount(cmd) => cmd.run_with(ledger, seed),
+        Command::NewBadgeFixed(cmd) => cmd.run_with(ledger, seed),
+        Command::NewBadgeMutable(cmd) => cmd.run_with(ledger, seed),
+        Command::NewSimpleBadge(cmd) => cmd.run_with(ledger, seed),
+        Command::NewTokenFixed(cmd) => cmd.run_with(ledger, seed),
+        Command::NewTokenMutable(cmd) => cmd.run_with(ledger, seed),
+        Command::Publish(cmd) => cmd.run_with(ledger, seed),
+        Command::Replay(cmd) => cmd.run_with(ledger, seed),
+        Command::Run(cmd) => cmd.run_with(ledger, seed),
+        Command::SetCurrentEpoch(cmd) => cmd.run_with(ledger, seed),
+        Command::SetDefaultAccount(cmd) => cmd.run_with(ledger),
+        Command::ShowConfigs(cmd) => cmd.run_with(ledger),
+        Command::ShowLedger(cmd) => cmd.run_with(ledger),
+        Command::ShowTransaction(cmd) => cmd.run(),
+        Command::Show(cmd) => cmd.run_with(ledger),
+        Command::Transfer(cmd) => cmd.run_with(ledger, seed),
+        Command::UnfreezeVault(cmd) => cmd.run_with(ledger, seed),
+        // These need the ledger closed, or don't return
+        Command::Repl(_) => Err(Error::UnsupportedInRepl("repl".to_owned())),
+        Command::Reset(_) => Err(Error::UnsupportedInRepl("reset".to_owned())),
+        Command::Serve(_) => Err(Error::UnsupportedInRepl("serve".to_owned())),
+    }
+}
+
+/// Splits a line into arguments as a shell would, honouring single and double quotes, and
+/// backslash escapes outside single quotes.
+fn split_line(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg: Option<String> = None;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (None, c) if c.is_whitespace() => args.extend(arg.take()),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                arg.get_or_insert_with(String::new);
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => arg.get_or_insert_with(String::new).push(c),
+            (_, '\\') => arg.get_or_insert_with(String::new).extend(chars.next()),
+            (_, c) => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+    args
+}
+
+/// Completes command names, and the addresses of the entities in the ledger.
+struct ReplHelper {
+    commands: Vec<String>,
+    addresses: Vec<String>,
+}
+
+impl ReplHelper {
+    fn new(ledger: &RadixEngineDB) -> Self {
+        let mut helper = Self {
+            commands: ResimCli::command()
+                .get_subcommands()
+                .map(|c| c.get_name().to_owned())
+                .chain(["exit".to_owned(), "quit".to_owned()])
+                .collect(),
+            addresses: Vec::new(),
+        };
+        helper.refresh(ledger);
+        helper
+    }
+
+    /// Reloads the addresses from the ledger.
+    fn refresh(&mut self, ledger: &RadixEngineDB) {
+        self.addresses = ledger
+            .list_packages()
+            .into_iter()
+            .chain(ledger.list_components())
+            .chain(ledger.list_resource_defs())
+            .map(|address| address.to_string())
+            .collect();
+    }
+}
+
+impl Completer for ReplHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let start = line[..pos]
+            .char_indices()
+            .rfind(|(_, c)| c.is_whitespace() || *c == '"' || *c == ',')
+            .map(|(i, c)| i + c.len_utf8())
+            .unwrap_or(0);
+        let word = &line[start..pos];
+        // Only the first word is a command
+        let candidates = if line[..start].trim().is_empty() {
+            &self.commands
+        } else {
+            &self.addresses
+        };
+        Ok((
+            start,
+            candidates
+                .iter()
+                .filter(|c| c.starts_with(word))
+                .cloned()
+                .collect(),
+        ))
+    }
+}
+
+impl Hinter for ReplHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
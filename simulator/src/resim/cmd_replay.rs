@@ -0,0 +1,38 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+use crate::ledger::*;
+use crate::resim::*;
+
+/// Run a transaction executed by this simulator again, against the current ledger state
+#[derive(Parser, Debug)]
+pub struct Replay {
+    /// The transaction hash
+    hash: H256,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl Replay {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        // The log is closed right away, as running the transaction records it again.
+        // If a failed transaction shares the hash, the committed one comes last.
+        let (_, record) = TransactionLog::new(get_transaction_log_dir()?)
+            .find(self.hash)
+            .pop()
+            .ok_or(Error::TransactionNotFound(self.hash))?;
+
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        process_transaction(record.transaction, &mut executor, &None, seed)
+    }
+}
@@ -1,17 +1,115 @@
 use clap::Parser;
-use std::fs::remove_dir_all;
+use radix_engine::ledger::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::{self, remove_dir_all};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
+use crate::ledger::*;
 use crate::resim::*;
 
 /// Reset this simulator
 #[derive(Parser, Debug)]
-pub struct Reset {}
+pub struct Reset {
+    /// A JSON file specifying the resources, accounts and packages to bootstrap the ledger with
+    #[clap(long)]
+    genesis: Option<PathBuf>,
+}
+
+/// The genesis file format, with addresses, keys and amounts written as in commands.
+///
+/// ```json
+/// {
+///     "resources": [{ "address": "03...", "divisibility": 18, "metadata": { "symbol": "USD" } }],
+///     "accounts": [{ "address": "02...", "public_key": "...", "balances": { "03...": "100" } }],
+///     "packages": [{ "address": "01...", "path": "package.wasm" }]
+/// }
+/// ```
+///
+/// Package paths are relative to the genesis file.
+#[derive(Deserialize)]
+struct GenesisFile {
+    #[serde(default)]
+    resources: Vec<GenesisFileResource>,
+    #[serde(default)]
+    accounts: Vec<GenesisFileAccount>,
+    #[serde(default)]
+    packages: Vec<GenesisFilePackage>,
+}
+
+#[derive(Deserialize)]
+struct GenesisFileResource {
+    address: String,
+    divisibility: u8,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct GenesisFileAccount {
+    address: String,
+    public_key: String,
+    #[serde(default)]
+    balances: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct GenesisFilePackage {
+    address: String,
+    path: PathBuf,
+}
 
 impl Reset {
     pub fn run(&self) -> Result<(), Error> {
         let dir = get_data_dir()?;
         remove_dir_all(dir).map_err(Error::IOError)?;
         println!("Data directory cleared.");
+
+        if let Some(path) = &self.genesis {
+            let genesis = read_genesis(path)?;
+            RadixEngineDB::with_genesis(get_data_dir()?, &genesis).map_err(Error::GenesisError)?;
+            println!("Ledger bootstrapped with genesis.");
+        }
         Ok(())
     }
 }
+
+fn read_genesis(path: &Path) -> Result<Genesis, Error> {
+    let text = fs::read_to_string(path).map_err(Error::IOError)?;
+    let file: GenesisFile = serde_json::from_str(&text).map_err(Error::JSONError)?;
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut genesis = Genesis::new();
+    for resource in file.resources {
+        genesis.resources.push(GenesisResource {
+            address: parse(&resource.address)?,
+            divisibility: resource.divisibility,
+            metadata: resource.metadata.into_iter().collect(),
+        });
+    }
+    for account in file.accounts {
+        let mut balances = Vec::new();
+        for (resource_address, amount) in account.balances {
+            balances.push((parse(&resource_address)?, parse(&amount)?));
+        }
+        genesis.accounts.push(GenesisAccount {
+            address: parse(&account.address)?,
+            public_key: parse(&account.public_key)?,
+            balances,
+        });
+    }
+    for package in file.packages {
+        genesis.packages.push(GenesisPackage {
+            address: parse(&package.address)?,
+            code: fs::read(dir.join(package.path)).map_err(Error::IOError)?,
+        });
+    }
+    Ok(genesis)
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| Error::InvalidGenesisValue(value.to_owned()))
+}
@@ -15,23 +15,44 @@ pub struct Run {
     #[clap(short, long)]
     signers: Option<Vec<EcdsaPublicKey>>,
 
+    /// A message to attach to the transaction, e.g. a memo labelling it
+    #[clap(long)]
+    message: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
 }
 
 impl Run {
-    pub fn run(&self) -> Result<(), Error> {
+    /// Creates a command which runs the given manifest with the default signers.
+    pub fn new(path: PathBuf, trace: bool) -> Self {
+        Self {
+            path,
+            signers: None,
+            message: None,
+            trace,
+        }
+    }
+
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        let mut executor = TransactionExecutor::new(&mut ledger, self.trace);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
         let default_signers = get_default_signers()?;
         let manifest = std::fs::read_to_string(&self.path).map_err(Error::IOError)?;
         let mut transaction =
             transaction_manifest::compile(&manifest).map_err(Error::CompileError)?;
+        transaction.message = self.message.clone().map(String::into_bytes);
         let signatures = self.signers.clone().unwrap_or(default_signers);
         transaction
             .instructions
             .push(Instruction::End { signatures });
-        process_transaction(transaction, &mut executor, &None)
+        process_transaction(transaction, &mut executor, &None, seed)
     }
 }
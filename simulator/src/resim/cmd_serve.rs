@@ -0,0 +1,523 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::ledger::*;
+use radix_engine::model::*;
+use radix_engine::transaction::*;
+use sbor::any::decode_any;
+use sbor::describe::Type;
+use scrypto::types::*;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::str::FromStr;
+use std::time::Duration;
+use transaction_manifest::decompile;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// Serve the simulator over HTTP, as a JSON-RPC endpoint for dApp front-ends
+#[derive(Parser, Debug)]
+pub struct Serve {
+    /// The address to listen on
+    #[clap(short, long, default_value = "127.0.0.1:8080")]
+    address: String,
+
+    /// An origin, besides those on localhost, from which browsers may call the endpoint,
+    /// e.g. `http://192.168.1.2:3000`
+    #[clap(long)]
+    allow_origin: Vec<String>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+/// The largest request body accepted, in bytes.
+const MAX_BODY_SIZE: usize = 4 * 1024 * 1024;
+
+/// How long to wait for a client to send its request, so that a stalled client doesn't hold
+/// up the requests queued behind it.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Represents an error when handling a JSON-RPC request.
+enum RpcError {
+    ParseError(serde_json::Error),
+    MethodNotFound(String),
+    InvalidParams(String),
+    Resim(Error),
+}
+
+impl RpcError {
+    /// Returns the JSON-RPC 2.0 error code.
+    fn code(&self) -> i64 {
+        match self {
+            RpcError::ParseError(_) => -32700,
+            RpcError::MethodNotFound(_) => -32601,
+            RpcError::InvalidParams(_) => -32602,
+            RpcError::Resim(_) => -32000,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            RpcError::ParseError(e) => format!("Parse error: {}", e),
+            RpcError::MethodNotFound(method) => format!("Method not found: {}", method),
+            RpcError::InvalidParams(e) => format!("Invalid params: {}", e),
+            RpcError::Resim(e) => format!("{:?}", e),
+        }
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(error: Error) -> Self {
+        RpcError::Resim(error)
+    }
+}
+
+#[derive(Deserialize)]
+struct PublishParams {
+    /// The hex-encoded WASM code of the package.
+    code: String,
+}
+
+#[derive(Deserialize)]
+struct CallFunctionParams {
+    package_address: String,
+    blueprint_name: String,
+    function_name: String,
+    #[serde(default)]
+    arguments: Vec<serde_json::Value>,
+    signers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct CallMethodParams {
+    component_address: String,
+    method_name: String,
+    #[serde(default)]
+    arguments: Vec<serde_json::Value>,
+    signers: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct ShowParams {
+    address: String,
+}
+
+impl Serve {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let listener = TcpListener::bind(&self.address).map_err(Error::IOError)?;
+        println!(
+            "Serving JSON-RPC at {}",
+            format!("http://{}", self.address).green()
+        );
+
+        // Requests are handled one at a time, as they all go to the same ledger, so none of
+        // them may take long, e.g. to build a package
+        for stream in listener.incoming() {
+            let result = stream.and_then(|s| self.handle_connection(s, seed));
+            if let Err(error) = result {
+                println!("{}: {}", "Error".red(), error);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_connection(&self, mut stream: TcpStream, seed: Option<u64>) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let method = request_line
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_owned();
+
+        let mut content_length = 0;
+        let mut origin = None;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                if name.trim().eq_ignore_ascii_case("content-length") {
+                    content_length = value.trim().parse().unwrap_or(0);
+                } else if name.trim().eq_ignore_ascii_case("origin") {
+                    origin = Some(value.trim().to_owned());
+                }
+            }
+        }
+
+        // Requests without an origin don't come from a browser, and aren't subject to CORS
+        let origin = match origin {
+            Some(origin) if !self.is_allowed_origin(&origin) => {
+                return write_response(&mut stream, "403 Forbidden", None, "");
+            }
+            origin => origin,
+        };
+        let origin = origin.as_deref();
+
+        match method.as_str() {
+            // CORS preflight, sent by browsers before the actual request
+            "OPTIONS" => write_response(&mut stream, "204 No Content", origin, ""),
+            "POST" if content_length > MAX_BODY_SIZE => {
+                write_response(&mut stream, "413 Payload Too Large", origin, "")
+            }
+            "POST" => {
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body)?;
+                let response = self.handle_request(&body, seed);
+                write_response(&mut stream, "200 OK", origin, &response.to_string())
+            }
+            _ => write_response(&mut stream, "405 Method Not Allowed", origin, ""),
+        }
+    }
+
+    /// Returns whether browsers may call the endpoint from the given origin, which is either
+    /// on localhost or allowed with `--allow-origin`.
+    fn is_allowed_origin(&self, origin: &str) -> bool {
+        if self.allow_origin.iter().any(|o| o == origin) {
+            return true;
+        }
+        let host = match origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"))
+        {
+            Some(host) => host,
+            None => return false,
+        };
+        let host = match host.rsplit_once(':') {
+            Some((host, port)) if !port.is_empty() && port.chars().all(|c| c.is_ascii_digit()) => {
+                host
+            }
+            _ => host,
+        };
+        matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+    }
+
+    /// Handles a JSON-RPC 2.0 request, and returns the response.
+    fn handle_request(&self, body: &[u8], seed: Option<u64>) -> serde_json::Value {
+        let request: serde_json::Value = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return error_response(serde_json::Value::Null, RpcError::ParseError(e)),
+        };
+        let id = request
+            .get("id")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+        let method = request
+            .get("method")
+            .and_then(|m| m.as_str())
+            .unwrap_or_default();
+        let params = request
+            .get("params")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null);
+
+        let result = match method {
+            "publish" => parse_params(params).and_then(|p| self.publish(p, seed)),
+            "call_function" => parse_params(params).and_then(|p| self.call_function(p, seed)),
+            "call_method" => parse_params(params).and_then(|p| self.call_method(p, seed)),
+            "show" => parse_params(params).and_then(|p| self.show(p)),
+            _ => Err(RpcError::MethodNotFound(method.to_owned())),
+        };
+        match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(error) => error_response(id, error),
+        }
+    }
+
+    /// Publishes a package from its code, which the caller builds, so that requests can't
+    /// read files or run builds on this machine.
+    fn publish(
+        &self,
+        params: PublishParams,
+        seed: Option<u64>,
+    ) -> Result<serde_json::Value, RpcError> {
+        let code = hex::decode(&params.code)
+            .map_err(|e| RpcError::InvalidParams(format!("code: {}", e)))?;
+
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor =
+            TransactionExecutor::new(&mut ledger, self.trace).with_cost_table(get_cost_table()?);
+        let transaction = TransactionBuilder::new(&executor)
+            .publish_package(&code)
+            .build(Vec::new())
+            .map_err(Error::TransactionConstructionError)?;
+        run_and_convert(transaction, &mut executor, seed)
+    }
+
+    fn call_function(
+        &self,
+        params: CallFunctionParams,
+        seed: Option<u64>,
+    ) -> Result<serde_json::Value, RpcError> {
+        let package_address = parse_param(&params.package_address)?;
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor =
+            TransactionExecutor::new(&mut ledger, self.trace).with_cost_table(get_cost_table()?);
+        let default_account = get_default_account()?;
+        let transaction = TransactionBuilder::new(&executor)
+            .call_function(
+                package_address,
+                &params.blueprint_name,
+                &params.function_name,
+                to_arguments(&params.arguments),
+                Some(default_account),
+            )
+            .call_method_with_all_resources(default_account, "deposit_batch")
+            .build(parse_signers(&params.signers)?)
+            .map_err(Error::TransactionConstructionError)?;
+        run_and_convert(transaction, &mut executor, seed)
+    }
+
+    fn call_method(
+        &self,
+        params: CallMethodParams,
+        seed: Option<u64>,
+    ) -> Result<serde_json::Value, RpcError> {
+        let component_address = parse_param(&params.component_address)?;
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut executor =
+            TransactionExecutor::new(&mut ledger, self.trace).with_cost_table(get_cost_table()?);
+        let default_account = get_default_account()?;
+        let transaction = TransactionBuilder::new(&executor)
+            .call_method(
+                component_address,
+                &params.method_name,
+                to_arguments(&params.arguments),
+                Some(default_account),
+            )
+            .call_method_with_all_resources(default_account, "deposit_batch")
+            .build(parse_signers(&params.signers)?)
+            .map_err(Error::TransactionConstructionError)?;
+        run_and_convert(transaction, &mut executor, seed)
+    }
+
+    fn show(&self, params: ShowParams) -> Result<serde_json::Value, RpcError> {
+        let address: Address = parse_param(&params.address)?;
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        match address {
+            Address::Package(_) => {
+                let package = ledger
+                    .get_package(address)
+                    .ok_or(Error::LedgerDumpError(DisplayError::PackageNotFound))?;
+                Ok(json!({
+                    "type": "Package",
+                    "address": address.to_string(),
+                    "code_size": package.code().len(),
+                    "version": package.version(),
+                    "owner_badge": package.owner_badge().map(|a| a.to_string()),
+                }))
+            }
+            Address::Component(_) => {
+                let component = ledger
+                    .get_component(address)
+                    .ok_or(Error::LedgerDumpError(DisplayError::ComponentNotFound))?;
+                let vaults: Vec<serde_json::Value> = ledger
+                    .get_vaults(&address)
+                    .into_iter()
+                    .map(|(vid, vault)| {
+                        json!({
+                            "vault_id": vid.to_string(),
+                            "resource_address": vault.resource_address().to_string(),
+                            "amount": vault.amount().to_string(),
+                        })
+                    })
+                    .collect();
+                let storage = ledger.get_storage_footprint(&address);
+
+                // Falls back to the state without field names if the ABI can't be exported
+                let state_type = TransactionExecutor::new(&mut ledger, false)
+                    .export_abi_component(address)
+                    .ok()
+                    .and_then(|abi| abi.state);
+                let state = decode_any(component.state()).map_err(Error::DataError)?;
+                Ok(json!({
+                    "type": "Component",
+                    "address": address.to_string(),
+                    "package_address": component.package_address().to_string(),
+                    "blueprint_name": component.blueprint_name(),
+                    "storage": storage,
+                    "state": value_to_json(&state, state_type.as_ref()),
+                    "vaults": vaults,
+                }))
+            }
+            Address::ResourceDef(_) => {
+                let resource_def = ledger
+                    .get_resource_def(address)
+                    .ok_or(Error::LedgerDumpError(DisplayError::ResourceDefNotFound))?;
+                let metadata: BTreeMap<&String, &String> = resource_def.metadata().iter().collect();
+                Ok(json!({
+                    "type": "ResourceDef",
+                    "address": address.to_string(),
+                    "resource_type": format!("{:?}", resource_def.resource_type()),
+                    "metadata": metadata,
+                    "flags": resource_def.flags(),
+                    "mutable_flags": resource_def.mutable_flags(),
+                    "total_supply": resource_def.total_supply().to_string(),
+                }))
+            }
+        }
+    }
+}
+
+fn parse_params<T: for<'de> Deserialize<'de>>(params: serde_json::Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::InvalidParams(e.to_string()))
+}
+
+fn parse_param<T: FromStr>(s: &str) -> Result<T, RpcError>
+where
+    T::Err: std::fmt::Debug,
+{
+    T::from_str(s).map_err(|e| RpcError::InvalidParams(format!("{}: {:?}", s, e)))
+}
+
+/// Parses the given signers, or returns the default ones.
+fn parse_signers(signers: &Option<Vec<String>>) -> Result<Vec<EcdsaPublicKey>, RpcError> {
+    match signers {
+        Some(signers) => signers.iter().map(|s| parse_param(s)).collect(),
+        None => Ok(get_default_signers()?),
+    }
+}
+
+/// Converts call arguments into their command-line form, which is parsed with the ABI.
+fn to_arguments(arguments: &[serde_json::Value]) -> Vec<String> {
+    arguments
+        .iter()
+        .map(|a| match a {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+        .collect()
+}
+
+/// Runs a transaction, and converts its receipt into JSON, with the call outputs decoded
+/// with the blueprint ABI.
+fn run_and_convert<L: SubstateStore>(
+    transaction: Transaction,
+    executor: &mut TransactionExecutor<L>,
+    seed: Option<u64>,
+) -> Result<serde_json::Value, RpcError> {
+    let transaction_hash = executor.next_transaction_hash();
+    let manifest = decompile(&transaction).map_err(Error::DecompileError)?;
+    let receipt = run_transaction(transaction, executor, seed)?;
+
+    let outputs: Vec<serde_json::Value> = receipt
+        .transaction
+        .instructions
+        .iter()
+        .zip(&receipt.outputs)
+        .map(|(instruction, output)| {
+            value_to_json(&output.dom, output_type(executor, instruction).as_ref())
+        })
+        .collect();
+    let error = match &receipt.status {
+        TransactionStatus::Committed => None,
+        TransactionStatus::Failed { error, .. } => Some(format!("{:?}", error)),
+        TransactionStatus::Rejected(reason) => Some(format!("{:?}", reason)),
+    };
+    let logs: Vec<serde_json::Value> = receipt
+        .logs
+        .iter()
+        .map(|(level, message)| json!({ "level": format!("{:?}", level), "message": message }))
+        .collect();
+    let new_entities: Vec<String> = receipt.new_entities.iter().map(|a| a.to_string()).collect();
+    let warnings: Vec<String> = receipt
+        .warnings
+        .iter()
+        .map(|w| format!("{:?}", w))
+        .collect();
+
+    Ok(json!({
+        "transaction_hash": transaction_hash.to_string(),
+        "status": receipt.status.to_string(),
+        "error": error,
+        "manifest": manifest,
+        "outputs": outputs,
+        "logs": logs,
+        "new_entities": new_entities,
+        "warnings": warnings,
+    }))
+}
+
+/// Returns the ABI type of the output of a call instruction.
+fn output_type<L: SubstateStore>(
+    executor: &TransactionExecutor<L>,
+    instruction: &ValidatedInstruction,
+) -> Option<Type> {
+    match instruction {
+        ValidatedInstruction::CallFunction {
+            package_address,
+            blueprint_name,
+            function,
+            ..
+        } => executor
+            .export_abi(*package_address, blueprint_name)
+            .ok()?
+            .functions
+            .into_iter()
+            .find(|f| &f.name == function)
+            .map(|f| f.output),
+        ValidatedInstruction::CallMethod {
+            component_address,
+            method,
+            ..
+        } => executor
+            .export_abi_component(*component_address)
+            .ok()?
+            .methods
+            .into_iter()
+            .find(|m| &m.name == method)
+            .map(|m| m.output),
+        _ => None,
+    }
+}
+
+fn error_response(id: serde_json::Value, error: RpcError) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code(), "message": error.message() },
+    })
+}
+
+/// Writes a response, which browsers only pass on to pages of the given origin, if any.
+fn write_response(
+    stream: &mut TcpStream,
+    status: &str,
+    origin: Option<&str>,
+    body: &str,
+) -> io::Result<()> {
+    let cors_headers = match origin {
+        Some(origin) => format!(
+            "Access-Control-Allow-Origin: {}\r\n\
+             Access-Control-Allow-Methods: POST, OPTIONS\r\n\
+             Access-Control-Allow-Headers: Content-Type\r\n\
+             Vary: Origin\r\n",
+            origin
+        ),
+        None => String::new(),
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         {}\
+         Connection: close\r\n\
+         \r\n\
+         {}",
+        status,
+        body.len(),
+        cors_headers,
+        body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,36 @@
+use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use radix_engine::engine::CostTable;
+
+use crate::resim::*;
+
+/// Set the cost table used to charge transactions
+#[derive(Parser, Debug)]
+pub struct SetCostTable {
+    /// The cost table file, one `key = price` entry per line; omit to restore the default table
+    path: Option<PathBuf>,
+}
+
+impl SetCostTable {
+    pub fn run(&self) -> Result<(), Error> {
+        let file = get_cost_table_file()?;
+        match &self.path {
+            Some(path) => {
+                let text = fs::read_to_string(path).map_err(Error::IOError)?;
+                let cost_table = CostTable::from_str(&text).map_err(Error::CostTableError)?;
+                fs::write(file, cost_table.to_string()).map_err(Error::IOError)?;
+                println!("Cost table updated!");
+            }
+            None => {
+                if file.exists() {
+                    fs::remove_file(file).map_err(Error::IOError)?;
+                }
+                println!("Default cost table restored!");
+            }
+        }
+        Ok(())
+    }
+}
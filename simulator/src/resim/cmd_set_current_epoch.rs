@@ -1,21 +1,42 @@
 use clap::Parser;
 use radix_engine::ledger::SubstateStore;
+use radix_engine::transaction::*;
 
 use crate::resim::*;
 
-/// Set the current epoch
+/// Set the current epoch, and make the calls scheduled up to it
 #[derive(Parser, Debug)]
 pub struct SetCurrentEpoch {
     /// The new epoch number
     epoch: u64,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
 }
 
 impl SetCurrentEpoch {
-    pub fn run(&self) -> Result<(), Error> {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
         let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
-        ledger.set_epoch(self.epoch);
+        self.run_with(&mut ledger, seed)
+    }
 
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        ledger.set_epoch(self.epoch);
         println!("Current epoch set!");
+
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        for mut receipt in executor
+            .run_due_callbacks(self.epoch)
+            .map_err(Error::TransactionValidationError)?
+        {
+            if seed.is_some() {
+                receipt.execution_time = None;
+            }
+            println!("{:?}", receipt);
+        }
         Ok(())
     }
 }
@@ -15,6 +15,16 @@ pub struct SetDefaultAccount {
 
 impl SetDefaultAccount {
     pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        if get_account_key(ledger, self.address)? != self.public_key {
+            return Err(Error::AccountKeyMismatch(self.address, self.public_key));
+        }
+
         set_configs(&Configs {
             default_account: self.address,
             default_signers: vec![self.public_key],
@@ -0,0 +1,31 @@
+use clap::Parser;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Set default transaction signers
+#[derive(Parser, Debug)]
+pub struct SetDefaultSigners {
+    /// The public keys to sign transactions with
+    #[clap(required = true)]
+    public_keys: Vec<EcdsaPublicKey>,
+}
+
+impl SetDefaultSigners {
+    pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        check_signers(ledger, &self.public_keys)?;
+
+        let mut configs = get_configs()?.ok_or(Error::NoDefaultAccount)?;
+        configs.default_signers = self.public_keys.clone();
+        set_configs(&configs)?;
+
+        println!("Default signers updated!");
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use clap::Parser;
+use radix_engine::transaction::*;
 use scrypto::types::*;
 
 use crate::ledger::*;
@@ -13,16 +14,26 @@ pub struct Show {
 
 impl Show {
     pub fn run(&self) -> Result<(), Error> {
-        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB) -> Result<(), Error> {
         match self.address {
             Address::Package(_) => {
-                dump_package(self.address, &ledger).map_err(Error::LedgerDumpError)
+                dump_package(self.address, ledger).map_err(Error::LedgerDumpError)
             }
             Address::Component(_) => {
-                dump_component(self.address, &ledger).map_err(Error::LedgerDumpError)
+                // Falls back to the raw state if the ABI can't be exported
+                let state_fields = TransactionExecutor::new(ledger, false)
+                    .export_abi_with_state(self.address)
+                    .map(|(_, fields)| fields)
+                    .unwrap_or_default();
+                dump_component(self.address, ledger, &state_fields).map_err(Error::LedgerDumpError)
             }
             Address::ResourceDef(_) => {
-                dump_resource_def(self.address, &ledger).map_err(Error::LedgerDumpError)
+                dump_resource_def(self.address, ledger).map_err(Error::LedgerDumpError)
             }
         }
     }
@@ -1,5 +1,6 @@
 use clap::Parser;
 use colored::*;
+use radix_engine::ledger::SubstateStore;
 
 use crate::resim::*;
 
@@ -9,6 +10,12 @@ pub struct ShowConfigs {}
 
 impl ShowConfigs {
     pub fn run(&self) -> Result<(), Error> {
+        let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
         if let Some(configs) = get_configs()? {
             println!(
                 "{}: {:?}",
@@ -23,6 +30,16 @@ impl ShowConfigs {
         } else {
             println!("No configuration found");
         }
+
+        let badges = get_badges()?;
+        if !badges.is_empty() {
+            println!("{}:", "Badges".green().bold());
+            for (name, address) in badges {
+                println!("- {}: {}", name, address);
+            }
+        }
+
+        println!("{}: {}", "Current Epoch".green().bold(), ledger.get_epoch());
         Ok(())
     }
 }
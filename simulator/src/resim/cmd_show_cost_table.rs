@@ -0,0 +1,14 @@
+use clap::Parser;
+
+use crate::resim::*;
+
+/// Show the cost table used to charge transactions
+#[derive(Parser, Debug)]
+pub struct ShowCostTable {}
+
+impl ShowCostTable {
+    pub fn run(&self) -> Result<(), Error> {
+        print!("{}", get_cost_table()?);
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use clap::Parser;
 use colored::*;
+use radix_engine::ledger::*;
 
 use crate::ledger::*;
 use crate::resim::*;
@@ -12,20 +13,46 @@ pub struct ShowLedger {}
 impl ShowLedger {
     pub fn run(&self) -> Result<(), Error> {
         let ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&ledger)
+    }
 
-        println!("{}:", "Packages".green().bold());
-        for (last, address) in ledger.list_packages().iter().identify_last() {
-            println!("{} {}", list_item_prefix(last), address,);
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &RadixEngineDB) -> Result<(), Error> {
+        let packages = ledger.list_packages();
+        println!("{} ({}):", "Packages".green().bold(), packages.len());
+        for (last, address) in packages.iter().identify_last() {
+            println!("{} {}", list_item_prefix(last), address);
         }
 
-        println!("{}:", "Components".green().bold());
-        for (last, address) in ledger.list_components().iter().identify_last() {
-            println!("{} {}", list_item_prefix(last), address,);
+        let query = LedgerQuery::new(ledger);
+
+        let components = ledger.list_components();
+        println!("{} ({}):", "Components".green().bold(), components.len());
+        for (last, address) in components.iter().identify_last() {
+            let component = query.component(*address).unwrap();
+            println!(
+                "{} {} {}",
+                list_item_prefix(last),
+                address,
+                component.blueprint_name
+            );
         }
 
-        println!("{}:", "Resource Definitions".green().bold());
-        for (last, address) in ledger.list_resource_defs().iter().identify_last() {
-            println!("{} {}", list_item_prefix(last), address,);
+        let resource_defs = ledger.list_resource_defs();
+        println!(
+            "{} ({}):",
+            "Resource Definitions".green().bold(),
+            resource_defs.len()
+        );
+        for (last, address) in resource_defs.iter().identify_last() {
+            let resource = query.resource(*address).unwrap();
+            let label = match (resource.symbol(), resource.name()) {
+                (Some(symbol), Some(name)) => format!(" {} (\"{}\")", symbol, name),
+                (Some(symbol), None) => format!(" {}", symbol),
+                (None, Some(name)) => format!(" \"{}\"", name),
+                (None, None) => String::new(),
+            };
+            println!("{} {}{}", list_item_prefix(last), address, label);
         }
 
         Ok(())
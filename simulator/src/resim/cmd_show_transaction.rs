@@ -0,0 +1,78 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::engine::*;
+use scrypto::types::*;
+use transaction_manifest::decompile;
+
+use crate::ledger::*;
+use crate::resim::*;
+use crate::utils::*;
+
+/// Show a transaction executed by this simulator
+#[derive(Parser, Debug)]
+pub struct ShowTransaction {
+    /// The transaction hash
+    hash: H256,
+}
+
+impl ShowTransaction {
+    pub fn run(&self) -> Result<(), Error> {
+        let log = TransactionLog::new(get_transaction_log_dir()?);
+
+        let records = log.find(self.hash);
+        if records.is_empty() {
+            return Err(Error::TransactionNotFound(self.hash));
+        }
+        for (index, record) in records {
+            dump_transaction(index, &record)?;
+        }
+        Ok(())
+    }
+}
+
+fn dump_transaction(index: u64, record: &TransactionRecord) -> Result<(), Error> {
+    println!("{}: #{}", "Index".green().bold(), index);
+    println!("{}: {}", "Hash".green().bold(), record.hash);
+    println!(
+        "{}: {}",
+        "Status".green().bold(),
+        if record.committed {
+            record.status.blue()
+        } else {
+            record.status.red()
+        }
+    );
+    println!("{}: {:?}", "Kind".green().bold(), record.transaction.kind);
+    if let Some(message) = &record.transaction.message {
+        println!(
+            "{}: {}",
+            "Message".green().bold(),
+            String::from_utf8_lossy(message)
+        );
+    }
+
+    let manifest = decompile(&record.transaction).map_err(Error::DecompileError)?;
+    println!("{}:\n{}", "Manifest".green().bold(), manifest.trim_end());
+
+    println!("{}:", "Instruction Outputs".green().bold());
+    for (last, output) in record.outputs.iter().identify_last() {
+        let output = validate_data(output).unwrap();
+        println!("{} {:?}", list_item_prefix(last), output);
+    }
+
+    println!("{}: {}", "Logs".green().bold(), record.logs.len());
+    for (last, (level, message)) in record.logs.iter().identify_last() {
+        println!("{} [{:?}] {}", list_item_prefix(last), level, message);
+    }
+
+    println!(
+        "{}: {}",
+        "New Entities".green().bold(),
+        record.new_entities.len()
+    );
+    for (last, address) in record.new_entities.iter().identify_last() {
+        println!("{} {}", list_item_prefix(last), address);
+    }
+
+    Ok(())
+}
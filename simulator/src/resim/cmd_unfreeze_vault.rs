@@ -0,0 +1,68 @@
+use clap::Parser;
+use radix_engine::transaction::*;
+use scrypto::types::*;
+
+use crate::resim::*;
+
+/// Unfreeze a vault
+#[derive(Parser, Debug)]
+pub struct UnfreezeVault {
+    /// The resource address
+    resource_address: Address,
+
+    /// The address of the component owning the vault
+    component_address: Address,
+
+    /// The vault ID
+    vid: VaultId,
+
+    /// The freeze badge address, or the name of a badge created by `new-simple-badge`
+    badge_address: String,
+
+    /// The transaction signers
+    #[clap(short, long)]
+    signers: Option<Vec<EcdsaPublicKey>>,
+
+    /// Output a transaction manifest without execution
+    #[clap(short, long)]
+    manifest: Option<PathBuf>,
+
+    /// Turn on tracing
+    #[clap(short, long)]
+    trace: bool,
+}
+
+impl UnfreezeVault {
+    pub fn run(&self, seed: Option<u64>) -> Result<(), Error> {
+        let mut ledger = RadixEngineDB::with_bootstrap(get_data_dir()?);
+        self.run_with(&mut ledger, seed)
+    }
+
+    /// Runs the command against an open ledger, e.g. the one kept open by `resim repl`.
+    pub fn run_with(&self, ledger: &mut RadixEngineDB, seed: Option<u64>) -> Result<(), Error> {
+        let badge_address = resolve_badge(&self.badge_address)?;
+        let mut executor =
+            TransactionExecutor::new(ledger, self.trace).with_cost_table(get_cost_table()?);
+        let default_account = get_default_account()?;
+        let default_signers = get_default_signers()?;
+        let signatures = self.signers.clone().unwrap_or(default_signers);
+        let transaction = TransactionBuilder::new(&executor)
+            .withdraw_from_account(
+                &Resource::Fungible {
+                    amount: 1.into(),
+                    resource_address: badge_address,
+                },
+                default_account,
+            )
+            .unfreeze_vault(
+                self.resource_address,
+                self.component_address,
+                self.vid,
+                badge_address,
+            )
+            .call_method_with_all_resources(default_account, "deposit_batch")
+            .build(signatures)
+            .map_err(Error::TransactionConstructionError)?;
+        process_transaction(transaction, &mut executor, &self.manifest, seed)
+    }
+}
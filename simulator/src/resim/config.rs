@@ -1,10 +1,16 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
 
+use radix_engine::engine::CostTable;
+use radix_engine::ledger::*;
+use sbor::any::*;
 use sbor::*;
 use scrypto::buffer::*;
 use scrypto::types::*;
 
+use crate::ledger::*;
 use crate::resim::*;
 
 /// Simulator configurations.
@@ -14,6 +20,12 @@ pub struct Configs {
     pub default_signers: Vec<EcdsaPublicKey>,
 }
 
+/// The config file format, one variant per version.
+#[derive(TypeId, Encode, Decode)]
+enum VersionedConfigs {
+    V1(Configs),
+}
+
 /// Returns the data directory.
 pub fn get_data_dir() -> Result<PathBuf, Error> {
     let mut path = dirs::home_dir().ok_or(Error::HomeDirUnknown)?;
@@ -24,6 +36,20 @@ pub fn get_data_dir() -> Result<PathBuf, Error> {
     Ok(path)
 }
 
+/// Returns the transaction log directory.
+pub fn get_transaction_log_dir() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("transactions");
+    Ok(path)
+}
+
+/// Returns the file which keeps the command history of `resim repl`.
+pub fn get_repl_history_file() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("repl_history");
+    Ok(path.with_extension("txt"))
+}
+
 /// Returns the config file.
 pub fn get_config_file() -> Result<PathBuf, Error> {
     let mut path = get_data_dir()?;
@@ -33,19 +59,56 @@ pub fn get_config_file() -> Result<PathBuf, Error> {
 
 pub fn get_configs() -> Result<Option<Configs>, Error> {
     let path = get_config_file()?;
-    if path.exists() {
-        Ok(Some(
-            scrypto_decode(&fs::read(path).map_err(Error::IOError)?.as_ref())
-                .map_err(Error::ConfigDecodingError)?,
-        ))
-    } else {
-        Ok(None)
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let bytes = fs::read(path).map_err(Error::IOError)?;
+    match scrypto_decode(&bytes) {
+        Ok(VersionedConfigs::V1(configs)) => Ok(Some(configs)),
+        Err(e) => {
+            // Config files written before versioning hold the configs directly
+            let configs = scrypto_decode(&bytes).map_err(|_| Error::ConfigDecodingError(e))?;
+            set_configs(&configs)?;
+            Ok(Some(configs))
+        }
     }
 }
 
 pub fn set_configs(configs: &Configs) -> Result<(), Error> {
     let path = get_config_file()?;
-    fs::write(path, scrypto_encode(configs)).map_err(Error::IOError)
+    fs::write(path, scrypto_encode(&VersionedConfigs::V1(configs.clone()))).map_err(Error::IOError)
+}
+
+/// Returns the public key which owns an account.
+pub fn get_account_key(ledger: &RadixEngineDB, address: Address) -> Result<EcdsaPublicKey, Error> {
+    let component = ledger
+        .get_component(address)
+        .filter(|c| c.package_address() == ACCOUNT_PACKAGE && c.blueprint_name() == "Account")
+        .ok_or(Error::AccountNotFound(address))?;
+
+    // The key is the first field of the account state
+    match decode_any(component.state()) {
+        Ok(Value::Struct(Fields::Named(fields))) if !fields.is_empty() => {
+            let mut encoder = Encoder::with_type(Vec::new());
+            encode_any(None, &fields[0], &mut encoder);
+            scrypto_decode(&Vec::from(encoder)).map_err(Error::DataError)
+        }
+        _ => Err(Error::AccountNotFound(address)),
+    }
+}
+
+/// Checks that each key owns an account in the ledger.
+pub fn check_signers(ledger: &RadixEngineDB, signers: &[EcdsaPublicKey]) -> Result<(), Error> {
+    let keys: Vec<EcdsaPublicKey> = ledger
+        .list_components()
+        .into_iter()
+        .filter_map(|address| get_account_key(ledger, address).ok())
+        .collect();
+    match signers.iter().find(|key| !keys.contains(key)) {
+        Some(key) => Err(Error::KeyNotFound(*key)),
+        None => Ok(()),
+    }
 }
 
 pub fn get_default_account() -> Result<Address, Error> {
@@ -59,3 +122,58 @@ pub fn get_default_signers() -> Result<Vec<EcdsaPublicKey>, Error> {
         .ok_or(Error::NoDefaultAccount)
         .map(|config| config.default_signers)
 }
+
+/// Returns the badge registry file.
+pub fn get_badges_file() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("badges");
+    Ok(path.with_extension("sbor"))
+}
+
+/// Returns the badges registered by name.
+pub fn get_badges() -> Result<BTreeMap<String, Address>, Error> {
+    let path = get_badges_file()?;
+    if path.exists() {
+        scrypto_decode(fs::read(path).map_err(Error::IOError)?.as_ref())
+            .map_err(Error::ConfigDecodingError)
+    } else {
+        Ok(BTreeMap::new())
+    }
+}
+
+/// Registers a badge by name, replacing any badge previously registered with the same name.
+pub fn add_badge(name: &str, address: Address) -> Result<(), Error> {
+    let mut badges = get_badges()?;
+    badges.insert(name.to_owned(), address);
+    let path = get_badges_file()?;
+    fs::write(path, scrypto_encode(&badges)).map_err(Error::IOError)
+}
+
+/// Parses a badge address, or looks up a badge by its registered name.
+pub fn resolve_badge(address_or_name: &str) -> Result<Address, Error> {
+    match Address::from_str(address_or_name) {
+        Ok(address) => Ok(address),
+        Err(_) => get_badges()?
+            .get(address_or_name)
+            .copied()
+            .ok_or_else(|| Error::BadgeNotFound(address_or_name.to_owned())),
+    }
+}
+
+/// Returns the cost table file.
+pub fn get_cost_table_file() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("cost_table");
+    Ok(path.with_extension("txt"))
+}
+
+/// Returns the cost table used to charge transactions, the default one unless set.
+pub fn get_cost_table() -> Result<CostTable, Error> {
+    let path = get_cost_table_file()?;
+    if path.exists() {
+        let text = fs::read_to_string(path).map_err(Error::IOError)?;
+        CostTable::from_str(&text).map_err(Error::CostTableError)
+    } else {
+        Ok(CostTable::default())
+    }
+}
@@ -1,8 +1,12 @@
+use rustyline::error::ReadlineError;
 use std::io;
 
+use radix_engine::engine::*;
+use radix_engine::ledger::*;
 use radix_engine::model::*;
 use radix_engine::transaction::*;
 use sbor::*;
+use scrypto::types::*;
 
 use crate::ledger::*;
 use crate::utils::*;
@@ -12,10 +16,25 @@ use crate::utils::*;
 pub enum Error {
     NoDefaultAccount,
 
+    AccountNotFound(Address),
+
+    AccountKeyMismatch(Address, EcdsaPublicKey),
+
+    KeyNotFound(EcdsaPublicKey),
+
+    BadgeNotFound(String),
+
+    /// More proofs are given than the method has bucket ref parameters left for.
+    UnexpectedProofs(usize),
+
+    InvalidIdentity,
+
     HomeDirUnknown,
 
     ConfigDecodingError(sbor::DecodeError),
 
+    CostTableError(ParseCostTableError),
+
     IOError(io::Error),
 
     DataError(DecodeError),
@@ -30,11 +49,25 @@ pub enum Error {
 
     TransactionExecutionError(RuntimeError),
 
+    TransactionRejected(RejectionReason),
+
+    TransactionNotFound(H256),
+
     AbiExportError(RuntimeError),
 
     LedgerDumpError(DisplayError),
 
+    LedgerCheckError(BootstrapError),
+
+    GenesisError(GenesisError),
+
+    InvalidGenesisValue(String),
+
     CompileError(transaction_manifest::CompileError),
 
     DecompileError(transaction_manifest::DecompileError),
+
+    ReplError(ReadlineError),
+
+    UnsupportedInRepl(String),
 }
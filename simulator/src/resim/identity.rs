@@ -0,0 +1,28 @@
+use scrypto::types::*;
+use scrypto::utils::*;
+
+use crate::resim::*;
+
+/// Normalizes an identity to lowercase words separated by single spaces, so that differently
+/// typed copies of the same identity derive the same keys.
+pub fn normalize_identity(identity: &str) -> Result<String, Error> {
+    let words: Vec<String> = identity
+        .split_whitespace()
+        .map(|word| word.to_lowercase())
+        .collect();
+    if words.is_empty() {
+        return Err(Error::InvalidIdentity);
+    }
+    Ok(words.join(" "))
+}
+
+/// Derives the public key at the given index from a normalized identity.
+///
+/// The same identity and index always derive the same key, on any machine. Like every other
+/// key made up by the simulator, it's a hash rather than a secp256k1 point, so it only
+/// identifies a signer within resim.
+pub fn derive_public_key(identity: &str, index: u32) -> EcdsaPublicKey {
+    let mut raw = [0u8; 33];
+    raw[1..].copy_from_slice(sha256(format!("identity/{}/{}", identity, index)).as_ref());
+    EcdsaPublicKey(raw)
+}
@@ -1,49 +1,93 @@
+mod args;
+mod cmd_balances;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_completion;
+mod cmd_config;
+mod cmd_doctor;
 mod cmd_export_abi;
+mod cmd_export_account;
+mod cmd_faucet;
+mod cmd_freeze_vault;
+mod cmd_fsck;
+mod cmd_history;
+mod cmd_import_account;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
 mod cmd_new_badge_mutable;
+mod cmd_new_simple_badge;
 mod cmd_new_token_fixed;
 mod cmd_new_token_mutable;
 mod cmd_publish;
+mod cmd_repl;
+mod cmd_replay;
 mod cmd_reset;
 mod cmd_run;
+mod cmd_serve;
+mod cmd_set_cost_table;
 mod cmd_set_current_epoch;
 mod cmd_set_default_account;
+mod cmd_set_default_signers;
 mod cmd_show;
 mod cmd_show_configs;
+mod cmd_show_cost_table;
 mod cmd_show_ledger;
+mod cmd_show_transaction;
 mod cmd_transfer;
+mod cmd_unfreeze_vault;
 mod config;
 mod error;
+mod identity;
 
+pub use args::*;
+pub use cmd_balances::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
+pub use cmd_completion::*;
+pub use cmd_config::*;
+pub use cmd_doctor::*;
 pub use cmd_export_abi::*;
+pub use cmd_export_account::*;
+pub use cmd_faucet::*;
+pub use cmd_freeze_vault::*;
+pub use cmd_fsck::*;
+pub use cmd_history::*;
+pub use cmd_import_account::*;
 pub use cmd_mint::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
+pub use cmd_new_simple_badge::*;
 pub use cmd_new_token_fixed::*;
 pub use cmd_new_token_mutable::*;
 pub use cmd_publish::*;
+pub use cmd_repl::*;
+pub use cmd_replay::*;
 pub use cmd_reset::*;
 pub use cmd_run::*;
+pub use cmd_serve::*;
+pub use cmd_set_cost_table::*;
 pub use cmd_set_current_epoch::*;
 pub use cmd_set_default_account::*;
+pub use cmd_set_default_signers::*;
 pub use cmd_show::*;
 pub use cmd_show_configs::*;
+pub use cmd_show_cost_table::*;
 pub use cmd_show_ledger::*;
+pub use cmd_show_transaction::*;
 pub use cmd_transfer::*;
+pub use cmd_unfreeze_vault::*;
 pub use config::*;
 pub use error::*;
+pub use identity::*;
 
 use clap::{Parser, Subcommand};
 use radix_engine::ledger::*;
 use radix_engine::model::*;
 use radix_engine::transaction::*;
+use scrypto::types::*;
+use scrypto::utils::*;
 use std::fs;
 use std::path::PathBuf;
 use transaction_manifest::decompile;
@@ -54,54 +98,110 @@ use crate::ledger::*;
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None, name = "resim")]
 pub struct ResimCli {
+    /// Run in deterministic mode, with all generated keys derived from this seed
+    #[clap(long, global = true)]
+    seed: Option<u64>,
+
     #[clap(subcommand)]
     command: Command,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    Balances(Balances),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    Completion(Completion),
+    Config(Config),
+    Doctor(Doctor),
     ExportAbi(ExportAbi),
+    ExportAccount(ExportAccount),
+    Faucet(Faucet),
+    FreezeVault(FreezeVault),
+    Fsck(Fsck),
+    History(History),
+    ImportAccount(ImportAccount),
     Mint(Mint),
     NewAccount(NewAccount),
     NewBadgeFixed(NewBadgeFixed),
     NewBadgeMutable(NewBadgeMutable),
+    NewSimpleBadge(NewSimpleBadge),
     NewTokenFixed(NewTokenFixed),
     NewTokenMutable(NewTokenMutable),
     Publish(Publish),
+    Repl(Repl),
+    Replay(Replay),
     Reset(Reset),
     Run(Run),
+    Serve(Serve),
     SetCurrentEpoch(SetCurrentEpoch),
+    /// Deprecated, use `config set-default-account` instead
+    #[clap(hide = true)]
     SetDefaultAccount(SetDefaultAccount),
+    /// Deprecated, use `config show` instead
+    #[clap(hide = true)]
     ShowConfigs(ShowConfigs),
     ShowLedger(ShowLedger),
+    ShowTransaction(ShowTransaction),
     Show(Show),
     Transfer(Transfer),
+    UnfreezeVault(UnfreezeVault),
 }
 
 pub fn run() -> Result<(), Error> {
     let cli = ResimCli::parse();
+    let seed = cli.seed;
+
+    // Fail fast on a stale or corrupted data directory, rather than with confusing errors
+    // halfway through a transaction
+    if !matches!(
+        cli.command,
+        Command::Completion(_)
+            | Command::Config(_)
+            | Command::Doctor(_)
+            | Command::Reset(_)
+            | Command::ShowConfigs(_)
+    ) {
+        RadixEngineDB::with_bootstrap(get_data_dir()?)
+            .check_bootstrap()
+            .map_err(Error::LedgerCheckError)?;
+    }
 
     match cli.command {
-        Command::CallFunction(cmd) => cmd.run(),
-        Command::CallMethod(cmd) => cmd.run(),
+        Command::Balances(cmd) => cmd.run(),
+        Command::CallFunction(cmd) => cmd.run(seed),
+        Command::CallMethod(cmd) => cmd.run(seed),
+        Command::Completion(cmd) => cmd.run(),
+        Command::Config(cmd) => cmd.run(),
+        Command::Doctor(cmd) => cmd.run(),
         Command::ExportAbi(cmd) => cmd.run(),
-        Command::Mint(cmd) => cmd.run(),
-        Command::NewAccount(cmd) => cmd.run(),
-        Command::NewBadgeFixed(cmd) => cmd.run(),
-        Command::NewBadgeMutable(cmd) => cmd.run(),
-        Command::NewTokenFixed(cmd) => cmd.run(),
-        Command::NewTokenMutable(cmd) => cmd.run(),
-        Command::Publish(cmd) => cmd.run(),
+        Command::ExportAccount(cmd) => cmd.run(),
+        Command::Faucet(cmd) => cmd.run(seed),
+        Command::FreezeVault(cmd) => cmd.run(seed),
+        Command::Fsck(cmd) => cmd.run(),
+        Command::History(cmd) => cmd.run(),
+        Command::ImportAccount(cmd) => cmd.run(seed),
+        Command::Mint(cmd) => cmd.run(seed),
+        Command::NewAccount(cmd) => cmd.run(seed),
+        Command::NewBadgeFixed(cmd) => cmd.run(seed),
+        Command::NewBadgeMutable(cmd) => cmd.run(seed),
+        Command::NewSimpleBadge(cmd) => cmd.run(seed),
+        Command::NewTokenFixed(cmd) => cmd.run(seed),
+        Command::NewTokenMutable(cmd) => cmd.run(seed),
+        Command::Publish(cmd) => cmd.run(seed),
+        Command::Repl(cmd) => cmd.run(seed),
+        Command::Replay(cmd) => cmd.run(seed),
         Command::Reset(cmd) => cmd.run(),
-        Command::Run(cmd) => cmd.run(),
-        Command::SetCurrentEpoch(cmd) => cmd.run(),
+        Command::Run(cmd) => cmd.run(seed),
+        Command::Serve(cmd) => cmd.run(seed),
+        Command::SetCurrentEpoch(cmd) => cmd.run(seed),
         Command::SetDefaultAccount(cmd) => cmd.run(),
         Command::ShowConfigs(cmd) => cmd.run(),
         Command::ShowLedger(cmd) => cmd.run(),
+        Command::ShowTransaction(cmd) => cmd.run(),
         Command::Show(cmd) => cmd.run(),
-        Command::Transfer(cmd) => cmd.run(),
+        Command::Transfer(cmd) => cmd.run(seed),
+        Command::UnfreezeVault(cmd) => cmd.run(seed),
     }
 }
 
@@ -109,18 +209,80 @@ pub fn process_transaction<L: SubstateStore>(
     transaction: Transaction,
     executor: &mut TransactionExecutor<L>,
     manifest: &Option<PathBuf>,
+    seed: Option<u64>,
 ) -> Result<(), Error> {
     match manifest {
         Some(path) => {
             let decompiled = decompile(&transaction).map_err(Error::DecompileError)?;
             fs::write(path, decompiled).map_err(Error::IOError)
         }
-        None => {
-            let receipt = executor
-                .run(transaction)
-                .map_err(Error::TransactionValidationError)?;
-            println!("{:?}", receipt);
-            receipt.result.map_err(Error::TransactionExecutionError)
+        None => check_status(run_transaction(transaction, executor, seed)?.status),
+    }
+}
+
+/// Runs a transaction, prints its receipt and records it in the transaction log.
+///
+/// In deterministic mode, i.e. with a seed, the receipt doesn't report the execution time.
+pub fn run_transaction<L: SubstateStore>(
+    transaction: Transaction,
+    executor: &mut TransactionExecutor<L>,
+    seed: Option<u64>,
+) -> Result<Receipt, Error> {
+    let hash = executor.next_transaction_hash();
+    let mut receipt = match transaction.kind {
+        TransactionKind::User => executor.run(transaction.clone()),
+        TransactionKind::System => executor.run_system(transaction.clone()),
+    }
+    .map_err(Error::TransactionValidationError)?;
+    if seed.is_some() {
+        receipt.execution_time = None;
+    }
+    println!("{:?}", receipt);
+
+    // Failed transactions are recorded too
+    TransactionLog::new(get_transaction_log_dir()?).append(&TransactionRecord::new(
+        hash,
+        transaction,
+        &receipt,
+    ));
+    Ok(receipt)
+}
+
+/// Creates an account funded with XRD by the system, recording the transaction in the log.
+pub fn new_account<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    public_key: EcdsaPublicKey,
+    seed: Option<u64>,
+) -> Result<Address, Error> {
+    let transaction = executor.new_account_transaction(public_key);
+    let receipt = run_transaction(transaction, executor, seed)?;
+    let account = receipt.component(0);
+    check_status(receipt.status)?;
+    Ok(account.unwrap())
+}
+
+/// Generates a new public key, derived from the seed in deterministic mode.
+pub fn new_public_key<L: SubstateStore>(
+    executor: &mut TransactionExecutor<L>,
+    seed: Option<u64>,
+) -> EcdsaPublicKey {
+    match seed {
+        Some(seed) => {
+            let ledger = executor.ledger_mut();
+            let mut raw = [0u8; 33];
+            raw[1..].copy_from_slice(sha256(format!("{}/{}", seed, ledger.get_nonce())).as_ref());
+            ledger.increase_nonce();
+            EcdsaPublicKey(raw)
         }
+        None => executor.new_public_key(),
+    }
+}
+
+/// Converts the status of a transaction receipt into a result.
+pub fn check_status(status: TransactionStatus) -> Result<(), Error> {
+    match status {
+        TransactionStatus::Committed => Ok(()),
+        TransactionStatus::Failed { error, .. } => Err(Error::TransactionExecutionError(error)),
+        TransactionStatus::Rejected(reason) => Err(Error::TransactionRejected(reason)),
     }
 }
@@ -1,5 +1,8 @@
 use clap::Parser;
+use colored::*;
+use scrypto::utils::sha256;
 use std::env::current_dir;
+use std::fs;
 use std::path::PathBuf;
 
 use crate::scrypto::*;
@@ -15,15 +18,26 @@ pub struct Build {
     /// Turn on tracing
     #[clap(short, long)]
     trace: bool,
+
+    /// Build byte-identical code across machines and print its hash
+    #[clap(long)]
+    reproducible: bool,
 }
 
 impl Build {
     pub fn run(&self) -> Result<(), Error> {
-        build_package(
+        let bin = build_package(
             self.path.clone().unwrap_or(current_dir().unwrap()),
             self.trace,
+            self.reproducible,
         )
-        .map(|_| ())
-        .map_err(Error::CargoError)
+        .map_err(Error::CargoError)?;
+
+        if self.reproducible {
+            let code = fs::read(&bin).map_err(Error::IOError)?;
+            println!("{}: {}", "Code".green().bold(), bin.display());
+            println!("{}: {}", "Code hash".green().bold(), sha256(&code));
+        }
+        Ok(())
     }
 }
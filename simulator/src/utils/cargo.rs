@@ -1,3 +1,4 @@
+use std::env;
 use std::ffi::OsStr;
 use std::fs;
 use std::io;
@@ -8,6 +9,8 @@ use std::process::ExitStatus;
 
 use cargo_toml::Manifest;
 
+use crate::utils::strip_custom_sections;
+
 /// Represents an error when running a cargo command.
 #[derive(Debug)]
 pub enum CargoExecutionError {
@@ -26,14 +29,25 @@ pub enum CargoExecutionError {
     FailedToFormat(ExitStatus),
 
     InvalidManifestFile,
+
+    InvalidWasmFile,
 }
 
 /// Builds a package.
-pub fn build_package<P: AsRef<Path>>(path: P, trace: bool) -> Result<PathBuf, CargoExecutionError> {
+///
+/// In reproducible mode, dependencies must match `Cargo.lock`, local paths are remapped and
+/// custom sections are stripped from the output, so that the same source always results in
+/// the same code.
+pub fn build_package<P: AsRef<Path>>(
+    path: P,
+    trace: bool,
+    reproducible: bool,
+) -> Result<PathBuf, CargoExecutionError> {
     let mut cargo = path.as_ref().to_owned();
     cargo.push("Cargo.toml");
     if cargo.exists() {
-        let status = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .arg("build")
             .arg("--target")
             .arg("wasm32-unknown-unknown")
@@ -44,7 +58,17 @@ pub fn build_package<P: AsRef<Path>>(path: P, trace: bool) -> Result<PathBuf, Ca
                 vec!["--features", "scrypto/trace"]
             } else {
                 vec![]
-            })
+            });
+        if reproducible {
+            command
+                .arg("--locked")
+                .env(
+                    "CARGO_ENCODED_RUSTFLAGS",
+                    reproducible_rustflags(path.as_ref())?,
+                )
+                .env("CARGO_INCREMENTAL", "0");
+        }
+        let status = command
             .status()
             .map_err(CargoExecutionError::FailedToRunCargo)?;
         if !status.success() {
@@ -70,19 +94,49 @@ pub fn build_package<P: AsRef<Path>>(path: P, trace: bool) -> Result<PathBuf, Ca
         bin.push("wasm32-unknown-unknown");
         bin.push("release");
         bin.push(lib_name.ok_or(CargoExecutionError::InvalidManifestFile)?);
-        Ok(bin.with_extension("wasm"))
+        let bin = bin.with_extension("wasm");
+
+        if reproducible {
+            let code = fs::read(&bin).map_err(CargoExecutionError::IOError)?;
+            let stripped =
+                strip_custom_sections(&code).ok_or(CargoExecutionError::InvalidWasmFile)?;
+            fs::write(&bin, stripped).map_err(CargoExecutionError::IOError)?;
+        }
+        Ok(bin)
     } else {
         Err(CargoExecutionError::NotCargoPackage)
     }
 }
 
+/// Compiler flags that keep machine-specific paths and parallel codegen out of the output.
+fn reproducible_rustflags(path: &Path) -> Result<String, CargoExecutionError> {
+    let package_dir = path.canonicalize().map_err(CargoExecutionError::IOError)?;
+    let cargo_home = env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+
+    let mut flags = vec![
+        "-Ccodegen-units=1".to_owned(),
+        "-Cstrip=symbols".to_owned(),
+        format!("--remap-path-prefix={}=/package", package_dir.display()),
+    ];
+    if let Some(cargo_home) = cargo_home {
+        flags.push(format!(
+            "--remap-path-prefix={}=/cargo",
+            cargo_home.display()
+        ));
+    }
+    // Flags are separated by 0x1f, so that paths may contain spaces
+    Ok(flags.join("\x1f"))
+}
+
 /// Runs tests within a package.
 pub fn test_package<P: AsRef<Path>, I, S>(path: P, args: I) -> Result<(), CargoExecutionError>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
 {
-    build_package(&path, false)?;
+    build_package(&path, false, false)?;
 
     let mut cargo = path.as_ref().to_owned();
     cargo.push("Cargo.toml");
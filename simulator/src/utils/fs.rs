@@ -0,0 +1,25 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Returns the latest modification time of a file, or of any file within a directory.
+///
+/// Hidden entries and `target` directories are skipped, so that build output does not count
+/// as a change.
+pub fn last_modified<P: AsRef<Path>>(path: P) -> io::Result<SystemTime> {
+    let metadata = fs::metadata(path.as_ref())?;
+    let mut latest = metadata.modified()?;
+    if metadata.is_dir() {
+        for entry in fs::read_dir(path.as_ref())? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            latest = latest.max(last_modified(entry.path())?);
+        }
+    }
+    Ok(latest)
+}
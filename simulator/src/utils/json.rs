@@ -0,0 +1,157 @@
+use sbor::any::{Fields, Value};
+use sbor::describe::{self, Type};
+use scrypto::buffer::*;
+use scrypto::types::*;
+use serde_json::{json, Map};
+
+/// Converts an SBOR value into JSON, using its type in the blueprint ABI, if known, to name
+/// struct fields and enum variants.
+///
+/// Integers wider than 32 bits and all custom values, e.g. `Decimal` and `Address`, are
+/// converted into strings, as JavaScript numbers can't represent them exactly.
+pub fn value_to_json(value: &Value, ty: Option<&Type>) -> serde_json::Value {
+    match value {
+        Value::Unit => serde_json::Value::Null,
+        Value::Bool(v) => json!(v),
+        Value::I8(v) => json!(v),
+        Value::I16(v) => json!(v),
+        Value::I32(v) => json!(v),
+        Value::I64(v) => json!(v.to_string()),
+        Value::I128(v) => json!(v.to_string()),
+        Value::U8(v) => json!(v),
+        Value::U16(v) => json!(v),
+        Value::U32(v) => json!(v),
+        Value::U64(v) => json!(v.to_string()),
+        Value::U128(v) => json!(v.to_string()),
+        Value::String(v) => json!(v),
+        Value::Struct(fields) => match ty {
+            Some(Type::Struct { fields: f, .. }) => fields_to_json(fields, Some(f)),
+            _ => fields_to_json(fields, None),
+        },
+        Value::Enum(index, fields) => {
+            let variant = match ty {
+                Some(Type::Enum { variants, .. }) => variants.get(*index as usize),
+                _ => None,
+            };
+            json!({
+                "variant": variant.map(|v| json!(v.name)).unwrap_or_else(|| json!(index)),
+                "fields": fields_to_json(fields, variant.map(|v| &v.fields)),
+            })
+        }
+        Value::Option(v) => match v.as_ref() {
+            Some(v) => value_to_json(
+                v,
+                match ty {
+                    Some(Type::Option { value }) => Some(value),
+                    _ => None,
+                },
+            ),
+            None => serde_json::Value::Null,
+        },
+        Value::Box(v) => value_to_json(
+            v,
+            match ty {
+                Some(Type::Box { value }) => Some(value),
+                _ => None,
+            },
+        ),
+        Value::Tuple(elements) => elements
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                value_to_json(
+                    e,
+                    match ty {
+                        Some(Type::Tuple { elements }) => elements.get(i),
+                        _ => None,
+                    },
+                )
+            })
+            .collect(),
+        Value::Result(v) => {
+            let (okay, error) = match ty {
+                Some(Type::Result { okay, error }) => (Some(okay.as_ref()), Some(error.as_ref())),
+                _ => (None, None),
+            };
+            match v.as_ref() {
+                Ok(v) => json!({ "Ok": value_to_json(v, okay) }),
+                Err(e) => json!({ "Err": value_to_json(e, error) }),
+            }
+        }
+        Value::Array(_, elements)
+        | Value::Vec(_, elements)
+        | Value::TreeSet(_, elements)
+        | Value::HashSet(_, elements) => {
+            let element = match ty {
+                Some(Type::Array { element, .. })
+                | Some(Type::Vec { element })
+                | Some(Type::TreeSet { element })
+                | Some(Type::HashSet { element }) => Some(element.as_ref()),
+                _ => None,
+            };
+            elements.iter().map(|e| value_to_json(e, element)).collect()
+        }
+        Value::TreeMap(_, _, entries) | Value::HashMap(_, _, entries) => {
+            let (key, value) = match ty {
+                Some(Type::TreeMap { key, value }) | Some(Type::HashMap { key, value }) => {
+                    (Some(key.as_ref()), Some(value.as_ref()))
+                }
+                _ => (None, None),
+            };
+            // Keys aren't necessarily strings, so entries are listed as pairs
+            entries
+                .chunks(2)
+                .map(|e| json!([value_to_json(&e[0], key), value_to_json(&e[1], value)]))
+                .collect()
+        }
+        Value::Custom(ty, data) => custom_to_json(*ty, data),
+    }
+}
+
+fn fields_to_json(fields: &Fields, ty: Option<&describe::Fields>) -> serde_json::Value {
+    match fields {
+        Fields::Named(values) => match ty {
+            Some(describe::Fields::Named { named }) if named.len() == values.len() => {
+                let mut map = Map::new();
+                for ((name, ty), value) in named.iter().zip(values) {
+                    map.insert(name.clone(), value_to_json(value, Some(ty)));
+                }
+                serde_json::Value::Object(map)
+            }
+            _ => values.iter().map(|v| value_to_json(v, None)).collect(),
+        },
+        Fields::Unnamed(values) => values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                value_to_json(
+                    v,
+                    match ty {
+                        Some(describe::Fields::Unnamed { unnamed }) => unnamed.get(i),
+                        _ => None,
+                    },
+                )
+            })
+            .collect(),
+        Fields::Unit => serde_json::Value::Null,
+    }
+}
+
+fn custom_to_json(ty: u8, data: &[u8]) -> serde_json::Value {
+    match ty {
+        SCRYPTO_TYPE_DECIMAL => json!(Decimal::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_BIG_DECIMAL => json!(BigDecimal::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_ADDRESS => json!(Address::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_H256 => json!(H256::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_MID => json!(Mid::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_BID => json!(BucketId::try_from(data).unwrap().id()),
+        SCRYPTO_TYPE_RID => json!(Rid::try_from(data).unwrap().0),
+        SCRYPTO_TYPE_VID => json!(VaultId::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_NON_FUNGIBLE_KEY => json!(NonFungibleKey::try_from(data).unwrap().to_string()),
+        SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS => {
+            json!(NonFungibleAddress::try_from(data).unwrap().to_string())
+        }
+        SCRYPTO_TYPE_URL => json!(Url::try_from(data).unwrap().to_string()),
+        _ => json!(hex::encode(data)),
+    }
+}
@@ -1,7 +1,13 @@
 mod cargo;
 mod display;
+mod fs;
 mod iter;
+mod json;
+mod wasm;
 
 pub use cargo::{build_package, fmt_package, test_package, CargoExecutionError};
 pub use display::list_item_prefix;
+pub use fs::last_modified;
 pub use iter::{IdentifyLast, Iter};
+pub use json::value_to_json;
+pub use wasm::strip_custom_sections;
@@ -0,0 +1,37 @@
+/// Removes all custom sections, e.g. `name` and `producers`, from a wasm module.
+///
+/// Custom sections carry debug names, paths and toolchain versions, none of which affect
+/// execution, so they are dropped to make builds comparable byte by byte.
+pub fn strip_custom_sections(code: &[u8]) -> Option<Vec<u8>> {
+    if code.len() < 8 || &code[0..4] != b"\0asm" {
+        return None;
+    }
+
+    let mut stripped = code[0..8].to_vec();
+    let mut offset = 8;
+    while offset < code.len() {
+        let start = offset;
+        let id = code[offset];
+        offset += 1;
+        let size = read_leb128_u32(code, &mut offset)? as usize;
+        let end = offset.checked_add(size).filter(|end| *end <= code.len())?;
+        if id != 0 {
+            stripped.extend_from_slice(&code[start..end]);
+        }
+        offset = end;
+    }
+    Some(stripped)
+}
+
+fn read_leb128_u32(code: &[u8], offset: &mut usize) -> Option<u32> {
+    let mut result = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *code.get(*offset)?;
+        *offset += 1;
+        result |= ((byte & 0x7f) as u32).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
@@ -13,10 +13,27 @@ temp=`$resim new-account | tee /dev/tty | awk '/Account address:|Public key:/ {p
 account=`echo $temp | cut -d " " -f1`
 account_key=`echo $temp | cut -d " " -f2`
 account2=`$resim new-account | tee /dev/tty | awk '/Account address:/ {print $NF}'`
+identity="correct horse battery staple"
+account3=`$resim import-account --identity "$identity" | tee /dev/tty | awk '/Account address:/ {print $NF}'`
+test "`$resim import-account --identity "$identity" | awk '/Account address:/ {print $NF}'`" = "$account3"
+account2_key=`$resim export-account $account2 | tee /dev/tty | awk '/Public key:/ {print $NF}'`
+test "`$resim import-account --public-key $account2_key | awk '/Account address:/ {print $NF}'`" = "$account2"
 mint_badge=`$resim new-badge-fixed 1 --name 'MintBadge' | tee /dev/tty | awk '/ResourceDef:/ {print $NF}'`
 resource_def=`$resim new-token-mutable $mint_badge | tee /dev/tty | awk '/ResourceDef:/ {print $NF}'`
 $resim mint 777 $resource_def $mint_badge --signers $account_key
+$resim new-simple-badge --name 'admin'
+admin_resource_def=`$resim new-token-mutable admin | tee /dev/tty | awk '/ResourceDef:/ {print $NF}'`
+$resim mint 1 $admin_resource_def admin --signers $account_key
 $resim transfer 111,$resource_def $account2 --signers $account_key
+$resim faucet $account2
+$resim call-method $account2 deposit_batch "Vec<Bucket>(Bucket(\"1,$resource_def\"))" --signers $account_key
+$resim set-current-epoch 10
+$resim config set-default-signers $account_key
+$resim config show
+echo "engine_call.CallMethod = 2" > target/cost_table.txt
+$resim config set-cost-table target/cost_table.txt
+$resim config show-cost-table
+$resim config set-cost-table
 
 # Test hello-world
 package=`$resim publish ../examples/core/hello-world | tee /dev/tty | awk '/Package:/ {print $NF}'`
@@ -31,6 +48,12 @@ $resim call-method $component free_token
 component=`$resim call-function $package Proxy2 instantiate_proxy | tee /dev/tty | awk '/Component:/ {print $NF}' | tail -n1`
 $resim call-method $component free_token
 
+# Test call-method with proofs
+admin_package=`$resim publish ../examples/core/flat-admin | tee /dev/tty | awk '/Package:/ {print $NF}'`
+admin_component=`$resim call-function $admin_package FlatAdmin instantiate_flat_admin "Admin" | tee /dev/tty | awk '/Component:/ {print $NF}'`
+admin_badge=`$resim show $admin_component | tee /dev/tty | grep -o 'Address("[0-9a-f]*")' | tail -n1 | cut -d '"' -f2`
+$resim call-method $admin_component create_additional_admin --proofs 1,$admin_badge
+
 # Export abi
 $resim export-abi $package Proxy1
 
@@ -39,6 +62,26 @@ $resim show $package
 $resim show $component
 $resim show $account
 $resim show $account2
+$resim balances $account
+
+# Check ledger consistency
+$resim fsck
+$resim doctor
 
 $resim new-badge-fixed 1 --name 'MintBadge' --manifest ./target/temp.rtm
-cat ./target/temp.rtm
\ No newline at end of file
+cat ./target/temp.rtm
+
+# Test genesis
+cat > target/genesis.json <<JSON
+{
+    "resources": [{ "address": "030101010101010101010101010101010101010101010101010101", "divisibility": 2, "metadata": { "symbol": "USD" } }],
+    "accounts": [{
+        "address": "020101010101010101010101010101010101010101010101010101",
+        "public_key": "$account_key",
+        "balances": { "030000000000000000000000000000000000000000000000000004": "1000", "030101010101010101010101010101010101010101010101010101": "50" }
+    }]
+}
+JSON
+$resim reset --genesis target/genesis.json
+$resim balances 020101010101010101010101010101010101010101010101010101
+$resim doctor
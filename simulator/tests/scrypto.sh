@@ -14,6 +14,7 @@ $scrypto new-package hello-world --path $test_pkg --local
 
 # Build
 $scrypto build --path $test_pkg
+$scrypto build --path $test_pkg --reproducible
 
 # Test
 $scrypto test --path $test_pkg
@@ -22,6 +22,12 @@ pub enum Instruction {
         new_bucket: Value,
     },
 
+    TakeUpToFromWorktop {
+        amount: Value,
+        resource_address: Value,
+        new_bucket: Value,
+    },
+
     ReturnToWorktop {
         bucket: Value,
     },
@@ -31,6 +37,21 @@ pub enum Instruction {
         resource_address: Value,
     },
 
+    AssertWorktopContainsNonFungibles {
+        keys: Value,
+        resource_address: Value,
+    },
+
+    BurnAllFromWorktop {
+        resource_address: Value,
+    },
+
+    AssertAccountBalanceAtLeast {
+        account: Value,
+        amount: Value,
+        resource_address: Value,
+    },
+
     CreateBucketRef {
         bucket: Value,
         new_bucket_ref: Value,
@@ -105,6 +126,8 @@ pub enum Type {
     LazyMap,
     Vault,
     NonFungibleKey,
+    NonFungibleAddress,
+    Url,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -145,6 +168,8 @@ pub enum Value {
     LazyMap(Box<Value>),
     Vault(Box<Value>),
     NonFungibleKey(Box<Value>),
+    NonFungibleAddress(Box<Value>),
+    Url(Box<Value>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -193,6 +218,46 @@ impl Value {
             Value::LazyMap(_) => Type::LazyMap,
             Value::Vault(_) => Type::Vault,
             Value::NonFungibleKey(_) => Type::NonFungibleKey,
+            Value::NonFungibleAddress(_) => Type::NonFungibleAddress,
+            Value::Url(_) => Type::Url,
+        }
+    }
+
+    /// Returns the `Bucket` and `BucketRef` values nested in this value, in order of appearance.
+    pub fn buckets(&self) -> Vec<&Value> {
+        let mut buckets = Vec::new();
+        self.collect_buckets(&mut buckets);
+        buckets
+    }
+
+    fn collect_buckets<'a>(&'a self, buckets: &mut Vec<&'a Value>) {
+        match self {
+            Value::Bucket(_) | Value::BucketRef(_) => buckets.push(self),
+            Value::Struct(fields) | Value::Enum(_, fields) => match fields {
+                Fields::Named(values) | Fields::Unnamed(values) => {
+                    values.iter().for_each(|v| v.collect_buckets(buckets))
+                }
+                Fields::Unit => {}
+            },
+            Value::Option(value) => {
+                if let Some(v) = &**value {
+                    v.collect_buckets(buckets);
+                }
+            }
+            Value::Box(value) => value.collect_buckets(buckets),
+            Value::Result(value) => match &**value {
+                Ok(v) | Err(v) => v.collect_buckets(buckets),
+            },
+            Value::Array(_, values)
+            | Value::Tuple(values)
+            | Value::Vec(_, values)
+            | Value::TreeSet(_, values)
+            | Value::TreeMap(_, _, values)
+            | Value::HashSet(_, values)
+            | Value::HashMap(_, _, values) => {
+                values.iter().for_each(|v| v.collect_buckets(buckets))
+            }
+            _ => {}
         }
     }
 }
@@ -12,7 +12,7 @@ pub enum DecompileError {
 pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
     let mut buf = String::new();
     let mut id_validator = IdValidator::new();
-    let mut buckets = HashMap::<Bid, String>::new();
+    let mut buckets = HashMap::<BucketId, String>::new();
     let mut bucket_refs = HashMap::<Rid, String>::new();
     for inst in &tx.instructions {
         match inst.clone() {
@@ -59,6 +59,20 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     resource_address, name
                 ));
             }
+            Instruction::TakeUpToFromWorktop {
+                amount,
+                resource_address,
+            } => {
+                let bid = id_validator
+                    .new_bucket()
+                    .map_err(DecompileError::IdValidatorError)?;
+                let name = format!("bucket{}", buckets.len() + 1);
+                buckets.insert(bid, name.clone());
+                buf.push_str(&format!(
+                    "TAKE_UP_TO_FROM_WORKTOP Decimal(\"{}\") Address(\"{}\") Bucket(\"{}\");\n",
+                    amount, resource_address, name
+                ));
+            }
             Instruction::ReturnToWorktop { bid } => {
                 id_validator
                     .drop_bucket(bid)
@@ -68,7 +82,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     buckets
                         .get(&bid)
                         .map(|name| format!("\"{}\"", name))
-                        .unwrap_or(format!("{}u32", bid.0))
+                        .unwrap_or(format!("{}u32", bid.id()))
                 ));
             }
             Instruction::AssertWorktopContains {
@@ -80,6 +94,35 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     amount, resource_address
                 ));
             }
+            Instruction::AssertWorktopContainsNonFungibles {
+                keys,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "ASSERT_WORKTOP_CONTAINS_NON_FUNGIBLES TreeSet<NonFungibleKey>({}) Address(\"{}\");\n",
+                    keys.iter()
+                    .map(|k| format!("NonFungibleKey(\"{}\")", k))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                    resource_address
+                ));
+            }
+            Instruction::BurnAllFromWorktop { resource_address } => {
+                buf.push_str(&format!(
+                    "BURN_ALL_FROM_WORKTOP Address(\"{}\");\n",
+                    resource_address
+                ));
+            }
+            Instruction::AssertAccountBalanceAtLeast {
+                account,
+                amount,
+                resource_address,
+            } => {
+                buf.push_str(&format!(
+                    "ASSERT_ACCOUNT_BALANCE_AT_LEAST Address(\"{}\") Decimal(\"{}\") Address(\"{}\");\n",
+                    account, amount, resource_address
+                ));
+            }
             Instruction::CreateBucketRef { bid } => {
                 let rid = id_validator
                     .new_bucket_ref(bid)
@@ -91,7 +134,7 @@ pub fn decompile(tx: &Transaction) -> Result<String, DecompileError> {
                     buckets
                         .get(&bid)
                         .map(|name| format!("\"{}\"", name))
-                        .unwrap_or(format!("{}u32", bid.0)),
+                        .unwrap_or(format!("{}u32", bid.id())),
                     name
                 ));
             }
@@ -27,6 +27,8 @@ pub enum GeneratorError {
     InvalidLazyMapId(String),
     InvalidVaultId(String),
     InvalidNonFungibleKey(String),
+    InvalidNonFungibleAddress(String),
+    InvalidUrl(String),
     OddNumberOfElements(usize),
     NameResolverError(NameResolverError),
     IdValidatorError(IdValidatorError),
@@ -40,7 +42,7 @@ pub enum NameResolverError {
 }
 
 pub struct NameResolver {
-    named_buckets: HashMap<String, Bid>,
+    named_buckets: HashMap<String, BucketId>,
     named_bucket_refs: HashMap<String, Rid>,
 }
 
@@ -52,7 +54,7 @@ impl NameResolver {
         }
     }
 
-    pub fn insert_bucket(&mut self, name: String, bid: Bid) -> Result<(), NameResolverError> {
+    pub fn insert_bucket(&mut self, name: String, bid: BucketId) -> Result<(), NameResolverError> {
         if self.named_buckets.contains_key(&name) || self.named_bucket_refs.contains_key(&name) {
             Err(NameResolverError::NamedAlreadyDefined(name))
         } else {
@@ -70,7 +72,7 @@ impl NameResolver {
         }
     }
 
-    pub fn resolve_bucket(&mut self, name: &str) -> Result<Bid, NameResolverError> {
+    pub fn resolve_bucket(&mut self, name: &str) -> Result<BucketId, NameResolverError> {
         match self.named_buckets.get(name).cloned() {
             Some(bid) => Ok(bid),
             None => Err(NameResolverError::UndefinedBucket(name.into())),
@@ -98,7 +100,13 @@ pub fn generate_transaction(tx: &ast::Transaction) -> Result<Transaction, Genera
         )?);
     }
 
-    Ok(Transaction { instructions })
+    Ok(Transaction {
+        instructions,
+        message: None,
+        nonce: None,
+        expiration_epoch: None,
+        kind: TransactionKind::User,
+    })
 }
 
 pub fn generate_instruction(
@@ -150,6 +158,21 @@ pub fn generate_instruction(
                 resource_address: generate_address(resource_address)?,
             }
         }
+        ast::Instruction::TakeUpToFromWorktop {
+            amount,
+            resource_address,
+            new_bucket,
+        } => {
+            let bid = id_validator
+                .new_bucket()
+                .map_err(GeneratorError::IdValidatorError)?;
+            declare_bucket(new_bucket, resolver, bid)?;
+
+            Instruction::TakeUpToFromWorktop {
+                amount: generate_decimal(amount)?,
+                resource_address: generate_address(resource_address)?,
+            }
+        }
         ast::Instruction::ReturnToWorktop { bucket } => {
             let bid = generate_bucket(bucket, resolver)?;
             id_validator
@@ -164,6 +187,27 @@ pub fn generate_instruction(
             amount: generate_decimal(amount)?,
             resource_address: generate_address(resource_address)?,
         },
+        ast::Instruction::AssertWorktopContainsNonFungibles {
+            keys,
+            resource_address,
+        } => Instruction::AssertWorktopContainsNonFungibles {
+            keys: generate_non_fungible_keys(keys)?,
+            resource_address: generate_address(resource_address)?,
+        },
+        ast::Instruction::BurnAllFromWorktop { resource_address } => {
+            Instruction::BurnAllFromWorktop {
+                resource_address: generate_address(resource_address)?,
+            }
+        }
+        ast::Instruction::AssertAccountBalanceAtLeast {
+            account,
+            amount,
+            resource_address,
+        } => Instruction::AssertAccountBalanceAtLeast {
+            account: generate_address(account)?,
+            amount: generate_decimal(amount)?,
+            resource_address: generate_address(resource_address)?,
+        },
         ast::Instruction::CreateBucketRef {
             bucket,
             new_bucket_ref,
@@ -264,15 +308,23 @@ fn generate_args(
 ) -> Result<Vec<Vec<u8>>, GeneratorError> {
     let mut result = Vec::new();
     for v in values {
-        let value = generate_value(v, None, resolver)?;
-
-        let mut enc = Encoder::with_type(Vec::new());
-        encode_any(None, &value, &mut enc);
-        result.push(enc.into());
+        result.push(generate_arg(v, resolver)?);
     }
     Ok(result)
 }
 
+/// Generates the SBOR encoding of a call argument.
+pub fn generate_arg(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+) -> Result<Vec<u8>, GeneratorError> {
+    let value = generate_value(value, None, resolver)?;
+
+    let mut enc = Encoder::with_type(Vec::new());
+    encode_any(None, &value, &mut enc);
+    Ok(enc.into())
+}
+
 fn generate_string(value: &ast::Value) -> Result<String, GeneratorError> {
     match value {
         ast::Value::String(s) => Ok(s.into()),
@@ -331,7 +383,7 @@ fn generate_hash(value: &ast::Value) -> Result<H256, GeneratorError> {
 fn declare_bucket(
     value: &ast::Value,
     resolver: &mut NameResolver,
-    bid: Bid,
+    bid: BucketId,
 ) -> Result<(), GeneratorError> {
     match value {
         ast::Value::Bucket(inner) => match &**inner {
@@ -344,10 +396,13 @@ fn declare_bucket(
     }
 }
 
-fn generate_bucket(value: &ast::Value, resolver: &mut NameResolver) -> Result<Bid, GeneratorError> {
+fn generate_bucket(
+    value: &ast::Value,
+    resolver: &mut NameResolver,
+) -> Result<BucketId, GeneratorError> {
     match value {
         ast::Value::Bucket(inner) => match &**inner {
-            ast::Value::U32(n) => Ok(Bid(*n)),
+            ast::Value::U32(n) => Ok(BucketId::new(*n)),
             ast::Value::String(s) => resolver
                 .resolve_bucket(&s)
                 .map_err(GeneratorError::NameResolverError),
@@ -401,11 +456,11 @@ fn generate_lazy_map(value: &ast::Value) -> Result<Mid, GeneratorError> {
     }
 }
 
-fn generate_vault(value: &ast::Value) -> Result<Vid, GeneratorError> {
+fn generate_vault(value: &ast::Value) -> Result<VaultId, GeneratorError> {
     match value {
         ast::Value::Vault(inner) => match &**inner {
             ast::Value::String(s) => {
-                Vid::from_str(s).map_err(|_| GeneratorError::InvalidVaultId(s.into()))
+                VaultId::from_str(s).map_err(|_| GeneratorError::InvalidVaultId(s.into()))
             }
             v @ _ => invalid_type!(v, ast::Type::String),
         },
@@ -424,6 +479,29 @@ fn generate_non_fungible_key(value: &ast::Value) -> Result<NonFungibleKey, Gener
     }
 }
 
+fn generate_non_fungible_address(value: &ast::Value) -> Result<NonFungibleAddress, GeneratorError> {
+    match value {
+        ast::Value::NonFungibleAddress(inner) => match &**inner {
+            ast::Value::String(s) => NonFungibleAddress::from_str(s)
+                .map_err(|_| GeneratorError::InvalidNonFungibleAddress(s.into())),
+            v @ _ => invalid_type!(v, ast::Type::String),
+        },
+        v @ _ => invalid_type!(v, ast::Type::NonFungibleAddress),
+    }
+}
+
+fn generate_url(value: &ast::Value) -> Result<Url, GeneratorError> {
+    match value {
+        ast::Value::Url(inner) => match &**inner {
+            ast::Value::String(s) => {
+                Url::from_str(s).map_err(|_| GeneratorError::InvalidUrl(s.into()))
+            }
+            v @ _ => invalid_type!(v, ast::Type::String),
+        },
+        v @ _ => invalid_type!(v, ast::Type::Url),
+    }
+}
+
 fn generate_non_fungible_keys(
     value: &ast::Value,
 ) -> Result<BTreeSet<NonFungibleKey>, GeneratorError> {
@@ -546,6 +624,11 @@ fn generate_value(
         }
         ast::Value::NonFungibleKey(_) => generate_non_fungible_key(value)
             .map(|v| Value::Custom(SCRYPTO_TYPE_NON_FUNGIBLE_KEY, v.to_vec())),
+        ast::Value::NonFungibleAddress(_) => generate_non_fungible_address(value)
+            .map(|v| Value::Custom(SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS, v.to_vec())),
+        ast::Value::Url(_) => {
+            generate_url(value).map(|v| Value::Custom(SCRYPTO_TYPE_URL, v.to_vec()))
+        }
     }
 }
 
@@ -633,6 +716,8 @@ fn generate_type(ty: &ast::Type) -> u8 {
         ast::Type::LazyMap => SCRYPTO_TYPE_MID,
         ast::Type::Vault => SCRYPTO_TYPE_VID,
         ast::Type::NonFungibleKey => SCRYPTO_TYPE_NON_FUNGIBLE_KEY,
+        ast::Type::NonFungibleAddress => SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS,
+        ast::Type::Url => SCRYPTO_TYPE_URL,
     }
 }
 
@@ -693,7 +778,7 @@ mod tests {
         generate_value_ok!(
             r#"Struct({Bucket(1u32), BucketRef(2u32), "bar"})"#,
             Value::Struct(Fields::Named(vec![
-                Value::Custom(SCRYPTO_TYPE_BID, Bid(1).to_vec()),
+                Value::Custom(SCRYPTO_TYPE_BID, BucketId::new(1).to_vec()),
                 Value::Custom(SCRYPTO_TYPE_RID, Rid(2).to_vec()),
                 Value::String("bar".into())
             ]))
@@ -719,7 +804,7 @@ mod tests {
                 ),
                 Value::Custom(
                     SCRYPTO_TYPE_VID,
-                    Vid::from_str(
+                    VaultId::from_str(
                         "aa37f5a71083a9aa044fb936678bfd74f848e930d2de482a49a73540ea72aa5c00000001"
                     )
                     .unwrap()
@@ -782,6 +867,17 @@ mod tests {
                 ]
             )
         );
+        generate_value_ok!(
+            r#"NonFungibleAddress("030000000000000000000000000000000000000000000000000005ff")"#,
+            Value::Custom(
+                SCRYPTO_TYPE_NON_FUNGIBLE_ADDRESS,
+                NonFungibleAddress::new(ECDSA_TOKEN, NonFungibleKey::new(vec![0xff])).to_vec()
+            )
+        );
+        generate_value_ok!(
+            r#"Url("https://www.radixdlt.com")"#,
+            Value::Custom(SCRYPTO_TYPE_URL, b"https://www.radixdlt.com".to_vec())
+        );
     }
 
     #[test]
@@ -801,6 +897,10 @@ mod tests {
             r#"Decimal("invalid_decimal")"#,
             GeneratorError::InvalidDecimal("invalid_decimal".into())
         );
+        generate_value_error!(
+            r#"Url("ftp://radixdlt.com")"#,
+            GeneratorError::InvalidUrl("ftp://radixdlt.com".into())
+        );
         generate_value_error!(
             r#"HashMap<String, String>("abc")"#,
             GeneratorError::OddNumberOfElements(1)
@@ -838,6 +938,52 @@ mod tests {
                 .unwrap(),
             }
         );
+        generate_instruction_ok!(
+            r#"TAKE_UP_TO_FROM_WORKTOP  Decimal("1.0")  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d")  Bucket("xrd_bucket");"#,
+            Instruction::TakeUpToFromWorktop {
+                amount: Decimal::from(1),
+                resource_address: Address::from_str(
+                    "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d"
+                )
+                .unwrap(),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_WORKTOP_CONTAINS_NON_FUNGIBLES  TreeSet<NonFungibleKey>(NonFungibleKey("11"), NonFungibleKey("22"))  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::AssertWorktopContainsNonFungibles {
+                keys: BTreeSet::from([
+                    NonFungibleKey::from_str("11").unwrap(),
+                    NonFungibleKey::from_str("22").unwrap(),
+                ]),
+                resource_address: Address::from_str(
+                    "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d"
+                )
+                .unwrap(),
+            }
+        );
+        generate_instruction_ok!(
+            r#"BURN_ALL_FROM_WORKTOP  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::BurnAllFromWorktop {
+                resource_address: Address::from_str(
+                    "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d"
+                )
+                .unwrap(),
+            }
+        );
+        generate_instruction_ok!(
+            r#"ASSERT_ACCOUNT_BALANCE_AT_LEAST  Address("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de")  Decimal("1.0")  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::AssertAccountBalanceAtLeast {
+                account: Address::from_str(
+                    "02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de"
+                )
+                .unwrap(),
+                amount: Decimal::from(1),
+                resource_address: Address::from_str(
+                    "03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d"
+                )
+                .unwrap(),
+            }
+        );
         generate_instruction_ok!(
             r#"CALL_FUNCTION  Address("01d1f50010e4102d88aacc347711491f852c515134a9ecf67ba17c")  "Airdrop"  "new"  500u32  HashMap<String, U8>("key", 1u8);"#,
             Instruction::CallFunction {
@@ -914,7 +1060,7 @@ mod tests {
                         )
                         .unwrap(),
                         method: "buy_gumball".into(),
-                        args: vec![scrypto_encode(&Bid(512)),]
+                        args: vec![scrypto_encode(&BucketId::new(512)),]
                     },
                     Instruction::AssertWorktopContains {
                         amount: Decimal::from(3),
@@ -936,11 +1082,15 @@ mod tests {
                         )
                         .unwrap(),
                     },
-                    Instruction::CreateBucketRef { bid: Bid(513) },
+                    Instruction::CreateBucketRef {
+                        bid: BucketId::new(513)
+                    },
                     Instruction::CloneBucketRef { rid: Rid(514) },
                     Instruction::DropBucketRef { rid: Rid(515) },
                     Instruction::DropBucketRef { rid: Rid(514) },
-                    Instruction::ReturnToWorktop { bid: Bid(513) },
+                    Instruction::ReturnToWorktop {
+                        bid: BucketId::new(513)
+                    },
                     Instruction::TakeNonFungiblesFromWorktop {
                         keys: BTreeSet::from([
                             NonFungibleKey::from_str("11").unwrap(),
@@ -958,7 +1108,11 @@ mod tests {
                         .unwrap(),
                         method: "deposit_batch".into(),
                     },
-                ]
+                ],
+                message: None,
+                nonce: None,
+                expiration_epoch: None,
+                kind: TransactionKind::User,
             }
         );
     }
@@ -59,6 +59,8 @@ pub enum TokenKind {
     LazyMap,
     Vault,
     NonFungibleKey,
+    NonFungibleAddress,
+    Url,
 
     /* Sub-types */
     Some,
@@ -80,8 +82,12 @@ pub enum TokenKind {
     TakeFromWorktop,
     TakeAllFromWorktop,
     TakeNonFungiblesFromWorktop,
+    TakeUpToFromWorktop,
     ReturnToWorktop,
     AssertWorktopContains,
+    AssertWorktopContainsNonFungibles,
+    BurnAllFromWorktop,
+    AssertAccountBalanceAtLeast,
     CreateBucketRef,
     CloneBucketRef,
     DropBucketRef,
@@ -379,6 +385,8 @@ impl Lexer {
             "LazyMap" => Ok(TokenKind::LazyMap),
             "Vault" => Ok(TokenKind::Vault),
             "NonFungibleKey" => Ok(TokenKind::NonFungibleKey),
+            "NonFungibleAddress" => Ok(TokenKind::NonFungibleAddress),
+            "Url" => Ok(TokenKind::Url),
 
             "Some" => Ok(TokenKind::Some),
             "None" => Ok(TokenKind::None),
@@ -388,8 +396,14 @@ impl Lexer {
             "TAKE_FROM_WORKTOP" => Ok(TokenKind::TakeFromWorktop),
             "TAKE_ALL_FROM_WORKTOP" => Ok(TokenKind::TakeAllFromWorktop),
             "TAKE_NON_FUNGIBLES_FROM_WORKTOP" => Ok(TokenKind::TakeNonFungiblesFromWorktop),
+            "TAKE_UP_TO_FROM_WORKTOP" => Ok(TokenKind::TakeUpToFromWorktop),
             "RETURN_TO_WORKTOP" => Ok(TokenKind::ReturnToWorktop),
             "ASSERT_WORKTOP_CONTAINS" => Ok(TokenKind::AssertWorktopContains),
+            "ASSERT_WORKTOP_CONTAINS_NON_FUNGIBLES" => {
+                Ok(TokenKind::AssertWorktopContainsNonFungibles)
+            }
+            "BURN_ALL_FROM_WORKTOP" => Ok(TokenKind::BurnAllFromWorktop),
+            "ASSERT_ACCOUNT_BALANCE_AT_LEAST" => Ok(TokenKind::AssertAccountBalanceAtLeast),
             "CREATE_BUCKET_REF" => Ok(TokenKind::CreateBucketRef),
             "CLONE_BUCKET_REF" => Ok(TokenKind::CloneBucketRef),
             "DROP_BUCKET_REF" => Ok(TokenKind::DropBucketRef),
@@ -22,3 +22,84 @@ pub fn compile(s: &str) -> Result<Transaction, CompileError> {
         .map_err(CompileError::ParserError)?;
     generator::generate_transaction(&ast).map_err(CompileError::GeneratorError)
 }
+
+/// Parses a single value, e.g. a call argument given on the command line.
+pub fn parse_value(s: &str) -> Result<ast::Value, CompileError> {
+    let tokens = lexer::tokenize(s).map_err(CompileError::LexerError)?;
+    let mut parser = parser::Parser::new(tokens);
+    let value = parser.parse_value().map_err(CompileError::ParserError)?;
+    if !parser.is_eof() {
+        let token = parser.peek().map_err(CompileError::ParserError)?;
+        return Err(CompileError::ParserError(
+            parser::ParserError::UnexpectedToken(token),
+        ));
+    }
+    Ok(value)
+}
+
+/// Compiles a single value into its SBOR encoding, resolving named buckets and bucket refs
+/// with the given resolver.
+pub fn compile_value(
+    value: &ast::Value,
+    resolver: &mut generator::NameResolver,
+) -> Result<Vec<u8>, CompileError> {
+    generator::generate_arg(value, resolver).map_err(CompileError::GeneratorError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use generator::NameResolver;
+    use sbor::any::{encode_any, Value};
+    use sbor::Encoder;
+    use scrypto::buffer::*;
+    use scrypto::types::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_compile_value_with_nested_buckets() {
+        let value = parse_value(
+            r#"Struct((Vec<Bucket>(Bucket("xrd")), Some(BucketRef("badge")), Decimal("1.5")))"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value.buckets(),
+            vec![
+                &ast::Value::Bucket(ast::Value::String("xrd".into()).into()),
+                &ast::Value::BucketRef(ast::Value::String("badge".into()).into())
+            ]
+        );
+
+        let mut resolver = NameResolver::new();
+        resolver
+            .insert_bucket("xrd".into(), BucketId::new(512))
+            .unwrap();
+        resolver
+            .insert_bucket_ref("badge".into(), Rid(513))
+            .unwrap();
+        let expected = Value::Struct(sbor::any::Fields::Unnamed(vec![
+            Value::Vec(
+                SCRYPTO_TYPE_BID,
+                vec![Value::Custom(SCRYPTO_TYPE_BID, BucketId::new(512).to_vec())],
+            ),
+            Value::Option(Some(Value::Custom(SCRYPTO_TYPE_RID, Rid(513).to_vec())).into()),
+            Value::Custom(
+                SCRYPTO_TYPE_DECIMAL,
+                Decimal::from_str("1.5").unwrap().to_vec(),
+            ),
+        ]));
+        let mut enc = Encoder::with_type(Vec::new());
+        encode_any(None, &expected, &mut enc);
+        assert_eq!(compile_value(&value, &mut resolver), Ok(enc.into()));
+    }
+
+    #[test]
+    fn test_parse_value_with_trailing_tokens() {
+        assert!(matches!(
+            parse_value(r#"1u32 2u32"#),
+            Err(CompileError::ParserError(
+                parser::ParserError::UnexpectedToken(_)
+            ))
+        ));
+    }
+}
@@ -81,6 +81,11 @@ impl Parser {
                 resource_address: self.parse_value()?,
                 new_bucket: self.parse_value()?,
             },
+            TokenKind::TakeUpToFromWorktop => Instruction::TakeUpToFromWorktop {
+                amount: self.parse_value()?,
+                resource_address: self.parse_value()?,
+                new_bucket: self.parse_value()?,
+            },
             TokenKind::ReturnToWorktop => Instruction::ReturnToWorktop {
                 bucket: self.parse_value()?,
             },
@@ -88,6 +93,20 @@ impl Parser {
                 amount: self.parse_value()?,
                 resource_address: self.parse_value()?,
             },
+            TokenKind::AssertWorktopContainsNonFungibles => {
+                Instruction::AssertWorktopContainsNonFungibles {
+                    keys: self.parse_value()?,
+                    resource_address: self.parse_value()?,
+                }
+            }
+            TokenKind::BurnAllFromWorktop => Instruction::BurnAllFromWorktop {
+                resource_address: self.parse_value()?,
+            },
+            TokenKind::AssertAccountBalanceAtLeast => Instruction::AssertAccountBalanceAtLeast {
+                account: self.parse_value()?,
+                amount: self.parse_value()?,
+                resource_address: self.parse_value()?,
+            },
             TokenKind::CreateBucketRef => Instruction::CreateBucketRef {
                 bucket: self.parse_value()?,
                 new_bucket_ref: self.parse_value()?,
@@ -174,7 +193,9 @@ impl Parser {
             | TokenKind::BucketRef
             | TokenKind::LazyMap
             | TokenKind::Vault
-            | TokenKind::NonFungibleKey => self.parse_scrypto_types(),
+            | TokenKind::NonFungibleKey
+            | TokenKind::NonFungibleAddress
+            | TokenKind::Url => self.parse_scrypto_types(),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -338,6 +359,10 @@ impl Parser {
             TokenKind::LazyMap => Ok(Value::LazyMap(self.parse_values_one()?.into())),
             TokenKind::Vault => Ok(Value::Vault(self.parse_values_one()?.into())),
             TokenKind::NonFungibleKey => Ok(Value::NonFungibleKey(self.parse_values_one()?.into())),
+            TokenKind::NonFungibleAddress => {
+                Ok(Value::NonFungibleAddress(self.parse_values_one()?.into()))
+            }
+            TokenKind::Url => Ok(Value::Url(self.parse_values_one()?.into())),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -431,6 +456,8 @@ impl Parser {
             TokenKind::LazyMap => Ok(Type::LazyMap),
             TokenKind::Vault => Ok(Type::Vault),
             TokenKind::NonFungibleKey => Ok(Type::NonFungibleKey),
+            TokenKind::NonFungibleAddress => Ok(Type::NonFungibleAddress),
+            TokenKind::Url => Ok(Type::Url),
             _ => Err(ParserError::UnexpectedToken(token)),
         }
     }
@@ -681,6 +708,53 @@ mod tests {
                 ),
             }
         );
+        parse_instruction_ok!(
+            r#"TAKE_UP_TO_FROM_WORKTOP  Decimal("1.0")  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d")  Bucket("xrd_bucket");"#,
+            Instruction::TakeUpToFromWorktop {
+                amount: Value::Decimal(Value::String("1.0".into()).into()),
+                resource_address: Value::Address(
+                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                        .into()
+                ),
+                new_bucket: Value::Bucket(Value::String("xrd_bucket".into()).into()),
+            }
+        );
+        parse_instruction_ok!(
+            r#"ASSERT_WORKTOP_CONTAINS_NON_FUNGIBLES  TreeSet<NonFungibleKey>(NonFungibleKey("11"))  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::AssertWorktopContainsNonFungibles {
+                keys: Value::TreeSet(
+                    Type::NonFungibleKey,
+                    vec![Value::NonFungibleKey(Value::String("11".into()).into())]
+                ),
+                resource_address: Value::Address(
+                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                        .into()
+                ),
+            }
+        );
+        parse_instruction_ok!(
+            r#"BURN_ALL_FROM_WORKTOP  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::BurnAllFromWorktop {
+                resource_address: Value::Address(
+                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                        .into()
+                ),
+            }
+        );
+        parse_instruction_ok!(
+            r#"ASSERT_ACCOUNT_BALANCE_AT_LEAST  Address("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de")  Decimal("1.0")  Address("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d");"#,
+            Instruction::AssertAccountBalanceAtLeast {
+                account: Value::Address(
+                    Value::String("02d43f479e9b2beb9df98bc3888344fc25eda181e8f710ce1bf1de".into())
+                        .into()
+                ),
+                amount: Value::Decimal(Value::String("1.0".into()).into()),
+                resource_address: Value::Address(
+                    Value::String("03cbdf875789d08cc80c97e2915b920824a69ea8d809e50b9fe09d".into())
+                        .into()
+                ),
+            }
+        );
         parse_instruction_ok!(
             r#"CREATE_BUCKET_REF  Bucket("xrd_bucket")  BucketRef("admin_auth");"#,
             Instruction::CreateBucketRef {